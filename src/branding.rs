@@ -0,0 +1,478 @@
+// Copyright (C) 2017 Christopher R. Field.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of simple banner and dialog images for the WixUI dialogs.
+//!
+//! The images are uncompressed, 24-bit [bitmap] (BMP) files with a gradient
+//! and the product name drawn with an embedded 5 x 8 pixel font. Only the
+//! printable ASCII characters are included in the font, so a product name with
+//! any other character, such as an accented letter, is not drawn and the
+//! images only have the gradient. See the [`can_draw`] function.
+//!
+//! [bitmap]: https://learn.microsoft.com/en-us/windows/win32/gdi/bitmap-storage
+
+/// The width of the banner image in pixels.
+pub const BANNER_WIDTH: usize = 493;
+
+/// The height of the banner image in pixels.
+pub const BANNER_HEIGHT: usize = 58;
+
+/// The width of the dialog image in pixels.
+pub const DIALOG_WIDTH: usize = 493;
+
+/// The height of the dialog image in pixels.
+pub const DIALOG_HEIGHT: usize = 312;
+
+/// The width of the left part of the dialog image that is not covered by the
+/// text of the dialogs.
+const DIALOG_PANEL_WIDTH: usize = 164;
+
+/// The maximum width of the product name in the banner image.
+///
+/// The titles and descriptions of the dialogs are drawn over the left part of
+/// the banner image, so the product name is kept to the right part.
+const BANNER_TEXT_WIDTH: usize = 160;
+
+/// The space around the product name in pixels.
+const MARGIN: usize = 12;
+
+/// The width of a glyph of the font, excluding the space between glyphs.
+const GLYPH_WIDTH: usize = 5;
+
+/// The width of a character, including the space between glyphs.
+const CHAR_WIDTH: usize = GLYPH_WIDTH + 1;
+
+/// The height of a line of text, including the space between lines.
+const LINE_HEIGHT: usize = 10;
+
+/// The size of the file header and information header of a bitmap file.
+const BMP_HEADER_SIZE: usize = 54;
+
+const WHITE: Rgb = Rgb(0xFF, 0xFF, 0xFF);
+const ACCENT_DARK: Rgb = Rgb(0x1F, 0x3A, 0x5F);
+const ACCENT_LIGHT: Rgb = Rgb(0x3C, 0x6E, 0xA8);
+const ACCENT_TINT: Rgb = Rgb(0xDC, 0xE6, 0xF2);
+
+/// The columns of the glyphs for the printable ASCII characters, from the
+/// space (0x20) to the tilde (0x7E). The least significant bit of a column is
+/// the top row of the glyph.
+const FONT: [[u8; GLYPH_WIDTH]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x5F, 0x00, 0x00],
+    [0x00, 0x07, 0x00, 0x07, 0x00],
+    [0x14, 0x7F, 0x14, 0x7F, 0x14],
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12],
+    [0x23, 0x13, 0x08, 0x64, 0x62],
+    [0x36, 0x49, 0x56, 0x20, 0x50],
+    [0x00, 0x08, 0x07, 0x03, 0x00],
+    [0x00, 0x1C, 0x22, 0x41, 0x00],
+    [0x00, 0x41, 0x22, 0x1C, 0x00],
+    [0x2A, 0x1C, 0x7F, 0x1C, 0x2A],
+    [0x08, 0x08, 0x3E, 0x08, 0x08],
+    [0x00, 0x80, 0x70, 0x30, 0x00],
+    [0x08, 0x08, 0x08, 0x08, 0x08],
+    [0x00, 0x00, 0x60, 0x60, 0x00],
+    [0x20, 0x10, 0x08, 0x04, 0x02],
+    [0x3E, 0x51, 0x49, 0x45, 0x3E],
+    [0x00, 0x42, 0x7F, 0x40, 0x00],
+    [0x72, 0x49, 0x49, 0x49, 0x46],
+    [0x21, 0x41, 0x49, 0x4D, 0x33],
+    [0x18, 0x14, 0x12, 0x7F, 0x10],
+    [0x27, 0x45, 0x45, 0x45, 0x39],
+    [0x3C, 0x4A, 0x49, 0x49, 0x31],
+    [0x41, 0x21, 0x11, 0x09, 0x07],
+    [0x36, 0x49, 0x49, 0x49, 0x36],
+    [0x46, 0x49, 0x49, 0x29, 0x1E],
+    [0x00, 0x00, 0x14, 0x00, 0x00],
+    [0x00, 0x40, 0x34, 0x00, 0x00],
+    [0x00, 0x08, 0x14, 0x22, 0x41],
+    [0x14, 0x14, 0x14, 0x14, 0x14],
+    [0x00, 0x41, 0x22, 0x14, 0x08],
+    [0x02, 0x01, 0x59, 0x09, 0x06],
+    [0x3E, 0x41, 0x5D, 0x59, 0x4E],
+    [0x7C, 0x12, 0x11, 0x12, 0x7C],
+    [0x7F, 0x49, 0x49, 0x49, 0x36],
+    [0x3E, 0x41, 0x41, 0x41, 0x22],
+    [0x7F, 0x41, 0x41, 0x41, 0x3E],
+    [0x7F, 0x49, 0x49, 0x49, 0x41],
+    [0x7F, 0x09, 0x09, 0x09, 0x01],
+    [0x3E, 0x41, 0x41, 0x51, 0x73],
+    [0x7F, 0x08, 0x08, 0x08, 0x7F],
+    [0x00, 0x41, 0x7F, 0x41, 0x00],
+    [0x20, 0x40, 0x41, 0x3F, 0x01],
+    [0x7F, 0x08, 0x14, 0x22, 0x41],
+    [0x7F, 0x40, 0x40, 0x40, 0x40],
+    [0x7F, 0x02, 0x1C, 0x02, 0x7F],
+    [0x7F, 0x04, 0x08, 0x10, 0x7F],
+    [0x3E, 0x41, 0x41, 0x41, 0x3E],
+    [0x7F, 0x09, 0x09, 0x09, 0x06],
+    [0x3E, 0x41, 0x51, 0x21, 0x5E],
+    [0x7F, 0x09, 0x19, 0x29, 0x46],
+    [0x26, 0x49, 0x49, 0x49, 0x32],
+    [0x03, 0x01, 0x7F, 0x01, 0x03],
+    [0x3F, 0x40, 0x40, 0x40, 0x3F],
+    [0x1F, 0x20, 0x40, 0x20, 0x1F],
+    [0x3F, 0x40, 0x38, 0x40, 0x3F],
+    [0x63, 0x14, 0x08, 0x14, 0x63],
+    [0x03, 0x04, 0x78, 0x04, 0x03],
+    [0x61, 0x59, 0x49, 0x4D, 0x43],
+    [0x00, 0x7F, 0x41, 0x41, 0x41],
+    [0x02, 0x04, 0x08, 0x10, 0x20],
+    [0x00, 0x41, 0x41, 0x41, 0x7F],
+    [0x04, 0x02, 0x01, 0x02, 0x04],
+    [0x40, 0x40, 0x40, 0x40, 0x40],
+    [0x00, 0x03, 0x07, 0x08, 0x00],
+    [0x20, 0x54, 0x54, 0x78, 0x40],
+    [0x7F, 0x28, 0x44, 0x44, 0x38],
+    [0x38, 0x44, 0x44, 0x44, 0x28],
+    [0x38, 0x44, 0x44, 0x28, 0x7F],
+    [0x38, 0x54, 0x54, 0x54, 0x18],
+    [0x00, 0x08, 0x7E, 0x09, 0x02],
+    [0x18, 0xA4, 0xA4, 0x9C, 0x78],
+    [0x7F, 0x08, 0x04, 0x04, 0x78],
+    [0x00, 0x44, 0x7D, 0x40, 0x00],
+    [0x20, 0x40, 0x40, 0x3D, 0x00],
+    [0x7F, 0x10, 0x28, 0x44, 0x00],
+    [0x00, 0x41, 0x7F, 0x40, 0x00],
+    [0x7C, 0x04, 0x78, 0x04, 0x78],
+    [0x7C, 0x08, 0x04, 0x04, 0x78],
+    [0x38, 0x44, 0x44, 0x44, 0x38],
+    [0xFC, 0x18, 0x24, 0x24, 0x18],
+    [0x18, 0x24, 0x24, 0x18, 0xFC],
+    [0x7C, 0x08, 0x04, 0x04, 0x08],
+    [0x48, 0x54, 0x54, 0x54, 0x24],
+    [0x04, 0x04, 0x3F, 0x44, 0x24],
+    [0x3C, 0x40, 0x40, 0x20, 0x7C],
+    [0x1C, 0x20, 0x40, 0x20, 0x1C],
+    [0x3C, 0x40, 0x30, 0x40, 0x3C],
+    [0x44, 0x28, 0x10, 0x28, 0x44],
+    [0x4C, 0x90, 0x90, 0x90, 0x7C],
+    [0x44, 0x64, 0x54, 0x4C, 0x44],
+    [0x00, 0x08, 0x36, 0x41, 0x00],
+    [0x00, 0x00, 0x77, 0x00, 0x00],
+    [0x00, 0x41, 0x36, 0x08, 0x00],
+    [0x02, 0x01, 0x02, 0x04, 0x02],
+];
+
+/// Checks if the product name can be drawn with the font, i.e. it only has
+/// printable ASCII characters.
+///
+/// The images are created without the product name if it cannot be drawn.
+pub fn can_draw(product_name: &str) -> bool {
+    product_name.chars().all(|c| (' '..='~').contains(&c))
+}
+
+/// Creates the content of a banner image with the product name.
+///
+/// The banner is displayed across the top of most of the dialogs. The image
+/// fades from white to a tint of the accent color, and the product name is
+/// drawn on the right side, so it does not collide with the title of a
+/// dialog. The product name is not drawn if it has characters that are not in
+/// the font.
+pub fn banner(product_name: &str) -> Vec<u8> {
+    let mut canvas = Canvas::new(BANNER_WIDTH, BANNER_HEIGHT);
+    for x in 0..BANNER_WIDTH {
+        canvas.fill_column(x, 0, BANNER_HEIGHT, WHITE.mix(ACCENT_TINT, x, BANNER_WIDTH));
+    }
+    if !can_draw(product_name) {
+        return canvas.to_bmp();
+    }
+    let (text, scale) = fit_line(product_name, BANNER_TEXT_WIDTH);
+    let x = BANNER_WIDTH - MARGIN - text_width(&text, scale);
+    let y = (BANNER_HEIGHT - LINE_HEIGHT * scale) / 2;
+    canvas.draw_text(x, y, &text, scale, ACCENT_DARK);
+    canvas.to_bmp()
+}
+
+/// Creates the content of a dialog image with the product name.
+///
+/// The dialog image is the background of the first and last dialogs, where
+/// the text of the dialog covers the right part of the image. A panel on the
+/// left fades from the dark to the light accent color with the product name
+/// drawn in white near the top. The product name is not drawn if it has
+/// characters that are not in the font.
+pub fn dialog(product_name: &str) -> Vec<u8> {
+    let mut canvas = Canvas::new(DIALOG_WIDTH, DIALOG_HEIGHT);
+    for y in 0..DIALOG_HEIGHT {
+        canvas.fill_row(
+            y,
+            0,
+            DIALOG_PANEL_WIDTH,
+            ACCENT_DARK.mix(ACCENT_LIGHT, y, DIALOG_HEIGHT),
+        );
+    }
+    if !can_draw(product_name) {
+        return canvas.to_bmp();
+    }
+    let lines = wrap(
+        product_name,
+        (DIALOG_PANEL_WIDTH - 2 * MARGIN) / (CHAR_WIDTH * 2),
+    );
+    for (index, line) in lines.iter().enumerate() {
+        canvas.draw_text(MARGIN, 2 * MARGIN + index * LINE_HEIGHT * 2, line, 2, WHITE);
+    }
+    canvas.to_bmp()
+}
+
+/// Gets the text and the largest scale, up to double size, for the text to
+/// fit the width. The text is shortened with an ellipsis if it does not fit
+/// at the normal size.
+fn fit_line(text: &str, width: usize) -> (String, usize) {
+    if text_width(text, 2) <= width {
+        return (text.to_owned(), 2);
+    }
+    let max_chars = width / CHAR_WIDTH;
+    if text.chars().count() <= max_chars {
+        (text.to_owned(), 1)
+    } else {
+        let mut shortened: String = text.chars().take(max_chars.saturating_sub(3)).collect();
+        shortened.push_str("...");
+        (shortened, 1)
+    }
+}
+
+/// Splits the text into lines with at most the number of characters. Words
+/// are only split if they are longer than a line.
+fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > max_chars {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            lines.push(word.drain(..max_chars).collect());
+        }
+        if word.is_empty() {
+            continue;
+        }
+        if !line.is_empty() && line.chars().count() + 1 + word.len() > max_chars {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.extend(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Gets the width of the text in pixels, without the space after the last
+/// character.
+fn text_width(text: &str, scale: usize) -> usize {
+    (text.chars().count() * CHAR_WIDTH).saturating_sub(1) * scale
+}
+
+fn glyph(c: char) -> &'static [u8; GLYPH_WIDTH] {
+    let index = if (' '..='~').contains(&c) {
+        c as usize - ' ' as usize
+    } else {
+        '?' as usize - ' ' as usize
+    };
+    &FONT[index]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rgb(u8, u8, u8);
+
+impl Rgb {
+    /// Mixes the color with another color, where the position within the
+    /// length is the amount of the other color.
+    fn mix(self, other: Rgb, position: usize, length: usize) -> Rgb {
+        let channel = |a: u8, b: u8| {
+            let (a, b) = (a as usize, b as usize);
+            ((a * (length - position) + b * position) / length) as u8
+        };
+        Rgb(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+}
+
+/// The pixels of an image, from the top-left corner row by row.
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Canvas {
+            width,
+            height,
+            pixels: vec![WHITE; width * height],
+        }
+    }
+
+    fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+
+    fn fill_column(&mut self, x: usize, y: usize, height: usize, color: Rgb) {
+        for y in y..y + height {
+            self.set(x, y, color);
+        }
+    }
+
+    fn fill_row(&mut self, y: usize, x: usize, width: usize, color: Rgb) {
+        for x in x..x + width {
+            self.set(x, y, color);
+        }
+    }
+
+    fn draw_text(&mut self, x: usize, y: usize, text: &str, scale: usize, color: Rgb) {
+        for (index, c) in text.chars().enumerate() {
+            let left = x + index * CHAR_WIDTH * scale;
+            for (column, bits) in glyph(c).iter().enumerate() {
+                for row in 0..8 {
+                    if bits & (1 << row) != 0 {
+                        for dy in 0..scale {
+                            self.fill_row(
+                                y + row * scale + dy,
+                                left + column * scale,
+                                scale,
+                                color,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Creates the content of a bitmap file with 24 bits per pixel.
+    ///
+    /// The rows are stored from the bottom to the top, each row is padded to
+    /// a multiple of four bytes, and the colors are in the blue, green, red
+    /// order.
+    fn to_bmp(&self) -> Vec<u8> {
+        let row_size = (self.width * 3 + 3) & !3;
+        let image_size = row_size * self.height;
+        let mut bmp = Vec::with_capacity(BMP_HEADER_SIZE + image_size);
+        bmp.extend_from_slice(b"BM");
+        bmp.extend_from_slice(&((BMP_HEADER_SIZE + image_size) as u32).to_le_bytes());
+        bmp.extend_from_slice(&0u32.to_le_bytes());
+        bmp.extend_from_slice(&(BMP_HEADER_SIZE as u32).to_le_bytes());
+        bmp.extend_from_slice(&40u32.to_le_bytes());
+        bmp.extend_from_slice(&(self.width as i32).to_le_bytes());
+        bmp.extend_from_slice(&(self.height as i32).to_le_bytes());
+        bmp.extend_from_slice(&1u16.to_le_bytes());
+        bmp.extend_from_slice(&24u16.to_le_bytes());
+        bmp.extend_from_slice(&0u32.to_le_bytes());
+        bmp.extend_from_slice(&(image_size as u32).to_le_bytes());
+        // 2835 pixels per meter is 72 DPI.
+        bmp.extend_from_slice(&2835i32.to_le_bytes());
+        bmp.extend_from_slice(&2835i32.to_le_bytes());
+        bmp.extend_from_slice(&0u32.to_le_bytes());
+        bmp.extend_from_slice(&0u32.to_le_bytes());
+        for row in self.pixels.chunks(self.width).rev() {
+            for pixel in row {
+                bmp.extend_from_slice(&[pixel.2, pixel.1, pixel.0]);
+            }
+            bmp.resize(bmp.len() + row_size - self.width * 3, 0);
+        }
+        bmp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_i32(bmp: &[u8], offset: usize) -> i32 {
+        i32::from_le_bytes(bmp[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn banner_has_correct_size() {
+        let actual = banner("Example");
+        assert_eq!(&actual[0..2], b"BM");
+        assert_eq!(read_i32(&actual, 18), BANNER_WIDTH as i32);
+        assert_eq!(read_i32(&actual, 22), BANNER_HEIGHT as i32);
+        assert_eq!(read_i32(&actual, 2) as usize, actual.len());
+        assert_eq!(actual.len(), BMP_HEADER_SIZE + 1480 * BANNER_HEIGHT);
+    }
+
+    #[test]
+    fn dialog_has_correct_size() {
+        let actual = dialog("Example");
+        assert_eq!(read_i32(&actual, 18), DIALOG_WIDTH as i32);
+        assert_eq!(read_i32(&actual, 22), DIALOG_HEIGHT as i32);
+        assert_eq!(actual.len(), BMP_HEADER_SIZE + 1480 * DIALOG_HEIGHT);
+    }
+
+    #[test]
+    fn banner_with_long_name_works() {
+        let actual = banner(&"Example".repeat(20));
+        assert_eq!(actual.len(), BMP_HEADER_SIZE + 1480 * BANNER_HEIGHT);
+    }
+
+    #[test]
+    fn fit_line_works() {
+        assert_eq!(fit_line("Example", 160), (String::from("Example"), 2));
+        assert_eq!(
+            fit_line("Example Application", 160),
+            (String::from("Example Application"), 1)
+        );
+        let (text, scale) = fit_line(&"x".repeat(40), 160);
+        assert_eq!(scale, 1);
+        assert_eq!(text, format!("{}...", "x".repeat(23)));
+    }
+
+    #[test]
+    fn wrap_works() {
+        assert_eq!(
+            wrap("Example Application Suite", 11),
+            vec!["Example", "Application", "Suite"]
+        );
+        assert_eq!(wrap("My App", 11), vec!["My App"]);
+        assert_eq!(
+            wrap("Supercalifragilistic", 11),
+            vec!["Supercalifr", "agilistic"]
+        );
+    }
+
+    #[test]
+    fn can_draw_works() {
+        assert!(can_draw("Example App 2.0"));
+        assert!(!can_draw("Exämple"));
+        assert!(!can_draw("例子"));
+    }
+
+    #[test]
+    fn banner_with_non_ascii_name_has_no_text() {
+        assert_eq!(banner("Exämple"), banner(""));
+        assert_ne!(banner("Example"), banner(""));
+    }
+
+    #[test]
+    fn dialog_with_non_ascii_name_has_no_text() {
+        assert_eq!(dialog("Exämple"), dialog(""));
+        assert_ne!(dialog("Example"), dialog(""));
+    }
+
+    #[test]
+    fn glyph_of_non_ascii_character_is_question_mark() {
+        assert_eq!(glyph('é'), glyph('?'));
+    }
+}
@@ -0,0 +1,730 @@
+// Copyright (C) 2017 Christopher R. Field.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The implementation for the `check` command. This command focuses on
+//! scanning the WiX Source (wxs) files of a project for common cargo-wix
+//! specific mistakes without compiling them.
+//!
+//! The checks are a read-only analysis of the files, and the WiX Toolset is
+//! not needed. The files are only modified to migrate the deprecated variables
+//! if the `fix` option is used.
+
+use crate::print::schema;
+use crate::read_wxs_source;
+use crate::Error;
+use crate::Result;
+use crate::WIX;
+use crate::WIX_SOURCE_FILE_EXTENSION;
+
+use cargo_metadata::Package;
+
+use log::{debug, info, trace, warn};
+
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sxd_document::dom::{ChildOfElement, ChildOfRoot, Element};
+
+/// The deprecated variables, and their replacements, that are still defined
+/// by the default _create_ subcommand for existing projects.
+const DEPRECATED_VARIABLES: &[(&str, &str)] = &[
+    ("$(var.Platform)", "$(sys.BUILDARCH)"),
+    ("$(var.Profile)", "$(var.CargoProfile)"),
+];
+
+/// The extension appended to the name of a WiX Source (wxs) file for the copy
+/// of the file made before migrating the deprecated variables.
+const BACKUP_FILE_EXTENSION: &str = "bak";
+
+/// The elements that must have an `UpgradeCode` attribute.
+const UPGRADE_CODE_ELEMENTS: &[&str] = &["Bundle", "Product"];
+
+/// A builder for creating an execution context to check the WiX Source (wxs)
+/// files of a project.
+#[derive(Debug, Clone)]
+pub struct Builder<'a> {
+    fix: bool,
+    includes: Option<Vec<&'a str>>,
+    input: Option<&'a str>,
+    package: Option<&'a str>,
+}
+
+impl<'a> Builder<'a> {
+    /// Creates a new `Builder` instance.
+    pub fn new() -> Self {
+        Builder {
+            fix: false,
+            includes: None,
+            input: None,
+            package: None,
+        }
+    }
+
+    /// Rewrites the deprecated variables in the WiX Source (wxs) files.
+    ///
+    /// If `true`, the `$(var.Platform)` and `$(var.Profile)` variables are
+    /// replaced with the `$(sys.BUILDARCH)` and `$(var.CargoProfile)`
+    /// variables, respectively, before the files are checked. The original
+    /// content of a rewritten file is saved next to it with the `.bak`
+    /// extension appended, i.e. `main.wxs.bak`. The default is to only report
+    /// the deprecated variables.
+    pub fn fix(&mut self, f: bool) -> &mut Self {
+        self.fix = f;
+        self
+    }
+
+    /// Sets the paths to the WiX Source (wxs) files to check.
+    ///
+    /// The default is to check the WiX Source files in the `wix` folder of
+    /// the package and the files in the `include` field of the
+    /// `[package.metadata.wix]` section of the package's manifest
+    /// (Cargo.toml).
+    pub fn includes(&mut self, i: Option<Vec<&'a str>>) -> &mut Self {
+        self.includes = i;
+        self
+    }
+
+    /// Sets the path to a package's manifest (Cargo.toml).
+    ///
+    /// The default is to use the current working directory if a Cargo.toml
+    /// file is found. The manifest is not used if WiX Source (wxs) files are
+    /// set with the [`includes`] method.
+    ///
+    /// [`includes`]: #method.includes
+    pub fn input(&mut self, i: Option<&'a str>) -> &mut Self {
+        self.input = i;
+        self
+    }
+
+    /// Sets the package within a workspace with the WiX Source (wxs) files to
+    /// check.
+    pub fn package(&mut self, p: Option<&'a str>) -> &mut Self {
+        self.package = p;
+        self
+    }
+
+    /// Builds an execution context to check the WiX Source (wxs) files.
+    pub fn build(&mut self) -> Execution {
+        Execution {
+            fix: self.fix,
+            includes: self
+                .includes
+                .as_ref()
+                .map(|v| v.iter().map(PathBuf::from).collect()),
+            input: self.input.map(PathBuf::from),
+            package: self.package.map(String::from),
+        }
+    }
+}
+
+impl<'a> Default for Builder<'a> {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
+
+/// A context for checking the WiX Source (wxs) files of a project.
+#[derive(Debug)]
+pub struct Execution {
+    fix: bool,
+    includes: Option<Vec<PathBuf>>,
+    input: Option<PathBuf>,
+    package: Option<String>,
+}
+
+impl Execution {
+    /// Checks the WiX Source (wxs) files for common mistakes.
+    ///
+    /// Each finding is printed to `STDOUT` on its own line prefixed with the
+    /// path to the file and the line number. An error is returned if any
+    /// findings are reported or no files are found to check.
+    pub fn run(self) -> Result<()> {
+        debug!("fix = {:?}", self.fix);
+        debug!("includes = {:?}", self.includes);
+        debug!("input = {:?}", self.input);
+        debug!("package = {:?}", self.package);
+        let sources = self.sources()?;
+        debug!("sources = {:?}", sources);
+        let mut findings = 0;
+        for source in &sources {
+            info!("Checking the '{}' file", source.display());
+            let mut content = read_wxs_source(source)?;
+            if self.fix {
+                content = fix(source, content)?;
+            }
+            for finding in check(&content) {
+                println!("{}:{}", source.display(), finding);
+                findings += 1;
+            }
+        }
+        if findings == 0 {
+            info!(
+                "No problems found in {} WiX Source (wxs) file(s)",
+                sources.len()
+            );
+            Ok(())
+        } else {
+            Err(Error::Generic(format!(
+                "Found {} problem(s) in {} WiX Source (wxs) file(s)",
+                findings,
+                sources.len()
+            )))
+        }
+    }
+
+    fn sources(&self) -> Result<Vec<PathBuf>> {
+        if let Some(includes) = &self.includes {
+            trace!("The WiX Source files to check have been explicitly set");
+            for include in includes {
+                if !include.is_file() {
+                    return Err(Error::Generic(format!(
+                        "The '{}' path is not a WiX Source (wxs) file",
+                        include.display()
+                    )));
+                }
+            }
+            return Ok(includes.clone());
+        }
+        let manifest = crate::manifest(self.input.as_ref())?;
+        let package = crate::package(&manifest, self.package.as_deref())?;
+        let mut sources = wix_folder_sources(&package)?;
+        if let Some(includes) = package
+            .metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("include"))
+            .and_then(|i| i.as_array())
+        {
+            trace!("Using the 'include' field in the package's manifest");
+            sources.extend(
+                includes
+                    .iter()
+                    .filter_map(|i| i.as_str())
+                    .map(|i| crate::package_relative_path(&package, i)),
+            );
+        }
+        if sources.is_empty() {
+            Err(Error::Generic(String::from(
+                "No WiX Source (wxs) files were found to check. Consider using the \
+                 'cargo wix init' command to create one, or the '-I,--include' option \
+                 to check a specific file.",
+            )))
+        } else {
+            Ok(sources)
+        }
+    }
+}
+
+impl Default for Execution {
+    fn default() -> Self {
+        Builder::new().build()
+    }
+}
+
+/// Rewrites the deprecated variables in a WiX Source (wxs) file.
+///
+/// The file is only written if a deprecated variable is found, after the
+/// original content is copied to a backup file. The migrated content is
+/// returned.
+fn fix(source: &Path, content: String) -> Result<String> {
+    let migrated = migrate_variables(&content);
+    if migrated == content {
+        trace!("No deprecated variables to migrate");
+        return Ok(content);
+    }
+    let mut backup = OsString::from(source.as_os_str());
+    backup.push(".");
+    backup.push(BACKUP_FILE_EXTENSION);
+    let backup = PathBuf::from(backup);
+    fs::copy(source, &backup)?;
+    fs::write(source, &migrated)?;
+    warn!(
+        "Migrated the deprecated variables in the '{}' file. The original file is saved \
+         as '{}'.",
+        source.display(),
+        backup.display()
+    );
+    Ok(migrated)
+}
+
+/// Replaces the deprecated variables with their replacements.
+///
+/// Only the exact references outside of XML comments are replaced, so the
+/// surrounding XML, comments, and variables that start with the same name,
+/// such as `$(var.PlatformProgramFilesFolder)`, are not changed.
+fn migrate_variables(content: &str) -> String {
+    let replace = |text: &str| {
+        DEPRECATED_VARIABLES
+            .iter()
+            .fold(text.to_owned(), |t, (variable, replacement)| {
+                t.replace(variable, replacement)
+            })
+    };
+    let mut migrated = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("<!--") {
+        migrated.push_str(&replace(&rest[..start]));
+        let tail = &rest[start..];
+        let end = tail.find("-->").map(|e| e + 3).unwrap_or(tail.len());
+        migrated.push_str(&tail[..end]);
+        rest = &tail[end..];
+    }
+    migrated.push_str(&replace(rest));
+    migrated
+}
+
+/// Gets the WiX Source (wxs) files in the `wix` folder of a package, sorted by
+/// name.
+fn wix_folder_sources(package: &Package) -> Result<Vec<PathBuf>> {
+    let wix_folder = crate::package_relative_path(package, WIX);
+    debug!("wix_folder = {:?}", wix_folder);
+    let mut sources = Vec::new();
+    if wix_folder.is_dir() {
+        for entry in fs::read_dir(wix_folder)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) == Some(WIX_SOURCE_FILE_EXTENSION) {
+                sources.push(path);
+            }
+        }
+    }
+    sources.sort();
+    Ok(sources)
+}
+
+/// Checks the content of a WiX Source (wxs) file for common mistakes.
+///
+/// The content is checked for deprecated variables, missing upgrade codes,
+/// hard-coded absolute paths, and placeholder GUIDs. Each finding is prefixed
+/// with the line number, and the column number for malformed XML, of the
+/// finding. An empty list is returned if nothing is found.
+pub fn check(content: &str) -> Vec<String> {
+    let mut findings = Vec::new();
+    for (index, line) in strip_comments(content).lines().enumerate() {
+        for (variable, replacement) in DEPRECATED_VARIABLES {
+            if line.contains(variable) {
+                findings.push((
+                    index + 1,
+                    format!(
+                        "The '{variable}' variable is deprecated. Replace it with the \
+                         '{replacement}' variable."
+                    ),
+                ));
+            }
+        }
+    }
+    match sxd_document::parser::parse(content) {
+        Ok(package) => {
+            let mut lines = schema::start_lines(content).into_iter();
+            for child in package.as_document().root().children() {
+                if let ChildOfRoot::Element(element) = child {
+                    check_element(element, &mut lines, &mut findings);
+                }
+            }
+        }
+        Err(err) => {
+            let (line, column) = schema::position(content, err.location());
+            findings.push((line, format!("{column}: The XML is malformed: {err}")));
+        }
+    }
+    findings.sort_by_key(|(line, _)| *line);
+    findings
+        .into_iter()
+        .map(|(line, finding)| format!("{line}: {finding}"))
+        .collect()
+}
+
+fn check_element(
+    element: Element,
+    lines: &mut std::vec::IntoIter<usize>,
+    findings: &mut Vec<(usize, String)>,
+) {
+    // The start lines are in the same order as the elements are visited, so
+    // the next line is always for this element.
+    let line = lines.next().unwrap_or(0);
+    let name = element.name().local_part();
+    if UPGRADE_CODE_ELEMENTS.contains(&name) && element.attribute("UpgradeCode").is_none() {
+        findings.push((
+            line,
+            format!(
+                "The '{name}' element is missing the 'UpgradeCode' attribute, so a newer \
+                 version cannot upgrade an installed version"
+            ),
+        ));
+    }
+    for attribute in element.attributes() {
+        let attribute_name = attribute.name().local_part();
+        let value = attribute.value();
+        if is_absolute_path(value) {
+            findings.push((
+                line,
+                format!(
+                    "The '{value}' value for the '{attribute_name}' attribute of the \
+                     '{name}' element is a hard-coded absolute path. Use a relative path \
+                     or a variable, such as '$(var.CargoTargetBinDir)', instead."
+                ),
+            ));
+        }
+        if is_placeholder_guid(value) {
+            findings.push((
+                line,
+                format!(
+                    "The '{value}' value for the '{attribute_name}' attribute of the \
+                     '{name}' element looks like a placeholder GUID. Generate a new GUID \
+                     instead."
+                ),
+            ));
+        }
+    }
+    for child in element.children() {
+        if let ChildOfElement::Element(child) = child {
+            check_element(child, lines, findings);
+        }
+    }
+}
+
+/// Replaces the XML comments in the content with whitespace.
+///
+/// The line breaks are kept, so the line numbers do not change.
+fn strip_comments(content: &str) -> String {
+    let mut stripped = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("<!--") {
+        stripped.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        let end = tail.find("-->").map(|e| e + 3).unwrap_or(tail.len());
+        stripped.extend(
+            tail[..end]
+                .chars()
+                .map(|c| if c == '\n' { '\n' } else { ' ' }),
+        );
+        rest = &tail[end..];
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+/// Checks if a value is an absolute path with a drive letter, such as
+/// `C:\Path`, or a UNC path, such as `\\Server\Share`.
+fn is_absolute_path(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    (bytes.len() > 2
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && (bytes[2] == b'\\' || bytes[2] == b'/'))
+        || value.starts_with("\\\\")
+}
+
+/// Checks if a value looks like a placeholder for a GUID.
+///
+/// A placeholder is the `PUT-GUID-HERE` text from the WiX Toolset
+/// documentation, a GUID with `X` or `?` characters, such as
+/// `XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX`, a GUID with the same digit
+/// repeated, such as the nil GUID, or a GUID that counts up from `12345678`.
+fn is_placeholder_guid(value: &str) -> bool {
+    let value = value.to_ascii_uppercase();
+    if value.contains("PUT-GUID-HERE") {
+        return true;
+    }
+    let value = value.trim_start_matches('{').trim_end_matches('}');
+    let groups: Vec<&str> = value.split('-').collect();
+    if groups.iter().map(|g| g.len()).collect::<Vec<usize>>() != [8, 4, 4, 4, 12] {
+        return false;
+    }
+    let digits: String = groups.concat();
+    if !digits
+        .chars()
+        .all(|c| c.is_ascii_hexdigit() || c == 'X' || c == '?')
+    {
+        return false;
+    }
+    digits.contains(['X', '?'])
+        || digits.chars().all(|c| digits.starts_with(c))
+        || digits.starts_with("1234567812341234")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::setup_project;
+
+    const MIN_MANIFEST: &str = r#"[package]
+        name = "Example"
+        version = "0.1.0"
+        authors = ["First Last <first.last@example.com>"]
+    "#;
+
+    const VALID: &str = r#"<?xml version='1.0'?>
+<Wix xmlns='http://schemas.microsoft.com/wix/2006/wi'>
+    <!-- <File Source='$(var.Platform)'/> -->
+    <Product Id='*' UpgradeCode='B36177BE-EA4D-44FB-B05C-EDDABDAA95CA'>
+        <File Source='$(var.CargoTargetBinDir)\example.exe'/>
+    </Product>
+</Wix>
+"#;
+
+    mod builder {
+        use super::*;
+
+        #[test]
+        fn fix_works() {
+            let mut actual = Builder::new();
+            actual.fix(true);
+            assert!(actual.fix);
+        }
+
+        #[test]
+        fn includes_works() {
+            const EXPECTED: &str = "C:\\main.wxs";
+            let mut actual = Builder::new();
+            actual.includes(Some(vec![EXPECTED]));
+            assert_eq!(actual.includes, Some(vec![EXPECTED]));
+        }
+
+        #[test]
+        fn input_works() {
+            const EXPECTED: &str = "C:\\Example";
+            let mut actual = Builder::new();
+            actual.input(Some(EXPECTED));
+            assert_eq!(actual.input, Some(EXPECTED));
+        }
+
+        #[test]
+        fn package_works() {
+            const EXPECTED: &str = "Example";
+            let mut actual = Builder::new();
+            actual.package(Some(EXPECTED));
+            assert_eq!(actual.package, Some(EXPECTED));
+        }
+    }
+
+    mod execution {
+        use super::*;
+
+        #[test]
+        fn sources_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let wix = project.path().join(WIX);
+            fs::create_dir(&wix).unwrap();
+            fs::write(wix.join("main.wxs"), VALID).unwrap();
+            fs::write(wix.join("License.rtf"), "").unwrap();
+            let input = project.path().join("Cargo.toml");
+            let actual = Builder::new()
+                .input(input.to_str())
+                .build()
+                .sources()
+                .unwrap();
+            assert_eq!(actual, vec![wix.join("main.wxs")]);
+        }
+
+        #[test]
+        fn sources_without_files_fails() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            assert!(Builder::new()
+                .input(input.to_str())
+                .build()
+                .sources()
+                .is_err());
+        }
+
+        #[test]
+        fn run_with_valid_source_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let source = project.path().join("main.wxs");
+            fs::write(&source, VALID).unwrap();
+            assert!(Builder::new()
+                .includes(Some(vec![source.to_str().unwrap()]))
+                .build()
+                .run()
+                .is_ok());
+        }
+
+        #[test]
+        fn run_with_utf16_source_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let source = project.path().join("main.wxs");
+            let mut content = vec![0xFF, 0xFE];
+            content.extend(VALID.encode_utf16().flat_map(u16::to_le_bytes));
+            fs::write(&source, content).unwrap();
+            assert!(Builder::new()
+                .includes(Some(vec![source.to_str().unwrap()]))
+                .build()
+                .run()
+                .is_ok());
+        }
+
+        #[test]
+        fn run_with_fix_works() {
+            const DEPRECATED: &str = r#"<?xml version='1.0'?>
+<Wix xmlns='http://schemas.microsoft.com/wix/2006/wi'>
+    <?if $(var.Platform) = x64 ?>
+    <?endif ?>
+    <Product Id='*' UpgradeCode='B36177BE-EA4D-44FB-B05C-EDDABDAA95CA'>
+        <File Source='target\$(var.Profile)\example.exe'/>
+    </Product>
+</Wix>
+"#;
+            let project = setup_project(MIN_MANIFEST);
+            let source = project.path().join("main.wxs");
+            fs::write(&source, DEPRECATED).unwrap();
+            assert!(Builder::new()
+                .fix(true)
+                .includes(Some(vec![source.to_str().unwrap()]))
+                .build()
+                .run()
+                .is_ok());
+            let actual = fs::read_to_string(&source).unwrap();
+            assert!(actual.contains("<?if $(sys.BUILDARCH) = x64 ?>"));
+            assert!(actual.contains("<File Source='target\\$(var.CargoProfile)\\example.exe'/>"));
+            assert_eq!(
+                fs::read_to_string(project.path().join("main.wxs.bak")).unwrap(),
+                DEPRECATED
+            );
+        }
+
+        #[test]
+        fn run_with_fix_without_deprecated_variables_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let source = project.path().join("main.wxs");
+            fs::write(&source, VALID).unwrap();
+            assert!(Builder::new()
+                .fix(true)
+                .includes(Some(vec![source.to_str().unwrap()]))
+                .build()
+                .run()
+                .is_ok());
+            assert_eq!(fs::read_to_string(&source).unwrap(), VALID);
+            assert!(!project.path().join("main.wxs.bak").exists());
+        }
+
+        #[test]
+        fn run_with_findings_fails() {
+            let project = setup_project(MIN_MANIFEST);
+            let source = project.path().join("main.wxs");
+            fs::write(&source, "<Wix><Product/></Wix>").unwrap();
+            assert!(Builder::new()
+                .includes(Some(vec![source.to_str().unwrap()]))
+                .build()
+                .run()
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn check_with_valid_source_works() {
+        assert!(check(VALID).is_empty());
+    }
+
+    #[test]
+    fn check_with_deprecated_variables_works() {
+        let actual = check(
+            "<Wix>\n<Product UpgradeCode='$(var.Code)'>\n\
+             <?if $(var.Platform) = x64 ?><?endif?>\n\
+             <File Source='target\\$(var.Profile)\\example.exe'/>\n</Product>\n</Wix>",
+        );
+        assert_eq!(actual.len(), 2);
+        assert!(actual[0].starts_with("3: The '$(var.Platform)' variable"));
+        assert!(actual[1].starts_with("4: The '$(var.Profile)' variable"));
+    }
+
+    #[test]
+    fn check_with_missing_upgrade_code_works() {
+        let actual = check("<Wix>\n  <Product Id='*'/>\n</Wix>");
+        assert_eq!(actual.len(), 1);
+        assert!(actual[0].starts_with("2: The 'Product' element is missing"));
+    }
+
+    #[test]
+    fn check_with_absolute_paths_works() {
+        let actual = check(
+            "<Wix>\n<Product UpgradeCode='$(var.Code)'>\n\
+             <File Source='C:\\Users\\Example\\example.exe'/>\n\
+             <File Source='\\\\Server\\Share\\example.exe'/>\n</Product>\n</Wix>",
+        );
+        assert_eq!(actual.len(), 2);
+        assert!(actual[0].starts_with("3: The 'C:\\Users\\Example\\example.exe' value"));
+        assert!(actual[1].starts_with("4: The '\\\\Server\\Share\\example.exe' value"));
+    }
+
+    #[test]
+    fn check_with_placeholder_guids_works() {
+        let actual = check(
+            "<Wix>\n<Product UpgradeCode='PUT-GUID-HERE'>\n\
+             <Component Guid='00000000-0000-0000-0000-000000000000'/>\n\
+             <Component Guid='B36177BE-EA4D-44FB-B05C-EDDABDAA95CA'/>\n</Product>\n</Wix>",
+        );
+        assert_eq!(actual.len(), 2);
+        assert!(actual[0].starts_with("2: The 'PUT-GUID-HERE' value"));
+        assert!(actual[1].starts_with("3: The '00000000-0000-0000-0000-000000000000' value"));
+    }
+
+    #[test]
+    fn check_with_malformed_xml_works() {
+        let actual = check("<Wix>\n<Product UpgradeCode='$(var.Code)'>\n</Wix>");
+        assert_eq!(actual.len(), 1);
+        assert!(actual[0].contains("The XML is malformed"));
+    }
+
+    #[test]
+    fn check_default_template_works() {
+        let project = setup_project(MIN_MANIFEST);
+        let input = project.path().join("Cargo.toml");
+        let rendered = crate::print::wxs::Builder::default()
+            .input(input.to_str())
+            .build()
+            .render()
+            .unwrap()
+            .wxs
+            .rendered;
+        assert_eq!(check(&rendered), Vec::<String>::new());
+    }
+
+    #[test]
+    fn migrate_variables_works() {
+        assert_eq!(
+            migrate_variables(
+                "<!-- $(var.Platform) --><?if $(var.Platform) = x86 ?>\
+                 <?define Folder = $(var.PlatformProgramFilesFolder) ?>\
+                 <File Source='target\\$(var.Profile)\\example.exe'/>"
+            ),
+            "<!-- $(var.Platform) --><?if $(sys.BUILDARCH) = x86 ?>\
+             <?define Folder = $(var.PlatformProgramFilesFolder) ?>\
+             <File Source='target\\$(var.CargoProfile)\\example.exe'/>"
+        );
+    }
+
+    #[test]
+    fn is_placeholder_guid_works() {
+        assert!(is_placeholder_guid("XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX"));
+        assert!(is_placeholder_guid(
+            "{FFFFFFFF-FFFF-FFFF-FFFF-FFFFFFFFFFFF}"
+        ));
+        assert!(is_placeholder_guid("12345678-1234-1234-1234-123456789012"));
+        assert!(is_placeholder_guid("put-guid-here"));
+        assert!(!is_placeholder_guid("B36177BE-EA4D-44FB-B05C-EDDABDAA95CA"));
+        assert!(!is_placeholder_guid("*"));
+        assert!(!is_placeholder_guid("Example"));
+    }
+
+    #[test]
+    fn is_absolute_path_works() {
+        assert!(is_absolute_path("C:\\Example"));
+        assert!(is_absolute_path("c:/Example"));
+        assert!(is_absolute_path("\\\\Server\\Share"));
+        assert!(!is_absolute_path("wix\\License.rtf"));
+        assert!(!is_absolute_path("$(var.CargoTargetBinDir)\\example.exe"));
+        assert!(!is_absolute_path("C:"));
+    }
+}
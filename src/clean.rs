@@ -16,24 +16,48 @@
 //! cleaning up build output, similar to the `cargo clean` subcommand.
 
 use crate::Result;
+use crate::EXE_FILE_EXTENSION;
+use crate::MSI_FILE_EXTENSION;
 use crate::WIX;
+use crate::ZIP_FILE_EXTENSION;
+
+use cargo_metadata::Package;
 
 use log::{debug, info, trace, warn};
 
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// The file extension of the WiX debug symbols written next to an installer
+/// by the linker (light.exe).
+const WIXPDB_FILE_EXTENSION: &str = "wixpdb";
+
+/// The file extensions of the files written to the output location when
+/// creating an installer.
+const OUTPUT_FILE_EXTENSIONS: [&str; 4] = [
+    EXE_FILE_EXTENSION,
+    MSI_FILE_EXTENSION,
+    WIXPDB_FILE_EXTENSION,
+    ZIP_FILE_EXTENSION,
+];
+
 /// A builder for creating an execution context to clean a package of WiX
 /// Toolset-related output.
 #[derive(Debug, Clone)]
 pub struct Builder<'a> {
     input: Option<&'a str>,
+    output: Option<&'a str>,
+    package: Option<&'a str>,
 }
 
 impl<'a> Builder<'a> {
     /// Creates a new `Builder` instance.
     pub fn new() -> Self {
-        Builder { input: None }
+        Builder {
+            input: None,
+            output: None,
+            package: None,
+        }
     }
 
     /// Sets the path to a package's manifest (Cargo.toml) to be cleaned.
@@ -45,11 +69,39 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the output location of the created installers to be cleaned.
+    ///
+    /// This is the same value used with the `output` method when creating an
+    /// installer. If the path is to an existing folder or has a trailing `/`
+    /// or `\`, then the installers named after the package, i.e.
+    /// `<name>-*.msi`, in the folder are removed. Otherwise, the path is the
+    /// installer file to remove. The WiX debug symbols (wixpdb) next to an
+    /// installer are removed, too, but no other files in the folder are.
+    ///
+    /// The default is to use the `output` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or to only remove the `target\wix` folder if the field does not exist.
+    pub fn output(&mut self, o: Option<&'a str>) -> &mut Self {
+        self.output = o;
+        self
+    }
+
+    /// Sets the package within a workspace to be cleaned.
+    ///
+    /// The package is used to find the output location and the name of the
+    /// installers.
+    pub fn package(&mut self, p: Option<&'a str>) -> &mut Self {
+        self.package = p;
+        self
+    }
+
     /// Builds an execution context to clean the package of WiX Toolset-related
     /// output.
     pub fn build(&mut self) -> Execution {
         Execution {
             input: self.input.map(PathBuf::from),
+            output: self.output.map(String::from),
+            package: self.package.map(String::from),
         }
     }
 }
@@ -64,24 +116,48 @@ impl<'a> Default for Builder<'a> {
 #[derive(Debug)]
 pub struct Execution {
     input: Option<PathBuf>,
+    output: Option<String>,
+    package: Option<String>,
 }
 
 impl Execution {
-    /// Removes WiX Toolset-related output from the package's `target` folder.
+    /// Removes WiX Toolset-related output from the package's `target` folder
+    /// and the installers from the output location, if one is specified.
     ///
     /// This is similar to the `cargo clean` subcommand.
     pub fn run(self) -> Result<()> {
         debug!("input = {:?}", self.input);
+        debug!("output = {:?}", self.output);
+        debug!("package = {:?}", self.package);
         let manifest = super::manifest(self.input.as_ref())?;
         debug!("target_directory = {:?}", manifest.target_directory);
+        let mut cleaned = false;
         let target_wix = self.target_wix(manifest.target_directory.as_std_path());
         debug!("target_wix = {:?}", target_wix);
         if target_wix.exists() {
             trace!("The 'target\\wix' folder exists");
             warn!("Removing the 'target\\wix' folder");
             fs::remove_dir_all(target_wix)?;
+            cleaned = true;
         } else {
             trace!("The 'target\\wix' folder does not exist");
+        }
+        let package = match super::package(&manifest, self.package.as_deref()) {
+            Ok(package) => Some(package),
+            Err(err) if self.output.is_none() && self.package.is_none() => {
+                trace!("Skipping the output location: {}", err);
+                None
+            }
+            Err(err) => return Err(err),
+        };
+        if let Some(package) = package {
+            for file in self.output_files(&package)? {
+                warn!("Removing the '{}' file", file.display());
+                fs::remove_file(file)?;
+                cleaned = true;
+            }
+        }
+        if !cleaned {
             info!("Nothing to clean");
         }
         Ok(())
@@ -90,6 +166,88 @@ impl Execution {
     fn target_wix(&self, target_directory: &Path) -> PathBuf {
         target_directory.join(WIX)
     }
+
+    /// Gets the files created at the output location that exist.
+    ///
+    /// Only the installers named after the package and their WiX debug
+    /// symbols (wixpdb) are included, so other files in an output folder are
+    /// never removed.
+    fn output_files(&self, package: &Package) -> Result<Vec<PathBuf>> {
+        let (path, is_dir) = if let Some(ref output) = self.output {
+            trace!("Using the explicitly specified output location");
+            let path = PathBuf::from(output);
+            let is_dir = output.ends_with('/') || output.ends_with('\\') || path.is_dir();
+            (path, is_dir)
+        } else if let Some(pkg_meta_wix_output) = package
+            .metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("output"))
+            .and_then(|o| o.as_str())
+        {
+            trace!("Using the output location in the package's metadata");
+            let path = crate::package_relative_path(package, pkg_meta_wix_output);
+            let is_dir = pkg_meta_wix_output.ends_with('/')
+                || pkg_meta_wix_output.ends_with('\\')
+                || path.is_dir();
+            (path, is_dir)
+        } else {
+            return Ok(Vec::new());
+        };
+        debug!("output = {:?}", path);
+        let mut files = Vec::new();
+        if is_dir {
+            if !path.is_dir() {
+                trace!("The '{}' output folder does not exist", path.display());
+                return Ok(files);
+            }
+            let prefix = format!("{}-", installer_name(package));
+            for entry in fs::read_dir(&path)? {
+                let file = entry?.path();
+                let is_output = file.is_file()
+                    && file
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| n.starts_with(&prefix))
+                    && has_output_extension(&file);
+                if is_output {
+                    files.push(file);
+                }
+            }
+            files.sort();
+        } else {
+            let wixpdb = path.with_extension(WIXPDB_FILE_EXTENSION);
+            if path.is_file() && has_output_extension(&path) {
+                files.push(path);
+            }
+            if wixpdb.is_file() {
+                files.push(wixpdb);
+            }
+        }
+        Ok(files)
+    }
+}
+
+/// Gets the name used for the file names of the installers, which is the
+/// `name` field in the `[package.metadata.wix]` section or the package's name.
+fn installer_name(package: &Package) -> &str {
+    package
+        .metadata
+        .get("wix")
+        .and_then(|w| w.as_object())
+        .and_then(|t| t.get("name"))
+        .and_then(|n| n.as_str())
+        .unwrap_or(&package.name)
+}
+
+/// Determines if a file has the extension of a file created at the output
+/// location, ignoring case.
+fn has_output_extension(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()).is_some_and(|e| {
+        OUTPUT_FILE_EXTENSIONS
+            .iter()
+            .any(|o| o.eq_ignore_ascii_case(e))
+    })
 }
 
 impl Default for Execution {
@@ -112,6 +270,22 @@ mod tests {
             actual.input(Some(EXPECTED));
             assert_eq!(actual.input, Some(EXPECTED));
         }
+
+        #[test]
+        fn output_works() {
+            const EXPECTED: &str = "C:\\dist\\";
+            let mut actual = Builder::default();
+            actual.output(Some(EXPECTED));
+            assert_eq!(actual.output, Some(EXPECTED));
+        }
+
+        #[test]
+        fn package_works() {
+            const EXPECTED: &str = "Example";
+            let mut actual = Builder::default();
+            actual.package(Some(EXPECTED));
+            assert_eq!(actual.package, Some(EXPECTED));
+        }
     }
 
     mod execution {
@@ -143,5 +317,113 @@ mod tests {
                 .target_wix(&target);
             assert_eq!(actual, expected);
         }
+
+        const MIN_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+        "#;
+
+        fn package(project: &assert_fs::TempDir) -> Package {
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
+            crate::package(&manifest, None).unwrap()
+        }
+
+        #[test]
+        fn output_files_without_output_works() {
+            let project = crate::tests::setup_project(MIN_MANIFEST);
+            let actual = Execution::default()
+                .output_files(&package(&project))
+                .unwrap();
+            assert!(actual.is_empty());
+        }
+
+        #[test]
+        fn output_files_with_folder_works() {
+            let project = crate::tests::setup_project(MIN_MANIFEST);
+            let dist = project.path().join("dist");
+            fs::create_dir(&dist).unwrap();
+            for name in [
+                "Example-0.1.0-x86_64.msi",
+                "Example-0.1.0-x86_64.wixpdb",
+                "Example-0.0.9-x86_64.zip",
+                "Example-0.1.0-x86_64.txt",
+                "Other-0.1.0-x86_64.msi",
+                "README.md",
+            ] {
+                File::create(dist.join(name)).unwrap();
+            }
+            let actual = Builder::new()
+                .output(dist.to_str())
+                .build()
+                .output_files(&package(&project))
+                .unwrap();
+            assert_eq!(
+                actual,
+                vec![
+                    dist.join("Example-0.0.9-x86_64.zip"),
+                    dist.join("Example-0.1.0-x86_64.msi"),
+                    dist.join("Example-0.1.0-x86_64.wixpdb"),
+                ]
+            );
+        }
+
+        #[test]
+        fn output_files_with_file_works() {
+            let project = crate::tests::setup_project(MIN_MANIFEST);
+            let installer = project.path().join("Installer.msi");
+            File::create(&installer).unwrap();
+            File::create(project.path().join("Installer.wixpdb")).unwrap();
+            let actual = Builder::new()
+                .output(installer.to_str())
+                .build()
+                .output_files(&package(&project))
+                .unwrap();
+            assert_eq!(
+                actual,
+                vec![installer, project.path().join("Installer.wixpdb")]
+            );
+        }
+
+        #[test]
+        fn output_files_with_metadata_works() {
+            const OUTPUT_MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                name = "Custom"
+                output = "dist/"
+            "#;
+            let project = crate::tests::setup_project(OUTPUT_MANIFEST);
+            let dist = project.path().join("dist");
+            fs::create_dir(&dist).unwrap();
+            File::create(dist.join("Custom-0.1.0-x86_64.msi")).unwrap();
+            File::create(dist.join("Example-0.1.0-x86_64.msi")).unwrap();
+            let actual = Execution::default()
+                .output_files(&package(&project))
+                .unwrap();
+            assert_eq!(actual, vec![dist.join("Custom-0.1.0-x86_64.msi")]);
+        }
+
+        #[test]
+        fn run_with_output_works() {
+            let project = crate::tests::setup_project(MIN_MANIFEST);
+            let dist = project.path().join("dist");
+            fs::create_dir(&dist).unwrap();
+            File::create(dist.join("Example-0.1.0-x86_64.msi")).unwrap();
+            File::create(dist.join("README.md")).unwrap();
+            let input = project.path().join("Cargo.toml");
+            Builder::new()
+                .input(input.to_str())
+                .output(dist.to_str())
+                .build()
+                .run()
+                .unwrap();
+            assert!(!dist.join("Example-0.1.0-x86_64.msi").exists());
+            assert!(dist.join("README.md").exists());
+        }
     }
 }
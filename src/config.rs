@@ -0,0 +1,228 @@
+// Copyright (C) 2017 Christopher R. Field.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The implementation for the `config show` command. This command focuses on
+//! printing the value of each supported field in the `[package.metadata.wix]`
+//! section of a package's manifest (Cargo.toml) and where the value comes
+//! from.
+//!
+//! A CLI option always overrides the value printed for the field, see the
+//! [`metadata`] module for the option of each field.
+//!
+//! [`metadata`]: ../metadata/index.html
+
+use crate::metadata::{self, FIELDS};
+use crate::Result;
+
+use cargo_metadata::Package;
+
+use log::{debug, warn};
+
+use serde_json::Value;
+
+use std::path::PathBuf;
+
+/// The source of a value from the `[package.metadata.wix]` section.
+const PACKAGE_SOURCE: &str = "package";
+
+/// The source of a value from the `[workspace.metadata.wix]` section.
+const WORKSPACE_SOURCE: &str = "workspace";
+
+/// The source of a field that is not set, so the default value is used.
+const DEFAULT_SOURCE: &str = "default";
+
+/// A builder for creating an execution context to print the configuration of
+/// a package.
+#[derive(Debug, Clone)]
+pub struct Builder<'a> {
+    input: Option<&'a str>,
+    package: Option<&'a str>,
+}
+
+impl<'a> Builder<'a> {
+    /// Creates a new `Builder` instance.
+    pub fn new() -> Self {
+        Builder {
+            input: None,
+            package: None,
+        }
+    }
+
+    /// Sets the path to a package's manifest (Cargo.toml).
+    ///
+    /// The default is to use the current working directory if a Cargo.toml
+    /// file is found.
+    pub fn input(&mut self, i: Option<&'a str>) -> &mut Self {
+        self.input = i;
+        self
+    }
+
+    /// Sets the package within a workspace to print the configuration of.
+    pub fn package(&mut self, p: Option<&'a str>) -> &mut Self {
+        self.package = p;
+        self
+    }
+
+    /// Builds an execution context to print the configuration.
+    pub fn build(&mut self) -> Execution {
+        Execution {
+            input: self.input.map(PathBuf::from),
+            package: self.package.map(String::from),
+        }
+    }
+}
+
+impl<'a> Default for Builder<'a> {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
+
+/// A context for printing the configuration of a package.
+#[derive(Debug)]
+pub struct Execution {
+    input: Option<PathBuf>,
+    package: Option<String>,
+}
+
+impl Execution {
+    /// Prints the value and the source of each supported field.
+    ///
+    /// Each field is printed to `STDOUT` on its own line with the source, which
+    /// is either the `package` or the `workspace` metadata section, or
+    /// `default` if the field is not set. A warning is displayed for each
+    /// field that is not supported or has a value with the wrong type.
+    pub fn run(self) -> Result<()> {
+        debug!("input = {:?}", self.input);
+        debug!("package = {:?}", self.package);
+        let manifest = crate::manifest(self.input.as_ref())?;
+        let package = crate::package(&manifest, self.package.as_deref())?;
+        let source = if manifest.workspace_members.contains(&package.id) {
+            PACKAGE_SOURCE
+        } else {
+            WORKSPACE_SOURCE
+        };
+        debug!("source = {:?}", source);
+        for problem in metadata::problems(&package.metadata) {
+            warn!("{}", problem);
+        }
+        println!("{:<30} {:<10} VALUE", "FIELD", "SOURCE");
+        for (name, source, value) in values(&package, source) {
+            let line = format!("{name:<30} {source:<10} {value}");
+            println!("{}", line.trim_end());
+        }
+        Ok(())
+    }
+}
+
+impl Default for Execution {
+    fn default() -> Self {
+        Builder::new().build()
+    }
+}
+
+/// Gets the name, source, and displayed value of each supported field.
+///
+/// The value of a field that is not set is empty, and the source is
+/// `default`.
+fn values(package: &Package, source: &'static str) -> Vec<(&'static str, &'static str, String)> {
+    let section = package.metadata.get("wix").and_then(|w| w.as_object());
+    FIELDS
+        .iter()
+        .map(|field| match section.and_then(|s| s.get(field.name)) {
+            Some(Value::String(s)) => (field.name, source, s.clone()),
+            Some(value) => (field.name, source, value.to_string()),
+            None => (field.name, DEFAULT_SOURCE, String::new()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::setup_project;
+
+    mod builder {
+        use super::*;
+
+        #[test]
+        fn input_works() {
+            const EXPECTED: &str = "C:\\Example";
+            let mut actual = Builder::new();
+            actual.input(Some(EXPECTED));
+            assert_eq!(actual.input, Some(EXPECTED));
+        }
+
+        #[test]
+        fn package_works() {
+            const EXPECTED: &str = "Example";
+            let mut actual = Builder::new();
+            actual.package(Some(EXPECTED));
+            assert_eq!(actual.package, Some(EXPECTED));
+        }
+    }
+
+    mod execution {
+        use super::*;
+
+        #[test]
+        fn run_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                culture = "fr-FR"
+            "#;
+            let project = setup_project(MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            assert!(Builder::new().input(input.to_str()).build().run().is_ok());
+        }
+    }
+
+    #[test]
+    fn values_works() {
+        const MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+
+            [package.metadata.wix]
+            culture = "fr-FR"
+            eula = false
+            include = ["Extra.wxs"]
+        "#;
+        let project = setup_project(MANIFEST);
+        let input = project.path().join("Cargo.toml");
+        let manifest = crate::manifest(Some(&input)).unwrap();
+        let package = crate::package(&manifest, None).unwrap();
+        let actual = values(&package, PACKAGE_SOURCE);
+        assert_eq!(actual.len(), FIELDS.len());
+        let find = |name| actual.iter().find(|(n, _, _)| *n == name).unwrap().clone();
+        assert_eq!(
+            find("culture"),
+            ("culture", PACKAGE_SOURCE, String::from("fr-FR"))
+        );
+        assert_eq!(
+            find("eula"),
+            ("eula", PACKAGE_SOURCE, String::from("false"))
+        );
+        assert_eq!(
+            find("include"),
+            ("include", PACKAGE_SOURCE, String::from("[\"Extra.wxs\"]"))
+        );
+        assert_eq!(find("banner"), ("banner", DEFAULT_SOURCE, String::new()));
+    }
+}
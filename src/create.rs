@@ -22,91 +22,296 @@
 //! the root of the package's manifest (Cargo.toml). A different WiX Source file
 //! can be set with the `input` method using the `Builder` struct.
 
+use crate::command_line;
+use crate::icon;
+use crate::pe;
+use crate::read_wxs_source;
+use crate::status_with_timeout;
 use crate::Cultures;
 use crate::Error;
 use crate::Result;
 use crate::WixArch;
 use crate::BINARY_FOLDER_NAME;
 use crate::CARGO;
+use crate::CHANGELOG_FILE_NAME;
 use crate::EXE_FILE_EXTENSION;
+use crate::GIT;
 use crate::MSIEXEC;
 use crate::MSI_FILE_EXTENSION;
+use crate::PRODUCT_ICON_VARIABLE;
+use crate::TASKLIST;
 use crate::WIX;
 use crate::WIX_COMPILER;
 use crate::WIX_LINKER;
 use crate::WIX_OBJECT_FILE_EXTENSION;
 use crate::WIX_PATH_KEY;
 use crate::WIX_SOURCE_FILE_EXTENSION;
+use crate::WIX_TORCH;
+use crate::ZIP_FILE_EXTENSION;
 
-use log::{debug, info, trace, warn};
+use log::{debug, error, info, trace, warn};
 
 use semver::Version;
 
+use std::cell::RefCell;
 use std::convert::TryFrom;
 use std::env;
-use std::ffi::OsString;
+use std::ffi::{OsStr, OsString};
 use std::fmt;
-use std::io::{ErrorKind, Read};
+use std::io::{ErrorKind, Read, Write};
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::process::{Command, ExitStatus, Stdio};
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
 use cargo_metadata::Package;
 
+use chrono::Utc;
+
 use rustc_cfg::Cfg;
 
 use serde_json::Value;
 
+use sha2::{Digest, Sha256};
+
+use uuid::Uuid;
+
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// The value for the culture to derive it from the locale of the system.
+pub(crate) const AUTO_CULTURE: &str = "auto";
+
+/// The format of the build time for the build metadata, which is ISO 8601 in
+/// UTC.
+const BUILD_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%SZ";
+
+/// The argument for the WiX Toolset applications to suppress the logo.
+const NO_LOGO: &str = "-nologo";
+
+/// The maximum number of characters of the changelog entry passed to the
+/// compiler, which is displayed as the comments in the Add/Remove Programs
+/// control panel.
+const MAX_CHANGELOG_LENGTH: usize = 255;
+
+/// The attributes rendered from fields of the package's manifest (Cargo.toml),
+/// with a hint for fixing an empty value.
+const MANIFEST_ATTRIBUTES: &[(&str, &str)] = &[
+    (
+        "Description",
+        "the 'description' field to the package's manifest",
+    ),
+    (
+        "Manufacturer",
+        "the 'authors' field to the package's manifest",
+    ),
+    ("Name", "the 'name' field to the package's manifest"),
+];
+
+/// The name used in an error for a bundle (exe) installer that fails, since
+/// the bundle is launched directly instead of with `msiexec`.
+const BUNDLE_APPLICATION: &str = "bundle";
+
+/// The file name for the hash of the inputs of the last installer, which is
+/// stored alongside the WiX object files.
+const INPUT_HASH_FILE_NAME: &str = "installer.inputs";
+
+/// The file name for the product icon extracted from the main binary.
+const PRODUCT_ICON_FILE_NAME: &str = "ProductIcon.ico";
+
+/// The letters allowed in the value for the `REINSTALLMODE` property.
+const REINSTALL_MODE_FLAGS: &str = "acdemopsuv";
+
+/// The file extension of a Windows installer transform.
+const TRANSFORM_FILE_EXTENSION: &str = "mst";
+
 /// A builder for running the `cargo wix` subcommand.
 #[derive(Debug, Clone)]
 pub struct Builder<'a> {
+    allow_unpublished: bool,
+    append_args: bool,
+    arp_size: Option<&'a str>,
     bin_path: Option<&'a str>,
+    build_command: Option<&'a str>,
+    bundle_upgrade_guid: Option<&'a str>,
+    bundle_version: Option<&'a str>,
     capture_output: bool,
+    changelog: Option<&'a str>,
+    check_localization: bool,
+    checksum_manifest: Option<&'a str>,
     compiler_args: Option<Vec<&'a str>>,
+    component_group_refs: Option<Vec<&'a str>>,
     culture: Option<&'a str>,
     debug_build: bool,
     profile: Option<&'a str>,
     debug_name: bool,
+    dry_run: bool,
+    dump_defines: bool,
+    dump_toolset_commands: bool,
+    dump_wixobj: Option<&'a str>,
+    emit_relative_manifest_path: bool,
+    features: Option<Vec<&'a str>>,
+    format: Option<&'a str>,
+    generate_transforms: Option<Vec<&'a str>>,
+    include_build_metadata: bool,
     includes: Option<Vec<&'a str>>,
+    incremental: bool,
     input: Option<&'a str>,
     linker_args: Option<Vec<&'a str>>,
     locale: Option<&'a str>,
+    metadata_output: Option<&'a str>,
     name: Option<&'a str>,
+    no_arch_in_name: bool,
     no_build: bool,
+    no_default_features: bool,
+    no_default_wix_folder: bool,
+    no_install_if_running: bool,
+    no_overwrite_increment: bool,
     target_bin_dir: Option<&'a str>,
     install: bool,
+    keep_going: bool,
     output: Option<&'a str>,
+    output_dir: Option<&'a str>,
+    output_layout: Option<&'a str>,
     package: Option<&'a str>,
-    target: Option<&'a str>,
+    preprocess_only: Option<&'a str>,
+    print_wxs: bool,
+    product_language: Option<&'a str>,
+    progress: Option<Progress>,
+    properties: Option<Vec<&'a str>>,
+    quiet_tools: bool,
+    reinstall_mode: Option<&'a str>,
+    retain_symbols: Option<&'a str>,
+    strict: bool,
+    strip: bool,
+    targets: Option<Vec<&'a str>>,
+    timeout: Option<&'a str>,
+    verify_manifest: bool,
     version: Option<&'a str>,
+    wix_root: Option<&'a str>,
+    wxs_sources: Option<Vec<&'a str>>,
+    wxs_variable_check: bool,
 }
 
 impl<'a> Builder<'a> {
     /// Creates a new `Builder` instance.
     pub fn new() -> Self {
         Builder {
+            allow_unpublished: false,
+            append_args: false,
+            arp_size: None,
             bin_path: None,
+            build_command: None,
+            bundle_upgrade_guid: None,
+            bundle_version: None,
             capture_output: true,
+            changelog: None,
+            check_localization: false,
+            checksum_manifest: None,
             compiler_args: None,
+            component_group_refs: None,
             culture: None,
             debug_build: false,
             profile: None,
             debug_name: false,
+            dry_run: false,
+            dump_defines: false,
+            dump_toolset_commands: false,
+            dump_wixobj: None,
+            emit_relative_manifest_path: false,
+            features: None,
+            format: None,
+            generate_transforms: None,
+            include_build_metadata: false,
             includes: None,
+            incremental: false,
             input: None,
             linker_args: None,
             locale: None,
+            metadata_output: None,
             name: None,
+            no_arch_in_name: false,
             no_build: false,
+            no_default_features: false,
+            no_default_wix_folder: false,
+            no_install_if_running: false,
+            no_overwrite_increment: false,
             install: false,
+            keep_going: false,
             target_bin_dir: None,
             output: None,
+            output_dir: None,
+            output_layout: None,
             package: None,
-            target: None,
+            preprocess_only: None,
+            print_wxs: false,
+            product_language: None,
+            progress: None,
+            properties: None,
+            quiet_tools: false,
+            reinstall_mode: None,
+            retain_symbols: None,
+            strict: false,
+            strip: false,
+            targets: None,
+            timeout: None,
+            verify_manifest: false,
             version: None,
+            wix_root: None,
+            wxs_sources: None,
+            wxs_variable_check: false,
         }
     }
 
+    /// Silences the warning for creating an installer for a package that is
+    /// not published.
+    ///
+    /// A warning is displayed if the package has `publish = false` in its
+    /// manifest (Cargo.toml), since such a package is often an internal or
+    /// experimental crate, and creating an installer for it might be a
+    /// mistake, such as selecting the wrong member of a workspace. The
+    /// installer is still created. If `true`, the warning is not displayed.
+    /// This can also be enabled with the `allow-unpublished` field in the
+    /// `[package.metadata.wix]` section.
+    pub fn allow_unpublished(&mut self, a: bool) -> &mut Self {
+        self.allow_unpublished = a;
+        self
+    }
+
+    /// Appends the compiler and linker arguments to the arguments in the
+    /// package's manifest (Cargo.toml).
+    ///
+    /// By default, the arguments from the [`compiler_args`] and
+    /// [`linker_args`] methods replace the `compiler-args` and `linker-args`
+    /// fields in the `[package.metadata.wix]` section. If `true`, the
+    /// arguments from the fields are used first and the arguments from the
+    /// methods are added after them, so a one-off argument can be added
+    /// without repeating the arguments from the fields. This can also be
+    /// enabled with the `append-args` field.
+    ///
+    /// [`compiler_args`]: #method.compiler_args
+    /// [`linker_args`]: #method.linker_args
+    pub fn append_args(&mut self, a: bool) -> &mut Self {
+        self.append_args = a;
+        self
+    }
+
+    /// Sets the estimated size of the installed application, in kilobytes,
+    /// that is displayed in the Add/Remove Programs control panel.
+    ///
+    /// The size is passed to the compiler (candle.exe) as the `ArpSize`
+    /// variable, which sets the `ARPSIZE` property in a WiX Source (wxs) file
+    /// generated with the _init_ subcommand. The default is to estimate the
+    /// size from the files referenced by the `Source` attribute of each `File`
+    /// element in the WiX Source (wxs) files, such as the binaries and the
+    /// license. This overrides the `arp-size` field in the
+    /// `[package.metadata.wix]` section.
+    pub fn arp_size(&mut self, a: Option<&'a str>) -> &mut Self {
+        self.arp_size = a;
+        self
+    }
+
     /// Sets the path to the WiX Toolset's `bin` folder.
     ///
     /// The WiX Toolset's `bin` folder should contain the needed `candle.exe`
@@ -118,6 +323,58 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets a command to build the binaries instead of the `cargo build`
+    /// command.
+    ///
+    /// The command is run with the system shell, i.e. `cmd /C` on Windows and
+    /// `sh -c` on other platforms, from the folder containing the package's
+    /// manifest (Cargo.toml). The `CARGO_TARGET_DIR`, `CARGO_WIX_PROFILE`,
+    /// `CARGO_WIX_TARGET`, and `CARGO_WIX_TARGET_BIN_DIR` environment variables
+    /// are set for the command, and the creation of the installer fails if the
+    /// command exits with a non-zero code. The command is run once for each
+    /// target. The value is ignored if the `no_build` method is set to `true`.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn build_command(&mut self, b: Option<&'a str>) -> &mut Self {
+        self.build_command = b;
+        self
+    }
+
+    /// Sets the upgrade code for a bundle-based installer.
+    ///
+    /// The value should be a GUID in the hyphenated, uppercase format. It is
+    /// passed to the WiX compiler (candle.exe) as the `BundleUpgradeCode`
+    /// variable, so it can be used with the `UpgradeCode` attribute of the
+    /// `Bundle` tag in a WiX Source (wxs) file, i.e.
+    /// `UpgradeCode='$(var.BundleUpgradeCode)'`, instead of manually editing the
+    /// GUID into the file. The variable is not defined if a bundle upgrade code
+    /// is not specified.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn bundle_upgrade_guid(&mut self, u: Option<&'a str>) -> &mut Self {
+        self.bundle_upgrade_guid = u;
+        self
+    }
+
+    /// Sets the version for a bundle-based installer.
+    ///
+    /// The version should be in the "Major.Minor.Patch" notation. It is
+    /// converted to the format required by the WiX Toolset in the same way as
+    /// the installer version and passed to the WiX compiler (candle.exe) as the
+    /// `BundleVersion` variable, so it can be used with the `Version` attribute
+    /// of the `Bundle` tag in a WiX Source (wxs) file, i.e.
+    /// `Version='$(var.BundleVersion)'`. The variable is not defined if a
+    /// bundle version is not specified.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn bundle_version(&mut self, v: Option<&'a str>) -> &mut Self {
+        self.bundle_version = v;
+        self
+    }
+
     /// Enables or disables capturing of the output from the builder (`cargo`),
     /// compiler (`candle`), linker (`light`), and signer (`signtool`).
     ///
@@ -128,22 +385,113 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the path to a changelog.
+    ///
+    /// The first entry of the section of the changelog with a heading that
+    /// matches the version of the installer is extracted and passed to the WiX
+    /// compiler (candle.exe) as the `Changelog` variable. The WiX Source (wxs)
+    /// file can use the variable to set the `ARPCOMMENTS` property, which is
+    /// displayed in the Add/Remove Programs (ARP) control panel. The default
+    /// is to use the `changelog` field in the `[package.metadata.wix]`
+    /// section, or to not use a changelog. The variable is not defined if a
+    /// matching section cannot be found.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn changelog(&mut self, c: Option<&'a str>) -> &mut Self {
+        self.changelog = c;
+        self
+    }
+
+    /// Reports the WixUI strings that are displayed in English.
+    ///
+    /// If `true`, the common strings of the WixUI dialogs that are not in the
+    /// localization (wxl) file set with the [`locale`] method are reported
+    /// with a warning, or an error if the [`strict`] method is used, before
+    /// the installer is created. Strings are only displayed in English if the
+    /// WixUI extension does not have localized strings for the culture, see
+    /// the [`Cultures::is_wixui_localized`] method. The default is to not
+    /// check the localization.
+    ///
+    /// [`locale`]: #method.locale
+    /// [`strict`]: #method.strict
+    /// [`Cultures::is_wixui_localized`]: ../enum.Cultures.html#method.is_wixui_localized
+    pub fn check_localization(&mut self, c: bool) -> &mut Self {
+        self.check_localization = c;
+        self
+    }
+
+    /// Sets a path to a file for the SHA-256 checksum of each created file.
+    ///
+    /// After all of the installers are created, one line is written to the
+    /// file for each installer (msi or exe), transform (mst), or zip archive
+    /// created in the run, in the `<sha256>  <path>` format of the `sha256sum`
+    /// tool, such as a `SHA256SUMS` file for a release. The path of a created
+    /// file is relative to the folder of the checksum file if it is within the
+    /// folder, so the checksums can be checked with `sha256sum -c` from the
+    /// folder. An installer that is skipped because it is up to date is
+    /// included. The folder is created if it does not exist. The file is not
+    /// written for a dry run or if no files are created. The default is to not
+    /// write the checksums.
+    pub fn checksum_manifest(&mut self, c: Option<&'a str>) -> &mut Self {
+        self.checksum_manifest = c;
+        self
+    }
+
     /// Adds an argument to the compiler command.
     ///
     /// This "passes" the argument directly to the WiX compiler (candle.exe).
     /// See the help documentation for the WiX compiler for information about
     /// valid options and flags.
+    ///
+    /// This value will override the `compiler-args` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// unless the [`append_args`] method is used.
+    ///
+    /// [`append_args`]: #method.append_args
     pub fn compiler_args(&mut self, c: Option<Vec<&'a str>>) -> &mut Self {
         self.compiler_args = c;
         self
     }
 
+    /// Adds component groups to the main feature of the installer.
+    ///
+    /// The IDs are passed to the WiX compiler (candle.exe) as the
+    /// `ComponentGroupRefs` variable, separated by semicolons, and the WiX
+    /// Source (wxs) file uses the variable to add a `ComponentGroupRef` tag for
+    /// each ID to the main feature. This is useful for installing the
+    /// components of a fragment that is generated, for example with the WiX
+    /// Toolset harvester (heat.exe), and included with the `includes` method
+    /// without modifying the WiX Source (wxs) file. Each ID must be a valid WiX
+    /// identifier.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn component_group_refs(&mut self, c: Option<Vec<&'a str>>) -> &mut Self {
+        self.component_group_refs = c;
+        self
+    }
+
     /// Sets the culture to use with the linker (light.exe) for building a
     /// localized installer.
     ///
+    /// The default is the `en-US` culture. If the value is `auto`, then the
+    /// culture is derived from the locale of the system, i.e. the `LC_ALL`,
+    /// `LC_MESSAGES`, or `LANG` environment variable, or the user interface
+    /// culture on Windows if none of the environment variables are set. The
+    /// `en-US` culture is used if the locale of the system is not a supported
+    /// culture.
+    ///
+    /// The value can also be a comma-separated list of cultures, such as
+    /// `fr-FR,de-DE`. The first culture is the culture of the installer, and a
+    /// transform is generated for each of the other cultures as if they were
+    /// set with the [`generate_transforms`] method.
+    ///
     /// This value will override any defaults and skip looking for a value in
     /// the `[package.metadata.wix]` section of the package's manifest
     /// (Cargo.toml).
+    ///
+    /// [`generate_transforms`]: Builder::generate_transforms
     pub fn culture(&mut self, c: Option<&'a str>) -> &mut Self {
         self.culture = c;
         self
@@ -177,6 +525,153 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Skips running the commands and any other side effects.
+    ///
+    /// If `true`, the commands for building the binaries, compiling and
+    /// linking the installer, and launching the installer are assembled as
+    /// usual, but the [`Execution::run`] method prints each command to stdout
+    /// instead of running it. Nothing is written to the disk, including the
+    /// WiX Object (wixobj) files, the installer, and the zip archive, which is
+    /// useful for checking the options without the WiX Toolset. See the
+    /// [`Execution::commands`] method for getting the commands instead of
+    /// printing them. The default is to run the commands.
+    pub fn dry_run(&mut self, d: bool) -> &mut Self {
+        self.dry_run = d;
+        self
+    }
+
+    /// Prints the variables defined for the WiX compiler instead of creating
+    /// the installer.
+    ///
+    /// If `true`, each variable that is passed to the WiX compiler (candle.exe)
+    /// with the `-d` argument is printed to stdout as `NAME=VALUE`, in the
+    /// order of the arguments, including the variables from the properties and
+    /// compiler arguments. Nothing is built, compiled, or linked, like a dry
+    /// run. If installers are created for multiple targets, then the variables
+    /// for each target are preceded by a line with the target triple. The
+    /// default is to create the installer.
+    pub fn dump_defines(&mut self, d: bool) -> &mut Self {
+        self.dump_defines = d;
+        self
+    }
+
+    /// Prints each command that is run to create the installer.
+    ///
+    /// If `true`, each external command, i.e. the build, the WiX Toolset
+    /// compiler (candle.exe), linker (light.exe), and transform tool
+    /// (torch.exe), and the installer if it is installed, is printed to stderr
+    /// right before it runs as a line that can be copied and pasted into a
+    /// shell, including the working directory and the environment variables
+    /// set for the command. Nothing is printed for a dry run. The default is to
+    /// not print the commands.
+    pub fn dump_toolset_commands(&mut self, d: bool) -> &mut Self {
+        self.dump_toolset_commands = d;
+        self
+    }
+
+    /// Sets a folder to copy the WiX Object (wixobj) files to for inspection.
+    ///
+    /// After the WiX compiler (candle.exe) runs, the WiX Object (wixobj) files
+    /// are copied to the folder, which is created if it does not exist, before
+    /// the WiX linker (light.exe) runs. The copies are kept regardless of any
+    /// later steps, which is useful for troubleshooting linker errors. If
+    /// installers are created for multiple targets, then the files for each
+    /// target are copied to a subfolder named after the target triple. The
+    /// default is to not copy the files.
+    pub fn dump_wixobj(&mut self, d: Option<&'a str>) -> &mut Self {
+        self.dump_wixobj = d;
+        self
+    }
+
+    /// Emits the paths defined for the WiX compiler (candle.exe) relative to
+    /// the folder of the package's manifest (Cargo.toml).
+    ///
+    /// If `true`, the `CargoTargetDir`, `CargoTargetBinDir`, and
+    /// `ProductIcon` variables are relative paths, such as `target\release`,
+    /// instead of absolute paths, so the compiled and linked files do not
+    /// contain machine-specific paths. The WiX linker (light.exe) resolves the
+    /// relative paths from the folder of the manifest. A path on a different
+    /// drive than the manifest is left absolute with a warning. The default
+    /// is to emit absolute paths.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn emit_relative_manifest_path(&mut self, e: bool) -> &mut Self {
+        self.emit_relative_manifest_path = e;
+        self
+    }
+
+    /// Sets the Cargo features to enable when building the package.
+    ///
+    /// Each value is passed to the `cargo build` command with the `--features`
+    /// option. The `CargoFeature_<FEATURE>` variable is defined for the WiX
+    /// compiler (candle.exe) for each enabled feature of the package, including
+    /// the default features and the features enabled by other features, so
+    /// that the WiX Source (wxs) file can include optional features in the
+    /// installer. Hyphens in the name of a feature are replaced with
+    /// underscores in the name of the variable.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn features(&mut self, f: Option<Vec<&'a str>>) -> &mut Self {
+        self.features = f;
+        self
+    }
+
+    /// Sets the format of the output.
+    ///
+    /// The `msi` format creates an installer with the WiX Toolset. The `zip`
+    /// format skips the WiX Toolset and creates a portable zip archive of the
+    /// files in the WiX Source (wxs) files instead, such as the binaries and
+    /// the license. The binaries are in a `bin` folder within the archive, like
+    /// the installed product. The archive is written to the same destination
+    /// as the installer but with the `zip` file extension. The default is
+    /// `msi`.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn format(&mut self, f: Option<&'a str>) -> &mut Self {
+        self.format = f;
+        self
+    }
+
+    /// Sets the additional cultures to generate transforms (mst) for.
+    ///
+    /// After the installer is linked for the culture from the [`culture`]
+    /// method, the WiX Object (wixobj) files are linked again for each
+    /// additional culture, and a language transform is generated from the
+    /// differences with the WiX Toolset's `torch` application. Each transform
+    /// is written next to the installer with the culture appended to the
+    /// name of the installer, e.g. `Example-0.1.0-x86_64-fr-FR.mst`, and it
+    /// is not embedded in the installer. A transform is applied with the
+    /// `TRANSFORMS` property of `msiexec`. Transforms can only be generated
+    /// for a Windows installer (msi). The default is to not generate any
+    /// transforms.
+    ///
+    /// [`culture`]: Builder::culture
+    pub fn generate_transforms(&mut self, g: Option<Vec<&'a str>>) -> &mut Self {
+        self.generate_transforms = g;
+        self
+    }
+
+    /// Embeds build metadata in the installer.
+    ///
+    /// If `true`, the short hash of the current git commit and the time of the
+    /// build, in UTC and the ISO 8601 format, are passed to the WiX compiler
+    /// (candle.exe) as the `GitHash` and `BuildTime` variables, respectively.
+    /// The WiX Source (wxs) file can use the variables to set the
+    /// `ARPCOMMENTS` property, which is displayed in the Add/Remove Programs
+    /// (ARP) control panel. The `GitHash` variable is not defined if the
+    /// package is not in a git repository or git is not available. The
+    /// default is to not embed any build metadata.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn include_build_metadata(&mut self, i: bool) -> &mut Self {
+        self.include_build_metadata = i;
+        self
+    }
+
     /// Adds multiple WiX Source (wxs) files to the creation of an installer.
     ///
     /// By default, any `.wxs` file located in the project's `wix` folder will
@@ -191,6 +686,26 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Skips compiling and linking the installer if its inputs are unchanged.
+    ///
+    /// If `true`, then a hash of the inputs is computed after building the
+    /// binaries and compared to the hash recorded for the last installer in a
+    /// sidecar file in the folder for the WiX object files, i.e.
+    /// `target\wix\installer.inputs`. If the hashes match and the installer
+    /// exists, then the WiX Toolset compiler (candle.exe) and linker
+    /// (light.exe) are not run. The inputs are the version of cargo-wix, the
+    /// compiler and linker arguments, except for the build time, the culture,
+    /// and the contents of the WiX Source (wxs) files, the files they include,
+    /// the files they reference, such as the license, icon, and images, the
+    /// WiX localization (wxl) file, and the package's binaries. The default is to always compile and link the installer.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn incremental(&mut self, i: bool) -> &mut Self {
+        self.incremental = i;
+        self
+    }
+
     /// Sets the path to a package's manifest (Cargo.toml) file.
     ///
     /// A package's manifest is used to create an installer. If no path is
@@ -210,6 +725,12 @@ impl<'a> Builder<'a> {
     /// This "passes" the argument directly to the WiX linker (light.exe). See
     /// the help documentation for the WiX compiler for information about valid
     /// options and flags.
+    ///
+    /// This value will override the `linker-args` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// unless the [`append_args`] method is used.
+    ///
+    /// [`append_args`]: #method.append_args
     pub fn linker_args(&mut self, l: Option<Vec<&'a str>>) -> &mut Self {
         self.linker_args = l;
         self
@@ -230,6 +751,23 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the path to write the resolved configuration of the installer to.
+    ///
+    /// After the installer is created, a JSON file is written with the
+    /// settings that produced it, such as the name, version, target,
+    /// architecture, culture, WiX Source (wxs) files, compiler variables, and
+    /// the paths of the WiX Toolset applications, so two builds can be compared
+    /// or audited. If the path is a folder, i.e. an existing folder or a path
+    /// with a trailing slash, then the file is named after the installer with
+    /// the `.json` file extension, such as `Example-0.1.0-x86_64.msi.json`. A
+    /// path to a file cannot be used when creating installers for multiple
+    /// targets. The file is not written for a dry run. The default is to not
+    /// write the configuration.
+    pub fn metadata_output(&mut self, m: Option<&'a str>) -> &mut Self {
+        self.metadata_output = m;
+        self
+    }
+
     /// Sets the name.
     ///
     /// The default is to use the `name` field under the `[package]` section of
@@ -254,6 +792,22 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Excludes the architecture from the file name of the installer.
+    ///
+    /// If `true`, the file name of the installer is `{name}-{version}.msi`
+    /// instead of `{name}-{version}-{arch}.msi`, which is useful for a product
+    /// that is only distributed for a single architecture. This cannot be
+    /// used when creating installers for multiple targets because the
+    /// installers would have the same name. The default is to include the
+    /// architecture.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn no_arch_in_name(&mut self, n: bool) -> &mut Self {
+        self.no_arch_in_name = n;
+        self
+    }
+
     /// Skips the building of the project with the release profile.
     ///
     /// If `true`, the project will _not_ be built using the release profile,
@@ -262,18 +816,91 @@ impl<'a> Builder<'a> {
     /// building the project is more involved or is handled in a separate
     /// process.
     ///
+    /// If the [`target_bin_dir`] method is not used and the main binary does
+    /// not exist in the expected folder, then the most recently modified binary
+    /// with the same name in the target folder is used instead, with a warning.
+    ///
     /// This value will override any default and skip looking for a value in the
     /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    ///
+    /// [`target_bin_dir`]: #method.target_bin_dir
     pub fn no_build(&mut self, n: bool) -> &mut Self {
         self.no_build = n;
         self
     }
 
+    /// Disables the default features of the package.
+    ///
+    /// If `true`, the `--no-default-features` flag is passed to the `cargo
+    /// build` command and the default features are not considered enabled when
+    /// defining the `CargoFeature_<FEATURE>` variables for the WiX compiler
+    /// (candle.exe). The default is to enable the default features.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn no_default_features(&mut self, n: bool) -> &mut Self {
+        self.no_default_features = n;
+        self
+    }
+
+    /// Skips collecting the WiX Source (wxs) files from the default `wix`
+    /// folder.
+    ///
+    /// If `true`, only the WiX Source (wxs) files explicitly included with the
+    /// `includes` method, or the `include` field in the `[package.metadata.wix]`
+    /// section, are used to create the installer. This is useful if the `wix`
+    /// folder contains WiX Source (wxs) files that should not be a part of the
+    /// installer. The default is to include all `.wxs` files in the `wix`
+    /// folder.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn no_default_wix_folder(&mut self, n: bool) -> &mut Self {
+        self.no_default_wix_folder = n;
+        self
+    }
+
+    /// Skips running the installer if any of the package's binaries are
+    /// running.
+    ///
+    /// If `true` and the installer is run after it is created with the
+    /// `install` method, then the running processes are checked for the file
+    /// name of each binary of the package before the installer is launched. An
+    /// error occurs, instead of launching the installer, if any of the binaries
+    /// are running, since installing over a running binary can fail or require
+    /// a reboot. The check is only performed on Windows. The default is to
+    /// launch the installer unconditionally.
+    pub fn no_install_if_running(&mut self, n: bool) -> &mut Self {
+        self.no_install_if_running = n;
+        self
+    }
+
+    /// Keeps an existing installer instead of overwriting it.
+    ///
+    /// If `true` and a file already exists at the destination of the
+    /// installer, then `-1`, `-2`, etc. is appended to the file stem of the
+    /// installer until a name that does not exist is found. This keeps the
+    /// installers from previous runs, which is useful for comparing
+    /// successive installers. The default is to overwrite an existing
+    /// installer.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn no_overwrite_increment(&mut self, n: bool) -> &mut Self {
+        self.no_overwrite_increment = n;
+        self
+    }
+
     /// Specifies that binaries should be sourced from the given directory.
     ///
     /// Specifically this sets `CargoTargetBinDir` in wxs templates. It is
     /// intended to be combined with `no_build(true)` to let another tool
     /// orchestrate cargo-wix and handle the builds for it.
+    ///
+    /// Each binary of the package, i.e. each `[[bin]]` target, must exist in
+    /// the folder before the installer is compiled. An error that lists every
+    /// missing binary occurs otherwise, instead of a failure from the compiler
+    /// (candle.exe) for a missing file.
     pub fn target_bin_dir(&mut self, p: Option<&'a str>) -> &mut Self {
         self.target_bin_dir = p;
         self
@@ -281,9 +908,12 @@ impl<'a> Builder<'a> {
 
     /// Runs the installer after creating it.
     ///
-    /// If `true`, the MSI installer will be created and then launched. This will
+    /// If `true`, the installer will be created and then launched. This will
     /// automatically open the installation wizard for the project and allow the
-    /// user to install it.
+    /// user to install it. A MSI installer is launched with `msiexec`, while a
+    /// bundle (exe) installer is launched directly. An error occurs if
+    /// installers are created for multiple targets, since only one installer
+    /// can be launched.
     ///
     /// This value will override any default and skip looking for a value in the
     /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
@@ -292,6 +922,18 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Continues creating installers for the remaining targets if creating the
+    /// installer for one target fails.
+    ///
+    /// This only applies if multiple targets are set with the `targets`
+    /// method. The default is to stop at the first failure. If `true`, an
+    /// error is still returned after all targets have been attempted if any of
+    /// them failed.
+    pub fn keep_going(&mut self, k: bool) -> &mut Self {
+        self.keep_going = k;
+        self
+    }
+
     /// Sets the output file and destination.
     ///
     /// The default is to create a MSI file with the
@@ -312,6 +954,39 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the destination folder for the installer.
+    ///
+    /// Unlike the [`output`] method, the path is always a folder, even if it
+    /// does not exist and does not have a trailing slash, and the installer
+    /// has the default `<product-name>-<version>-<arch>.msi` file name. The
+    /// folder is created if it does not exist. This takes precedence over the
+    /// [`output`] method and the `output` field in the `[package.metadata.wix]`
+    /// section of the package's manifest (Cargo.toml).
+    ///
+    /// [`output`]: #method.output
+    pub fn output_dir(&mut self, o: Option<&'a str>) -> &mut Self {
+        self.output_dir = o;
+        self
+    }
+
+    /// Sets the layout of the destination folder for the installers.
+    ///
+    /// The value is `flat` to write the installers directly to the destination
+    /// folder, or `per-arch` to write each installer to a subfolder named
+    /// after its WiX Toolset architecture, such as `dist\x64` and `dist\x86`.
+    /// With the `per-arch` layout, the architecture can be excluded from the
+    /// installer's name, see the [`no_arch_in_name`] method, even if
+    /// installers are created for multiple targets. The layout is not used if
+    /// the output is a file. The default is to use the `output-layout` field in
+    /// the `[package.metadata.wix]` section of the package's manifest
+    /// (Cargo.toml), or `flat` if the field is not specified.
+    ///
+    /// [`no_arch_in_name`]: #method.no_arch_in_name
+    pub fn output_layout(&mut self, o: Option<&'a str>) -> &mut Self {
+        self.output_layout = o;
+        self
+    }
+
     /// Sets the package.
     ///
     /// If the project is organized using a workspace, this selects the package
@@ -322,123 +997,596 @@ impl<'a> Builder<'a> {
         self
     }
 
-    /// Sets the build target.
+    /// Sets a folder to write the preprocessed WiX Source (wxs) files to
+    /// instead of creating the installer.
     ///
-    /// The default is to use the default target for the environment. Use this
-    /// method to change the target for the build and installer creation. The
-    /// value should be a string from the `rustc --print target-list` command.
-    /// This enables "cross-compilation" of installers similar to the
-    /// cross-compilation of Rust code, but only for Windows targets.
-    pub fn target(&mut self, v: Option<&'a str>) -> &mut Self {
-        self.target = v;
+    /// The WiX compiler (candle.exe) only runs its preprocessor, which
+    /// expands the `$(var.*)` variables and evaluates the `<?if?>`,
+    /// `<?include?>`, etc. directives, with the same variables as a normal
+    /// build. Each preprocessed file is written to the folder, which is
+    /// created if it does not exist, with the same file name as its source,
+    /// and nothing is linked. If installers are created for multiple
+    /// targets, then the files for each target are written to a subfolder
+    /// named after the target triple. The default is to create the installer.
+    pub fn preprocess_only(&mut self, p: Option<&'a str>) -> &mut Self {
+        self.preprocess_only = p;
         self
     }
 
-    /// Sets the version.
+    /// Prints the WiX Source (wxs) files before they are compiled.
     ///
-    /// This overrides the `version` field of the package's manifest
-    /// (Cargo.toml). The version should be in the "Major.Minor.Patch" notation.
+    /// If `true`, the path and content of each WiX Source (wxs) file that is
+    /// passed to the WiX compiler (candle.exe) is printed to stdout right
+    /// before the compiler runs, including for a dry run. Each file is
+    /// preceded by an XML comment with its path. This shows exactly what is
+    /// compiled, which is useful for debugging a customized template. The
+    /// default is to not print the files.
+    pub fn print_wxs(&mut self, p: bool) -> &mut Self {
+        self.print_wxs = p;
+        self
+    }
+
+    /// Sets the product language of the installer.
+    ///
+    /// The value is either a culture, such as `fr-FR`, or a numeric Windows
+    /// locale identifier (LCID), such as `1036`. The LCID is defined as the
+    /// `ProductLanguage` variable for the WiX compiler (candle.exe), which the
+    /// `Language` attribute of the `Product` tag and the `Languages` attribute
+    /// of the `Package` tag use in the default template. This is independent
+    /// of the [`culture`] of the user interface, so an installer with an
+    /// English user interface can declare a language-neutral product with the
+    /// `0` LCID, for example. The default is the LCID of the culture.
     ///
     /// This value will override any default and skip looking for a value in the
     /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
-    pub fn version(&mut self, v: Option<&'a str>) -> &mut Self {
-        self.version = v;
+    ///
+    /// [`culture`]: Builder::culture
+    pub fn product_language(&mut self, p: Option<&'a str>) -> &mut Self {
+        self.product_language = p;
         self
     }
 
-    /// Builds a context for creating, or building, the installer.
-    pub fn build(&mut self) -> Execution {
-        Execution {
-            bin_path: self.bin_path.map(PathBuf::from),
-            capture_output: self.capture_output,
-            compiler_args: self
-                .compiler_args
-                .as_ref()
-                .map(|c| c.iter().map(|s| (*s).to_string()).collect()),
-            culture: self.culture.map(String::from),
-            debug_build: self.debug_build,
-            profile: self.profile.map(String::from),
-            debug_name: self.debug_name,
-            includes: self
-                .includes
-                .as_ref()
-                .map(|v| v.iter().map(&PathBuf::from).collect()),
-            input: self.input.map(PathBuf::from),
-            linker_args: self
-                .linker_args
-                .as_ref()
-                .map(|l| l.iter().map(|s| (*s).to_string()).collect()),
-            locale: self.locale.map(PathBuf::from),
-            name: self.name.map(String::from),
-            no_build: self.no_build,
-            target_bin_dir: self.target_bin_dir.map(PathBuf::from),
-            install: self.install,
-            output: self.output.map(String::from),
-            package: self.package.map(String::from),
-            version: self.version.map(String::from),
-            target: self.target.map(String::from),
-        }
+    /// Sets a callback that is invoked each time the creation of the installer
+    /// moves to a new stage.
+    ///
+    /// This is useful for displaying progress in an application that uses the
+    /// library, such as a Graphical User Interface (GUI), without parsing the
+    /// log statements. The callback is invoked with the [`CreateStage`] that is
+    /// about to start, and it is invoked for each target if multiple targets
+    /// are set. The default is to not report the progress.
+    ///
+    /// [`CreateStage`]: enum.CreateStage.html
+    pub fn progress<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(CreateStage) + Send + Sync + 'static,
+    {
+        self.progress = Some(Progress(Arc::new(f)));
+        self
     }
 
-    pub fn profile(&mut self, profile: Option<&'a str>) -> &mut Self {
-        self.profile = profile;
+    /// Sets the values of properties for the installer.
+    ///
+    /// Each value has the `NAME=VALUE` form and it is passed to the WiX
+    /// compiler (candle.exe) as the `-dNAME=VALUE` argument, i.e. a
+    /// preprocessor variable. The WiX Source (wxs) file sets the default value
+    /// of a property with the variable, such as `<Property Id='NAME'
+    /// Value='$(var.NAME)'/>`. The name must be a valid WiX identifier. Unlike
+    /// the [`compiler_args`] method, each value is validated before it is
+    /// passed to the compiler.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    ///
+    /// [`compiler_args`]: #method.compiler_args
+    pub fn properties(&mut self, p: Option<Vec<&'a str>>) -> &mut Self {
+        self.properties = p;
         self
     }
-}
 
-impl<'a> Default for Builder<'a> {
-    fn default() -> Self {
-        Builder::new()
+    /// Suppresses the logo, or banner, of the WiX Toolset applications.
+    ///
+    /// If `true`, the `-nologo` argument is passed to both the compiler
+    /// (candle.exe) and the linker (light.exe), unless it is already included
+    /// in the compiler or linker arguments. This reduces the noise in the
+    /// output when the output is not captured. The default is to display the
+    /// logo.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn quiet_tools(&mut self, q: bool) -> &mut Self {
+        self.quiet_tools = q;
+        self
     }
-}
+
+    /// Sets the value of the `REINSTALLMODE` property for the installer.
+    ///
+    /// The `REINSTALLMODE` property controls which files are replaced when an
+    /// existing installation is upgraded or repaired. The value is passed to
+    /// the WiX compiler (candle.exe) as the `ReinstallMode` variable, which is
+    /// used by the WiX Source (wxs) file to set the property. The default is
+    /// to not define the variable, in which case the Windows Installer default
+    /// of `omus` is used. The `omus` value only replaces a file if the
+    /// installed file has an older version, so a value like `amus` can be used
+    /// to always replace files, such as files without a version, during a
+    /// major upgrade.
+    ///
+    /// The value must only contain the letters allowed for the
+    /// `REINSTALLMODE` property: `a`, `c`, `d`, `e`, `m`, `o`, `p`, `s`, `u`,
+    /// and `v`.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn reinstall_mode(&mut self, r: Option<&'a str>) -> &mut Self {
+        self.reinstall_mode = r;
+        self
+    }
+
+    /// Sets whether the debug symbols of the installer are retained.
+    ///
+    /// The value must be either `yes` or `no`. If `yes`, then the WiX linker
+    /// (light.exe) writes the WiX debug symbols (wixpdb) file next to the
+    /// installer, which can be useful for debugging installer issues. If `no`,
+    /// then the `-spdb` option is passed to the linker to suppress the wixpdb
+    /// file. The default is `no`.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn retain_symbols(&mut self, r: Option<&'a str>) -> &mut Self {
+        self.retain_symbols = r;
+        self
+    }
+
+    /// Fails instead of falling back to a supported culture or warning about
+    /// undefined variables.
+    ///
+    /// If a culture is not supported by the WixUI extension, but a supported
+    /// culture exists for the same language, such as `fr-FR` for `fr-CA`, then
+    /// the supported culture is used with a warning. If `true`, an error
+    /// occurs instead. The undefined variables found with the
+    /// [`wxs_variable_check`] method, and binaries built for a different
+    /// architecture than the installer, are also errors instead of warnings.
+    /// The default is to fall back to the supported culture and only warn.
+    ///
+    /// [`wxs_variable_check`]: #method.wxs_variable_check
+    pub fn strict(&mut self, s: bool) -> &mut Self {
+        self.strict = s;
+        self
+    }
+
+    /// Strips the symbols from the binaries before they are packaged.
+    ///
+    /// If `true`, the `strip` setting of the Cargo profile is enabled for the
+    /// build with the `CARGO_PROFILE_<NAME>_STRIP` environment variable, which
+    /// makes the installer smaller without changing the profile in the
+    /// package's manifest (Cargo.toml). The environment variable is also set
+    /// for a custom build command. Nothing is stripped if the `no_build`
+    /// method is set to `true`. The default is to use the `strip` setting of
+    /// the profile.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn strip(&mut self, s: bool) -> &mut Self {
+        self.strip = s;
+        self
+    }
+
+    /// Sets the build target.
+    ///
+    /// The default is to use the default target for the environment. Use this
+    /// method to change the target for the build and installer creation. The
+    /// value should be a string from the `rustc --print target-list` command.
+    /// This enables "cross-compilation" of installers similar to the
+    /// cross-compilation of Rust code, but only for Windows targets.
+    pub fn target(&mut self, v: Option<&'a str>) -> &mut Self {
+        self.targets = v.map(|t| vec![t]);
+        self
+    }
+
+    /// Sets multiple build targets.
+    ///
+    /// An installer is created for each target in a single run, i.e. the
+    /// package is built, and the installer is compiled and linked, for each
+    /// target in turn. This is useful for creating installers for both the
+    /// x86 and x64 architectures at once. The installer file names include the
+    /// architecture, so the installers do not overwrite each other. If an
+    /// output is specified with the `output` method, then it must be a folder.
+    ///
+    /// This overrides any target set with the `target` method.
+    pub fn targets(&mut self, t: Option<Vec<&'a str>>) -> &mut Self {
+        self.targets = t;
+        self
+    }
+
+    /// Sets the number of seconds to wait for each external application.
+    ///
+    /// The build command, compiler (candle.exe), linker (light.exe), and
+    /// installer (msiexec.exe) are stopped if they do not finish within the
+    /// timeout, and an error is returned, so a stuck application does not hang
+    /// the creation of an installer, such as in a CI job, indefinitely. Only
+    /// the application itself is stopped, not any processes it started. The
+    /// default is no timeout.
+    pub fn timeout(&mut self, t: Option<&'a str>) -> &mut Self {
+        self.timeout = t;
+        self
+    }
+
+    /// Verifies the WiX Source (wxs) files have the values from the package's
+    /// manifest (Cargo.toml) before compiling.
+    ///
+    /// If `true`, then the WiX Source (wxs) files are checked for references
+    /// to compiler variables that are not defined, such as `$(var.Example)`,
+    /// and attributes that are rendered from fields of the package's manifest
+    /// but are empty or missing, such as the `Manufacturer` attribute from the
+    /// `authors` field. An error occurs, instead of compiling, if any problems
+    /// are found. This avoids creating an installer with blank fields in the
+    /// Add/Remove Programs control panel. The default is to not verify.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn verify_manifest(&mut self, v: bool) -> &mut Self {
+        self.verify_manifest = v;
+        self
+    }
+
+    /// Sets the version.
+    ///
+    /// This overrides the `version` field of the package's manifest
+    /// (Cargo.toml). The version should be in the "Major.Minor.Patch" notation.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn version(&mut self, v: Option<&'a str>) -> &mut Self {
+        self.version = v;
+        self
+    }
+
+    /// Sets the path to the root folder of the WiX Toolset installation.
+    ///
+    /// This is the same as the value of the `WIX` system environment variable
+    /// that is created during installation of the WiX Toolset, and the
+    /// compiler (candle.exe) and linker (light.exe) are found in its `bin`
+    /// folder. It takes precedence over the `WIX` system environment variable
+    /// but not the path to the `bin` folder from the [`bin_path`] method.
+    ///
+    /// [`bin_path`]: Builder::bin_path
+    pub fn wix_root(&mut self, w: Option<&'a str>) -> &mut Self {
+        self.wix_root = w;
+        self
+    }
+
+    /// Sets the WiX Source (wxs) files used to create the installer.
+    ///
+    /// If set, these files are the only WiX Source files used to create the
+    /// installer. The files in the project's `wix` folder, the files from the
+    /// [`includes`] method, and the `include` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml)
+    /// are all ignored. The values are relative or absolute paths. The default
+    /// is to use the `wix` folder and the included files.
+    ///
+    /// [`includes`]: #method.includes
+    pub fn wxs_sources(&mut self, w: Option<Vec<&'a str>>) -> &mut Self {
+        self.wxs_sources = w;
+        self
+    }
+
+    /// Checks the WiX Source (wxs) files for references to compiler variables
+    /// that are not defined before compiling.
+    ///
+    /// If `true`, then each reference to a compiler variable, such as
+    /// `$(var.Example)`, is reported with a warning if the variable is not
+    /// defined by cargo-wix, the compiler arguments, or a preprocessor
+    /// instruction in the same file or a file it includes with the
+    /// `<?include?>` instruction. An error occurs instead of a warning if
+    /// the [`strict`] method is also set to `true`. Unlike the
+    /// [`verify_manifest`] method, only the variables are checked. The default
+    /// is to not check.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    ///
+    /// [`strict`]: #method.strict
+    /// [`verify_manifest`]: #method.verify_manifest
+    pub fn wxs_variable_check(&mut self, w: bool) -> &mut Self {
+        self.wxs_variable_check = w;
+        self
+    }
+
+    /// Builds a context for creating, or building, the installer.
+    pub fn build(&mut self) -> Execution {
+        Execution {
+            allow_unpublished: self.allow_unpublished,
+            append_args: self.append_args,
+            arp_size: self.arp_size.map(String::from),
+            bin_path: self.bin_path.map(PathBuf::from),
+            build_command: self.build_command.map(String::from),
+            bundle_upgrade_guid: self.bundle_upgrade_guid.map(String::from),
+            bundle_version: self.bundle_version.map(String::from),
+            capture_output: self.capture_output,
+            changelog: self.changelog.map(PathBuf::from),
+            check_localization: self.check_localization,
+            checksum_manifest: self.checksum_manifest.map(PathBuf::from),
+            compiler_args: self
+                .compiler_args
+                .as_ref()
+                .map(|c| c.iter().map(|s| (*s).to_string()).collect()),
+            component_group_refs: self
+                .component_group_refs
+                .as_ref()
+                .map(|c| c.iter().map(|s| (*s).to_string()).collect()),
+            culture: self.culture.map(String::from),
+            debug_build: self.debug_build,
+            profile: self.profile.map(String::from),
+            debug_name: self.debug_name,
+            dry_run: self.dry_run,
+            dump_defines: self.dump_defines,
+            dump_toolset_commands: self.dump_toolset_commands,
+            dump_wixobj: self.dump_wixobj.map(PathBuf::from),
+            emit_relative_manifest_path: self.emit_relative_manifest_path,
+            features: self
+                .features
+                .as_ref()
+                .map(|f| f.iter().map(|s| (*s).to_string()).collect()),
+            format: self.format.map(String::from),
+            generate_transforms: self
+                .generate_transforms
+                .as_ref()
+                .map(|g| g.iter().map(|s| (*s).to_string()).collect()),
+            include_build_metadata: self.include_build_metadata,
+            incremental: self.incremental,
+            includes: self
+                .includes
+                .as_ref()
+                .map(|v| v.iter().map(&PathBuf::from).collect()),
+            input: self.input.map(PathBuf::from),
+            linker_args: self
+                .linker_args
+                .as_ref()
+                .map(|l| l.iter().map(|s| (*s).to_string()).collect()),
+            locale: self.locale.map(PathBuf::from),
+            metadata_output: self.metadata_output.map(PathBuf::from),
+            name: self.name.map(String::from),
+            no_arch_in_name: self.no_arch_in_name,
+            no_build: self.no_build,
+            no_default_features: self.no_default_features,
+            no_default_wix_folder: self.no_default_wix_folder,
+            no_install_if_running: self.no_install_if_running,
+            no_overwrite_increment: self.no_overwrite_increment,
+            target_bin_dir: self.target_bin_dir.map(PathBuf::from),
+            install: self.install,
+            keep_going: self.keep_going,
+            output: self.output.map(String::from),
+            output_dir: self.output_dir.map(PathBuf::from),
+            output_layout: self.output_layout.map(String::from),
+            package: self.package.map(String::from),
+            preprocess_only: self.preprocess_only.map(PathBuf::from),
+            print_wxs: self.print_wxs,
+            product_language: self.product_language.map(String::from),
+            progress: self.progress.clone(),
+            properties: self
+                .properties
+                .as_ref()
+                .map(|p| p.iter().map(|s| (*s).to_string()).collect()),
+            quiet_tools: self.quiet_tools,
+            reinstall_mode: self.reinstall_mode.map(String::from),
+            retain_symbols: self.retain_symbols.map(String::from),
+            strict: self.strict,
+            strip: self.strip,
+            verify_manifest: self.verify_manifest,
+            version: self.version.map(String::from),
+            wix_root: self.wix_root.map(PathBuf::from),
+            wxs_sources: self
+                .wxs_sources
+                .as_ref()
+                .map(|v| v.iter().map(&PathBuf::from).collect()),
+            wxs_variable_check: self.wxs_variable_check,
+            targets: self
+                .targets
+                .as_ref()
+                .map(|t| t.iter().map(|s| (*s).to_string()).collect()),
+            timeout: self.timeout.map(String::from),
+        }
+    }
+
+    pub fn profile(&mut self, profile: Option<&'a str>) -> &mut Self {
+        self.profile = profile;
+        self
+    }
+}
+
+impl<'a> Default for Builder<'a> {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
 
 /// A context for creating, or building, an installer.
 #[derive(Debug)]
 pub struct Execution {
+    allow_unpublished: bool,
+    append_args: bool,
+    arp_size: Option<String>,
     bin_path: Option<PathBuf>,
+    build_command: Option<String>,
+    bundle_upgrade_guid: Option<String>,
+    bundle_version: Option<String>,
     capture_output: bool,
+    changelog: Option<PathBuf>,
+    check_localization: bool,
+    checksum_manifest: Option<PathBuf>,
     compiler_args: Option<Vec<String>>,
+    component_group_refs: Option<Vec<String>>,
     culture: Option<String>,
     debug_build: bool,
     profile: Option<String>,
     debug_name: bool,
+    dry_run: bool,
+    dump_defines: bool,
+    dump_toolset_commands: bool,
+    dump_wixobj: Option<PathBuf>,
+    emit_relative_manifest_path: bool,
+    features: Option<Vec<String>>,
+    format: Option<String>,
+    generate_transforms: Option<Vec<String>>,
+    include_build_metadata: bool,
     includes: Option<Vec<PathBuf>>,
+    incremental: bool,
     input: Option<PathBuf>,
     target_bin_dir: Option<PathBuf>,
     linker_args: Option<Vec<String>>,
     locale: Option<PathBuf>,
+    metadata_output: Option<PathBuf>,
     name: Option<String>,
+    no_arch_in_name: bool,
     no_build: bool,
+    no_default_features: bool,
+    no_default_wix_folder: bool,
+    no_install_if_running: bool,
+    no_overwrite_increment: bool,
     install: bool,
+    keep_going: bool,
     output: Option<String>,
+    output_dir: Option<PathBuf>,
+    output_layout: Option<String>,
     package: Option<String>,
-    target: Option<String>,
+    preprocess_only: Option<PathBuf>,
+    print_wxs: bool,
+    product_language: Option<String>,
+    progress: Option<Progress>,
+    properties: Option<Vec<String>>,
+    quiet_tools: bool,
+    reinstall_mode: Option<String>,
+    retain_symbols: Option<String>,
+    strict: bool,
+    strip: bool,
+    targets: Option<Vec<String>>,
+    timeout: Option<String>,
+    verify_manifest: bool,
     version: Option<String>,
+    wix_root: Option<PathBuf>,
+    wxs_sources: Option<Vec<PathBuf>>,
+    wxs_variable_check: bool,
 }
 
 impl Execution {
     /// Creates, or builds, an installer within a built context.
-    #[allow(clippy::cognitive_complexity)]
+    ///
+    /// If the dry run is enabled with the [`Builder::dry_run`] method, each
+    /// command is printed to stdout instead of being run, and there are no
+    /// other side effects.
     pub fn run(self) -> Result<()> {
+        if self.dump_defines {
+            // Nothing is built or run to get the variables, like a dry run.
+            self.execute(Some(&RefCell::new(Vec::new())))
+        } else if self.dry_run {
+            for command in self.commands()? {
+                println!("{command}");
+            }
+            Ok(())
+        } else {
+            self.execute(None)
+        }
+    }
+
+    /// Gets the commands for creating the installer without running them.
+    ///
+    /// This is always a dry run, regardless of the [`Builder::dry_run`]
+    /// method, so nothing is built, compiled, linked, or written to the disk.
+    /// Each command is described in the same way as the debug log, and the
+    /// commands are in the order they would run. This is useful for testing an
+    /// integration with this crate without the WiX Toolset.
+    pub fn commands(self) -> Result<Vec<String>> {
+        let commands = RefCell::new(Vec::new());
+        self.execute(Some(&commands))?;
+        Ok(commands.into_inner())
+    }
+
+    /// Creates the installer, or records the commands instead of running them
+    /// if the commands are given for a dry run.
+    #[allow(clippy::cognitive_complexity)]
+    fn execute(&self, dry_run: Option<&RefCell<Vec<String>>>) -> Result<()> {
+        debug!("self.allow_unpublished = {:?}", self.allow_unpublished);
+        debug!("self.append_args = {:?}", self.append_args);
+        debug!("self.arp_size = {:?}", self.arp_size);
         debug!("self.bin_path = {:?}", self.bin_path);
+        debug!("self.build_command = {:?}", self.build_command);
+        debug!("self.bundle_upgrade_guid = {:?}", self.bundle_upgrade_guid);
+        debug!("self.bundle_version = {:?}", self.bundle_version);
         debug!("self.capture_output = {:?}", self.capture_output);
+        debug!("self.changelog = {:?}", self.changelog);
+        debug!("self.check_localization = {:?}", self.check_localization);
+        debug!("self.checksum_manifest = {:?}", self.checksum_manifest);
         debug!("self.compiler_args = {:?}", self.compiler_args);
+        debug!(
+            "self.component_group_refs = {:?}",
+            self.component_group_refs
+        );
         debug!("self.culture = {:?}", self.culture);
         debug!("self.debug_build = {:?}", self.debug_build);
         debug!("self.profile = {:?}", self.profile);
         debug!("self.debug_name = {:?}", self.debug_name);
+        debug!("self.dry_run = {:?}", self.dry_run);
+        debug!("self.dump_defines = {:?}", self.dump_defines);
+        debug!(
+            "self.dump_toolset_commands = {:?}",
+            self.dump_toolset_commands
+        );
+        debug!("self.dump_wixobj = {:?}", self.dump_wixobj);
+        debug!(
+            "self.emit_relative_manifest_path = {:?}",
+            self.emit_relative_manifest_path
+        );
+        debug!("self.features = {:?}", self.features);
+        debug!("self.format = {:?}", self.format);
+        debug!("self.generate_transforms = {:?}", self.generate_transforms);
+        debug!(
+            "self.include_build_metadata = {:?}",
+            self.include_build_metadata
+        );
         debug!("self.includes = {:?}", self.includes);
+        debug!("self.incremental = {:?}", self.incremental);
         debug!("self.input = {:?}", self.input);
         debug!("self.linker_args = {:?}", self.linker_args);
         debug!("self.locale = {:?}", self.locale);
+        debug!("self.metadata_output = {:?}", self.metadata_output);
         debug!("self.name = {:?}", self.name);
+        debug!("self.no_arch_in_name = {:?}", self.no_arch_in_name);
         debug!("self.no_build = {:?}", self.no_build);
+        debug!("self.no_default_features = {:?}", self.no_default_features);
+        debug!(
+            "self.no_default_wix_folder = {:?}",
+            self.no_default_wix_folder
+        );
+        debug!(
+            "self.no_install_if_running = {:?}",
+            self.no_install_if_running
+        );
+        debug!(
+            "self.no_overwrite_increment = {:?}",
+            self.no_overwrite_increment
+        );
         debug!("self.target_bin_dir = {:?}", self.target_bin_dir);
         debug!("self.install = {:?}", self.install);
+        debug!("self.keep_going = {:?}", self.keep_going);
         debug!("self.output = {:?}", self.output);
+        debug!("self.output_dir = {:?}", self.output_dir);
+        debug!("self.output_layout = {:?}", self.output_layout);
         debug!("self.package = {:?}", self.package);
-        debug!("self.target = {:?}", self.target);
+        debug!("self.preprocess_only = {:?}", self.preprocess_only);
+        debug!("self.print_wxs = {:?}", self.print_wxs);
+        debug!("self.product_language = {:?}", self.product_language);
+        debug!("self.properties = {:?}", self.properties);
+        debug!("self.quiet_tools = {:?}", self.quiet_tools);
+        debug!("self.reinstall_mode = {:?}", self.reinstall_mode);
+        debug!("self.retain_symbols = {:?}", self.retain_symbols);
+        debug!("self.strict = {:?}", self.strict);
+        debug!("self.strip = {:?}", self.strip);
+        debug!("self.targets = {:?}", self.targets);
+        debug!("self.timeout = {:?}", self.timeout);
+        debug!("self.verify_manifest = {:?}", self.verify_manifest);
         debug!("self.version = {:?}", self.version);
+        debug!("self.wix_root = {:?}", self.wix_root);
+        debug!("self.wxs_sources = {:?}", self.wxs_sources);
+        debug!("self.wxs_variable_check = {:?}", self.wxs_variable_check);
         let manifest_path = super::cargo_toml_file(self.input.as_ref())?;
         debug!("manifest_path = {:?}", manifest_path);
         let manifest = super::manifest(self.input.as_ref())?;
@@ -447,292 +1595,1165 @@ impl Execution {
         debug!("package = {:?}", package);
         let metadata = package.metadata.clone();
         debug!("metadata = {:?}", metadata);
+        for problem in super::metadata::problems(&metadata) {
+            warn!("{}", problem);
+        }
+        if is_unpublished(&package) && !self.allow_unpublished(&metadata) {
+            warn!(
+                "The '{}' package has 'publish = false' in its manifest (Cargo.toml), so it \
+                 might be an internal or experimental crate. Please check that the installer \
+                 is created for the intended package. Use the '--allow-unpublished' flag or \
+                 the 'allow-unpublished' field in the '[package.metadata.wix]' section to \
+                 silence this warning.",
+                package.name
+            );
+        }
         let name = self.name(&package);
         debug!("name = {:?}", name);
-        let target = self.target()?;
-        debug!("target = {:?}", target);
+        let relative_root = if self.emit_relative_manifest_path(&metadata) {
+            package.manifest_path.parent().map(|p| p.as_std_path())
+        } else {
+            None
+        };
+        debug!("relative_root = {:?}", relative_root);
+        let targets = self.targets()?;
+        debug!("targets = {:?}", targets);
+        let per_arch_layout = self.per_arch_layout(&metadata)?;
+        debug!("per_arch_layout = {:?}", per_arch_layout);
+        let timeout = super::timeout(self.timeout.as_deref())?;
+        debug!("timeout = {:?}", timeout);
         let version = self.version(&package)?;
         debug!("version = {:?}", version);
+        let changelog = self.changelog(&package)?;
+        debug!("changelog = {:?}", changelog);
+        let bundle_upgrade_guid = self.bundle_upgrade_guid(&metadata)?;
+        debug!("bundle_upgrade_guid = {:?}", bundle_upgrade_guid);
+        let bundle_version = self.bundle_version(&metadata)?;
+        debug!("bundle_version = {:?}", bundle_version);
         let compiler_args = self.compiler_args(&metadata);
         debug!("compiler_args = {:?}", compiler_args);
+        let component_group_refs = self.component_group_refs(&metadata)?;
+        debug!("component_group_refs = {:?}", component_group_refs);
+        let properties = self.properties(&metadata)?;
+        debug!("properties = {:?}", properties);
+        let features = self.features(&metadata)?;
+        debug!("features = {:?}", features);
+        let format = self.format(&metadata)?;
+        debug!("format = {:?}", format);
+        let no_default_features = self.no_default_features(&metadata);
+        debug!("no_default_features = {:?}", no_default_features);
+        let enabled_features = super::enabled_features(&package, &features, !no_default_features);
+        debug!("enabled_features = {:?}", enabled_features);
         let culture = self.culture(&metadata)?;
         debug!("culture = {:?}", culture);
+        let transform_cultures = self.transform_cultures(&metadata, &culture)?;
+        debug!("transform_cultures = {:?}", transform_cultures);
+        let product_language = self.product_language(&metadata, &culture)?;
+        debug!("product_language = {:?}", product_language);
         let linker_args = self.linker_args(&metadata);
         debug!("linker_args = {:?}", linker_args);
-        let locale = self.locale(&metadata)?;
+        let locale = self.locale(&package)?;
         debug!("locale = {:?}", locale);
+        if self.check_localization {
+            check_localization(&culture, locale.as_deref(), self.strict)?;
+        }
+        let reinstall_mode = self.reinstall_mode(&metadata)?;
+        debug!("reinstall_mode = {:?}", reinstall_mode);
+        let arp_size = self.arp_size(&metadata)?;
+        debug!("arp_size = {:?}", arp_size);
+        let retain_symbols = self.retain_symbols(&metadata)?;
+        debug!("retain_symbols = {:?}", retain_symbols);
+        let quiet_tools = self.quiet_tools(&metadata);
+        debug!("quiet_tools = {:?}", quiet_tools);
         let profile = self.profile(&metadata);
         debug!("profile = {:?}", profile);
         let debug_name = self.debug_name(&metadata);
         debug!("debug_name = {:?}", debug_name);
+        let (git_hash, build_time) = if self.include_build_metadata(&metadata) {
+            let git_hash = package
+                .manifest_path
+                .parent()
+                .and_then(|p| git_hash(p.as_std_path()));
+            let build_time = Utc::now().format(BUILD_TIME_FORMAT).to_string();
+            (git_hash, Some(build_time))
+        } else {
+            (None, None)
+        };
+        debug!("git_hash = {:?}", git_hash);
+        debug!("build_time = {:?}", build_time);
         let wxs_sources = self.wxs_sources(&package)?;
         debug!("wxs_sources = {:?}", wxs_sources);
+        let incremental = self.incremental(&metadata);
+        debug!("incremental = {:?}", incremental);
+        let verify_manifest = self.verify_manifest(&metadata);
+        debug!("verify_manifest = {:?}", verify_manifest);
+        let wxs_variable_check = self.wxs_variable_check(&metadata);
+        debug!("wxs_variable_check = {:?}", wxs_variable_check);
+        let product_icon_from_binary = uses_product_icon_variable(&wxs_sources)?;
+        debug!("product_icon_from_binary = {:?}", product_icon_from_binary);
         let wixobj_destination = self.wixobj_destination(manifest.target_directory.as_std_path());
-        debug!("wixobj_destination = {:?}", wixobj_destination);
         let no_build = self.no_build(&metadata);
         debug!("no_build = {:?}", no_build);
-        let target_bin_dir =
-            self.target_bin_dir(manifest.target_directory.as_std_path(), &target, &profile);
-        debug!("target_bin_dir = {:?}", target_bin_dir);
-        let cfg = Cfg::of(&target.triple).map_err(|e| Error::Generic(e.to_string()))?;
-        let wix_arch = WixArch::try_from(&cfg)?;
-        debug!("wix_arch = {:?}", wix_arch);
-
-        if no_build {
-            // Only warn if the user isn't clearly trying to be in charge of builds
-            if self.target_bin_dir.is_none() {
-                warn!("Skipped building the binary");
-            }
-        } else {
-            // Build the binary, if a binary been built, then this will essentially do nothing.
-            info!("Building the binary");
-            let mut builder = Command::new(
-                env::var("CARGO")
-                    .map(PathBuf::from)
-                    .ok()
-                    .unwrap_or_else(|| PathBuf::from(CARGO)),
+        let build_command = self.build_command(&metadata);
+        debug!("build_command = {:?}", build_command);
+        let no_overwrite_increment = self.no_overwrite_increment(&metadata);
+        debug!("no_overwrite_increment = {:?}", no_overwrite_increment);
+        let strip = self.strip(&metadata);
+        debug!("strip = {:?}", strip);
+        if strip && no_build {
+            warn!(
+                "The binary is not stripped because it is not built. Please strip the binary \
+                 when it is built by another tool."
             );
-            debug!("builder = {:?}", builder);
-            if self.capture_output {
-                trace!("Capturing the '{}' output", CARGO);
-                builder.stdout(Stdio::null());
-                builder.stderr(Stdio::null());
+        }
+        let multiple_targets = targets.len() > 1;
+        if multiple_targets {
+            if let Some(output) = self.output.as_ref().filter(|_| self.output_dir.is_none()) {
+                if !(output.ends_with('/') || output.ends_with('\\') || Path::new(output).is_dir())
+                {
+                    return Err(Error::Generic(format!(
+                        "The '{output}' output is a file, but installers are created for \
+                         multiple targets. Please use a path to an existing folder or a path \
+                         with a trailing slash for the output when using multiple targets."
+                    )));
+                }
             }
-            builder.arg("build");
-            builder.arg(format!("--profile={}", profile.name));
-            if let Some(target) = &target.arg {
-                builder.arg(format!("--target={target}"));
+            if let Some(metadata_output) =
+                self.metadata_output.as_ref().filter(|m| !is_folder_path(m))
+            {
+                return Err(Error::Generic(format!(
+                    "The '{}' metadata output is a file, but installers are created for \
+                     multiple targets. Please use a path to an existing folder or a path \
+                     with a trailing slash for the metadata output when using multiple \
+                     targets.",
+                    metadata_output.display()
+                )));
             }
-            if let Some(ref package) = self.package {
-                builder.arg(format!("--package={package}"));
+            if self.install {
+                return Err(Error::Generic(String::from(
+                    "The installer cannot be launched because installers are created for \
+                     multiple targets. Please use a single target when using the install \
+                     flag.",
+                )));
             }
-            builder.arg("--manifest-path").arg(&manifest_path);
-            debug!("command = {:?}", builder);
-            let status = builder.status()?;
-            if !status.success() {
-                return Err(Error::Command(
-                    CARGO,
-                    status.code().unwrap_or(100),
-                    self.capture_output,
-                ));
+            if self.no_arch_in_name(&metadata) && !per_arch_layout {
+                return Err(Error::Generic(String::from(
+                    "The architecture is excluded from the name of the installer, but \
+                     installers are created for multiple targets, so the installers would \
+                     have the same name. Please include the architecture in the name or use \
+                     the 'per-arch' output layout when using multiple targets.",
+                )));
             }
         }
-
-        // Compile the installer
-        info!("Compiling the installer");
-        let mut compiler = self.compiler()?;
-        debug!("compiler = {:?}", compiler);
-        if self.capture_output {
-            trace!("Capturing the '{}' output", WIX_COMPILER);
-            compiler.stdout(Stdio::null());
-            compiler.stderr(Stdio::null());
-        }
-        compiler
-            .arg("-arch")
-            .arg(&wix_arch.to_string())
-            .arg("-ext")
-            .arg("WixUtilExtension");
-        if let Some(vendor) = &cfg.target_vendor {
-            compiler.arg(format!("-dTargetVendor={vendor}"));
-        }
-        compiler
-            .arg(format!("-dVersion={version}"))
-            .arg(format!("-dPlatform={wix_arch}"))
-            .arg(format!("-dProfile={}", profile.name))
-            .arg(format!("-dTargetEnv={}", cfg.target_env))
-            .arg(format!("-dTargetTriple={}", target.triple))
-            .arg(format!("-dCargoProfile={}", profile.name))
-            .arg({
-                let mut s = OsString::from("-dCargoTargetDir=");
-                s.push(&manifest.target_directory);
-                s
-            })
-            .arg({
-                let mut s = OsString::from("-dCargoTargetBinDir=");
-                s.push(&target_bin_dir);
-                s
-            })
-            .arg("-o")
-            .arg(&wixobj_destination);
-        if let Some(args) = &compiler_args {
-            trace!("Appending compiler arguments");
-            compiler.args(args);
-        }
-        compiler.args(&wxs_sources);
-        debug!("command = {:?}", compiler);
-        let status = compiler.status().map_err(|err| {
-            if err.kind() == ErrorKind::NotFound {
-                Error::Generic(format!(
-                    "The compiler application ({WIX_COMPILER}) could not be found in the PATH environment \
-                    variable. Please check the WiX Toolset (http://wixtoolset.org/) is \
-                    installed and check the WiX Toolset's '{BINARY_FOLDER_NAME}' folder has been added to the PATH \
-                    system environment variable, the {WIX_PATH_KEY} system environment variable exists, or use \
-                    the '-b,--bin-path' command line argument."
-                ))
-            } else {
-                err.into()
-            }
-        })?;
-        if !status.success() {
-            return Err(Error::Command(
-                WIX_COMPILER,
-                status.code().unwrap_or(100),
-                self.capture_output,
-            ));
+        if dry_run.is_none() {
+            let installer_folder =
+                self.installer_folder(&package, manifest.target_directory.as_std_path());
+            debug!("installer_folder = {:?}", installer_folder);
+            check_writable(&installer_folder)?;
         }
-        let wixobj_sources = self.wixobj_sources(&wixobj_destination)?;
-        debug!("wixobj_sources = {:?}", wixobj_sources);
-        let installer_kind = InstallerKind::try_from(
-            wixobj_sources
-                .iter()
-                .map(WixObjKind::try_from)
-                .collect::<Result<Vec<WixObjKind>>>()?,
-        )?;
-        debug!("installer_kind = {:?}", installer_kind);
-        let installer_destination = self.installer_destination(
-            &name,
-            &version,
-            &cfg,
-            debug_name,
-            &installer_kind,
-            &package,
-            manifest.target_directory.as_std_path(),
-        );
-        debug!("installer_destination = {:?}", installer_destination);
-
-        // Link the installer
-        info!("Linking the installer");
-        let mut linker = self.linker()?;
-        debug!("linker = {:?}", linker);
-        let base_path = manifest_path.parent().ok_or_else(|| {
-            Error::Generic(String::from("The base path for the linker is invalid"))
-        })?;
-        debug!("base_path = {:?}", base_path);
-        if self.capture_output {
-            trace!("Capturing the '{}' output", WIX_LINKER);
-            linker.stdout(Stdio::null());
-            linker.stderr(Stdio::null());
-        }
-        linker
-            .arg("-spdb")
-            .arg("-ext")
-            .arg("WixUIExtension")
-            .arg("-ext")
-            .arg("WixUtilExtension")
-            .arg(format!("-cultures:{culture}"))
-            .arg("-out")
-            .arg(&installer_destination)
-            .arg("-b")
-            .arg(base_path);
-        if let Some(l) = locale {
-            trace!("Using the a WiX localization file");
-            linker.arg("-loc").arg(l);
-        }
-        if let InstallerKind::Exe = installer_kind {
-            trace!("Adding the WixBalExtension for the bundle-based installer");
-            linker.arg("-ext").arg("WixBalExtension");
-        }
-        if let Some(args) = &linker_args {
-            trace!("Appending linker arguments");
-            linker.args(args);
-        }
-        linker.args(&wixobj_sources);
-        debug!("command = {:?}", linker);
-        let status = linker.status().map_err(|err| {
-            if err.kind() == ErrorKind::NotFound {
-                Error::Generic(format!(
-                    "The linker application ({WIX_LINKER}) could not be found in the PATH environment \
-                     variable. Please check the WiX Toolset (http://wixtoolset.org/) is \
-                     installed and check the WiX Toolset's '{BINARY_FOLDER_NAME}' folder has been added to the PATH \
-                     environment variable, the {WIX_PATH_KEY} system environment variable exists, or use the \
-                     '-b,--bin-path' command line argument."
-                ))
+        // The files created for all of the targets, which are listed in the
+        // checksum manifest after the installers are created.
+        let artifacts = RefCell::new(Vec::new());
+        let create = |target: &Target| -> Result<()> {
+            info!("Creating the installer for the '{}' target", target.triple);
+            // Each target gets its own folder for the WiX object files;
+            // otherwise, the object files for one target would be linked into
+            // the installer for another target.
+            let wixobj_destination = if multiple_targets {
+                wixobj_destination.join(&target.triple).join("")
             } else {
-                err.into()
+                wixobj_destination.clone()
+            };
+            debug!("wixobj_destination = {:?}", wixobj_destination);
+            let target_bin_dir =
+                self.target_bin_dir(manifest.target_directory.as_std_path(), target, &profile);
+            let target_bin_dir = if no_build && self.target_bin_dir.is_none() {
+                let binary_name = format!("{}.{EXE_FILE_EXTENSION}", main_binary_name(&package));
+                if target_bin_dir.join(&binary_name).exists() {
+                    target_bin_dir
+                } else if let Some(binary) =
+                    find_binary(manifest.target_directory.as_std_path(), &binary_name)
+                {
+                    warn!(
+                        "The '{}' binary was not found, so the most recent '{}' binary in the \
+                         target folder is used instead. Use the '--target-bin-dir' option to \
+                         select the folder of the binaries.",
+                        target_bin_dir.join(&binary_name).display(),
+                        binary.display()
+                    );
+                    binary
+                        .parent()
+                        .map(Path::to_path_buf)
+                        .unwrap_or(target_bin_dir)
+                } else {
+                    target_bin_dir
+                }
+            } else {
+                target_bin_dir
+            };
+            debug!("target_bin_dir = {:?}", target_bin_dir);
+            let cfg = Cfg::of(&target.triple).map_err(|e| Error::Generic(e.to_string()))?;
+            let wix_arch = WixArch::try_from(&cfg)?;
+            debug!("wix_arch = {:?}", wix_arch);
+
+            if no_build {
+                // Only warn if the user isn't clearly trying to be in charge of builds
+                if self.target_bin_dir.is_none() {
+                    warn!("Skipped building the binary");
+                }
+            } else if let Some(command) = &build_command {
+                info!("Building the binary with the '{}' command", command);
+                self.report(CreateStage::Building);
+                let mut builder = shell_command(command);
+                if self.capture_output {
+                    trace!("Capturing the build command output");
+                    builder.stdout(Stdio::null());
+                    builder.stderr(Stdio::null());
+                }
+                if let Some(package_dir) = manifest_path.parent() {
+                    builder.current_dir(package_dir);
+                }
+                builder
+                    .env("CARGO_TARGET_DIR", &manifest.target_directory)
+                    .env("CARGO_WIX_PROFILE", &profile.name)
+                    .env("CARGO_WIX_TARGET", &target.triple)
+                    .env("CARGO_WIX_TARGET_BIN_DIR", &target_bin_dir);
+                if strip {
+                    builder.env(profile.strip_env_var(), "true");
+                }
+                debug!("command = {:?}", builder);
+                let status = self.status(&mut builder, timeout, dry_run)?;
+                if let Some(status) = status.filter(|s| !s.success()) {
+                    return Err(Error::Generic(format!(
+                        "The '{command}' build command failed with exit code = {}.{}",
+                        status.code().unwrap_or(100),
+                        if self.capture_output {
+                            " Consider using the '--nocapture' flag to obtain more information."
+                        } else {
+                            ""
+                        }
+                    )));
+                }
+            } else {
+                // Build the binary, if a binary been built, then this will essentially do nothing.
+                info!("Building the binary");
+                self.report(CreateStage::Building);
+                let mut builder = Command::new(
+                    env::var("CARGO")
+                        .map(PathBuf::from)
+                        .ok()
+                        .unwrap_or_else(|| PathBuf::from(CARGO)),
+                );
+                debug!("builder = {:?}", builder);
+                if self.capture_output {
+                    trace!("Capturing the '{}' output", CARGO);
+                    builder.stdout(Stdio::null());
+                    builder.stderr(Stdio::null());
+                }
+                builder.arg("build");
+                builder.arg(format!("--profile={}", profile.name));
+                if let Some(target) = &target.arg {
+                    builder.arg(format!("--target={target}"));
+                }
+                if let Some(ref package) = self.package {
+                    builder.arg(format!("--package={package}"));
+                }
+                if !features.is_empty() {
+                    builder.arg(format!("--features={}", features.join(",")));
+                }
+                if no_default_features {
+                    builder.arg("--no-default-features");
+                }
+                builder.arg("--manifest-path").arg(&manifest_path);
+                if strip {
+                    trace!("Stripping the symbols from the binary");
+                    builder.env(profile.strip_env_var(), "true");
+                }
+                debug!("command = {:?}", builder);
+                let status = self.status(&mut builder, timeout, dry_run)?;
+                if let Some(status) = status.filter(|s| !s.success()) {
+                    return Err(Error::Command(
+                        CARGO,
+                        status.code().unwrap_or(100),
+                        self.capture_output,
+                    ));
+                }
             }
-        })?;
-        if !status.success() {
-            return Err(Error::Command(
-                WIX_LINKER,
-                status.code().unwrap_or(100),
-                self.capture_output,
-            ));
-        }
 
-        // Launch the installer
-        if self.install {
-            info!("Launching the installer");
-            let mut installer = Command::new(MSIEXEC);
-            installer.arg("/i").arg(&installer_destination);
-            let status = installer.status()?;
-            if !status.success() {
-                return Err(Error::Command(
-                    MSIEXEC,
-                    status.code().unwrap_or(100),
-                    self.capture_output,
-                ));
+            if format == InstallerKind::Zip {
+                info!("Archiving the files");
+                self.report(CreateStage::Archiving);
+                if dry_run.is_some() {
+                    trace!("Skipped archiving the files for the dry run");
+                    return Ok(());
+                }
+                let base_path = manifest_path.parent().ok_or_else(|| {
+                    Error::Generic(String::from("The base path for the archive is invalid"))
+                })?;
+                let mut values = vec![
+                    (String::from("CargoProfile"), profile.name.clone()),
+                    (
+                        String::from("CargoTargetBinDir"),
+                        target_bin_dir.display().to_string(),
+                    ),
+                    (
+                        String::from("CargoTargetDir"),
+                        manifest.target_directory.to_string(),
+                    ),
+                    (String::from("Platform"), wix_arch.to_string()),
+                    (String::from("Profile"), profile.name.clone()),
+                    (String::from("TargetTriple"), target.triple.clone()),
+                    (String::from("Version"), version.clone()),
+                ];
+                values.extend(properties.iter().cloned());
+                let files = portable_files(&wxs_sources, &values, base_path)?;
+                debug!("files = {:?}", files);
+                let archive_destination = self.installer_destination(
+                    &name,
+                    &version,
+                    &cfg,
+                    debug_name,
+                    &InstallerKind::Zip,
+                    &package,
+                    manifest.target_directory.as_std_path(),
+                );
+                let archive_destination = if no_overwrite_increment {
+                    incremented_destination(&archive_destination)
+                } else {
+                    archive_destination
+                };
+                debug!("archive_destination = {:?}", archive_destination);
+                if per_arch_layout && dry_run.is_none() {
+                    if let Some(parent) = archive_destination.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                }
+                let folder = archive_destination
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or(&name);
+                write_zip_archive(&archive_destination, folder, &files, &target_bin_dir)?;
+                artifacts.borrow_mut().push(archive_destination.clone());
+                if self.install {
+                    warn!("The zip archive is not installed because it is not an installer");
+                }
+                return Ok(());
             }
-        }
 
-        Ok(())
-    }
+            if dry_run.is_none() {
+                if self.target_bin_dir.is_some() {
+                    check_target_bin_dir(&target_bin_dir, &package)?;
+                }
+                let binaries = package
+                    .targets
+                    .iter()
+                    .filter(|t| t.kind.iter().any(|k| k == "bin"))
+                    .map(|t| target_bin_dir.join(format!("{}.{EXE_FILE_EXTENSION}", t.name)))
+                    .collect::<Vec<PathBuf>>();
+                check_binary_arch(&binaries, &wix_arch, self.strict)?;
+            }
 
-    fn compiler(&self) -> Result<Command> {
-        if let Some(mut path) = self.bin_path.as_ref().map(|s| {
-            let mut p = PathBuf::from(s);
-            trace!(
-                "Using the '{}' path to the WiX Toolset's '{}' folder for the compiler",
-                p.display(),
-                BINARY_FOLDER_NAME
-            );
-            p.push(WIX_COMPILER);
-            p.set_extension(EXE_FILE_EXTENSION);
-            p
-        }) {
-            if !path.exists() {
-                path.pop(); // Remove the `candle` application from the path
-                Err(Error::Generic(format!(
-                    "The compiler application ('{}') does not exist at the '{}' path specified via \
-                    the '-b,--bin-path' command line argument. Please check the path is correct and \
-                    the compiler application exists at the path.",
-                    WIX_COMPILER,
-                    path.display()
-                )))
+            let product_icon = if product_icon_from_binary {
+                let binary = target_bin_dir.join(format!(
+                    "{}.{EXE_FILE_EXTENSION}",
+                    main_binary_name(&package)
+                ));
+                if no_build && self.target_bin_dir.is_none() && !binary.exists() {
+                    return Err(Error::Generic(format!(
+                        "The product icon is extracted from the '{}' binary, but the binary \
+                         has not been built. Please build the binary or use the \
+                         '--target-bin-dir' option with the '--no-build' flag.",
+                        binary.display()
+                    )));
+                }
+                let destination = wixobj_destination.join(PRODUCT_ICON_FILE_NAME);
+                if dry_run.is_none() {
+                    extract_product_icon(&binary, &destination)?;
+                }
+                Some(destination)
             } else {
-                Ok(Command::new(path))
+                None
+            };
+            debug!("product_icon = {:?}", product_icon);
+
+            // Compile the installer
+            info!("Compiling the installer");
+            self.report(CreateStage::Compiling);
+            let mut compiler = self.compiler()?;
+            debug!("compiler = {:?}", compiler);
+            if self.capture_output {
+                trace!("Capturing the '{}' output", WIX_COMPILER);
+                compiler.stdout(Stdio::null());
+                compiler.stderr(Stdio::null());
             }
-        } else if let Some(mut path) = env::var_os(WIX_PATH_KEY).map(|s| {
-            let mut p = PathBuf::from(s);
-            trace!(
-                "Using the '{}' path to the WiX Toolset's '{}' folder for the compiler",
-                p.display(),
-                BINARY_FOLDER_NAME
+            compiler
+                .arg("-arch")
+                .arg(&wix_arch.to_string())
+                .arg("-ext")
+                .arg("WixUtilExtension");
+            if let Some(vendor) = &cfg.target_vendor {
+                compiler.arg(format!("-dTargetVendor={vendor}"));
+            }
+            compiler
+                .arg(format!("-dVersion={version}"))
+                .arg(format!("-dPlatform={wix_arch}"))
+                .arg(format!("-dProductLanguage={product_language}"))
+                .arg(format!("-dProfile={}", profile.name))
+                .arg(format!("-dTargetEnv={}", cfg.target_env))
+                .arg(format!("-dTargetTriple={}", target.triple))
+                .arg(format!("-dCargoProfile={}", profile.name))
+                .arg({
+                    let mut s = OsString::from("-dCargoTargetDir=");
+                    s.push(emitted_path(
+                        manifest.target_directory.as_std_path(),
+                        relative_root,
+                    ));
+                    s
+                })
+                .arg({
+                    let mut s = OsString::from("-dCargoTargetBinDir=");
+                    s.push(emitted_path(&target_bin_dir, relative_root));
+                    s
+                })
+                .arg("-o")
+                .arg(&wixobj_destination);
+            if let Some(bundle_upgrade_guid) = &bundle_upgrade_guid {
+                compiler.arg(format!("-dBundleUpgradeCode={bundle_upgrade_guid}"));
+            }
+            if let Some(bundle_version) = &bundle_version {
+                compiler.arg(format!("-dBundleVersion={bundle_version}"));
+            }
+            if let Some(changelog) = &changelog {
+                compiler.arg(format!("-dChangelog={changelog}"));
+            }
+            if let Some(reinstall_mode) = &reinstall_mode {
+                compiler.arg(format!("-dReinstallMode={reinstall_mode}"));
+            }
+            if let Some(git_hash) = &git_hash {
+                compiler.arg(format!("-dGitHash={git_hash}"));
+            }
+            if let Some(build_time) = &build_time {
+                compiler.arg(format!("-dBuildTime={build_time}"));
+            }
+            if let Some(product_icon) = &product_icon {
+                compiler.arg({
+                    let mut s = OsString::from(format!("-d{PRODUCT_ICON_VARIABLE}="));
+                    s.push(emitted_path(product_icon, relative_root));
+                    s
+                });
+            }
+            if !component_group_refs.is_empty() {
+                compiler.arg(format!(
+                    "-dComponentGroupRefs={}",
+                    component_group_refs.join(";")
+                ));
+            }
+            for feature in &enabled_features {
+                compiler.arg(format!("-d{}=1", super::cargo_feature_variable(feature)));
+            }
+            for (name, value) in &properties {
+                compiler.arg(format!("-d{name}={value}"));
+            }
+            if let Some(args) = &compiler_args {
+                trace!("Appending compiler arguments");
+                compiler.args(args);
+            }
+            if quiet_tools && !has_nologo(compiler_args.as_deref()) {
+                trace!("Suppressing the compiler logo");
+                compiler.arg(NO_LOGO);
+            }
+            let arp_size = match arp_size {
+                Some(size) => Some(size),
+                None => {
+                    let base_path = manifest_path.parent().ok_or_else(|| {
+                        Error::Generic(String::from(
+                            "The base path for the estimated size is invalid",
+                        ))
+                    })?;
+                    estimated_size(
+                        &wxs_sources,
+                        compiler.get_args(),
+                        &bind_paths(base_path, linker_args.as_deref()),
+                    )?
+                }
+            };
+            debug!("arp_size = {:?}", arp_size);
+            if let Some(arp_size) = arp_size {
+                compiler.arg(format!("-dArpSize={arp_size}"));
+            }
+            if self.dump_defines {
+                if multiple_targets {
+                    println!("[{}]", target.triple);
+                }
+                for (name, value) in defines(compiler.get_args()) {
+                    println!("{name}={value}");
+                }
+                return Ok(());
+            }
+            compiler.args(&wxs_sources);
+            debug!("command = {:?}", compiler);
+            if self.print_wxs {
+                print_wxs_sources(&wxs_sources, &mut std::io::stdout())?;
+            }
+            if verify_manifest {
+                trace!("Verifying the WiX Source files have the values from the manifest");
+                let defined = defined_variables(compiler.get_args());
+                debug!("defined = {:?}", defined);
+                let include_paths = include_paths(compiler.get_args());
+                debug!("include_paths = {:?}", include_paths);
+                verify_wxs_sources(&wxs_sources, &defined, &include_paths)?;
+            } else if wxs_variable_check {
+                trace!("Checking the WiX Source files for undefined variables");
+                let defined = defined_variables(compiler.get_args());
+                debug!("defined = {:?}", defined);
+                let include_paths = include_paths(compiler.get_args());
+                debug!("include_paths = {:?}", include_paths);
+                check_wxs_variables(&wxs_sources, &defined, &include_paths, self.strict)?;
+            }
+            if let Some(preprocess_only) = &self.preprocess_only {
+                let preprocess_only = if multiple_targets {
+                    preprocess_only.join(&target.triple)
+                } else {
+                    preprocess_only.clone()
+                };
+                info!(
+                    "Preprocessing the WiX Source files to the '{}' folder",
+                    preprocess_only.display()
+                );
+                return self.preprocess(
+                    &compiler,
+                    &wxs_sources,
+                    &preprocess_only,
+                    timeout,
+                    dry_run,
+                );
+            }
+            let input_hash_destination = wixobj_destination.join(INPUT_HASH_FILE_NAME);
+            let (input_hash, up_to_date) = if incremental && dry_run.is_none() {
+                let mut args: Vec<&OsStr> = compiler
+                    .get_args()
+                    .filter(|a| !a.to_string_lossy().starts_with("-dBuildTime="))
+                    .collect();
+                let culture = OsString::from(culture.to_string());
+                args.push(&culture);
+                let transform_cultures_arg = OsString::from(
+                    transform_cultures
+                        .iter()
+                        .map(Cultures::to_string)
+                        .collect::<Vec<String>>()
+                        .join(","),
+                );
+                args.push(&transform_cultures_arg);
+                // The destination depends on the output, name, and layout
+                // options, so a change to any of them creates a new installer.
+                let destination = self
+                    .installer_destination(
+                        &name,
+                        &version,
+                        &cfg,
+                        debug_name,
+                        &wxs_installer_kind(&wxs_sources)?,
+                        &package,
+                        manifest.target_directory.as_std_path(),
+                    )
+                    .into_os_string();
+                args.push(&destination);
+                if let Some(linker_args) = &linker_args {
+                    args.extend(linker_args.iter().map(OsStr::new));
+                }
+                // The included files and the files referenced by the sources,
+                // such as the license, icon, and images, are inputs, too.
+                let mut files = wxs_sources.clone();
+                let include_paths = include_paths(compiler.get_args());
+                for source in &wxs_sources {
+                    files.extend(included_files(source, &include_paths)?);
+                }
+                let base_path = manifest_path.parent().ok_or_else(|| {
+                    Error::Generic(String::from("The base path for the input hash is invalid"))
+                })?;
+                files.extend(referenced_files(
+                    &files.clone(),
+                    compiler.get_args(),
+                    &bind_paths(base_path, linker_args.as_deref()),
+                )?);
+                files.extend(locale.clone());
+                files.extend(
+                    binary_names(&package)
+                        .into_iter()
+                        .map(|n| target_bin_dir.join(format!("{n}.{EXE_FILE_EXTENSION}"))),
+                );
+                let input_hash = input_hash(&args, &files)?;
+                debug!("input_hash = {:?}", input_hash);
+                let up_to_date = up_to_date_installer(&input_hash_destination, &input_hash).filter(
+                    |installer| {
+                        transform_cultures
+                            .iter()
+                            .all(|c| transform_destination(installer, c).exists())
+                    },
+                );
+                if let Some(installer) = &up_to_date {
+                    info!(
+                        "Skipped compiling and linking the installer because the inputs of \
+                         the '{}' installer are unchanged",
+                        installer.display()
+                    );
+                }
+                (Some(input_hash), up_to_date)
+            } else {
+                if dry_run.is_none() && input_hash_destination.exists() {
+                    trace!("Removing the stale input hash");
+                    std::fs::remove_file(&input_hash_destination)?;
+                }
+                (None, None)
+            };
+            if up_to_date.is_none() {
+                let status = self
+                    .status(&mut compiler, timeout, dry_run)
+                    .map_err(compiler_error)?;
+                if let Some(status) = status.filter(|s| !s.success()) {
+                    return Err(Error::Command(
+                        WIX_COMPILER,
+                        status.code().unwrap_or(100),
+                        self.capture_output,
+                    ));
+                }
+            }
+            let wixobj_sources = if dry_run.is_some() {
+                compiled_wixobj_sources(&wxs_sources, &wixobj_destination)
+            } else {
+                self.wixobj_sources(&wixobj_destination)?
+            };
+            debug!("wixobj_sources = {:?}", wixobj_sources);
+            if let Some(dump_wixobj) = self.dump_wixobj.as_ref().filter(|_| dry_run.is_none()) {
+                let dump_wixobj = if multiple_targets {
+                    dump_wixobj.join(&target.triple)
+                } else {
+                    dump_wixobj.clone()
+                };
+                info!(
+                    "Copying the WiX object files to the '{}' folder",
+                    dump_wixobj.display()
+                );
+                copy_wixobj_sources(&wixobj_sources, &dump_wixobj)?;
+            }
+            let installer_kind = if dry_run.is_some() {
+                wxs_installer_kind(&wxs_sources)?
+            } else {
+                InstallerKind::try_from(
+                    wixobj_sources
+                        .iter()
+                        .map(WixObjKind::try_from)
+                        .collect::<Result<Vec<WixObjKind>>>()?,
+                )?
+            };
+            debug!("installer_kind = {:?}", installer_kind);
+            let installer_destination = self.installer_destination(
+                &name,
+                &version,
+                &cfg,
+                debug_name,
+                &installer_kind,
+                &package,
+                manifest.target_directory.as_std_path(),
             );
-            p.push(BINARY_FOLDER_NAME);
-            p.push(WIX_COMPILER);
-            p.set_extension(EXE_FILE_EXTENSION);
-            p
-        }) {
-            if !path.exists() {
-                path.pop(); // Remove the `candle` application from the path
-                Err(Error::Generic(format!(
-                    "The compiler application ('{}') does not exist at the '{}' path specified \
-                     via the {} environment variable. Please check the path is correct and the \
-                     compiler application exists at the path.",
-                    WIX_COMPILER,
-                    path.display(),
-                    WIX_PATH_KEY
-                )))
+            let installer_destination = if let Some(installer) = up_to_date.clone() {
+                installer
+            } else if no_overwrite_increment {
+                let incremented = incremented_destination(&installer_destination);
+                if incremented != installer_destination {
+                    info!(
+                        "The '{}' installer exists, so the installer is written to '{}'",
+                        installer_destination.display(),
+                        incremented.display()
+                    );
+                }
+                incremented
             } else {
-                Ok(Command::new(path))
+                installer_destination
+            };
+            debug!("installer_destination = {:?}", installer_destination);
+            if per_arch_layout && dry_run.is_none() {
+                if let Some(parent) = installer_destination.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+            }
+
+            // Link the installer
+            info!("Linking the installer");
+            self.report(CreateStage::Linking);
+            let mut linker = self.linker()?;
+            debug!("linker = {:?}", linker);
+            let base_path = manifest_path.parent().ok_or_else(|| {
+                Error::Generic(String::from("The base path for the linker is invalid"))
+            })?;
+            debug!("base_path = {:?}", base_path);
+            if self.capture_output {
+                trace!("Capturing the '{}' output", WIX_LINKER);
+                linker.stdout(Stdio::null());
+                linker.stderr(Stdio::null());
+            }
+            if !retain_symbols {
+                trace!("Suppressing the WiX debug symbols (wixpdb) file");
+                linker.arg("-spdb");
+            }
+            linker
+                .arg("-ext")
+                .arg("WixUIExtension")
+                .arg("-ext")
+                .arg("WixUtilExtension")
+                .arg(linker_cultures(&culture))
+                .arg("-out")
+                .arg(&installer_destination)
+                .arg("-b")
+                .arg(base_path);
+            if let Some(l) = &locale {
+                trace!("Using the a WiX localization file");
+                linker.arg("-loc").arg(l);
+            }
+            if let InstallerKind::Exe = installer_kind {
+                trace!("Adding the WixBalExtension for the bundle-based installer");
+                linker.arg("-ext").arg("WixBalExtension");
+            }
+            if let Some(args) = &linker_args {
+                trace!("Appending linker arguments");
+                linker.args(args);
+            }
+            if quiet_tools && !has_nologo(linker_args.as_deref()) {
+                trace!("Suppressing the linker logo");
+                linker.arg(NO_LOGO);
+            }
+            linker.args(&wixobj_sources);
+            debug!("command = {:?}", linker);
+            if up_to_date.is_some() {
+                artifacts.borrow_mut().push(installer_destination.clone());
+                artifacts.borrow_mut().extend(
+                    transform_cultures
+                        .iter()
+                        .map(|c| transform_destination(&installer_destination, c)),
+                );
+            } else {
+                let status = self
+                    .status(&mut linker, timeout, dry_run)
+                    .map_err(linker_error)?;
+                if let Some(status) = status.filter(|s| !s.success()) {
+                    return Err(Error::Command(
+                        WIX_LINKER,
+                        status.code().unwrap_or(100),
+                        self.capture_output,
+                    ));
+                }
+                if dry_run.is_none() {
+                    artifacts.borrow_mut().push(installer_destination.clone());
+                }
+            }
+            if !transform_cultures.is_empty() && up_to_date.is_none() {
+                if installer_kind != InstallerKind::Msi {
+                    return Err(Error::Generic(format!(
+                        "Transforms can only be generated for a Windows installer (msi), but \
+                         the '{}' installer is a {} installer.",
+                        installer_destination.display(),
+                        installer_kind
+                    )));
+                }
+                info!("Generating the transforms");
+                for transform_culture in &transform_cultures {
+                    let transform = self.generate_transform(
+                        &linker,
+                        &installer_destination,
+                        &wixobj_destination,
+                        transform_culture,
+                        timeout,
+                        dry_run,
+                    )?;
+                    if dry_run.is_none() {
+                        artifacts.borrow_mut().push(transform);
+                    }
+                }
+            }
+
+            if let Some(metadata_output) =
+                self.metadata_output.as_ref().filter(|_| dry_run.is_none())
+            {
+                let metadata_output = if is_folder_path(metadata_output) {
+                    let mut file_name = installer_destination
+                        .file_name()
+                        .map(OsString::from)
+                        .unwrap_or_default();
+                    file_name.push(".json");
+                    metadata_output.join(file_name)
+                } else {
+                    metadata_output.clone()
+                };
+                info!(
+                    "Writing the configuration of the installer to '{}'",
+                    metadata_output.display()
+                );
+                let configuration = serde_json::json!({
+                    "package": package.name,
+                    "name": name,
+                    "version": version,
+                    "target": target.triple,
+                    "arch": wix_arch.to_string(),
+                    "profile": profile.name,
+                    "culture": culture.to_string(),
+                    "product-language": product_language,
+                    "transform-cultures": transform_cultures
+                        .iter()
+                        .map(Cultures::to_string)
+                        .collect::<Vec<String>>(),
+                    "locale": locale,
+                    "features": enabled_features,
+                    "format": installer_kind.to_string(),
+                    "installer": installer_destination,
+                    "sources": wxs_sources,
+                    "defines": defines(compiler.get_args())
+                        .into_iter()
+                        .map(|(name, value)| (name, Value::from(value)))
+                        .collect::<serde_json::Map<String, Value>>(),
+                    "compiler-args": compiler_args,
+                    "linker-args": linker_args,
+                    "toolset": {
+                        "compiler": compiler.get_program(),
+                        "linker": linker.get_program(),
+                    },
+                });
+                write_metadata_output(&metadata_output, &configuration)?;
+            }
+            if let Some(input_hash) = input_hash {
+                trace!("Recording the input hash");
+                std::fs::write(
+                    &input_hash_destination,
+                    format!("{input_hash}\n{}\n", installer_destination.display()),
+                )?;
+            }
+            self.launch_installer(&package, &installer_destination, dry_run)
+        };
+        let mut failed_targets = Vec::new();
+        for target in &targets {
+            if let Err(err) = create(target) {
+                if self.keep_going && multiple_targets {
+                    error!(
+                        "Failed to create the installer for the '{}' target: {}",
+                        target.triple, err
+                    );
+                    failed_targets.push(target.triple.as_str());
+                } else {
+                    return Err(err);
+                }
+            }
+        }
+        if let Some(checksum_manifest) = self.checksum_manifest.as_ref() {
+            let artifacts = artifacts.into_inner();
+            if artifacts.is_empty() {
+                trace!("Skipped writing the checksum manifest because no files were created");
+            } else {
+                info!(
+                    "Writing the checksums of the created files to '{}'",
+                    checksum_manifest.display()
+                );
+                write_checksum_manifest(checksum_manifest, &artifacts)?;
+            }
+        }
+        if failed_targets.is_empty() {
+            self.report(CreateStage::Done);
+            Ok(())
+        } else {
+            Err(Error::Generic(format!(
+                "Failed to create the installer for the following targets: {}",
+                failed_targets.join(", ")
+            )))
+        }
+    }
+
+    fn bundle_upgrade_guid(&self, metadata: &Value) -> Result<Option<String>> {
+        if let Some(ref u) = self.bundle_upgrade_guid {
+            trace!("A bundle upgrade GUID has been explicitly specified");
+            crate::guid(u, "'--bundle-upgrade-guid' option").map(Some)
+        } else if let Some(pkg_meta_wix_bundle_upgrade_guid) = metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("bundle-upgrade-guid"))
+            .and_then(|u| u.as_str())
+        {
+            crate::guid(
+                pkg_meta_wix_bundle_upgrade_guid,
+                "'bundle-upgrade-guid' field in the '[package.metadata.wix]' section",
+            )
+            .map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn bundle_version(&self, metadata: &Value) -> Result<Option<String>> {
+        if let Some(ref v) = self.bundle_version {
+            Version::parse(v)
+                .map_err(Error::from)
+                .and_then(|v| wix_version(&v))
+                .map(Some)
+        } else if let Some(pkg_meta_wix_bundle_version) = metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("bundle-version"))
+            .and_then(|v| v.as_str())
+        {
+            Version::parse(pkg_meta_wix_bundle_version)
+                .map_err(Error::from)
+                .and_then(|v| wix_version(&v))
+                .map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Gets the first entry of the section of the changelog for the version
+    /// of the installer.
+    ///
+    /// A changelog is only used if it is specified with the `--changelog`
+    /// option or the `changelog` field, which is either a path or `true` for
+    /// the `CHANGELOG.md` file alongside the package's manifest (Cargo.toml).
+    /// The entry is a single, truncated line because it is passed to the
+    /// compiler as a variable on the command line. A changelog is not detected
+    /// automatically, so an existing `CHANGELOG.md` file does not change the
+    /// comments of an installer without the author opting in.
+    fn changelog(&self, package: &Package) -> Result<Option<String>> {
+        let path = if let Some(ref c) = self.changelog {
+            trace!("A changelog has been explicitly specified");
+            if !c.exists() {
+                return Err(Error::Generic(format!(
+                    "The '{}' changelog does not exist. Please check the path is correct and \
+                     the file exists.",
+                    c.display()
+                )));
+            }
+            c.to_owned()
+        } else if let Some(pkg_meta_wix_changelog) = package
+            .metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("changelog"))
+        {
+            if let Some(c) = pkg_meta_wix_changelog.as_str() {
+                trace!("Using the changelog from the package's metadata");
+                super::package_relative_path(package, c)
+            } else if pkg_meta_wix_changelog.as_bool() == Some(true) {
+                trace!("Using the changelog alongside the package's manifest");
+                super::package_relative_path(package, CHANGELOG_FILE_NAME)
+            } else {
+                trace!("The changelog has been disabled in the package's metadata");
+                return Ok(None);
             }
         } else {
-            Ok(Command::new(WIX_COMPILER))
+            trace!("A changelog has not been specified");
+            return Ok(None);
+        };
+        let version = self.selected_version(package)?.to_string();
+        // The changelog is decoded like a WiX Source file, so a byte-order mark
+        // does not hide the first heading and UTF-16 is supported.
+        let content = read_wxs_source(&path)?;
+        let section = changelog_section(&content, &version);
+        if section.is_none() {
+            warn!(
+                "A section for the '{}' version could not be found in the '{}' changelog. The \
+                 changelog will be excluded from the installer.",
+                version,
+                path.display()
+            );
+        }
+        Ok(section)
+    }
+
+    /// Gets the root folder of the WiX Toolset installation and a description
+    /// of where it was specified.
+    ///
+    /// The `--wix-root` option takes precedence over the `WIX` system
+    /// environment variable.
+    fn wix_root(&self) -> Option<(PathBuf, String)> {
+        self.wix_root
+            .clone()
+            .map(|r| (r, String::from("'--wix-root' command line argument")))
+            .or_else(|| {
+                env::var_os(WIX_PATH_KEY).map(|w| {
+                    (
+                        PathBuf::from(w),
+                        format!("{WIX_PATH_KEY} environment variable"),
+                    )
+                })
+            })
+    }
+
+    /// Runs only the preprocessor of the WiX compiler (candle.exe) for each
+    /// WiX Source (wxs) file, writing the preprocessed files to the
+    /// destination folder.
+    ///
+    /// The preprocessor is given the same arguments as the `compiler`, except
+    /// for the output and the sources, so the variables are the same as for
+    /// a normal build.
+    fn preprocess(
+        &self,
+        compiler: &Command,
+        wxs_sources: &[PathBuf],
+        destination: &Path,
+        timeout: Option<Duration>,
+        dry_run: Option<&RefCell<Vec<String>>>,
+    ) -> Result<()> {
+        let args = preprocessor_args(compiler.get_args(), wxs_sources.len());
+        if dry_run.is_none() {
+            std::fs::create_dir_all(destination)?;
+        }
+        for source in wxs_sources {
+            let file_name = source.file_name().ok_or_else(|| {
+                Error::Generic(format!(
+                    "The '{}' WiX Source file does not have a file name",
+                    source.display()
+                ))
+            })?;
+            let mut preprocessor = self.compiler()?;
+            if self.capture_output {
+                trace!("Capturing the '{}' output", WIX_COMPILER);
+                preprocessor.stdout(Stdio::null());
+                preprocessor.stderr(Stdio::null());
+            }
+            preprocessor
+                .args(&args)
+                .arg({
+                    let mut s = OsString::from("-p");
+                    s.push(destination.join(file_name));
+                    s
+                })
+                .arg(source);
+            debug!("command = {:?}", preprocessor);
+            let status = self
+                .status(&mut preprocessor, timeout, dry_run)
+                .map_err(compiler_error)?;
+            if let Some(status) = status.filter(|s| !s.success()) {
+                return Err(Error::Command(
+                    WIX_COMPILER,
+                    status.code().unwrap_or(100),
+                    self.capture_output,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Generates a language transform (mst) for an additional culture.
+    ///
+    /// The installer is linked again for the culture, using the same
+    /// arguments as the `linker` except for the cultures and the output, and
+    /// then the WiX Toolset's `torch` application creates the transform from
+    /// the differences between the installer and the installer for the
+    /// culture. The transform is written next to the installer, and its path
+    /// is returned.
+    fn generate_transform(
+        &self,
+        linker: &Command,
+        installer: &Path,
+        wixobj_destination: &Path,
+        culture: &Cultures,
+        timeout: Option<Duration>,
+        dry_run: Option<&RefCell<Vec<String>>>,
+    ) -> Result<PathBuf> {
+        let stem = installer
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let variant = wixobj_destination.join(format!("{stem}-{culture}.{MSI_FILE_EXTENSION}"));
+        let transform = transform_destination(installer, culture);
+        debug!("variant = {:?}", variant);
+        debug!("transform = {:?}", transform);
+        let mut variant_linker = self.linker()?;
+        if self.capture_output {
+            trace!("Capturing the '{}' output", WIX_LINKER);
+            variant_linker.stdout(Stdio::null());
+            variant_linker.stderr(Stdio::null());
+        }
+        variant_linker.args(variant_linker_args(
+            linker.get_args(),
+            &linker_cultures(culture),
+            &variant,
+        ));
+        debug!("command = {:?}", variant_linker);
+        let status = self
+            .status(&mut variant_linker, timeout, dry_run)
+            .map_err(linker_error)?;
+        if let Some(status) = status.filter(|s| !s.success()) {
+            return Err(Error::Command(
+                WIX_LINKER,
+                status.code().unwrap_or(100),
+                self.capture_output,
+            ));
+        }
+        let mut torch = self.torch()?;
+        if self.capture_output {
+            trace!("Capturing the '{}' output", WIX_TORCH);
+            torch.stdout(Stdio::null());
+            torch.stderr(Stdio::null());
+        }
+        torch
+            .arg("-t")
+            .arg("language")
+            .arg(installer)
+            .arg(&variant)
+            .arg("-out")
+            .arg(&transform);
+        debug!("command = {:?}", torch);
+        let status = self.status(&mut torch, timeout, dry_run).map_err(|err| {
+            if err.kind() == ErrorKind::NotFound {
+                Error::Generic(format!(
+                    "The transform application ({WIX_TORCH}) could not be found in the PATH \
+                     environment variable. Please check the WiX Toolset (http://wixtoolset.org/) \
+                     is installed and check the WiX Toolset's '{BINARY_FOLDER_NAME}' folder has \
+                     been added to the PATH environment variable, the {WIX_PATH_KEY} system \
+                     environment variable exists, or use the '-b,--bin-path' command line \
+                     argument."
+                ))
+            } else {
+                err.into()
+            }
+        })?;
+        if let Some(status) = status.filter(|s| !s.success()) {
+            return Err(Error::Command(
+                WIX_TORCH,
+                status.code().unwrap_or(100),
+                self.capture_output,
+            ));
         }
+        info!(
+            "Generated the '{}' transform for the '{}' culture",
+            transform.display(),
+            culture
+        );
+        Ok(transform)
+    }
+
+    pub(crate) fn compiler(&self) -> Result<Command> {
+        self.wix_tool(WIX_COMPILER, "compiler")
+    }
+
+    fn allow_unpublished(&self, metadata: &Value) -> bool {
+        self.allow_unpublished
+            || metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("allow-unpublished"))
+                .and_then(|a| a.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn append_args(&self, metadata: &Value) -> bool {
+        self.append_args
+            || metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("append-args"))
+                .and_then(|a| a.as_bool())
+                .unwrap_or(false)
     }
 
     fn compiler_args(&self, metadata: &Value) -> Option<Vec<String>> {
-        self.compiler_args.to_owned().or_else(|| {
+        merge_args(
+            self.compiler_args.as_ref(),
             metadata
                 .get("wix")
                 .and_then(|w| w.as_object())
@@ -742,22 +2763,123 @@ impl Execution {
                     a.iter()
                         .map(|s| s.as_str().map(String::from).unwrap())
                         .collect::<Vec<String>>()
+                }),
+            self.append_args(metadata),
+        )
+    }
+
+    fn component_group_refs(&self, metadata: &Value) -> Result<Vec<String>> {
+        let ids = if let Some(ref c) = self.component_group_refs {
+            trace!("Component group references have been explicitly specified");
+            c.to_owned()
+        } else if let Some(pkg_meta_wix_component_group_refs) = metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("component-group-ref"))
+            .and_then(|c| c.as_array())
+        {
+            trace!("Component group references have been specified in the metadata");
+            pkg_meta_wix_component_group_refs
+                .iter()
+                .map(|c| {
+                    c.as_str().map(String::from).ok_or_else(|| {
+                        Error::Generic(format!(
+                            "The '{c}' value for the 'component-group-ref' field in the \
+                             'package.metadata.wix' section of the package's manifest \
+                             (Cargo.toml) is not a string."
+                        ))
+                    })
                 })
-        })
+                .collect::<Result<Vec<String>>>()?
+        } else {
+            Vec::new()
+        };
+        if let Some(id) = ids.iter().find(|id| !is_wix_identifier(id)) {
+            return Err(Error::Generic(format!(
+                "The '{id}' component group ID is invalid. An ID must begin with a letter or \
+                 an underscore and only contain letters, digits, underscores, and periods."
+            )));
+        }
+        Ok(ids)
     }
 
-    fn culture(&self, metadata: &Value) -> Result<Cultures> {
+    fn properties(&self, metadata: &Value) -> Result<Vec<(String, String)>> {
+        let properties = if let Some(ref p) = self.properties {
+            trace!("Properties have been explicitly specified");
+            p.to_owned()
+        } else if let Some(pkg_meta_wix_properties) = metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("properties"))
+            .and_then(|p| p.as_array())
+        {
+            trace!("Properties have been specified in the metadata");
+            pkg_meta_wix_properties
+                .iter()
+                .map(|p| {
+                    p.as_str().map(String::from).ok_or_else(|| {
+                        Error::Generic(format!(
+                            "The '{p}' value for the 'properties' field in the \
+                             'package.metadata.wix' section of the package's manifest \
+                             (Cargo.toml) is not a string."
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<String>>>()?
+        } else {
+            Vec::new()
+        };
+        properties.iter().map(|p| parse_property(p)).collect()
+    }
+
+    /// Gets the listed cultures, where the first culture is the culture of the
+    /// installer and the other cultures generate transforms.
+    ///
+    /// The `default-culture` field is only used if a culture is not listed with
+    /// the option or the `culture` field.
+    fn cultures<'b>(&'b self, metadata: &'b Value) -> Vec<&'b str> {
         if let Some(culture) = &self.culture {
-            Cultures::from_str(culture)
-        } else if let Some(pkg_meta_wix_culture) = metadata
+            super::culture_list(culture)
+        } else if let Some(pkg_meta_wix_cultures) =
+            super::metadata_cultures(metadata).filter(|c| !c.is_empty())
+        {
+            pkg_meta_wix_cultures
+        } else if let Some(pkg_meta_wix_default_culture) = metadata
             .get("wix")
             .and_then(|w| w.as_object())
-            .and_then(|t| t.get("culture"))
+            .and_then(|t| t.get("default-culture"))
             .and_then(|c| c.as_str())
         {
-            Cultures::from_str(pkg_meta_wix_culture)
+            trace!("Using the default culture from the metadata");
+            vec![pkg_meta_wix_default_culture]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn culture(&self, metadata: &Value) -> Result<Cultures> {
+        let culture = if let Some(culture) = self.cultures(metadata).first() {
+            *culture
         } else {
-            Ok(Cultures::EnUs)
+            return Ok(Cultures::EnUs);
+        };
+        if culture.trim().eq_ignore_ascii_case(AUTO_CULTURE) {
+            trace!("Deriving the culture from the locale of the system");
+            Ok(system_culture())
+        } else {
+            Cultures::from_str(culture).or_else(|err| match nearest_culture(culture) {
+                Some(nearest) if !self.strict => {
+                    warn!(
+                        "The '{}' culture is not supported by the WixUI extension. The '{}' \
+                         culture for the same language is used instead. Use the '--strict' \
+                         flag to fail instead.",
+                        culture.trim(),
+                        nearest
+                    );
+                    Ok(nearest)
+                }
+                _ => Err(err),
+            })
         }
     }
 
@@ -841,18 +2963,30 @@ impl Execution {
         package: &Package,
         target_directory: &Path,
     ) -> PathBuf {
+        let stem = if self.no_arch_in_name(&package.metadata) {
+            trace!("Excluding the architecture from the name of the installer");
+            format!("{name}-{version}")
+        } else {
+            format!("{}-{}-{}", name, version, cfg.target_arch)
+        };
         let filename = if debug_name {
-            format!(
-                "{}-{}-{}-debug.{}",
-                name, version, cfg.target_arch, installer_kind
-            )
+            format!("{stem}-debug.{installer_kind}")
         } else {
-            format!(
-                "{}-{}-{}.{}",
-                name, version, cfg.target_arch, installer_kind
-            )
+            format!("{stem}.{installer_kind}")
+        };
+        let filename = if self.per_arch_layout(&package.metadata).unwrap_or(false) {
+            trace!("Using the architecture subfolder for the MSI destination");
+            let arch = WixArch::try_from(cfg)
+                .map(|a| a.to_string())
+                .unwrap_or_else(|_| cfg.target_arch.to_string());
+            PathBuf::from(arch).join(filename)
+        } else {
+            PathBuf::from(filename)
         };
-        if let Some(ref path_str) = self.output {
+        if let Some(output_dir) = &self.output_dir {
+            trace!("Using the explicitly specified output folder for the MSI destination");
+            output_dir.join(filename)
+        } else if let Some(ref path_str) = self.output {
             trace!("Using the explicitly specified output path for the MSI destination");
             let path = Path::new(path_str);
             if path_str.ends_with('/') || path_str.ends_with('\\') || path.is_dir() {
@@ -868,14 +3002,14 @@ impl Execution {
             .and_then(|o| o.as_str())
         {
             trace!("Using the output path in the package's metadata for the MSI destination");
-            let path = Path::new(pkg_meta_wix_output);
+            let path = super::package_relative_path(package, pkg_meta_wix_output);
             if pkg_meta_wix_output.ends_with('/')
                 || pkg_meta_wix_output.ends_with('\\')
                 || path.is_dir()
             {
                 path.join(filename)
             } else {
-                path.to_owned()
+                path
             }
         } else {
             trace!("Using the package's manifest (Cargo.toml) file path to specify the MSI destination");
@@ -883,62 +3017,172 @@ impl Execution {
         }
     }
 
-    fn linker(&self) -> Result<Command> {
+    /// Gets the folder that the installer is written to.
+    ///
+    /// This is the parent folder of the [`installer_destination`], but it is
+    /// known before the installer kind and target, so it can be checked
+    /// before building the binaries.
+    ///
+    /// [`installer_destination`]: #method.installer_destination
+    fn installer_folder(&self, package: &Package, target_directory: &Path) -> PathBuf {
+        if let Some(output_dir) = &self.output_dir {
+            return output_dir.clone();
+        }
+        let output = self
+            .output
+            .as_deref()
+            .map(|o| (o, PathBuf::from(o)))
+            .or_else(|| {
+                package
+                    .metadata
+                    .get("wix")
+                    .and_then(|w| w.as_object())
+                    .and_then(|t| t.get("output"))
+                    .and_then(|o| o.as_str())
+                    .map(|o| (o, super::package_relative_path(package, o)))
+            });
+        match output {
+            Some((o, path)) if o.ends_with('/') || o.ends_with('\\') || path.is_dir() => path,
+            Some((_, path)) => path.parent().map(Path::to_path_buf).unwrap_or_default(),
+            None => target_directory.join(WIX),
+        }
+    }
+
+    pub(crate) fn linker(&self) -> Result<Command> {
+        self.wix_tool(WIX_LINKER, "linker")
+    }
+
+    fn torch(&self) -> Result<Command> {
+        self.wix_tool(WIX_TORCH, "transform")
+    }
+
+    /// Gets the command for an application of the WiX Toolset, such as the
+    /// compiler (candle.exe), from its name and the kind of application used
+    /// in the messages.
+    ///
+    /// The application is in the `-b,--bin-path` folder, or else the `bin`
+    /// folder of the WiX Toolset's root folder from the `--wix-root` option or
+    /// the `WIX` system environment variable. An error occurs if it does not
+    /// exist in the folder. The application is found with the `PATH` system
+    /// environment variable if neither are specified.
+    fn wix_tool(&self, name: &str, kind: &str) -> Result<Command> {
         if let Some(mut path) = self.bin_path.as_ref().map(|s| {
             let mut p = PathBuf::from(s);
             trace!(
-                "Using the '{}' path to the WiX Toolset '{}' folder for the linker",
+                "Using the '{}' path to the WiX Toolset's '{}' folder for the {} application",
                 p.display(),
-                BINARY_FOLDER_NAME
+                BINARY_FOLDER_NAME,
+                kind
             );
-            p.push(WIX_LINKER);
+            p.push(name);
             p.set_extension(EXE_FILE_EXTENSION);
             p
         }) {
             if !path.exists() {
-                path.pop(); // Remove the 'light' application from the path
+                path.pop(); // Remove the application from the path
                 Err(Error::Generic(format!(
-                    "The linker application ('{}') does not exist at the '{}' path specified via \
-                     the '-b,--bin-path' command line argument. Please check the path is correct \
-                     and the linker application exists at the path.",
-                    WIX_LINKER,
+                    "The {kind} application ('{name}') does not exist at the '{}' path specified \
+                     via the '-b,--bin-path' command line argument. Please check the path is \
+                     correct and the {kind} application exists at the path.",
                     path.display()
                 )))
             } else {
                 Ok(Command::new(path))
             }
-        } else if let Some(mut path) = env::var_os(WIX_PATH_KEY).map(|s| {
-            let mut p = PathBuf::from(s);
+        } else if let Some((root, source)) = self.wix_root() {
             trace!(
-                "Using the '{}' path to the WiX Toolset's '{}' folder for the linker",
-                p.display(),
-                BINARY_FOLDER_NAME
+                "Using the '{}' path from the {} to the WiX Toolset's '{}' folder for the {} \
+                 application",
+                root.display(),
+                source,
+                BINARY_FOLDER_NAME,
+                kind
             );
-            p.push(BINARY_FOLDER_NAME);
-            p.push(WIX_LINKER);
-            p.set_extension(EXE_FILE_EXTENSION);
-            p
-        }) {
+            let mut path = root
+                .join(BINARY_FOLDER_NAME)
+                .join(name)
+                .with_extension(EXE_FILE_EXTENSION);
             if !path.exists() {
-                path.pop(); // Remove the `candle` application from the path
+                path.pop(); // Remove the application from the path
                 Err(Error::Generic(format!(
-                    "The linker application ('{}') does not exist at the '{}' path specified \
-                     via the {} environment variable. Please check the path is correct and the \
-                     linker application exists at the path.",
-                    WIX_LINKER,
-                    path.display(),
-                    WIX_PATH_KEY
+                    "The {kind} application ('{name}') does not exist at the '{}' path specified \
+                     via the {source}. Please check the path is correct and the {kind} \
+                     application exists at the path.",
+                    path.display()
                 )))
             } else {
                 Ok(Command::new(path))
             }
         } else {
-            Ok(Command::new(WIX_LINKER))
+            Ok(Command::new(name))
+        }
+    }
+
+    /// Gets the additional cultures to generate transforms for.
+    ///
+    /// These are the cultures listed after the culture of the installer and the
+    /// cultures from the `generate_transforms` method. A culture that is the
+    /// same as the culture of the installer is skipped with a warning, since
+    /// the transform would be empty.
+    fn transform_cultures(&self, metadata: &Value, culture: &Cultures) -> Result<Vec<Cultures>> {
+        let mut cultures: Vec<Cultures> = Vec::new();
+        let listed = self.cultures(metadata).into_iter().skip(1);
+        let generated = self
+            .generate_transforms
+            .iter()
+            .flatten()
+            .map(String::as_str);
+        for value in listed.chain(generated) {
+            let transform_culture = Cultures::from_str(value)?;
+            if transform_culture == *culture {
+                warn!(
+                    "The '{transform_culture}' culture is the culture of the installer, so a \
+                     transform is not generated for it."
+                );
+            } else if !cultures.contains(&transform_culture) {
+                cultures.push(transform_culture);
+            }
+        }
+        Ok(cultures)
+    }
+
+    /// Gets the Windows locale identifier (LCID) for the product language.
+    ///
+    /// The LCID of the culture is used if a product language is not specified.
+    fn product_language(&self, metadata: &Value, culture: &Cultures) -> Result<u32> {
+        let (value, source) = if let Some(ref p) = self.product_language {
+            trace!("A product language has been explicitly specified");
+            (p.as_str(), "'--product-language' option")
+        } else if let Some(pkg_meta_wix_product_language) = metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("product-language"))
+            .and_then(|p| p.as_str())
+        {
+            (
+                pkg_meta_wix_product_language,
+                "'product-language' field in the '[package.metadata.wix]' section",
+            )
+        } else {
+            return Ok(culture.lcid());
+        };
+        let value = value.trim();
+        if let Ok(lcid) = value.parse::<u16>() {
+            Ok(u32::from(lcid))
+        } else {
+            Cultures::from_str(value).map(|c| c.lcid()).map_err(|_| {
+                Error::Generic(format!(
+                    "The '{value}' value for the {source} is not a valid product \
+                         language. The product language must be a supported culture, \
+                         such as 'fr-FR', or a numeric LCID, such as '1036'."
+                ))
+            })
         }
     }
 
     fn linker_args(&self, metadata: &Value) -> Option<Vec<String>> {
-        self.linker_args.to_owned().or_else(|| {
+        merge_args(
+            self.linker_args.as_ref(),
             metadata
                 .get("wix")
                 .and_then(|w| w.as_object())
@@ -948,11 +3192,12 @@ impl Execution {
                     a.iter()
                         .map(|s| s.as_str().map(String::from).unwrap())
                         .collect::<Vec<String>>()
-                })
-        })
+                }),
+            self.append_args(metadata),
+        )
     }
 
-    fn locale(&self, metadata: &Value) -> Result<Option<PathBuf>> {
+    fn locale(&self, package: &Package) -> Result<Option<PathBuf>> {
         if let Some(locale) = self.locale.as_ref().map(PathBuf::from) {
             if locale.exists() {
                 Ok(Some(locale))
@@ -963,12 +3208,13 @@ impl Execution {
                     locale.display()
                 )))
             }
-        } else if let Some(pkg_meta_wix_locale) = metadata
+        } else if let Some(pkg_meta_wix_locale) = package
+            .metadata
             .get("wix")
             .and_then(|w| w.as_object())
             .and_then(|t| t.get("locale"))
             .and_then(|l| l.as_str())
-            .map(PathBuf::from)
+            .map(|l| super::package_relative_path(package, l))
         {
             Ok(Some(pkg_meta_wix_locale))
         } else {
@@ -993,6 +3239,98 @@ impl Execution {
         }
     }
 
+    fn build_command(&self, metadata: &Value) -> Option<String> {
+        self.build_command.to_owned().or_else(|| {
+            metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("build-command"))
+                .and_then(|b| b.as_str())
+                .map(String::from)
+        })
+    }
+
+    fn format(&self, metadata: &Value) -> Result<InstallerKind> {
+        let format = if let Some(ref f) = self.format {
+            trace!("A format has been explicitly specified");
+            f.to_owned()
+        } else if let Some(pkg_meta_wix_format) = metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("format"))
+            .and_then(|f| f.as_str())
+        {
+            trace!("A format has been specified in the metadata");
+            pkg_meta_wix_format.to_owned()
+        } else {
+            trace!("Using the default format");
+            return Ok(InstallerKind::Msi);
+        };
+        match format.trim().to_lowercase().as_str() {
+            "msi" => Ok(InstallerKind::Msi),
+            "zip" => Ok(InstallerKind::Zip),
+            _ => Err(Error::Generic(format!(
+                "The '{format}' format is invalid. Please use either 'msi' or 'zip'."
+            ))),
+        }
+    }
+
+    fn features(&self, metadata: &Value) -> Result<Vec<String>> {
+        let features = if let Some(ref f) = self.features {
+            trace!("Features have been explicitly specified");
+            f.to_owned()
+        } else if let Some(pkg_meta_wix_features) = metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("features"))
+            .and_then(|f| f.as_array())
+        {
+            trace!("Features have been specified in the metadata");
+            pkg_meta_wix_features
+                .iter()
+                .map(|f| {
+                    f.as_str().map(String::from).ok_or_else(|| {
+                        Error::Generic(format!(
+                            "The '{f}' value for the 'features' field in the \
+                             'package.metadata.wix' section of the package's manifest \
+                             (Cargo.toml) is not a string."
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<String>>>()?
+        } else {
+            Vec::new()
+        };
+        // Similar to Cargo, multiple features can be separated by commas or
+        // spaces in a single value.
+        Ok(features
+            .iter()
+            .flat_map(|f| f.split([',', ' ']))
+            .filter(|f| !f.is_empty())
+            .map(String::from)
+            .collect())
+    }
+
+    fn emit_relative_manifest_path(&self, metadata: &Value) -> bool {
+        self.emit_relative_manifest_path
+            || metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("emit-relative-manifest-path"))
+                .and_then(|e| e.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn no_arch_in_name(&self, metadata: &Value) -> bool {
+        self.no_arch_in_name
+            || metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("no-arch-in-name"))
+                .and_then(|c| c.as_bool())
+                .unwrap_or(false)
+    }
+
     fn no_build(&self, metadata: &Value) -> bool {
         if self.no_build {
             true
@@ -1008,6 +3346,292 @@ impl Execution {
         }
     }
 
+    fn no_default_features(&self, metadata: &Value) -> bool {
+        self.no_default_features
+            || metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("no-default-features"))
+                .and_then(|c| c.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn no_overwrite_increment(&self, metadata: &Value) -> bool {
+        self.no_overwrite_increment
+            || metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("no-overwrite-increment"))
+                .and_then(|n| n.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn per_arch_layout(&self, metadata: &Value) -> Result<bool> {
+        let layout = if let Some(ref o) = self.output_layout {
+            trace!("An output layout has been explicitly specified");
+            o.to_owned()
+        } else if let Some(pkg_meta_wix_output_layout) = metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("output-layout"))
+            .and_then(|o| o.as_str())
+        {
+            trace!("An output layout has been specified in the metadata");
+            pkg_meta_wix_output_layout.to_owned()
+        } else {
+            trace!("Using the default output layout");
+            return Ok(false);
+        };
+        match layout.trim().to_lowercase().as_str() {
+            "flat" => Ok(false),
+            "per-arch" => Ok(true),
+            _ => Err(Error::Generic(format!(
+                "The '{layout}' output layout is invalid. Please use either 'flat' or \
+                 'per-arch'."
+            ))),
+        }
+    }
+
+    fn no_default_wix_folder(&self, metadata: &Value) -> bool {
+        self.no_default_wix_folder
+            || metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("no-default-wix-folder"))
+                .and_then(|c| c.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn report(&self, stage: CreateStage) {
+        if let Some(Progress(callback)) = &self.progress {
+            callback(stage);
+        }
+    }
+
+    /// Launches the installer if the `install` method is used.
+    fn launch_installer(
+        &self,
+        package: &Package,
+        installer_destination: &Path,
+        dry_run: Option<&RefCell<Vec<String>>>,
+    ) -> Result<()> {
+        if self.install {
+            if self.no_install_if_running && dry_run.is_none() {
+                let running = running_binaries(package)?;
+                if !running.is_empty() {
+                    return Err(Error::Generic(format!(
+                        "The installer was not launched because the following binaries are \
+                         running: {}. Please close the application and run the '{}' \
+                         installer or run the subcommand again.",
+                        running.join(", "),
+                        installer_destination.display()
+                    )));
+                }
+            }
+            info!("Launching the installer");
+            self.report(CreateStage::Installing);
+            let installer_kind = installer_destination
+                .extension()
+                .and_then(|e| e.to_str())
+                .and_then(|e| InstallerKind::from_str(e).ok())
+                .unwrap_or_default();
+            debug!("installer_kind = {:?}", installer_kind);
+            let (application, mut installer) = if installer_kind == InstallerKind::Exe {
+                trace!(
+                    "Launching the bundle directly instead of with '{}'",
+                    MSIEXEC
+                );
+                (BUNDLE_APPLICATION, Command::new(installer_destination))
+            } else {
+                let mut installer = Command::new(MSIEXEC);
+                installer.arg("/i").arg(installer_destination);
+                (MSIEXEC, installer)
+            };
+            let timeout = super::timeout(self.timeout.as_deref())?;
+            let status = self.status(&mut installer, timeout, dry_run)?;
+            if let Some(status) = status.filter(|s| !s.success()) {
+                return Err(Error::Command(
+                    application,
+                    status.code().unwrap_or(100),
+                    self.capture_output,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs the command and waits for it to finish, unless the commands are
+    /// given for a dry run, in which case the command is recorded instead and
+    /// `None` is returned.
+    fn status(
+        &self,
+        command: &mut Command,
+        timeout: Option<Duration>,
+        dry_run: Option<&RefCell<Vec<String>>>,
+    ) -> std::io::Result<Option<ExitStatus>> {
+        if let Some(commands) = dry_run {
+            trace!("Skipped running the command for the dry run");
+            commands.borrow_mut().push(format!("{command:?}"));
+            Ok(None)
+        } else {
+            if self.dump_toolset_commands {
+                eprintln!("{}", command_line(command));
+            }
+            status_with_timeout(command, timeout).map(Some)
+        }
+    }
+
+    fn incremental(&self, metadata: &Value) -> bool {
+        self.incremental
+            || metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("incremental"))
+                .and_then(|i| i.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn verify_manifest(&self, metadata: &Value) -> bool {
+        self.verify_manifest
+            || metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("verify-manifest"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn wxs_variable_check(&self, metadata: &Value) -> bool {
+        self.wxs_variable_check
+            || metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("wxs-variable-check"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn include_build_metadata(&self, metadata: &Value) -> bool {
+        self.include_build_metadata
+            || metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("include-build-metadata"))
+                .and_then(|i| i.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn quiet_tools(&self, metadata: &Value) -> bool {
+        self.quiet_tools
+            || metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("quiet-tools"))
+                .and_then(|q| q.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn retain_symbols(&self, metadata: &Value) -> Result<bool> {
+        let value = if let Some(ref r) = self.retain_symbols {
+            trace!("Retaining the symbols has been explicitly specified");
+            r.to_owned()
+        } else if let Some(pkg_meta_wix_retain_symbols) = metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("retain-symbols"))
+        {
+            trace!("Retaining the symbols has been specified in the metadata");
+            match pkg_meta_wix_retain_symbols {
+                Value::Bool(true) => String::from("yes"),
+                Value::Bool(false) => String::from("no"),
+                v => v.as_str().map(String::from).ok_or_else(|| {
+                    Error::Generic(format!(
+                        "The '{v}' value for the 'retain-symbols' field in the \
+                         metadata is not a string or a boolean"
+                    ))
+                })?,
+            }
+        } else {
+            trace!("Suppressing the symbols by default");
+            return Ok(false);
+        };
+        match value.trim().to_lowercase().as_str() {
+            "yes" => Ok(true),
+            "no" => Ok(false),
+            _ => Err(Error::Generic(format!(
+                "The '{value}' value for retaining the symbols is invalid. Please use \
+                 either 'yes' or 'no'."
+            ))),
+        }
+    }
+
+    fn strip(&self, metadata: &Value) -> bool {
+        self.strip
+            || metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("strip"))
+                .and_then(|s| s.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn arp_size(&self, metadata: &Value) -> Result<Option<u64>> {
+        if let Some(ref s) = self.arp_size {
+            trace!("An estimated size has been explicitly specified");
+            s.trim().parse::<u64>().map(Some).map_err(|_| {
+                Error::Generic(format!(
+                    "The '{s}' estimated size is invalid. The estimated size must be a whole \
+                     number of kilobytes."
+                ))
+            })
+        } else if let Some(pkg_meta_wix_arp_size) = metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("arp-size"))
+        {
+            trace!("An estimated size has been specified in the metadata");
+            pkg_meta_wix_arp_size.as_u64().map(Some).ok_or_else(|| {
+                Error::Generic(format!(
+                    "The '{pkg_meta_wix_arp_size}' estimated size in the 'arp-size' field is \
+                     invalid. The estimated size must be a whole number of kilobytes."
+                ))
+            })
+        } else {
+            trace!("The estimated size is calculated from the WiX Source files");
+            Ok(None)
+        }
+    }
+
+    fn reinstall_mode(&self, metadata: &Value) -> Result<Option<String>> {
+        let mode = if let Some(ref r) = self.reinstall_mode {
+            trace!("A reinstall mode has been explicitly specified");
+            r.to_owned()
+        } else if let Some(pkg_meta_wix_reinstall_mode) = metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("reinstall-mode"))
+            .and_then(|r| r.as_str())
+        {
+            trace!("A reinstall mode has been specified in the metadata");
+            pkg_meta_wix_reinstall_mode.to_owned()
+        } else {
+            trace!("Using the Windows Installer default reinstall mode");
+            return Ok(None);
+        };
+        if mode.is_empty()
+            || !mode
+                .chars()
+                .all(|c| REINSTALL_MODE_FLAGS.contains(c.to_ascii_lowercase()))
+        {
+            return Err(Error::Generic(format!(
+                "The '{mode}' reinstall mode is invalid. The reinstall mode must only contain \
+                 the following letters: {REINSTALL_MODE_FLAGS}. See the Windows Installer \
+                 documentation for the REINSTALLMODE property for more information."
+            )));
+        }
+        Ok(Some(mode.to_ascii_lowercase()))
+    }
+
     /// Get the value of CargoTargetBinDir
     ///
     /// If it's explicitly set, just use that.
@@ -1037,39 +3661,45 @@ impl Execution {
     // This does not support default-target. Ideally we would use cargo
     // --unit-graph to figure this out without having to second-guess the
     // compiler. Unfortunately, cargo --unit-graph is unstable.
-    fn target(&self) -> Result<Target> {
-        if let Some(t) = &self.target {
-            Ok(Target {
-                triple: t.clone(),
-                arg: Some(t.clone()),
-            })
-        } else {
-            let output = Command::new("rustc")
-                .args(["--version", "--verbose"])
-                .output()?;
-            for line in output.stdout.split(|b| *b == b'\n') {
-                let mut line_elt = line.splitn(2, |b| *b == b':');
-                let first = line_elt.next();
-                let second = line_elt.next();
-                if let (Some(b"host"), Some(host_triple)) = (first, second) {
-                    let s = String::from_utf8(host_triple.to_vec()).map_err(|_| {
-                        Error::Generic(
-                            "Failed to parse output of the 'rustc --verbose \
-                            --version' command: invalid UTF8"
-                                .to_string(),
-                        )
-                    });
-                    return Ok(Target {
-                        triple: s?.trim().to_string(),
-                        arg: None,
-                    });
-                }
-            }
-            Err(Error::Generic(
-                "Failed to parse output of the 'rustc --verbose --version' \
-                command"
-                    .to_string(),
-            ))
+    fn host_target(&self) -> Result<Target> {
+        let output = Command::new("rustc")
+            .args(["--version", "--verbose"])
+            .output()?;
+        for line in output.stdout.split(|b| *b == b'\n') {
+            let mut line_elt = line.splitn(2, |b| *b == b':');
+            let first = line_elt.next();
+            let second = line_elt.next();
+            if let (Some(b"host"), Some(host_triple)) = (first, second) {
+                let s = String::from_utf8(host_triple.to_vec()).map_err(|_| {
+                    Error::Generic(
+                        "Failed to parse output of the 'rustc --verbose \
+                        --version' command: invalid UTF8"
+                            .to_string(),
+                    )
+                });
+                return Ok(Target {
+                    triple: s?.trim().to_string(),
+                    arg: None,
+                });
+            }
+        }
+        Err(Error::Generic(
+            "Failed to parse output of the 'rustc --verbose --version' \
+            command"
+                .to_string(),
+        ))
+    }
+
+    fn targets(&self) -> Result<Vec<Target>> {
+        match &self.targets {
+            Some(t) if !t.is_empty() => Ok(t
+                .iter()
+                .map(|t| Target {
+                    triple: t.clone(),
+                    arg: Some(t.clone()),
+                })
+                .collect()),
+            _ => self.host_target().map(|t| vec![t]),
         }
     }
 
@@ -1101,6 +3731,23 @@ impl Execution {
     }
 
     fn wxs_sources(&self, package: &Package) -> Result<Vec<PathBuf>> {
+        if let Some(paths) = self.wxs_sources.as_ref() {
+            trace!(
+                "Using only the explicitly set WiX Source files and ignoring the '{}' \
+                 folder and any included files",
+                WIX
+            );
+            for p in paths {
+                if !p.is_file() {
+                    return Err(Error::Generic(format!(
+                        "The '{}' path is not a WiX Source (wxs) file. Please check the \
+                         path and ensure it is to an existing file.",
+                        p.display()
+                    )));
+                }
+            }
+            return Ok(paths.clone());
+        }
         let project_wix_dir = package
             .manifest_path
             .parent()
@@ -1112,7 +3759,13 @@ impl Execution {
             })
             .map(|d| PathBuf::from(d).join(WIX))?;
         let mut wix_sources = {
-            if project_wix_dir.exists() {
+            if self.no_default_wix_folder(&package.metadata) {
+                trace!(
+                    "Skipping the WiX Source files in the default '{}' folder",
+                    WIX
+                );
+                Vec::new()
+            } else if project_wix_dir.exists() {
                 std::fs::read_dir(project_wix_dir)?
                     .filter(|r| r.is_ok())
                     .map(|r| r.unwrap().path())
@@ -1153,7 +3806,11 @@ impl Execution {
             .and_then(|i| i.as_array())
             .map(|a| {
                 a.iter()
-                    .map(|s| s.as_str().map(PathBuf::from).unwrap())
+                    .map(|s| {
+                        s.as_str()
+                            .map(|s| super::package_relative_path(package, s))
+                            .unwrap()
+                    })
                     .collect::<Vec<PathBuf>>()
             })
         {
@@ -1194,28 +3851,14 @@ impl Execution {
         }
     }
 
-    /// Attempts to convert a Rust SemVer version to the format WiX desires.
-    ///
-    /// WiX only supports numbers in versions, with a format of "x.x.x.x"
-    /// WiX itself requires each component to be an integer from 0 to 65534 (inclusive).
-    /// However the first 3 parts are forwarded to Windows as a [ProductVersion][0],
-    /// which interprets them as "major.minor.build" and states:
-    ///
-    /// > The major version and has a maximum value of 255.
-    /// > The minor version and has a maximum value of 255.
-    /// > The build version or the update version and has a maximum value of 65,535.
-    ///
-    /// So we take the intersection of these requirements, and shove the rust "major.minor.patch"
-    /// format into it. This leaves the more freeform "prerelease" and "build" components of a
-    /// SemVer Version to get squeezed into the 4th value.
-    ///
-    /// The 4th value is seemingly just a bonus value that WiX keeps to itself, so it's not
-    /// terribly important that we get it perfect. We therefore attempt to heuritistically
-    /// parse out a numeric "prerelease version" based on common formats.
-    ///
-    /// [0]: https://learn.microsoft.com/en-us/windows/win32/msi/productversion
+    /// Selects the version for the installer and converts it to the format
+    /// WiX desires with the `wix_version` function.
     fn version(&self, package: &Package) -> Result<String> {
-        // Select the version
+        wix_version(&self.selected_version(package)?)
+    }
+
+    /// Selects the version for the installer without any conversion.
+    fn selected_version(&self, package: &Package) -> Result<Version> {
         let version = if let Some(ref v) = self.version {
             Version::parse(v).map_err(Error::from)?
         } else if let Some(pkg_meta_wix_version) = package
@@ -1229,885 +3872,5710 @@ impl Execution {
         } else {
             package.version.clone()
         };
+        Ok(version)
+    }
+}
 
-        // validate basic parts
-        if version.major > 255 {
-            return Err(Error::Generic(format!(
-                "The app's major version {} can't be greater than 255 for an msi",
-                version.major
-            )));
-        }
-        if version.minor > 255 {
-            return Err(Error::Generic(format!(
-                "The app's minor version {} can't be greater than 255 for an msi",
-                version.minor
-            )));
-        }
-        if version.patch > 65534 {
-            return Err(Error::Generic(format!(
-                "The app's patch version {} can't be greater than 65534 for an msi",
-                version.patch
-            )));
+/// Extracts the first entry of the section of a changelog for a version.
+///
+/// The section begins with a heading, i.e. a line starting with `#`, that
+/// contains the version, such as `## [1.2.3] - 2023-01-01` or `## v1.2.3`, and
+/// it ends at the next heading of the same or higher level. The entry is the
+/// first line of the section that is not a heading, without a list marker, and
+/// it is truncated to [`MAX_CHANGELOG_LENGTH`] characters. `None` is returned
+/// if a heading for the version does not exist or the section is empty.
+fn changelog_section(content: &str, version: &str) -> Option<String> {
+    let mut lines = content.lines();
+    let level = lines.by_ref().find_map(|line| {
+        let trimmed = line.trim_start();
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if level > 0 && heading_matches(&trimmed[level..], version) {
+            Some(level)
+        } else {
+            None
         }
+    })?;
+    let entry = lines
+        .take_while(|line| {
+            let trimmed = line.trim_start();
+            let l = trimmed.chars().take_while(|c| *c == '#').count();
+            l == 0 || l > level
+        })
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))?
+        .trim_start_matches(['-', '*', '+'])
+        .trim_start();
+    if entry.chars().count() > MAX_CHANGELOG_LENGTH {
+        let mut truncated: String = entry.chars().take(MAX_CHANGELOG_LENGTH - 3).collect();
+        truncated.push_str("...");
+        Some(truncated)
+    } else if entry.is_empty() {
+        None
+    } else {
+        Some(entry.to_owned())
+    }
+}
 
-        // Attempt to validate + convert the prerelease parts
-        let needs_prerelease_handling = !version.build.is_empty() || !version.pre.is_empty();
-        if needs_prerelease_handling {
-            // This mess is trying 3 approaches in sequence:
-            //
-            // * parse as if it's `1.2.3-4`
-            // * parse as if it's `1.2.3-prerelease.4`
-            // * parse as if it's `1.2.3-prerelease+4`
-            let bonus = version
-                .pre
-                .parse::<u64>()
-                .or_else(|e| {
-                    if let Some((_, dotted)) = version.pre.split_once('.') {
-                        dotted.parse::<u64>()
-                    } else {
-                        Err(e)
-                    }
-                })
-                .or_else(|_| version.build.parse::<u64>());
+/// Determines if the text of a changelog heading contains the version as a
+/// whole word, i.e. `1.2.3` matches `[1.2.3]` and `v1.2.3` but not `11.2.3`
+/// or `1.2.34`.
+fn heading_matches(heading: &str, version: &str) -> bool {
+    heading.match_indices(version).any(|(i, _)| {
+        let before = heading[..i].chars().next_back();
+        let after = heading[i + version.len()..].chars().next();
+        !before.is_some_and(|c| c.is_ascii_digit() || c == '.')
+            && !after.is_some_and(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+    })
+}
 
-            let bonus = if let Ok(bonus) = bonus {
-                bonus
+/// Gets the culture for the locale of the system.
+///
+/// The `en-US` culture is used if the locale cannot be determined or it is not
+/// a supported culture.
+pub(crate) fn system_culture() -> Cultures {
+    let locale = system_locale();
+    debug!("locale = {:?}", locale);
+    if let Some(culture) = locale.as_deref().and_then(culture_from_locale) {
+        culture
+    } else {
+        warn!(
+            "The '{}' system locale is not a supported culture. The '{}' culture is used \
+             instead.",
+            locale.as_deref().unwrap_or("unknown"),
+            Cultures::EnUs
+        );
+        Cultures::EnUs
+    }
+}
+
+/// Gets the name of the locale of the system.
+///
+/// The POSIX locale environment variables are checked first, in order of
+/// precedence, and then the user interface culture is obtained from PowerShell
+/// on Windows.
+fn system_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|key| env::var(key).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+        .or_else(|| {
+            if cfg!(windows) {
+                Command::new("powershell")
+                    .args(["-NoProfile", "-Command", "(Get-UICulture).Name"])
+                    .output()
+                    .ok()
+                    .filter(|output| output.status.success())
+                    .and_then(|output| String::from_utf8(output.stdout).ok())
+                    .map(|name| name.trim().to_owned())
+                    .filter(|name| !name.is_empty())
             } else {
-                return Err(Error::Generic(format!(
-                    "The app's version {} is a prerelease, but we couldn't convert the prerelease \
-                     components to an integer. We recommend a format like 1.2.3-prerelease.4, \
-                     as we can map it to the 1.2.3.4 format that works for an msi.",
-                    version,
-                )));
-            };
-            if bonus > 65534 {
-                return Err(Error::Generic(format!(
-                    "The app's prerelease version {} can't be greater than 65534 for an msi",
-                    bonus
-                )));
+                None
             }
+        })
+}
 
-            Ok(format!(
-                "{}.{}.{}.{}",
-                version.major, version.minor, version.patch, bonus
-            ))
-        } else {
-            Ok(format!(
-                "{}.{}.{}",
-                version.major, version.minor, version.patch
-            ))
-        }
+/// Converts the name of a locale to a culture.
+///
+/// Both POSIX, e.g. `fr_FR.UTF-8`, and Windows, e.g. `fr-FR`, locale names are
+/// supported.
+fn culture_from_locale(locale: &str) -> Option<Cultures> {
+    let name = locale
+        .split(['.', '@'])
+        .next()
+        .unwrap_or(locale)
+        .replace('_', "-");
+    Cultures::from_str(&name).ok()
+}
+
+/// Gets the supported culture with the same language as a culture that is not
+/// supported by the WixUI extension.
+///
+/// The language is the first subtag of the culture code, e.g. `fr` for
+/// `fr-CA`. For Chinese, the script or region selects between the simplified
+/// and traditional cultures.
+fn nearest_culture(culture: &str) -> Option<Cultures> {
+    let culture = culture.trim().to_lowercase().replace('_', "-");
+    let mut subtags = culture.split('-');
+    let language = subtags.next()?;
+    let rest: Vec<&str> = subtags.collect();
+    let nearest = match language {
+        "ar" => Cultures::ArSa,
+        "bg" => Cultures::BgBg,
+        "ca" => Cultures::CaEs,
+        "cs" => Cultures::CsCz,
+        "da" => Cultures::DaDk,
+        "de" => Cultures::DeDe,
+        "el" => Cultures::ElGr,
+        "en" => Cultures::EnUs,
+        "es" => Cultures::EsEs,
+        "et" => Cultures::EtEe,
+        "fi" => Cultures::FiFi,
+        "fr" => Cultures::FrFr,
+        "he" => Cultures::HeIl,
+        "hi" => Cultures::HiIn,
+        "hr" => Cultures::HrHr,
+        "hu" => Cultures::HuHu,
+        "id" => Cultures::IdId,
+        "it" => Cultures::ItIt,
+        "ja" => Cultures::JaJp,
+        "kk" => Cultures::KkKz,
+        "ko" => Cultures::KoKr,
+        "lt" => Cultures::LtLt,
+        "lv" => Cultures::LvLv,
+        "nb" | "nn" | "no" => Cultures::NbNo,
+        "nl" => Cultures::NlNl,
+        "pl" => Cultures::PlPl,
+        "pt" => Cultures::PtPt,
+        "ro" => Cultures::RoRo,
+        "ru" => Cultures::RuRu,
+        "sk" => Cultures::SkSk,
+        "sl" => Cultures::SlSi,
+        "sr" => Cultures::SrLatnCs,
+        "sv" => Cultures::SvSe,
+        "th" => Cultures::ThTh,
+        "tr" => Cultures::TrTr,
+        "uk" => Cultures::UkUa,
+        "vi" => Cultures::ViVn,
+        "zh" if rest.contains(&"hant") || rest.contains(&"tw") => Cultures::ZhTw,
+        "zh" if rest.contains(&"hk") || rest.contains(&"mo") => Cultures::ZhHk,
+        "zh" => Cultures::ZhCn,
+        _ => return None,
+    };
+    Some(nearest)
+}
+
+/// The IDs of the common strings of the WixUI dialogs, i.e. the buttons and
+/// the titles and descriptions of the dialogs in the WixUI dialog sets.
+const WIXUI_STRING_IDS: &[&str] = &[
+    "CancelDlgText",
+    "CancelDlg_Title",
+    "CustomizeDlgBrowse",
+    "CustomizeDlgDescription",
+    "CustomizeDlgDiskCost",
+    "CustomizeDlgReset",
+    "CustomizeDlgText",
+    "CustomizeDlgTitle",
+    "CustomizeDlg_Title",
+    "ExitDialogDescription",
+    "ExitDialogTitle",
+    "ExitDialog_Title",
+    "FatalErrorDescription1",
+    "FatalErrorDescription2",
+    "FatalErrorTitle",
+    "FatalError_Title",
+    "InstallDirDlgChange",
+    "InstallDirDlgDescription",
+    "InstallDirDlgFolderLabel",
+    "InstallDirDlgTitle",
+    "InstallDirDlg_Title",
+    "LicenseAgreementDlgAcceptCheckBox",
+    "LicenseAgreementDlgDescription",
+    "LicenseAgreementDlgTitle",
+    "LicenseAgreementDlg_Title",
+    "MaintenanceTypeDlgChangeButton",
+    "MaintenanceTypeDlgDescription",
+    "MaintenanceTypeDlgRemoveButton",
+    "MaintenanceTypeDlgRepairButton",
+    "MaintenanceTypeDlgTitle",
+    "MaintenanceTypeDlg_Title",
+    "MaintenanceWelcomeDlgDescription",
+    "MaintenanceWelcomeDlgTitle",
+    "MaintenanceWelcomeDlg_Title",
+    "ProgressDlgStatusLabel",
+    "ProgressDlgTextInstalling",
+    "ProgressDlgTitleInstalling",
+    "ProgressDlg_Title",
+    "UserExitDescription1",
+    "UserExitDescription2",
+    "UserExitTitle",
+    "UserExit_Title",
+    "VerifyReadyDlgInstall",
+    "VerifyReadyDlgInstallText",
+    "VerifyReadyDlgInstallTitle",
+    "VerifyReadyDlg_Title",
+    "WelcomeDlgDescription",
+    "WelcomeDlgTitle",
+    "WelcomeDlg_Title",
+    "WixUIBack",
+    "WixUICancel",
+    "WixUIFinish",
+    "WixUIIgnore",
+    "WixUINext",
+    "WixUINo",
+    "WixUIOK",
+    "WixUIPrint",
+    "WixUIRetry",
+    "WixUIYes",
+];
+
+/// Gets the value of the `-cultures` argument for the linker (light.exe).
+///
+/// The English, United States culture is added as a fallback for a culture
+/// that the WixUI extension does not have localized strings for, so the
+/// strings that are not in the localization (wxl) file are displayed in
+/// English instead of failing to link.
+fn linker_cultures(culture: &Cultures) -> String {
+    if culture.is_wixui_localized() {
+        format!("-cultures:{culture}")
+    } else {
+        format!("-cultures:{culture};{}", Cultures::EnUs)
     }
 }
 
-impl Default for Execution {
-    fn default() -> Self {
-        Builder::new().build()
+/// Gets the IDs of the strings in a WiX localization (wxl) file.
+fn localized_string_ids(locale: &Path) -> Result<Vec<String>> {
+    let file = std::fs::File::open(locale)?;
+    let mut decoder = encoding_rs_io::DecodeReaderBytes::new(file);
+    let mut content = String::new();
+    decoder.read_to_string(&mut content)?;
+    let package = sxd_document::parser::parse(&content)?;
+    let document = package.as_document();
+    let context = sxd_xpath::Context::new();
+    // The assumption is that the following cannot fail because the path is known to be valid at
+    // compile-time.
+    let xpath = sxd_xpath::Factory::new()
+        .build("//*[local-name()='String']/@Id")
+        .unwrap()
+        .unwrap();
+    match xpath.evaluate(&context, document.root())? {
+        sxd_xpath::Value::Nodeset(nodes) => Ok(nodes
+            .document_order()
+            .iter()
+            .map(|n| n.string_value())
+            .collect()),
+        _ => Ok(Vec::new()),
     }
 }
 
-/// The kind of WiX Object (wixobj) file.
-#[derive(Debug, PartialEq, Eq)]
-pub enum WixObjKind {
-    /// A WiX Object (wixobj) file that ultimately links back to a WiX Source
-    /// (wxs) file with a [`bundle`] tag.
-    ///
-    /// [`bundle`]: https://wixtoolset.org/documentation/manual/v3/xsd/wix/bundle.html
-    Bundle,
-    /// A WiX Object (wixobj) file that ultimately links back to a WiX Source
-    /// (wxs) file with a [`fragment`] tag.
-    ///
-    /// [`fragment`]: https://wixtoolset.org/documentation/manual/v3/xsd/wix/fragment.html
-    Fragment,
-    /// A WiX Object (wixobj) file that ultimately links back to a WiX Source
-    /// (wxs) file with a [`product`] tag.
-    ///
-    /// [`product`]: https://wixtoolset.org/documentation/manual/v3/xsd/wix/product.html
-    Product,
+/// Reports the common WixUI strings that are displayed in English because the
+/// WixUI extension is not localized for the culture and the strings are not in
+/// the localization (wxl) file.
+fn check_localization(culture: &Cultures, locale: Option<&Path>, strict: bool) -> Result<()> {
+    if culture.is_wixui_localized() {
+        info!(
+            "The WixUI extension has localized strings for the '{}' culture",
+            culture
+        );
+        return Ok(());
+    }
+    let ids = match locale {
+        Some(locale) => localized_string_ids(locale)?,
+        None => Vec::new(),
+    };
+    debug!("ids = {:?}", ids);
+    let missing: Vec<&str> = WIXUI_STRING_IDS
+        .iter()
+        .copied()
+        .filter(|id| !ids.iter().any(|i| i == id))
+        .collect();
+    if missing.is_empty() {
+        info!(
+            "The localization file has all of the common WixUI strings for the '{}' culture",
+            culture
+        );
+        return Ok(());
+    }
+    let message = format!(
+        "The WixUI extension does not have localized strings for the '{culture}' culture, \
+         so the following strings are displayed in English unless they are added to the \
+         localization file with the '-l,--locale' option: {}",
+        missing.join(", ")
+    );
+    if strict {
+        Err(Error::Generic(message))
+    } else {
+        warn!("{}", message);
+        Ok(())
+    }
 }
 
-impl WixObjKind {
-    /// Determines if the WiX Object (wixobj) file kind is a [`bundle`].
-    ///
-    /// # Examples
-    ///
-    /// A WiX Object (wixobj) file identified as a WXS Source (wxs) file
-    /// containing a [`bundle`] tag.
-    ///
-    /// ```
-    /// use wix::create::WixObjKind;
-    ///
-    /// assert!(WixObjKind::Bundle.is_bundle())
-    /// ```
-    ///
-    /// A WiX Object (wixobj) file identified as a WXS Source (wxs) file
-    /// containing a [`product`] tag.
-    ///
-    /// ```
-    /// use wix::create::WixObjKind;
-    ///
-    /// assert!(!WixObjKind::Product.is_bundle())
-    /// ```
-    ///
-    /// [`bundle`]: https://wixtoolset.org/documentation/manual/v3/xsd/wix/bundle.html
-    /// [`product`]: https://wixtoolset.org/documentation/manual/v3/xsd/wix/product.html
-    pub fn is_bundle(&self) -> bool {
-        match *self {
-            Self::Bundle => true,
-            Self::Fragment => false,
-            Self::Product => false,
-        }
-    }
+/// Checks if a value is a valid identifier, such as the `Id` attribute of a
+/// tag, for the WiX Toolset.
+fn is_wix_identifier(value: &str) -> bool {
+    let mut chars = value.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
 }
 
-impl FromStr for WixObjKind {
-    type Err = crate::Error;
+/// Creates a command to run a command line with the system shell.
+fn shell_command(command: &str) -> Command {
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
 
-    fn from_str(value: &str) -> Result<Self> {
-        match &*value.to_lowercase() {
-            "bundle" => Ok(Self::Bundle),
-            "fragment" => Ok(Self::Fragment),
-            "product" => Ok(Self::Product),
-            v => Err(Self::Err::Generic(format!(
-                "Unknown '{v}' tag name from a WiX Object (wixobj) file."
-            ))),
-        }
+        let mut shell = Command::new("cmd");
+        shell.arg("/C").raw_arg(command);
+        shell
+    }
+    #[cfg(not(windows))]
+    {
+        let mut shell = Command::new("sh");
+        shell.arg("-c").arg(command);
+        shell
     }
 }
 
-impl TryFrom<&PathBuf> for WixObjKind {
-    type Error = crate::Error;
-
-    fn try_from(path: &PathBuf) -> Result<Self> {
-        let file = std::fs::File::open(path)?;
-        let mut decoder = encoding_rs_io::DecodeReaderBytes::new(file);
-        let mut content = String::new();
-        decoder.read_to_string(&mut content)?;
-        Self::try_from(content.as_str())
+/// Splits a property in the `NAME=VALUE` form into its name and value.
+///
+/// The value can be empty, but the name must be a valid WiX identifier.
+fn parse_property(property: &str) -> Result<(String, String)> {
+    let (name, value) = property.split_once('=').ok_or_else(|| {
+        Error::Generic(format!(
+            "The '{property}' property is invalid. A property must have the NAME=VALUE form."
+        ))
+    })?;
+    if !is_wix_identifier(name) {
+        return Err(Error::Generic(format!(
+            "The '{name}' property name is invalid. A name must begin with a letter or an \
+             underscore and only contain letters, digits, underscores, and periods."
+        )));
     }
+    Ok((name.to_owned(), value.to_owned()))
 }
 
-impl TryFrom<&str> for WixObjKind {
-    type Error = crate::Error;
-
-    fn try_from(content: &str) -> Result<Self> {
-        let package = sxd_document::parser::parse(content)?;
-        let document = package.as_document();
-        let mut context = sxd_xpath::Context::new();
-        context.set_namespace("wix", "http://schemas.microsoft.com/wix/2006/objects");
-        // The assumption is that the following cannot fail because the path is known to be valid at
-        // compile-time.
-        let xpath = sxd_xpath::Factory::new()
-            .build("/wix:wixObject/wix:section/@type")
-            .unwrap()
-            .unwrap();
-        let value = xpath.evaluate(&context, document.root())?.string();
-        Self::from_str(&value)
+/// Gets the short hash of the current commit for the git repository that
+/// contains a folder.
+///
+/// `None` is returned if the folder is not in a git repository or git is not
+/// available.
+fn git_hash(directory: &Path) -> Option<String> {
+    let output = Command::new(GIT)
+        .arg("rev-parse")
+        .arg("--short")
+        .arg("HEAD")
+        .current_dir(directory)
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|err| trace!("The {} application could not be run: {}", GIT, err))
+        .ok()?;
+    if !output.status.success() {
+        trace!(
+            "The '{}' folder is not in a git repository",
+            directory.display()
+        );
+        return None;
+    }
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+    if hash.is_empty() {
+        None
+    } else {
+        Some(hash)
     }
 }
 
-/// The kinds of installers that can be created using the WiX compiler
-/// (candle.exe) and linker (light.exe).
-#[derive(Debug, Default, PartialEq, Eq)]
-pub enum InstallerKind {
-    /// An executable is used when an [Installation Package Bundle] is created.
-    ///
-    /// [Installation Package Bundle]: https://wixtoolset.org/documentation/manual/v3/bundle/
-    Exe,
-    /// A Microsoft installer. This is the more common and typical installer to be created.
-    #[default]
-    Msi,
+/// Gets the names of the package's binaries.
+///
+/// The package's name is used if the package does not have any `[[bin]]`
+/// sections.
+fn binary_names(package: &Package) -> Vec<&str> {
+    let mut names: Vec<&str> = package
+        .targets
+        .iter()
+        .filter(|t| t.kind.iter().any(|k| k == "bin"))
+        .map(|t| t.name.as_str())
+        .collect();
+    if names.is_empty() {
+        names.push(package.name.as_str());
+    }
+    names
 }
 
-impl InstallerKind {
-    /// Gets the file extension _without_ the dot separator.
-    ///
-    /// # Examples
-    ///
-    /// The extension for an installer of an [Installation Package Bundle]. Also
-    /// see the [`EXE_FILE_EXTENSION`] constant.
-    ///
-    /// ```
-    /// use wix::create::InstallerKind;
-    ///
-    /// assert_eq!(InstallerKind::Exe.extension(), "exe")
-    /// ```
-    ///
-    /// The extension for a typical Microsoft installer. Also see the
-    /// [`MSI_FILE_EXTENSION`] constant.
-    ///
-    /// ```
-    /// use wix::create::InstallerKind;
-    ///
-    /// assert_eq!(InstallerKind::Msi.extension(), "msi")
-    /// ```
-    ///
-    /// [Installation Package Bundle]: https://wixtoolset.org/documentation/manual/v3/bundle/
-    /// [`EXE_FILE_EXTENSION`]: lib.html#exe-file-extension
-    /// [`MSI_FILE_EXTENSION`]: lib.html#msi-file-extension
-    pub fn extension(&self) -> &'static str {
-        match *self {
-            Self::Exe => EXE_FILE_EXTENSION,
-            Self::Msi => MSI_FILE_EXTENSION,
+/// Computes a hash of the inputs for an installer.
+///
+/// The hash is the SHA-256 hash of the cargo-wix version, the arguments, and
+/// the path and contents of each file. Each value is prefixed with its length,
+/// so adjacent values cannot be confused. A missing file is included by its
+/// path only.
+fn input_hash(args: &[&OsStr], files: &[PathBuf]) -> Result<String> {
+    let mut hasher = Sha256::new();
+    let mut update = |bytes: &[u8]| {
+        hasher.update((bytes.len() as u64).to_le_bytes());
+        hasher.update(bytes);
+    };
+    update(env!("CARGO_PKG_VERSION").as_bytes());
+    for arg in args {
+        update(arg.to_string_lossy().as_bytes());
+    }
+    for file in files {
+        update(file.to_string_lossy().as_bytes());
+        match std::fs::read(file) {
+            Ok(content) => update(&content),
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                trace!("The '{}' input does not exist", file.display());
+            }
+            Err(err) => return Err(err.into()),
         }
     }
+    Ok(format!("{:x}", hasher.finalize()))
 }
 
-impl FromStr for InstallerKind {
-    type Err = crate::Error;
+/// Gets the path to the language transform (mst) for a culture, which is
+/// written next to the installer.
+fn transform_destination(installer: &Path, culture: &Cultures) -> PathBuf {
+    let stem = installer
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    installer.with_file_name(format!("{stem}-{culture}.{TRANSFORM_FILE_EXTENSION}"))
+}
 
-    fn from_str(value: &str) -> Result<Self> {
-        match &*value.to_lowercase() {
-            "exe" => Ok(Self::Exe),
-            "msi" => Ok(Self::Msi),
-            _ => Err(Self::Err::Generic(format!(
-                "Unknown '{value}' file extension for an installer"
-            ))),
-        }
+/// Gets the path to the installer recorded in an input hash file if the
+/// recorded hash matches and the installer exists.
+fn up_to_date_installer(input_hash_file: &Path, input_hash: &str) -> Option<PathBuf> {
+    let content = std::fs::read_to_string(input_hash_file).ok()?;
+    let mut lines = content.lines();
+    if lines.next()? != input_hash {
+        trace!("The input hash has changed");
+        return None;
+    }
+    let installer = PathBuf::from(lines.next()?);
+    if installer.exists() {
+        Some(installer)
+    } else {
+        trace!("The '{}' installer does not exist", installer.display());
+        None
     }
 }
 
-impl fmt::Display for InstallerKind {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.extension())
+/// Merges the compiler or linker arguments from the command line with the
+/// arguments from the `[package.metadata.wix]` section.
+///
+/// The arguments from the command line replace the arguments from the section,
+/// unless `append` is `true`, in which case they are added after them.
+fn merge_args(
+    cli: Option<&Vec<String>>,
+    metadata: Option<Vec<String>>,
+    append: bool,
+) -> Option<Vec<String>> {
+    match (cli, metadata) {
+        (Some(cli), Some(mut metadata)) if append => {
+            metadata.extend(cli.iter().cloned());
+            Some(metadata)
+        }
+        (Some(cli), _) => Some(cli.to_owned()),
+        (None, metadata) => metadata,
     }
 }
 
-impl TryFrom<Vec<WixObjKind>> for InstallerKind {
-    type Error = crate::Error;
+/// Checks that an installer can be written to a folder, creating the folder and
+/// any missing parent folders.
+///
+/// A file is created and immediately removed in the folder, since a read-only
+/// attribute or access control list would otherwise only be found when the
+/// linker writes the installer, i.e. after the binaries are built.
+fn check_writable(folder: &Path) -> Result<()> {
+    let unwritable = |err: std::io::Error| {
+        Error::Io(std::io::Error::new(
+            ErrorKind::Other,
+            format!(
+                "The installer cannot be written to the '{}' folder: {err}. Please check \
+                 the output path and the permissions of the folder.",
+                folder.display()
+            ),
+        ))
+    };
+    std::fs::create_dir_all(folder).map_err(unwritable)?;
+    let probe = folder.join(format!(".cargo-wix-{}", Uuid::new_v4().as_simple()));
+    std::fs::File::create(&probe).map_err(unwritable)?;
+    std::fs::remove_file(&probe)?;
+    Ok(())
+}
 
-    fn try_from(v: Vec<WixObjKind>) -> Result<Self> {
-        v.iter()
-            .fold(None, |a, v| match v {
-                WixObjKind::Bundle => Some(Self::Exe),
-                WixObjKind::Product => a.or(Some(Self::Msi)),
-                _ => a,
-            })
-            .ok_or_else(|| {
-                Self::Error::Generic(String::from(
-                    "Could not determine the installer kind based on the WiX \
-                     object files. There needs to be at least one 'product' or \
-                     'bundle' tag in the collective WiX source files (wxs).",
-                ))
-            })
+/// Gets the first path that does not exist by appending `-1`, `-2`, etc. to
+/// the file stem of the destination of an installer.
+///
+/// The destination is returned unchanged if it does not exist.
+fn incremented_destination(destination: &Path) -> PathBuf {
+    if !destination.exists() {
+        return destination.to_owned();
     }
+    let stem = destination
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = destination
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (1..)
+        .map(|n| destination.with_file_name(format!("{stem}-{n}{extension}")))
+        .find(|p| !p.exists())
+        .expect("An unused file name")
 }
 
-/// Details of the cargo build profile
-#[derive(Debug, Clone)]
-pub struct Profile {
-    /// The name of the profile to pass to `--profile=...`
-    name: String,
-    /// The name of the subdirectory of the cargo target dir that the profile will get
-    dir: String,
+/// Gets the file names of the package's binaries that are currently running.
+///
+/// Running processes can only be checked on Windows, so an empty list is
+/// always returned on other platforms.
+fn running_binaries(package: &Package) -> Result<Vec<String>> {
+    if !cfg!(windows) {
+        debug!("Checking for running binaries is only supported on Windows");
+        return Ok(Vec::new());
+    }
+    let mut running = Vec::new();
+    for name in binary_names(package) {
+        let image_name = format!("{name}.{EXE_FILE_EXTENSION}");
+        let output = Command::new(TASKLIST)
+            .arg("/FI")
+            .arg(format!("IMAGENAME eq {image_name}"))
+            .arg("/NH")
+            .arg("/FO")
+            .arg("CSV")
+            .output()?;
+        if !output.status.success() {
+            return Err(Error::Command(
+                TASKLIST,
+                output.status.code().unwrap_or(100),
+                true,
+            ));
+        }
+        if tasklist_contains(&String::from_utf8_lossy(&output.stdout), &image_name) {
+            trace!("The '{}' binary is running", image_name);
+            running.push(image_name);
+        }
+    }
+    Ok(running)
 }
 
-/// Details of the cargo build target
-#[derive(Debug, Clone)]
-pub struct Target {
-    /// The name of the target triple being built
-    triple: String,
-    /// If an explicit --target flag is being passed to Cargo, this is it
-    arg: Option<String>,
+/// Checks if the CSV output of the `tasklist` utility contains a process for
+/// an image name.
+///
+/// The image name is the first field of each line. If no process matches the
+/// filter, then an informational message is printed instead of a CSV line.
+fn tasklist_contains(output: &str, image_name: &str) -> bool {
+    output.lines().any(|line| {
+        line.split(',')
+            .next()
+            .map(|field| field.trim().trim_matches('"'))
+            .is_some_and(|field| field.eq_ignore_ascii_case(image_name))
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Copies WiX Object (wixobj) files to a folder, creating the folder if it
+/// does not exist.
+fn copy_wixobj_sources(wixobj_sources: &[PathBuf], destination: &Path) -> Result<()> {
+    std::fs::create_dir_all(destination)?;
+    for source in wixobj_sources {
+        let file_name = source.file_name().ok_or_else(|| {
+            Error::Generic(format!(
+                "The '{}' WiX object file does not have a file name",
+                source.display()
+            ))
+        })?;
+        trace!("Copying the '{}' WiX object file", source.display());
+        std::fs::copy(source, destination.join(file_name))?;
+    }
+    Ok(())
+}
 
-    mod builder {
-        use super::*;
+/// Converts an error from running the WiX compiler (candle.exe) into a
+/// helpful error if the compiler cannot be found.
+fn compiler_error(err: std::io::Error) -> Error {
+    if err.kind() == ErrorKind::NotFound {
+        Error::Generic(format!(
+            "The compiler application ({WIX_COMPILER}) could not be found in the PATH environment \
+            variable. Please check the WiX Toolset (http://wixtoolset.org/) is \
+            installed and check the WiX Toolset's '{BINARY_FOLDER_NAME}' folder has been added to the PATH \
+            system environment variable, the {WIX_PATH_KEY} system environment variable exists, or use \
+            the '-b,--bin-path' command line argument."
+        ))
+    } else {
+        err.into()
+    }
+}
 
-        #[test]
-        fn defaults_are_correct() {
-            let actual = Builder::new();
-            assert!(actual.bin_path.is_none());
-            assert!(actual.capture_output);
-            assert!(actual.compiler_args.is_none());
-            assert!(actual.culture.is_none());
-            assert!(!actual.debug_build);
-            assert!(!actual.debug_name);
-            assert!(actual.includes.is_none());
-            assert!(actual.input.is_none());
-            assert!(actual.linker_args.is_none());
-            assert!(actual.locale.is_none());
-            assert!(actual.name.is_none());
-            assert!(!actual.no_build);
-            assert!(actual.output.is_none());
-            assert!(actual.version.is_none());
-        }
+/// Converts an error from running the WiX linker (light.exe) into a helpful
+/// error if the linker cannot be found.
+fn linker_error(err: std::io::Error) -> Error {
+    if err.kind() == ErrorKind::NotFound {
+        Error::Generic(format!(
+            "The linker application ({WIX_LINKER}) could not be found in the PATH environment \
+             variable. Please check the WiX Toolset (http://wixtoolset.org/) is \
+             installed and check the WiX Toolset's '{BINARY_FOLDER_NAME}' folder has been added to the PATH \
+             environment variable, the {WIX_PATH_KEY} system environment variable exists, or use the \
+             '-b,--bin-path' command line argument."
+        ))
+    } else {
+        err.into()
+    }
+}
 
-        #[test]
-        fn bin_path_works() {
-            const EXPECTED: &str = "C:\\Wix Toolset\\bin";
-            let mut actual = Builder::new();
-            actual.bin_path(Some(EXPECTED));
-            assert_eq!(actual.bin_path, Some(EXPECTED));
+/// Gets the arguments of the linker for linking the installer for another
+/// culture.
+///
+/// The `-cultures:` argument is replaced with the cultures and the value of
+/// the output (`-out`) argument is replaced with the destination.
+fn variant_linker_args<'a, I>(mut args: I, cultures: &str, destination: &Path) -> Vec<OsString>
+where
+    I: Iterator<Item = &'a OsStr>,
+{
+    let mut variant_args = Vec::new();
+    while let Some(arg) = args.next() {
+        if arg.to_string_lossy().starts_with("-cultures:") {
+            variant_args.push(OsString::from(cultures));
+        } else if arg == "-out" {
+            args.next();
+            variant_args.push(arg.to_os_string());
+            variant_args.push(destination.as_os_str().to_os_string());
+        } else {
+            variant_args.push(arg.to_os_string());
         }
+    }
+    variant_args
+}
 
-        #[test]
-        fn target_bin_dir_works() {
-            const EXPECTED: &str = "target\\special\\build\\";
-            let mut actual = Builder::new();
-            actual.target_bin_dir(Some(EXPECTED));
-            assert_eq!(actual.target_bin_dir, Some(EXPECTED));
+/// Gets the arguments of the compiler for only running its preprocessor.
+///
+/// The output (`-o`) argument and its value are removed, along with the
+/// trailing WiX Source (wxs) files, which are the last `source_count`
+/// arguments.
+fn preprocessor_args<'a, I>(args: I, source_count: usize) -> Vec<OsString>
+where
+    I: Iterator<Item = &'a OsStr>,
+{
+    let mut args: Vec<&OsStr> = args.collect();
+    args.truncate(args.len().saturating_sub(source_count));
+    let mut preprocessor_args = Vec::with_capacity(args.len());
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "-o" {
+            args.next();
+        } else {
+            preprocessor_args.push(arg.to_os_string());
         }
+    }
+    preprocessor_args
+}
 
-        #[test]
-        fn capture_output_works() {
-            let mut actual = Builder::new();
-            actual.capture_output(false);
-            assert!(!actual.capture_output);
+/// Writes the path and content of each WiX Source (wxs) file.
+///
+/// The content of each file is preceded by an XML comment with its path, so
+/// the output of multiple files remains readable.
+fn print_wxs_sources<W: Write>(wxs_sources: &[PathBuf], writer: &mut W) -> Result<()> {
+    for source in wxs_sources {
+        let content = read_wxs_source(source)?;
+        writeln!(writer, "<!-- {} -->", source.display())?;
+        write!(writer, "{content}")?;
+        if !content.ends_with('\n') {
+            writeln!(writer)?;
         }
+    }
+    Ok(())
+}
 
-        #[test]
-        fn compiler_args_with_single_value_works() {
-            const EXPECTED: &str = "-nologo";
-            let mut actual = Builder::new();
-            actual.compiler_args(Some(vec![EXPECTED]));
-            assert_eq!(actual.compiler_args, Some(vec![EXPECTED]));
+/// Checks if any of the WiX Source (wxs) files use the compiler variable for
+/// the product icon extracted from the main binary.
+///
+/// The files are read in order and the remaining files are not read once a
+/// file that uses the variable is found.
+fn uses_product_icon_variable(wxs_sources: &[PathBuf]) -> Result<bool> {
+    let variable = format!("$(var.{PRODUCT_ICON_VARIABLE})");
+    for source in wxs_sources {
+        if read_wxs_source(source)?.contains(&variable) {
+            trace!(
+                "The '{}' WiX Source file uses the product icon from the binary",
+                source.display()
+            );
+            return Ok(true);
         }
+    }
+    Ok(false)
+}
 
-        #[test]
-        fn compiler_args_with_multiple_values_works() {
-            let expected: Vec<&str> = vec!["-arch", "x86"];
-            let mut actual = Builder::new();
-            actual.compiler_args(Some(expected.clone()));
-            assert_eq!(actual.compiler_args, Some(expected));
-        }
+/// Determines if a package cannot be published, i.e. it has `publish = false`
+/// in its manifest (Cargo.toml).
+///
+/// A package that can only be published to some registries is not included.
+fn is_unpublished(package: &Package) -> bool {
+    package.publish.as_ref().is_some_and(Vec::is_empty)
+}
 
-        #[test]
-        fn culture_works() {
-            const EXPECTED: &str = "FrFr";
+/// Gets the name of the main binary, which is the binary from the
+/// `default-run` field, or with the same name as the package, or the first
+/// binary if none match.
+fn main_binary_name(package: &Package) -> &str {
+    let mut binaries = package
+        .targets
+        .iter()
+        .filter(|t| t.kind.iter().any(|k| k == "bin"))
+        .map(|t| t.name.as_str());
+    let first = binaries.clone().next();
+    let primary = super::primary_binary_name(package);
+    binaries
+        .find(|name| *name == primary)
+        .or(first)
+        .unwrap_or(package.name.as_str())
+}
+
+/// Gets a path to define for the WiX compiler (candle.exe).
+///
+/// The path is made relative to the root if there is a root, otherwise it is
+/// unchanged.
+fn emitted_path(path: &Path, root: Option<&Path>) -> PathBuf {
+    match root {
+        Some(root) => relative_path(path, root).unwrap_or_else(|| {
+            warn!(
+                "The '{}' path cannot be made relative to the '{}' folder of the package's \
+                 manifest (Cargo.toml), so the absolute path is used.",
+                path.display(),
+                root.display()
+            );
+            path.to_path_buf()
+        }),
+        None => path.to_path_buf(),
+    }
+}
+
+/// Gets a path relative to a base folder.
+///
+/// A path outside of the base folder is prefixed with the `..` components to
+/// reach it, and a relative path is unchanged. `None` is returned if the paths
+/// do not have a common root, such as paths on different drives.
+fn relative_path(path: &Path, base: &Path) -> Option<PathBuf> {
+    if path.is_relative() {
+        return Some(path.to_path_buf());
+    }
+    match path.strip_prefix(base) {
+        Ok(p) if p.as_os_str().is_empty() => Some(PathBuf::from(".")),
+        Ok(p) => Some(p.to_path_buf()),
+        Err(_) => {
+            let common = path
+                .components()
+                .zip(base.components())
+                .take_while(|(p, b)| p == b)
+                .count();
+            if common == 0 {
+                return None;
+            }
+            let mut relative: PathBuf = base.components().skip(common).map(|_| "..").collect();
+            relative.extend(path.components().skip(common));
+            Some(relative)
+        }
+    }
+}
+
+/// Finds the most recently modified file with the name in a folder or any of
+/// its subfolders.
+///
+/// The folders of Cargo's intermediate build artifacts, such as `deps` and
+/// `incremental`, are skipped, since the binaries in them are not the final
+/// binaries.
+fn find_binary(directory: &Path, file_name: &str) -> Option<PathBuf> {
+    const SKIPPED: [&str; 5] = ["build", "deps", "examples", "incremental", WIX];
+    let mut found: Option<(std::time::SystemTime, PathBuf)> = None;
+    let mut folders = vec![directory.to_path_buf()];
+    while let Some(folder) = folders.pop() {
+        let Ok(entries) = std::fs::read_dir(&folder) else {
+            continue;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if !SKIPPED.contains(&entry.file_name().to_string_lossy().as_ref()) {
+                    folders.push(path);
+                }
+            } else if entry
+                .file_name()
+                .to_string_lossy()
+                .eq_ignore_ascii_case(file_name)
+            {
+                let modified = entry
+                    .metadata()
+                    .and_then(|m| m.modified())
+                    .unwrap_or(std::time::UNIX_EPOCH);
+                if found.as_ref().map_or(true, |(t, _)| modified > *t) {
+                    found = Some((modified, path));
+                }
+            }
+        }
+    }
+    found.map(|(_, path)| path)
+}
+
+/// Checks that each binary of a package exists in the folder set with the
+/// `--target-bin-dir` option.
+///
+/// All of the missing binaries are listed in the error, so a partial build of
+/// a package with multiple binaries is reported at once.
+fn check_target_bin_dir(target_bin_dir: &Path, package: &Package) -> Result<()> {
+    if !target_bin_dir.is_dir() {
+        return Err(Error::Generic(format!(
+            "The '{}' folder set with the '--target-bin-dir' option does not exist",
+            target_bin_dir.display()
+        )));
+    }
+    let missing = binary_names(package)
+        .into_iter()
+        .map(|n| format!("{n}.{EXE_FILE_EXTENSION}"))
+        .filter(|b| {
+            let binary = target_bin_dir.join(b);
+            debug!("binary = {:?}, exists = {}", binary, binary.is_file());
+            !binary.is_file()
+        })
+        .collect::<Vec<String>>();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Generic(format!(
+            "The {} binary(ies) could not be found in the '{}' folder set with the \
+             '--target-bin-dir' option. Please build all of the binaries of the package \
+             into the folder.",
+            missing
+                .iter()
+                .map(|b| format!("'{b}'"))
+                .collect::<Vec<String>>()
+                .join(", "),
+            target_bin_dir.display()
+        )))
+    }
+}
+
+/// Checks that the architecture of each binary matches the architecture of the
+/// installer.
+///
+/// A binary built for a different architecture, such as an x86 binary in an
+/// x64 installer, is reported with a warning, or with an error if `strict` is
+/// `true`. Binaries that do not exist or cannot be read are skipped.
+fn check_binary_arch(binaries: &[PathBuf], wix_arch: &WixArch, strict: bool) -> Result<()> {
+    let mut count = 0;
+    for binary in binaries {
+        let arch = match std::fs::read(binary)
+            .map_err(Error::from)
+            .and_then(|executable| pe::arch(&executable))
+        {
+            Ok(Some(arch)) => arch,
+            Ok(None) => {
+                trace!(
+                    "The architecture of the '{}' binary is unknown",
+                    binary.display()
+                );
+                continue;
+            }
+            Err(e) => {
+                trace!(
+                    "Skipped checking the architecture of the '{}' binary: {e}",
+                    binary.display()
+                );
+                continue;
+            }
+        };
+        debug!("{} architecture = {}", binary.display(), arch);
+        if &arch != wix_arch {
+            count += 1;
+            let problem = format!(
+                "The '{}' binary is built for the '{arch}' architecture, but the installer \
+                 is for the '{wix_arch}' architecture",
+                binary.display()
+            );
+            if strict {
+                error!("{}", problem);
+            } else {
+                warn!("{}", problem);
+            }
+        }
+    }
+    if strict && count > 0 {
+        Err(Error::Generic(format!(
+            "{count} binary(ies) are built for a different architecture than the installer. \
+             Please build the binaries for the target of the installer or use the \
+             '--target' option to create the installer for the architecture of the \
+             binaries."
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Extracts the icon embedded in the resources of a binary to an icon (.ico)
+/// file, creating the parent folder if it does not exist.
+fn extract_product_icon(binary: &Path, destination: &Path) -> Result<()> {
+    trace!("Extracting the product icon from '{}'", binary.display());
+    let executable = std::fs::read(binary).map_err(|e| {
+        Error::Generic(format!(
+            "The '{}' binary could not be read to extract the product icon: {e}",
+            binary.display()
+        ))
+    })?;
+    let ico = icon::extract_icon(&executable).map_err(|e| {
+        Error::Generic(format!(
+            "The product icon could not be extracted from the '{}' binary: {e}",
+            binary.display()
+        ))
+    })?;
+    if let Some(parent) = destination.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(destination, ico)?;
+    Ok(())
+}
+
+/// Gets the names of the compiler variables defined with the `-d` argument.
+fn defined_variables<'a, I>(args: I) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a OsStr>,
+{
+    args.into_iter()
+        .filter_map(|a| a.to_str())
+        .filter_map(|a| a.strip_prefix("-d"))
+        .map(|a| a.split('=').next().unwrap_or(a).to_owned())
+        .collect()
+}
+
+/// Gets the include paths added with the `-I` argument, which are searched
+/// for the files included with the `<?include?>` preprocessor instruction.
+fn include_paths<'a, I>(args: I) -> Vec<PathBuf>
+where
+    I: IntoIterator<Item = &'a OsStr>,
+{
+    args.into_iter()
+        .filter_map(|a| a.to_str())
+        .filter_map(|a| a.strip_prefix("-I"))
+        .filter(|a| !a.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Gets the name and value of each compiler variable defined with the `-d`
+/// argument.
+///
+/// The value is empty for a variable that is defined without a value.
+fn defines<'a, I>(args: I) -> Vec<(String, String)>
+where
+    I: IntoIterator<Item = &'a OsStr>,
+{
+    args.into_iter()
+        .map(OsStr::to_string_lossy)
+        .filter_map(|a| {
+            a.strip_prefix("-d").map(|d| match d.split_once('=') {
+                Some((name, value)) => (name.to_owned(), value.to_owned()),
+                None => (d.to_owned(), String::new()),
+            })
+        })
+        .collect()
+}
+
+/// Determines if a path is for a folder, i.e. it is an existing folder or it
+/// ends with a slash.
+fn is_folder_path(path: &Path) -> bool {
+    let s = path.to_string_lossy();
+    s.ends_with('/') || s.ends_with('\\') || path.is_dir()
+}
+
+/// Writes the SHA-256 checksum of each created file in the format of the
+/// `sha256sum` tool, i.e. a `<sha256>  <path>` line for each file.
+///
+/// The path of a file is relative to the folder of the destination if the
+/// file is within the folder, and it uses forward slashes in that case. The
+/// parent folder is created if it does not exist.
+fn write_checksum_manifest(destination: &Path, artifacts: &[PathBuf]) -> Result<()> {
+    let folder = destination
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    std::fs::create_dir_all(folder)?;
+    let folder = folder.canonicalize()?;
+    let mut content = String::new();
+    for artifact in artifacts {
+        let mut file = std::fs::File::open(artifact)?;
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut file, &mut hasher)?;
+        let digest = format!("{:x}", hasher.finalize());
+        let path = artifact
+            .canonicalize()?
+            .strip_prefix(&folder)
+            .map(|p| {
+                p.components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .unwrap_or_else(|_| artifact.display().to_string());
+        content.push_str(&format!("{digest}  {path}\n"));
+    }
+    std::fs::write(destination, content)?;
+    Ok(())
+}
+
+/// Writes the resolved configuration of an installer as JSON.
+///
+/// The parent folder is created if it does not exist.
+fn write_metadata_output(destination: &Path, configuration: &Value) -> Result<()> {
+    if let Some(parent) = destination.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut content = serde_json::to_string_pretty(configuration)
+        .map_err(|err| Error::Generic(err.to_string()))?;
+    content.push('\n');
+    std::fs::write(destination, content)?;
+    Ok(())
+}
+
+/// Estimates the size, in kilobytes, of the installed files from the `Source`
+/// attribute of each `File` element in the WiX Source (wxs) files.
+///
+/// The `$(var.NAME)` references in a path are replaced with the values defined
+/// in the compiler arguments. Paths with other references are skipped. A
+/// relative path is resolved against each of the bind paths in order, like it
+/// is for the WiX linker (light.exe), and then against the current working
+/// directory. Files that cannot be found are skipped with a warning. `None` is
+/// returned if no files are found.
+fn estimated_size<'a, I>(
+    wxs_sources: &[PathBuf],
+    args: I,
+    bind_paths: &[PathBuf],
+) -> Result<Option<u64>>
+where
+    I: IntoIterator<Item = &'a OsStr>,
+{
+    let values = define_values(args);
+    let mut files = Vec::new();
+    for source in wxs_sources {
+        let content = blank_comments(&read_wxs_source(source)?);
+        for path in file_sources(&content) {
+            match replace_variables(path, &values) {
+                Some(path) if !files.contains(&path) => files.push(path),
+                Some(_) => {}
+                None => debug!("The '{}' file is skipped for the estimated size", path),
+            }
+        }
+    }
+    let mut bytes = 0;
+    let mut found = false;
+    for file in &files {
+        match bound_file(file, bind_paths).and_then(|p| std::fs::metadata(p).ok()) {
+            Some(metadata) if metadata.is_file() => {
+                found = true;
+                bytes += metadata.len();
+            }
+            _ => warn!(
+                "The '{}' file could not be found for the estimated size of the installer",
+                file
+            ),
+        }
+    }
+    Ok(found.then_some((bytes + 1023) / 1024))
+}
+
+/// Gets the files referenced by WiX Source (wxs) files, i.e. the `Source`
+/// attribute of each `File` element, the `SourceFile` attribute of each
+/// `Binary` and `Icon` element, and the `Value` attribute of each `WixVariable`
+/// element, such as the license, the banner, and the dialog images.
+///
+/// The references are resolved like they are for the estimated size of the
+/// installer. A reference that cannot be found is returned unresolved, so a
+/// change to it is still detected. References with a variable that is not
+/// defined in the compiler arguments are skipped.
+fn referenced_files<'a, I>(
+    wxs_sources: &[PathBuf],
+    args: I,
+    bind_paths: &[PathBuf],
+) -> Result<Vec<PathBuf>>
+where
+    I: IntoIterator<Item = &'a OsStr>,
+{
+    let values = define_values(args);
+    let mut files = Vec::new();
+    for source in wxs_sources {
+        let content = blank_comments(&read_wxs_source(source)?);
+        let references = file_sources(&content)
+            .into_iter()
+            .chain(attribute_values(&content, "Binary", "SourceFile"))
+            .chain(attribute_values(&content, "Icon", "SourceFile"))
+            .chain(attribute_values(&content, "WixVariable", "Value"));
+        for reference in references {
+            if let Some(reference) = replace_variables(reference, &values) {
+                let file =
+                    bound_file(&reference, bind_paths).unwrap_or_else(|| PathBuf::from(reference));
+                if !files.contains(&file) {
+                    files.push(file);
+                }
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Gets the name and value of each compiler variable defined with a value with
+/// the `-d` argument.
+fn define_values<'a, I>(args: I) -> Vec<(&'a str, &'a str)>
+where
+    I: IntoIterator<Item = &'a OsStr>,
+{
+    args.into_iter()
+        .filter_map(|a| a.to_str())
+        .filter_map(|a| a.strip_prefix("-d"))
+        .filter_map(|a| a.split_once('='))
+        .collect()
+}
+
+/// Replaces the `$(var.NAME)` references in a path with the values of the
+/// compiler variables.
+///
+/// `None` is returned if the path has any other references.
+fn replace_variables(path: &str, values: &[(&str, &str)]) -> Option<String> {
+    let mut path = path.to_owned();
+    for (name, value) in values {
+        path = path.replace(&format!("$(var.{name})"), value);
+    }
+    (!path.contains("$(")).then_some(path)
+}
+
+/// Finds a file from a WiX Source (wxs) file, resolving a relative path
+/// against each of the bind paths in order, like the WiX linker (light.exe)
+/// does, and then against the current working directory.
+fn bound_file(file: &str, bind_paths: &[PathBuf]) -> Option<PathBuf> {
+    let path = if cfg!(windows) {
+        PathBuf::from(file)
+    } else {
+        PathBuf::from(file.replace('\\', "/"))
+    };
+    if path.is_absolute() {
+        Some(path)
+    } else {
+        bind_paths
+            .iter()
+            .map(|b| b.join(&path))
+            .chain(std::iter::once(path.clone()))
+            .find(|p| p.is_file())
+    }
+}
+
+/// Gets the paths the WiX linker (light.exe) uses to resolve the relative
+/// paths of files, i.e. the base path followed by the unnamed bind paths in
+/// the linker arguments.
+fn bind_paths(base_path: &Path, linker_args: Option<&[String]>) -> Vec<PathBuf> {
+    let mut paths = vec![base_path.to_owned()];
+    if let Some(args) = linker_args {
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "-b" {
+                if let Some(path) = args.next().filter(|p| !p.contains('=')) {
+                    paths.push(PathBuf::from(path));
+                }
+            }
+        }
+    }
+    paths
+}
+
+/// Gets the values of the `Source` attribute of each `File` element.
+fn file_sources(content: &str) -> Vec<&str> {
+    attribute_values(content, "File", "Source")
+}
+
+/// Gets the values of an attribute of each element with a name.
+fn attribute_values<'a>(content: &'a str, element: &str, attribute: &str) -> Vec<&'a str> {
+    let start = format!("<{element}");
+    let name = format!("{attribute}=");
+    let mut values = Vec::new();
+    for (offset, _) in content.match_indices(&start) {
+        let tag = content[offset..].split('>').next().unwrap_or_default();
+        if !tag[start.len()..].starts_with(char::is_whitespace) {
+            continue;
+        }
+        for (index, _) in tag.match_indices(&name) {
+            if !tag[..index].ends_with(char::is_whitespace) {
+                continue;
+            }
+            let value = &tag[index + name.len()..];
+            if let Some(quote @ ('\'' | '"')) = value.chars().next() {
+                if let Some(value) = value[1..].split(quote).next() {
+                    values.push(value);
+                }
+            }
+        }
+    }
+    values
+}
+
+/// Gets the paths of the WiX Object (wixobj) files that the WiX compiler
+/// (candle.exe) writes to the destination for the WiX Source (wxs) files.
+fn compiled_wixobj_sources(wxs_sources: &[PathBuf], wixobj_destination: &Path) -> Vec<PathBuf> {
+    wxs_sources
+        .iter()
+        .filter_map(|s| s.file_stem())
+        .map(|stem| {
+            let mut name = stem.to_owned();
+            name.push(format!(".{WIX_OBJECT_FILE_EXTENSION}"));
+            wixobj_destination.join(name)
+        })
+        .collect()
+}
+
+/// Gets the kind of installer from the WiX Source (wxs) files.
+///
+/// This is used instead of the WiX Object (wixobj) files for a dry run, since
+/// the object files are not compiled.
+fn wxs_installer_kind(wxs_sources: &[PathBuf]) -> Result<InstallerKind> {
+    for source in wxs_sources {
+        if blank_comments(&read_wxs_source(source)?).contains("<Bundle") {
+            return Ok(InstallerKind::Exe);
+        }
+    }
+    Ok(InstallerKind::Msi)
+}
+
+/// Gets the files for a portable zip archive from the `Source` attribute of
+/// each `File` element in the WiX Source (wxs) files.
+///
+/// The `$(var.NAME)` references in a path are replaced with the values. A
+/// relative path is relative to the base path, like it is for the WiX linker
+/// (light.exe).
+fn portable_files(
+    wxs_sources: &[PathBuf],
+    values: &[(String, String)],
+    base_path: &Path,
+) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for source in wxs_sources {
+        let content = blank_comments(&read_wxs_source(source)?);
+        for path in file_sources(&content) {
+            let mut path = path.to_owned();
+            for (name, value) in values {
+                path = path.replace(&format!("$(var.{name})"), value);
+            }
+            if path.contains("$(") {
+                return Err(Error::Generic(format!(
+                    "The '{path}' file in the '{}' WiX Source (wxs) file has a variable that \
+                     is not available for a zip archive. Please use a path without the \
+                     variable or the 'msi' format.",
+                    source.display()
+                )));
+            }
+            let path = if cfg!(windows) {
+                base_path.join(path)
+            } else {
+                base_path.join(path.replace('\\', "/"))
+            };
+            if !path.is_file() {
+                return Err(Error::Generic(format!(
+                    "The '{}' file for the zip archive does not exist. Please check the \
+                     binary has been built and the path is correct.",
+                    path.display()
+                )));
+            }
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+    }
+    if files.is_empty() {
+        return Err(Error::Generic(String::from(
+            "There are no files for the zip archive. Please check the WiX Source (wxs) \
+             files have at least one 'File' element.",
+        )));
+    }
+    Ok(files)
+}
+
+/// Writes a portable zip archive of the files.
+///
+/// The files are in a folder with the name of the archive. The files in the
+/// target binary folder are in a `bin` folder, like the installed product, and
+/// the other files are in the top of the folder.
+fn write_zip_archive(
+    destination: &Path,
+    folder: &str,
+    files: &[PathBuf],
+    target_bin_dir: &Path,
+) -> Result<()> {
+    let mut entries = Vec::new();
+    for file in files {
+        let entry = if let Ok(relative) = file.strip_prefix(target_bin_dir) {
+            Path::new("bin").join(relative)
+        } else {
+            PathBuf::from(file.file_name().unwrap_or_default())
+        };
+        let entry = format!(
+            "{folder}/{}",
+            entry
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        );
+        if entries.iter().any(|(e, _)| e == &entry) {
+            return Err(Error::Generic(format!(
+                "More than one file is archived as '{entry}' in the zip archive. Please \
+                 check the file names are unique.",
+            )));
+        }
+        entries.push((entry, file));
+    }
+    if let Some(parent) = destination.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut archive = ZipWriter::new(std::fs::File::create(destination)?);
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (entry, file) in entries {
+        trace!("Archiving the '{}' file as '{}'", file.display(), entry);
+        archive
+            .start_file(entry, options)
+            .map_err(|err| Error::Generic(err.to_string()))?;
+        std::io::copy(&mut std::fs::File::open(file)?, &mut archive)?;
+    }
+    archive
+        .finish()
+        .map_err(|err| Error::Generic(err.to_string()))?;
+    info!("The zip archive is written to '{}'", destination.display());
+    Ok(())
+}
+
+/// Checks the WiX Source (wxs) files for values that are missing from the
+/// package's manifest (Cargo.toml) or are not defined.
+///
+/// The variables declared in the files included by a WiX Source (wxs) file are
+/// also defined for it.
+fn verify_wxs_sources(
+    wxs_sources: &[PathBuf],
+    defined: &[String],
+    include_paths: &[PathBuf],
+) -> Result<()> {
+    let mut problems = Vec::new();
+    for source in wxs_sources {
+        let content = read_wxs_source(source)?;
+        let mut defined = defined.to_vec();
+        defined.extend(included_variables(source, include_paths)?);
+        problems.extend(
+            manifest_problems(&content, &defined)
+                .into_iter()
+                .map(|p| format!("{}:{p}", source.display())),
+        );
+    }
+    for problem in &problems {
+        error!("{}", problem);
+    }
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Generic(format!(
+            "The WiX Source (wxs) files have {} value(s) that are missing from the \
+             package's manifest (Cargo.toml) or are not defined. Please add the fields \
+             to the manifest and re-initialize, or edit the WiX Source (wxs) files.",
+            problems.len()
+        )))
+    }
+}
+
+/// Checks the WiX Source (wxs) files for references to compiler variables that
+/// are not defined.
+///
+/// Each problem is reported with a warning, or with an error if `strict` is
+/// `true`, in which case an error is also returned if any problems are found.
+/// The variables declared in the files included by a WiX Source (wxs) file are
+/// also defined for it.
+fn check_wxs_variables(
+    wxs_sources: &[PathBuf],
+    defined: &[String],
+    include_paths: &[PathBuf],
+    strict: bool,
+) -> Result<()> {
+    let mut count = 0;
+    for source in wxs_sources {
+        let content = read_wxs_source(source)?;
+        let mut defined = defined.to_vec();
+        defined.extend(included_variables(source, include_paths)?);
+        for (line, problem) in variable_problems(&content, &defined) {
+            count += 1;
+            if strict {
+                error!("{}:{line}: {problem}", source.display());
+            } else {
+                warn!("{}:{line}: {problem}", source.display());
+            }
+        }
+    }
+    if strict && count > 0 {
+        Err(Error::Generic(format!(
+            "The WiX Source (wxs) files reference {count} compiler variable(s) that are \
+             not defined. Please define the variables with the '-C,--compiler-arg' \
+             option or fix the references in the WiX Source (wxs) files."
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Finds the references to compiler variables in the content of a WiX Source
+/// (wxs) file that are not defined.
+///
+/// Each problem is the line number and a description. Variables that are
+/// defined, tested, or used as a loop variable with a preprocessor instruction
+/// in the file are not reported, since their use is assumed to be guarded.
+/// References in comments are ignored.
+fn variable_problems(content: &str, defined: &[String]) -> Vec<(usize, String)> {
+    let content = blank_comments(content);
+    let line = |offset: usize| content[..offset].matches('\n').count() + 1;
+    let mut declared = Vec::new();
+    for (instruction, argument) in instructions(&content) {
+        if let "define" | "ifdef" | "ifndef" | "foreach" = instruction {
+            declared.push(argument.split('=').next().unwrap_or(argument));
+        }
+    }
+    let mut problems = Vec::new();
+    for (offset, _) in content.match_indices("$(var.") {
+        let name = content[offset + 6..].split(')').next().unwrap_or_default();
+        if !defined.iter().any(|d| d == name) && !declared.contains(&name) {
+            problems.push((
+                line(offset),
+                format!("The '{name}' variable is not defined"),
+            ));
+        }
+    }
+    problems
+}
+
+/// Gets the variables declared with a preprocessor instruction in the files
+/// included by a WiX Source (wxs) file with the `<?include?>` instruction, and
+/// in the files they include.
+fn included_variables(source: &Path, include_paths: &[PathBuf]) -> Result<Vec<String>> {
+    let mut variables = Vec::new();
+    for file in included_files(source, include_paths)? {
+        let content = blank_comments(&read_wxs_source(&file)?);
+        for (instruction, argument) in instructions(&content) {
+            if let "define" | "ifdef" | "ifndef" | "foreach" = instruction {
+                variables.push(argument.split('=').next().unwrap_or(argument).to_owned());
+            }
+        }
+    }
+    Ok(variables)
+}
+
+/// Gets the files included by a WiX Source (wxs) file with the `<?include?>`
+/// instruction, and the files they include.
+///
+/// An included file is found relative to the folder of the including file and
+/// then to each of the include paths, like it is for the WiX compiler
+/// (candle.exe). Included files with a variable in their path, or that cannot
+/// be found, are skipped.
+fn included_files(source: &Path, include_paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut visited = vec![source.to_owned()];
+    let mut pending = vec![source.to_owned()];
+    while let Some(file) = pending.pop() {
+        let content = blank_comments(&read_wxs_source(&file)?);
+        let folder = file.parent().unwrap_or_else(|| Path::new(""));
+        for (instruction, argument) in instructions(&content) {
+            if instruction != "include" {
+                continue;
+            }
+            let path = argument.trim_matches(|c| c == '"' || c == '\'');
+            if path.contains("$(") {
+                debug!(
+                    "The '{}' included file has a variable, so it is skipped",
+                    path
+                );
+                continue;
+            }
+            let path = if cfg!(windows) {
+                PathBuf::from(path)
+            } else {
+                PathBuf::from(path.replace('\\', "/"))
+            };
+            match std::iter::once(folder)
+                .chain(include_paths.iter().map(PathBuf::as_path))
+                .map(|f| f.join(&path))
+                .find(|p| p.is_file())
+            {
+                Some(included) if !visited.contains(&included) => {
+                    visited.push(included.clone());
+                    pending.push(included);
+                }
+                Some(_) => {}
+                None => debug!("The '{}' included file could not be found", path.display()),
+            }
+        }
+    }
+    visited.remove(0);
+    Ok(visited)
+}
+
+/// Gets the name and first argument of each preprocessor instruction, such as
+/// `<?define NAME = value ?>`, in the content of a WiX Source (wxs) file.
+fn instructions(content: &str) -> Vec<(&str, &str)> {
+    content
+        .match_indices("<?")
+        .filter_map(|(offset, _)| {
+            let instruction = content[offset + 2..].split("?>").next().unwrap_or_default();
+            let mut words = instruction.split_whitespace();
+            words.next().zip(words.next())
+        })
+        .collect()
+}
+
+/// Finds the values in the content of a WiX Source (wxs) file that are missing
+/// from the package's manifest (Cargo.toml) or are not defined.
+///
+/// Each problem starts with its line number. Variables that are defined,
+/// tested, or used as a loop variable with a preprocessor instruction in the
+/// file are not reported, since their use is assumed to be guarded.
+fn manifest_problems(content: &str, defined: &[String]) -> Vec<String> {
+    let mut problems = variable_problems(content, defined);
+    let content = blank_comments(content);
+    let line = |offset: usize| content[..offset].matches('\n').count() + 1;
+    for (attribute, field) in MANIFEST_ATTRIBUTES {
+        for quote in ['\'', '"'] {
+            let empty = format!(" {attribute}={quote}{quote}");
+            for (offset, _) in content.match_indices(&empty) {
+                problems.push((
+                    line(offset),
+                    format!("The '{attribute}' attribute is empty, add {field}"),
+                ));
+            }
+        }
+    }
+    for (offset, _) in content.match_indices("<Package") {
+        let tag = content[offset..].split('>').next().unwrap_or_default();
+        let is_package = tag[8..].starts_with(|c: char| c.is_whitespace() || c == '/');
+        if is_package && !tag.contains("Description=") {
+            problems.push((
+                line(offset),
+                String::from(
+                    "The 'Package' element does not have a 'Description' attribute, add \
+                     the 'description' field to the package's manifest",
+                ),
+            ));
+        }
+    }
+    problems.sort_by_key(|(line, _)| *line);
+    problems
+        .into_iter()
+        .map(|(line, problem)| format!("{line}: {problem}"))
+        .collect()
+}
+
+/// Replaces the content of XML comments with spaces, keeping line breaks so
+/// line numbers are unchanged.
+fn blank_comments(content: &str) -> String {
+    let mut blanked = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("<!--") {
+        blanked.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find("-->")
+            .map_or(rest.len(), |e| start + e + 3);
+        blanked.extend(
+            rest[start..end]
+                .chars()
+                .map(|c| if c == '\n' { c } else { ' ' }),
+        );
+        rest = &rest[end..];
+    }
+    blanked.push_str(rest);
+    blanked
+}
+
+/// Checks if the arguments for a WiX Toolset application already suppress the
+/// logo.
+fn has_nologo(args: Option<&[String]>) -> bool {
+    args.is_some_and(|args| args.iter().any(|a| a.eq_ignore_ascii_case(NO_LOGO)))
+}
+
+/// Attempts to convert a Rust SemVer version to the format WiX desires.
+///
+/// WiX only supports numbers in versions, with a format of "x.x.x.x"
+/// WiX itself requires each component to be an integer from 0 to 65534 (inclusive).
+/// However the first 3 parts are forwarded to Windows as a [ProductVersion][0],
+/// which interprets them as "major.minor.build" and states:
+///
+/// > The major version and has a maximum value of 255.
+/// > The minor version and has a maximum value of 255.
+/// > The build version or the update version and has a maximum value of 65,535.
+///
+/// So we take the intersection of these requirements, and shove the rust "major.minor.patch"
+/// format into it. This leaves the more freeform "prerelease" and "build" components of a
+/// SemVer Version to get squeezed into the 4th value.
+///
+/// The 4th value is seemingly just a bonus value that WiX keeps to itself, so it's not
+/// terribly important that we get it perfect. We therefore attempt to heuritistically
+/// parse out a numeric "prerelease version" based on common formats.
+///
+/// [0]: https://learn.microsoft.com/en-us/windows/win32/msi/productversion
+fn wix_version(version: &Version) -> Result<String> {
+    // validate basic parts
+    if version.major > 255 {
+        return Err(Error::Generic(format!(
+            "The app's major version {} can't be greater than 255 for an msi",
+            version.major
+        )));
+    }
+    if version.minor > 255 {
+        return Err(Error::Generic(format!(
+            "The app's minor version {} can't be greater than 255 for an msi",
+            version.minor
+        )));
+    }
+    if version.patch > 65534 {
+        return Err(Error::Generic(format!(
+            "The app's patch version {} can't be greater than 65534 for an msi",
+            version.patch
+        )));
+    }
+
+    // Attempt to validate + convert the prerelease parts
+    let needs_prerelease_handling = !version.build.is_empty() || !version.pre.is_empty();
+    if needs_prerelease_handling {
+        // This mess is trying 3 approaches in sequence:
+        //
+        // * parse as if it's `1.2.3-4`
+        // * parse as if it's `1.2.3-prerelease.4`
+        // * parse as if it's `1.2.3-prerelease+4`
+        let bonus = version
+            .pre
+            .parse::<u64>()
+            .or_else(|e| {
+                if let Some((_, dotted)) = version.pre.split_once('.') {
+                    dotted.parse::<u64>()
+                } else {
+                    Err(e)
+                }
+            })
+            .or_else(|_| version.build.parse::<u64>());
+
+        let bonus = if let Ok(bonus) = bonus {
+            bonus
+        } else {
+            return Err(Error::Generic(format!(
+                "The app's version {} is a prerelease, but we couldn't convert the prerelease \
+                 components to an integer. We recommend a format like 1.2.3-prerelease.4, \
+                 as we can map it to the 1.2.3.4 format that works for an msi.",
+                version,
+            )));
+        };
+        if bonus > 65534 {
+            return Err(Error::Generic(format!(
+                "The app's prerelease version {} can't be greater than 65534 for an msi",
+                bonus
+            )));
+        }
+
+        Ok(format!(
+            "{}.{}.{}.{}",
+            version.major, version.minor, version.patch, bonus
+        ))
+    } else {
+        Ok(format!(
+            "{}.{}.{}",
+            version.major, version.minor, version.patch
+        ))
+    }
+}
+
+impl Default for Execution {
+    fn default() -> Self {
+        Builder::new().build()
+    }
+}
+
+/// The stages of creating an installer.
+///
+/// See the [`Builder::progress`] method.
+///
+/// [`Builder::progress`]: struct.Builder.html#method.progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreateStage {
+    /// The package is being built with Cargo.
+    Building,
+    /// The WiX Source (wxs) files are being compiled with the WiX compiler
+    /// (candle.exe).
+    Compiling,
+    /// The WiX Object (wixobj) files are being linked into the installer with
+    /// the WiX linker (light.exe).
+    Linking,
+    /// The installer, or the engine of a bundle, is being signed with the
+    /// signer (signtool.exe).
+    ///
+    /// This stage is only reported when signing an installer, see the
+    /// [`sign::Builder::progress`] method.
+    ///
+    /// [`sign::Builder::progress`]: ../sign/struct.Builder.html#method.progress
+    Signing,
+    /// The files are being archived into a portable zip archive because the
+    /// `zip` format was used.
+    Archiving,
+    /// The installer is being launched because the `install` method was used.
+    Installing,
+    /// The installer has been created for all targets.
+    Done,
+}
+
+/// A callback for reporting the progress of creating, or signing, an
+/// installer.
+#[derive(Clone)]
+pub(crate) struct Progress(pub(crate) Arc<dyn Fn(CreateStage) + Send + Sync>);
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Progress")
+    }
+}
+
+/// The kind of WiX Object (wixobj) file.
+#[derive(Debug, PartialEq, Eq)]
+pub enum WixObjKind {
+    /// A WiX Object (wixobj) file that ultimately links back to a WiX Source
+    /// (wxs) file with a [`bundle`] tag.
+    ///
+    /// [`bundle`]: https://wixtoolset.org/documentation/manual/v3/xsd/wix/bundle.html
+    Bundle,
+    /// A WiX Object (wixobj) file that ultimately links back to a WiX Source
+    /// (wxs) file with a [`fragment`] tag.
+    ///
+    /// [`fragment`]: https://wixtoolset.org/documentation/manual/v3/xsd/wix/fragment.html
+    Fragment,
+    /// A WiX Object (wixobj) file that ultimately links back to a WiX Source
+    /// (wxs) file with a [`product`] tag.
+    ///
+    /// [`product`]: https://wixtoolset.org/documentation/manual/v3/xsd/wix/product.html
+    Product,
+}
+
+impl WixObjKind {
+    /// Determines if the WiX Object (wixobj) file kind is a [`bundle`].
+    ///
+    /// # Examples
+    ///
+    /// A WiX Object (wixobj) file identified as a WXS Source (wxs) file
+    /// containing a [`bundle`] tag.
+    ///
+    /// ```
+    /// use wix::create::WixObjKind;
+    ///
+    /// assert!(WixObjKind::Bundle.is_bundle())
+    /// ```
+    ///
+    /// A WiX Object (wixobj) file identified as a WXS Source (wxs) file
+    /// containing a [`product`] tag.
+    ///
+    /// ```
+    /// use wix::create::WixObjKind;
+    ///
+    /// assert!(!WixObjKind::Product.is_bundle())
+    /// ```
+    ///
+    /// [`bundle`]: https://wixtoolset.org/documentation/manual/v3/xsd/wix/bundle.html
+    /// [`product`]: https://wixtoolset.org/documentation/manual/v3/xsd/wix/product.html
+    pub fn is_bundle(&self) -> bool {
+        match *self {
+            Self::Bundle => true,
+            Self::Fragment => false,
+            Self::Product => false,
+        }
+    }
+}
+
+impl FromStr for WixObjKind {
+    type Err = crate::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match &*value.to_lowercase() {
+            "bundle" => Ok(Self::Bundle),
+            "fragment" => Ok(Self::Fragment),
+            "product" => Ok(Self::Product),
+            v => Err(Self::Err::Generic(format!(
+                "Unknown '{v}' tag name from a WiX Object (wixobj) file."
+            ))),
+        }
+    }
+}
+
+impl TryFrom<&PathBuf> for WixObjKind {
+    type Error = crate::Error;
+
+    fn try_from(path: &PathBuf) -> Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let mut decoder = encoding_rs_io::DecodeReaderBytes::new(file);
+        let mut content = String::new();
+        decoder.read_to_string(&mut content)?;
+        Self::try_from(content.as_str())
+    }
+}
+
+impl TryFrom<&str> for WixObjKind {
+    type Error = crate::Error;
+
+    fn try_from(content: &str) -> Result<Self> {
+        let package = sxd_document::parser::parse(content)?;
+        let document = package.as_document();
+        let mut context = sxd_xpath::Context::new();
+        context.set_namespace("wix", "http://schemas.microsoft.com/wix/2006/objects");
+        // The assumption is that the following cannot fail because the path is known to be valid at
+        // compile-time.
+        let xpath = sxd_xpath::Factory::new()
+            .build("/wix:wixObject/wix:section/@type")
+            .unwrap()
+            .unwrap();
+        let value = xpath.evaluate(&context, document.root())?.string();
+        Self::from_str(&value)
+    }
+}
+
+/// The kinds of installers that can be created using the WiX compiler
+/// (candle.exe) and linker (light.exe), or the portable zip archive that can be
+/// created instead.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub enum InstallerKind {
+    /// An executable is used when an [Installation Package Bundle] is created.
+    ///
+    /// [Installation Package Bundle]: https://wixtoolset.org/documentation/manual/v3/bundle/
+    Exe,
+    /// A Microsoft installer. This is the more common and typical installer to be created.
+    #[default]
+    Msi,
+    /// A portable zip archive of the files, which is created without the WiX
+    /// Toolset.
+    Zip,
+}
+
+impl InstallerKind {
+    /// Gets the file extension _without_ the dot separator.
+    ///
+    /// # Examples
+    ///
+    /// The extension for an installer of an [Installation Package Bundle]. Also
+    /// see the [`EXE_FILE_EXTENSION`] constant.
+    ///
+    /// ```
+    /// use wix::create::InstallerKind;
+    ///
+    /// assert_eq!(InstallerKind::Exe.extension(), "exe")
+    /// ```
+    ///
+    /// The extension for a typical Microsoft installer. Also see the
+    /// [`MSI_FILE_EXTENSION`] constant.
+    ///
+    /// ```
+    /// use wix::create::InstallerKind;
+    ///
+    /// assert_eq!(InstallerKind::Msi.extension(), "msi")
+    /// ```
+    ///
+    /// [Installation Package Bundle]: https://wixtoolset.org/documentation/manual/v3/bundle/
+    /// [`EXE_FILE_EXTENSION`]: lib.html#exe-file-extension
+    /// [`MSI_FILE_EXTENSION`]: lib.html#msi-file-extension
+    pub fn extension(&self) -> &'static str {
+        match *self {
+            Self::Exe => EXE_FILE_EXTENSION,
+            Self::Msi => MSI_FILE_EXTENSION,
+            Self::Zip => ZIP_FILE_EXTENSION,
+        }
+    }
+}
+
+impl FromStr for InstallerKind {
+    type Err = crate::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match &*value.to_lowercase() {
+            "exe" => Ok(Self::Exe),
+            "msi" => Ok(Self::Msi),
+            "zip" => Ok(Self::Zip),
+            _ => Err(Self::Err::Generic(format!(
+                "Unknown '{value}' file extension for an installer"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for InstallerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+impl TryFrom<Vec<WixObjKind>> for InstallerKind {
+    type Error = crate::Error;
+
+    fn try_from(v: Vec<WixObjKind>) -> Result<Self> {
+        v.iter()
+            .fold(None, |a, v| match v {
+                WixObjKind::Bundle => Some(Self::Exe),
+                WixObjKind::Product => a.or(Some(Self::Msi)),
+                _ => a,
+            })
+            .ok_or_else(|| {
+                Self::Error::Generic(String::from(
+                    "Could not determine the installer kind based on the WiX \
+                     object files. There needs to be at least one 'product' or \
+                     'bundle' tag in the collective WiX source files (wxs).",
+                ))
+            })
+    }
+}
+
+/// Details of the cargo build profile
+#[derive(Debug, Clone)]
+pub struct Profile {
+    /// The name of the profile to pass to `--profile=...`
+    name: String,
+    /// The name of the subdirectory of the cargo target dir that the profile will get
+    dir: String,
+}
+
+impl Profile {
+    /// Gets the name of the environment variable that overrides the `strip`
+    /// setting of the profile for Cargo.
+    fn strip_env_var(&self) -> String {
+        format!(
+            "CARGO_PROFILE_{}_STRIP",
+            self.name.to_uppercase().replace('-', "_")
+        )
+    }
+}
+
+/// Details of the cargo build target
+#[derive(Debug, Clone)]
+pub struct Target {
+    /// The name of the target triple being built
+    triple: String,
+    /// If an explicit --target flag is being passed to Cargo, this is it
+    arg: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod builder {
+        use super::*;
+
+        #[test]
+        fn defaults_are_correct() {
+            let actual = Builder::new();
+            assert!(!actual.append_args);
+            assert!(actual.bin_path.is_none());
+            assert!(actual.bundle_upgrade_guid.is_none());
+            assert!(actual.bundle_version.is_none());
+            assert!(actual.capture_output);
+            assert!(actual.changelog.is_none());
+            assert!(actual.compiler_args.is_none());
+            assert!(actual.component_group_refs.is_none());
+            assert!(actual.culture.is_none());
+            assert!(!actual.debug_build);
+            assert!(!actual.debug_name);
+            assert!(actual.dump_wixobj.is_none());
+            assert!(actual.features.is_none());
+            assert!(actual.includes.is_none());
+            assert!(actual.input.is_none());
+            assert!(!actual.keep_going);
+            assert!(actual.linker_args.is_none());
+            assert!(actual.locale.is_none());
+            assert!(actual.name.is_none());
+            assert!(!actual.no_build);
+            assert!(!actual.no_default_features);
+            assert!(!actual.no_default_wix_folder);
+            assert!(!actual.no_install_if_running);
+            assert!(!actual.no_overwrite_increment);
+            assert!(!actual.verify_manifest);
+            assert!(!actual.incremental);
+            assert!(actual.output.is_none());
+            assert!(actual.progress.is_none());
+            assert!(!actual.include_build_metadata);
+            assert!(!actual.quiet_tools);
+            assert!(actual.reinstall_mode.is_none());
+            assert!(actual.retain_symbols.is_none());
+            assert!(!actual.strict);
+            assert!(!actual.strip);
+            assert!(actual.targets.is_none());
+            assert!(actual.version.is_none());
+        }
+
+        #[test]
+        fn allow_unpublished_works() {
+            let mut actual = Builder::new();
+            actual.allow_unpublished(true);
+            assert!(actual.allow_unpublished);
+        }
+
+        #[test]
+        fn append_args_works() {
+            let mut actual = Builder::new();
+            actual.append_args(true);
+            assert!(actual.append_args);
+        }
+
+        #[test]
+        fn arp_size_works() {
+            const EXPECTED: &str = "2048";
+            let mut actual = Builder::new();
+            actual.arp_size(Some(EXPECTED));
+            assert_eq!(actual.arp_size, Some(EXPECTED));
+        }
+
+        #[test]
+        fn bin_path_works() {
+            const EXPECTED: &str = "C:\\Wix Toolset\\bin";
+            let mut actual = Builder::new();
+            actual.bin_path(Some(EXPECTED));
+            assert_eq!(actual.bin_path, Some(EXPECTED));
+        }
+
+        #[test]
+        fn build_command_works() {
+            const EXPECTED: &str = "build.cmd --release";
+            let mut actual = Builder::new();
+            actual.build_command(Some(EXPECTED));
+            assert_eq!(actual.build_command, Some(EXPECTED));
+        }
+
+        #[test]
+        fn bundle_upgrade_guid_works() {
+            const EXPECTED: &str = "B36177BE-EA4D-44FB-B05C-EDDABDAA95CA";
+            let mut actual = Builder::new();
+            actual.bundle_upgrade_guid(Some(EXPECTED));
+            assert_eq!(actual.bundle_upgrade_guid, Some(EXPECTED));
+        }
+
+        #[test]
+        fn bundle_version_works() {
+            const EXPECTED: &str = "1.2.3";
+            let mut actual = Builder::new();
+            actual.bundle_version(Some(EXPECTED));
+            assert_eq!(actual.bundle_version, Some(EXPECTED));
+        }
+
+        #[test]
+        fn target_bin_dir_works() {
+            const EXPECTED: &str = "target\\special\\build\\";
+            let mut actual = Builder::new();
+            actual.target_bin_dir(Some(EXPECTED));
+            assert_eq!(actual.target_bin_dir, Some(EXPECTED));
+        }
+
+        #[test]
+        fn capture_output_works() {
+            let mut actual = Builder::new();
+            actual.capture_output(false);
+            assert!(!actual.capture_output);
+        }
+
+        #[test]
+        fn changelog_works() {
+            const EXPECTED: &str = "C:\\tmp\\hello_world\\CHANGELOG.md";
+            let mut actual = Builder::new();
+            actual.changelog(Some(EXPECTED));
+            assert_eq!(actual.changelog, Some(EXPECTED));
+        }
+
+        #[test]
+        fn component_group_refs_works() {
+            const EXPECTED: &str = "HarvestedFiles";
+            let mut actual = Builder::new();
+            actual.component_group_refs(Some(vec![EXPECTED]));
+            assert_eq!(actual.component_group_refs, Some(vec![EXPECTED]));
+        }
+
+        #[test]
+        fn compiler_args_with_single_value_works() {
+            const EXPECTED: &str = "-nologo";
+            let mut actual = Builder::new();
+            actual.compiler_args(Some(vec![EXPECTED]));
+            assert_eq!(actual.compiler_args, Some(vec![EXPECTED]));
+        }
+
+        #[test]
+        fn compiler_args_with_multiple_values_works() {
+            let expected: Vec<&str> = vec!["-arch", "x86"];
+            let mut actual = Builder::new();
+            actual.compiler_args(Some(expected.clone()));
+            assert_eq!(actual.compiler_args, Some(expected));
+        }
+
+        #[test]
+        fn culture_works() {
+            const EXPECTED: &str = "FrFr";
+            let mut actual = Builder::new();
+            actual.culture(Some(EXPECTED));
+            assert_eq!(actual.culture, Some(EXPECTED));
+        }
+
+        #[test]
+        fn russian_culture_works() {
+            const EXPECTED: &str = "RuRu";
+            let mut actual = Builder::new();
+            actual.culture(Some(EXPECTED));
+            assert_eq!(actual.culture, Some(EXPECTED));
+        }
+
+        #[test]
+        fn debug_build_works() {
+            let mut actual = Builder::new();
+            actual.debug_build(true);
+            assert!(actual.debug_build);
+        }
+
+        #[test]
+        fn profile_works() {
+            const EXPECTED: &str = "dist";
+            let mut actual = Builder::new();
+            actual.profile(Some(EXPECTED));
+            assert_eq!(actual.profile, Some(EXPECTED));
+        }
+
+        #[test]
+        fn debug_name_works() {
+            let mut actual = Builder::new();
+            actual.debug_name(true);
+            assert!(actual.debug_name);
+        }
+
+        #[test]
+        fn dump_defines_works() {
+            let mut actual = Builder::new();
+            actual.dump_defines(true);
+            assert!(actual.dump_defines);
+        }
+
+        #[test]
+        fn dump_toolset_commands_works() {
+            let mut actual = Builder::new();
+            actual.dump_toolset_commands(true);
+            assert!(actual.dump_toolset_commands);
+        }
+
+        #[test]
+        fn dump_wixobj_works() {
+            const EXPECTED: &str = "C:\\tmp\\wixobj";
+            let mut actual = Builder::new();
+            actual.dump_wixobj(Some(EXPECTED));
+            assert_eq!(actual.dump_wixobj, Some(EXPECTED));
+        }
+
+        #[test]
+        fn emit_relative_manifest_path_works() {
+            let mut actual = Builder::new();
+            actual.emit_relative_manifest_path(true);
+            assert!(actual.emit_relative_manifest_path);
+        }
+
+        #[test]
+        fn features_works() {
+            const EXPECTED: &str = "gui";
+            let mut actual = Builder::new();
+            actual.features(Some(vec![EXPECTED]));
+            assert_eq!(actual.features, Some(vec![EXPECTED]));
+        }
+
+        #[test]
+        fn generate_transforms_works() {
+            const EXPECTED: &str = "fr-FR";
+            let mut actual = Builder::new();
+            actual.generate_transforms(Some(vec![EXPECTED]));
+            assert_eq!(actual.generate_transforms, Some(vec![EXPECTED]));
+        }
+
+        #[test]
+        fn dry_run_works() {
+            let mut actual = Builder::new();
+            actual.dry_run(true);
+            assert!(actual.dry_run);
+        }
+
+        #[test]
+        fn format_works() {
+            const EXPECTED: &str = "zip";
+            let mut actual = Builder::new();
+            actual.format(Some(EXPECTED));
+            assert_eq!(actual.format, Some(EXPECTED));
+        }
+
+        #[test]
+        fn includes_works() {
+            const EXPECTED: &str = "C:\\tmp\\hello_world\\wix\\main.wxs";
+            let mut actual = Builder::new();
+            actual.includes(Some(vec![EXPECTED]));
+            assert_eq!(actual.includes, Some(vec![EXPECTED]));
+        }
+
+        #[test]
+        fn input_works() {
+            const EXPECTED: &str = "C:\\tmp\\hello_world\\Cargo.toml";
+            let mut actual = Builder::new();
+            actual.input(Some(EXPECTED));
+            assert_eq!(actual.input, Some(EXPECTED));
+        }
+
+        #[test]
+        fn linker_args_with_single_value_works() {
+            const EXPECTED: &str = "-nologo";
+            let mut actual = Builder::new();
+            actual.linker_args(Some(vec![EXPECTED]));
+            assert_eq!(actual.linker_args, Some(vec![EXPECTED]));
+        }
+
+        #[test]
+        fn linker_args_with_multiple_values_works() {
+            let expected: Vec<&str> = vec!["-ext", "HelloExtension"];
+            let mut actual = Builder::new();
+            actual.linker_args(Some(expected.clone()));
+            assert_eq!(actual.linker_args, Some(expected));
+        }
+
+        #[test]
+        fn locale_works() {
+            const EXPECTED: &str = "C:\\tmp\\hello_world\\wix\\main.wxl";
+            let mut actual = Builder::new();
+            actual.locale(Some(EXPECTED));
+            assert_eq!(actual.locale, Some(EXPECTED));
+        }
+
+        #[test]
+        fn metadata_output_works() {
+            const EXPECTED: &str = "target\\wix\\";
+            let mut actual = Builder::new();
+            actual.metadata_output(Some(EXPECTED));
+            assert_eq!(actual.metadata_output, Some(EXPECTED));
+        }
+
+        #[test]
+        fn name_works() {
+            const EXPECTED: &str = "Name";
+            let mut actual = Builder::new();
+            actual.name(Some(EXPECTED));
+            assert_eq!(actual.name, Some(EXPECTED));
+        }
+
+        #[test]
+        fn no_arch_in_name_works() {
+            let mut actual = Builder::new();
+            actual.no_arch_in_name(true);
+            assert!(actual.no_arch_in_name);
+        }
+
+        #[test]
+        fn keep_going_works() {
+            let mut actual = Builder::new();
+            actual.keep_going(true);
+            assert!(actual.keep_going);
+        }
+
+        #[test]
+        fn no_build_works() {
+            let mut actual = Builder::new();
+            actual.no_build(true);
+            assert!(actual.no_build);
+        }
+
+        #[test]
+        fn no_default_features_works() {
+            let mut actual = Builder::new();
+            actual.no_default_features(true);
+            assert!(actual.no_default_features);
+        }
+
+        #[test]
+        fn no_default_wix_folder_works() {
+            let mut actual = Builder::new();
+            actual.no_default_wix_folder(true);
+            assert!(actual.no_default_wix_folder);
+        }
+
+        #[test]
+        fn no_install_if_running_works() {
+            let mut actual = Builder::new();
+            actual.no_install_if_running(true);
+            assert!(actual.no_install_if_running);
+        }
+
+        #[test]
+        fn no_overwrite_increment_works() {
+            let mut actual = Builder::new();
+            actual.no_overwrite_increment(true);
+            assert!(actual.no_overwrite_increment);
+        }
+
+        #[test]
+        fn incremental_works() {
             let mut actual = Builder::new();
-            actual.culture(Some(EXPECTED));
-            assert_eq!(actual.culture, Some(EXPECTED));
+            actual.incremental(true);
+            assert!(actual.incremental);
+        }
+
+        #[test]
+        fn verify_manifest_works() {
+            let mut actual = Builder::new();
+            actual.verify_manifest(true);
+            assert!(actual.verify_manifest);
+        }
+
+        #[test]
+        fn output_works() {
+            const EXPECTED: &str = "C:\\tmp\\hello_world\\output";
+            let mut actual = Builder::new();
+            actual.output(Some(EXPECTED));
+            assert_eq!(actual.output, Some(EXPECTED));
+        }
+
+        #[test]
+        fn output_dir_works() {
+            const EXPECTED: &str = "C:\\tmp\\hello_world\\dist";
+            let mut actual = Builder::new();
+            actual.output_dir(Some(EXPECTED));
+            assert_eq!(actual.output_dir, Some(EXPECTED));
+        }
+
+        #[test]
+        fn output_layout_works() {
+            const EXPECTED: &str = "per-arch";
+            let mut actual = Builder::new();
+            actual.output_layout(Some(EXPECTED));
+            assert_eq!(actual.output_layout, Some(EXPECTED));
+        }
+
+        #[test]
+        fn properties_works() {
+            const EXPECTED: &str = "INSTALLDESKTOPSHORTCUT=1";
+            let mut actual = Builder::new();
+            actual.properties(Some(vec![EXPECTED]));
+            assert_eq!(actual.properties, Some(vec![EXPECTED]));
+        }
+
+        #[test]
+        fn check_localization_works() {
+            let mut actual = Builder::new();
+            actual.check_localization(true);
+            assert!(actual.check_localization);
+        }
+
+        #[test]
+        fn checksum_manifest_works() {
+            const EXPECTED: &str = "dist\\SHA256SUMS";
+            let mut actual = Builder::new();
+            actual.checksum_manifest(Some(EXPECTED));
+            assert_eq!(actual.checksum_manifest, Some(EXPECTED));
+        }
+
+        #[test]
+        fn preprocess_only_works() {
+            const EXPECTED: &str = "target\\wix\\preprocessed";
+            let mut actual = Builder::new();
+            actual.preprocess_only(Some(EXPECTED));
+            assert_eq!(actual.preprocess_only, Some(EXPECTED));
+        }
+
+        #[test]
+        fn print_wxs_works() {
+            let mut actual = Builder::new();
+            actual.print_wxs(true);
+            assert!(actual.print_wxs);
+        }
+
+        #[test]
+        fn product_language_works() {
+            const EXPECTED: &str = "1036";
+            let mut actual = Builder::new();
+            actual.product_language(Some(EXPECTED));
+            assert_eq!(actual.product_language, Some(EXPECTED));
+        }
+
+        #[test]
+        fn progress_works() {
+            let mut actual = Builder::new();
+            actual.progress(|_| {});
+            assert!(actual.progress.is_some());
+        }
+
+        #[test]
+        fn include_build_metadata_works() {
+            let mut actual = Builder::new();
+            actual.include_build_metadata(true);
+            assert!(actual.include_build_metadata);
+        }
+
+        #[test]
+        fn quiet_tools_works() {
+            let mut actual = Builder::new();
+            actual.quiet_tools(true);
+            assert!(actual.quiet_tools);
+        }
+
+        #[test]
+        fn reinstall_mode_works() {
+            const EXPECTED: &str = "amus";
+            let mut actual = Builder::new();
+            actual.reinstall_mode(Some(EXPECTED));
+            assert_eq!(actual.reinstall_mode, Some(EXPECTED));
+        }
+
+        #[test]
+        fn retain_symbols_works() {
+            const EXPECTED: &str = "yes";
+            let mut actual = Builder::new();
+            actual.retain_symbols(Some(EXPECTED));
+            assert_eq!(actual.retain_symbols, Some(EXPECTED));
+        }
+
+        #[test]
+        fn strict_works() {
+            let mut actual = Builder::new();
+            actual.strict(true);
+            assert!(actual.strict);
+        }
+
+        #[test]
+        fn strip_works() {
+            let mut actual = Builder::new();
+            actual.strip(true);
+            assert!(actual.strip);
+        }
+
+        #[test]
+        fn target_works() {
+            const EXPECTED: &str = "i686-pc-windows-msvc";
+            let mut actual = Builder::new();
+            actual.target(Some(EXPECTED));
+            assert_eq!(actual.targets, Some(vec![EXPECTED]));
+        }
+
+        #[test]
+        fn targets_works() {
+            const EXPECTED: [&str; 2] = ["i686-pc-windows-msvc", "x86_64-pc-windows-msvc"];
+            let mut actual = Builder::new();
+            actual.targets(Some(EXPECTED.to_vec()));
+            assert_eq!(actual.targets, Some(EXPECTED.to_vec()));
+        }
+
+        #[test]
+        fn version_works() {
+            const EXPECTED: &str = "1.2.3";
+            let mut actual = Builder::new();
+            actual.version(Some(EXPECTED));
+            assert_eq!(actual.version, Some(EXPECTED));
+        }
+
+        #[test]
+        fn wix_root_works() {
+            const EXPECTED: &str = "C:\\WiX Toolset";
+            let mut actual = Builder::new();
+            actual.wix_root(Some(EXPECTED));
+            assert_eq!(actual.wix_root, Some(EXPECTED));
+        }
+
+        #[test]
+        fn timeout_works() {
+            const EXPECTED: &str = "600";
+            let mut actual = Builder::new();
+            actual.timeout(Some(EXPECTED));
+            assert_eq!(actual.timeout, Some(EXPECTED));
+        }
+
+        #[test]
+        fn wxs_sources_works() {
+            const EXPECTED: &str = "C:\\main.wxs";
+            let mut actual = Builder::new();
+            actual.wxs_sources(Some(vec![EXPECTED]));
+            assert_eq!(actual.wxs_sources, Some(vec![EXPECTED]));
+        }
+
+        #[test]
+        fn wxs_variable_check_works() {
+            let mut actual = Builder::new();
+            actual.wxs_variable_check(true);
+            assert!(actual.wxs_variable_check);
+        }
+
+        #[test]
+        fn build_with_defaults_works() {
+            let mut b = Builder::new();
+            let default_execution = b.build();
+            assert!(default_execution.bin_path.is_none());
+            assert!(default_execution.bundle_upgrade_guid.is_none());
+            assert!(default_execution.bundle_version.is_none());
+            assert!(default_execution.capture_output);
+            assert!(default_execution.changelog.is_none());
+            assert!(default_execution.checksum_manifest.is_none());
+            assert!(default_execution.compiler_args.is_none());
+            assert!(default_execution.component_group_refs.is_none());
+            assert!(default_execution.culture.is_none());
+            assert!(!default_execution.debug_build);
+            assert!(!default_execution.debug_name);
+            assert!(!default_execution.dump_defines);
+            assert!(!default_execution.dump_toolset_commands);
+            assert!(default_execution.dump_wixobj.is_none());
+            assert!(default_execution.generate_transforms.is_none());
+            assert!(default_execution.includes.is_none());
+            assert!(default_execution.input.is_none());
+            assert!(!default_execution.keep_going);
+            assert!(default_execution.linker_args.is_none());
+            assert!(default_execution.locale.is_none());
+            assert!(default_execution.name.is_none());
+            assert!(!default_execution.no_arch_in_name);
+            assert!(!default_execution.no_build);
+            assert!(!default_execution.no_default_wix_folder);
+            assert!(!default_execution.no_install_if_running);
+            assert!(!default_execution.no_overwrite_increment);
+            assert!(!default_execution.verify_manifest);
+            assert!(!default_execution.incremental);
+            assert!(default_execution.output.is_none());
+            assert!(default_execution.preprocess_only.is_none());
+            assert!(default_execution.progress.is_none());
+            assert!(!default_execution.include_build_metadata);
+            assert!(!default_execution.quiet_tools);
+            assert!(default_execution.reinstall_mode.is_none());
+            assert!(default_execution.targets.is_none());
+            assert!(default_execution.version.is_none());
+        }
+
+        #[test]
+        fn build_with_all_works() {
+            const EXPECTED_BIN_PATH: &str = "C:\\Wix Toolset\\bin";
+            const EXPECTED_BUNDLE_UPGRADE_GUID: &str = "B36177BE-EA4D-44FB-B05C-EDDABDAA95CA";
+            const EXPECTED_BUNDLE_VERSION: &str = "2.0.0";
+            const EXPECTED_CULTURE: &str = "FrFr";
+            const EXPECTED_CHANGELOG: &str = "C:\\tmp\\hello_world\\CHANGELOG.md";
+            const EXPECTED_COMPILER_ARGS: &str = "-nologo";
+            const EXPECTED_COMPONENT_GROUP_REFS: &str = "HarvestedFiles";
+            const EXPECTED_DUMP_WIXOBJ: &str = "C:\\tmp\\hello_world\\wixobj";
+            const EXPECTED_INCLUDES: &str = "C:\\tmp\\hello_world\\wix\\main.wxs";
+            const EXPECTED_INPUT: &str = "C:\\tmp\\hello_world\\Cargo.toml";
+            const EXPECTED_LINKER_ARGS: &str = "-nologo";
+            const EXPECTED_LOCALE: &str = "C:\\tmp\\hello_world\\wix\\main.wxl";
+            const EXPECTED_NAME: &str = "Name";
+            const EXPECTED_OUTPUT: &str = "C:\\tmp\\hello_world\\output";
+            const EXPECTED_REINSTALL_MODE: &str = "amus";
+            const EXPECTED_TARGETS: [&str; 2] = ["i686-pc-windows-msvc", "x86_64-pc-windows-msvc"];
+            const EXPECTED_VERSION: &str = "1.2.3";
+            let mut b = Builder::new();
+            b.bin_path(Some(EXPECTED_BIN_PATH));
+            b.bundle_upgrade_guid(Some(EXPECTED_BUNDLE_UPGRADE_GUID));
+            b.bundle_version(Some(EXPECTED_BUNDLE_VERSION));
+            b.capture_output(false);
+            b.changelog(Some(EXPECTED_CHANGELOG));
+            b.culture(Some(EXPECTED_CULTURE));
+            b.compiler_args(Some(vec![EXPECTED_COMPILER_ARGS]));
+            b.component_group_refs(Some(vec![EXPECTED_COMPONENT_GROUP_REFS]));
+            b.debug_build(true);
+            b.debug_name(true);
+            b.dump_wixobj(Some(EXPECTED_DUMP_WIXOBJ));
+            b.includes(Some(vec![EXPECTED_INCLUDES]));
+            b.input(Some(EXPECTED_INPUT));
+            b.keep_going(true);
+            b.linker_args(Some(vec![EXPECTED_LINKER_ARGS]));
+            b.locale(Some(EXPECTED_LOCALE));
+            b.name(Some(EXPECTED_NAME));
+            b.no_build(true);
+            b.no_default_wix_folder(true);
+            b.no_install_if_running(true);
+            b.no_overwrite_increment(true);
+            b.verify_manifest(true);
+            b.incremental(true);
+            b.output(Some(EXPECTED_OUTPUT));
+            b.include_build_metadata(true);
+            b.quiet_tools(true);
+            b.reinstall_mode(Some(EXPECTED_REINSTALL_MODE));
+            b.targets(Some(EXPECTED_TARGETS.to_vec()));
+            b.version(Some(EXPECTED_VERSION));
+            let execution = b.build();
+            assert_eq!(execution.bin_path, Some(PathBuf::from(EXPECTED_BIN_PATH)));
+            assert_eq!(
+                execution.bundle_upgrade_guid,
+                Some(String::from(EXPECTED_BUNDLE_UPGRADE_GUID))
+            );
+            assert_eq!(
+                execution.bundle_version,
+                Some(String::from(EXPECTED_BUNDLE_VERSION))
+            );
+            assert!(!execution.capture_output);
+            assert_eq!(execution.changelog, Some(PathBuf::from(EXPECTED_CHANGELOG)));
+            assert_eq!(
+                execution.compiler_args,
+                Some(vec![String::from(EXPECTED_COMPILER_ARGS)])
+            );
+            assert_eq!(
+                execution.component_group_refs,
+                Some(vec![String::from(EXPECTED_COMPONENT_GROUP_REFS)])
+            );
+            assert_eq!(execution.culture, Some(String::from(EXPECTED_CULTURE)));
+            assert!(execution.debug_build);
+            assert!(execution.debug_name);
+            assert_eq!(
+                execution.dump_wixobj,
+                Some(PathBuf::from(EXPECTED_DUMP_WIXOBJ))
+            );
+            assert_eq!(
+                execution.includes,
+                Some(vec![PathBuf::from(EXPECTED_INCLUDES)])
+            );
+            assert_eq!(execution.input, Some(PathBuf::from(EXPECTED_INPUT)));
+            assert!(execution.keep_going);
+            assert_eq!(
+                execution.linker_args,
+                Some(vec![String::from(EXPECTED_LINKER_ARGS)])
+            );
+            assert_eq!(execution.locale, Some(PathBuf::from(EXPECTED_LOCALE)));
+            assert_eq!(execution.name, Some(String::from(EXPECTED_NAME)));
+            assert!(execution.no_build);
+            assert!(execution.no_default_wix_folder);
+            assert!(execution.no_install_if_running);
+            assert!(execution.no_overwrite_increment);
+            assert!(execution.verify_manifest);
+            assert!(execution.incremental);
+            assert_eq!(execution.output, Some(String::from(EXPECTED_OUTPUT)));
+            assert!(execution.include_build_metadata);
+            assert!(execution.quiet_tools);
+            assert_eq!(
+                execution.reinstall_mode,
+                Some(String::from(EXPECTED_REINSTALL_MODE))
+            );
+            assert_eq!(
+                execution.targets,
+                Some(EXPECTED_TARGETS.iter().map(|t| String::from(*t)).collect())
+            );
+            assert_eq!(execution.version, Some(String::from(EXPECTED_VERSION)));
+        }
+    }
+
+    mod execution {
+        use super::*;
+        use serial_test::serial;
+        use std::time::{Duration, Instant};
+
+        #[test]
+        fn default_profile_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": { }
+            }"#;
+            let execution = Execution::default();
+            let profile = execution.profile(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(profile.name, "release");
+            assert_eq!(profile.dir, "release");
+        }
+
+        #[test]
+        fn debug_build_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "dbg-build": true
+                }
+            }"#;
+            let execution = Execution::default();
+            let profile = execution.profile(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(profile.name, "dev");
+            assert_eq!(profile.dir, "debug");
+        }
+
+        #[test]
+        fn debug_name_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "dbg-name": true
+                }
+            }"#;
+            let execution = Execution::default();
+            let debug_name = execution.debug_name(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert!(debug_name);
+        }
+
+        #[test]
+        fn version_metadata_works() {
+            const PKG_META_WIX: &str = r#"
+            {
+                "name": "Example",
+                "version": "0.1.0",
+                "authors": ["First Last <first.last@example.com>"],
+                "license": "Apache-2.0",
+
+                "id": "",
+                "dependencies": [],
+                "targets": [],
+                "features": {},
+                "manifest_path": "",
+
+                "metadata": {
+                    "wix": {
+                        "version": "2.1.0"
+                    }
+                }
+            }"#;
+            let execution = Execution::default();
+            let version = execution
+                .version(&serde_json::from_str(PKG_META_WIX).unwrap())
+                .unwrap();
+            assert_eq!(version, "2.1.0");
+        }
+
+        #[test]
+        fn profile_metadata_works() {
+            const PKG_META_WIX: &str = r#" {
+                "wix": {
+                    "profile": "dist"
+                }
+            }"#;
+            let execution = Execution::default();
+            let profile = execution.profile(&serde_json::from_str(PKG_META_WIX).unwrap());
+            assert_eq!(profile.name, "dist");
+            assert_eq!(profile.dir, "dist");
+        }
+
+        #[test]
+        fn version_prerelease_parse_works() {
+            const PKG_META_WIX: &str = r#"
+            {
+                "name": "Example",
+                "version": "2.1.0-5",
+                "authors": ["First Last <first.last@example.com>"],
+                "license": "Apache-2.0",
+
+                "id": "",
+                "dependencies": [],
+                "targets": [],
+                "features": {},
+                "manifest_path": ""
+            }"#;
+            let execution = Execution::default();
+            let version = execution
+                .version(&serde_json::from_str(PKG_META_WIX).unwrap())
+                .unwrap();
+            assert_eq!(version, "2.1.0.5");
+        }
+
+        #[test]
+        fn version_prerelease_dot_parse_works() {
+            const PKG_META_WIX: &str = r#"
+            {
+                "name": "Example",
+                "version": "2.1.0-prerelease.5",
+                "authors": ["First Last <first.last@example.com>"],
+                "license": "Apache-2.0",
+
+                "id": "",
+                "dependencies": [],
+                "targets": [],
+                "features": {},
+                "manifest_path": ""
+            }"#;
+            let execution = Execution::default();
+            let version = execution
+                .version(&serde_json::from_str(PKG_META_WIX).unwrap())
+                .unwrap();
+            assert_eq!(version, "2.1.0.5");
+        }
+
+        #[test]
+        fn version_build_parse_works() {
+            const PKG_META_WIX: &str = r#"
+            {
+                "name": "Example",
+                "version": "2.1.0-prerelease+5",
+                "authors": ["First Last <first.last@example.com>"],
+                "license": "Apache-2.0",
+
+                "id": "",
+                "dependencies": [],
+                "targets": [],
+                "features": {},
+                "manifest_path": ""
+            }"#;
+            let execution = Execution::default();
+            let version = execution
+                .version(&serde_json::from_str(PKG_META_WIX).unwrap())
+                .unwrap();
+            assert_eq!(version, "2.1.0.5");
+        }
+
+        #[test]
+        fn name_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "name": "Example",
+                "version": "0.1.0",
+                "metadata": {
+                    "wix": {
+                        "name": "Metadata"
+                    }
+                },
+
+                "id": "",
+                "dependencies": [],
+                "targets": [],
+                "features": {},
+                "manifest_path": ""
+            }"#;
+            let execution = Execution::default();
+            let name = execution.name(&serde_json::from_str(PKG_META_WIX).unwrap());
+            assert_eq!(name, "Metadata".to_owned());
+        }
+
+        #[test]
+        fn no_build_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "no-build": true
+                }
+            }"#;
+            let execution = Execution::default();
+            let no_build = execution.no_build(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert!(no_build);
+        }
+
+        #[test]
+        fn features_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "features": ["gui,tray", "color-output"]
+                }
+            }"#;
+            let execution = Execution::default();
+            let features = execution
+                .features(&PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(features, vec!["gui", "tray", "color-output"]);
+        }
+
+        #[test]
+        fn features_override_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "features": ["gui"]
+                }
+            }"#;
+            let mut builder = Builder::new();
+            builder.features(Some(vec!["cli color-output"]));
+            let execution = builder.build();
+            let features = execution
+                .features(&PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(features, vec!["cli", "color-output"]);
+        }
+
+        #[test]
+        fn no_default_features_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "no-default-features": true
+                }
+            }"#;
+            let execution = Execution::default();
+            assert!(execution.no_default_features(&PKG_META_WIX.parse::<Value>().unwrap()));
+        }
+
+        #[test]
+        fn build_command_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "build-command": "build.cmd"
+                }
+            }"#;
+            let execution = Execution::default();
+            let build_command = execution.build_command(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(build_command, Some(String::from("build.cmd")));
+        }
+
+        #[test]
+        fn build_command_override_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "build-command": "build.cmd"
+                }
+            }"#;
+            let mut builder = Builder::new();
+            builder.build_command(Some("make installer"));
+            let execution = builder.build();
+            let build_command = execution.build_command(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(build_command, Some(String::from("make installer")));
+        }
+
+        #[test]
+        fn culture_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "culture": "Fr-Fr"
+                }
+            }"#;
+            let execution = Execution::default();
+            let culture = execution
+                .culture(&PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(culture, Cultures::FrFr);
+        }
+
+        #[test]
+        fn culture_default_culture_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "default-culture": "de-DE"
+                }
+            }"#;
+            let execution = Execution::default();
+            let culture = execution
+                .culture(&PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(culture, Cultures::DeDe);
+        }
+
+        #[test]
+        fn culture_metadata_and_default_culture_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "culture": "fr-FR",
+                    "default-culture": "de-DE"
+                }
+            }"#;
+            let mut builder = Builder::new();
+            let metadata = PKG_META_WIX.parse::<Value>().unwrap();
+            assert_eq!(builder.build().culture(&metadata).unwrap(), Cultures::FrFr);
+            builder.culture(Some("ja-JP"));
+            assert_eq!(builder.build().culture(&metadata).unwrap(), Cultures::JaJp);
+        }
+
+        #[test]
+        fn culture_list_works() {
+            let mut builder = Builder::new();
+            builder.culture(Some("fr-FR, de-DE,es-ES"));
+            builder.generate_transforms(Some(vec!["ja-JP", "de-DE"]));
+            let execution = builder.build();
+            let metadata = Value::Null;
+            let culture = execution.culture(&metadata).unwrap();
+            assert_eq!(culture, Cultures::FrFr);
+            assert_eq!(
+                execution.transform_cultures(&metadata, &culture).unwrap(),
+                vec![Cultures::DeDe, Cultures::EsEs, Cultures::JaJp]
+            );
+        }
+
+        #[test]
+        fn culture_metadata_array_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "culture": ["de-DE", "fr-FR"],
+                    "default-culture": "ja-JP"
+                }
+            }"#;
+            let execution = Execution::default();
+            let metadata = PKG_META_WIX.parse::<Value>().unwrap();
+            let culture = execution.culture(&metadata).unwrap();
+            assert_eq!(culture, Cultures::DeDe);
+            assert_eq!(
+                execution.transform_cultures(&metadata, &culture).unwrap(),
+                vec![Cultures::FrFr]
+            );
+        }
+
+        #[test]
+        fn culture_metadata_list_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "culture": "es-ES,fr-FR"
+                }
+            }"#;
+            let execution = Execution::default();
+            let metadata = PKG_META_WIX.parse::<Value>().unwrap();
+            let culture = execution.culture(&metadata).unwrap();
+            assert_eq!(culture, Cultures::EsEs);
+            assert_eq!(
+                execution.transform_cultures(&metadata, &culture).unwrap(),
+                vec![Cultures::FrFr]
+            );
+        }
+
+        #[test]
+        fn locale_metadata_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                locale = "wix/French.wxl"
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let execution = Execution::default();
+            let locale = execution.locale(&package).unwrap();
+            assert_eq!(locale, Some(project.path().join("wix/French.wxl")));
+        }
+
+        #[test]
+        fn changelog_metadata_is_relative_to_manifest() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "1.2.3"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                changelog = "docs/History.md"
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            std::fs::create_dir(project.path().join("docs")).unwrap();
+            std::fs::write(project.path().join("docs").join("History.md"), CHANGELOG).unwrap();
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let execution = Execution::default();
+            let changelog = execution.changelog(&package).unwrap();
+            assert!(changelog.is_some());
+        }
+
+        #[test]
+        fn changelog_is_opt_in() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "1.2.3"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            std::fs::write(project.path().join(CHANGELOG_FILE_NAME), CHANGELOG).unwrap();
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let execution = Execution::default();
+            assert!(execution.changelog(&package).unwrap().is_none());
+        }
+
+        #[test]
+        fn changelog_with_byte_order_mark_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "1.2.3"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                changelog = true
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let mut content = vec![0xFF, 0xFE];
+            content.extend(
+                "## [1.2.3]\n\n- A new feature\n"
+                    .encode_utf16()
+                    .flat_map(u16::to_le_bytes),
+            );
+            std::fs::write(project.path().join(CHANGELOG_FILE_NAME), content).unwrap();
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let execution = Execution::default();
+            assert_eq!(
+                execution.changelog(&package).unwrap(),
+                Some(String::from("A new feature"))
+            );
+        }
+
+        #[test]
+        fn changelog_metadata_enabled_uses_changelog_alongside_manifest() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "1.2.3"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                changelog = true
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            std::fs::write(project.path().join(CHANGELOG_FILE_NAME), CHANGELOG).unwrap();
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let execution = Execution::default();
+            assert_eq!(
+                execution.changelog(&package).unwrap(),
+                Some(String::from("A new feature"))
+            );
+        }
+
+        #[test]
+        fn output_metadata_is_relative_to_manifest() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                output = "dist/"
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let execution = Execution::default();
+            let output = execution.installer_destination(
+                "Example",
+                "0.1.0",
+                &Cfg::of("x86_64-pc-windows-msvc").unwrap(),
+                false,
+                &InstallerKind::default(),
+                &package,
+                Path::new("target/"),
+            );
+            assert_eq!(
+                output,
+                project.path().join("dist").join("Example-0.1.0-x86_64.msi")
+            );
+        }
+
+        #[test]
+        fn installer_destination_with_no_arch_in_name_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+            let cfg = Cfg::of("x86_64-pc-windows-msvc").unwrap();
+            let execution = Builder::new().no_arch_in_name(true).build();
+
+            let output = execution.installer_destination(
+                "Example",
+                "0.1.0",
+                &cfg,
+                false,
+                &InstallerKind::default(),
+                &package,
+                Path::new("target"),
+            );
+            assert_eq!(
+                output,
+                Path::new("target").join(WIX).join("Example-0.1.0.msi")
+            );
+            let output = execution.installer_destination(
+                "Example",
+                "0.1.0",
+                &cfg,
+                true,
+                &InstallerKind::default(),
+                &package,
+                Path::new("target"),
+            );
+            assert_eq!(
+                output,
+                Path::new("target")
+                    .join(WIX)
+                    .join("Example-0.1.0-debug.msi")
+            );
+        }
+
+        #[test]
+        fn installer_destination_with_no_arch_in_name_metadata_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                no-arch-in-name = true
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let output = Execution::default().installer_destination(
+                "Example",
+                "0.1.0",
+                &Cfg::of("x86_64-pc-windows-msvc").unwrap(),
+                false,
+                &InstallerKind::default(),
+                &package,
+                Path::new("target"),
+            );
+            assert_eq!(
+                output,
+                Path::new("target").join(WIX).join("Example-0.1.0.msi")
+            );
+        }
+
+        #[test]
+        fn commands_with_no_arch_in_name_and_multiple_targets_fails() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            std::fs::create_dir(project.path().join(WIX)).unwrap();
+            std::fs::write(
+                project.path().join(WIX).join("main.wxs"),
+                "<Wix><Product/></Wix>",
+            )
+            .unwrap();
+
+            let result = Builder::new()
+                .input(project.path().join("Cargo.toml").to_str())
+                .no_arch_in_name(true)
+                .targets(Some(vec!["x86_64-pc-windows-msvc", "i686-pc-windows-msvc"]))
+                .build()
+                .commands();
+            assert!(result.unwrap_err().to_string().contains("architecture"));
+        }
+
+        #[test]
+        fn output_dir_without_existing_folder_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+            let output_dir = project.path().join("dist");
+            let output = Builder::new()
+                .output(Some("other.msi"))
+                .output_dir(output_dir.to_str())
+                .build()
+                .installer_destination(
+                    "Example",
+                    "0.1.0",
+                    &Cfg::of("x86_64-pc-windows-msvc").unwrap(),
+                    false,
+                    &InstallerKind::default(),
+                    &package,
+                    Path::new("target/"),
+                );
+            assert_eq!(output, output_dir.join("Example-0.1.0-x86_64.msi"));
+        }
+
+        #[test]
+        fn installer_destination_with_per_arch_layout_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+            let output_dir = project.path().join("dist");
+            let execution = Builder::new()
+                .output_dir(output_dir.to_str())
+                .output_layout(Some("per-arch"))
+                .no_arch_in_name(true)
+                .build();
+
+            let output = execution.installer_destination(
+                "Example",
+                "0.1.0",
+                &Cfg::of("x86_64-pc-windows-msvc").unwrap(),
+                false,
+                &InstallerKind::default(),
+                &package,
+                Path::new("target"),
+            );
+            assert_eq!(output, output_dir.join("x64").join("Example-0.1.0.msi"));
+            let output = execution.installer_destination(
+                "Example",
+                "0.1.0",
+                &Cfg::of("i686-pc-windows-msvc").unwrap(),
+                false,
+                &InstallerKind::default(),
+                &package,
+                Path::new("target"),
+            );
+            assert_eq!(output, output_dir.join("x86").join("Example-0.1.0.msi"));
+        }
+
+        #[test]
+        fn installer_destination_with_per_arch_layout_metadata_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                output-layout = "per-arch"
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let output = Execution::default().installer_destination(
+                "Example",
+                "0.1.0",
+                &Cfg::of("x86_64-pc-windows-msvc").unwrap(),
+                false,
+                &InstallerKind::default(),
+                &package,
+                Path::new("target"),
+            );
+            assert_eq!(
+                output,
+                Path::new("target")
+                    .join(WIX)
+                    .join("x64")
+                    .join("Example-0.1.0-x86_64.msi")
+            );
+            let output = Builder::new()
+                .output_layout(Some("flat"))
+                .build()
+                .installer_destination(
+                    "Example",
+                    "0.1.0",
+                    &Cfg::of("x86_64-pc-windows-msvc").unwrap(),
+                    false,
+                    &InstallerKind::default(),
+                    &package,
+                    Path::new("target"),
+                );
+            assert_eq!(
+                output,
+                Path::new("target")
+                    .join(WIX)
+                    .join("Example-0.1.0-x86_64.msi")
+            );
+        }
+
+        #[test]
+        fn commands_with_per_arch_layout_and_multiple_targets_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            std::fs::create_dir(project.path().join(WIX)).unwrap();
+            std::fs::write(
+                project.path().join(WIX).join("main.wxs"),
+                "<Wix><Product/></Wix>",
+            )
+            .unwrap();
+
+            let commands = Builder::new()
+                .input(project.path().join("Cargo.toml").to_str())
+                .no_arch_in_name(true)
+                .output_layout(Some("per-arch"))
+                .targets(Some(vec!["x86_64-pc-windows-msvc", "i686-pc-windows-msvc"]))
+                .build()
+                .commands()
+                .unwrap();
+            let linked = |arch: &str| {
+                let installer = Path::new(arch).join("Example-0.1.0.msi");
+                commands
+                    .iter()
+                    .any(|c| c.contains(WIX_LINKER) && c.contains(installer.to_str().unwrap()))
+            };
+            assert!(linked("x64"));
+            assert!(linked("x86"));
+        }
+
+        #[test]
+        fn commands_with_invalid_output_layout_fails() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+
+            let result = Builder::new()
+                .input(project.path().join("Cargo.toml").to_str())
+                .output_layout(Some("nested"))
+                .build()
+                .commands();
+            assert!(result.unwrap_err().to_string().contains("output layout"));
+        }
+
+        #[test]
+        fn installer_folder_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+            let target = Path::new("target");
+            assert_eq!(
+                Execution::default().installer_folder(&package, target),
+                target.join(WIX)
+            );
+            assert_eq!(
+                Builder::new()
+                    .output(Some("dist/Example.msi"))
+                    .build()
+                    .installer_folder(&package, target),
+                PathBuf::from("dist")
+            );
+            assert_eq!(
+                Builder::new()
+                    .output(Some("dist/"))
+                    .build()
+                    .installer_folder(&package, target),
+                PathBuf::from("dist/")
+            );
+            assert_eq!(
+                Builder::new()
+                    .output(Some("Example.msi"))
+                    .output_dir(Some("other"))
+                    .build()
+                    .installer_folder(&package, target),
+                PathBuf::from("other")
+            );
+        }
+
+        #[test]
+        fn check_writable_creates_folders() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let folder = temp_dir.path().join("dist").join("installers");
+            check_writable(&folder).unwrap();
+            assert!(folder.is_dir());
+            assert_eq!(std::fs::read_dir(&folder).unwrap().count(), 0);
+        }
+
+        #[test]
+        fn check_writable_with_file_in_path_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let file = temp_dir.path().join("dist");
+            std::fs::write(&file, "").unwrap();
+            let err = check_writable(&file.join("installers")).unwrap_err();
+            assert!(matches!(err, Error::Io(_)));
+            assert!(err.to_string().contains("cannot be written"));
+        }
+
+        #[test]
+        fn no_overwrite_increment_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "no-overwrite-increment": true
+                }
+            }"#;
+            let execution = Execution::default();
+            assert!(
+                !execution.no_overwrite_increment(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+            );
+            assert!(execution.no_overwrite_increment(&PKG_META_WIX.parse::<Value>().unwrap()));
+        }
+
+        #[test]
+        fn incremented_destination_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let destination = temp_dir.path().join("Example-0.1.0-x86_64.msi");
+            assert_eq!(incremented_destination(&destination), destination);
+            std::fs::write(&destination, "").unwrap();
+            assert_eq!(
+                incremented_destination(&destination),
+                temp_dir.path().join("Example-0.1.0-x86_64-1.msi")
+            );
+            std::fs::write(temp_dir.path().join("Example-0.1.0-x86_64-1.msi"), "").unwrap();
+            assert_eq!(
+                incremented_destination(&destination),
+                temp_dir.path().join("Example-0.1.0-x86_64-2.msi")
+            );
+        }
+
+        #[test]
+        fn output_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "name": "Example",
+                "version": "0.1.0",
+                "authors": ["First Last <first.last@example.com>"],
+                "license": "XYZ",
+
+                "id": "",
+                "dependencies": [],
+                "targets": [],
+                "features": {},
+                "manifest_path": "",
+                "metadata": {
+                    "wix": {
+                        "output": "target/wix/test.msi"
+                    }
+                }
+            }"#;
+            let execution = Execution::default();
+            let output = execution.installer_destination(
+                "Different",
+                "2.1.0",
+                &Cfg::of("x86_64-pc-windows-msvc").unwrap(),
+                false,
+                &InstallerKind::default(),
+                &serde_json::from_str(PKG_META_WIX).unwrap(),
+                Path::new("target/"),
+            );
+            assert_eq!(output, PathBuf::from("target/wix/test.msi"));
+        }
+
+        #[test]
+        fn include_metadata_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                include = ["installer/other.wxs"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let included = project.path().join("installer").join("other.wxs");
+            std::fs::create_dir(project.path().join("installer")).unwrap();
+            std::fs::write(&included, "").unwrap();
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let execution = Execution::default();
+            let sources = execution.wxs_sources(&package).unwrap();
+            assert_eq!(sources, vec![included]);
+        }
+
+        #[test]
+        fn no_default_wix_folder_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "no-default-wix-folder": true
+                }
+            }"#;
+            let execution = Execution::default();
+            let no_default_wix_folder =
+                execution.no_default_wix_folder(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert!(no_default_wix_folder);
+        }
+
+        #[test]
+        fn wxs_sources_no_default_wix_folder_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let wix_dir = project.path().join(WIX);
+            std::fs::create_dir(&wix_dir).unwrap();
+            std::fs::write(wix_dir.join("main.wxs"), "").unwrap();
+            let included = project.path().join("other.wxs");
+            std::fs::write(&included, "").unwrap();
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let mut builder = Builder::default();
+            let included_str = included.to_str().unwrap();
+            builder.includes(Some(vec![included_str]));
+            builder.no_default_wix_folder(true);
+            let sources = builder.build().wxs_sources(&package).unwrap();
+            assert_eq!(sources, vec![included]);
+        }
+
+        #[test]
+        fn wxs_sources_explicit_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                include = ["metadata.wxs"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let wix_dir = project.path().join(WIX);
+            std::fs::create_dir(&wix_dir).unwrap();
+            std::fs::write(wix_dir.join("main.wxs"), "").unwrap();
+            std::fs::write(project.path().join("metadata.wxs"), "").unwrap();
+            let included = project.path().join("included.wxs");
+            std::fs::write(&included, "").unwrap();
+            let explicit = project.path().join("explicit.wxs");
+            std::fs::write(&explicit, "").unwrap();
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let mut builder = Builder::default();
+            builder.includes(Some(vec![included.to_str().unwrap()]));
+            builder.wxs_sources(Some(vec![explicit.to_str().unwrap()]));
+            let sources = builder.build().wxs_sources(&package).unwrap();
+            assert_eq!(sources, vec![explicit]);
+        }
+
+        #[test]
+        fn wxs_sources_explicit_missing_file_fails() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let missing = project.path().join("missing.wxs");
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let mut builder = Builder::default();
+            builder.wxs_sources(Some(vec![missing.to_str().unwrap()]));
+            assert!(builder.build().wxs_sources(&package).is_err());
+        }
+
+        #[test]
+        fn wxs_sources_no_default_wix_folder_without_includes_fails() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let wix_dir = project.path().join(WIX);
+            std::fs::create_dir(&wix_dir).unwrap();
+            std::fs::write(wix_dir.join("main.wxs"), "").unwrap();
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let mut builder = Builder::default();
+            builder.no_default_wix_folder(true);
+            assert!(builder.build().wxs_sources(&package).is_err());
+        }
+
+        const CHANGELOG: &str = "# Changelog
+
+## [Unreleased]
+
+- Something in progress
+
+## [1.2.3] - 2023-01-01
+
+### Added
+
+- A new feature
+
+### Fixed
+
+- An old bug
+
+## [1.2.0] - 2022-12-01
+
+- The initial release
+";
+
+        #[test]
+        fn changelog_section_works() {
+            assert_eq!(
+                changelog_section(CHANGELOG, "1.2.3"),
+                Some(String::from("A new feature"))
+            );
+        }
+
+        #[test]
+        fn changelog_section_truncates_long_entry_works() {
+            let content = format!("## 1.0.0\n\n- {}\n- Second\n", "a".repeat(300));
+            let entry = changelog_section(&content, "1.0.0").unwrap();
+            assert_eq!(entry.chars().count(), MAX_CHANGELOG_LENGTH);
+            assert!(entry.ends_with("..."));
+        }
+
+        #[test]
+        fn changelog_section_matches_whole_version_works() {
+            assert_eq!(
+                changelog_section(CHANGELOG, "1.2.0"),
+                Some(String::from("The initial release"))
+            );
+            assert_eq!(changelog_section(CHANGELOG, "1.2"), None);
+        }
+
+        #[test]
+        fn changelog_section_missing_version_works() {
+            assert_eq!(changelog_section(CHANGELOG, "2.0.0"), None);
+        }
+
+        #[test]
+        fn changelog_metadata_disabled_works() {
+            const PKG_META_WIX: &str = r#"{
+                "name": "Example",
+                "version": "0.1.0",
+
+                "id": "",
+                "dependencies": [],
+                "targets": [],
+                "features": {},
+                "manifest_path": "",
+                "metadata": {
+                    "wix": {
+                        "changelog": false
+                    }
+                }
+            }"#;
+            let execution = Execution::default();
+            let changelog = execution
+                .changelog(&serde_json::from_str(PKG_META_WIX).unwrap())
+                .unwrap();
+            assert!(changelog.is_none());
+        }
+
+        #[test]
+        fn compiler_args_override_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "compiler-args": ["-nologo"]
+                }
+            }"#;
+            let mut builder = Builder::default();
+            builder.compiler_args(Some(vec!["-ws"]));
+            let execution = builder.build();
+            let args = execution.compiler_args(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(args, Some(vec![String::from("-ws")]));
+        }
+
+        #[test]
+        fn compiler_args_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "compiler-args": ["-nologo", "-ws"]
+                }
+            }"#;
+            let execution = Execution::default();
+            let args = execution.compiler_args(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(
+                args,
+                Some(vec![String::from("-nologo"), String::from("-ws")])
+            );
+        }
+
+        #[test]
+        fn compiler_args_append_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "compiler-args": ["-nologo", "-wx"]
+                }
+            }"#;
+            let mut builder = Builder::default();
+            builder.compiler_args(Some(vec!["-ws"]));
+            builder.append_args(true);
+            let execution = builder.build();
+            let args = execution.compiler_args(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(
+                args,
+                Some(vec![
+                    String::from("-nologo"),
+                    String::from("-wx"),
+                    String::from("-ws")
+                ])
+            );
+        }
+
+        #[test]
+        fn linker_args_override_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "linker-args": ["-nologo"]
+                }
+            }"#;
+            let mut builder = Builder::default();
+            builder.linker_args(Some(vec!["-ws"]));
+            let execution = builder.build();
+            let args = execution.linker_args(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(args, Some(vec![String::from("-ws")]));
+        }
+
+        #[test]
+        fn linker_args_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "linker-args": ["-nologo", "-ws"]
+                }
+            }"#;
+            let execution = Execution::default();
+            let args = execution.linker_args(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(
+                args,
+                Some(vec![String::from("-nologo"), String::from("-ws")])
+            );
+        }
+
+        #[test]
+        fn linker_args_append_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "append-args": true,
+                    "linker-args": ["-nologo"]
+                }
+            }"#;
+            let mut builder = Builder::default();
+            builder.linker_args(Some(vec!["-ws"]));
+            let execution = builder.build();
+            let args = execution.linker_args(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(
+                args,
+                Some(vec![String::from("-nologo"), String::from("-ws")])
+            );
+        }
+
+        #[test]
+        fn append_args_without_metadata_args_works() {
+            let mut builder = Builder::default();
+            builder.linker_args(Some(vec!["-ws"]));
+            builder.append_args(true);
+            let execution = builder.build();
+            let args = execution.linker_args(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(args, Some(vec![String::from("-ws")]));
+        }
+
+        #[test]
+        fn allow_unpublished_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "allow-unpublished": true
+                }
+            }"#;
+            let execution = Execution::default();
+            assert!(!execution.allow_unpublished(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap()));
+            assert!(execution.allow_unpublished(&PKG_META_WIX.parse::<Value>().unwrap()));
+        }
+
+        #[test]
+        fn bundle_upgrade_guid_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "bundle-upgrade-guid": "b36177be-ea4d-44fb-b05c-eddabdaa95ca"
+                }
+            }"#;
+            let execution = Execution::default();
+            let bundle_upgrade_guid = execution
+                .bundle_upgrade_guid(&PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(
+                bundle_upgrade_guid,
+                Some(String::from("B36177BE-EA4D-44FB-B05C-EDDABDAA95CA"))
+            );
+        }
+
+        #[test]
+        fn bundle_upgrade_guid_override_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "bundle-upgrade-guid": "B36177BE-EA4D-44FB-B05C-EDDABDAA95CA"
+                }
+            }"#;
+            let mut builder = Builder::default();
+            builder.bundle_upgrade_guid(Some("BFD25009-65A4-4D1E-97F1-0030465D90D6"));
+            let execution = builder.build();
+            let bundle_upgrade_guid = execution
+                .bundle_upgrade_guid(&PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(
+                bundle_upgrade_guid,
+                Some(String::from("BFD25009-65A4-4D1E-97F1-0030465D90D6"))
+            );
+        }
+
+        #[test]
+        fn bundle_upgrade_guid_invalid_fails() {
+            let mut builder = Builder::default();
+            builder.bundle_upgrade_guid(Some("Not a GUID"));
+            let execution = builder.build();
+            let err = execution
+                .bundle_upgrade_guid(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap_err();
+            assert_eq!(err.code(), 6);
+        }
+
+        #[test]
+        fn bundle_version_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "bundle-version": "1.2.0-beta.3"
+                }
+            }"#;
+            let execution = Execution::default();
+            let bundle_version = execution
+                .bundle_version(&PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(bundle_version, Some(String::from("1.2.0.3")));
+        }
+
+        const EMPTY_PKG_META_WIX: &str = r#"{"wix": {}}"#;
+
+        #[test]
+        fn bundle_upgrade_guid_works() {
+            let execution = Execution::default();
+            let bundle_upgrade_guid = execution
+                .bundle_upgrade_guid(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert!(bundle_upgrade_guid.is_none());
+        }
+
+        #[test]
+        fn bundle_version_works() {
+            let execution = Execution::default();
+            let bundle_version = execution
+                .bundle_version(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert!(bundle_version.is_none());
+        }
+
+        #[test]
+        fn component_group_refs_works() {
+            let execution = Execution::default();
+            let component_group_refs = execution
+                .component_group_refs(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert!(component_group_refs.is_empty());
+        }
+
+        #[test]
+        fn component_group_refs_with_invalid_id_fails() {
+            let mut builder = Builder::new();
+            builder.component_group_refs(Some(vec!["Harvested Files"]));
+            let execution = builder.build();
+            assert!(execution
+                .component_group_refs(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .is_err());
+        }
+
+        #[test]
+        fn component_group_refs_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "component-group-ref": ["HarvestedFiles", "_Extra.Files"]
+                }
+            }"#;
+            let execution = Execution::default();
+            let component_group_refs = execution
+                .component_group_refs(&PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(
+                component_group_refs,
+                vec![String::from("HarvestedFiles"), String::from("_Extra.Files")]
+            );
+        }
+
+        #[test]
+        fn product_language_defaults_to_culture() {
+            let execution = Execution::default();
+            let product_language = execution
+                .product_language(
+                    &EMPTY_PKG_META_WIX.parse::<Value>().unwrap(),
+                    &Cultures::FrFr,
+                )
+                .unwrap();
+            assert_eq!(product_language, 1036);
+        }
+
+        #[test]
+        fn product_language_with_culture_works() {
+            let mut builder = Builder::new();
+            builder.product_language(Some("de-de"));
+            let execution = builder.build();
+            let product_language = execution
+                .product_language(
+                    &EMPTY_PKG_META_WIX.parse::<Value>().unwrap(),
+                    &Cultures::EnUs,
+                )
+                .unwrap();
+            assert_eq!(product_language, 1031);
+        }
+
+        #[test]
+        fn product_language_with_neutral_lcid_works() {
+            let mut builder = Builder::new();
+            builder.product_language(Some("0"));
+            let execution = builder.build();
+            let product_language = execution
+                .product_language(
+                    &EMPTY_PKG_META_WIX.parse::<Value>().unwrap(),
+                    &Cultures::EnUs,
+                )
+                .unwrap();
+            assert_eq!(product_language, 0);
+        }
+
+        #[test]
+        fn product_language_with_invalid_value_fails() {
+            let mut builder = Builder::new();
+            builder.product_language(Some("xx-XX"));
+            let execution = builder.build();
+            let err = execution
+                .product_language(
+                    &EMPTY_PKG_META_WIX.parse::<Value>().unwrap(),
+                    &Cultures::EnUs,
+                )
+                .unwrap_err();
+            assert!(err.to_string().contains("'--product-language' option"));
+        }
+
+        #[test]
+        fn product_language_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "product-language": "1041"
+                }
+            }"#;
+            let execution = Execution::default();
+            let product_language = execution
+                .product_language(&PKG_META_WIX.parse::<Value>().unwrap(), &Cultures::EnUs)
+                .unwrap();
+            assert_eq!(product_language, 1041);
+        }
+
+        #[test]
+        fn properties_works() {
+            let execution = Execution::default();
+            let properties = execution
+                .properties(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert!(properties.is_empty());
+        }
+
+        #[test]
+        fn properties_with_values_works() {
+            let mut builder = Builder::new();
+            builder.properties(Some(vec!["ALLUSERS=2", "Empty=", "URL=a=b"]));
+            let execution = builder.build();
+            let properties = execution
+                .properties(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(
+                properties,
+                vec![
+                    (String::from("ALLUSERS"), String::from("2")),
+                    (String::from("Empty"), String::new()),
+                    (String::from("URL"), String::from("a=b")),
+                ]
+            );
+        }
+
+        #[test]
+        fn properties_without_value_fails() {
+            let mut builder = Builder::new();
+            builder.properties(Some(vec!["ALLUSERS"]));
+            let execution = builder.build();
+            assert!(execution
+                .properties(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .is_err());
+        }
+
+        #[test]
+        fn properties_with_invalid_name_fails() {
+            let mut builder = Builder::new();
+            builder.properties(Some(vec!["1NSTALL=1"]));
+            let execution = builder.build();
+            assert!(execution
+                .properties(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .is_err());
+        }
+
+        #[test]
+        fn properties_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "properties": ["INSTALLDESKTOPSHORTCUT=1"]
+                }
+            }"#;
+            let execution = Execution::default();
+            let properties = execution
+                .properties(&PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(
+                properties,
+                vec![(String::from("INSTALLDESKTOPSHORTCUT"), String::from("1"))]
+            );
+        }
+
+        #[test]
+        fn culture_works() {
+            let execution = Execution::default();
+            let culture = execution
+                .culture(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(culture, Cultures::EnUs);
+        }
+
+        #[test]
+        #[serial]
+        fn culture_with_auto_works() {
+            let mut builder = Builder::new();
+            builder.culture(Some("Auto"));
+            let execution = builder.build();
+            let metadata = EMPTY_PKG_META_WIX.parse::<Value>().unwrap();
+            let lc_all = env::var_os("LC_ALL");
+            env::set_var("LC_ALL", "fr_FR.UTF-8");
+            let supported = execution.culture(&metadata);
+            env::set_var("LC_ALL", "xx_YY.UTF-8");
+            let unsupported = execution.culture(&metadata);
+            match lc_all {
+                Some(value) => env::set_var("LC_ALL", value),
+                None => env::remove_var("LC_ALL"),
+            }
+            assert_eq!(supported, Ok(Cultures::FrFr));
+            assert_eq!(unsupported, Ok(Cultures::EnUs));
+        }
+
+        #[test]
+        fn culture_with_unsupported_culture_falls_back_works() {
+            let mut builder = Builder::new();
+            builder.culture(Some("fr-CA"));
+            let execution = builder.build();
+            let culture = execution
+                .culture(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(culture, Cultures::FrFr);
+        }
+
+        #[test]
+        fn culture_with_unsupported_culture_and_strict_fails() {
+            let mut builder = Builder::new();
+            builder.culture(Some("fr-CA"));
+            builder.strict(true);
+            let execution = builder.build();
+            assert!(execution
+                .culture(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .is_err());
+        }
+
+        #[test]
+        fn culture_with_unknown_language_fails() {
+            let mut builder = Builder::new();
+            builder.culture(Some("xx-YY"));
+            let execution = builder.build();
+            assert!(execution
+                .culture(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .is_err());
+        }
+
+        #[test]
+        fn nearest_culture_works() {
+            assert_eq!(nearest_culture("en-GB"), Some(Cultures::EnUs));
+            assert_eq!(nearest_culture("es_MX"), Some(Cultures::EsEs));
+            assert_eq!(nearest_culture("pt-AO"), Some(Cultures::PtPt));
+            assert_eq!(nearest_culture("nn-NO"), Some(Cultures::NbNo));
+            assert_eq!(nearest_culture("vi"), Some(Cultures::ViVn));
+            assert_eq!(nearest_culture("zh-SG"), Some(Cultures::ZhCn));
+            assert_eq!(nearest_culture("zh-Hant-HK"), Some(Cultures::ZhTw));
+            assert_eq!(nearest_culture("zh-MO"), Some(Cultures::ZhHk));
+            assert_eq!(nearest_culture("xx-YY"), None);
+        }
+
+        #[test]
+        fn culture_from_locale_works() {
+            assert_eq!(culture_from_locale("fr_FR.UTF-8"), Some(Cultures::FrFr));
+            assert_eq!(culture_from_locale("de_DE@euro"), Some(Cultures::DeDe));
+            assert_eq!(culture_from_locale("ja-JP"), Some(Cultures::JaJp));
+            assert_eq!(culture_from_locale("en_GB.UTF-8"), None);
+        }
+
+        #[test]
+        fn locale_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let execution = Execution::default();
+            let locale = execution.locale(&package).unwrap();
+            assert!(locale.is_none());
+        }
+
+        #[test]
+        fn no_build_works() {
+            let execution = Execution::default();
+            let no_build = execution.no_build(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap());
+            assert!(!no_build);
         }
 
         #[test]
-        fn russian_culture_works() {
-            const EXPECTED: &str = "RuRu";
-            let mut actual = Builder::new();
-            actual.culture(Some(EXPECTED));
-            assert_eq!(actual.culture, Some(EXPECTED));
+        fn build_command_is_none_by_default() {
+            let execution = Execution::default();
+            let build_command =
+                execution.build_command(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap());
+            assert!(build_command.is_none());
         }
 
         #[test]
-        fn debug_build_works() {
-            let mut actual = Builder::new();
-            actual.debug_build(true);
-            assert!(actual.debug_build);
+        fn shell_command_works() {
+            let status = shell_command("exit 3").status().unwrap();
+            assert_eq!(status.code(), Some(3));
         }
 
         #[test]
-        fn profile_works() {
-            const EXPECTED: &str = "dist";
-            let mut actual = Builder::new();
-            actual.profile(Some(EXPECTED));
-            assert_eq!(actual.profile, Some(EXPECTED));
+        fn timeout_works() {
+            assert_eq!(crate::timeout(None), Ok(None));
+            assert_eq!(
+                crate::timeout(Some("90")),
+                Ok(Some(Duration::from_secs(90)))
+            );
+            assert!(crate::timeout(Some("0")).is_err());
+            assert!(crate::timeout(Some("1.5")).is_err());
+            assert!(crate::timeout(Some("soon")).is_err());
+        }
+
+        #[test]
+        fn command_line_works() {
+            let mut command = Command::new(WIX_COMPILER);
+            command.arg("-nologo").arg("main file.wxs");
+            let expected = if cfg!(windows) {
+                format!("{WIX_COMPILER} -nologo \"main file.wxs\"")
+            } else {
+                format!("{WIX_COMPILER} -nologo 'main file.wxs'")
+            };
+            assert_eq!(command_line(&command), expected);
+        }
+
+        #[test]
+        fn command_line_with_folder_and_environment_works() {
+            let mut command = Command::new(CARGO);
+            command
+                .arg("build")
+                .current_dir("project")
+                .env("CARGO_PROFILE_RELEASE_STRIP", "true");
+            let expected = if cfg!(windows) {
+                "cd /d project && set \"CARGO_PROFILE_RELEASE_STRIP=true\" && cargo build"
+            } else {
+                "cd project && env CARGO_PROFILE_RELEASE_STRIP=true cargo build"
+            };
+            assert_eq!(command_line(&command), expected);
+        }
+
+        #[test]
+        fn status_with_timeout_works() {
+            let status =
+                status_with_timeout(&mut shell_command("exit 3"), Some(Duration::from_secs(30)))
+                    .unwrap();
+            assert_eq!(status.code(), Some(3));
+        }
+
+        #[test]
+        fn status_with_timeout_stops_command() {
+            let mut command = if cfg!(windows) {
+                shell_command("ping -n 31 127.0.0.1 > nul")
+            } else {
+                shell_command("sleep 30")
+            };
+            let start = Instant::now();
+            let err = status_with_timeout(&mut command, Some(Duration::from_secs(1))).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::TimedOut);
+            assert!(start.elapsed() < Duration::from_secs(30));
+        }
+
+        #[test]
+        fn status_with_stderr_works() {
+            let mut command = shell_command("echo in use 1>&2 && exit 3");
+            let (status, stderr) =
+                crate::status_with_stderr(&mut command, Some(Duration::from_secs(30))).unwrap();
+            assert_eq!(status.code(), Some(3));
+            assert!(String::from_utf8_lossy(&stderr).contains("in use"));
+        }
+
+        #[test]
+        fn report_works() {
+            let stages = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let reported = Arc::clone(&stages);
+            let mut builder = Builder::new();
+            builder.progress(move |stage| reported.lock().unwrap().push(stage));
+            let execution = builder.build();
+            execution.report(CreateStage::Building);
+            execution.report(CreateStage::Done);
+            assert_eq!(
+                *stages.lock().unwrap(),
+                vec![CreateStage::Building, CreateStage::Done]
+            );
+        }
+
+        #[test]
+        fn report_without_progress_works() {
+            let execution = Execution::default();
+            execution.report(CreateStage::Done);
+        }
+
+        #[test]
+        fn quiet_tools_works() {
+            let execution = Execution::default();
+            let quiet_tools = execution.quiet_tools(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap());
+            assert!(!quiet_tools);
+        }
+
+        #[test]
+        fn quiet_tools_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "quiet-tools": true
+                }
+            }"#;
+            let execution = Execution::default();
+            let quiet_tools = execution.quiet_tools(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert!(quiet_tools);
+        }
+
+        #[test]
+        fn strip_works() {
+            let execution = Execution::default();
+            let strip = execution.strip(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap());
+            assert!(!strip);
+        }
+
+        #[test]
+        fn strip_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "strip": true
+                }
+            }"#;
+            let execution = Execution::default();
+            let strip = execution.strip(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert!(strip);
+        }
+
+        #[test]
+        fn strip_env_var_works() {
+            let execution = Execution::default();
+            let profile = execution.profile(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(profile.strip_env_var(), "CARGO_PROFILE_RELEASE_STRIP");
+            let profile = Builder::new()
+                .profile(Some("release-lto"))
+                .build()
+                .profile(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap());
+            assert_eq!(profile.strip_env_var(), "CARGO_PROFILE_RELEASE_LTO_STRIP");
+        }
+
+        #[test]
+        fn include_build_metadata_defaults_works() {
+            let execution = Execution::default();
+            let include_build_metadata =
+                execution.include_build_metadata(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap());
+            assert!(!include_build_metadata);
+        }
+
+        #[test]
+        fn include_build_metadata_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "include-build-metadata": true
+                }
+            }"#;
+            let execution = Execution::default();
+            let include_build_metadata =
+                execution.include_build_metadata(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert!(include_build_metadata);
+        }
+
+        #[test]
+        fn verify_manifest_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "verify-manifest": true
+                }
+            }"#;
+            let execution = Execution::default();
+            let verify_manifest =
+                execution.verify_manifest(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert!(verify_manifest);
+        }
+
+        #[test]
+        fn incremental_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "incremental": true
+                }
+            }"#;
+            let execution = Execution::default();
+            let incremental = execution.incremental(&PKG_META_WIX.parse::<Value>().unwrap());
+            assert!(incremental);
+        }
+
+        #[test]
+        fn input_hash_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let source = temp_dir.path().join("main.wxs");
+            let missing = temp_dir.path().join("Example.exe");
+            std::fs::write(&source, "<Wix/>").unwrap();
+            let args = [OsStr::new("-dVersion=1.0.0")];
+            let files = [source.clone(), missing];
+            let expected = input_hash(&args, &files).unwrap();
+            assert_eq!(expected.len(), 64);
+            assert_eq!(input_hash(&args, &files).unwrap(), expected);
+            assert_ne!(
+                input_hash(&[OsStr::new("-dVersion=1.0.1")], &files).unwrap(),
+                expected
+            );
+            std::fs::write(&source, "<Wix></Wix>").unwrap();
+            assert_ne!(input_hash(&args, &files).unwrap(), expected);
+        }
+
+        #[test]
+        fn input_hash_with_moved_boundary_works() {
+            assert_ne!(
+                input_hash(&[OsStr::new("-dA=1"), OsStr::new("-dB=2")], &[]).unwrap(),
+                input_hash(&[OsStr::new("-dA=1-dB"), OsStr::new("=2")], &[]).unwrap()
+            );
+        }
+
+        #[test]
+        fn included_files_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let include_dir = temp_dir.path().join("include");
+            std::fs::create_dir(&include_dir).unwrap();
+            let source = temp_dir.path().join("main.wxs");
+            std::fs::write(
+                &source,
+                "<?include Common.wxi ?><?include $(var.Other).wxi ?><Wix/>",
+            )
+            .unwrap();
+            let common = include_dir.join("Common.wxi");
+            std::fs::write(&common, "<?include Paths.wxi ?><Include/>").unwrap();
+            let paths = include_dir.join("Paths.wxi");
+            std::fs::write(&paths, "<?include Common.wxi ?><Include/>").unwrap();
+            assert_eq!(
+                included_files(&source, &[include_dir]).unwrap(),
+                vec![common, paths]
+            );
+            assert!(included_files(&source, &[]).unwrap().is_empty());
+        }
+
+        #[test]
+        fn referenced_files_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let wix_dir = temp_dir.path().join("wix");
+            std::fs::create_dir(&wix_dir).unwrap();
+            std::fs::write(wix_dir.join("License.rtf"), "").unwrap();
+            std::fs::write(wix_dir.join("Banner.bmp"), "").unwrap();
+            let source = wix_dir.join("main.wxs");
+            std::fs::write(
+                &source,
+                r"<Wix>
+    <File Id='LicenseFile' Source='wix\License.rtf'/>
+    <Icon Id='ProductICO' SourceFile='$(var.ProductIcon)'/>
+    <WixVariable Id='WixUIBannerBmp' Value='wix\Banner.bmp'/>
+    <WixVariable Id='WixUILicenseRtf' Value='$(var.Eula)'/>
+</Wix>",
+            )
+            .unwrap();
+            let icon = temp_dir.path().join("Product.ico");
+            let icon_define = format!("-dProductIcon={}", icon.display());
+            assert_eq!(
+                referenced_files(
+                    &[source],
+                    [OsStr::new(&icon_define)],
+                    &[temp_dir.path().to_owned()]
+                )
+                .unwrap(),
+                vec![
+                    temp_dir.path().join("wix").join("License.rtf"),
+                    icon,
+                    temp_dir.path().join("wix").join("Banner.bmp"),
+                ]
+            );
+        }
+
+        #[test]
+        fn transform_destination_works() {
+            assert_eq!(
+                transform_destination(
+                    Path::new("target/wix/Example-0.1.0-x86_64.msi"),
+                    &Cultures::DeDe
+                ),
+                PathBuf::from("target/wix/Example-0.1.0-x86_64-de-DE.mst")
+            );
+        }
+
+        #[test]
+        fn up_to_date_installer_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let input_hash_file = temp_dir.path().join(INPUT_HASH_FILE_NAME);
+            let installer = temp_dir.path().join("Example-0.1.0-x86_64.msi");
+            assert!(up_to_date_installer(&input_hash_file, "0123456789abcdef").is_none());
+            std::fs::write(
+                &input_hash_file,
+                format!("0123456789abcdef\n{}\n", installer.display()),
+            )
+            .unwrap();
+            assert!(up_to_date_installer(&input_hash_file, "0123456789abcdef").is_none());
+            std::fs::write(&installer, "").unwrap();
+            assert_eq!(
+                up_to_date_installer(&input_hash_file, "0123456789abcdef"),
+                Some(installer)
+            );
+            assert!(up_to_date_installer(&input_hash_file, "fedcba9876543210").is_none());
+        }
+
+        #[test]
+        fn file_sources_works() {
+            const WXS: &str = r#"<Wix>
+    <File Id='exe0' Name='example.exe' DiskId='1' Source='$(var.CargoTargetBinDir)\example.exe'/>
+    <File Id='LicenseFile'
+        Name='License.rtf'
+        Source="wix\License.rtf"
+        KeyPath='yes'/>
+    <FileSearch Id='Search' Name='Source.txt'/>
+    <Icon Id='ProductICO' SourceFile='Product.ico'/>
+</Wix>"#;
+            assert_eq!(
+                file_sources(WXS),
+                vec!["$(var.CargoTargetBinDir)\\example.exe", "wix\\License.rtf"]
+            );
+        }
+
+        #[test]
+        fn estimated_size_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let bin_dir = temp_dir.path().join("bin");
+            std::fs::create_dir(&bin_dir).unwrap();
+            std::fs::write(bin_dir.join("example.exe"), vec![0; 3000]).unwrap();
+            std::fs::write(bin_dir.join("helper.exe"), vec![0; 100]).unwrap();
+            let wxs = temp_dir.path().join("main.wxs");
+            std::fs::write(
+                &wxs,
+                "<Wix>\
+                 <File Source='$(var.BinDir)/example.exe'/>\
+                 <File Source='$(var.BinDir)/example.exe'/>\
+                 <File Source='$(var.BinDir)/helper.exe'/>\
+                 <File Source='$(var.Missing)/other.exe'/>\
+                 <!-- <File Source='$(var.BinDir)/helper.exe'/> -->\
+                 </Wix>",
+            )
+            .unwrap();
+            let define = format!("-dBinDir={}", bin_dir.display());
+            assert_eq!(
+                estimated_size(&[wxs.clone()], [OsStr::new(&define)], &[]).unwrap(),
+                Some(4)
+            );
+            assert_eq!(estimated_size(&[wxs], [], &[]).unwrap(), None);
+        }
+
+        #[test]
+        fn estimated_size_with_relative_sources_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let package_dir = temp_dir.path().join("package");
+            let bind_dir = temp_dir.path().join("assets");
+            std::fs::create_dir_all(package_dir.join("wix")).unwrap();
+            std::fs::create_dir(&bind_dir).unwrap();
+            std::fs::write(package_dir.join("wix").join("License.rtf"), vec![0; 2000]).unwrap();
+            std::fs::write(bind_dir.join("helper.exe"), vec![0; 1000]).unwrap();
+            let wxs = temp_dir.path().join("main.wxs");
+            std::fs::write(
+                &wxs,
+                "<Wix>\
+                 <File Source='wix\\License.rtf'/>\
+                 <File Source='helper.exe'/>\
+                 <File Source='missing.exe'/>\
+                 </Wix>",
+            )
+            .unwrap();
+            let linker_args = vec![String::from("-b"), bind_dir.display().to_string()];
+            assert_eq!(
+                estimated_size(
+                    &[wxs.clone()],
+                    [],
+                    &bind_paths(&package_dir, Some(&linker_args))
+                )
+                .unwrap(),
+                Some(3)
+            );
+            assert_eq!(
+                estimated_size(&[wxs], [], &bind_paths(&package_dir, None)).unwrap(),
+                Some(2)
+            );
+        }
+
+        #[test]
+        fn bind_paths_works() {
+            let linker_args = vec![
+                String::from("-b"),
+                String::from("assets"),
+                String::from("-b"),
+                String::from("name=named"),
+                String::from("-nologo"),
+            ];
+            assert_eq!(
+                bind_paths(Path::new("package"), Some(&linker_args)),
+                vec![PathBuf::from("package"), PathBuf::from("assets")]
+            );
+            assert_eq!(
+                bind_paths(Path::new("package"), None),
+                vec![PathBuf::from("package")]
+            );
+        }
+
+        #[test]
+        fn arp_size_works() {
+            let metadata = EMPTY_PKG_META_WIX.parse::<Value>().unwrap();
+            let mut builder = Builder::new();
+            assert_eq!(builder.build().arp_size(&metadata), Ok(None));
+            assert_eq!(
+                builder.arp_size(Some("2048")).build().arp_size(&metadata),
+                Ok(Some(2048))
+            );
+            assert!(builder
+                .arp_size(Some("2 MB"))
+                .build()
+                .arp_size(&metadata)
+                .is_err());
+        }
+
+        #[test]
+        fn arp_size_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "arp-size": 1024
+                }
+            }"#;
+            let metadata = PKG_META_WIX.parse::<Value>().unwrap();
+            assert_eq!(Execution::default().arp_size(&metadata), Ok(Some(1024)));
+            assert_eq!(
+                Builder::new()
+                    .arp_size(Some("2048"))
+                    .build()
+                    .arp_size(&metadata),
+                Ok(Some(2048))
+            );
+            assert!(Execution::default()
+                .arp_size(&r#"{"wix": {"arp-size": "1 MB"}}"#.parse::<Value>().unwrap())
+                .is_err());
+        }
+
+        #[test]
+        fn format_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "format": "zip"
+                }
+            }"#;
+            let metadata = PKG_META_WIX.parse::<Value>().unwrap();
+            let mut builder = Builder::new();
+            assert_eq!(
+                builder
+                    .build()
+                    .format(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap()),
+                Ok(InstallerKind::Msi)
+            );
+            assert_eq!(builder.build().format(&metadata), Ok(InstallerKind::Zip));
+            assert_eq!(
+                builder.format(Some("MSI")).build().format(&metadata),
+                Ok(InstallerKind::Msi)
+            );
+            assert!(builder
+                .format(Some("exe"))
+                .build()
+                .format(&metadata)
+                .is_err());
+        }
+
+        #[test]
+        fn commands_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            std::fs::create_dir(project.path().join(WIX)).unwrap();
+            std::fs::write(
+                project.path().join(WIX).join("main.wxs"),
+                "<Wix><Product/></Wix>",
+            )
+            .unwrap();
+            let input = project.path().join("Cargo.toml");
+
+            let commands = Builder::new()
+                .input(input.to_str())
+                .install(true)
+                .targets(Some(vec!["x86_64-pc-windows-msvc"]))
+                .build()
+                .commands()
+                .unwrap();
+            assert_eq!(commands.len(), 4);
+            assert!(commands[0].contains("\"build\""));
+            assert!(commands[1].contains(WIX_COMPILER));
+            assert!(commands[2].contains(WIX_LINKER));
+            assert!(commands[2].contains("main.wixobj"));
+            assert!(commands[2].contains("Example-0.1.0-x86_64.msi"));
+            assert!(commands[3].contains(MSIEXEC));
+            assert!(!project.path().join("target").join(WIX).exists());
+
+            let commands = Builder::new()
+                .input(input.to_str())
+                .format(Some("zip"))
+                .build()
+                .commands()
+                .unwrap();
+            assert_eq!(commands.len(), 1);
+        }
+
+        #[test]
+        fn commands_with_metadata_output_file_and_multiple_targets_fails() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            std::fs::create_dir(project.path().join(WIX)).unwrap();
+            std::fs::write(
+                project.path().join(WIX).join("main.wxs"),
+                "<Wix><Product/></Wix>",
+            )
+            .unwrap();
+
+            let result = Builder::new()
+                .input(project.path().join("Cargo.toml").to_str())
+                .metadata_output(Some("config.json"))
+                .targets(Some(vec!["x86_64-pc-windows-msvc", "i686-pc-windows-msvc"]))
+                .build()
+                .commands();
+            assert!(result.unwrap_err().to_string().contains("metadata output"));
+        }
+
+        #[test]
+        fn commands_with_install_and_multiple_targets_fails() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            std::fs::create_dir(project.path().join(WIX)).unwrap();
+            std::fs::write(
+                project.path().join(WIX).join("main.wxs"),
+                "<Wix><Product/></Wix>",
+            )
+            .unwrap();
+
+            let result = Builder::new()
+                .input(project.path().join("Cargo.toml").to_str())
+                .install(true)
+                .targets(Some(vec!["x86_64-pc-windows-msvc", "i686-pc-windows-msvc"]))
+                .build()
+                .commands();
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("installers are created for multiple targets"));
+        }
+
+        #[test]
+        fn write_checksum_manifest_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let dist = temp_dir.path().join("dist");
+            std::fs::create_dir_all(dist.join("x64")).unwrap();
+            let installer = dist.join("x64").join("Example-0.1.0.msi");
+            std::fs::write(&installer, "abc").unwrap();
+            let other = temp_dir.path().join("Example-0.1.0.zip");
+            std::fs::write(&other, "").unwrap();
+            let destination = dist.join("SHA256SUMS");
+            write_checksum_manifest(&destination, &[installer, other.clone()]).unwrap();
+            assert_eq!(
+                std::fs::read_to_string(&destination).unwrap(),
+                format!(
+                    "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  \
+                     x64/Example-0.1.0.msi\n\
+                     e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855  {}\n",
+                    other.display()
+                )
+            );
+        }
+
+        #[test]
+        fn write_checksum_manifest_with_missing_file_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let destination = temp_dir.path().join("SHA256SUMS");
+            let missing = temp_dir.path().join("Example-0.1.0.msi");
+            assert!(write_checksum_manifest(&destination, &[missing]).is_err());
+        }
+
+        #[test]
+        fn write_metadata_output_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let destination = temp_dir.path().join("audit").join("Example.msi.json");
+            let configuration = serde_json::json!({"name": "Example", "version": "0.1.0"});
+            write_metadata_output(&destination, &configuration).unwrap();
+            let actual: Value =
+                serde_json::from_str(&std::fs::read_to_string(&destination).unwrap()).unwrap();
+            assert_eq!(actual, configuration);
+        }
+
+        #[test]
+        fn is_folder_path_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            assert!(is_folder_path(temp_dir.path()));
+            assert!(is_folder_path(Path::new("target/wix/")));
+            assert!(is_folder_path(Path::new("target\\wix\\")));
+            assert!(!is_folder_path(&temp_dir.path().join("config.json")));
+        }
+
+        #[test]
+        fn commands_with_emit_relative_manifest_path_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            std::fs::create_dir(project.path().join(WIX)).unwrap();
+            std::fs::write(
+                project.path().join(WIX).join("main.wxs"),
+                "<Wix><Product/></Wix>",
+            )
+            .unwrap();
+
+            let commands = Builder::new()
+                .input(project.path().join("Cargo.toml").to_str())
+                .emit_relative_manifest_path(true)
+                .targets(Some(vec!["x86_64-pc-windows-msvc"]))
+                .build()
+                .commands()
+                .unwrap();
+            assert!(commands[1].contains(WIX_COMPILER));
+            assert!(commands[1].contains("\"-dCargoTargetDir=target\""));
+            assert!(commands[1].contains("\"-dCargoTargetBinDir=target"));
+        }
+
+        #[test]
+        fn commands_with_generate_transforms_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            std::fs::create_dir(project.path().join(WIX)).unwrap();
+            std::fs::write(
+                project.path().join(WIX).join("main.wxs"),
+                "<Wix><Product/></Wix>",
+            )
+            .unwrap();
+
+            let commands = Builder::new()
+                .input(project.path().join("Cargo.toml").to_str())
+                .generate_transforms(Some(vec!["fr-FR", "en-US", "de-DE", "fr-FR"]))
+                .targets(Some(vec!["x86_64-pc-windows-msvc"]))
+                .build()
+                .commands()
+                .unwrap();
+            assert_eq!(commands.len(), 7);
+            assert!(commands[3].contains(WIX_LINKER));
+            assert!(commands[3].contains("-cultures:fr-FR"));
+            assert!(commands[3].contains("Example-0.1.0-x86_64-fr-FR.msi"));
+            assert!(commands[4].contains(WIX_TORCH));
+            assert!(commands[4].contains("Example-0.1.0-x86_64-fr-FR.mst"));
+            assert!(commands[5].contains("-cultures:de-DE"));
+            assert!(commands[6].contains("Example-0.1.0-x86_64-de-DE.mst"));
+        }
+
+        #[test]
+        fn commands_with_generate_transforms_and_unknown_culture_fails() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+
+            let result = Builder::new()
+                .input(project.path().join("Cargo.toml").to_str())
+                .generate_transforms(Some(vec!["xx-XX"]))
+                .build()
+                .commands();
+            assert!(result.unwrap_err().to_string().contains("culture"));
+        }
+
+        #[test]
+        fn commands_with_preprocess_only_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            std::fs::create_dir(project.path().join(WIX)).unwrap();
+            std::fs::write(
+                project.path().join(WIX).join("main.wxs"),
+                "<Wix><Product/></Wix>",
+            )
+            .unwrap();
+            let preprocessed = project.path().join("preprocessed");
+
+            let commands = Builder::new()
+                .input(project.path().join("Cargo.toml").to_str())
+                .install(true)
+                .preprocess_only(preprocessed.to_str())
+                .targets(Some(vec!["x86_64-pc-windows-msvc"]))
+                .build()
+                .commands()
+                .unwrap();
+            assert_eq!(commands.len(), 2);
+            assert!(commands[0].contains("\"build\""));
+            assert!(commands[1].contains(WIX_COMPILER));
+            assert!(commands[1].contains("-dVersion=0.1.0"));
+            assert!(commands[1].contains("preprocessed"));
+            assert!(!commands[1].contains("\"-o\""));
+            assert!(!preprocessed.exists());
         }
 
         #[test]
-        fn debug_name_works() {
-            let mut actual = Builder::new();
-            actual.debug_name(true);
-            assert!(actual.debug_name);
+        fn commands_with_bundle_launches_bundle_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            std::fs::create_dir(project.path().join(WIX)).unwrap();
+            std::fs::write(
+                project.path().join(WIX).join("main.wxs"),
+                "<Wix><Bundle/></Wix>",
+            )
+            .unwrap();
+
+            let commands = Builder::new()
+                .input(project.path().join("Cargo.toml").to_str())
+                .install(true)
+                .targets(Some(vec!["x86_64-pc-windows-msvc"]))
+                .build()
+                .commands()
+                .unwrap();
+            let launch = commands.last().unwrap();
+            assert!(!launch.contains(MSIEXEC));
+            assert!(launch.ends_with("Example-0.1.0-x86_64.exe\""));
         }
 
         #[test]
-        fn includes_works() {
-            const EXPECTED: &str = "C:\\tmp\\hello_world\\wix\\main.wxs";
-            let mut actual = Builder::new();
-            actual.includes(Some(vec![EXPECTED]));
-            assert_eq!(actual.includes, Some(vec![EXPECTED]));
+        fn wxs_installer_kind_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let product = temp_dir.path().join("main.wxs");
+            std::fs::write(&product, "<Wix><!-- <Bundle/> --><Product/></Wix>").unwrap();
+            let bundle = temp_dir.path().join("bundle.wxs");
+            std::fs::write(&bundle, "<Wix><Bundle/></Wix>").unwrap();
+            assert_eq!(
+                wxs_installer_kind(&[product.clone()]).unwrap(),
+                InstallerKind::Msi
+            );
+            assert_eq!(
+                wxs_installer_kind(&[product, bundle]).unwrap(),
+                InstallerKind::Exe
+            );
         }
 
         #[test]
-        fn input_works() {
-            const EXPECTED: &str = "C:\\tmp\\hello_world\\Cargo.toml";
-            let mut actual = Builder::new();
-            actual.input(Some(EXPECTED));
-            assert_eq!(actual.input, Some(EXPECTED));
+        fn portable_files_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let bin_dir = temp_dir.path().join("bin");
+            std::fs::create_dir(&bin_dir).unwrap();
+            std::fs::write(bin_dir.join("example.exe"), "").unwrap();
+            std::fs::write(temp_dir.path().join("License.rtf"), "").unwrap();
+            let wxs = temp_dir.path().join("main.wxs");
+            std::fs::write(
+                &wxs,
+                "<Wix>\
+                 <File Source='License.rtf'/>\
+                 <File Source='$(var.BinDir)\\example.exe'/>\
+                 <File Source='$(var.BinDir)\\example.exe'/>\
+                 <!-- <File Source='$(var.Missing)\\other.exe'/> -->\
+                 </Wix>",
+            )
+            .unwrap();
+            let values = [(String::from("BinDir"), bin_dir.display().to_string())];
+            assert_eq!(
+                portable_files(&[wxs.clone()], &values, temp_dir.path()).unwrap(),
+                vec![
+                    temp_dir.path().join("License.rtf"),
+                    bin_dir.join("example.exe")
+                ]
+            );
+            assert!(portable_files(&[wxs], &[], temp_dir.path()).is_err());
         }
 
         #[test]
-        fn linker_args_with_single_value_works() {
-            const EXPECTED: &str = "-nologo";
-            let mut actual = Builder::new();
-            actual.linker_args(Some(vec![EXPECTED]));
-            assert_eq!(actual.linker_args, Some(vec![EXPECTED]));
+        fn portable_files_with_missing_file_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let wxs = temp_dir.path().join("main.wxs");
+            std::fs::write(&wxs, "<Wix><File Source='License.rtf'/></Wix>").unwrap();
+            assert!(portable_files(&[wxs], &[], temp_dir.path()).is_err());
         }
 
         #[test]
-        fn linker_args_with_multiple_values_works() {
-            let expected: Vec<&str> = vec!["-ext", "HelloExtension"];
-            let mut actual = Builder::new();
-            actual.linker_args(Some(expected.clone()));
-            assert_eq!(actual.linker_args, Some(expected));
+        fn write_zip_archive_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let bin_dir = temp_dir.path().join("bin");
+            std::fs::create_dir(&bin_dir).unwrap();
+            std::fs::write(bin_dir.join("example.exe"), "binary").unwrap();
+            std::fs::write(temp_dir.path().join("License.rtf"), "license").unwrap();
+            let destination = temp_dir.path().join("wix").join("Example-0.1.0-x86_64.zip");
+            write_zip_archive(
+                &destination,
+                "Example-0.1.0-x86_64",
+                &[
+                    temp_dir.path().join("License.rtf"),
+                    bin_dir.join("example.exe"),
+                ],
+                &bin_dir,
+            )
+            .unwrap();
+            let mut archive =
+                zip::ZipArchive::new(std::fs::File::open(&destination).unwrap()).unwrap();
+            let mut names: Vec<&str> = archive.file_names().collect();
+            names.sort_unstable();
+            assert_eq!(
+                names,
+                vec![
+                    "Example-0.1.0-x86_64/License.rtf",
+                    "Example-0.1.0-x86_64/bin/example.exe"
+                ]
+            );
+            let mut content = String::new();
+            archive
+                .by_name("Example-0.1.0-x86_64/bin/example.exe")
+                .unwrap()
+                .read_to_string(&mut content)
+                .unwrap();
+            assert_eq!(content, "binary");
         }
 
         #[test]
-        fn locale_works() {
-            const EXPECTED: &str = "C:\\tmp\\hello_world\\wix\\main.wxl";
-            let mut actual = Builder::new();
-            actual.locale(Some(EXPECTED));
-            assert_eq!(actual.locale, Some(EXPECTED));
+        fn defines_works() {
+            let args = [
+                OsStr::new("-arch"),
+                OsStr::new("x64"),
+                OsStr::new("-dVersion=1.2.3"),
+                OsStr::new("-dCargoFeature_gui"),
+                OsStr::new("-dCargoTargetDir=C:\\target"),
+                OsStr::new("-nologo"),
+            ];
+            assert_eq!(
+                defines(args),
+                vec![
+                    (String::from("Version"), String::from("1.2.3")),
+                    (String::from("CargoFeature_gui"), String::new()),
+                    (String::from("CargoTargetDir"), String::from("C:\\target")),
+                ]
+            );
         }
 
         #[test]
-        fn name_works() {
-            const EXPECTED: &str = "Name";
-            let mut actual = Builder::new();
-            actual.name(Some(EXPECTED));
-            assert_eq!(actual.name, Some(EXPECTED));
+        fn defined_variables_works() {
+            let args = [
+                OsStr::new("-arch"),
+                OsStr::new("x64"),
+                OsStr::new("-dVersion=1.2.3"),
+                OsStr::new("-dCargoTargetDir=C:\\target"),
+                OsStr::new("-nologo"),
+            ];
+            assert_eq!(
+                defined_variables(args),
+                vec![String::from("Version"), String::from("CargoTargetDir")]
+            );
         }
 
         #[test]
-        fn no_build_works() {
-            let mut actual = Builder::new();
-            actual.no_build(true);
-            assert!(actual.no_build);
+        fn manifest_problems_works() {
+            const WXS: &str = r#"<?define Local = 'value' ?>
+<Wix>
+    <!-- <Product Name='$(var.Commented)'/> -->
+    <Product Name='$(var.Local)' Version='$(var.Version)' Manufacturer=''>
+        <Package Id='*' Manufacturer=""/>
+        <?ifdef Changelog ?>
+        <Property Id='ARPCOMMENTS' Value='$(var.Changelog)'/>
+        <?endif ?>
+        <Property Id='Missing' Value='$(var.Missing)'/>
+    </Product>
+</Wix>"#;
+            let problems = manifest_problems(WXS, &[String::from("Version")]);
+            assert_eq!(problems.len(), 4);
+            assert!(problems[0].starts_with("4: The 'Manufacturer' attribute is empty"));
+            assert!(problems[1].starts_with("5: The 'Manufacturer' attribute is empty"));
+            assert!(problems[2].starts_with("5: The 'Package' element"));
+            assert_eq!(problems[3], "9: The 'Missing' variable is not defined");
         }
 
         #[test]
-        fn output_works() {
-            const EXPECTED: &str = "C:\\tmp\\hello_world\\output";
-            let mut actual = Builder::new();
-            actual.output(Some(EXPECTED));
-            assert_eq!(actual.output, Some(EXPECTED));
+        fn variable_problems_works() {
+            const WXS: &str = r#"<?define Local = 'value' ?>
+<Wix>
+    <!-- <Product Name='$(var.Commented)'/> -->
+    <Product Name='$(var.Local)' Version='$(var.Version)' Manufacturer=''>
+        <File Source='$(var.CargoTargetBinDr)\example.exe'/>
+    </Product>
+</Wix>"#;
+            let problems = variable_problems(WXS, &[String::from("Version")]);
+            assert_eq!(
+                problems,
+                vec![(
+                    5,
+                    String::from("The 'CargoTargetBinDr' variable is not defined")
+                )]
+            );
         }
 
         #[test]
-        fn version_works() {
-            const EXPECTED: &str = "1.2.3";
-            let mut actual = Builder::new();
-            actual.version(Some(EXPECTED));
-            assert_eq!(actual.version, Some(EXPECTED));
+        fn check_wxs_variables_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let source = temp_dir.path().join("main.wxs");
+            std::fs::write(&source, "<Wix><Product Version='$(var.Missing)'/></Wix>").unwrap();
+            let sources = [source];
+            assert!(check_wxs_variables(&sources, &[], &[], false).is_ok());
+            assert!(check_wxs_variables(&sources, &[], &[], true).is_err());
+            assert!(check_wxs_variables(&sources, &[String::from("Missing")], &[], true).is_ok());
         }
 
         #[test]
-        fn build_with_defaults_works() {
-            let mut b = Builder::new();
-            let default_execution = b.build();
-            assert!(default_execution.bin_path.is_none());
-            assert!(default_execution.capture_output);
-            assert!(default_execution.compiler_args.is_none());
-            assert!(default_execution.culture.is_none());
-            assert!(!default_execution.debug_build);
-            assert!(!default_execution.debug_name);
-            assert!(default_execution.includes.is_none());
-            assert!(default_execution.input.is_none());
-            assert!(default_execution.linker_args.is_none());
-            assert!(default_execution.locale.is_none());
-            assert!(default_execution.name.is_none());
-            assert!(!default_execution.no_build);
-            assert!(default_execution.output.is_none());
-            assert!(default_execution.version.is_none());
+        fn check_wxs_variables_with_include_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let include_dir = temp_dir.path().join("include");
+            std::fs::create_dir(&include_dir).unwrap();
+            std::fs::write(
+                temp_dir.path().join("Common.wxi"),
+                "<Include><?include Versions.wxi ?><?define Local = 'value' ?></Include>",
+            )
+            .unwrap();
+            std::fs::write(
+                include_dir.join("Versions.wxi"),
+                "<Include><?define Missing = '1.0.0' ?></Include>",
+            )
+            .unwrap();
+            let source = temp_dir.path().join("main.wxs");
+            std::fs::write(
+                &source,
+                "<Wix><?include Common.wxi ?>\
+                 <Product Name='$(var.Local)' Version='$(var.Missing)'/></Wix>",
+            )
+            .unwrap();
+            let sources = [source];
+            assert!(check_wxs_variables(&sources, &[], &[], true).is_err());
+            assert!(check_wxs_variables(&sources, &[], &[include_dir.clone()], true).is_ok());
+            assert!(verify_wxs_sources(&sources, &[], &[include_dir]).is_ok());
         }
 
         #[test]
-        fn build_with_all_works() {
-            const EXPECTED_BIN_PATH: &str = "C:\\Wix Toolset\\bin";
-            const EXPECTED_CULTURE: &str = "FrFr";
-            const EXPECTED_COMPILER_ARGS: &str = "-nologo";
-            const EXPECTED_INCLUDES: &str = "C:\\tmp\\hello_world\\wix\\main.wxs";
-            const EXPECTED_INPUT: &str = "C:\\tmp\\hello_world\\Cargo.toml";
-            const EXPECTED_LINKER_ARGS: &str = "-nologo";
-            const EXPECTED_LOCALE: &str = "C:\\tmp\\hello_world\\wix\\main.wxl";
-            const EXPECTED_NAME: &str = "Name";
-            const EXPECTED_OUTPUT: &str = "C:\\tmp\\hello_world\\output";
-            const EXPECTED_VERSION: &str = "1.2.3";
-            let mut b = Builder::new();
-            b.bin_path(Some(EXPECTED_BIN_PATH));
-            b.capture_output(false);
-            b.culture(Some(EXPECTED_CULTURE));
-            b.compiler_args(Some(vec![EXPECTED_COMPILER_ARGS]));
-            b.debug_build(true);
-            b.debug_name(true);
-            b.includes(Some(vec![EXPECTED_INCLUDES]));
-            b.input(Some(EXPECTED_INPUT));
-            b.linker_args(Some(vec![EXPECTED_LINKER_ARGS]));
-            b.locale(Some(EXPECTED_LOCALE));
-            b.name(Some(EXPECTED_NAME));
-            b.no_build(true);
-            b.output(Some(EXPECTED_OUTPUT));
-            b.version(Some(EXPECTED_VERSION));
-            let execution = b.build();
-            assert_eq!(execution.bin_path, Some(PathBuf::from(EXPECTED_BIN_PATH)));
-            assert!(!execution.capture_output);
-            assert_eq!(
-                execution.compiler_args,
-                Some(vec![String::from(EXPECTED_COMPILER_ARGS)])
-            );
-            assert_eq!(execution.culture, Some(String::from(EXPECTED_CULTURE)));
-            assert!(execution.debug_build);
-            assert!(execution.debug_name);
-            assert_eq!(
-                execution.includes,
-                Some(vec![PathBuf::from(EXPECTED_INCLUDES)])
-            );
-            assert_eq!(execution.input, Some(PathBuf::from(EXPECTED_INPUT)));
-            assert_eq!(
-                execution.linker_args,
-                Some(vec![String::from(EXPECTED_LINKER_ARGS)])
-            );
-            assert_eq!(execution.locale, Some(PathBuf::from(EXPECTED_LOCALE)));
-            assert_eq!(execution.name, Some(String::from(EXPECTED_NAME)));
-            assert!(execution.no_build);
-            assert_eq!(execution.output, Some(String::from(EXPECTED_OUTPUT)));
-            assert_eq!(execution.version, Some(String::from(EXPECTED_VERSION)));
+        fn include_paths_works() {
+            let args = [
+                OsStr::new("-dVersion=1.2.3"),
+                OsStr::new("-Iinclude"),
+                OsStr::new("-I"),
+                OsStr::new("-nologo"),
+            ];
+            assert_eq!(include_paths(args), vec![PathBuf::from("include")]);
         }
-    }
 
-    mod execution {
-        use super::*;
+        #[test]
+        fn check_target_bin_dir_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [[bin]]
+                name = "first"
+                path = "src/main.rs"
+
+                [[bin]]
+                name = "second"
+                path = "src/main.rs"
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+            let bin_dir = project.path().join("bin");
+            assert!(check_target_bin_dir(&bin_dir, &package).is_err());
+            std::fs::create_dir(&bin_dir).unwrap();
+            std::fs::write(bin_dir.join("first.exe"), "").unwrap();
+            let err = check_target_bin_dir(&bin_dir, &package).unwrap_err();
+            assert!(err.to_string().contains("'second.exe'"));
+            assert!(!err.to_string().contains("'first.exe'"));
+            std::fs::write(bin_dir.join("second.exe"), "").unwrap();
+            assert!(check_target_bin_dir(&bin_dir, &package).is_ok());
+        }
 
         #[test]
-        fn default_profile_works() {
-            const PKG_META_WIX: &str = r#"{
-                "wix": { }
-            }"#;
-            let execution = Execution::default();
-            let profile = execution.profile(&PKG_META_WIX.parse::<Value>().unwrap());
-            assert_eq!(profile.name, "release");
-            assert_eq!(profile.dir, "release");
+        fn check_binary_arch_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let binary = temp_dir.path().join("Example.exe");
+            let mut executable = vec![0u8; 0x58 + 224];
+            executable[0..2].copy_from_slice(b"MZ");
+            executable[0x3c..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+            executable[0x40..0x44].copy_from_slice(b"PE\0\0");
+            executable[0x44..0x46].copy_from_slice(&0x014cu16.to_le_bytes());
+            executable[0x54..0x56].copy_from_slice(&224u16.to_le_bytes());
+            executable[0x58..0x5a].copy_from_slice(&0x10bu16.to_le_bytes());
+            std::fs::write(&binary, executable).unwrap();
+            let binaries = [binary, temp_dir.path().join("Missing.exe")];
+            assert!(check_binary_arch(&binaries, &WixArch::X86, true).is_ok());
+            assert!(check_binary_arch(&binaries, &WixArch::X64, false).is_ok());
+            assert!(check_binary_arch(&binaries, &WixArch::X64, true).is_err());
         }
 
         #[test]
-        fn debug_build_metadata_works() {
+        fn wxs_variable_check_metadata_works() {
             const PKG_META_WIX: &str = r#"{
                 "wix": {
-                    "dbg-build": true
+                    "wxs-variable-check": true
                 }
             }"#;
             let execution = Execution::default();
-            let profile = execution.profile(&PKG_META_WIX.parse::<Value>().unwrap());
-            assert_eq!(profile.name, "dev");
-            assert_eq!(profile.dir, "debug");
+            assert!(!execution.wxs_variable_check(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap()));
+            assert!(execution.wxs_variable_check(&PKG_META_WIX.parse::<Value>().unwrap()));
         }
 
         #[test]
-        fn debug_name_metadata_works() {
-            const PKG_META_WIX: &str = r#"{
-                "wix": {
-                    "dbg-name": true
-                }
-            }"#;
-            let execution = Execution::default();
-            let debug_name = execution.debug_name(&PKG_META_WIX.parse::<Value>().unwrap());
-            assert!(debug_name);
+        fn manifest_problems_with_template_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+                description = "An example"
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let wxs = crate::print::wxs::Builder::new()
+                .input(input.to_str())
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            let defined: Vec<String> = [
+                "Version",
+                "Platform",
+                "Profile",
+                "TargetEnv",
+                "TargetTriple",
+                "CargoProfile",
+                "CargoTargetDir",
+                "CargoTargetBinDir",
+            ]
+            .iter()
+            .map(|v| String::from(*v))
+            .collect();
+            assert!(manifest_problems(&wxs, &defined).is_empty());
         }
 
         #[test]
-        fn version_metadata_works() {
-            const PKG_META_WIX: &str = r#"
-            {
-                "name": "Example",
-                "version": "0.1.0",
-                "authors": ["First Last <first.last@example.com>"],
-                "license": "Apache-2.0",
+        fn git_hash_works() {
+            let project = assert_fs::TempDir::new().unwrap();
+            assert!(git_hash(project.path()).is_none());
+            let status = Command::new(GIT)
+                .arg("init")
+                .arg("--quiet")
+                .current_dir(project.path())
+                .status();
+            if !status.is_ok_and(|s| s.success()) {
+                // Git is not available
+                return;
+            }
+            assert!(git_hash(project.path()).is_none());
+            Command::new(GIT)
+                .args([
+                    "-c",
+                    "user.name=Example",
+                    "-c",
+                    "user.email=example@example.com",
+                ])
+                .args(["commit", "--quiet", "--allow-empty", "-m", "Initial"])
+                .current_dir(project.path())
+                .status()
+                .unwrap();
+            let hash = git_hash(project.path()).unwrap();
+            assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+        }
 
-                "id": "",
-                "dependencies": [],
-                "targets": [],
-                "features": {},
-                "manifest_path": "",
+        #[test]
+        fn uses_product_icon_variable_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let main = temp_dir.path().join("main.wxs");
+            std::fs::write(
+                &main,
+                "<Icon Id='ProductICO' SourceFile='wix\\Product.ico'/>",
+            )
+            .unwrap();
+            let other = temp_dir.path().join("other.wxs");
+            std::fs::write(
+                &other,
+                "<Icon Id='ProductICO' SourceFile='$(var.ProductIcon)'/>",
+            )
+            .unwrap();
+            assert!(!uses_product_icon_variable(&[main.clone()]).unwrap());
+            assert!(uses_product_icon_variable(&[main, other]).unwrap());
+        }
 
-                "metadata": {
-                    "wix": {
-                        "version": "2.1.0"
-                    }
-                }
-            }"#;
-            let execution = Execution::default();
-            let version = execution
-                .version(&serde_json::from_str(PKG_META_WIX).unwrap())
-                .unwrap();
-            assert_eq!(version, "2.1.0");
+        #[test]
+        fn uses_product_icon_variable_stops_at_first_use_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let main = temp_dir.path().join("main.wxs");
+            std::fs::write(
+                &main,
+                "<Icon Id='ProductICO' SourceFile='$(var.ProductIcon)'/>",
+            )
+            .unwrap();
+            let missing = temp_dir.path().join("missing.wxs");
+            assert!(uses_product_icon_variable(&[main, missing.clone()]).unwrap());
+            assert!(uses_product_icon_variable(&[missing]).is_err());
         }
 
         #[test]
-        fn profile_metadata_works() {
-            const PKG_META_WIX: &str = r#" {
-                "wix": {
-                    "profile": "dist"
-                }
-            }"#;
-            let execution = Execution::default();
-            let profile = execution.profile(&serde_json::from_str(PKG_META_WIX).unwrap());
-            assert_eq!(profile.name, "dist");
-            assert_eq!(profile.dir, "dist");
+        fn is_unpublished_works() {
+            let package = |publish: &str| {
+                let manifest = format!(
+                    r#"[package]
+                    name = "Example"
+                    version = "0.1.0"
+                    authors = ["First Last <first.last@example.com>"]
+                    {publish}
+                    "#
+                );
+                let project = crate::tests::setup_project(&manifest);
+                let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+                crate::package(&manifest, None).unwrap()
+            };
+            assert!(is_unpublished(&package("publish = false")));
+            assert!(!is_unpublished(&package("")));
+            assert!(!is_unpublished(&package("publish = true")));
+            assert!(!is_unpublished(&package(r#"publish = ["internal"]"#)));
         }
 
         #[test]
-        fn version_prerelease_parse_works() {
-            const PKG_META_WIX: &str = r#"
-            {
-                "name": "Example",
-                "version": "2.1.0-5",
-                "authors": ["First Last <first.last@example.com>"],
-                "license": "Apache-2.0",
+        fn main_binary_name_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [[bin]]
+                name = "Other"
+                path = "src/main.rs"
+
+                [[bin]]
+                name = "Example"
+                path = "src/main.rs"
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+            assert_eq!(main_binary_name(&package), "Example");
+        }
 
-                "id": "",
-                "dependencies": [],
-                "targets": [],
-                "features": {},
-                "manifest_path": ""
-            }"#;
-            let execution = Execution::default();
-            let version = execution
-                .version(&serde_json::from_str(PKG_META_WIX).unwrap())
-                .unwrap();
-            assert_eq!(version, "2.1.0.5");
+        #[test]
+        #[cfg(not(windows))]
+        fn relative_path_works() {
+            let base = Path::new("/project/member");
+            assert_eq!(
+                relative_path(Path::new("/project/member/target/release"), base),
+                Some(PathBuf::from("target").join("release"))
+            );
+            assert_eq!(
+                relative_path(Path::new("/project/member"), base),
+                Some(PathBuf::from("."))
+            );
+            assert_eq!(
+                relative_path(Path::new("/project/target"), base),
+                Some(PathBuf::from("..").join("target"))
+            );
+            assert_eq!(
+                relative_path(Path::new("target"), base),
+                Some(PathBuf::from("target"))
+            );
+        }
+
+        #[test]
+        fn emitted_path_without_root_works() {
+            let path = Path::new("/project/target");
+            assert_eq!(emitted_path(path, None), path);
+        }
+
+        #[test]
+        fn find_binary_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let custom = temp_dir.path().join("custom").join("dist");
+            std::fs::create_dir_all(&custom).unwrap();
+            std::fs::write(custom.join("Example.exe"), "").unwrap();
+            let deps = temp_dir.path().join("release").join("deps");
+            std::fs::create_dir_all(&deps).unwrap();
+            std::fs::write(deps.join("Example.exe"), "").unwrap();
+            assert_eq!(
+                find_binary(temp_dir.path(), "Example.exe"),
+                Some(custom.join("Example.exe"))
+            );
         }
 
         #[test]
-        fn version_prerelease_dot_parse_works() {
-            const PKG_META_WIX: &str = r#"
-            {
-                "name": "Example",
-                "version": "2.1.0-prerelease.5",
-                "authors": ["First Last <first.last@example.com>"],
-                "license": "Apache-2.0",
+        fn find_binary_without_binary_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            assert_eq!(find_binary(temp_dir.path(), "Example.exe"), None);
+        }
 
-                "id": "",
-                "dependencies": [],
-                "targets": [],
-                "features": {},
-                "manifest_path": ""
-            }"#;
-            let execution = Execution::default();
-            let version = execution
-                .version(&serde_json::from_str(PKG_META_WIX).unwrap())
-                .unwrap();
-            assert_eq!(version, "2.1.0.5");
+        #[test]
+        fn main_binary_name_with_default_run_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+                default-run = "Other"
+
+                [[bin]]
+                name = "Example"
+                path = "src/main.rs"
+
+                [[bin]]
+                name = "Other"
+                path = "src/main.rs"
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+            assert_eq!(main_binary_name(&package), "Other");
         }
 
         #[test]
-        fn version_build_parse_works() {
-            const PKG_META_WIX: &str = r#"
-            {
-                "name": "Example",
-                "version": "2.1.0-prerelease+5",
-                "authors": ["First Last <first.last@example.com>"],
-                "license": "Apache-2.0",
+        fn main_binary_name_without_package_name_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [[bin]]
+                name = "Other"
+                path = "src/main.rs"
+            "#;
+            let project = crate::tests::setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+            assert_eq!(main_binary_name(&package), "Other");
+        }
 
-                "id": "",
-                "dependencies": [],
-                "targets": [],
-                "features": {},
-                "manifest_path": ""
-            }"#;
-            let execution = Execution::default();
-            let version = execution
-                .version(&serde_json::from_str(PKG_META_WIX).unwrap())
-                .unwrap();
-            assert_eq!(version, "2.1.0.5");
+        #[test]
+        fn extract_product_icon_without_binary_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            assert!(extract_product_icon(
+                &temp_dir.path().join("Example.exe"),
+                &temp_dir.path().join(PRODUCT_ICON_FILE_NAME)
+            )
+            .is_err());
         }
 
         #[test]
-        fn name_metadata_works() {
-            const PKG_META_WIX: &str = r#"{
-                "name": "Example",
-                "version": "0.1.0",
-                "metadata": {
-                    "wix": {
-                        "name": "Metadata"
-                    }
-                },
+        fn tasklist_contains_works() {
+            const RUNNING: &str = "\"Example.exe\",\"1234\",\"Console\",\"1\",\"10,000 K\"\r\n";
+            const NOT_RUNNING: &str =
+                "INFO: No tasks are running which match the specified criteria.\r\n";
+            assert!(tasklist_contains(RUNNING, "Example.exe"));
+            assert!(tasklist_contains(RUNNING, "example.exe"));
+            assert!(!tasklist_contains(RUNNING, "Other.exe"));
+            assert!(!tasklist_contains(NOT_RUNNING, "Example.exe"));
+        }
 
-                "id": "",
-                "dependencies": [],
-                "targets": [],
-                "features": {},
-                "manifest_path": ""
-            }"#;
-            let execution = Execution::default();
-            let name = execution.name(&serde_json::from_str(PKG_META_WIX).unwrap());
-            assert_eq!(name, "Metadata".to_owned());
+        #[test]
+        fn copy_wixobj_sources_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let source = temp_dir.path().join("main.wixobj");
+            std::fs::write(&source, "<wixObject/>").unwrap();
+            let destination = temp_dir.path().join("dump").join("wixobj");
+            copy_wixobj_sources(&[source], &destination).unwrap();
+            assert_eq!(
+                std::fs::read_to_string(destination.join("main.wixobj")).unwrap(),
+                "<wixObject/>"
+            );
         }
 
         #[test]
-        fn no_build_metadata_works() {
-            const PKG_META_WIX: &str = r#"{
-                "wix": {
-                    "no-build": true
-                }
-            }"#;
-            let execution = Execution::default();
-            let no_build = execution.no_build(&PKG_META_WIX.parse::<Value>().unwrap());
-            assert!(no_build);
+        fn variant_linker_args_works() {
+            let args = [
+                "-ext",
+                "WixUIExtension",
+                "-cultures:en-US",
+                "-out",
+                "target\\wix\\Example.msi",
+                "main.wixobj",
+            ];
+            let destination = PathBuf::from("target").join("Example-fr-FR.msi");
+            assert_eq!(
+                variant_linker_args(args.iter().map(OsStr::new), "-cultures:fr-FR", &destination),
+                vec![
+                    OsString::from("-ext"),
+                    OsString::from("WixUIExtension"),
+                    OsString::from("-cultures:fr-FR"),
+                    OsString::from("-out"),
+                    destination.into_os_string(),
+                    OsString::from("main.wixobj"),
+                ]
+            );
         }
 
         #[test]
-        fn culture_metadata_works() {
-            const PKG_META_WIX: &str = r#"{
-                "wix": {
-                    "culture": "Fr-Fr"
-                }
-            }"#;
-            let execution = Execution::default();
-            let culture = execution
-                .culture(&PKG_META_WIX.parse::<Value>().unwrap())
-                .unwrap();
-            assert_eq!(culture, Cultures::FrFr);
+        fn linker_cultures_works() {
+            assert_eq!(linker_cultures(&Cultures::FrFr), "-cultures:fr-FR");
+            assert_eq!(linker_cultures(&Cultures::ViVn), "-cultures:vi-VN;en-US");
         }
 
         #[test]
-        fn locale_metadata_works() {
-            const PKG_META_WIX: &str = r#"{
-                "wix": {
-                    "locale": "wix/French.wxl"
-                }
-            }"#;
-            let execution = Execution::default();
-            let locale = execution
-                .locale(&PKG_META_WIX.parse::<Value>().unwrap())
-                .unwrap();
-            assert_eq!(locale, Some(PathBuf::from("wix/French.wxl")));
+        fn localized_string_ids_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let locale = temp_dir.path().join("main.wxl");
+            std::fs::write(
+                &locale,
+                r#"<?xml version='1.0' encoding='utf-8'?>
+                <WixLocalization Culture='vi-VN' xmlns='http://schemas.microsoft.com/wix/2006/localization'>
+                    <String Id='WixUINext'>Tiếp</String>
+                    <String Id='WixUIBack'>Quay lại</String>
+                </WixLocalization>"#,
+            )
+            .unwrap();
+            assert_eq!(
+                localized_string_ids(&locale).unwrap(),
+                vec![String::from("WixUINext"), String::from("WixUIBack")]
+            );
         }
 
         #[test]
-        fn output_metadata_works() {
-            const PKG_META_WIX: &str = r#"{
-                "name": "Example",
-                "version": "0.1.0",
-                "authors": ["First Last <first.last@example.com>"],
-                "license": "XYZ",
+        fn check_localization_works() {
+            assert!(check_localization(&Cultures::FrFr, None, true).is_ok());
+            assert!(check_localization(&Cultures::IdId, None, false).is_ok());
+            assert!(check_localization(&Cultures::IdId, None, true).is_err());
+        }
 
-                "id": "",
-                "dependencies": [],
-                "targets": [],
-                "features": {},
-                "manifest_path": "",
-                "metadata": {
-                    "wix": {
-                        "output": "target/wix/test.msi"
-                    }
-                }
-            }"#;
-            let execution = Execution::default();
-            let output = execution.installer_destination(
-                "Different",
-                "2.1.0",
-                &Cfg::of("x86_64-pc-windows-msvc").unwrap(),
-                false,
-                &InstallerKind::default(),
-                &serde_json::from_str(PKG_META_WIX).unwrap(),
-                Path::new("target/"),
+        #[test]
+        fn check_localization_with_complete_locale_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let locale = temp_dir.path().join("main.wxl");
+            let strings: String = WIXUI_STRING_IDS
+                .iter()
+                .map(|id| format!("<String Id='{id}'>{id}</String>"))
+                .collect();
+            std::fs::write(
+                &locale,
+                format!(
+                    "<WixLocalization Culture='id-ID' \
+                     xmlns='http://schemas.microsoft.com/wix/2006/localization'>{strings}\
+                     </WixLocalization>"
+                ),
+            )
+            .unwrap();
+            assert!(check_localization(&Cultures::IdId, Some(&locale), true).is_ok());
+        }
+
+        #[test]
+        fn preprocessor_args_works() {
+            let args = [
+                "-arch",
+                "x64",
+                "-o",
+                "target\\wix\\",
+                "-dVersion=1.0.0",
+                "wix\\main.wxs",
+                "wix\\other.wxs",
+            ];
+            assert_eq!(
+                preprocessor_args(args.iter().map(OsStr::new), 2),
+                vec![
+                    OsString::from("-arch"),
+                    OsString::from("x64"),
+                    OsString::from("-dVersion=1.0.0"),
+                ]
             );
-            assert_eq!(output, PathBuf::from("target/wix/test.msi"));
         }
 
         #[test]
-        fn include_metadata_works() {
-            const PKG_META_WIX: &str = r#"{
-                "name": "Example",
-                "version": "0.1.0",
-                "authors": ["First Last <first.last@example.com>"],
-                "license": "XYZ",
+        fn print_wxs_sources_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let main = temp_dir.path().join("main.wxs");
+            std::fs::write(&main, "<Wix/>").unwrap();
+            let other = temp_dir.path().join("other.wxs");
+            std::fs::write(&other, "<Wix>\n</Wix>\n").unwrap();
+            let mut actual = Vec::new();
+            print_wxs_sources(&[main.clone(), other.clone()], &mut actual).unwrap();
+            assert_eq!(
+                String::from_utf8(actual).unwrap(),
+                format!(
+                    "<!-- {} -->\n<Wix/>\n<!-- {} -->\n<Wix>\n</Wix>\n",
+                    main.display(),
+                    other.display()
+                )
+            );
+        }
 
-                "id": "",
-                "dependencies": [],
-                "targets": [],
-                "features": {},
-                "manifest_path": "C:\\Cargo.toml",
-                "metadata": {
-                    "wix": {
-                        "include": ["Cargo.toml"]
-                    }
-                }
-            }"#;
+        #[test]
+        fn has_nologo_works() {
+            assert!(!has_nologo(None));
+            assert!(!has_nologo(Some(&[String::from("-wx")])));
+            assert!(has_nologo(Some(&[
+                String::from("-wx"),
+                String::from("-NoLogo")
+            ])));
+        }
+
+        #[test]
+        fn reinstall_mode_works() {
             let execution = Execution::default();
-            let sources = execution
-                .wxs_sources(&serde_json::from_str(PKG_META_WIX).unwrap())
+            let reinstall_mode = execution
+                .reinstall_mode(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
                 .unwrap();
-            assert_eq!(sources, vec![PathBuf::from("Cargo.toml")]);
+            assert!(reinstall_mode.is_none());
         }
 
         #[test]
-        fn compiler_args_override_works() {
-            const PKG_META_WIX: &str = r#"{
-                "wix": {
-                    "compiler-args": ["-nologo"]
-                }
-            }"#;
-            let mut builder = Builder::default();
-            builder.compiler_args(Some(vec!["-ws"]));
+        fn reinstall_mode_with_value_works() {
+            let mut builder = Builder::new();
+            builder.reinstall_mode(Some("AMUS"));
             let execution = builder.build();
-            let args = execution.compiler_args(&PKG_META_WIX.parse::<Value>().unwrap());
-            assert_eq!(args, Some(vec![String::from("-ws")]));
+            let reinstall_mode = execution
+                .reinstall_mode(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(reinstall_mode, Some(String::from("amus")));
         }
 
         #[test]
-        fn compiler_args_metadata_works() {
-            const PKG_META_WIX: &str = r#"{
-                "wix": {
-                    "compiler-args": ["-nologo", "-ws"]
-                }
-            }"#;
-            let execution = Execution::default();
-            let args = execution.compiler_args(&PKG_META_WIX.parse::<Value>().unwrap());
-            assert_eq!(
-                args,
-                Some(vec![String::from("-nologo"), String::from("-ws")])
-            );
+        fn reinstall_mode_with_invalid_value_fails() {
+            let mut builder = Builder::new();
+            builder.reinstall_mode(Some("amxs"));
+            let execution = builder.build();
+            assert!(execution
+                .reinstall_mode(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .is_err());
         }
 
         #[test]
-        fn linker_args_override_works() {
+        fn reinstall_mode_metadata_works() {
             const PKG_META_WIX: &str = r#"{
                 "wix": {
-                    "linker-args": ["-nologo"]
+                    "reinstall-mode": "emus"
                 }
             }"#;
-            let mut builder = Builder::default();
-            builder.linker_args(Some(vec!["-ws"]));
-            let execution = builder.build();
-            let args = execution.linker_args(&PKG_META_WIX.parse::<Value>().unwrap());
-            assert_eq!(args, Some(vec![String::from("-ws")]));
+            let execution = Execution::default();
+            let reinstall_mode = execution
+                .reinstall_mode(&PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap();
+            assert_eq!(reinstall_mode, Some(String::from("emus")));
         }
 
         #[test]
-        fn linker_args_metadata_works() {
-            const PKG_META_WIX: &str = r#"{
-                "wix": {
-                    "linker-args": ["-nologo", "-ws"]
-                }
-            }"#;
+        fn retain_symbols_is_false_by_default() {
             let execution = Execution::default();
-            let args = execution.linker_args(&PKG_META_WIX.parse::<Value>().unwrap());
-            assert_eq!(
-                args,
-                Some(vec![String::from("-nologo"), String::from("-ws")])
-            );
+            assert!(!execution
+                .retain_symbols(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap());
         }
 
-        const EMPTY_PKG_META_WIX: &str = r#"{"wix": {}}"#;
+        #[test]
+        fn retain_symbols_with_value_works() {
+            let mut builder = Builder::new();
+            builder.retain_symbols(Some("Yes"));
+            let execution = builder.build();
+            assert!(execution
+                .retain_symbols(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap());
+        }
 
         #[test]
-        fn culture_works() {
-            let execution = Execution::default();
-            let culture = execution
-                .culture(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
-                .unwrap();
-            assert_eq!(culture, Cultures::EnUs);
+        fn retain_symbols_with_invalid_value_fails() {
+            let mut builder = Builder::new();
+            builder.retain_symbols(Some("maybe"));
+            let execution = builder.build();
+            assert!(execution
+                .retain_symbols(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
+                .is_err());
         }
 
         #[test]
-        fn locale_works() {
+        fn retain_symbols_metadata_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "retain-symbols": true
+                }
+            }"#;
             let execution = Execution::default();
-            let locale = execution
-                .locale(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap())
-                .unwrap();
-            assert!(locale.is_none());
+            assert!(execution
+                .retain_symbols(&PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap());
         }
 
         #[test]
-        fn no_build_works() {
-            let execution = Execution::default();
-            let no_build = execution.no_build(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap());
-            assert!(!no_build);
+        fn retain_symbols_override_works() {
+            const PKG_META_WIX: &str = r#"{
+                "wix": {
+                    "retain-symbols": "yes"
+                }
+            }"#;
+            let mut builder = Builder::new();
+            builder.retain_symbols(Some("no"));
+            let execution = builder.build();
+            assert!(!execution
+                .retain_symbols(&PKG_META_WIX.parse::<Value>().unwrap())
+                .unwrap());
         }
 
         #[test]
@@ -2118,13 +9586,34 @@ mod tests {
 
             let execution = builder.build();
             let target_directory = PathBuf::from("C:\\my-app\\target");
-            let target = execution.target().unwrap();
+            let target = execution.targets().unwrap().remove(0);
             let profile = execution.profile(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap());
 
             let target_bin_dir = execution.target_bin_dir(&target_directory, &target, &profile);
             assert_eq!(target_bin_dir, PathBuf::from(EXPECTED));
         }
 
+        #[test]
+        fn targets_works() {
+            let mut builder = Builder::new();
+            builder.targets(Some(vec!["i686-pc-windows-msvc", "x86_64-pc-windows-msvc"]));
+            let execution = builder.build();
+            let targets = execution.targets().unwrap();
+            assert_eq!(targets.len(), 2);
+            assert_eq!(targets[0].triple, "i686-pc-windows-msvc");
+            assert_eq!(targets[0].arg, Some(String::from("i686-pc-windows-msvc")));
+            assert_eq!(targets[1].triple, "x86_64-pc-windows-msvc");
+            assert_eq!(targets[1].arg, Some(String::from("x86_64-pc-windows-msvc")));
+        }
+
+        #[test]
+        fn targets_with_defaults_is_host() {
+            let execution = Execution::default();
+            let targets = execution.targets().unwrap();
+            assert_eq!(targets.len(), 1);
+            assert!(targets[0].arg.is_none());
+        }
+
         #[test]
         #[cfg(windows)]
         fn target_bin_dir_computation_works() {
@@ -2135,7 +9624,7 @@ mod tests {
             let target_directory = PathBuf::from("C:\\my-app\\target");
 
             let execution = builder.build();
-            let target = execution.target().unwrap();
+            let target = execution.targets().unwrap().remove(0);
             let profile = execution.profile(&EMPTY_PKG_META_WIX.parse::<Value>().unwrap());
 
             let target_bin_dir = execution.target_bin_dir(&target_directory, &target, &profile);
@@ -2161,6 +9650,56 @@ mod tests {
             assert_eq!(format!("{actual:?}"), format!("{expected:?}"));
         }
 
+        #[test]
+        fn compiler_and_linker_with_wix_root_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let bin = temp_dir.path().join(BINARY_FOLDER_NAME);
+            std::fs::create_dir(&bin).unwrap();
+            let compiler = bin.join(WIX_COMPILER).with_extension(EXE_FILE_EXTENSION);
+            let linker = bin.join(WIX_LINKER).with_extension(EXE_FILE_EXTENSION);
+            std::fs::write(&compiler, "").unwrap();
+            std::fs::write(&linker, "").unwrap();
+            let execution = Builder::new().wix_root(temp_dir.path().to_str()).build();
+            assert_eq!(
+                format!("{:?}", execution.compiler().unwrap()),
+                format!("{:?}", Command::new(compiler))
+            );
+            assert_eq!(
+                format!("{:?}", execution.linker().unwrap()),
+                format!("{:?}", Command::new(linker))
+            );
+        }
+
+        #[test]
+        fn compiler_with_nonexistent_wix_root_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let result = Builder::new()
+                .wix_root(temp_dir.path().to_str())
+                .build()
+                .compiler();
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn compiler_with_bin_path_and_wix_root_uses_bin_path() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let compiler = temp_dir
+                .path()
+                .join(WIX_COMPILER)
+                .with_extension(EXE_FILE_EXTENSION);
+            std::fs::write(&compiler, "").unwrap();
+            let actual = Builder::new()
+                .bin_path(temp_dir.path().to_str())
+                .wix_root(Some("C:\\Nonexistent"))
+                .build()
+                .compiler()
+                .unwrap();
+            assert_eq!(
+                format!("{actual:?}"),
+                format!("{:?}", Command::new(compiler))
+            );
+        }
+
         #[test]
         fn wixobj_destination_works() {
             let execution = Execution::default();
@@ -0,0 +1,312 @@
+// Copyright (C) 2017 Christopher R. Field.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The implementation for the `extensions` command. This command focuses on
+//! listing the extensions installed for the modern WiX Toolset (v4 and later)
+//! using its `wix` application.
+
+use crate::command_line;
+use crate::Error;
+use crate::Result;
+use crate::EXE_FILE_EXTENSION;
+use crate::WIX_COMPILER;
+use crate::WIX_PATH_KEY;
+use crate::WIX_TOOL;
+
+use log::{debug, info, trace};
+
+use std::env;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// A builder for creating an execution context to list the extensions of the
+/// modern WiX Toolset.
+#[derive(Debug, Clone)]
+pub struct Builder<'a> {
+    bin_path: Option<&'a str>,
+    dump_toolset_commands: bool,
+    global: bool,
+}
+
+impl<'a> Builder<'a> {
+    /// Creates a new `Builder` instance.
+    pub fn new() -> Self {
+        Builder {
+            bin_path: None,
+            dump_toolset_commands: false,
+            global: false,
+        }
+    }
+
+    /// Sets the path to the folder containing the `wix` application.
+    ///
+    /// The default is to use the PATH system environment variable to locate
+    /// the application.
+    pub fn bin_path(&mut self, b: Option<&'a str>) -> &mut Self {
+        self.bin_path = b;
+        self
+    }
+
+    /// Prints the `wix extension list` command to stderr right before it runs
+    /// as a line that can be copied and pasted into a shell.
+    ///
+    /// The default is to not print the command.
+    pub fn dump_toolset_commands(&mut self, d: bool) -> &mut Self {
+        self.dump_toolset_commands = d;
+        self
+    }
+
+    /// Lists the extensions in the global cache instead of the extensions for
+    /// the current folder.
+    ///
+    /// The default is to list the extensions for the current folder, i.e. the
+    /// `wix extension list` command without the `--global` option.
+    pub fn global(&mut self, g: bool) -> &mut Self {
+        self.global = g;
+        self
+    }
+
+    /// Builds an execution context to list the extensions.
+    pub fn build(&mut self) -> Execution {
+        Execution {
+            bin_path: self.bin_path.map(PathBuf::from),
+            dump_toolset_commands: self.dump_toolset_commands,
+            global: self.global,
+        }
+    }
+}
+
+impl<'a> Default for Builder<'a> {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
+
+/// A context for listing the extensions of the modern WiX Toolset.
+#[derive(Debug)]
+pub struct Execution {
+    bin_path: Option<PathBuf>,
+    dump_toolset_commands: bool,
+    global: bool,
+}
+
+impl Execution {
+    /// Lists the extensions of the modern WiX Toolset.
+    ///
+    /// Each extension is printed to `STDOUT` on its own line with its version.
+    pub fn run(self) -> Result<()> {
+        debug!("bin_path = {:?}", self.bin_path);
+        debug!("dump_toolset_commands = {:?}", self.dump_toolset_commands);
+        debug!("global = {:?}", self.global);
+        let extensions = self.extensions()?;
+        debug!("extensions = {:?}", extensions);
+        if extensions.is_empty() {
+            info!("No extensions are installed");
+        }
+        for extension in extensions {
+            if let Some(version) = extension.version {
+                println!("{} {}", extension.id, version);
+            } else {
+                println!("{}", extension.id);
+            }
+        }
+        Ok(())
+    }
+
+    fn extensions(&self) -> Result<Vec<Extension>> {
+        let mut wix = self.wix();
+        wix.arg("extension").arg("list");
+        if self.global {
+            trace!("Listing the global extensions");
+            wix.arg("--global");
+        }
+        debug!("command = {:?}", wix);
+        if self.dump_toolset_commands {
+            eprintln!("{}", command_line(&wix));
+        }
+        let output = wix.output().map_err(|err| {
+            if err.kind() == ErrorKind::NotFound {
+                Error::Generic(not_found_message(legacy_toolset_exists()))
+            } else {
+                err.into()
+            }
+        })?;
+        if !output.status.success() {
+            return Err(Error::Command(
+                WIX_TOOL,
+                output.status.code().unwrap_or(100),
+                true,
+            ));
+        }
+        Ok(parse_extension_list(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    fn wix(&self) -> Command {
+        if let Some(bin_path) = &self.bin_path {
+            trace!(
+                "Using the '{}' path for the '{}' application",
+                bin_path.display(),
+                WIX_TOOL
+            );
+            Command::new(bin_path.join(WIX_TOOL))
+        } else {
+            Command::new(WIX_TOOL)
+        }
+    }
+}
+
+impl Default for Execution {
+    fn default() -> Self {
+        Builder::new().build()
+    }
+}
+
+/// An extension installed for the modern WiX Toolset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Extension {
+    id: String,
+    version: Option<String>,
+}
+
+/// Parses the output of the `wix extension list` command.
+///
+/// Each non-empty line is an extension ID followed by its version. Any
+/// additional text, such as a note that the extension is damaged, is ignored.
+fn parse_extension_list(output: &str) -> Vec<Extension> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            fields.next().map(|id| Extension {
+                id: id.to_owned(),
+                version: fields.next().map(String::from),
+            })
+        })
+        .collect()
+}
+
+/// Checks if the legacy WiX Toolset (v3) compiler can be found with the
+/// environment variable created by its installer.
+fn legacy_toolset_exists() -> bool {
+    env::var_os(WIX_PATH_KEY).is_some_and(|p| {
+        PathBuf::from(p)
+            .join(crate::BINARY_FOLDER_NAME)
+            .join(WIX_COMPILER)
+            .with_extension(EXE_FILE_EXTENSION)
+            .exists()
+    })
+}
+
+fn not_found_message(legacy_toolset_exists: bool) -> String {
+    if legacy_toolset_exists {
+        format!(
+            "The '{WIX_TOOL}' application could not be found, but the legacy WiX Toolset \
+             (v3) is installed. Extensions can only be listed for the WiX Toolset v4 or \
+             later. The legacy WiX Toolset includes its extensions in its '{}' folder.",
+            crate::BINARY_FOLDER_NAME
+        )
+    } else {
+        format!(
+            "The '{WIX_TOOL}' application could not be found in the PATH environment \
+             variable. Please check the WiX Toolset v4 or later is installed, such as \
+             with the 'dotnet tool install --global wix' command, or use the \
+             '-b,--bin-path' command line argument."
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod builder {
+        use super::*;
+
+        #[test]
+        fn defaults_are_correct() {
+            let actual = Builder::new();
+            assert!(actual.bin_path.is_none());
+            assert!(!actual.dump_toolset_commands);
+            assert!(!actual.global);
+        }
+
+        #[test]
+        fn bin_path_works() {
+            const EXPECTED: &str = "C:\\Users\\Example\\.dotnet\\tools";
+            let mut actual = Builder::new();
+            actual.bin_path(Some(EXPECTED));
+            assert_eq!(actual.bin_path, Some(EXPECTED));
+        }
+
+        #[test]
+        fn dump_toolset_commands_works() {
+            let mut actual = Builder::new();
+            actual.dump_toolset_commands(true);
+            assert!(actual.dump_toolset_commands);
+        }
+
+        #[test]
+        fn global_works() {
+            let mut actual = Builder::new();
+            actual.global(true);
+            assert!(actual.global);
+        }
+    }
+
+    mod execution {
+        use super::*;
+
+        #[test]
+        fn wix_with_bin_path_works() {
+            let mut builder = Builder::new();
+            builder.bin_path(Some("tools"));
+            let actual = builder.build().wix();
+            assert_eq!(actual.get_program(), PathBuf::from("tools").join(WIX_TOOL));
+        }
+
+        #[test]
+        fn parse_extension_list_works() {
+            const OUTPUT: &str = "WixToolset.UI.wixext 4.0.4\r\n\
+                WixToolset.Util.wixext 4.0.4 (damaged)\r\n\
+                \r\n";
+            assert_eq!(
+                parse_extension_list(OUTPUT),
+                vec![
+                    Extension {
+                        id: String::from("WixToolset.UI.wixext"),
+                        version: Some(String::from("4.0.4")),
+                    },
+                    Extension {
+                        id: String::from("WixToolset.Util.wixext"),
+                        version: Some(String::from("4.0.4")),
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn parse_extension_list_without_extensions_works() {
+            assert!(parse_extension_list("").is_empty());
+        }
+
+        #[test]
+        fn not_found_message_with_legacy_toolset_works() {
+            assert!(not_found_message(true).contains("legacy WiX Toolset"));
+            assert!(!not_found_message(false).contains("legacy WiX Toolset"));
+        }
+    }
+}
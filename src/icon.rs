@@ -0,0 +1,194 @@
+// Copyright (C) 2017 Christopher R. Field.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Extraction of the application icon embedded in the resources of a
+//! Portable Executable (PE), i.e. an `.exe` file.
+//!
+//! The icon resources are read with the `pe` module. The first icon group is
+//! the application icon displayed by the File Explorer, and it is converted to
+//! the layout of an icon (.ico) file.
+
+use crate::pe::{add, malformed, read_u16, PeImage};
+use crate::Error;
+use crate::Result;
+
+/// The resource type ID for an icon image.
+const RT_ICON: u32 = 3;
+
+/// The resource type ID for a group of icon images.
+const RT_GROUP_ICON: u32 = 14;
+
+/// The size of an entry in the icon group resource.
+const GROUP_ENTRY_SIZE: usize = 14;
+
+/// The size of an entry in the directory of an icon (.ico) file.
+const ICO_ENTRY_SIZE: usize = 16;
+
+/// The size of the header of an icon (.ico) file and an icon group resource.
+const ICO_HEADER_SIZE: usize = 6;
+
+/// Extracts the application icon from the content of an executable.
+///
+/// The content of an icon (.ico) file with all of the images of the first
+/// icon group is returned. An error occurs if the content is not an
+/// executable or the executable does not have any icons.
+pub fn extract_icon(executable: &[u8]) -> Result<Vec<u8>> {
+    let image = PeImage::parse(executable)?;
+    let group = image.resource(RT_GROUP_ICON, None)?.ok_or_else(|| {
+        Error::Generic(String::from(
+            "The executable does not have an embedded icon",
+        ))
+    })?;
+    let count = read_u16(group, 4)? as usize;
+    let mut entries = Vec::with_capacity(count);
+    for index in 0..count {
+        let offset = ICO_HEADER_SIZE + index * GROUP_ENTRY_SIZE;
+        let header = group
+            .get(offset..add(offset, 8)?)
+            .ok_or_else(|| malformed("icon group"))?;
+        let id = read_u16(group, add(offset, 12)?)?;
+        let data = image
+            .resource(RT_ICON, Some(u32::from(id)))?
+            .ok_or_else(|| malformed("icon group"))?;
+        entries.push((header, data));
+    }
+    let mut ico = Vec::new();
+    ico.extend_from_slice(&0u16.to_le_bytes());
+    ico.extend_from_slice(&1u16.to_le_bytes());
+    ico.extend_from_slice(&(count as u16).to_le_bytes());
+    let mut offset = ICO_HEADER_SIZE + count * ICO_ENTRY_SIZE;
+    for (header, data) in &entries {
+        // The width, height, color count, reserved, planes, and bit count
+        // fields are the same for both layouts.
+        ico.extend_from_slice(header);
+        ico.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        ico.extend_from_slice(&(offset as u32).to_le_bytes());
+        offset += data.len();
+    }
+    for (_, data) in entries {
+        ico.extend_from_slice(data);
+    }
+    Ok(ico)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pe::HIGH_BIT;
+
+    const SECTION_OFFSET: usize = 0x200;
+    const SECTION_RVA: u32 = 0x1000;
+
+    fn put_u16(data: &mut [u8], offset: usize, value: u16) {
+        data[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_u32(data: &mut [u8], offset: usize, value: u32) {
+        data[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    /// Puts a resource directory with ID entries in the resource section.
+    fn put_directory(data: &mut [u8], offset: usize, entries: &[(u32, u32)]) {
+        let offset = SECTION_OFFSET + offset;
+        put_u16(data, offset + 14, entries.len() as u16);
+        for (index, (id, value)) in entries.iter().enumerate() {
+            put_u32(data, offset + 16 + index * 8, *id);
+            put_u32(data, offset + 20 + index * 8, *value);
+        }
+    }
+
+    /// Builds a minimal 64-bit executable with one 16x16 icon.
+    fn executable() -> Vec<u8> {
+        let mut data = vec![0u8; SECTION_OFFSET + 0x100];
+        data[0..2].copy_from_slice(b"MZ");
+        put_u32(&mut data, 0x3c, 0x40);
+        data[0x40..0x44].copy_from_slice(b"PE\0\0");
+        put_u16(&mut data, 0x46, 1);
+        put_u16(&mut data, 0x54, 240);
+        put_u16(&mut data, 0x58, 0x20b);
+        put_u32(&mut data, 0x58 + 112 + 16, SECTION_RVA);
+        put_u32(&mut data, 0x58 + 112 + 20, 0x100);
+        let section = 0x58 + 240;
+        data[section..section + 5].copy_from_slice(b".rsrc");
+        put_u32(&mut data, section + 8, 0x100);
+        put_u32(&mut data, section + 12, SECTION_RVA);
+        put_u32(&mut data, section + 16, 0x100);
+        put_u32(&mut data, section + 20, SECTION_OFFSET as u32);
+        put_directory(
+            &mut data,
+            0x00,
+            &[(RT_ICON, HIGH_BIT | 0x20), (RT_GROUP_ICON, HIGH_BIT | 0x60)],
+        );
+        put_directory(&mut data, 0x20, &[(1, HIGH_BIT | 0x38)]);
+        put_directory(&mut data, 0x38, &[(1033, 0xA0)]);
+        put_directory(&mut data, 0x60, &[(1, HIGH_BIT | 0x78)]);
+        put_directory(&mut data, 0x78, &[(1033, 0xB0)]);
+        put_u32(&mut data, SECTION_OFFSET + 0xA0, SECTION_RVA + 0xC0);
+        put_u32(&mut data, SECTION_OFFSET + 0xA4, 4);
+        put_u32(&mut data, SECTION_OFFSET + 0xB0, SECTION_RVA + 0xD0);
+        put_u32(&mut data, SECTION_OFFSET + 0xB4, 20);
+        data[SECTION_OFFSET + 0xC0..SECTION_OFFSET + 0xC4].copy_from_slice(&[1, 2, 3, 4]);
+        let group = SECTION_OFFSET + 0xD0;
+        put_u16(&mut data, group + 2, 1);
+        put_u16(&mut data, group + 4, 1);
+        data[group + 6] = 16;
+        data[group + 7] = 16;
+        put_u16(&mut data, group + 10, 1);
+        put_u16(&mut data, group + 12, 32);
+        put_u32(&mut data, group + 14, 4);
+        put_u16(&mut data, group + 18, 1);
+        data
+    }
+
+    #[test]
+    fn extract_icon_works() {
+        assert_eq!(
+            extract_icon(&executable()).unwrap(),
+            vec![0, 0, 1, 0, 1, 0, 16, 16, 0, 0, 1, 0, 32, 0, 4, 0, 0, 0, 22, 0, 0, 0, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn extract_icon_without_icon_fails() {
+        let mut data = executable();
+        // Change the type of the icon group to a bitmap.
+        put_u32(&mut data, SECTION_OFFSET + 0x18, 2);
+        assert!(extract_icon(&data).is_err());
+    }
+
+    #[test]
+    fn extract_icon_without_executable_fails() {
+        assert!(extract_icon(b"Not an executable").is_err());
+    }
+
+    #[test]
+    fn extract_icon_with_overflowing_offset_fails() {
+        let mut data = executable();
+        // Add a second section with its raw data at the end of the address
+        // space and point the icon group data into it, so its offset overflows.
+        put_u16(&mut data, 0x46, 2);
+        let section = 0x58 + 240 + 40;
+        put_u32(&mut data, section + 8, 0x100);
+        put_u32(&mut data, section + 12, 2 * SECTION_RVA);
+        put_u32(&mut data, section + 20, u32::MAX - 0x10);
+        put_u32(&mut data, SECTION_OFFSET + 0xB0, 2 * SECTION_RVA + 0x20);
+        assert!(extract_icon(&data).is_err());
+    }
+
+    #[test]
+    fn extract_icon_with_truncated_executable_fails() {
+        let data = executable();
+        assert!(extract_icon(&data[..0x100]).is_err());
+    }
+}
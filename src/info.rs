@@ -0,0 +1,313 @@
+// Copyright (C) 2017 Christopher R. Field.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The implementation for the `info` command. This command focuses on printing
+//! the details of the installed WiX Toolset, such as the paths to the
+//! applications used to create an installer and their versions.
+
+use crate::create;
+use crate::Result;
+use crate::EXE_FILE_EXTENSION;
+use crate::SIGNTOOL;
+use crate::SIGNTOOL_PATH_KEY;
+use crate::WIX_COMPILER;
+use crate::WIX_LINKER;
+use crate::WIX_PATH_KEY;
+use crate::WIX_TOOL;
+
+use lazy_static::lazy_static;
+use log::{debug, trace};
+
+use regex::Regex;
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The value printed for an application or variable that could not be found.
+const NOT_FOUND: &str = "not found";
+
+/// A builder for creating an execution context to print the details of the
+/// installed WiX Toolset.
+#[derive(Debug, Clone)]
+pub struct Builder<'a> {
+    bin_path: Option<&'a str>,
+}
+
+impl<'a> Builder<'a> {
+    /// Creates a new `Builder` instance.
+    pub fn new() -> Self {
+        Builder { bin_path: None }
+    }
+
+    /// Sets the path to the WiX Toolset's `bin` folder.
+    ///
+    /// This is the same path used by the default _create_ subcommand to find
+    /// the compiler (candle.exe) and linker (light.exe). The default is to use
+    /// the WIX system environment variable, and then the PATH system
+    /// environment variable.
+    pub fn bin_path(&mut self, b: Option<&'a str>) -> &mut Self {
+        self.bin_path = b;
+        self
+    }
+
+    /// Builds an execution context to print the details of the WiX Toolset.
+    pub fn build(&mut self) -> Execution {
+        Execution {
+            bin_path: self.bin_path.map(PathBuf::from),
+        }
+    }
+}
+
+impl<'a> Default for Builder<'a> {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
+
+/// A context for printing the details of the installed WiX Toolset.
+#[derive(Debug)]
+pub struct Execution {
+    bin_path: Option<PathBuf>,
+}
+
+impl Execution {
+    /// Prints the details of the installed WiX Toolset.
+    ///
+    /// Each detail is printed to `STDOUT` on its own line as a name and value
+    /// pair. Applications that cannot be found are reported as "not found"
+    /// instead of causing an error.
+    pub fn run(self) -> Result<()> {
+        debug!("bin_path = {:?}", self.bin_path);
+        for (name, value) in self.details() {
+            println!("{name:<9} {value}");
+        }
+        Ok(())
+    }
+
+    fn details(&self) -> Vec<(&'static str, String)> {
+        let create = create::Builder::new()
+            .bin_path(self.bin_path.as_deref().and_then(Path::to_str))
+            .build();
+        let compiler = legacy_application(WIX_COMPILER, create.compiler());
+        debug!("compiler = {:?}", compiler);
+        let linker = legacy_application(WIX_LINKER, create.linker());
+        debug!("linker = {:?}", linker);
+        let wix = find_application(&search_paths(None), WIX_TOOL);
+        debug!("wix = {:?}", wix);
+        let signtool = find_application(
+            &search_paths(env::var_os(SIGNTOOL_PATH_KEY).map(PathBuf::from)),
+            SIGNTOOL,
+        );
+        debug!("signtool = {:?}", signtool);
+        let toolset = match (&compiler, &wix) {
+            (Some(_), Some(_)) => "legacy (v3), used by cargo-wix, and modern (v4+)",
+            (Some(_), None) => "legacy (v3)",
+            (None, Some(_)) => "modern (v4+), not supported for creating installers",
+            (None, None) => NOT_FOUND,
+        };
+        vec![
+            ("Toolset:", String::from(toolset)),
+            ("Compiler:", display_path(compiler.as_deref())),
+            ("Linker:", display_path(linker.as_deref())),
+            (
+                "Version:",
+                compiler
+                    .as_deref()
+                    .and_then(|c| application_version(c, "-?"))
+                    .unwrap_or_else(|| String::from(NOT_FOUND)),
+            ),
+            ("wix:", display_path(wix.as_deref())),
+            (
+                "wix ver:",
+                wix.as_deref()
+                    .and_then(|w| application_version(w, "--version"))
+                    .unwrap_or_else(|| String::from(NOT_FOUND)),
+            ),
+            (
+                "WIX:",
+                env::var_os(WIX_PATH_KEY)
+                    .map(|w| w.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| String::from("not set")),
+            ),
+            ("SignTool:", display_path(signtool.as_deref())),
+        ]
+    }
+}
+
+impl Default for Execution {
+    fn default() -> Self {
+        Builder::new().build()
+    }
+}
+
+/// Gets the path of an application of the legacy WiX Toolset from the command
+/// the default _create_ subcommand uses to run it.
+///
+/// The command is only the name of the application if the `bin_path` and the
+/// `WIX` environment variable are not set, in which case the application is
+/// found with the `PATH` environment variable, like it is when the command is
+/// run. `None` is returned if the application cannot be found.
+fn legacy_application(name: &str, command: Result<Command>) -> Option<PathBuf> {
+    match command {
+        Ok(command) => {
+            let program = Path::new(command.get_program());
+            if program.parent() == Some(Path::new("")) {
+                find_application(&search_paths(None), name)
+            } else {
+                Some(program.to_path_buf())
+            }
+        }
+        Err(e) => {
+            trace!("The '{}' application could not be found: {}", name, e);
+            None
+        }
+    }
+}
+
+/// Gets the folders to search for an application: an optional folder followed
+/// by the folders in the `PATH` environment variable.
+fn search_paths(first: Option<PathBuf>) -> Vec<PathBuf> {
+    first
+        .into_iter()
+        .chain(
+            env::var_os("PATH")
+                .map(|p| env::split_paths(&p).collect::<Vec<PathBuf>>())
+                .unwrap_or_default(),
+        )
+        .collect()
+}
+
+/// Finds the first folder that contains the application, with or without the
+/// executable file extension.
+fn find_application(folders: &[PathBuf], name: &str) -> Option<PathBuf> {
+    folders.iter().find_map(|folder| {
+        [
+            folder.join(name).with_extension(EXE_FILE_EXTENSION),
+            folder.join(name),
+        ]
+        .into_iter()
+        .find(|p| p.is_file())
+    })
+}
+
+fn display_path(path: Option<&Path>) -> String {
+    path.map(|p| p.display().to_string())
+        .unwrap_or_else(|| String::from(NOT_FOUND))
+}
+
+/// Runs an application with an argument that prints its version and parses
+/// the version from the output.
+fn application_version(application: &Path, arg: &str) -> Option<String> {
+    let output = Command::new(application).arg(arg).output().ok()?;
+    parse_version(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Gets the first version number, such as `3.11.2.4516`, in the output of an
+/// application.
+fn parse_version(output: &str) -> Option<String> {
+    lazy_static! {
+        static ref VERSION: Regex = Regex::new(r"\d+(\.\d+)+").unwrap();
+    }
+    VERSION.find(output).map(|m| String::from(m.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod builder {
+        use super::*;
+
+        #[test]
+        fn defaults_are_correct() {
+            let actual = Builder::new();
+            assert!(actual.bin_path.is_none());
+        }
+
+        #[test]
+        fn bin_path_works() {
+            const EXPECTED: &str = "C:\\Wix Toolset\\bin";
+            let mut actual = Builder::new();
+            actual.bin_path(Some(EXPECTED));
+            assert_eq!(actual.bin_path, Some(EXPECTED));
+        }
+    }
+
+    mod execution {
+        use super::*;
+        use assert_fs::TempDir;
+        use std::fs;
+
+        #[test]
+        fn parse_version_of_legacy_toolset_works() {
+            const OUTPUT: &str = "Windows Installer XML Toolset Compiler version 3.11.2.4516\r\n\
+                Copyright (c) .NET Foundation and contributors. All rights reserved.\r\n";
+            assert_eq!(parse_version(OUTPUT), Some(String::from("3.11.2.4516")));
+        }
+
+        #[test]
+        fn parse_version_of_modern_toolset_works() {
+            assert_eq!(
+                parse_version("4.0.4+a8592982\r\n"),
+                Some(String::from("4.0.4"))
+            );
+        }
+
+        #[test]
+        fn parse_version_without_version_works() {
+            assert!(parse_version("'wix' is not recognized").is_none());
+        }
+
+        #[test]
+        fn legacy_application_with_bin_path_works() {
+            let temp_dir = TempDir::new().unwrap();
+            let expected = temp_dir
+                .path()
+                .join(WIX_COMPILER)
+                .with_extension(EXE_FILE_EXTENSION);
+            fs::write(&expected, "").unwrap();
+            let create = create::Builder::new()
+                .bin_path(temp_dir.path().to_str())
+                .build();
+            assert_eq!(
+                legacy_application(WIX_COMPILER, create.compiler()),
+                Some(expected)
+            );
+            assert!(legacy_application(WIX_LINKER, create.linker()).is_none());
+        }
+
+        #[test]
+        fn find_application_uses_first_folder_works() {
+            let first = TempDir::new().unwrap();
+            let second = TempDir::new().unwrap();
+            fs::write(second.path().join(SIGNTOOL), "").unwrap();
+            let folders = vec![first.path().to_path_buf(), second.path().to_path_buf()];
+            assert_eq!(
+                find_application(&folders, SIGNTOOL),
+                Some(second.path().join(SIGNTOOL))
+            );
+            fs::write(first.path().join(SIGNTOOL), "").unwrap();
+            assert_eq!(
+                find_application(&folders, SIGNTOOL),
+                Some(first.path().join(SIGNTOOL))
+            );
+        }
+
+        #[test]
+        fn display_path_without_path_works() {
+            assert_eq!(display_path(None), NOT_FOUND);
+        }
+    }
+}
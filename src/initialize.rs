@@ -22,70 +22,160 @@
 //! exists for the project, the `init` command does not need to be executed
 //! again.
 
-use camino::Utf8PathBuf;
-use cargo_metadata::Package;
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::{Metadata, Package};
 
+use crate::branding;
 use crate::print;
+use crate::read_wxs_source;
 use crate::stored_path::StoredPathBuf;
 use crate::Error;
 use crate::Result;
+use crate::BANNER_FILE_NAME;
+use crate::BMP_FILE_EXTENSION;
+use crate::DIALOG_FILE_NAME;
 use crate::WIX;
 use crate::WIX_SOURCE_FILE_EXTENSION;
 use crate::WIX_SOURCE_FILE_NAME;
 
-use log::{debug, info, trace};
+use log::{debug, info, trace, warn};
+
+use mustache::MapBuilder;
+
+use uuid::Uuid;
 
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 /// A builder for running the `cargo wix init` subcommand.
 #[derive(Debug, Clone)]
 pub struct Builder<'a> {
+    after_install_launch: bool,
+    author_separator: Option<&'a str>,
     banner: Option<&'a str>,
     binaries: Option<Vec<&'a str>>,
     copyright_year: Option<&'a str>,
+    culture: Option<&'a str>,
+    culture_banners: Vec<(&'a str, &'a str)>,
+    culture_dialogs: Vec<(&'a str, &'a str)>,
     copyright_holder: Option<&'a str>,
     description: Option<&'a str>,
+    deterministic_component_guids: bool,
     dialog: Option<&'a str>,
+    dialog_set: Option<&'a str>,
+    dump_context: Option<&'a str>,
     eula: Option<&'a str>,
+    feature_maps: Option<Vec<&'a str>>,
     force: bool,
+    generate_branding: bool,
+    guid_from: Option<&'a str>,
     help_url: Option<&'a str>,
     input: Option<&'a str>,
+    install_scope: Option<&'a str>,
+    keep_author_emails: bool,
+    launch_binary: Option<&'a str>,
     license: Option<&'a str>,
     manufacturer: Option<&'a str>,
+    manufacturer_from: Option<&'a str>,
+    msbuild: bool,
+    no_modify: bool,
+    no_path: bool,
+    no_repair: bool,
+    no_upgrade: bool,
     output: Option<&'a str>,
     package: Option<&'a str>,
     path_guid: Option<&'a str>,
     product_icon: Option<&'a str>,
     product_name: Option<&'a str>,
+    regenerate_guids: bool,
+    run_after_install: Option<&'a str>,
+    skip_eula_dialog: bool,
+    skip_license_sidecar: bool,
+    uninstall_shortcut: bool,
     upgrade_guid: Option<&'a str>,
+    wxs_template_dir: Option<&'a str>,
 }
 
 impl<'a> Builder<'a> {
     /// Creates a new `Builder` instance.
     pub fn new() -> Self {
         Builder {
+            after_install_launch: false,
+            author_separator: None,
             banner: None,
             binaries: None,
             copyright_year: None,
+            culture: None,
+            culture_banners: Vec::new(),
+            culture_dialogs: Vec::new(),
             copyright_holder: None,
             description: None,
+            deterministic_component_guids: false,
             dialog: None,
+            dialog_set: None,
+            dump_context: None,
             eula: None,
+            feature_maps: None,
             force: false,
+            generate_branding: false,
+            guid_from: None,
             help_url: None,
             input: None,
+            install_scope: None,
+            keep_author_emails: false,
+            launch_binary: None,
             license: None,
             manufacturer: None,
+            manufacturer_from: None,
+            msbuild: false,
+            no_modify: false,
+            no_path: false,
+            no_repair: false,
+            no_upgrade: false,
             output: None,
             package: None,
             path_guid: None,
             product_icon: None,
             product_name: None,
+            regenerate_guids: false,
+            run_after_install: None,
+            skip_eula_dialog: false,
+            skip_license_sidecar: false,
+            uninstall_shortcut: false,
             upgrade_guid: None,
+            wxs_template_dir: None,
         }
     }
 
+    /// Adds a checkbox to the last dialog of the installer to launch a binary.
+    ///
+    /// If `true`, the last dialog of the installer has a checkbox to launch
+    /// one of the installed binaries after the installation completes. The
+    /// binary is the one from the `default-run` field of the package's
+    /// manifest, or with the same name as the package, or the first binary if
+    /// none match, unless a binary is selected with the [`launch_binary`]
+    /// method. The default is to not add the checkbox.
+    ///
+    /// [`launch_binary`]: #method.launch_binary
+    pub fn after_install_launch(&mut self, a: bool) -> &mut Self {
+        self.after_install_launch = a;
+        self
+    }
+
+    /// Sets the separator between the authors when all of the authors from
+    /// the `authors` field of the package's manifest (Cargo.toml) are used for
+    /// the manufacturer.
+    ///
+    /// The default is to use the `author-separator` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or a semicolon followed by a space (`; `) if the field is not
+    /// specified.
+    pub fn author_separator(&mut self, a: Option<&'a str>) -> &mut Self {
+        self.author_separator = a;
+        self
+    }
+
     /// Sets the path to a bitmap (BMP) file to be used as a banner image across
     /// the top of each dialog in the installer.
     ///
@@ -114,7 +204,9 @@ impl<'a> Builder<'a> {
     /// package's manifest (Cargo.toml) and uses the supplied paths, regardless
     /// of the number of `bin` sections in the package's manifest. The binary
     /// name is extracted from each supplied path as the file stem (file name
-    /// without extension).
+    /// without extension). A path can be suffixed with a colon and a folder
+    /// name, e.g. `helper.exe:tools`, to install the binary in that subfolder
+    /// of the `bin` folder.
     ///
     /// This method is useful for including binaries, a.k.a. executables, in the
     /// installer that are necessary for the application to run but are not
@@ -164,6 +256,43 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the culture for the generated EULA.
+    ///
+    /// The translation of the license template for the culture is used if
+    /// one is available; otherwise, the English license template is used. The
+    /// `auto` value derives the culture from the locale of the system.
+    ///
+    /// The default is to use the `culture` field in the `[package.metadata.wix]`
+    /// section of the package's manifest (Cargo.toml), or `en-US` if the field
+    /// does not exist. This value is ignored if an EULA is _not_ generated from
+    /// the `license` field in the package's manifest (Cargo.toml).
+    pub fn culture(&mut self, c: Option<&'a str>) -> &mut Self {
+        self.culture = c;
+        self
+    }
+
+    /// Adds the path to a banner image for a culture, e.g. `fr-FR`, or a
+    /// language, e.g. `fr`.
+    ///
+    /// See the [`print::wxs::Builder::culture_banner`] method for details.
+    ///
+    /// [`print::wxs::Builder::culture_banner`]: crate::print::wxs::Builder::culture_banner
+    pub fn culture_banner(&mut self, culture: &'a str, path: &'a str) -> &mut Self {
+        self.culture_banners.push((culture, path));
+        self
+    }
+
+    /// Adds the path to a dialog image for a culture, e.g. `fr-FR`, or a
+    /// language, e.g. `fr`.
+    ///
+    /// See the [`print::wxs::Builder::culture_dialog`] method for details.
+    ///
+    /// [`print::wxs::Builder::culture_dialog`]: crate::print::wxs::Builder::culture_dialog
+    pub fn culture_dialog(&mut self, culture: &'a str, path: &'a str) -> &mut Self {
+        self.culture_dialogs.push((culture, path));
+        self
+    }
+
     /// Sets the description.
     ///
     /// This overrides the description determined from the `description` field
@@ -173,6 +302,17 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Enables or disables deterministic GUIDs for the components.
+    ///
+    /// If enabled, the GUIDs for the components in the generated WiX Source
+    /// (wxs) file are derived from the install location of each component
+    /// instead of generated by the WiX Toolset or randomly, so identical inputs
+    /// always yield identical GUIDs.
+    pub fn deterministic_component_guids(&mut self, d: bool) -> &mut Self {
+        self.deterministic_component_guids = d;
+        self
+    }
+
     /// Sets the path to a bitmap (`.bmp`) file that will be displayed on the
     /// first dialog to the left.
     ///
@@ -186,6 +326,34 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the set of dialogs from the WixUI dialog library for the installer.
+    ///
+    /// The value is `feature-tree`, `install-dir`, or `mondo`, which is
+    /// substituted into the `UIRef` element of the WiX Source (wxs) file. The
+    /// license agreement dialog of the set is shown if there is an EULA, see
+    /// the [`eula`] method, and skipped otherwise.
+    ///
+    /// The default is to use the `dialog-set` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or the `feature-tree` set if the field is not specified.
+    ///
+    /// [`eula`]: #method.eula
+    pub fn dialog_set(&mut self, d: Option<&'a str>) -> &mut Self {
+        self.dialog_set = d;
+        self
+    }
+
+    /// Sets the path to a file for the context used to render the WiX Source
+    /// (wxs) file.
+    ///
+    /// All of the values substituted into the template are written to the
+    /// file as JSON, which is useful for debugging an unexpected value in the
+    /// generated file. The default is to not write the context.
+    pub fn dump_context(&mut self, d: Option<&'a str>) -> &mut Self {
+        self.dump_context = d;
+        self
+    }
+
     /// Sets the path to a custom End User License Agreement (EULA).
     ///
     /// The EULA is the text that appears in the license agreement dialog of the
@@ -215,6 +383,22 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the Cargo features of the package that are optional features of
+    /// the installer.
+    ///
+    /// Each value is in the form of `CARGO_FEATURE=TITLE`. A feature with the
+    /// title is added to the installer for each value if the Cargo feature is
+    /// enabled when creating the installer, and the binaries that require the
+    /// Cargo feature are installed with it.
+    ///
+    /// The default is to use the `feature-map` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or to not add any features if the field is not specified.
+    pub fn feature_maps(&mut self, f: Option<Vec<&'a str>>) -> &mut Self {
+        self.feature_maps = f;
+        self
+    }
+
     /// Forces the generation of new output even if the various outputs already
     /// exists at the destination.
     pub fn force(&mut self, f: bool) -> &mut Self {
@@ -222,6 +406,41 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Generates simple banner and dialog images with the product name.
+    ///
+    /// If `true`, a banner image (493 x 58 pixels) and a dialog image (493 x
+    /// 312 pixels) are written to the destination folder as `Banner.bmp` and
+    /// `Dialog.bmp`, respectively, and the generated WiX Source (wxs) file
+    /// uses them to customize the installer. An image is _not_ generated if
+    /// its path is specified with the [`banner`] or [`dialog`] methods, or the
+    /// `banner` or `dialog` fields in the `[package.metadata.wix]` section of
+    /// the package's manifest (Cargo.toml). The default is to not generate the
+    /// images and use the default images of the WiX Toolset.
+    ///
+    /// Only the printable ASCII characters are supported in the product name.
+    /// If the product name has any other character, such as an accented
+    /// letter, the images are generated without the product name and a warning
+    /// is logged.
+    ///
+    /// [`banner`]: #method.banner
+    /// [`dialog`]: #method.dialog
+    pub fn generate_branding(&mut self, g: bool) -> &mut Self {
+        self.generate_branding = g;
+        self
+    }
+
+    /// Sets a seed for deriving the upgrade code and path component GUIDs as
+    /// version 5 UUIDs, so the same seed always yields the same GUIDs.
+    ///
+    /// An explicit upgrade or path GUID takes precedence. The default is to
+    /// use the `guid-from` field in the `[package.metadata.wix]` section of
+    /// the package's manifest (Cargo.toml), or to randomly generate the GUIDs
+    /// if the field is not specified.
+    pub fn guid_from(&mut self, g: Option<&'a str>) -> &mut Self {
+        self.guid_from = g;
+        self
+    }
+
     /// Sets the help URL.
     ///
     /// The default is to obtain a URL from one of the following fields in the
@@ -249,6 +468,46 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the installation scope, i.e. per-machine or per-user.
+    ///
+    /// A per-machine installation requires elevated privileges, while a
+    /// per-user installation does not. The `auto` value uses the per-user
+    /// scope if the PATH environment variable is _not_ modified, see the
+    /// [`no_path`] method, and the per-machine scope otherwise.
+    ///
+    /// The default is to use the `install-scope` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or the per-machine scope if the field is not specified.
+    ///
+    /// [`no_path`]: #method.no_path
+    pub fn install_scope(&mut self, i: Option<&'a str>) -> &mut Self {
+        self.install_scope = i;
+        self
+    }
+
+    /// Keeps the email of each author from the `authors` field of the
+    /// package's manifest (Cargo.toml) in the manufacturer.
+    ///
+    /// The default is to use the `keep-author-emails` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or to remove the emails if the field is not specified.
+    pub fn keep_author_emails(&mut self, k: bool) -> &mut Self {
+        self.keep_author_emails = k;
+        self
+    }
+
+    /// Sets the name of the binary to launch after installation.
+    ///
+    /// The name is the file stem, i.e. the file name without the extension, of
+    /// one of the binaries included in the installer. This is only used with
+    /// the [`after_install_launch`] method.
+    ///
+    /// [`after_install_launch`]: #method.after_install_launch
+    pub fn launch_binary(&mut self, l: Option<&'a str>) -> &mut Self {
+        self.launch_binary = l;
+        self
+    }
+
     /// Sets the path to a file to be used as the [sidecar] license file.
     ///
     /// This will override the `license-file` field in the package's manifest
@@ -281,6 +540,81 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets which authors from the `authors` field of the package's manifest
+    /// (Cargo.toml) are used for the manufacturer.
+    ///
+    /// The value is `first`, `all`, or the zero-based index of an author. The
+    /// default is to use the `manufacturer-from` field in the
+    /// `[package.metadata.wix]` section, or `all` if the field is not
+    /// specified.
+    pub fn manufacturer_from(&mut self, m: Option<&'a str>) -> &mut Self {
+        self.manufacturer_from = m;
+        self
+    }
+
+    /// Generates a MSBuild project file (wixproj) for the modern WiX Toolset
+    /// (v4 and later) alongside the WiX Source (wxs) file.
+    ///
+    /// The project is named after the package, lists the WiX Source (wxs) files
+    /// in the destination folder, and references the packages for the
+    /// extensions used in those files, so that the installer can be built with
+    /// `dotnet build`. The project is overwritten only if the [`force`] method
+    /// is `true`. The default is to only generate the WiX Source (wxs) file.
+    ///
+    /// [`force`]: #method.force
+    pub fn msbuild(&mut self, m: bool) -> &mut Self {
+        self.msbuild = m;
+        self
+    }
+
+    /// Disables the Change button for the application in the Add/Remove
+    /// Programs control panel by setting the `ARPNOMODIFY` property in the
+    /// generated WiX Source (wxs) file.
+    ///
+    /// The default is to use the `no-modify` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or to allow changing the features if the field is not specified.
+    pub fn no_modify(&mut self, n: bool) -> &mut Self {
+        self.no_modify = n;
+        self
+    }
+
+    /// Excludes the component and feature that add the `bin` folder to the
+    /// PATH environment variable from the generated WiX Source (wxs) file.
+    ///
+    /// The default is to use the `no-path` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or to include the PATH environment variable feature if the field is not
+    /// specified.
+    pub fn no_path(&mut self, n: bool) -> &mut Self {
+        self.no_path = n;
+        self
+    }
+
+    /// Disables the Repair button for the application in the Add/Remove
+    /// Programs control panel by setting the `ARPNOREPAIR` property in the
+    /// generated WiX Source (wxs) file.
+    ///
+    /// The default is to use the `no-repair` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or to allow repairing the installation if the field is not specified.
+    pub fn no_repair(&mut self, n: bool) -> &mut Self {
+        self.no_repair = n;
+        self
+    }
+
+    /// Omits the `MajorUpgrade` element from the generated WiX Source (wxs)
+    /// file, so each version of the application is installed side by side.
+    ///
+    /// The default is to use the `no-upgrade` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or to replace a previously installed version with a major upgrade if
+    /// the field is not specified.
+    pub fn no_upgrade(&mut self, n: bool) -> &mut Self {
+        self.no_upgrade = n;
+        self
+    }
+
     /// Sets the destination for creating all of the output from initialization.
     ///
     /// The default is to create all initialization output in the same folder as
@@ -347,6 +681,71 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Generates new GUIDs when an existing WiX Source (wxs) file is
+    /// overwritten.
+    ///
+    /// By default, the `UpgradeCode` and the GUID of the path component are
+    /// read from an existing WiX Source (wxs) file that is overwritten with the
+    /// [`force`] method and reused, because new GUIDs break upgrades of, and
+    /// orphan the PATH entry for, installations from previous installers. The
+    /// GUIDs are not reused if they are set with the [`upgrade_guid`],
+    /// [`path_guid`], or [`guid_from`] methods, or the matching fields in the
+    /// `[package.metadata.wix]` section. If `true`, new GUIDs are generated
+    /// instead.
+    ///
+    /// [`force`]: #method.force
+    /// [`guid_from`]: #method.guid_from
+    /// [`path_guid`]: #method.path_guid
+    /// [`upgrade_guid`]: #method.upgrade_guid
+    pub fn regenerate_guids(&mut self, r: bool) -> &mut Self {
+        self.regenerate_guids = r;
+        self
+    }
+
+    /// Sets a command to run at the end of the installation.
+    ///
+    /// The command runs from the installation folder with the privileges of
+    /// the installer, and it is not undone if the installation is rolled back.
+    /// The default is to use the `run-after-install` field in the
+    /// `[package.metadata.wix]` section of the package's manifest
+    /// (Cargo.toml), or to not run any command.
+    pub fn run_after_install(&mut self, r: Option<&'a str>) -> &mut Self {
+        self.run_after_install = r;
+        self
+    }
+
+    /// Excludes the license agreement dialog from the installer, while the
+    /// license can still be installed as a sidecar file.
+    ///
+    /// The default is to use the `skip-eula-dialog` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or to include the dialog if a EULA is found.
+    pub fn skip_eula_dialog(&mut self, s: bool) -> &mut Self {
+        self.skip_eula_dialog = s;
+        self
+    }
+
+    /// Excludes the license sidecar file from the installer, while the
+    /// license can still be displayed in the license agreement dialog.
+    ///
+    /// The default is to use the `skip-license-sidecar` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or to install the license if one is found.
+    pub fn skip_license_sidecar(&mut self, s: bool) -> &mut Self {
+        self.skip_license_sidecar = s;
+        self
+    }
+
+    /// Adds a Start Menu shortcut that uninstalls the application.
+    ///
+    /// The default is to use the `uninstall-shortcut` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or to only uninstall from the Add/Remove Programs control panel.
+    pub fn uninstall_shortcut(&mut self, u: bool) -> &mut Self {
+        self.uninstall_shortcut = u;
+        self
+    }
+
     /// Sets the Upgrade Code GUID.
     ///
     /// The default automatically generates the need GUID for the `UpgradeCode`
@@ -366,30 +765,83 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the folder of templates that overrides the embedded templates.
+    ///
+    /// The `main.wxs.mustache` file in the folder is used to generate the WiX
+    /// Source (wxs) file, and a license template with the same file name as
+    /// the embedded template, e.g. `MIT.rtf.mustache`, is used to generate
+    /// the license. The embedded templates are used for any template the
+    /// folder does not contain.
+    ///
+    /// The default is to use the `wxs-template-dir` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or only the embedded templates if the field does not exist.
+    pub fn wxs_template_dir(&mut self, w: Option<&'a str>) -> &mut Self {
+        self.wxs_template_dir = w;
+        self
+    }
+
     /// Builds a read-only initialization execution.
     pub fn build(&mut self) -> Execution {
         Execution {
+            after_install_launch: self.after_install_launch,
+            author_separator: self.author_separator.map(String::from),
             banner: self.banner.map(StoredPathBuf::from),
             binaries: self
                 .binaries
                 .as_ref()
                 .map(|b| b.iter().copied().map(StoredPathBuf::from).collect()),
             copyright_year: self.copyright_year.map(String::from),
+            culture: self.culture.map(String::from),
+            culture_banners: self
+                .culture_banners
+                .iter()
+                .map(|(c, p)| ((*c).to_owned(), (*p).to_owned()))
+                .collect(),
+            culture_dialogs: self
+                .culture_dialogs
+                .iter()
+                .map(|(c, p)| ((*c).to_owned(), (*p).to_owned()))
+                .collect(),
             copyright_holder: self.copyright_holder.map(String::from),
             description: self.description.map(String::from),
+            deterministic_component_guids: self.deterministic_component_guids,
             dialog: self.dialog.map(StoredPathBuf::from),
+            dialog_set: self.dialog_set.map(String::from),
+            dump_context: self.dump_context.map(PathBuf::from),
             eula: self.eula.map(StoredPathBuf::from),
+            feature_maps: self
+                .feature_maps
+                .as_ref()
+                .map(|f| f.iter().map(|s| (*s).to_string()).collect()),
             force: self.force,
+            generate_branding: self.generate_branding,
+            guid_from: self.guid_from.map(String::from),
             help_url: self.help_url.map(String::from),
             input: self.input.map(PathBuf::from),
+            install_scope: self.install_scope.map(String::from),
+            keep_author_emails: self.keep_author_emails,
+            launch_binary: self.launch_binary.map(String::from),
             license: self.license.map(StoredPathBuf::from),
             manufacturer: self.manufacturer.map(String::from),
+            manufacturer_from: self.manufacturer_from.map(String::from),
+            msbuild: self.msbuild,
+            no_modify: self.no_modify,
+            no_path: self.no_path,
+            no_repair: self.no_repair,
+            no_upgrade: self.no_upgrade,
             output: self.output.map(PathBuf::from),
             package: self.package.map(String::from),
             path_guid: self.path_guid.map(String::from),
             product_icon: self.product_icon.map(StoredPathBuf::from),
             product_name: self.product_name.map(String::from),
+            regenerate_guids: self.regenerate_guids,
+            run_after_install: self.run_after_install.map(String::from),
+            skip_eula_dialog: self.skip_eula_dialog,
+            skip_license_sidecar: self.skip_license_sidecar,
+            uninstall_shortcut: self.uninstall_shortcut,
             upgrade_guid: self.upgrade_guid.map(String::from),
+            wxs_template_dir: self.wxs_template_dir.map(PathBuf::from),
         }
     }
 }
@@ -403,48 +855,103 @@ impl<'a> Default for Builder<'a> {
 /// A context for creating the necessary files to eventually build an installer.
 #[derive(Debug)]
 pub struct Execution {
+    after_install_launch: bool,
+    author_separator: Option<String>,
     banner: Option<StoredPathBuf>,
     binaries: Option<Vec<StoredPathBuf>>,
     copyright_holder: Option<String>,
     copyright_year: Option<String>,
+    culture: Option<String>,
+    culture_banners: Vec<(String, String)>,
+    culture_dialogs: Vec<(String, String)>,
     description: Option<String>,
+    deterministic_component_guids: bool,
     dialog: Option<StoredPathBuf>,
+    dialog_set: Option<String>,
+    dump_context: Option<PathBuf>,
     eula: Option<StoredPathBuf>,
+    feature_maps: Option<Vec<String>>,
     force: bool,
+    generate_branding: bool,
+    guid_from: Option<String>,
     help_url: Option<String>,
     input: Option<PathBuf>,
+    install_scope: Option<String>,
+    keep_author_emails: bool,
+    launch_binary: Option<String>,
     license: Option<StoredPathBuf>,
     manufacturer: Option<String>,
+    manufacturer_from: Option<String>,
+    msbuild: bool,
+    no_modify: bool,
+    no_path: bool,
+    no_repair: bool,
+    no_upgrade: bool,
     output: Option<PathBuf>,
     package: Option<String>,
     path_guid: Option<String>,
     product_icon: Option<StoredPathBuf>,
     product_name: Option<String>,
+    regenerate_guids: bool,
+    run_after_install: Option<String>,
+    skip_eula_dialog: bool,
+    skip_license_sidecar: bool,
+    uninstall_shortcut: bool,
     upgrade_guid: Option<String>,
+    wxs_template_dir: Option<PathBuf>,
 }
 
 impl Execution {
     /// Generates the necessary files to eventually create, or build, an
     /// installer based on a built context.
     pub fn run(self) -> Result<()> {
+        debug!("after_install_launch = {:?}", self.after_install_launch);
+        debug!("author_separator = {:?}", self.author_separator);
         debug!("banner = {:?}", self.banner);
         debug!("binaries = {:?}", self.binaries);
         debug!("copyright_holder = {:?}", self.copyright_holder);
         debug!("copyright_year = {:?}", self.copyright_year);
+        debug!("culture = {:?}", self.culture);
+        debug!("culture_banners = {:?}", self.culture_banners);
+        debug!("culture_dialogs = {:?}", self.culture_dialogs);
         debug!("description = {:?}", self.description);
+        debug!(
+            "deterministic_component_guids = {:?}",
+            self.deterministic_component_guids
+        );
         debug!("dialog = {:?}", self.dialog);
+        debug!("dialog_set = {:?}", self.dialog_set);
+        debug!("dump_context = {:?}", self.dump_context);
         debug!("eula = {:?}", self.eula);
+        debug!("feature_maps = {:?}", self.feature_maps);
         debug!("force = {:?}", self.force);
+        debug!("generate_branding = {:?}", self.generate_branding);
+        debug!("guid_from = {:?}", self.guid_from);
         debug!("help_url = {:?}", self.help_url);
         debug!("input = {:?}", self.input);
+        debug!("install_scope = {:?}", self.install_scope);
+        debug!("keep_author_emails = {:?}", self.keep_author_emails);
+        debug!("launch_binary = {:?}", self.launch_binary);
         debug!("license = {:?}", self.license);
         debug!("manufacturer = {:?}", self.manufacturer);
+        debug!("manufacturer_from = {:?}", self.manufacturer_from);
+        debug!("msbuild = {:?}", self.msbuild);
+        debug!("no_modify = {:?}", self.no_modify);
+        debug!("no_path = {:?}", self.no_path);
+        debug!("no_repair = {:?}", self.no_repair);
+        debug!("no_upgrade = {:?}", self.no_upgrade);
         debug!("output = {:?}", self.output);
         debug!("package = {:?}", self.package);
         debug!("path_guid = {:?}", self.path_guid);
         debug!("product_icon = {:?}", self.product_icon);
         debug!("product_name = {:?}", self.product_name);
+        debug!("regenerate_guids = {:?}", self.regenerate_guids);
+        debug!("run_after_install = {:?}", self.run_after_install);
+        debug!("skip_eula_dialog = {:?}", self.skip_eula_dialog);
+        debug!("skip_license_sidecar = {:?}", self.skip_license_sidecar);
+        debug!("uninstall_shortcut = {:?}", self.uninstall_shortcut);
         debug!("upgrade_guid = {:?}", self.upgrade_guid);
+        debug!("wxs_template_dir = {:?}", self.wxs_template_dir);
         let manifest = super::manifest(self.input.as_ref())?;
         let package = super::package(&manifest, self.package.as_deref())?;
         let mut destination = self.destination(&package);
@@ -459,33 +966,214 @@ impl Execution {
         if destination.exists() && !self.force {
             return Err(Error::already_exists(&destination));
         } else {
+            let (existing_upgrade_guid, existing_path_guid) =
+                if destination.exists() && !self.regenerate_guids {
+                    existing_guids(&destination)
+                } else {
+                    (None, None)
+                };
+            let seeded = self.guid_from.is_some() || has_metadata_field(&package, "guid-from");
+            let upgrade_guid = self.upgrade_guid.clone().or_else(|| {
+                existing_upgrade_guid
+                    .filter(|_| !seeded && !has_metadata_field(&package, "upgrade-guid"))
+                    .map(|guid| {
+                        info!(
+                            "Reusing the '{}' upgrade code from the existing '{}' file",
+                            guid, destination
+                        );
+                        guid
+                    })
+            });
+            let path_guid = self.path_guid.clone().or_else(|| {
+                existing_path_guid
+                    .filter(|_| !seeded && !has_metadata_field(&package, "path-guid"))
+                    .map(|guid| {
+                        info!(
+                            "Reusing the '{}' path component GUID from the existing '{}' file",
+                            guid, destination
+                        );
+                        guid
+                    })
+            });
+            let (banner, dialog) = if self.generate_branding {
+                self.generate_branding(&package, destination.parent().unwrap())?
+            } else {
+                (None, None)
+            };
             info!("Creating the '{}' file", destination);
             let mut wxs_printer = print::wxs::Builder::new();
-            wxs_printer.banner(self.banner.as_ref().map(|s| s.as_str()));
+            wxs_printer.after_install_launch(self.after_install_launch);
+            wxs_printer.author_separator(self.author_separator.as_deref());
+            wxs_printer.banner(self.banner.as_ref().or(banner.as_ref()).map(|s| s.as_str()));
             wxs_printer.binaries(
                 self.binaries
                     .as_ref()
                     .map(|b| b.iter().map(|s| s.as_str()).collect()),
             );
+            wxs_printer.culture(self.culture.as_deref());
+            for (culture, path) in &self.culture_banners {
+                wxs_printer.culture_banner(culture, path);
+            }
+            for (culture, path) in &self.culture_dialogs {
+                wxs_printer.culture_dialog(culture, path);
+            }
             wxs_printer.description(self.description.as_ref().map(String::as_ref));
-            wxs_printer.dialog(self.dialog.as_deref().map(|s| s.as_str()));
+            wxs_printer.deterministic_component_guids(self.deterministic_component_guids);
+            wxs_printer.dialog(self.dialog.as_ref().or(dialog.as_ref()).map(|s| s.as_str()));
+            wxs_printer.dialog_set(self.dialog_set.as_deref());
+            wxs_printer.dump_context(self.dump_context.as_deref().and_then(Path::to_str));
             wxs_printer.eula(self.eula.as_deref().map(|p| p.as_str()));
+            wxs_printer.feature_maps(
+                self.feature_maps
+                    .as_ref()
+                    .map(|f| f.iter().map(String::as_str).collect()),
+            );
+            wxs_printer.guid_from(self.guid_from.as_deref());
             wxs_printer.help_url(self.help_url.as_ref().map(String::as_ref));
             wxs_printer.input(self.input.as_deref().and_then(Path::to_str));
+            wxs_printer.install_scope(self.install_scope.as_deref());
+            wxs_printer.keep_author_emails(self.keep_author_emails);
+            wxs_printer.launch_binary(self.launch_binary.as_deref());
             wxs_printer.license(self.license.as_deref().map(|p| p.as_str()));
             wxs_printer.manufacturer(self.manufacturer.as_ref().map(String::as_ref));
+            wxs_printer.manufacturer_from(self.manufacturer_from.as_ref().map(String::as_ref));
+            wxs_printer.no_modify(self.no_modify);
+            wxs_printer.no_path(self.no_path);
+            wxs_printer.no_repair(self.no_repair);
+            wxs_printer.no_upgrade(self.no_upgrade);
             wxs_printer.output(Some(destination.as_str()));
             wxs_printer.package(self.package.as_deref());
-            wxs_printer.path_guid(self.path_guid.as_ref().map(String::as_ref));
+            wxs_printer.path_guid(path_guid.as_deref());
             wxs_printer.product_icon(self.product_icon.as_ref().map(|s| s.as_str()));
             wxs_printer.product_name(self.product_name.as_ref().map(String::as_ref));
-            wxs_printer.upgrade_guid(self.upgrade_guid.as_ref().map(String::as_ref));
+            wxs_printer.run_after_install(self.run_after_install.as_deref());
+            wxs_printer.skip_eula_dialog(self.skip_eula_dialog);
+            wxs_printer.skip_license_sidecar(self.skip_license_sidecar);
+            wxs_printer.uninstall_shortcut(self.uninstall_shortcut);
+            wxs_printer.upgrade_guid(upgrade_guid.as_deref());
+            wxs_printer.wxs_template_dir(self.wxs_template_dir.as_deref().and_then(Path::to_str));
 
             wxs_printer.build().run()?;
+            if self.msbuild {
+                self.write_msbuild_project(&manifest, &package, destination.parent().unwrap())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a MSBuild project file (wixproj) for the modern WiX Toolset that
+    /// compiles the WiX Source (wxs) files in the destination folder.
+    fn write_msbuild_project(
+        &self,
+        manifest: &Metadata,
+        package: &Package,
+        destination: &Utf8Path,
+    ) -> Result<()> {
+        let path = destination
+            .join(&package.name)
+            .with_extension(WIXPROJ_FILE_EXTENSION);
+        if path.exists() && !self.force {
+            return Err(Error::already_exists(&path));
+        }
+        let mut sources = Vec::new();
+        let mut extensions = Vec::new();
+        for entry in fs::read_dir(destination)? {
+            let source = entry?.path();
+            if source.extension().and_then(|e| e.to_str()) != Some(WIX_SOURCE_FILE_EXTENSION) {
+                continue;
+            }
+            for extension in detect_extensions(&read_wxs_source(&source)?) {
+                if !extensions.contains(&extension) {
+                    extensions.push(extension);
+                }
+            }
+            if let Some(name) = source.file_name().and_then(|n| n.to_str()) {
+                sources.push(name.to_owned());
+            }
         }
+        sources.sort();
+        extensions.sort();
+        debug!("sources = {:?}", sources);
+        debug!("extensions = {:?}", extensions);
+        let target_dir = relative_path(destination, &manifest.target_directory);
+        debug!("target_dir = {:?}", target_dir);
+        info!("Creating the '{}' file", path);
+        fs::write(
+            &path,
+            msbuild_project(package, &target_dir, &sources, &extensions)?,
+        )?;
         Ok(())
     }
 
+    /// Writes the generated banner and dialog images to the destination
+    /// folder, unless an image is specified otherwise, and gets the paths to
+    /// the generated images relative to the package's manifest.
+    fn generate_branding(
+        &self,
+        package: &Package,
+        destination: &Utf8Path,
+    ) -> Result<(Option<StoredPathBuf>, Option<StoredPathBuf>)> {
+        let product_name = crate::product_name(self.product_name.as_ref(), package);
+        debug!("product_name = {:?}", product_name);
+        let banner_specified = self.banner.is_some() || has_metadata_field(package, "banner");
+        let dialog_specified = self.dialog.is_some() || has_metadata_field(package, "dialog");
+        if (!banner_specified || !dialog_specified) && !branding::can_draw(&product_name) {
+            warn!(
+                "The '{}' product name has characters that are not supported for the \
+                 generated images, so the images are generated without the product name. \
+                 Please use the '-b,--banner' and '-D,--dialog' options for custom images \
+                 with the product name.",
+                product_name
+            );
+        }
+        let banner = if banner_specified {
+            trace!("A banner image has been specified, so it is not generated");
+            None
+        } else {
+            Some(self.write_image(
+                package,
+                destination,
+                BANNER_FILE_NAME,
+                &branding::banner(&product_name),
+            )?)
+        };
+        let dialog = if dialog_specified {
+            trace!("A dialog image has been specified, so it is not generated");
+            None
+        } else {
+            Some(self.write_image(
+                package,
+                destination,
+                DIALOG_FILE_NAME,
+                &branding::dialog(&product_name),
+            )?)
+        };
+        Ok((banner, dialog))
+    }
+
+    fn write_image(
+        &self,
+        package: &Package,
+        destination: &Utf8Path,
+        file_name: &str,
+        content: &[u8],
+    ) -> Result<StoredPathBuf> {
+        let path = destination
+            .join(file_name)
+            .with_extension(BMP_FILE_EXTENSION);
+        if path.exists() && !self.force {
+            return Err(Error::already_exists(&path));
+        }
+        info!("Creating the '{}' file", path);
+        fs::write(&path, content)?;
+        let relative = package
+            .manifest_path
+            .parent()
+            .and_then(|p| path.strip_prefix(p).ok())
+            .unwrap_or(&path);
+        Ok(StoredPathBuf::from_utf8_path(relative))
+    }
+
     fn destination(&self, package: &Package) -> Utf8PathBuf {
         if let Some(output) = &self.output {
             trace!("An output path has been explicitly specified");
@@ -505,6 +1193,208 @@ impl Execution {
     }
 }
 
+/// The packages for the WiX Toolset extensions and the text that indicates an
+/// extension is used in a WiX Source (wxs) file, which is either a namespace for
+/// the v3 or modern schemas or an element.
+const EXTENSIONS: &[(&str, &[&str])] = &[
+    (
+        "WixToolset.Firewall.wixext",
+        &[
+            "http://schemas.microsoft.com/wix/FirewallExtension",
+            "http://wixtoolset.org/schemas/v4/wxs/firewall",
+        ],
+    ),
+    (
+        "WixToolset.Http.wixext",
+        &[
+            "http://schemas.microsoft.com/wix/HttpExtension",
+            "http://wixtoolset.org/schemas/v4/wxs/http",
+        ],
+    ),
+    (
+        "WixToolset.Iis.wixext",
+        &[
+            "http://schemas.microsoft.com/wix/IIsExtension",
+            "http://wixtoolset.org/schemas/v4/wxs/iis",
+        ],
+    ),
+    (
+        "WixToolset.Netfx.wixext",
+        &[
+            "http://schemas.microsoft.com/wix/NetFxExtension",
+            "http://wixtoolset.org/schemas/v4/wxs/netfx",
+        ],
+    ),
+    (
+        "WixToolset.Sql.wixext",
+        &[
+            "http://schemas.microsoft.com/wix/SqlExtension",
+            "http://wixtoolset.org/schemas/v4/wxs/sql",
+        ],
+    ),
+    ("WixToolset.UI.wixext", &["<UIRef", "<ui:WixUI"]),
+    (
+        "WixToolset.Util.wixext",
+        &[
+            "http://schemas.microsoft.com/wix/UtilExtension",
+            "http://wixtoolset.org/schemas/v4/wxs/util",
+        ],
+    ),
+];
+
+/// The version of the WiX Toolset SDK and extension packages referenced by a
+/// generated MSBuild project file (wixproj).
+const WIX_SDK_VERSION: &str = "4.0.5";
+
+/// The file extension for a MSBuild project file of the WiX Toolset.
+const WIXPROJ_FILE_EXTENSION: &str = "wixproj";
+
+/// Gets the packages for the WiX Toolset extensions used in the content of a
+/// WiX Source (wxs) file.
+fn detect_extensions(content: &str) -> Vec<&'static str> {
+    EXTENSIONS
+        .iter()
+        .filter(|(_, patterns)| patterns.iter().any(|p| content.contains(p)))
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+/// Gets the path to a folder relative to another folder with the Windows path
+/// separator.
+///
+/// The absolute path is used if the folders do not share a root, such as
+/// folders on different drives.
+fn relative_path(from: &Utf8Path, to: &Utf8Path) -> String {
+    let from: Vec<_> = from.components().collect();
+    let to: Vec<_> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(f, t)| f == t).count();
+    if common == 0 {
+        return to.iter().map(|c| c.as_str()).collect::<Vec<_>>().join("\\");
+    }
+    let mut parts = vec![".."; from.len() - common];
+    parts.extend(to[common..].iter().map(|c| c.as_str()));
+    if parts.is_empty() {
+        String::from(".")
+    } else {
+        parts.join("\\")
+    }
+}
+
+/// The template for a MSBuild project file (wixproj) for the modern WiX Toolset.
+const MSBUILD_PROJECT_TEMPLATE: &str = r#"<!--
+  Generated by cargo-wix. Regenerate with `cargo wix init --msbuild --force`.
+
+  The WiX Source (wxs) files generated by cargo-wix use the WiX Toolset v3
+  schema. Convert them with `wix convert` before building this project.
+-->
+<Project Sdk="WixToolset.Sdk/{{sdk-version}}">
+  <PropertyGroup>
+    <OutputName>{{name}}</OutputName>
+    <Version Condition="'$(Version)' == ''">{{version}}</Version>
+    <CargoProfile Condition="'$(CargoProfile)' == ''">release</CargoProfile>
+    <CargoTargetDir Condition="'$(CargoTargetDir)' == ''">{{target-dir}}</CargoTargetDir>
+    <EnableDefaultCompileItems>false</EnableDefaultCompileItems>
+    <DefineConstants>Version=$(Version);CargoProfile=$(CargoProfile);CargoTargetDir=$(CargoTargetDir);CargoTargetBinDir=$(CargoTargetDir)\$(CargoProfile)</DefineConstants>
+  </PropertyGroup>
+  <ItemGroup>
+    {{#sources}}
+    <Compile Include="{{.}}" />
+    {{/sources}}
+  </ItemGroup>
+  {{#has-extensions}}
+  <ItemGroup>
+    {{#extensions}}
+    <PackageReference Include="{{.}}" Version="{{sdk-version}}" />
+    {{/extensions}}
+  </ItemGroup>
+  {{/has-extensions}}
+</Project>
+"#;
+
+/// Renders a MSBuild project file (wixproj) for the modern WiX Toolset.
+fn msbuild_project(
+    package: &Package,
+    target_dir: &str,
+    sources: &[String],
+    extensions: &[&str],
+) -> Result<String> {
+    let version = &package.version;
+    let data = MapBuilder::new()
+        .insert_str("name", &package.name)
+        .insert_str("sdk-version", WIX_SDK_VERSION)
+        .insert_str(
+            "version",
+            format!("{}.{}.{}", version.major, version.minor, version.patch),
+        )
+        .insert_str("target-dir", target_dir)
+        .insert_vec("sources", |mut builder| {
+            for source in sources {
+                builder = builder.push_str(source);
+            }
+            builder
+        })
+        .insert_bool("has-extensions", !extensions.is_empty())
+        .insert_vec("extensions", |mut builder| {
+            for extension in extensions {
+                builder = builder.push_str(extension);
+            }
+            builder
+        })
+        .build();
+    mustache::compile_str(MSBUILD_PROJECT_TEMPLATE)?
+        .render_data_to_string(&data)
+        .map_err(Error::from)
+}
+
+/// Gets the `UpgradeCode` and the GUID of the path component from an existing
+/// WiX Source (wxs) file.
+///
+/// A GUID is `None` if it is not found or it is not a valid GUID, such as a
+/// preprocessor variable in a customized file. Both are `None` if the file
+/// cannot be read or parsed, since it is about to be overwritten anyway.
+fn existing_guids(path: &Utf8Path) -> (Option<String>, Option<String>) {
+    let package = match read_wxs_source(path.as_std_path())
+        .and_then(|content| sxd_document::parser::parse(&content).map_err(Error::from))
+    {
+        Ok(package) => package,
+        Err(err) => {
+            warn!(
+                "The GUIDs cannot be read from the existing '{}' file, so new GUIDs are \
+                 generated: {}",
+                path, err
+            );
+            return (None, None);
+        }
+    };
+    let document = package.as_document();
+    let context = sxd_xpath::Context::new();
+    let guid = |xpath: &str| {
+        // The assumption is that the following cannot fail because the paths
+        // are known to be valid at compile-time.
+        sxd_xpath::Factory::new()
+            .build(xpath)
+            .unwrap()
+            .unwrap()
+            .evaluate(&context, document.root())
+            .ok()
+            .map(|v| v.string())
+            .and_then(|s| Uuid::from_str(s.trim()).ok())
+            .map(|u| u.as_hyphenated().to_string().to_uppercase())
+    };
+    (
+        guid("//*[local-name()='Product']/@UpgradeCode"),
+        guid("//*[local-name()='Component'][@Id='Path']/@Guid"),
+    )
+}
+
+fn has_metadata_field(package: &Package, field: &str) -> bool {
+    package
+        .metadata
+        .get("wix")
+        .and_then(|w| w.as_object())
+        .is_some_and(|t| t.contains_key(field))
+}
+
 impl Default for Execution {
     fn default() -> Self {
         Builder::new().build()
@@ -524,6 +1414,7 @@ mod tests {
         #[test]
         fn defaults_are_correct() {
             let actual = Builder::new();
+            assert!(!actual.after_install_launch);
             assert!(actual.banner.is_none());
             assert!(actual.binaries.is_none());
             assert!(actual.copyright_year.is_none());
@@ -531,9 +1422,12 @@ mod tests {
             assert!(actual.description.is_none());
             assert!(actual.dialog.is_none());
             assert!(actual.eula.is_none());
+            assert!(actual.feature_maps.is_none());
             assert!(!actual.force);
+            assert!(!actual.generate_branding);
             assert!(actual.help_url.is_none());
             assert!(actual.input.is_none());
+            assert!(actual.launch_binary.is_none());
             assert!(actual.license.is_none());
             assert!(actual.manufacturer.is_none());
             assert!(actual.output.is_none());
@@ -541,6 +1435,22 @@ mod tests {
             assert!(actual.product_icon.is_none());
             assert!(actual.product_name.is_none());
             assert!(actual.upgrade_guid.is_none());
+            assert!(actual.wxs_template_dir.is_none());
+        }
+
+        #[test]
+        fn after_install_launch_works() {
+            let mut actual = Builder::new();
+            actual.after_install_launch(true);
+            assert!(actual.after_install_launch);
+        }
+
+        #[test]
+        fn author_separator_works() {
+            const EXPECTED: &str = ", ";
+            let mut actual = Builder::new();
+            actual.author_separator(Some(EXPECTED));
+            assert_eq!(actual.author_separator, Some(EXPECTED));
         }
 
         #[test]
@@ -591,6 +1501,14 @@ mod tests {
             assert_eq!(actual.dialog, Some(EXPECTED));
         }
 
+        #[test]
+        fn dialog_set_works() {
+            const EXPECTED: &str = "install-dir";
+            let mut actual = Builder::new();
+            actual.dialog_set(Some(EXPECTED));
+            assert_eq!(actual.dialog_set, Some(EXPECTED));
+        }
+
         #[test]
         fn eula_works() {
             const EXPECTED: &str = "eula.rtf";
@@ -599,6 +1517,14 @@ mod tests {
             assert_eq!(actual.eula, Some(EXPECTED));
         }
 
+        #[test]
+        fn feature_maps_works() {
+            const EXPECTED: &str = "cli=Command Line Tools";
+            let mut actual = Builder::new();
+            actual.feature_maps(Some(vec![EXPECTED]));
+            assert_eq!(actual.feature_maps, Some(vec![EXPECTED]));
+        }
+
         #[test]
         fn force_works() {
             let mut actual = Builder::new();
@@ -606,6 +1532,28 @@ mod tests {
             assert!(actual.force);
         }
 
+        #[test]
+        fn msbuild_works() {
+            let mut actual = Builder::new();
+            actual.msbuild(true);
+            assert!(actual.msbuild);
+        }
+
+        #[test]
+        fn generate_branding_works() {
+            let mut actual = Builder::new();
+            actual.generate_branding(true);
+            assert!(actual.generate_branding);
+        }
+
+        #[test]
+        fn guid_from_works() {
+            const EXPECTED: &str = "com.example.product";
+            let mut actual = Builder::new();
+            actual.guid_from(Some(EXPECTED));
+            assert_eq!(actual.guid_from, Some(EXPECTED));
+        }
+
         #[test]
         fn help_url_works() {
             const EXPECTED: &str = "http://github.com/volks73/cargo-wix";
@@ -622,6 +1570,29 @@ mod tests {
             assert_eq!(actual.input, Some(EXPECTED));
         }
 
+        #[test]
+        fn install_scope_works() {
+            const EXPECTED: &str = "per-user";
+            let mut actual = Builder::new();
+            actual.install_scope(Some(EXPECTED));
+            assert_eq!(actual.install_scope, Some(EXPECTED));
+        }
+
+        #[test]
+        fn keep_author_emails_works() {
+            let mut actual = Builder::new();
+            actual.keep_author_emails(true);
+            assert!(actual.keep_author_emails);
+        }
+
+        #[test]
+        fn launch_binary_works() {
+            const EXPECTED: &str = "Example";
+            let mut actual = Builder::new();
+            actual.launch_binary(Some(EXPECTED));
+            assert_eq!(actual.launch_binary, Some(EXPECTED));
+        }
+
         #[test]
         fn license_works() {
             const EXPECTED: &str = "License.txt";
@@ -638,6 +1609,57 @@ mod tests {
             assert_eq!(actual.manufacturer, Some(EXPECTED));
         }
 
+        #[test]
+        fn manufacturer_from_works() {
+            const EXPECTED: &str = "first";
+            let mut actual = Builder::new();
+            actual.manufacturer_from(Some(EXPECTED));
+            assert_eq!(actual.manufacturer_from, Some(EXPECTED));
+        }
+
+        #[test]
+        fn no_path_works() {
+            let mut actual = Builder::new();
+            actual.no_path(true);
+            assert!(actual.no_path);
+        }
+
+        #[test]
+        fn no_modify_works() {
+            let mut actual = Builder::new();
+            actual.no_modify(true);
+            assert!(actual.no_modify);
+        }
+
+        #[test]
+        fn no_repair_works() {
+            let mut actual = Builder::new();
+            actual.no_repair(true);
+            assert!(actual.no_repair);
+        }
+
+        #[test]
+        fn no_upgrade_works() {
+            let mut actual = Builder::new();
+            actual.no_upgrade(true);
+            assert!(actual.no_upgrade);
+        }
+
+        #[test]
+        fn deterministic_component_guids_works() {
+            let mut actual = Builder::new();
+            actual.deterministic_component_guids(true);
+            assert!(actual.deterministic_component_guids);
+        }
+
+        #[test]
+        fn dump_context_works() {
+            const EXPECTED: &str = "context.json";
+            let mut actual = Builder::new();
+            actual.dump_context(Some(EXPECTED));
+            assert_eq!(actual.dump_context, Some(EXPECTED));
+        }
+
         #[test]
         fn output_works() {
             const EXPECTED: &str = "output";
@@ -669,6 +1691,42 @@ mod tests {
             assert_eq!(actual.product_name, Some(EXPECTED));
         }
 
+        #[test]
+        fn regenerate_guids_works() {
+            let mut actual = Builder::new();
+            actual.regenerate_guids(true);
+            assert!(actual.regenerate_guids);
+        }
+
+        #[test]
+        fn run_after_install_works() {
+            const EXPECTED: &str = "setup.exe --register";
+            let mut actual = Builder::new();
+            actual.run_after_install(Some(EXPECTED));
+            assert_eq!(actual.run_after_install, Some(EXPECTED));
+        }
+
+        #[test]
+        fn skip_eula_dialog_works() {
+            let mut actual = Builder::new();
+            actual.skip_eula_dialog(true);
+            assert!(actual.skip_eula_dialog);
+        }
+
+        #[test]
+        fn skip_license_sidecar_works() {
+            let mut actual = Builder::new();
+            actual.skip_license_sidecar(true);
+            assert!(actual.skip_license_sidecar);
+        }
+
+        #[test]
+        fn uninstall_shortcut_works() {
+            let mut actual = Builder::new();
+            actual.uninstall_shortcut(true);
+            assert!(actual.uninstall_shortcut);
+        }
+
         #[test]
         fn upgrade_guid_works() {
             let mut actual = Builder::new();
@@ -676,6 +1734,14 @@ mod tests {
             assert_eq!(actual.upgrade_guid, Some(UPGRADE_GUID));
         }
 
+        #[test]
+        fn wxs_template_dir_works() {
+            const EXPECTED: &str = "C:\\tmp\\templates";
+            let mut actual = Builder::new();
+            actual.wxs_template_dir(Some(EXPECTED));
+            assert_eq!(actual.wxs_template_dir, Some(EXPECTED));
+        }
+
         #[test]
         fn build_with_defaults_works() {
             let mut b = Builder::new();
@@ -688,12 +1754,14 @@ mod tests {
             assert!(!default_execution.force);
             assert!(default_execution.help_url.is_none());
             assert!(default_execution.input.is_none());
+            assert!(default_execution.launch_binary.is_none());
             assert!(default_execution.license.is_none());
             assert!(default_execution.manufacturer.is_none());
             assert!(default_execution.output.is_none());
             assert!(default_execution.product_icon.is_none());
             assert!(default_execution.product_name.is_none());
             assert!(default_execution.upgrade_guid.is_none());
+            assert!(default_execution.wxs_template_dir.is_none());
         }
 
         #[test]
@@ -710,7 +1778,9 @@ mod tests {
             const EXPECTED_OUTPUT: &str = "C:\\tmp\\output";
             const EXPECTED_PRODUCT_ICON: &str = "img\\Product.ico";
             const EXPECTED_PRODUCT_NAME: &str = "Product Name";
+            const EXPECTED_LAUNCH_BINARY: &str = "Example";
             let mut b = Builder::new();
+            b.after_install_launch(true);
             b.binaries(Some(vec![EXPECTED_BINARY]));
             b.copyright_holder(Some(EXPECTED_COPYRIGHT_HOLDER));
             b.copyright_year(Some(EXPECTED_COPYRIGHT_YEAR));
@@ -719,6 +1789,7 @@ mod tests {
             b.force(true);
             b.help_url(Some(EXPECTED_URL));
             b.input(Some(EXPECTED_INPUT));
+            b.launch_binary(Some(EXPECTED_LAUNCH_BINARY));
             b.license(Some(EXPECTED_LICENSE));
             b.manufacturer(Some(EXPECTED_MANUFACTURER));
             b.output(Some(EXPECTED_OUTPUT));
@@ -727,6 +1798,7 @@ mod tests {
             b.product_name(Some(EXPECTED_PRODUCT_NAME));
             b.upgrade_guid(Some(UPGRADE_GUID));
             let execution = b.build();
+            assert!(execution.after_install_launch);
             assert_eq!(
                 execution.binaries,
                 Some(vec![EXPECTED_BINARY])
@@ -748,6 +1820,10 @@ mod tests {
             assert!(execution.force);
             assert_eq!(execution.help_url, Some(String::from(EXPECTED_URL)));
             assert_eq!(execution.input, Some(PathBuf::from(EXPECTED_INPUT)));
+            assert_eq!(
+                execution.launch_binary,
+                Some(String::from(EXPECTED_LAUNCH_BINARY))
+            );
             assert_eq!(
                 execution.license,
                 Some(StoredPathBuf::from(EXPECTED_LICENSE))
@@ -64,18 +64,27 @@
 
 pub use crate::templates::Template;
 
+mod branding;
+pub mod check;
 pub mod clean;
+pub mod config;
 pub mod create;
+pub mod extensions;
+mod icon;
+pub mod info;
 pub mod initialize;
 mod licenses;
+pub mod metadata;
+mod pe;
 pub mod print;
 pub mod purge;
 pub mod sign;
 pub mod stored_path;
 mod templates;
+pub mod verify;
 
 use camino::Utf8Path;
-use log::debug;
+use log::{debug, error};
 
 use std::convert::TryFrom;
 use std::default::Default;
@@ -84,32 +93,63 @@ use std::error::Error as StdError;
 use std::ffi::OsStr;
 use std::fmt;
 use std::fmt::Display;
-use std::io::{self, ErrorKind};
+use std::io::{self, ErrorKind, Read};
 use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
 use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use cargo_metadata::{Metadata, MetadataCommand, Package};
+use cargo_metadata::{Metadata, MetadataCommand, Package, PackageId};
 
 use rustc_cfg::Cfg;
+use uuid::Uuid;
 
 /// The name of the folder where binaries are typically stored.
 pub const BINARY_FOLDER_NAME: &str = "bin";
 
+/// The file name without the extension of a generated banner image.
+pub const BANNER_FILE_NAME: &str = "Banner";
+
+/// The file extension of a bitmap image.
+pub const BMP_FILE_EXTENSION: &str = "bmp";
+
 /// The file name with extension for a package's manifest.
 pub const CARGO_MANIFEST_FILE: &str = "Cargo.toml";
 
 /// The name of the builder application for a Rust project.
 pub const CARGO: &str = "cargo";
 
+/// The prefix of the WiX compiler (candle.exe) variables that are defined for
+/// the enabled Cargo features of a package.
+pub const CARGO_FEATURE_VARIABLE_PREFIX: &str = "CargoFeature_";
+
+/// The file name with extension of a package's changelog.
+pub const CHANGELOG_FILE_NAME: &str = "CHANGELOG.md";
+
+/// The file name without the extension of a generated dialog image.
+pub const DIALOG_FILE_NAME: &str = "Dialog";
+
 /// The file extension for an executable.
 pub const EXE_FILE_EXTENSION: &str = "exe";
 
+/// The name of the version control application used to get the current commit.
+pub const GIT: &str = "git";
+
 /// The file name without an extension when generating a license.
 pub const LICENSE_FILE_NAME: &str = "License";
 
 /// The file extension for a Windows installer.
 pub const MSI_FILE_EXTENSION: &str = "msi";
 
+/// The value for the product icon that uses the icon embedded in the resources
+/// of the main binary instead of an icon file.
+pub const PRODUCT_ICON_FROM_BINARY: &str = "from-binary";
+
+/// The name of the WiX Toolset compiler variable for the path to the product
+/// icon extracted from the main binary.
+pub const PRODUCT_ICON_VARIABLE: &str = "ProductIcon";
+
 /// The file extension for a Rich Text Format (RTF) file.
 pub const RTF_FILE_EXTENSION: &str = "rtf";
 
@@ -131,9 +171,25 @@ pub const WIX_COMPILER: &str = "candle";
 /// Windows installer.
 pub const WIX_LINKER: &str = "light";
 
+/// The application name without the file extension of the WiX Toolset
+/// application for detaching and reattaching the engine of a bundle.
+pub const WIX_INSIGNIA: &str = "insignia";
+
+/// The application name without the file extension of the WiX Toolset
+/// application for creating a transform (mst) from the differences between two
+/// installers.
+pub const WIX_TORCH: &str = "torch";
+
+/// The application name without the file extension of the command line tool
+/// for the modern WiX Toolset (v4 and later).
+pub const WIX_TOOL: &str = "wix";
+
 /// The application name without the file extension of the `msiexec` utility.
 pub const MSIEXEC: &str = "msiexec";
 
+/// The application name without the file extension of the `tasklist` utility.
+pub const TASKLIST: &str = "tasklist";
+
 /// The file extension for a WiX Toolset object file, which is the output from
 /// the WiX compiler.
 pub const WIX_OBJECT_FILE_EXTENSION: &str = "wixobj";
@@ -151,6 +207,9 @@ pub const WIX_SOURCE_FILE_EXTENSION: &str = "wxs";
 /// Toolset compiler.
 pub const WIX_SOURCE_FILE_NAME: &str = "main";
 
+/// The file extension for a portable zip archive.
+pub const ZIP_FILE_EXTENSION: &str = "zip";
+
 /// A specialized [`Result`] type for wix operations.
 ///
 /// [`Result`]: https://doc.rust-lang.org/std/result/
@@ -202,18 +261,389 @@ fn package(manifest: &Metadata, package: Option<&str>) -> Result<Package> {
             .ok_or_else(|| Error::Generic(format!("No `{p}` package found in the project")))?
     } else if manifest.workspace_members.len() == 1 {
         &manifest.workspace_members[0]
+    } else if let Some(package_id) = env::current_dir()
+        .ok()
+        .and_then(|cwd| member_containing(manifest, &cwd))
+    {
+        debug!(
+            "Using the '{}' workspace member that contains the current working directory",
+            manifest[package_id].name
+        );
+        package_id
+    } else if manifest
+        .workspace_metadata
+        .get("wix")
+        .is_some_and(|w| w.is_object())
+    {
+        debug!("Using the '[workspace.metadata.wix]' section for the workspace");
+        return workspace_package(manifest);
     } else {
         // TODO: Replace error with creating installers for all packages in a
         // workspace. I think this currently means that to create installers for
         // all packages in workspace, a `cargo wix --package <name>` must be
         // executed for each workspace member.
         return Err(Error::Generic(String::from(
-            "Workspace detected. Please pass a package name.",
+            "Workspace detected. Please pass a package name or add a \
+             '[workspace.metadata.wix]' section to the workspace's manifest \
+             (Cargo.toml) to create an installer for the entire workspace.",
         )));
     };
     Ok(manifest[package_id].clone())
 }
 
+/// Finds the member of a workspace whose folder contains a folder.
+///
+/// This is how cargo selects the "current package" in a workspace from the
+/// current working directory. The member with the deepest folder is used if
+/// the folders of members are nested, such as a root package that is also a
+/// member of the workspace.
+fn member_containing<'a>(manifest: &'a Metadata, dir: &Path) -> Option<&'a PackageId> {
+    manifest
+        .workspace_members
+        .iter()
+        .filter_map(|id| manifest[id].manifest_path.parent().map(|p| (id, p)))
+        .filter(|(_, member_dir)| dir.starts_with(member_dir))
+        .max_by_key(|(_, member_dir)| member_dir.components().count())
+        .map(|(id, _)| id)
+}
+
+/// Creates a package for the root of a workspace.
+///
+/// A virtual manifest does not have a `[package]` section, so the name and
+/// version are read from the `[workspace.metadata.wix]` section instead, and
+/// the section is used as the package's metadata. The binaries and authors of
+/// all workspace members are included, which makes it possible to create a
+/// single "suite" installer for the entire workspace.
+fn workspace_package(manifest: &Metadata) -> Result<Package> {
+    let wix = &manifest.workspace_metadata["wix"];
+    let name = wix
+        .get("name")
+        .and_then(|n| n.as_str())
+        .or_else(|| manifest.workspace_root.file_name())
+        .ok_or_else(|| {
+            Error::Generic(String::from(
+                "The name for the workspace could not be determined. Please add the 'name' \
+                 field to the '[workspace.metadata.wix]' section of the workspace's manifest \
+                 (Cargo.toml).",
+            ))
+        })?;
+    let version = wix.get("version").and_then(|v| v.as_str()).ok_or_else(|| {
+        Error::Generic(String::from(
+            "The version for the workspace could not be determined. Please add the 'version' \
+             field to the '[workspace.metadata.wix]' section of the workspace's manifest \
+             (Cargo.toml).",
+        ))
+    })?;
+    let members = manifest
+        .workspace_members
+        .iter()
+        .map(|id| &manifest[id])
+        .collect::<Vec<&Package>>();
+    let mut authors: Vec<&String> = Vec::new();
+    for author in members.iter().flat_map(|p| p.authors.iter()) {
+        if !authors.contains(&author) {
+            authors.push(author);
+        }
+    }
+    let targets = members
+        .iter()
+        .flat_map(|p| p.targets.iter())
+        .filter(|t| t.kind.iter().any(|k| k == "bin"))
+        .collect::<Vec<_>>();
+    let manifest_path = manifest.workspace_root.join(CARGO_MANIFEST_FILE);
+    serde_json::from_value(serde_json::json!({
+        "name": name,
+        "version": version,
+        "authors": authors,
+        "id": format!("{name} {version} (path+file://{})", manifest.workspace_root),
+        "dependencies": [],
+        "targets": targets,
+        "features": {},
+        "manifest_path": manifest_path,
+        "metadata": manifest.workspace_metadata,
+    }))
+    .map_err(|e| {
+        Error::Generic(format!(
+            "The '[workspace.metadata.wix]' section of the workspace's manifest (Cargo.toml) is \
+             invalid: {e}"
+        ))
+    })
+}
+
+/// Resolves a path from the `[package.metadata.wix]` section of a package's
+/// manifest (Cargo.toml) relative to the folder containing the manifest, so the
+/// path does not depend on the current working directory.
+///
+/// Absolute paths are returned unchanged.
+fn package_relative_path(package: &Package, path: &str) -> PathBuf {
+    package
+        .manifest_path
+        .parent()
+        .map(|d| d.as_std_path().join(path))
+        .unwrap_or_else(|| PathBuf::from(path))
+}
+
+/// Gets the name of the primary binary of a package.
+///
+/// This is the binary from the `default-run` field of the package's manifest
+/// (Cargo.toml), which is the binary that `cargo run` runs, or the name of the
+/// package if the field does not exist.
+fn primary_binary_name(package: &Package) -> &str {
+    package.default_run.as_deref().unwrap_or(&package.name)
+}
+
+/// Splits a comma-separated list of cultures.
+///
+/// Whitespace around each culture is removed, and empty entries are skipped.
+fn culture_list(value: &str) -> Vec<&str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .collect()
+}
+
+/// Gets the cultures from the `culture` field in the `[package.metadata.wix]`
+/// section of a package's metadata.
+///
+/// The field is either a string with a culture, or a comma-separated list of
+/// cultures, or an array of cultures. The first culture is the culture of the
+/// installer. `None` is returned if the field does not exist or has a
+/// different type.
+fn metadata_cultures(metadata: &serde_json::Value) -> Option<Vec<&str>> {
+    let culture = metadata
+        .get("wix")
+        .and_then(|w| w.as_object())
+        .and_then(|t| t.get("culture"))?;
+    if let Some(culture) = culture.as_str() {
+        Some(culture_list(culture))
+    } else {
+        culture.as_array().map(|a| {
+            a.iter()
+                .filter_map(|c| c.as_str())
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .collect()
+        })
+    }
+}
+
+/// The interval for checking if a command with a timeout has finished.
+const TIMEOUT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Parses the number of seconds for the `--timeout` option.
+///
+/// `None` is returned if there is no timeout.
+fn timeout(value: Option<&str>) -> Result<Option<Duration>> {
+    value
+        .map(|v| match v.trim().parse::<u64>() {
+            Ok(seconds) if seconds > 0 => Ok(Duration::from_secs(seconds)),
+            _ => Err(Error::Generic(format!(
+                "The '{v}' timeout is not valid. The timeout must be a whole number of \
+                 seconds greater than zero."
+            ))),
+        })
+        .transpose()
+}
+
+/// Parses a GUID from an option or a field in the `[package.metadata.wix]`
+/// section.
+///
+/// The GUID is returned in the hyphenated, uppercase format used in the WiX
+/// Source (wxs) file. The `source` names the option or field in an error log
+/// message before the [`Error::Uuid`] error is returned if the value is not a
+/// valid GUID.
+fn guid(value: &str, source: &str) -> Result<String> {
+    Uuid::from_str(value.trim())
+        .map(|u| u.as_hyphenated().to_string().to_uppercase())
+        .map_err(|err| {
+            error!(
+                "The '{value}' value for the {source} is not a valid GUID. The expected \
+                 format is a hyphenated, uppercase UUID, i.e. \
+                 'XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX' where each 'X' is a \
+                 hexadecimal digit."
+            );
+            Error::from(err)
+        })
+}
+
+/// Runs a command and waits for it to finish, like the [`Command::status`]
+/// method, but stops the command if it does not finish within the timeout.
+///
+/// An error with the [`ErrorKind::TimedOut`] kind is returned if the command
+/// is stopped. Only the command is stopped, not any processes it started. The
+/// command is not limited if the timeout is `None`.
+fn status_with_timeout(command: &mut Command, timeout: Option<Duration>) -> io::Result<ExitStatus> {
+    let Some(timeout) = timeout else {
+        return command.status();
+    };
+    let mut child = command.spawn()?;
+    wait_with_timeout(command, &mut child, timeout)
+}
+
+/// Runs a command like the [`status_with_timeout`] function, but captures the
+/// standard error (stderr) of the command instead of inheriting it.
+///
+/// The stderr is returned with the exit status, so the caller can classify
+/// a failure from the messages of the command.
+fn status_with_stderr(
+    command: &mut Command,
+    timeout: Option<Duration>,
+) -> io::Result<(ExitStatus, Vec<u8>)> {
+    command.stderr(Stdio::piped());
+    let mut child = command.spawn()?;
+    // The stderr is read on a separate thread, so the command cannot block
+    // on a full pipe while waiting for it to finish.
+    let reader = child.stderr.take().map(|mut stderr| {
+        thread::spawn(move || {
+            let mut buffer = Vec::new();
+            stderr.read_to_end(&mut buffer).map(|_| buffer)
+        })
+    });
+    let status = match timeout {
+        Some(timeout) => wait_with_timeout(command, &mut child, timeout),
+        None => child.wait(),
+    }?;
+    let stderr = match reader.map(|r| r.join()) {
+        Some(Ok(stderr)) => stderr?,
+        Some(Err(_)) => {
+            return Err(io::Error::new(
+                ErrorKind::Other,
+                "The standard error of the command could not be read",
+            ))
+        }
+        None => Vec::new(),
+    };
+    Ok((status, stderr))
+}
+
+/// Formats a command as a line that can be copied and pasted into a shell to
+/// run the command again.
+///
+/// The line changes to the working directory and sets the environment
+/// variables of the command first, if either is set for the command. A value
+/// with whitespace or shell metacharacters is quoted for the shell of the
+/// platform, i.e. the Command Prompt on Windows and a POSIX shell otherwise.
+fn command_line(command: &Command) -> String {
+    let mut parts = Vec::new();
+    if let Some(dir) = command.get_current_dir() {
+        if cfg!(windows) {
+            parts.push(format!("cd /d {}", shell_quote(dir.as_os_str())));
+        } else {
+            parts.push(format!("cd {}", shell_quote(dir.as_os_str())));
+        }
+    }
+    let mut program = Vec::new();
+    for (key, value) in command.get_envs() {
+        let key = key.to_string_lossy();
+        if cfg!(windows) {
+            let value = value.map(OsStr::to_string_lossy).unwrap_or_default();
+            parts.push(format!("set \"{key}={value}\""));
+        } else if let Some(value) = value {
+            program.push(format!("{key}={}", shell_quote(value)));
+        } else {
+            program.push(format!("-u {key}"));
+        }
+    }
+    if !program.is_empty() {
+        program.insert(0, String::from("env"));
+    }
+    program.push(shell_quote(command.get_program()));
+    program.extend(command.get_args().map(shell_quote));
+    parts.push(program.join(" "));
+    parts.join(" && ")
+}
+
+/// Quotes a value for the shell of the platform if it is empty or contains
+/// whitespace or shell metacharacters.
+fn shell_quote(value: &OsStr) -> String {
+    let value = value.to_string_lossy();
+    let special = |c: char| c.is_whitespace() || "\"'&|<>^;$`()*?!%".contains(c);
+    if !value.is_empty() && !value.contains(special) {
+        value.into_owned()
+    } else if cfg!(windows) {
+        format!("\"{}\"", value.replace('"', r#"\""#))
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+/// Waits for a spawned command to finish, but stops it if it does not finish
+/// within the timeout.
+fn wait_with_timeout(
+    command: &Command,
+    child: &mut Child,
+    timeout: Duration,
+) -> io::Result<ExitStatus> {
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            // The command may have finished since it was checked, in which
+            // case it cannot be killed, but it still must be waited on.
+            let _ = child.kill();
+            child.wait()?;
+            return Err(io::Error::new(
+                ErrorKind::TimedOut,
+                format!(
+                    "The '{}' application did not finish within {} seconds and was stopped. \
+                     Use the '--timeout' option to allow more time.",
+                    command.get_program().to_string_lossy(),
+                    timeout.as_secs()
+                ),
+            ));
+        }
+        thread::sleep(TIMEOUT_POLL_INTERVAL.min(timeout - elapsed));
+    }
+}
+
+/// Gets the name of the WiX compiler (candle.exe) variable that is defined
+/// when a Cargo feature of the package is enabled, such as `CargoFeature_cli`
+/// for the `cli` feature.
+///
+/// Characters that are not allowed in a WiX identifier, such as the hyphen, are
+/// replaced with an underscore.
+fn cargo_feature_variable(feature: &str) -> String {
+    let mut variable = String::from(CARGO_FEATURE_VARIABLE_PREFIX);
+    variable.extend(feature.chars().map(|c| {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            c
+        } else {
+            '_'
+        }
+    }));
+    variable
+}
+
+/// Gets the sorted Cargo features of the package that are enabled for a build.
+///
+/// The `default` feature is enabled unless `default_features` is `false`, and
+/// each enabled feature enables the other features of the package that it
+/// lists. Optional dependencies and the features of dependencies, such as
+/// `dep:name` and `name/feature`, are not features of the package and are
+/// ignored.
+fn enabled_features(package: &Package, features: &[String], default_features: bool) -> Vec<String> {
+    let mut pending: Vec<&str> = features.iter().map(String::as_str).collect();
+    if default_features {
+        pending.push("default");
+    }
+    let mut enabled: Vec<String> = Vec::new();
+    while let Some(feature) = pending.pop() {
+        if enabled.iter().any(|e| e == feature) {
+            continue;
+        }
+        if let Some(requirements) = package.features.get(feature) {
+            enabled.push(feature.to_owned());
+            pending.extend(requirements.iter().map(String::as_str));
+        }
+    }
+    enabled.sort();
+    enabled
+}
+
 fn product_name(product_name: Option<&String>, manifest: &Package) -> String {
     if let Some(p) = product_name {
         p.to_owned()
@@ -222,6 +652,41 @@ fn product_name(product_name: Option<&String>, manifest: &Package) -> String {
     }
 }
 
+/// Reads a WiX Source (wxs) file, decoding it from UTF-16 if it has a
+/// byte-order mark.
+///
+/// A file without a byte-order mark is decoded with the encoding from its XML
+/// declaration, e.g. `<?xml version="1.0" encoding="windows-1252"?>`, or as
+/// UTF-8 if it does not declare one. An override for the encoding is not
+/// needed because the XML specification requires a byte-order mark for UTF-16
+/// and a declaration for any other encoding, which is how the WiX Toolset
+/// compiler determines the encoding, too.
+fn read_wxs_source(source: &Path) -> Result<String> {
+    let bytes = std::fs::read(source)?;
+    let mut decoder = encoding_rs_io::DecodeReaderBytesBuilder::new()
+        .encoding(declared_encoding(&bytes))
+        .bom_override(true)
+        .build(bytes.as_slice());
+    let mut content = String::new();
+    decoder.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+/// Gets the encoding from the XML declaration at the start of a file.
+///
+/// `None` is returned if the file does not start with an XML declaration, the
+/// declaration does not have an encoding, or the encoding is unknown.
+fn declared_encoding(bytes: &[u8]) -> Option<&'static encoding_rs::Encoding> {
+    let declaration = bytes.strip_prefix(b"<?xml")?;
+    let end = declaration.windows(2).position(|w| w == b"?>")?;
+    let declaration = std::str::from_utf8(&declaration[..end]).ok()?;
+    let (_, value) = declaration.split_once("encoding")?;
+    let value = value.trim_start().strip_prefix('=')?.trim_start();
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let (label, _) = value[1..].split_once(quote)?;
+    encoding_rs::Encoding::for_label(label.as_bytes())
+}
+
 /// The error type for wix-related operations and associated traits.
 ///
 /// Errors mostly originate from the dependencies, but custom instances of `Error` can be created
@@ -265,6 +730,25 @@ impl Error {
     /// This is useful as a return, or exit, code for a command line application, where a non-zero
     /// integer indicates a failure in the application. it can also be used for quickly and easily
     /// testing equality between two errors.
+    ///
+    /// The codes are stable and can be relied upon in scripts. The code for an existing variant is
+    /// never changed, and a new variant is assigned the next unused code. See the [`codes`] method
+    /// for the complete list.
+    ///
+    /// | Code | Variant       |
+    /// |------|---------------|
+    /// | 1    | Command       |
+    /// | 2    | Generic       |
+    /// | 3    | Io            |
+    /// | 4    | Manifest      |
+    /// | 5    | Mustache      |
+    /// | 6    | UUID          |
+    /// | 7    | Version       |
+    /// | 8    | XML           |
+    /// | 9    | XPath         |
+    /// | 10   | CargoMetadata |
+    ///
+    /// [`codes`]: Error::codes
     pub fn code(&self) -> i32 {
         match *self {
             Error::Command(..) => 1,
@@ -280,6 +764,34 @@ impl Error {
         }
     }
 
+    /// Gets the error code and short name, see the [`as_str`] method, for every
+    /// variant in ascending order of the code.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use wix::Error;
+    ///
+    /// let err = Error::from("A generic error");
+    /// assert!(Error::codes().contains(&(err.code(), err.as_str())));
+    /// ```
+    ///
+    /// [`as_str`]: Error::as_str
+    pub fn codes() -> &'static [(i32, &'static str)] {
+        &[
+            (1, "Command"),
+            (2, "Generic"),
+            (3, "Io"),
+            (4, "Manifest"),
+            (5, "Mustache"),
+            (6, "UUID"),
+            (7, "Version"),
+            (8, "XML"),
+            (9, "XPath"),
+            (10, "CargoMetadata"),
+        ]
+    }
+
     /// Creates a new `Error` from a [std::io::Error] with the
     /// [std::io::ErrorKind::AlreadyExists] variant.
     ///
@@ -662,6 +1174,119 @@ impl FromStr for TimestampServer {
     }
 }
 
+/// The installation scopes, or contexts, for an installer.
+///
+/// A per-machine installation requires elevated privileges, i.e. the user must
+/// be an administrator, while a per-user installation does not.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InstallScope {
+    /// Installs for all users of the machine.
+    #[default]
+    PerMachine,
+    /// Installs for the current user only.
+    PerUser,
+}
+
+impl InstallScope {
+    /// The value that picks the installation scope based on other options,
+    /// instead of a specific installation scope.
+    pub const AUTO: &'static str = "auto";
+}
+
+impl fmt::Display for InstallScope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InstallScope::PerMachine => write!(f, "perMachine"),
+            InstallScope::PerUser => write!(f, "perUser"),
+        }
+    }
+}
+
+impl FromStr for InstallScope {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().trim() {
+            "per-machine" | "permachine" => Ok(InstallScope::PerMachine),
+            "per-user" | "peruser" => Ok(InstallScope::PerUser),
+            s => Err(Error::Generic(format!(
+                "Unknown '{s}' installation scope. The installation scope must be \
+                 'per-machine', 'per-user', or '{}'.",
+                InstallScope::AUTO
+            ))),
+        }
+    }
+}
+
+/// The set of dialogs from the [WixUI dialog library] for the installer.
+///
+/// Each set has a license agreement dialog after the welcome dialog, which is
+/// skipped if the installer does not have an EULA.
+///
+/// [WixUI dialog library]: https://wixtoolset.org/documentation/manual/v3/wixui/wixui_dialog_library.html
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DialogSet {
+    /// Has a feature tree dialog for selecting the features and the
+    /// installation folder.
+    #[default]
+    FeatureTree,
+    /// Has a dialog for selecting the installation folder without a feature
+    /// tree.
+    InstallDir,
+    /// Has a dialog for selecting a typical, custom, or complete installation.
+    Mondo,
+}
+
+impl DialogSet {
+    /// Gets the identifier of the dialog that follows the license agreement
+    /// dialog.
+    ///
+    /// The welcome dialog goes directly to this dialog when the license
+    /// agreement dialog is skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wix::DialogSet;
+    ///
+    /// assert_eq!(DialogSet::FeatureTree.license_next_dialog(), "CustomizeDlg");
+    /// assert_eq!(DialogSet::InstallDir.license_next_dialog(), "InstallDirDlg");
+    /// ```
+    pub fn license_next_dialog(&self) -> &'static str {
+        match *self {
+            DialogSet::FeatureTree => "CustomizeDlg",
+            DialogSet::InstallDir => "InstallDirDlg",
+            DialogSet::Mondo => "SetupTypeDlg",
+        }
+    }
+}
+
+impl fmt::Display for DialogSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DialogSet::FeatureTree => write!(f, "WixUI_FeatureTree"),
+            DialogSet::InstallDir => write!(f, "WixUI_InstallDir"),
+            DialogSet::Mondo => write!(f, "WixUI_Mondo"),
+        }
+    }
+}
+
+impl FromStr for DialogSet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().trim() {
+            "feature-tree" | "featuretree" | "wixui_featuretree" => Ok(DialogSet::FeatureTree),
+            "install-dir" | "installdir" | "wixui_installdir" => Ok(DialogSet::InstallDir),
+            "mondo" | "wixui_mondo" => Ok(DialogSet::Mondo),
+            s => Err(Error::Generic(format!(
+                "Unknown '{s}' dialog set. The dialog set must be 'feature-tree', \
+                 'install-dir', or 'mondo'."
+            ))),
+        }
+    }
+}
+
 /// The various culture codes for localization.
 ///
 /// These are taken from the table in the [WixUI localization] documentation.
@@ -702,6 +1327,8 @@ pub enum Cultures {
     HiIn,
     /// Hungarian, Hungary
     HuHu,
+    /// Indonesian, Indonesia
+    IdId,
     /// Italian, Italy
     ItIt,
     /// Japanese, Japan
@@ -748,6 +1375,8 @@ pub enum Cultures {
     TrTr,
     /// Ukrainian, Ukraine
     UkUa,
+    /// Vietnamese, Vietnam
+    ViVn,
 }
 
 impl Cultures {
@@ -774,6 +1403,7 @@ impl Cultures {
     /// assert_eq!(Cultures::HeIl.language(), "Hebrew");
     /// assert_eq!(Cultures::HiIn.language(), "Hindi");
     /// assert_eq!(Cultures::HuHu.language(), "Hungarian");
+    /// assert_eq!(Cultures::IdId.language(), "Indonesian");
     /// assert_eq!(Cultures::ItIt.language(), "Italian");
     /// assert_eq!(Cultures::JaJp.language(), "Japanese");
     /// assert_eq!(Cultures::KkKz.language(), "Kazakh");
@@ -797,6 +1427,7 @@ impl Cultures {
     /// assert_eq!(Cultures::ZhTw.language(), "Traditional Chinese");
     /// assert_eq!(Cultures::TrTr.language(), "Turkish");
     /// assert_eq!(Cultures::UkUa.language(), "Ukrainian");
+    /// assert_eq!(Cultures::ViVn.language(), "Vietnamese");
     /// ```
     pub fn language(&self) -> &'static str {
         match *self {
@@ -816,6 +1447,7 @@ impl Cultures {
             Cultures::HeIl => "Hebrew",
             Cultures::HiIn => "Hindi",
             Cultures::HuHu => "Hungarian",
+            Cultures::IdId => "Indonesian",
             Cultures::ItIt => "Italian",
             Cultures::JaJp => "Japanese",
             Cultures::KkKz => "Kazakh",
@@ -839,6 +1471,144 @@ impl Cultures {
             Cultures::ZhTw => "Traditional Chinese",
             Cultures::TrTr => "Turkish",
             Cultures::UkUa => "Ukrainian",
+            Cultures::ViVn => "Vietnamese",
+        }
+    }
+
+    /// Determines if the WixUI extension has localized strings for the culture.
+    ///
+    /// The strings of the WixUI dialogs for a culture without localized
+    /// strings must be provided with a localization (wxl) file, or they are
+    /// displayed in English.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use wix::Cultures;
+    ///
+    /// assert!(Cultures::FrFr.is_wixui_localized());
+    /// assert!(!Cultures::IdId.is_wixui_localized());
+    /// assert!(!Cultures::ViVn.is_wixui_localized());
+    /// ```
+    pub fn is_wixui_localized(&self) -> bool {
+        !matches!(*self, Cultures::IdId | Cultures::ViVn)
+    }
+
+    /// The Windows locale identifier (LCID) of the culture.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use wix::Cultures;
+    ///
+    /// assert_eq!(Cultures::EnUs.lcid(), 1033);
+    /// assert_eq!(Cultures::FrFr.lcid(), 1036);
+    /// assert_eq!(Cultures::ZhCn.lcid(), 2052);
+    /// ```
+    pub fn lcid(&self) -> u32 {
+        match *self {
+            Cultures::ArSa => 1025,
+            Cultures::BgBg => 1026,
+            Cultures::CaEs => 1027,
+            Cultures::HrHr => 1050,
+            Cultures::CsCz => 1029,
+            Cultures::DaDk => 1030,
+            Cultures::NlNl => 1043,
+            Cultures::EnUs => 1033,
+            Cultures::EtEe => 1061,
+            Cultures::FiFi => 1035,
+            Cultures::FrFr => 1036,
+            Cultures::DeDe => 1031,
+            Cultures::ElGr => 1032,
+            Cultures::HeIl => 1037,
+            Cultures::HiIn => 1081,
+            Cultures::HuHu => 1038,
+            Cultures::IdId => 1057,
+            Cultures::ItIt => 1040,
+            Cultures::JaJp => 1041,
+            Cultures::KkKz => 1087,
+            Cultures::KoKr => 1042,
+            Cultures::LvLv => 1062,
+            Cultures::LtLt => 1063,
+            Cultures::NbNo => 1044,
+            Cultures::PlPl => 1045,
+            Cultures::PtBr => 1046,
+            Cultures::PtPt => 2070,
+            Cultures::RoRo => 1048,
+            Cultures::RuRu => 1049,
+            Cultures::SrLatnCs => 2074,
+            Cultures::ZhCn => 2052,
+            Cultures::SkSk => 1051,
+            Cultures::SlSi => 1060,
+            Cultures::EsEs => 3082,
+            Cultures::SvSe => 1053,
+            Cultures::ThTh => 1054,
+            Cultures::ZhHk => 3076,
+            Cultures::ZhTw => 1028,
+            Cultures::TrTr => 1055,
+            Cultures::UkUa => 1058,
+            Cultures::ViVn => 1066,
+        }
+    }
+
+    /// Gets the culture for a Windows locale identifier (LCID).
+    ///
+    /// Both the modern (3082) and traditional (1034) sort LCIDs are accepted
+    /// for Spanish, Spain. An error occurs if the LCID does not have a
+    /// corresponding culture.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use wix::Cultures;
+    ///
+    /// assert_eq!(Cultures::from_lcid(1036).unwrap(), Cultures::FrFr);
+    /// assert!(Cultures::from_lcid(9999).is_err());
+    /// ```
+    pub fn from_lcid(lcid: u32) -> Result<Self> {
+        match lcid {
+            1025 => Ok(Cultures::ArSa),
+            1026 => Ok(Cultures::BgBg),
+            1027 => Ok(Cultures::CaEs),
+            1050 => Ok(Cultures::HrHr),
+            1029 => Ok(Cultures::CsCz),
+            1030 => Ok(Cultures::DaDk),
+            1043 => Ok(Cultures::NlNl),
+            1033 => Ok(Cultures::EnUs),
+            1061 => Ok(Cultures::EtEe),
+            1035 => Ok(Cultures::FiFi),
+            1036 => Ok(Cultures::FrFr),
+            1031 => Ok(Cultures::DeDe),
+            1032 => Ok(Cultures::ElGr),
+            1037 => Ok(Cultures::HeIl),
+            1081 => Ok(Cultures::HiIn),
+            1038 => Ok(Cultures::HuHu),
+            1057 => Ok(Cultures::IdId),
+            1040 => Ok(Cultures::ItIt),
+            1041 => Ok(Cultures::JaJp),
+            1087 => Ok(Cultures::KkKz),
+            1042 => Ok(Cultures::KoKr),
+            1062 => Ok(Cultures::LvLv),
+            1063 => Ok(Cultures::LtLt),
+            1044 => Ok(Cultures::NbNo),
+            1045 => Ok(Cultures::PlPl),
+            1046 => Ok(Cultures::PtBr),
+            2070 => Ok(Cultures::PtPt),
+            1048 => Ok(Cultures::RoRo),
+            1049 => Ok(Cultures::RuRu),
+            2074 => Ok(Cultures::SrLatnCs),
+            2052 => Ok(Cultures::ZhCn),
+            1051 => Ok(Cultures::SkSk),
+            1060 => Ok(Cultures::SlSi),
+            3082 | 1034 => Ok(Cultures::EsEs),
+            1053 => Ok(Cultures::SvSe),
+            1054 => Ok(Cultures::ThTh),
+            3076 => Ok(Cultures::ZhHk),
+            1028 => Ok(Cultures::ZhTw),
+            1055 => Ok(Cultures::TrTr),
+            1058 => Ok(Cultures::UkUa),
+            1066 => Ok(Cultures::ViVn),
+            l => Err(Error::Generic(format!("Unknown '{l}' culture LCID"))),
         }
     }
 
@@ -865,6 +1635,7 @@ impl Cultures {
     /// assert_eq!(Cultures::HeIl.location(), "Israel");
     /// assert_eq!(Cultures::HiIn.location(), "India");
     /// assert_eq!(Cultures::HuHu.location(), "Hungary");
+    /// assert_eq!(Cultures::IdId.location(), "Indonesia");
     /// assert_eq!(Cultures::ItIt.location(), "Italy");
     /// assert_eq!(Cultures::JaJp.location(), "Japan");
     /// assert_eq!(Cultures::KkKz.location(), "Kazakhstan");
@@ -888,6 +1659,7 @@ impl Cultures {
     /// assert_eq!(Cultures::ZhTw.location(), "Taiwan");
     /// assert_eq!(Cultures::TrTr.location(), "Turkey");
     /// assert_eq!(Cultures::UkUa.location(), "Ukraine");
+    /// assert_eq!(Cultures::ViVn.location(), "Vietnam");
     /// ```
     pub fn location(&self) -> &'static str {
         match *self {
@@ -907,6 +1679,7 @@ impl Cultures {
             Cultures::HeIl => "Israel",
             Cultures::HiIn => "India",
             Cultures::HuHu => "Hungary",
+            Cultures::IdId => "Indonesia",
             Cultures::ItIt => "Italy",
             Cultures::JaJp => "Japan",
             Cultures::KkKz => "Kazakhstan",
@@ -930,6 +1703,7 @@ impl Cultures {
             Cultures::ZhTw => "Taiwan",
             Cultures::TrTr => "Turkey",
             Cultures::UkUa => "Ukraine",
+            Cultures::ViVn => "Vietnam",
         }
     }
 }
@@ -953,6 +1727,7 @@ impl fmt::Display for Cultures {
             Cultures::HeIl => write!(f, "he-IL"),
             Cultures::HiIn => write!(f, "hi-IN"),
             Cultures::HuHu => write!(f, "hu-HU"),
+            Cultures::IdId => write!(f, "id-ID"),
             Cultures::ItIt => write!(f, "it-IT"),
             Cultures::JaJp => write!(f, "ja-JP"),
             Cultures::KkKz => write!(f, "kk-KZ"),
@@ -976,6 +1751,7 @@ impl fmt::Display for Cultures {
             Cultures::ZhTw => write!(f, "zh-TW"),
             Cultures::TrTr => write!(f, "tr-TR"),
             Cultures::UkUa => write!(f, "uk-UA"),
+            Cultures::ViVn => write!(f, "vi-VN"),
         }
     }
 }
@@ -984,7 +1760,14 @@ impl FromStr for Cultures {
     type Err = Error;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s.to_lowercase().trim() {
+        let s = s.trim();
+        if !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) {
+            return s
+                .parse::<u32>()
+                .map_err(|_| Error::Generic(format!("Unknown '{s}' culture LCID")))
+                .and_then(Cultures::from_lcid);
+        }
+        match s.to_lowercase().as_str() {
             "ar-sa" => Ok(Cultures::ArSa),
             "bg-bg" => Ok(Cultures::BgBg),
             "ca-es" => Ok(Cultures::CaEs),
@@ -1001,6 +1784,7 @@ impl FromStr for Cultures {
             "he-il" => Ok(Cultures::HeIl),
             "hi-in" => Ok(Cultures::HiIn),
             "hu-hu" => Ok(Cultures::HuHu),
+            "id-id" => Ok(Cultures::IdId),
             "it-it" => Ok(Cultures::ItIt),
             "ja-jp" => Ok(Cultures::JaJp),
             "kk-kz" => Ok(Cultures::KkKz),
@@ -1024,6 +1808,7 @@ impl FromStr for Cultures {
             "zh-tw" => Ok(Cultures::ZhTw),
             "tr-tr" => Ok(Cultures::TrTr),
             "uk-ua" => Ok(Cultures::UkUa),
+            "vi-vn" => Ok(Cultures::ViVn),
             e => Err(Error::Generic(format!("Unknown '{e}' culture"))),
         }
     }
@@ -1087,6 +1872,323 @@ mod tests {
         fn display_is_correct_for_slovak() {
             assert_eq!(format!("{}", Cultures::SkSk), String::from("sk-SK"));
         }
+
+        #[test]
+        fn from_str_is_correct_for_indonesian() {
+            assert_eq!(Cultures::from_str("id-ID"), Ok(Cultures::IdId));
+        }
+
+        #[test]
+        fn display_is_correct_for_vietnamese() {
+            assert_eq!(format!("{}", Cultures::ViVn), String::from("vi-VN"));
+        }
+
+        #[test]
+        fn from_str_is_correct_for_french_lcid() {
+            assert_eq!(Cultures::from_str("1036"), Ok(Cultures::FrFr));
+        }
+
+        #[test]
+        fn from_str_is_correct_for_lcids() {
+            assert_eq!(Cultures::from_str("1033"), Ok(Cultures::EnUs));
+            assert_eq!(Cultures::from_str("2052"), Ok(Cultures::ZhCn));
+            assert_eq!(Cultures::from_str(" 1049 "), Ok(Cultures::RuRu));
+            assert_eq!(Cultures::from_str("1034"), Ok(Cultures::EsEs));
+        }
+
+        #[test]
+        fn from_str_fails_for_unknown_lcid() {
+            assert!(Cultures::from_str("9999").is_err());
+            assert!(Cultures::from_str("99999999999").is_err());
+        }
+
+        #[test]
+        fn from_lcid_is_inverse_of_lcid() {
+            for culture in [
+                Cultures::ArSa,
+                Cultures::IdId,
+                Cultures::PtPt,
+                Cultures::SrLatnCs,
+                Cultures::ViVn,
+                Cultures::ZhHk,
+            ] {
+                assert_eq!(Cultures::from_lcid(culture.lcid()), Ok(culture));
+            }
+        }
+    }
+
+    mod install_scope {
+        use super::*;
+
+        #[test]
+        fn from_str_is_correct_for_per_machine() {
+            assert_eq!(
+                InstallScope::from_str("per-machine").unwrap(),
+                InstallScope::PerMachine
+            );
+            assert_eq!(
+                InstallScope::from_str("perMachine").unwrap(),
+                InstallScope::PerMachine
+            );
+        }
+
+        #[test]
+        fn from_str_is_correct_for_per_user() {
+            assert_eq!(
+                InstallScope::from_str("Per-User").unwrap(),
+                InstallScope::PerUser
+            );
+        }
+
+        #[test]
+        fn from_str_fails_for_auto() {
+            assert!(InstallScope::from_str(InstallScope::AUTO).is_err());
+        }
+
+        #[test]
+        fn display_is_correct() {
+            assert_eq!(InstallScope::PerMachine.to_string(), "perMachine");
+            assert_eq!(InstallScope::PerUser.to_string(), "perUser");
+        }
+    }
+
+    mod error {
+        use super::*;
+        use std::str::FromStr;
+
+        #[test]
+        fn codes_are_stable() {
+            assert_eq!(
+                Error::codes(),
+                &[
+                    (1, "Command"),
+                    (2, "Generic"),
+                    (3, "Io"),
+                    (4, "Manifest"),
+                    (5, "Mustache"),
+                    (6, "UUID"),
+                    (7, "Version"),
+                    (8, "XML"),
+                    (9, "XPath"),
+                    (10, "CargoMetadata"),
+                ]
+            );
+        }
+
+        #[test]
+        fn codes_match_variants() {
+            let errors = vec![
+                Error::Command("candle", 1, true),
+                Error::Generic(String::from("A generic error")),
+                Error::from(io::Error::new(ErrorKind::Other, "An I/O error")),
+                Error::Manifest("name"),
+                Error::from(uuid::Uuid::from_str("Not a GUID").unwrap_err()),
+                Error::from(semver::Version::parse("Not a version").unwrap_err()),
+                Error::from(sxd_document::parser::parse("<").unwrap_err()),
+            ];
+            for err in errors {
+                assert!(
+                    Error::codes().contains(&(err.code(), err.as_str())),
+                    "The '{}' variant does not match the codes",
+                    err.as_str()
+                );
+            }
+        }
+    }
+
+    mod package {
+        use super::*;
+
+        fn setup_workspace(toml: &str) -> TempDir {
+            let project = setup_project(toml);
+            for member in ["member0", "member1"] {
+                let member_dir = project.path().join(member);
+                fs::create_dir_all(member_dir.join("src")).unwrap();
+                fs::write(
+                    member_dir.join("Cargo.toml"),
+                    format!(
+                        r#"[package]
+                        name = "{member}"
+                        version = "0.1.0"
+                        authors = ["First Last <first.last@example.com>"]
+                        "#
+                    ),
+                )
+                .unwrap();
+                fs::write(member_dir.join("src").join("main.rs"), "fn main() {}").unwrap();
+            }
+            project
+        }
+
+        #[test]
+        fn workspace_without_package_fails() {
+            let project = setup_workspace(
+                r#"[workspace]
+                members = ["member0", "member1"]
+                "#,
+            );
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            assert!(crate::package(&manifest, None).is_err());
+        }
+
+        #[test]
+        fn workspace_with_package_works() {
+            let project = setup_workspace(
+                r#"[workspace]
+                members = ["member0", "member1"]
+                "#,
+            );
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, Some("member1")).unwrap();
+            assert_eq!(package.name, "member1");
+        }
+
+        #[test]
+        fn member_containing_works() {
+            let project = setup_workspace(
+                r#"[workspace]
+                members = ["member0", "member1"]
+                "#,
+            );
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let root = manifest.workspace_root.as_std_path();
+            let member = member_containing(&manifest, &root.join("member1").join("src"));
+            assert_eq!(member.map(|id| manifest[id].name.as_str()), Some("member1"));
+            let member = member_containing(&manifest, &root.join("member0"));
+            assert_eq!(member.map(|id| manifest[id].name.as_str()), Some("member0"));
+            assert!(member_containing(&manifest, root).is_none());
+        }
+
+        #[test]
+        fn member_containing_with_root_package_works() {
+            let project = setup_workspace(
+                r#"[package]
+                name = "root"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [workspace]
+                members = ["member0", "member1"]
+                "#,
+            );
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let root = manifest.workspace_root.as_std_path();
+            let member = member_containing(&manifest, &root.join("member1"));
+            assert_eq!(member.map(|id| manifest[id].name.as_str()), Some("member1"));
+            let member = member_containing(&manifest, &root.join("src"));
+            assert_eq!(member.map(|id| manifest[id].name.as_str()), Some("root"));
+        }
+
+        #[test]
+        fn workspace_with_metadata_works() {
+            let project = setup_workspace(
+                r#"[workspace]
+                members = ["member0", "member1"]
+
+                [workspace.metadata.wix]
+                name = "Suite"
+                version = "2.1.0"
+                "#,
+            );
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+            assert_eq!(package.name, "Suite");
+            assert_eq!(package.version, semver::Version::new(2, 1, 0));
+            assert_eq!(
+                package.authors,
+                vec![String::from("First Last <first.last@example.com>")]
+            );
+            assert_eq!(
+                package
+                    .targets
+                    .iter()
+                    .map(|t| t.name.as_str())
+                    .collect::<Vec<&str>>(),
+                vec!["member0", "member1"]
+            );
+            assert_eq!(
+                package.manifest_path.into_std_path_buf(),
+                manifest
+                    .workspace_root
+                    .join("Cargo.toml")
+                    .into_std_path_buf()
+            );
+            assert_eq!(package.metadata["wix"]["name"], "Suite");
+        }
+
+        #[test]
+        fn workspace_with_metadata_without_version_fails() {
+            let project = setup_workspace(
+                r#"[workspace]
+                members = ["member0", "member1"]
+
+                [workspace.metadata.wix]
+                name = "Suite"
+                "#,
+            );
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            assert!(crate::package(&manifest, None).is_err());
+        }
+    }
+
+    mod cargo_features {
+        use super::*;
+
+        const FEATURES_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+
+            [features]
+            default = ["cli"]
+            cli = ["color-output"]
+            color-output = []
+            gui = []
+            "#;
+
+        fn features_package() -> (TempDir, Package) {
+            let project = setup_project(FEATURES_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+            (project, package)
+        }
+
+        #[test]
+        fn cargo_feature_variable_works() {
+            assert_eq!(cargo_feature_variable("cli"), "CargoFeature_cli");
+            assert_eq!(
+                cargo_feature_variable("color-output"),
+                "CargoFeature_color_output"
+            );
+        }
+
+        #[test]
+        fn enabled_features_with_defaults_works() {
+            let (_project, package) = features_package();
+            assert_eq!(
+                enabled_features(&package, &[], true),
+                vec!["cli", "color-output", "default"]
+            );
+        }
+
+        #[test]
+        fn enabled_features_without_defaults_works() {
+            let (_project, package) = features_package();
+            assert!(enabled_features(&package, &[], false).is_empty());
+            assert_eq!(
+                enabled_features(&package, &[String::from("gui")], false),
+                vec!["gui"]
+            );
+        }
+
+        #[test]
+        fn enabled_features_ignores_unknown_features() {
+            let (_project, package) = features_package();
+            assert_eq!(
+                enabled_features(&package, &[String::from("serde/derive")], false),
+                Vec::<String>::new()
+            );
+        }
     }
 
     mod wix_arch {
@@ -1192,4 +2294,45 @@ mod tests {
             assert_eq!(arch, WixArch::Arm);
         }
     }
+
+    mod wxs_source {
+        use super::*;
+
+        #[test]
+        fn read_wxs_source_with_utf16_works() {
+            let temp_dir = TempDir::new().unwrap();
+            let source = temp_dir.path().join("main.wxs");
+            let mut content = vec![0xFF, 0xFE];
+            content.extend("<Wix/>".encode_utf16().flat_map(u16::to_le_bytes));
+            fs::write(&source, content).unwrap();
+            assert_eq!(read_wxs_source(&source).unwrap(), "<Wix/>");
+        }
+
+        #[test]
+        fn read_wxs_source_with_declared_encoding_works() {
+            let temp_dir = TempDir::new().unwrap();
+            let source = temp_dir.path().join("main.wxs");
+            let mut content = b"<?xml version='1.0' encoding='windows-1252'?><Wix Name='".to_vec();
+            content.extend([0x45, 0xE9, 0x27, 0x2F, 0x3E]);
+            fs::write(&source, content).unwrap();
+            assert_eq!(
+                read_wxs_source(&source).unwrap(),
+                "<?xml version='1.0' encoding='windows-1252'?><Wix Name='E\u{e9}'/>"
+            );
+        }
+
+        #[test]
+        fn declared_encoding_works() {
+            assert_eq!(
+                declared_encoding(b"<?xml version=\"1.0\" encoding=\"utf-8\"?>"),
+                Some(encoding_rs::UTF_8)
+            );
+            assert_eq!(
+                declared_encoding(b"<?xml version='1.0' encoding = 'ISO-8859-1' ?>"),
+                Some(encoding_rs::WINDOWS_1252)
+            );
+            assert_eq!(declared_encoding(b"<?xml version='1.0'?>"), None);
+            assert_eq!(declared_encoding(b"<Wix/>"), None);
+        }
+    }
 }
@@ -170,8 +170,14 @@ impl Licenses {
         // Next try Cargo's license field
         if let Some(name) = package.license.clone() {
             trace!("Cargo.toml license is specified");
-            // If there's a template for this license, generate it
-            if let Ok(generate) = Template::from_str(&name) {
+            // If there's a template for this license, or the first license of
+            // an SPDX expression, generate it
+            let generate = Template::from_str(&name).ok().or_else(|| {
+                let template = expression_template(&name)?;
+                warn!("{}", expression_warning(&name, template));
+                Some(template)
+            });
+            if let Some(generate) = generate {
                 trace!("Found a matching template, generating that");
                 let file_name = format!("{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}");
                 let dest_file = dest_dir.join(file_name);
@@ -261,7 +267,8 @@ impl Licenses {
             "Could not find your project's EULA. The license agreement dialog will be excluded \
 from the installer. You can add one by either:
 
-* Setting 'package.license' to a recognized value (MIT, Apache-2.0, or GPL-3.0)
+* Setting 'package.license' to a recognized value (MIT, Apache-2.0, or GPL-3.0), or an \
+SPDX expression with one of them
 * Setting 'package.license-file', 'package.metadata.wix.license', or 'package.metadata.wix.eula' \
 to point to an RTF file
 * Passing an RTF file with --license or --eula to the cargo-wix CLI
@@ -272,3 +279,59 @@ To supress this warning, set 'package.metadata.wix.eula = false'"
         Ok(None)
     }
 }
+
+/// Finds the first license with a template in an SPDX license expression, such
+/// as `MIT OR Apache-2.0`, from the `license` field of a package's manifest
+/// (Cargo.toml).
+///
+/// The expression is split on the `OR` and `AND` operators, parentheses, and
+/// the `/` separator of older manifests, and the licenses are checked in the
+/// order they appear in the expression. A license with an exception, such as
+/// `Apache-2.0 WITH LLVM-exception`, is skipped because the template does not
+/// include the exception.
+fn expression_template(expression: &str) -> Option<Template> {
+    let expression = expression.replace(['(', ')', '/'], " ");
+    let mut licenses: Vec<(&str, bool)> = Vec::new();
+    let mut tokens = expression.split_whitespace();
+    while let Some(token) = tokens.next() {
+        if token.eq_ignore_ascii_case("OR") || token.eq_ignore_ascii_case("AND") {
+            continue;
+        } else if token.eq_ignore_ascii_case("WITH") {
+            tokens.next();
+            if let Some(license) = licenses.last_mut() {
+                license.1 = true;
+            }
+        } else {
+            licenses.push((token, false));
+        }
+    }
+    licenses
+        .into_iter()
+        .filter(|(_, exception)| !exception)
+        .filter_map(|(id, _)| Template::from_str(id).ok())
+        .find(|t| *t != Template::Wxs)
+}
+
+/// Gets the warning about the license that is selected from an SPDX license
+/// expression for the EULA.
+fn expression_warning(expression: &str, template: Template) -> String {
+    let mut warning = format!(
+        "The '{expression}' license expression has more than one license, so the EULA is \
+         generated from the '{template}' license, which is the first license in the \
+         expression with a template."
+    );
+    if expression
+        .split_whitespace()
+        .any(|t| t.eq_ignore_ascii_case("AND"))
+    {
+        warning.push_str(
+            " All of the licenses in the expression apply, so the other licenses might \
+             need to be added to the EULA manually.",
+        );
+    }
+    warning.push_str(
+        " Set the 'license' field in the 'package.metadata.wix' section to the path of an \
+         RTF file to use a different license.",
+    );
+    warning
+}
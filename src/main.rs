@@ -35,6 +35,7 @@
 //!   - [Multiple WiX Sources](#multiple-wix-sources)
 //!   - [Bundles](#bundles)
 //! - [Configuration](#configuration)
+//! - [Exit Codes](#exit-codes)
 //! - [Flags and Options](#flags-and-options)
 //!
 //! ## Quick Start
@@ -351,13 +352,50 @@
 //! environment variable is used by the `cargo wix` subcommand with
 //! the [`std::process::Command`] module to create installers.
 //!
+//! The _info_ subcommand, i.e. `cargo wix info`, prints which WiX Toolset is
+//! installed and will be used: the paths to the compiler, linker, and `wix`
+//! (v4 or later) applications, their versions, the value of the `WIX` system
+//! environment variable, and the path to the `signtool` application. Unlike the
+//! other subcommands, applications that cannot be found are reported instead of
+//! causing an error. Including the output when reporting a bug helps with
+//! reproducing it, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix info
+//! Toolset:  legacy (v3)
+//! Compiler: C:\Program Files (x86)\WiX Toolset v3.11\bin\candle.exe
+//! Linker:   C:\Program Files (x86)\WiX Toolset v3.11\bin\light.exe
+//! Version:  3.11.2.4516
+//! wix:      not found
+//! wix ver:  not found
+//! WIX:      C:\Program Files (x86)\WiX Toolset v3.11\
+//! SignTool: not found
+//! ```
+//!
 //! ### Signing
 //!
 //! The Windows SDK provides a signer (`signtool`) application for signing
 //! installers. The application is installed in the `bin` folder of the Windows
 //! SDK installation. The location of the `bin` folder varies depending on the
 //! version. It is recommended to use the Developer Prompt to ensure the
-//! `signtool` application is available. Signing an installer is optional.
+//! `signtool` application is available. Alternatively, the `SIGNTOOL_PATH`
+//! system environment variable can be set to the folder containing the
+//! `signtool` application to use a specific version of the Windows SDK without
+//! modifying the `PATH` system environment variable. Signing an installer is
+//! optional.
+//!
+//! The signature of an existing installer, including an installer that was not
+//! created with this subcommand, can be checked with the `cargo wix verify`
+//! subcommand without signing it. The certificate chain and the timestamp of
+//! the signature are displayed, and the subcommand fails if the signature is
+//! not valid, which is useful as a gate after a release, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix verify target\wix\Example-0.1.0-x86_64.msi
+//! ```
+//!
+//! The most recently modified installer (msi) in the `target\wix` folder is
+//! verified if a path is not given.
 //!
 //! ### Templates
 //!
@@ -411,6 +449,11 @@
 //! `wix` folder as part of the `cargo wix init` subcommand. This generated RTF
 //! file will be used as a sidecar file and for the End User License Agreement
 //! (EULA) that is displayed in the license agreement dialog of the installer.
+//! If the `license` field is an [SPDX expression] with multiple licenses, such
+//! as `MIT OR Apache-2.0`, then the template for the first supported license in
+//! the expression is used with a warning, skipping any license with an
+//! exception, e.g. `Apache-2.0 WITH LLVM-exception`, because the templates do
+//! not include the exceptions.
 //! If the `license-file` field is used and it contains a path to a file with
 //! the `.rtf` extension, then this file will be used as a sidecar file and for
 //! the EULA. If neither of these fields exist or contain valid values, then no
@@ -456,10 +499,58 @@
 //! Note, this variable is deprecated and will eventually be removed because it is
 //! ultimately redundant to the `$(sys.BUILDARCH)` variable that is already provided
 //! by the WiX Toolset compiler. Existing projects should replace usage of
-//! `$(var.Platform)` with `$(sys.BUILDARCH)`. No action is needed for new projects.
+//! `$(var.Platform)` with `$(sys.BUILDARCH)`, which the `cargo wix check --fix`
+//! command does automatically. No action is needed for new projects.
 //! - `Profile` = (Deprecated) See `CargoProfile`.
 //! - `Version` = The version for the installer. The default is the
 //! `Major.Minor.Fix` semantic versioning number of the Rust package.
+//! - `BundleUpgradeCode` = The upgrade code for a bundle-based installer from
+//! the `--bundle-upgrade-guid` option or the `bundle-upgrade-guid` field in the
+//! `[package.metadata.wix]` section. This variable is only defined if a value is
+//! specified.
+//! - `BundleVersion` = The version for a bundle-based installer from the
+//! `--bundle-version` option or the `bundle-version` field in the
+//! `[package.metadata.wix]` section. This variable is only defined if a value is
+//! specified.
+//! - `Changelog` = The first entry of the section of the package's changelog
+//! with a heading that matches the version of the installer, truncated to a
+//! single line of at most 255 characters. The changelog is from the
+//! `--changelog` option or the `changelog` field in the `[package.metadata.wix]`
+//! section. The template uses this variable for the `ARPCOMMENTS` property.
+//! This variable is only defined if a changelog is used and a matching section
+//! is found.
+//! - `ComponentGroupRefs` = The IDs of the component groups from the
+//! `--component-group-ref` option or the `component-group-ref` field in the
+//! `[package.metadata.wix]` section, separated by semicolons. The template adds
+//! a `ComponentGroupRef` tag for each ID to the main feature. This variable is
+//! only defined if a value is specified.
+//! - `ReinstallMode` = The value for the `REINSTALLMODE` property from the
+//! `--reinstall-mode` option or the `reinstall-mode` field in the
+//! `[package.metadata.wix]` section. The template uses this variable to set the
+//! property. This variable is only defined if a value is specified.
+//! - `GitHash` = The short hash of the git commit the installer is built from.
+//! This variable is only defined if the `--include-build-metadata` flag is used,
+//! or the `include-build-metadata` field in the `[package.metadata.wix]` section
+//! is `true`, and the package is in a git repository.
+//! - `BuildTime` = The time the installer is built in UTC and the ISO 8601
+//! format, such as `2024-01-31T12:00:00Z`. This variable is only defined if the
+//! `--include-build-metadata` flag is used, or the `include-build-metadata`
+//! field in the `[package.metadata.wix]` section is `true`. The template uses
+//! this variable, and the `GitHash` variable, for the `ARPCOMMENTS` property if
+//! the `Changelog` variable is not defined.
+//! - `ProductIcon` = The path to the icon file (.ico) extracted from the
+//! resources of the main binary, i.e. the binary from the `default-run` field
+//! of the package's manifest (Cargo.toml), the binary with the same name as
+//! the package, or the first binary. This variable is only defined if a WiX Source
+//! file (wxs) uses it, such as when the `--product-icon from-binary` option is
+//! used with the _init_ or _print_ subcommands. The icon file is written to the
+//! `target\wix` folder.
+//! - `ArpSize` = The estimated size, in kilobytes, of the installed files from
+//! the `--arp-size` option, or else the total size of the files referenced by
+//! the `Source` attribute of each `File` element in the WXS files. The template
+//! uses this variable for the `ARPSIZE` property, which is the size displayed in
+//! the Add/Remove Programs control panel. This variable is only defined if the
+//! size is known.
 //!
 //! Additional, user-defined variables for custom WXS files can be passed to the
 //! WiX Toolset compiler (candle.exe) using the cargo-wix subcommand
@@ -484,6 +575,15 @@
 //! MSI package. See the [Bundles](#bundles) section for more information about
 //! creating and managing bundles with the cargo-wix subcommand.
 //!
+//! The extensions installed for the WiX Toolset v4 or later can be listed with
+//! the _extensions_ subcommand, i.e. `cargo wix extensions`, which runs the
+//! `wix extension list` command and prints the ID and version of each
+//! extension. Use the `--global` flag to list the extensions in the global
+//! cache. This is useful to verify the environment before building. The legacy
+//! WiX Toolset (v3), which is used to create installers, includes its
+//! extensions with its installation, so an error occurs if only the legacy
+//! WiX Toolset is installed.
+//!
 //! ### Multiple WiX Sources
 //!
 //! The cargo-wix subcommand supports including multiple WXS files when creating
@@ -517,15 +617,48 @@
 //! ```
 //!
 //! Alternatively, multiple WXS files can also be included when creating an
-//! installer by including the relative or absolute paths to the WXS files as
-//! arguments to the subcommand, but any WXS files in the default, `wix`,
-//! sub-folder are ignored and would have to be explicitly included. For
-//! example,
+//! installer with the relative or absolute paths to the WXS files using the
+//! `-I,--include` option. Any WXS files in the default, `wix`, sub-folder are
+//! still included unless the `--no-default-wix-folder` flag is used, in which
+//! case only the explicitly included WXS files are used. For example,
+//!
+//! ```dos
+//! C:\Path\To\Project> cargo wix --no-default-wix-folder -I path\to\first\wxs\file\one.wxs -I path\to\second\wxs\file\two.wxs
+//! ```
+//!
+//! The WXS files can also be passed as positional arguments after the path to
+//! the package's manifest (Cargo.toml). The positional WXS files are the only
+//! WXS files used to create the installer, so the default `wix` folder, the
+//! `-I,--include` option, and the `include` field in the
+//! `[package.metadata.wix]` section are all ignored. The path to the manifest
+//! is required before the WXS files, for example,
+//!
+//! ```dos
+//! C:\Path\To\Project> cargo wix Cargo.toml path\to\first\wxs\file\one.wxs path\to\second\wxs\file\two.wxs
+//! ```
+//!
+//! The WXS files of a project can be checked for common mistakes, without
+//! compiling them, with the _check_ subcommand, i.e. `cargo wix check`. The
+//! files in the `wix` folder and the `include` field in the
+//! `[package.metadata.wix]` section are scanned for references to the
+//! deprecated `$(var.Platform)` and `$(var.Profile)` variables, a `Product` or
+//! `Bundle` element without an `UpgradeCode` attribute, hard-coded absolute
+//! paths, such as `C:\Users\...`, and GUIDs that look like placeholders, such
+//! as `PUT-GUID-HERE` or the nil GUID. Each finding is printed with the path to
+//! the file and the line number, for example:
 //!
 //! ```dos
-//! C:\Path\To\Project> cargo wix path\to\first\wxs\file\one.wxs path\to\second\wxs\file\two.wxs
+//! C:\Path\to\Project> cargo wix check
+//! C:\Path\to\Project\wix\main.wxs:74: The '$(var.Platform)' variable is deprecated. Replace it with the '$(sys.BUILDARCH)' variable.
+//! Error[2] (Generic): Found 1 problem(s) in 1 WiX Source (wxs) file(s)
 //! ```
 //!
+//! The subcommand only reads the files and fails if anything is found, so it
+//! can be used in continuous integration to catch mistakes before building an
+//! installer. Use the `-I,--include` option to check specific files instead.
+//! Use the `--fix` flag to rewrite the deprecated variables in the files to
+//! their replacements, after a backup of each rewritten file is saved.
+//!
 //! ### Bundles
 //!
 //! It is possible to create [bundle-based installers] with the WiX Toolset. The
@@ -647,15 +780,41 @@
 //! ```
 //!
 //! A GUID will need to be manually generated for the `UpgradeCode` attribute of
-//! the `Bundle` tag and used to replace the `[Your GUID Here]` value. Now, the
-//! bundle can be created but first both the client and server MSI packages must
-//! be created. Thus, creating the bundle is a multi-step, or multi-command,
-//! process:
+//! the `Bundle` tag and used to replace the `[Your GUID Here]` value.
+//! Alternatively, the `UpgradeCode` and `Version` attributes can be set to
+//! `$(var.BundleUpgradeCode)` and `$(var.BundleVersion)`, respectively, and the
+//! values passed with the `--bundle-upgrade-guid` and `--bundle-version`
+//! options, or the `bundle-upgrade-guid` and `bundle-version` fields in the
+//! `[package.metadata.wix]` section.
+//!
+//! The virtual manifest of the workspace does not have a `[package]` section,
+//! so the name and version of the bundle, along with any other configuration
+//! for the bundle, are added to a `[workspace.metadata.wix]` section in the
+//! virtual manifest instead:
+//!
+//! ```toml
+//! [workspace]
+//! members = ["client", "server"]
+//!
+//! [workspace.metadata.wix]
+//! name = "Bundle"
+//! version = "1.0.0"
+//! bundle-upgrade-guid = "C4C1A1E4-5A36-4C0B-9C8B-3B5B5E3A2D0F"
+//! ```
+//!
+//! The `[workspace.metadata.wix]` section is used when the cargo-wix
+//! subcommand is executed for the workspace root without the `-p,--package`
+//! option. The `name` field defaults to the name of the workspace root folder,
+//! but the `version` field is required. All of the other fields are the same as
+//! the `[package.metadata.wix]` section, see the
+//! [configuration](#configuration) section. Now, the bundle can be created but
+//! first both the client and server MSI packages must be created. Thus,
+//! creating the bundle is a multi-step, or multi-command, process:
 //!
 //! ```dos
 //! C:\Path\to\Workspace> cargo wix client\Cargo.toml
 //! C:\Path\to\Workspace> cargo wix server\Cargo.toml
-//! C:\Path\to\Workspace> cargo wix --name Bundle --install-version 1.0.0
+//! C:\Path\to\Workspace> cargo wix
 //! ```
 //!
 //! The following project layout should exist:
@@ -697,11 +856,7 @@
 //! in the member's `target\wix` folders. This will fail if the various `cargo
 //! wix` commands are _not_ executed from the workspace root.
 //!
-//! The `name` and `install-version` options can be moved into a
-//! [configuration](#configuration) section for the subcommand within the
-//! virtual manifest of the workspace, i.e. the Cargo.toml file with the
-//! `[workspace]` section to avoid having to retype them each time a bundle is
-//! created, but all three `cargo wix` commands must be issued each time.
+//! All three `cargo wix` commands must be issued each time a bundle is created.
 //!
 //! While the above steps will create a bundle installer for the workspace-based
 //! Rust project with a default, placeholder EULA, it is very manual and
@@ -729,24 +884,68 @@
 //!
 //! ```toml
 //! [package.metadata.wix]
+//! allow-unpublished = false
+//! append-args = false
+//! arp-size = 2048
+//! author-separator = "; "
 //! banner = "path\to\banner.png"
+//! build-command = "build.cmd"
+//! bundle-upgrade-guid = "C4C1A1E4-5A36-4C0B-9C8B-3B5B5E3A2D0F"
+//! bundle-version = "1.0.0"
+//! changelog = "path\to\CHANGELOG.md"
 //! compiler-args = ["-nologo", "-wn"]
+//! component-group-ref = ["HarvestedFiles"]
 //! culture = "Fr-Fr"
 //! dbg-build = false
 //! dbg-name = false
+//! default-culture = "fr-FR"
+//! deterministic-component-guids = false
 //! dialog = "path\to\dialog.png"
+//! dialog-set = "feature-tree"
+//! emit-relative-manifest-path = false
 //! eula = "path\to\eula.rtf"
+//! feature-map = ["gui=Graphical Interface"]
+//! features = ["gui"]
+//! format = "msi"
+//! guid-from = "com.example.product"
+//! include-build-metadata = false
 //! include = ["Path\to\WIX\Source\File\One.wxs", "Path\to\WIX\Source\File\Two.wxs"]
+//! incremental = false
+//! install-scope = "auto"
 //! license = "path\to\license.txt"
 //! linker-args = ["-nologo"]
 //! locale = "Path\to\WIX\Localization\File.wxl"
+//! keep-author-emails = false
+//! manufacturer-from = "all"
 //! name = "example"
+//! no-arch-in-name = false
 //! no-build = false
+//! no-default-features = false
+//! no-default-wix-folder = false
+//! no-overwrite-increment = false
+//! no-modify = false
+//! no-path = false
+//! no-repair = false
+//! no-upgrade = false
 //! output = "Path\and\file\name\for\installer.msi"
+//! output-layout = "flat"
 //! path-guid = "BFD25009-65A4-4D1E-97F1-0030465D90D6"
 //! product-icon = "path\to\product_icon.ico"
+//! product-language = "1033"
+//! properties = ["INSTALLDESKTOPSHORTCUT=1"]
+//! quiet-tools = false
+//! reinstall-mode = "amus"
+//! retain-symbols = "no"
+//! run-after-install = '"[APPLICATIONFOLDER]bin\setup.exe" --register'
+//! skip-eula-dialog = false
+//! skip-license-sidecar = false
+//! strip = false
+//! uninstall-shortcut = false
 //! upgrade-guid = "B36177BE-EA4D-44FB-B05C-EDDABDAA95CA"
+//! verify-manifest = false
 //! version = "2.1.0"
+//! wxs-template-dir = "path\to\templates"
+//! wxs-variable-check = false
 //! ```
 //!
 //! See the documentation for each CLI option for more information about each
@@ -764,20 +963,90 @@
 //! to the default _create_ subcommand using multiple `-I,--include` options or
 //! including multiple WXS files in the default, `wix`, project source location.
 //!
-//! The only CLI option, or argument, that is not supported in the
-//! `[package.metadata.wix]` section is the `<INPUT>` argument for the default
-//! _create_ command, which specifies a relative or absolute path to a package's
-//! manifest file. The assumption is that the package manifest (Cargo.toml) to
+//! The paths in the `[package.metadata.wix]` section, such as the `banner`,
+//! `changelog`, `dialog`, `eula`, `include`, `license`, `locale`, `output`, and
+//! `product-icon` fields, are relative to the folder containing the package's
+//! manifest (Cargo.toml), not the current working directory. This way, the same
+//! installer is created when the subcommands are run from a sub-folder of the
+//! package or from a workspace root with the `-p,--package` option. Paths
+//! passed to the default _create_ subcommand at the command line are relative
+//! to the current working directory.
+//!
+//! The only CLI arguments that are not supported in the
+//! `[package.metadata.wix]` section are the `<INPUT>` and `<WXS>` arguments for
+//! the default _create_ command. The `<INPUT>` argument specifies a relative or
+//! absolute path to a package's manifest file, and the `<WXS>` arguments are
+//! the WiX Source (wxs) files, see the [Multiple WiX Sources](#multiple-wix-sources)
+//! section. The assumption is that the package manifest (Cargo.toml) to
 //! be used for the default _create_ subcommand is the same manifest that
 //! contains the `[package.metadata.wix]` section.
 //!
+//! The supported fields, the type of each value, the subcommands that use
+//! each field, and the CLI option that overrides each field can be listed with
+//! the `--print-config-schema` flag:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --print-config-schema
+//! FIELD                          TYPE         SUBCOMMANDS  OPTION
+//! after-install-launch           bool         init, print  --after-install-launch
+//! banner                         string       init, print  -b,--banner
+//! build-command                  string       create       --build-command
+//! ...
+//! ```
+//!
+//! A warning is displayed when an installer is created if the
+//! `[package.metadata.wix]` section contains a field that is not supported or
+//! a value with the wrong type, which usually indicates a typo.
+//!
+//! The value of each supported field for a package, and where the value comes
+//! from, can be printed with the _config show_ subcommand, i.e. `cargo wix
+//! config show`. The source is `package` for a value from the
+//! `[package.metadata.wix]` section, `workspace` for a value from the
+//! `[workspace.metadata.wix]` section of a workspace without a selected
+//! package, or `default` if the field is not set and the default is used:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix config show
+//! FIELD                          SOURCE     VALUE
+//! after-install-launch           default
+//! append-args                    package    false
+//! banner                         package    img\Banner.bmp
+//! ...
+//! ```
+//!
+//! Remember, a CLI option overrides the printed value of the corresponding
+//! field. The same warnings for fields that are not supported or values with
+//! the wrong type are displayed, too.
+//!
+//! ## Exit Codes
+//!
+//! The cargo-wix binary and subcommand exits with a non-zero code if an error
+//! occurs. The code depends on the kind of error, and it is stable, i.e. the
+//! code for a kind of error is never changed and new kinds of errors are
+//! assigned new codes. The codes can be listed with the hidden
+//! `--print-exit-codes` flag:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --print-exit-codes
+//! 1       Command
+//! 2       Generic
+//! 3       Io
+//! 4       Manifest
+//! 5       Mustache
+//! 6       UUID
+//! 7       Version
+//! 8       XML
+//! 9       XPath
+//! 10      CargoMetadata
+//! ```
+//!
 //! ## Flags and Options
 //!
 //! Generally, any value that is obtained from the package's manifest
 //! (Cargo.toml) can be overridden at the command line with an appropriate
 //! option. For example, the manufacturer, which is displayed as the "Publisher"
-//! in the Add/Remove Programs (ARP) control panel is obtained from the first
-//! author listed in the `authors` field of a project's manifest, but it can be
+//! in the Add/Remove Programs (ARP) control panel is obtained from the authors
+//! listed in the `authors` field of a project's manifest, but it can be
 //! overridden using the `-m,--manufacturer` option with the `cargo wix init`
 //! subcommand.
 //!
@@ -788,6 +1057,98 @@
 //! this section is a list of all flags and options implemented for all
 //! subcommands.
 //!
+//! ### `--after-install-launch`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Adds a checkbox to the last dialog of the installer to launch the
+//! application after the installation completes. The checkbox is checked by
+//! default. The launched binary is the one from the `default-run` field of the
+//! package's manifest (Cargo.toml), the one with the same name as the package,
+//! or the first binary if none match. The `--launch-binary` option selects a
+//! different binary for packages with multiple binaries. The `WixShellExec`
+//! custom action from the WiX Toolset utility extension is used to launch the
+//! binary, which is already linked when creating the installer. The checkbox
+//! is not displayed for repairs or uninstalls. The
+//! `after-install-launch = true` field in the `[package.metadata.wix]` section
+//! can be used instead of this flag.
+//!
+//! ### `--allow-unpublished`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Silences the warning that is displayed when an installer is created for a
+//! package with `publish = false` in its manifest (Cargo.toml). Such a package
+//! is often an internal or experimental crate, so the warning is a reminder to
+//! check that the intended package is selected, which is easy to get wrong
+//! with the `-p,--package` option in a workspace. The installer is created
+//! either way. The `allow-unpublished = true` field in the
+//! `[package.metadata.wix]` section can be used instead of this flag for a
+//! package that is meant to be distributed only with an installer.
+//!
+//! ### `--append-args`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Appends the arguments from the `-C,--compiler-arg` and `-L,--linker-arg`
+//! options to the arguments from the `compiler-args` and `linker-args` fields
+//! in the `[package.metadata.wix]` section. By default, the options replace
+//! the fields, so the arguments from a field are not used at all if the
+//! option is used. With this flag, the arguments from a field are passed first
+//! and the arguments from the option are passed after them, which is useful
+//! for adding a one-off argument without repeating the arguments from the
+//! field:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --append-args -L -v
+//! ```
+//!
+//! This can also be enabled with the `append-args` field in the
+//! `[package.metadata.wix]` section.
+//!
+//! ### `--arp-size`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Sets the estimated size, in kilobytes, of the installed application that is
+//! displayed in the Add/Remove Programs control panel. By default, the size is
+//! the total size of the files referenced by the `Source` attribute of each
+//! `File` element in the WiX Source (wxs) files, such as the binaries and the
+//! license, after the `$(var.NAME)` references are replaced with the values
+//! passed to the compiler (candle.exe). The size is passed to the compiler as
+//! the `ArpSize` variable, which sets the `ARPSIZE` property in a WiX Source
+//! (wxs) file generated with the _init_ subcommand. Add the following to an
+//! existing WiX Source (wxs) file, within the `Product` tag, to use it:
+//!
+//! ```xml
+//! <?ifdef ArpSize ?>
+//! <Property Id='ARPSIZE' Value='$(var.ArpSize)'/>
+//! <?endif ?>
+//! ```
+//!
+//! The size can also be set with the `arp-size` field in the
+//! `[package.metadata.wix]` section as a whole number, e.g. `arp-size = 2048`.
+//! The option takes precedence over the field.
+//!
+//! ### `--author-separator`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Sets the separator between the authors when all of the authors from the
+//! `authors` field of the package's manifest (Cargo.toml) are used for the
+//! manufacturer, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix init --author-separator ", "
+//! ```
+//!
+//! The default is a semicolon followed by a space (`; `). This is not used if
+//! the `-m,--manufacturer` option is used or a single author is selected with
+//! the `--manufacturer-from` option, and it can also be set with the
+//! `author-separator` field in the `[package.metadata.wix]` section.
+//!
 //! ### `-b,--banner`
 //!
 //! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
@@ -797,25 +1158,55 @@
 //! the top of each dialog in the installer. The banner image dimensions should
 //! be 493 x 58 pixels, but the left-most 50% of that image is covered with text,
 //! so if you want to leave a blank background for text readability, you only want
-//! to color in the right-most ~200 pixels of that image.
+//! to color in the right-most ~200 pixels of that image. A warning is displayed if
+//! a bitmap (.bmp) image is not 24-bit, which is the bit depth the WixUI dialog
+//! library expects, because other bit depths, such as a 32-bit bitmap with an
+//! alpha channel, can render incorrectly in the installer.
+//!
+//! For localized installers, the path can be prefixed with a culture and a colon
+//! and the option used repeatedly to select a different image for each culture,
+//! for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix init -c fr-FR -b img\Banner.bmp -b fr:img\Banner_fr.bmp
+//! ```
 //!
+//! The image for the culture from the `-c,--culture` option, or the `culture`
+//! field in the `[package.metadata.wix]` section, is used. Otherwise, the image
+//! for the language of the culture, such as `fr` for `fr-CA`, is used, or else
+//! the image without a culture prefix. A path without a prefix is used for all
+//! cultures, which is the same as a single path. The `banner` field in the
+//! `[package.metadata.wix]` section is a single path.
+//!
+//! If neither this option nor the `banner` field is used, then the
+//! `wix\Banner.bmp` file is used if it exists in the package's folder. The
+//! file name is not case sensitive, so committing the branding images
+//! alongside the WiX Source (wxs) file is enough to use them.
 //!
 //! ### `-b,--bin-path`
 //!
-//! Available for the default _create_ (`cargo wix`) and _sign_ (`cargo wix
-//! sign`) subcommands.
+//! Available for the default _create_ (`cargo wix`), _extensions_ (`cargo wix
+//! extensions`), _info_ (`cargo wix info`), _sign_ (`cargo wix sign`), and
+//! _verify_ (`cargo wix verify`) subcommands.
 //!
 //! The `-b,--bin-path` option can be used to specify a path (relative or
 //! absolute) to the WiX Toolset `bin` folder. The `-b,--bin-path` option is
 //! useful if a different version of the WiX Toolset needs to be used to create
 //! the installer. The descending order of precedence is: (1) `-b,--bin-path`
-//! option then (2) `WIX` system environment variable. An error will be
-//! displayed if the compiler and/or linker cannot be found.
+//! option, (2) `--wix-root` option, then (3) `WIX` system environment variable.
+//! An error will be displayed if the compiler and/or linker cannot be found.
 //!
-//! This option is also available for the `cargo wix sign` subcommand and can be
-//! used to specify a path to the Windows SDK `bin` folder. This can be used to
+//! This option is also available for the `cargo wix sign` and `cargo wix
+//! verify` subcommands and can be used to specify a path to the Windows SDK `bin` folder. This can be used to
 //! override default `signtool` application found using the
-//! [`std::process::Command::status`] method.
+//! [`std::process::Command::status`] method. The descending order of
+//! precedence for the signer is: (1) `-b,--bin-path` option, (2)
+//! `SIGNTOOL_PATH` system environment variable, then (3) `PATH` system
+//! environment variable.
+//!
+//! For the `cargo wix extensions` subcommand, the option specifies a path to
+//! the folder containing the `wix` application of the WiX Toolset v4 or later.
+//! The default is to use the PATH system environment variable.
 //!
 //! ### `-B,--binary`
 //!
@@ -835,6 +1226,163 @@
 //! stem (file name without extension) is used as the binary name within the WXS
 //! file. A relative or absolute path is acceptable.
 //!
+//! The path can optionally be followed by a colon (`:`) and the name of a
+//! subfolder of the `bin` folder, in which case the binary is installed in that
+//! subfolder instead of the `bin` folder, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix init -B target\release\app.exe -B target\release\helper.exe:tools
+//! ```
+//!
+//! installs `app.exe` in the `bin` folder and `helper.exe` in the `bin\tools`
+//! folder. Only the `bin` folder is added to the `PATH` environment variable.
+//!
+//! The path can also be a folder, or the file name can contain the `*` and `?`
+//! wildcards, to include all of the matching executables, such as the helper
+//! executables of an application with plugins, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix init -B target\release\app.exe -B target\release\*-plugin.exe:plugins
+//! ```
+//!
+//! A folder includes all of the executables (`.exe`) in it. The matching
+//! executables are included in order of their file names, and an executable
+//! with the same file stem as a binary already included is skipped. An error
+//! occurs if nothing matches.
+//!
+//! ### `--build-command`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Runs a custom command, such as a wrapper script, in place of the `cargo
+//! build` command to build the binaries before creating the installer. This is
+//! between the default, where cargo-wix builds the binaries with Cargo, and the
+//! `--no-build` flag, where the binaries are built by another tool before
+//! cargo-wix is run. The command is run with the system shell, i.e. `cmd /C` on
+//! Windows and `sh -c` on other platforms, from the folder containing the
+//! package's manifest (Cargo.toml), and it is run once for each target. The
+//! following environment variables are set for the command:
+//!
+//! - `CARGO_TARGET_DIR` = The path to the directory for the build artifacts.
+//! - `CARGO_WIX_PROFILE` = The name of the Cargo profile, such as `release`.
+//! - `CARGO_WIX_TARGET` = The target triple, such as `x86_64-pc-windows-msvc`.
+//! - `CARGO_WIX_TARGET_BIN_DIR` = The path to the folder where the installer
+//!   expects the binaries, i.e. the `CargoTargetBinDir` variable.
+//!
+//! The installer is not created if the command exits with a non-zero code. This
+//! option cannot be used with the `--no-build` flag, and it can also be set with
+//! the `build-command` field in the `[package.metadata.wix]` section of the
+//! package's manifest.
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --build-command "build.cmd --locked"
+//! ```
+//!
+//! ### `--bundle-upgrade-guid`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Defines the `BundleUpgradeCode` variable for the WiX compiler (candle.exe)
+//! with a GUID in the hyphenated, uppercase format. This is intended for the
+//! `UpgradeCode` attribute of the `Bundle` tag in a bundle-based WiX Source
+//! (wxs) file, i.e. `UpgradeCode='$(var.BundleUpgradeCode)'`, so the GUID does
+//! not need to be manually edited into the file. Like the upgrade code for a
+//! product, the bundle's upgrade code should only be generated once and then
+//! used for all installer creations of the same bundle.
+//!
+//! ### `--bundle-version`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Defines the `BundleVersion` variable for the WiX compiler (candle.exe). This
+//! is intended for the `Version` attribute of the `Bundle` tag in a bundle-based
+//! WiX Source (wxs) file, i.e. `Version='$(var.BundleVersion)'`. The version is
+//! converted to the format required by the WiX Toolset in the same way as the
+//! `-i,--install-version` option.
+//!
+//! ### `--cert-subject`
+//!
+//! Available for the _sign_ (`cargo wix sign`) subcommand.
+//!
+//! Selects the certificate used to sign the installer by its subject name. The
+//! value is passed to the signer (signtool.exe) with the `/n` option, which
+//! selects a certificate from the Windows certificate store with a subject name
+//! that contains the value, instead of the `/a` flag, which automatically
+//! selects the best certificate. This is useful when multiple certificates are
+//! installed, such as a named code signing certificate in the certificate store
+//! of a build machine, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix sign --cert-subject "Example Corp"
+//! ```
+//!
+//! ### `--changelog`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Sets the path to a changelog. The section of the changelog with a heading,
+//! i.e. a line starting with `#`, that contains the version of the installer is
+//! extracted, such as the `## [1.2.3] - 2023-01-01` heading for the `1.2.3`
+//! version. The section ends at the next heading of the same or higher level.
+//! The first entry of the section, i.e. the first line that is not a heading
+//! without its list marker, is truncated to 255 characters and passed to the
+//! WiX compiler (candle.exe) as the `Changelog` variable, which the template
+//! uses for the `ARPCOMMENTS` property to display the most recent change in the
+//! Add/Remove Programs (ARP) control panel. Only the first entry is used
+//! because the property is displayed as a single line. If a matching section is
+//! not found, then a warning is displayed and the variable is not defined. The
+//! changelog can be encoded as UTF-8 or UTF-16 with a byte-order mark.
+//!
+//! A changelog is not used by default, so the comments of an existing installer
+//! do not change just because a `CHANGELOG.md` file exists. The path can also
+//! be set with the `changelog` field in the `[package.metadata.wix]` section,
+//! and setting the field to `true` uses the `CHANGELOG.md` file alongside the
+//! package's manifest (Cargo.toml).
+//!
+//! ### `--check-localization`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Reports the common strings of the WixUI dialogs, such as the titles,
+//! descriptions, and buttons, that are displayed in English. The WixUI
+//! extension has localized strings for most cultures, but not for all of the
+//! cultures supported by the `-c,--culture` option, such as `id-ID` and
+//! `vi-VN`. For these cultures, the localization (wxl) file from the
+//! `-l,--locale` option must provide the strings, and the strings that it does
+//! not provide are displayed in English, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --culture vi-VN --locale wix\vi-VN.wxl --check-localization
+//! ```
+//!
+//! The strings are reported with a warning before the installer is created, or
+//! with an error if the `--strict` flag is also used. Nothing is reported if the
+//! WixUI extension has localized strings for the culture.
+//!
+//! ### `--checksum-manifest`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Writes the SHA-256 checksum of each installer (msi or exe), transform (mst),
+//! and zip archive created in the run to a single file after all of the
+//! installers are created. This is useful for a release that publishes a
+//! consolidated `SHA256SUMS` file for installers created for multiple targets
+//! or cultures, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --target x86_64-pc-windows-msvc --target i686-pc-windows-msvc --output-dir dist --checksum-manifest dist\SHA256SUMS
+//! ```
+//!
+//! Each line of the file is `<sha256>  <path>`, which is the format of the
+//! `sha256sum` tool. The path of a created file is relative to the folder of
+//! the checksum file, with forward slashes, if the created file is within the
+//! folder, so the checksums can be checked with `sha256sum -c SHA256SUMS` from
+//! the folder. Otherwise, the full path is used. An installer that is skipped
+//! with the `--incremental` flag because it is up to date is included. The
+//! folder is created if it does not exist, and the file is not written for a
+//! `--dry-run` or if no files are created. With the `--keep-going` flag, the
+//! file lists the files created for the targets that succeeded.
+//!
 //! ### `-C,--compiler-arg`
 //!
 //! Available for the default _create (`cargo wix`) subcommand.
@@ -848,14 +1396,111 @@
 //! an user-defined compiler extension would require the following command
 //! `cargo wix -C -ext -C UserDefinedExtension` to yield a `candle -ext
 //! UserDefinedExtension` invocation.
+//! The arguments replace the `compiler-args` field in the
+//! `[package.metadata.wix]` section, unless the `--append-args` flag is used.
+//! The arguments are not validated, so the `--property` option should be
+//! preferred for setting the default value of a property with a preprocessor
+//! variable.
 //!
-//! ### `-c,--culture`
+//! ### `--component-group-ref`
 //!
 //! Available for the default _create_ (`cargo wix`) subcommand.
 //!
+//! Adds a component group to the main feature of the installer, so the
+//! components of the group are installed along with the binaries. This is
+//! useful for installing additional files from a WiX Source (wxs) fragment, such
+//! as a fragment generated with the WiX Toolset harvester (heat.exe), without
+//! modifying the `main.wxs` file, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> heat dir assets -cg AssetFiles -dr APPLICATIONFOLDER -gg -srd -var var.Assets -out wix\assets.wxs
+//! C:\Path\to\Project> cargo wix --component-group-ref AssetFiles -C -dAssets=assets
+//! ```
+//!
+//! This option can be used multiple times to add multiple component groups. The
+//! IDs are passed to the WiX Toolset compiler (candle.exe) as the
+//! `ComponentGroupRefs` variable, separated by semicolons. WiX Source (wxs)
+//! files generated before this option was added must be updated to use the
+//! variable, see the template from the _print_ subcommand.
+//!
+//! ### `--color`
+//!
+//! Available for all subcommands.
+//!
+//! Controls the coloring of the log statements and errors written to stderr.
+//! The value must be `auto`, `always`, or `never`. The default is `auto`, which
+//! uses color only if stderr is a terminal. Use `always` to force color, or
+//! `never` to disable color for CI logs and redirected output, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --color never > build.log 2>&1
+//! ```
+//!
+//! ### `-c,--culture`
+//!
+//! Available for the default _create_ (`cargo wix`), _init_ (`cargo wix init`),
+//! and _print_ (`cargo wix print`) subcommands.
+//!
 //! Sets the culture for localization. Use with the [`-l,--locale`] option. See
 //! the [WixUI localization documentation] for more information about acceptable
-//! culture codes. The codes are case insensitive.
+//! culture codes. The codes are case insensitive. The default is `en-US`. A
+//! numeric Windows locale identifier (LCID), such as `1036` for French, France,
+//! is also accepted and converted to the corresponding culture code.
+//!
+//! If the value is `auto`, then the culture is derived from the locale of the
+//! system. The `LC_ALL`, `LC_MESSAGES`, and `LANG` environment variables are
+//! checked first, in that order, and then the user interface culture of Windows
+//! is used. The `en-US` culture is used, with a warning, if the locale of the
+//! system is not a supported culture. This is useful for building installers in
+//! the language of the developer's machine without hard-coding the culture.
+//!
+//! For the default _create_ subcommand, a culture that is not supported by the
+//! WixUI extension falls back, with a warning, to the supported culture for the
+//! same language, such as `fr-FR` for `fr-CA` or `es-ES` for `es-MX`. Use the
+//! `--strict` flag to fail instead. An error still occurs if no supported
+//! culture exists for the language.
+//!
+//! For the default _create_ subcommand, the value can also be a
+//! comma-separated list of cultures, such as `fr-FR,de-DE,es-ES`. The installer
+//! is created for the first culture, and a language transform is generated for
+//! each of the other cultures, which is the same as using the
+//! `--generate-transforms` option for them. The `culture` field in the
+//! `[package.metadata.wix]` section can be a string with a comma-separated list
+//! or an array of cultures, so all of the cultures of a project are configured
+//! once in the package's manifest (Cargo.toml), for example:
+//!
+//! ```toml
+//! [package.metadata.wix]
+//! culture = ["fr-FR", "de-DE", "es-ES"]
+//! ```
+//!
+//! The _init_ and _print_ subcommands only use the first culture of the
+//! `culture` field.
+//!
+//! The WixUI extension does not have localized strings for the `id-ID` and
+//! `vi-VN` cultures, so the strings of the dialogs must be provided with the
+//! `-l,--locale` option. The strings that are not provided are displayed in
+//! English. Use the `--check-localization` flag to list them.
+//!
+//! For the _init_ and _print_ subcommands, the culture selects the translation
+//! of the EULA generated from the `license` field of the package's manifest
+//! (Cargo.toml). The MIT license is available in French (`fr-FR`) and German
+//! (`de-DE`). The English license is used for all other cultures and licenses.
+//! The translations are unofficial, and each one notes that the English license
+//! is the legally binding version. The `culture` field in the
+//! `[package.metadata.wix]` section is also used if the option is not
+//! specified.
+//!
+//! The `default-culture` field in the `[package.metadata.wix]` section changes
+//! the `en-US` fallback for all three subcommands, which is useful for a
+//! project whose installers are primarily in a language other than English.
+//! Unlike the `culture` field, it is only used if neither the option nor the
+//! `culture` field is specified, for example:
+//!
+//! ```toml
+//! [package.metadata.wix]
+//! default-culture = "fr-FR"
+//! ```
 //!
 //! ### `-d,--dbg-build`
 //!
@@ -890,6 +1535,38 @@
 //! installer. This can be overridden using the `-d,--description` option with
 //! the `cargo wix init` or `cargo wix sign` subcommands, respectively.
 //!
+//! For the _sign_ subcommand, the description is appended to the product name,
+//! delimited by a dash, and passed to the signer (signtool.exe) with the `/d`
+//! option. This is the program name displayed in the UAC dialog. The publisher
+//! displayed in the UAC dialog is always the subject of the signing
+//! certificate. It cannot be changed with an option and it is independent of the
+//! manufacturer of the installer, which is displayed in the Add/Remove Programs
+//! (ARP) control panel.
+//!
+//! ### `--deterministic-component-guids`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Derives the GUIDs of the components in the generated WiX Source (wxs) file
+//! from their install locations. Each GUID is a version 5 UUID of a fixed
+//! namespace and the key path of the component, such as
+//! `ProgramFiles64Folder\<product-name>\bin\<binary-name>.exe`, so identical
+//! inputs always yield identical GUIDs. The key path includes the Program
+//! Files folder of the platform, so a GUID is derived for the 32-bit and
+//! 64-bit platforms and the installers for the platforms do not share
+//! components. This applies to the components of the binaries, the
+//! license sidecar file, and the PATH environment variable. Reproducible GUIDs
+//! are important for patching and make generated WiX Source (wxs) files easy to
+//! diff. The `--path-guid` option still takes precedence for the PATH
+//! environment variable component.
+//!
+//! The default is to use `Guid='*'` for the file components, which lets the WiX
+//! Toolset generate the GUIDs when the installer is created, and a random GUID
+//! for the PATH environment variable component. This can also be set with the
+//! `deterministic-component-guids` field in the `[package.metadata.wix]`
+//! section of the package's manifest (Cargo.toml).
+//!
 //! ### `-D,--dialog`
 //!
 //! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
@@ -900,122 +1577,646 @@
 //! should be 493 x 312 pixels, but the right-most 60% of that area is covered
 //! by the actual text of the dialog, so if you want to leave a blank background for text
 //! readability, you only want to color in the left-most ~200 pixels of that image.
+//! A bitmap (.bmp) image is checked for a 24-bit depth in the same way as the
+//! `-b,--banner` option.
 //!
 //! The first dialog is known as the "Welcome" dialog.
 //!
-//! ### `-e,--eula`
+//! The path can be prefixed with a culture and a colon, and the option used
+//! repeatedly, to select a different image for each culture in the same way as
+//! the `-b,--banner` option. If neither this option nor the `dialog` field in
+//! the `[package.metadata.wix]` section is used, then the `wix\Dialog.bmp`
+//! file is used if it exists.
+//!
+//! ### `--dialog-set`
 //!
 //! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
 //! subcommands.
 //!
-//! Specifies a Rich Text Format (RTF) file to use as the End User License
-//! Agreement (EULA) for the license agreement dialog of the installer. The
-//! default is to disable the license agreement dialog unless one of the
-//! supported licenses (GPL-3.0, Apache-2.0, or MIT) is generated based on the
-//! value of the `license` field in the package's manifest (Cargo.toml). An EULA
-//! can be enabled later by directly modifying the WiX Source (WXS) file with a
-//! text editor.
+//! Selects the set of dialogs from the [WixUI dialog library] for the
+//! installer, which is substituted into the `UIRef` element of the generated
+//! WiX Source (wxs) file. The value is one of the following:
 //!
-//! When specified via `package.metadata.wix.eula` the path is assumed to be relative
-//! to the Cargo.toml (directory). This field can also be set to `false` to disable
-//! the eula even if we could auto-generate one for you, as described above.
+//! - `feature-tree`: A dialog with a feature tree for selecting the features
+//!   and the installation folder. This is the default.
+//! - `install-dir`: A dialog for selecting the installation folder without a
+//!   feature tree.
+//! - `mondo`: A dialog for selecting a typical, custom, or complete
+//!   installation.
 //!
-//! ### `--force`
+//! Each set has a license agreement dialog after the welcome dialog. The
+//! license agreement dialog is shown if the installer has an EULA, see the
+//! `-e,--eula` option, and the welcome dialog goes directly to the next dialog
+//! of the set otherwise, so disabling the EULA does not leave an empty license
+//! agreement dialog. The value can also be set with the `dialog-set` field in
+//! the `[package.metadata.wix]` section of the package's manifest
+//! (Cargo.toml).
 //!
-//! Available for the _init_ (`cargo wix init`) subcommand.
+//! [WixUI dialog library]: https://wixtoolset.org/documentation/manual/v3/wixui/wixui_dialog_library.html
 //!
-//! Forces overwriting of generated files from the _init_ subcommand. Use with
-//! caution! This cannot be undone.
+//! ### `--dry-run`
 //!
-//! ### `-h,--help`
+//! Available for the default _create_ (`cargo wix`) subcommand.
 //!
-//! Available for all subcommands.
+//! Prints the commands for building the binaries, compiling and linking the
+//! installer, and launching the installer, one per line, instead of running
+//! them. Nothing is built or written to the disk, so this can be used to check
+//! the effect of the other options without the WiX Toolset, for example:
 //!
-//! The short flag, `-h`, will print a condensed version of the help text, while
-//! the long flag, `--help`, will print a more detailed version of the help
-//! text.
+//! ```dos
+//! C:\Path\to\Project> cargo wix --dry-run --profile dist
+//! ```
 //!
-//! ### `-u,--homepage`
+//! The commands are assembled in the same way as without the flag, except the
+//! kind of installer is determined from the WiX Source (wxs) files instead of
+//! the WiX Object (wixobj) files. No commands are printed for archiving the
+//! files with the `zip` format, see the `--format` option.
 //!
-//! Available for the _sign_ (`cargo wix sign`) subcommand.
+//! ### `--dump-context`
 //!
-//! This will be displayed in the ACL dialog.
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
 //!
-//! ### `--install`
+//! Writes the context used to render the template, i.e. all of the values that
+//! are substituted into the template, to a file as JSON before the template is
+//! rendered. The values are resolved from the package's manifest (Cargo.toml)
+//! with all of the other options applied, so this shows exactly what the
+//! template receives during an actual run. This is useful for debugging an
+//! unexpected value in the generated file, such as the wrong version or an
+//! empty manufacturer, for example:
 //!
-//! Available for the default _create_ (`cargo wix`) subcommand.
+//! ```dos
+//! C:\Path\to\Project> cargo wix print wxs --dump-context target\wix\context.json
+//! ```
 //!
-//! Automatically runs the installer after creating it.
+//! For the _init_ subcommand, only the context of the WiX Source (wxs) file is
+//! written. Any missing parent folders of the file are created.
 //!
-//! ### `-i,--install-version`
+//! ### `--dump-defines`
 //!
 //! Available for the default _create_ (`cargo wix`) subcommand.
 //!
-//! Overrides the version from the package's manifest (Cargo.toml), which is
-//! used for the installer name and appears in the Add/Remove Programs (ARP)
-//! control panel.
+//! Prints each variable that would be defined for the WiX Toolset compiler
+//! (candle.exe) as `NAME=VALUE` to stdout and exits without building,
+//! compiling, or linking anything. The variables depend on the options, the
+//! `[package.metadata.wix]` section, and the target, such as the
+//! `TargetVendor` variable, which is only defined for a target with a vendor,
+//! and the variables from the `--property` option and the `-C,--compiler-arg`
+//! option. This shows exactly which `$(var.NAME)` variables are available to a
+//! WiX Source (wxs) file, for example:
 //!
-//! ### `-I,--include`
+//! ```dos
+//! C:\Path\to\Project> cargo wix --dump-defines
+//! ```
 //!
-//! Available only for the default _create_ (`cargo wix`) subcommand.
+//! If installers are created for multiple targets, then the variables for each
+//! target are preceded by a line with the target triple in square brackets.
 //!
-//! This option can be used multiple times to include multiple WiX Source (WXS)
-//! files in the creation of an installer. The option takes a path to a single
-//! WXS file as its value. Any WXS files located in the default `wix` folder
-//! located within the package's root folder, i.e. same location as the
-//! package's manifest (Cargo.toml) are automatically included and used in the
-//! creation of the installer. This option allows the inclusion of other WXS
-//! files outside of the default `wix` location.
+//! ### `--dump-toolset-commands`
 //!
-//! ### `-l,--license`
+//! Available for the default _create_ (`cargo wix`), _extensions_ (`cargo wix
+//! extensions`), and _sign_ (`cargo wix sign`) subcommands.
 //!
-//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
-//! subcommands.
+//! Prints each external command to stderr right before it runs, as a line that
+//! can be copied and pasted into a shell to run the command again. The line
+//! includes the working directory and the environment variables set for the
+//! command, such as the `CARGO_WIX_TARGET` variable for a custom build command.
+//! For the default _create_ subcommand, this covers the build, the WiX Toolset
+//! compiler (candle.exe), linker (light.exe), and transform tool (torch.exe),
+//! and the installer if the `--install` flag is used. For the _sign_
+//! subcommand, this covers each run of the signer (signtool.exe) and the WiX
+//! Toolset insignia application (insignia.exe), and for the _extensions_
+//! subcommand, the `wix extension list` command. For example:
 //!
-//! Overrides the `license-file` field or any license generated from the
-//! `license` field of the package's manifest (Cargo.toml). If an appropriate
-//! license file does not exist, cannot be found, or is not specified, then no
-//! license file is included in the installer or in the installation folder
-//! along side the `bin` folder. A file containing the license, such as a TXT,
-//! PDF, or RTF file, can be added later by directly editing the generated WiX
-//! Source file (WXS) in a text editor.
+//! ```dos
+//! C:\Path\to\Project> cargo wix --dump-toolset-commands
+//! ```
 //!
-//! When specified via `package.metadata.wix.license` the path is assumed to be relative
-//! to the Cargo.toml (directory). This field can also be set to `false` to disable
-//! the license auto-generation features described above.
+//! Unlike the `-v,--verbose` flag, the other logging statements are not
+//! printed, and the commands of a `--dry-run` are not printed because they do
+//! not run.
 //!
-//! ### `-L,--linker-arg`
+//! ### `--dump-wixobj`
 //!
-//! Available for the default _create (`cargo wix`) subcommand.
+//! Available for the default _create_ (`cargo wix`) subcommand.
 //!
-//! Appends an argument to the WiX linker (light.exe) invocation. This provides
-//! a mechanism for "passing" arguments to the linker. This can be called
-//! multiple times to pass multiple arguments (flags or options). Only one value
-//! per occurrence is allowed to avoid ambiguity during argument parsing. Note,
-//! if it is an option, i.e. argument with an accompanying value, then the value
-//! must be passed as a separate usage of this option. For example, adding an
-//! user-defined linker extension would require the following command `cargo wix
-//! -L -ext -L UserDefinedExtension` to yield a `light -ext
-//! UserDefinedExtension` invocation.
+//! Copies the WiX Object (wixobj) files from the `target\wix` folder to the
+//! specified folder after the WiX Toolset compiler (candle.exe) runs and before
+//! the linker (light.exe) runs. The folder is created if it does not exist. The
+//! WiX Object files are XML, so the copies can be inspected with a text editor
+//! when troubleshooting linker errors. The copies are made even if the linker
+//! fails. If installers are created for multiple targets, then the files for
+//! each target are copied to a subfolder named after the target triple.
 //!
-//! ### `-l,--locale`
+//! ### `--emit-relative-manifest-path`
 //!
 //! Available for the default _create_ (`cargo wix`) subcommand.
 //!
-//! Sets the path to a WiX localization file (wxl) which contains localized
-//! strings. Use in conjunction with the [`-c,--culture`] option.
+//! Defines the `CargoTargetDir`, `CargoTargetBinDir`, and `ProductIcon`
+//! variables for the WiX Toolset compiler (candle.exe) with paths relative to
+//! the folder of the package's manifest (Cargo.toml), such as
+//! `target\release`, instead of absolute paths. The absolute paths contain
+//! machine-specific information, such as the user name, that ends up in the
+//! WiX Object (wixobj) files, logs, and the output of the `--dump-defines`
+//! flag, so relative paths are more reproducible and safer to share or archive.
+//! A target folder outside of the package's folder, such as the target folder
+//! of a workspace, is reached with `..` components. The WiX Toolset linker
+//! (light.exe) resolves the relative paths from the folder of the manifest. A
+//! path that cannot be made relative, such as a path on a different drive, is
+//! left absolute with a warning. This can also be set with the
+//! `emit-relative-manifest-path` field in the `[package.metadata.wix]` section.
 //!
-//! ### `-m,--manufacturer`
+//! ### `-e,--eula`
 //!
 //! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
 //! subcommands.
 //!
-//! Overrides the `authors` field of the package's manifest
-//! (Cargo.toml) as the manufacturer within the installer. The manufacturer can
-//! be changed after initialization by directly modifying the WiX Source file
-//! (WXS) with a text editor.
+//! Specifies a Rich Text Format (RTF) file to use as the End User License
+//! Agreement (EULA) for the license agreement dialog of the installer. The
+//! default is to disable the license agreement dialog unless one of the
+//! supported licenses (GPL-3.0, Apache-2.0, or MIT) is generated based on the
+//! value of the `license` field in the package's manifest (Cargo.toml). An EULA
+//! can be enabled later by directly modifying the WiX Source (WXS) file with a
+//! text editor.
 //!
-//! ### `-n,--name`
+//! When specified via `package.metadata.wix.eula` the path is assumed to be relative
+//! to the Cargo.toml (directory). This field can also be set to `false` to disable
+//! the eula even if we could auto-generate one for you, as described above.
+//!
+//! ### `--feature-map`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Adds an optional feature to the feature tree of the installer for a Cargo
+//! feature of the package. The value is in the form of `CARGO_FEATURE=TITLE`,
+//! where the title is displayed for the feature in the installer, and the
+//! option can be used multiple times to add multiple features, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix init --feature-map "gui=Graphical Interface"
+//! ```
+//!
+//! Each feature is only included in the installer if the
+//! `CargoFeature_<CARGO_FEATURE>` variable is defined for the WiX Toolset
+//! compiler (candle.exe). The default _create_ subcommand defines the variable
+//! for each enabled Cargo feature of the package, see the `--features`
+//! option. Binaries that list the Cargo feature in their `required-features`
+//! field are installed with the feature instead of the main feature, and other
+//! files can be added to the feature by editing the WiX Source (wxs) file.
+//! Hyphens in the name of the Cargo feature are replaced with underscores in
+//! the name of the variable. An error occurs if the package does not have the
+//! Cargo feature.
+//!
+//! ### `--features`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Enables Cargo features of the package when building the binaries. The value
+//! is passed to the `cargo build` command with the `--features` option, and the
+//! option can be used multiple times or with a comma-separated list of
+//! features. The `CargoFeature_<FEATURE>` variable is defined for the WiX
+//! Toolset compiler (candle.exe) for each enabled feature of the package,
+//! including the default features and the features enabled by other features,
+//! which includes the optional features added with the `--feature-map`
+//! option. The features are not passed to a command set with the
+//! `--build-command` option.
+//!
+//! ### `--fix`
+//!
+//! Available for the _check_ (`cargo wix check`) subcommand.
+//!
+//! Migrates the WiX Source (wxs) files off the deprecated variables before
+//! checking them. Every `$(var.Platform)` reference is replaced with
+//! `$(sys.BUILDARCH)`, and every `$(var.Profile)` reference is replaced with
+//! `$(var.CargoProfile)`. Only the exact references outside of XML comments are
+//! replaced, so the rest of the file is unchanged. The original content of each
+//! rewritten file is saved next to it with the `.bak` extension appended, i.e.
+//! `main.wxs.bak`, and files without the deprecated variables are not touched.
+//! See the [Variables](#variables) section for more information about the
+//! deprecated variables.
+//!
+//! ### `--force`
+//!
+//! Available for the _init_ (`cargo wix init`) subcommand.
+//!
+//! Forces overwriting of generated files from the _init_ subcommand. Use with
+//! caution! This cannot be undone.
+//!
+//! The `UpgradeCode` and the GUID of the path component are read from an
+//! existing `main.wxs` file and reused in the overwritten file, because new
+//! GUIDs would break upgrades of installations from previous installers. Use
+//! the `--regenerate-guids` flag to generate new GUIDs instead.
+//!
+//! ### `--format`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Sets the format of the output, which is either `msi` or `zip`. The default
+//! is `msi`, which creates an installer with the WiX Toolset. The `zip` format
+//! skips the WiX Toolset and creates a portable zip archive for distribution
+//! channels that do not want an installer, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --format zip
+//! ```
+//!
+//! The binary is still built, and the files in the archive are the files of
+//! the `File` elements in the WiX Source (wxs) files, such as the binaries and
+//! the license. The `$(var.CargoTargetBinDir)` and other variables defined by
+//! the _create_ subcommand for the WiX Toolset compiler (candle.exe) are
+//! replaced in the paths to the files. The files are in a folder with the
+//! same name as the archive, and the binaries are in a `bin` folder within it,
+//! like the installed product. The archive is named
+//! `{name}-{version}-{arch}.zip` and is written to the same destination as the
+//! installer, see the `-o,--output` option.
+//!
+//! ### `--generate-branding`
+//!
+//! Available for the _init_ (`cargo wix init`) subcommand.
+//!
+//! Generates a banner image (493 x 58 pixels) and a dialog image (493 x 312
+//! pixels) with the product name and writes them to the `wix` folder as
+//! `Banner.bmp` and `Dialog.bmp`, respectively. The generated WiX Source (wxs)
+//! file uses the images to customize the installer instead of the default
+//! images of the WiX Toolset. The banner fades from white to a light blue with
+//! the product name on the right, and the dialog has a blue panel on the left
+//! with the product name at the top. Only the printable ASCII characters are
+//! supported, so if the product name has any other character, such as an
+//! accented letter, the images are generated without the product name and a
+//! warning is printed. An image is not generated if it is specified with the
+//! `-b,--banner` or `-D,--dialog` options, or the `banner` or `dialog` fields
+//! in the `[package.metadata.wix]` section. The images can be replaced later
+//! with custom images of the same size.
+//!
+//! ### `--generate-transforms`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Generates a language transform (mst) for each additional culture after the
+//! installer is created for the culture from the `-c,--culture` option. The WiX
+//! Object (wixobj) files are linked again for each additional culture, and the
+//! transform is created from the differences with the WiX Toolset's transform
+//! application (torch.exe), so the `torch` application must be installed with
+//! the WiX Toolset v3 and found in the same way as the compiler (candle.exe)
+//! and linker (light.exe). Use the option multiple times to generate multiple
+//! transforms, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --generate-transforms fr-FR --generate-transforms de-DE
+//! ```
+//!
+//! Each transform is written next to the installer with the culture appended
+//! to the name of the installer, such as `{name}-{version}-{arch}-fr-FR.mst`.
+//! The transforms are not embedded in the installer, and a transform is
+//! applied with the `TRANSFORMS` property when installing, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> msiexec /i Example-0.1.0-x86_64.msi TRANSFORMS=Example-0.1.0-x86_64-fr-FR.mst
+//! ```
+//!
+//! Transforms can only be generated for a Windows installer (msi), not a
+//! bundle.
+//!
+//! The cultures listed after the first culture of the `-c,--culture` option,
+//! or the `culture` field in the `[package.metadata.wix]` section, also
+//! generate transforms.
+//!
+//! ### `--global`
+//!
+//! Available for the _extensions_ (`cargo wix extensions`) subcommand.
+//!
+//! Lists the extensions in the global cache of the WiX Toolset v4 or later
+//! instead of the extensions for the current folder. This is the same as the
+//! `wix extension list --global` command.
+//!
+//! ### `--guid-from`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Derives the upgrade code and path component GUIDs from a seed, such as a
+//! product identifier, instead of randomly generating them. The GUIDs are
+//! version 5 UUIDs, so the same seed always yields the same GUIDs and the GUIDs
+//! are unique to the seed instead of the package name. This is useful for
+//! regenerating the WiX Source (wxs) file without storing the GUIDs in the
+//! package's manifest (Cargo.toml). The `--upgrade-guid` and `--path-guid`
+//! options, and the `upgrade-guid` and `path-guid` fields, take precedence. This
+//! can also be set with the `guid-from` field in the `[package.metadata.wix]`
+//! section.
+//!
+//! ### `-h,--help`
+//!
+//! Available for all subcommands.
+//!
+//! The short flag, `-h`, will print a condensed version of the help text, while
+//! the long flag, `--help`, will print a more detailed version of the help
+//! text.
+//!
+//! ### `-u,--homepage`
+//!
+//! Available for the _sign_ (`cargo wix sign`) subcommand.
+//!
+//! This will be displayed in the UAC dialog as the link for the program name.
+//! It is passed to the signer (signtool.exe) with the `/du` option. The default
+//! is to use the `homepage` field from the package's manifest (Cargo.toml). If
+//! neither exists, then the `/du` option is not used.
+//!
+//! ### `--include-licenses`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand with the `WXS`
+//! template.
+//!
+//! Reports which license files and help URL the rendered WiX Source (wxs) file
+//! references, since the license is detected automatically from several
+//! places, see the `-e,--eula` and `-l,--license` options. The EULA displayed in
+//! the license agreement dialog, the license installed as a sidecar file, and
+//! the help URL are written to stderr, so the report is not mixed with the
+//! rendered template printed to stdout:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix print wxs --include-licenses
+//! EULA for the license agreement dialog: 'wix\License.rtf', generated from the MIT template
+//! License sidecar file: 'wix\License.rtf', generated from the MIT template
+//! Help URL: https://github.com/volks73/cargo-wix
+//! ```
+//!
+//! The same report is logged without this flag when the verbosity is increased
+//! with the `-v,--verbose` flag.
+//!
+//! ### `--install`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Automatically runs the installer after creating it. A Windows installer
+//! (msi) is run with the `msiexec /i` command, while a bundle (exe) installer
+//! is run directly. An error occurs with the exit code of the installer if it
+//! fails. An error also occurs if installers are created for multiple targets
+//! with the `-t,--target` option, since only one installer can be run.
+//!
+//! ### `--install-scope`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Sets the installation scope, `per-machine` or `per-user`, for the generated
+//! WiX Source (wxs) file. A per-machine installation requires elevated
+//! privileges, i.e. the installer asks to run as an administrator, while a
+//! per-user installation does not. With the per-user scope, the application is
+//! installed to the user's programs folder, i.e. `%LOCALAPPDATA%\Programs`,
+//! instead of the Program Files folder, and the `bin` folder is added to the
+//! user's PATH environment variable instead of the system's PATH environment
+//! variable.
+//!
+//! The `auto` value picks the installation scope based on the `--no-path` flag:
+//! the per-user scope is used if the PATH environment variable is _not_
+//! modified, and the per-machine scope is used otherwise. The default is the
+//! per-machine scope. The value can also be set with the `install-scope` field
+//! in the `[package.metadata.wix]` section of the package's manifest
+//! (Cargo.toml).
+//!
+//! ### `--keep-author-emails`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Keeps the email of each author from the `authors` field of the package's
+//! manifest (Cargo.toml) in the manufacturer, which is displayed as the
+//! "Publisher" in the Add/Remove Programs control panel. By default, an author
+//! such as `First Last <first.last@example.com>` is used as `First Last`. This
+//! is not used if the `-m,--manufacturer` option is used, and the
+//! `keep-author-emails = true` field in the `[package.metadata.wix]` section
+//! can be used instead of this flag.
+//!
+//! ### `--keep-going`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Continues creating the installers for the remaining targets when one of
+//! multiple targets, see the `-t,--target` option, fails. The failed targets
+//! are listed and a non-zero exit code is returned after all of the targets
+//! have been attempted. The default is to stop at the first failure.
+//!
+//! ### `-i,--install-version`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Overrides the version from the package's manifest (Cargo.toml), which is
+//! used for the installer name and appears in the Add/Remove Programs (ARP)
+//! control panel.
+//!
+//! ### `--include-build-metadata`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Embeds the short hash of the current git commit and the time of the build in
+//! the installer for traceability. The hash is obtained with the `git rev-parse
+//! --short HEAD` command in the package's root folder and passed to the WiX
+//! Toolset compiler (candle.exe) as the `GitHash` variable. The time is in UTC
+//! and the ISO 8601 format and passed as the `BuildTime` variable. The `GitHash`
+//! variable is omitted, without an error, if the package is not in a git
+//! repository or git is not installed. The template displays the build
+//! metadata as the comments in the Add/Remove Programs (ARP) control panel if a
+//! section of the changelog is not used for the comments.
+//!
+//! ### `-I,--include`
+//!
+//! Available for the default _create_ (`cargo wix`) and _check_ (`cargo wix
+//! check`) subcommands.
+//!
+//! This option can be used multiple times to include multiple WiX Source (WXS)
+//! files in the creation of an installer. The option takes a path to a single
+//! WXS file as its value. Any WXS files located in the default `wix` folder
+//! located within the package's root folder, i.e. same location as the
+//! package's manifest (Cargo.toml) are automatically included and used in the
+//! creation of the installer. This option allows the inclusion of other WXS
+//! files outside of the default `wix` location.
+//!
+//! For the _check_ subcommand, only the WXS files from this option are checked
+//! instead of the files in the `wix` folder and the `include` field in the
+//! `[package.metadata.wix]` section.
+//!
+//! ### `--incremental`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Skips running the WiX Toolset compiler (candle.exe) and linker (light.exe)
+//! if the inputs of the installer are unchanged since it was last created. The
+//! binaries are still built with Cargo, then a hash of the following inputs is
+//! computed:
+//!
+//! - The version of cargo-wix
+//! - The compiler arguments, including the variables listed above, except for
+//! the `BuildTime` variable
+//! - The culture and the linker arguments
+//! - The contents of the WiX Source (wxs) files and the files they include with
+//! the `<?include?>` preprocessor instruction
+//! - The contents of the files referenced by the `Source` attribute of the
+//! `File` elements, the `SourceFile` attribute of the `Binary` and `Icon`
+//! elements, and the `Value` attribute of the `WixVariable` elements, such as
+//! the license, the icon, and the banner and dialog images, after the
+//! `$(var.NAME)` references are replaced with the compiler variables
+//! - The contents of the WiX localization (wxl) file, if one is used
+//! - The contents of the package's binaries
+//!
+//! The hash and the path to the installer are recorded in the
+//! `installer.inputs` file in the `target\wix` folder, or the folder for each
+//! target if multiple targets are used. If the hash matches the recorded one and
+//! the installer exists, then the existing installer is kept. Other inputs,
+//! such as files referenced through other elements or extensions, are _not_
+//! included in the hash, so omit this flag, or use the _clean_ subcommand, to
+//! force the installer to be created after changing them. The recorded hash is
+//! removed when the flag is not used. The
+//! `incremental` field in the `[package.metadata.wix]` section can also be
+//! used.
+//!
+//! ### `--launch-binary`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Selects the binary to launch after installation with the
+//! `--after-install-launch` flag. The value is the name of one of the binaries
+//! included in the installer without the `.exe` file extension, such as the
+//! `name` field of a `[[bin]]` section in the package's manifest (Cargo.toml).
+//! An error occurs if the binary is not included in the installer. The
+//! `launch-binary` field in the `[package.metadata.wix]` section can be used
+//! instead of this option.
+//!
+//! ### `-l,--license`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Overrides the `license-file` field or any license generated from the
+//! `license` field of the package's manifest (Cargo.toml). If an appropriate
+//! license file does not exist, cannot be found, or is not specified, then no
+//! license file is included in the installer or in the installation folder
+//! along side the `bin` folder. A file containing the license, such as a TXT,
+//! PDF, or RTF file, can be added later by directly editing the generated WiX
+//! Source file (WXS) in a text editor.
+//!
+//! When specified via `package.metadata.wix.license` the path is assumed to be relative
+//! to the Cargo.toml (directory). This field can also be set to `false` to disable
+//! the license auto-generation features described above.
+//!
+//! ### `-L,--linker-arg`
+//!
+//! Available for the default _create (`cargo wix`) subcommand.
+//!
+//! Appends an argument to the WiX linker (light.exe) invocation. This provides
+//! a mechanism for "passing" arguments to the linker. This can be called
+//! multiple times to pass multiple arguments (flags or options). Only one value
+//! per occurrence is allowed to avoid ambiguity during argument parsing. Note,
+//! if it is an option, i.e. argument with an accompanying value, then the value
+//! must be passed as a separate usage of this option. For example, adding an
+//! user-defined linker extension would require the following command `cargo wix
+//! -L -ext -L UserDefinedExtension` to yield a `light -ext
+//! UserDefinedExtension` invocation. The arguments replace the `linker-args`
+//! field in the `[package.metadata.wix]` section, unless the `--append-args`
+//! flag is used.
+//!
+//! ### `-l,--locale`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Sets the path to a WiX localization file (wxl) which contains localized
+//! strings. Use in conjunction with the [`-c,--culture`] option.
+//!
+//! ### `--manifest-path`
+//!
+//! Available for the default _create_ (`cargo wix`), _check_ (`cargo wix
+//! check`), _clean_ (`cargo wix clean`), _init_ (`cargo wix init`), _print_
+//! (`cargo wix print`), _purge_
+//! (`cargo wix purge`), _sign_ (`cargo wix sign`), and _verify_ (`cargo wix
+//! verify`) subcommands.
+//!
+//! Sets the path to a package's manifest (Cargo.toml), consistent with other
+//! cargo subcommands. This is an alternative to the positional `INPUT`
+//! argument of each subcommand, for example, `cargo wix --manifest-path
+//! Path\to\Cargo.toml` is the same as `cargo wix Path\to\Cargo.toml`. An
+//! error occurs if both the option and the positional argument are used.
+//!
+//! ### `-m,--manufacturer`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Overrides the `authors` field of the package's manifest
+//! (Cargo.toml) as the manufacturer within the installer. The manufacturer can
+//! be changed after initialization by directly modifying the WiX Source file
+//! (WXS) with a text editor.
+//!
+//! ### `--manufacturer-from`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Selects which authors from the `authors` field of the package's manifest
+//! (Cargo.toml) are used for the manufacturer, which is displayed as the
+//! "Publisher" in the Add/Remove Programs control panel. The value is `first`
+//! for the first author, `all` for all of the authors separated by semicolons,
+//! or the zero-based index of an author, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix init --manufacturer-from first
+//! ```
+//!
+//! The default is `all`, which can be too long for the Publisher field when
+//! a package lists many authors. This is not used if the `-m,--manufacturer`
+//! option is used, and it can also be set with the `manufacturer-from` field in
+//! the `[package.metadata.wix]` section.
+//!
+//! ### `--metadata-output`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Writes the resolved configuration that produced the installer to a JSON
+//! file after the installer is created, which is useful for release audits
+//! and for finding why two builds differ. The file records the inputs of the
+//! installer, unlike the output of the WiX Toolset, including the name,
+//! version, target, architecture, profile, culture, product language,
+//! features, WiX Source (wxs) files, the variables defined for the compiler
+//! (candle.exe), the compiler and linker arguments, and the paths of the WiX
+//! Toolset applications. If the path is a folder, i.e. an existing folder or a
+//! path with a trailing slash, then the file is named after the installer with
+//! the `.json` file extension, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --metadata-output target\wix\
+//! ```
+//!
+//! writes the `target\wix\Example-0.1.0-x86_64.msi.json` file next to the
+//! installer. A folder must be used when creating installers for multiple
+//! targets. The file is not written for a dry run or when the
+//! `--incremental` flag skips an unchanged installer.
+//!
+//! ### `--msbuild`
+//!
+//! Available for the _init_ (`cargo wix init`) subcommand.
+//!
+//! Writes a MSBuild project file (wixproj) named after the package, such as
+//! `wix\example.wixproj`, in addition to the WiX Source (wxs) file. The project
+//! uses the SDK of the modern WiX Toolset (v4 and later), lists the WiX Source
+//! (wxs) files in the `wix` folder, and references the packages for the
+//! extensions used in those files, such as `WixToolset.UI.wixext`, so the
+//! installer can be built with `dotnet build` in a MSBuild-based pipeline. The
+//! `Version`, `CargoProfile`, `CargoTargetDir`, and `CargoTargetBinDir`
+//! variables are defined for the compiler, and the first three can be changed
+//! with MSBuild properties, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix init --msbuild
+//! C:\Path\to\Project> wix convert wix\main.wxs
+//! C:\Path\to\Project> dotnet build wix\example.wixproj -p:Version=1.2.3
+//! ```
+//!
+//! The generated WiX Source (wxs) file uses the WiX Toolset v3 schema, so it
+//! must be converted with the `wix convert` command before building the
+//! project. The project can be regenerated with the `--force` flag after adding
+//! or removing WiX Source (wxs) files.
+//!
+//! ### `-n,--name`
 //!
 //! Available for the default _create_ (`cargo wix`) subcommand.
 //!
@@ -1023,12 +2224,145 @@
 //! used in the file name of the installer (msi). This does not change the name
 //! of the executable _within_ the installer.
 //!
+//! ### `--no-arch-in-name`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Excludes the architecture from the file name of the installer, so the
+//! installer is named `{name}-{version}.msi` instead of
+//! `{name}-{version}-{arch}.msi`. This is useful for a product that is only
+//! distributed for a single architecture. The flag cannot be used when creating
+//! installers for multiple targets with the `-t,--target` option because the
+//! installers would have the same name, unless the `--output-layout per-arch`
+//! option is also used. The `no-arch-in-name` field in the
+//! `[package.metadata.wix]` section can be used instead of the flag.
+//!
 //! ### `--no-build`
 //!
 //! Available for the default _create_ (`cargo wix`) subcommand.
 //!
 //! This skips building the Rust package using Cargo for the Release target.
 //!
+//! If the `--target-bin-dir` option is not used and the main binary is not in
+//! the expected `target\<profile>` folder, such as after a build with a custom
+//! profile or folder layout, then the most recently modified binary with the
+//! same name in the target folder is used instead and a warning is displayed.
+//! If the `--target-bin-dir` option is used, then every binary of the package,
+//! i.e. each `[[bin]]` target, must exist in that folder. An error that lists
+//! all of the missing binaries occurs otherwise, which catches a partial build
+//! of a package with multiple binaries before the installer is compiled.
+//!
+//! ### `--no-default-features`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Passes the `--no-default-features` flag to the `cargo build` command, and
+//! the `CargoFeature_<FEATURE>` variables are not defined for the default
+//! features of the package, see the `--features` option.
+//!
+//! ### `--no-default-wix-folder`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Skips collecting the WiX Source (wxs) files in the default `wix` folder. Only
+//! the WiX Source (wxs) files included with the `-I,--include` option, or the
+//! `include` field in the `[package.metadata.wix]` section, are used to create
+//! the installer. This is useful if the `wix` folder contains files that should
+//! not be a part of the installer. An error occurs if no WiX Source (wxs) files
+//! remain.
+//!
+//! ### `--no-install-if-running`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Checks if any of the package's binaries are running before running the
+//! installer with the `--install` flag. The running processes are matched by
+//! the file name of each binary, i.e. the `name` field of each `[[bin]]` section
+//! in the package's manifest (Cargo.toml) with the `.exe` file extension. If any
+//! of the binaries are running, then an error occurs and the installer is not
+//! run, but it is still created. Installing over a running binary can fail or
+//! require a reboot, so this is useful when repeatedly creating and installing
+//! the application during development. This flag can only be used with the
+//! `--install` flag, and the check is only performed on Windows.
+//!
+//! ### `--no-overwrite-increment`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Keeps an existing installer instead of overwriting it. If a file already
+//! exists at the destination of the installer, then `-1`, `-2`, etc. is appended
+//! to the file stem until a name that does not exist is found, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --no-overwrite-increment
+//! C:\Path\to\Project> cargo wix --no-overwrite-increment
+//! C:\Path\to\Project> dir /b target\wix\*.msi
+//! example-0.1.0-x86_64-1.msi
+//! example-0.1.0-x86_64.msi
+//! ```
+//!
+//! This is useful for comparing successive installers while debugging. The
+//! destination is still determined by the `-o,--output` option, or the `output`
+//! field, and the default is to overwrite an existing installer. This can also
+//! be set with the `no-overwrite-increment` field in the
+//! `[package.metadata.wix]` section.
+//!
+//! ### `--no-modify`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Sets the `ARPNOMODIFY` property in the generated WiX Source (wxs) file. The
+//! Change button for the application in the Add/Remove Programs control panel
+//! is then hidden or grayed out, depending on the version of Windows, so users
+//! cannot add or remove features of an installation from the control panel. The
+//! application can still be uninstalled. This is useful for installers with a
+//! single feature, where changing the installation has no purpose. This can
+//! also be set with the `no-modify` field in the `[package.metadata.wix]`
+//! section. The default is to let the Windows Installer show the button.
+//!
+//! ### `--no-path`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Excludes the component and feature that add the `bin` folder of the
+//! installation to the PATH environment variable from the generated WiX Source
+//! (wxs) file. This can also be set with the `no-path` field in the
+//! `[package.metadata.wix]` section of the package's manifest (Cargo.toml). See
+//! the `--install-scope` option for using this flag to avoid installers that
+//! require elevated privileges.
+//!
+//! ### `--no-repair`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Sets the `ARPNOREPAIR` property in the generated WiX Source (wxs) file. The
+//! Repair button for the application in the Add/Remove Programs control panel
+//! is then hidden or grayed out, so users cannot repair an installation from
+//! the control panel. Running the installer again still offers a repair in the
+//! maintenance dialog. This can also be set with the `no-repair` field in the
+//! `[package.metadata.wix]` section. The default is to let the Windows
+//! Installer show the button.
+//!
+//! ### `--no-upgrade`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Omits the `MajorUpgrade` element from the generated WiX Source (wxs) file.
+//! By default, installing a new version of the application removes any
+//! previously installed version with a major upgrade. With this flag, each
+//! version is installed independently and side by side, which is useful for
+//! products, like toolchains, where multiple versions are intentionally kept
+//! installed. Note, users accumulate every installed version, and each version
+//! must be uninstalled manually from the Add/Remove Programs control panel.
+//! Installing different versions into the same folder overwrites shared
+//! files, so the installation folder should include the version when using
+//! this flag. This can also be set with the `no-upgrade` field in the
+//! `[package.metadata.wix]` section.
+//!
 //! ### `--nocapture`
 //!
 //! Available for the default _create_ (`cargo wix`) and _sign_ (`cargo wix sign`)
@@ -1037,10 +2371,24 @@
 //! Displays all output from the builder (Cargo), compiler (candle.exe), linker
 //! (light.exe), and signer (signtool.exe) applications.
 //!
+//! ### `--open`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand with the `WXS`
+//! template.
+//!
+//! Writes the rendered WiX Source (wxs) file to a new folder in the temporary
+//! directory and opens it with the default application for WiX Source (wxs)
+//! files, as if the file was double-clicked in the File Explorer. If the
+//! `-o,--output` option is also used, then the file is written to the output
+//! instead of the temporary directory. This is useful for exploring and
+//! customizing the template without manually saving and opening the output. This
+//! flag is only supported on Windows and has no effect on other platforms.
+//!
 //! ### `-o,--output`
 //!
-//! Available for the default _create_ (`cargo wix`), _init_ (`cargo wix init`) and
-//! _print_ (`cargo wix print`) subcommands.
+//! Available for the default _create_ (`cargo wix`), _clean_ (`cargo wix
+//! clean`), _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
 //!
 //! Sets the destination for _init_ subcommand files, such as the WiX Source
 //! file (WXS), an alternative to stdout for _print_ subcommand, and the created
@@ -1050,7 +2398,60 @@
 //! if the path is to an existing directory or the path has a trailing `/` or
 //! `\`, then the MSI will be available after creation at the specified path,
 //! but the MSI file name will be the default file name based on the package
-//! name, version, and platform.
+//! name, version, and platform. Use the `--output-dir` option instead if the
+//! folder might not exist yet.
+//!
+//! When used with the _print_ subcommand, a value of `-` explicitly prints to
+//! stdout, and any missing parent folders of the destination file are created,
+//! so `cargo wix print wxs -o dist\installer\main.wxs` works in one step.
+//!
+//! When used with the _clean_ subcommand, the installers created at the output
+//! location are removed along with the `target\wix` folder. The `output` field
+//! in the `[package.metadata.wix]` section is used if the option is not. Only
+//! the installers (msi, exe, or zip) whose file names start with the package's
+//! name, and the WiX debug symbols (wixpdb) next to them, are removed from a
+//! folder, so other files in the folder are kept.
+//!
+//! ### `--output-dir`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Sets the destination folder for the created installer, which has the
+//! default file name based on the package name, version, and platform. Unlike
+//! the `-o,--output` option, the value is always treated as a folder, even if
+//! it does not exist and does not have a trailing `/` or `\`, and the folder is
+//! created if it does not exist. This avoids the installer being written to a
+//! file named after the folder on a fresh CI machine, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --output-dir dist
+//! ```
+//!
+//! This option cannot be used with the `-o,--output` option, and it takes
+//! precedence over the `output` field in the `[package.metadata.wix]` section.
+//!
+//! ### `--output-layout`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Sets the layout of the destination folder for the installers. The value is
+//! either `flat` or `per-arch`. The default is `flat`, where the installers are
+//! written directly to the destination folder. With `per-arch`, each installer
+//! is written to a subfolder named after its architecture, which keeps the
+//! installers for multiple targets apart for release pipelines that upload a
+//! folder per architecture, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --target x86_64-pc-windows-msvc --target i686-pc-windows-msvc --output-dir dist --output-layout per-arch --no-arch-in-name
+//! ```
+//!
+//! creates the `dist\x64\{name}-{version}.msi` and
+//! `dist\x86\{name}-{version}.msi` installers. The subfolder is `x86`, `x64`,
+//! `arm`, or `arm64`. The layout is not used if the `-o,--output` option or
+//! the `output` field is a file. The `output-layout` field in the
+//! `[package.metadata.wix]` section can be used instead of this option. Note,
+//! the _clean_ subcommand only removes the installers directly in the output
+//! folder, not the ones in the architecture subfolders.
 //!
 //! ### `-O,--owner`
 //!
@@ -1064,42 +2465,400 @@
 //!
 //! ### `-p,--package`
 //!
-//! Available for the _create_ (`cargo wix`), _init_ (`cargo wix init`), and
-//! _print_ (`cargo wix print`) subcommands.
+//! Available for the _create_ (`cargo wix`), _check_ (`cargo wix check`), _clean_
+//! (`cargo wix clean`), _init_ (`cargo wix init`), and _print_ (`cargo wix
+//! print`) subcommands.
+//!
+//! Selects the package within a workspace. This is required if a project
+//! organized with a workspace, unless the current working directory is within
+//! the folder of a member, in which case that member is selected like cargo
+//! selects the current package, or the virtual manifest of the workspace has a
+//! `[workspace.metadata.wix]` section. A workspace can have one or more
+//! members, where each member may have a separate installer. This option has no
+//! effect if the project does not use a workspace. See the [Bundles](#bundles)
+//! section for creating an installer for the entire workspace.
+//!
+//! ### `--path-guid`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Overrides the automatically generated GUID for the path component with an
+//! existing GUID in the hyphenated, uppercase format. The path GUID should only
+//! be generated once for a product/project. The same GUID should be used for
+//! all installer creations to ensure no artifacts are left after uninstalling
+//! and proper modification of the `PATH` environment variable. The GUID is
+//! validated before the WiX Source (wxs) file is rendered, and an error naming
+//! the option, or the `path-guid` field, is displayed if it is malformed.
+//!
+//! ### `--preprocess-only`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Runs only the preprocessor of the WiX Toolset compiler (candle.exe) and
+//! writes the preprocessed WiX Source (wxs) files to the folder instead of
+//! creating the installer. The preprocessor expands the `$(var.*)` variables
+//! and evaluates the `<?if?>`, `<?include?>`, etc. directives with the same
+//! variables as a normal build, so this is useful for debugging why a
+//! conditional block is, or is not, included in the installer. Each file is
+//! written with the same name as its source, and the folder is created if it
+//! does not exist. Nothing is linked and the installer is not launched with
+//! the `--install` flag. If installers are created for multiple targets, then
+//! the files for each target are written to a subfolder named after the target
+//! triple, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --preprocess-only target\wix\preprocessed
+//! ```
+//!
+//! ### `--print-wxs`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Prints the path and content of each WiX Source (wxs) file to stdout right
+//! before the WiX Toolset compiler (candle.exe) runs, including the files in
+//! the `wix` folder and the files from the `-I,--include` option. Each file is
+//! preceded by an XML comment with its path. This shows exactly what is
+//! compiled without searching the `target\wix` folder, which is useful when a
+//! customized template produces unexpected results. The files are also printed
+//! with the `--dry-run` flag, and the output can be redirected to a file, for
+//! example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --print-wxs > target\wix\compiled.wxs
+//! ```
+//!
+//! ### `--product-icon`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Sets the path to a 16x16 image file (.ico) that will be display as an icon in the
+//! Add/Remove Programs (ARP) control panel for the installed application.
+//!
+//! The `from-binary` value uses the icon embedded in the resources of the main
+//! binary instead of a separate icon file. The WiX Source file (wxs) uses the
+//! `ProductIcon` variable for the icon, and the _create_ (`cargo wix`)
+//! subcommand extracts the icon from the binary after building it. The binary
+//! must have an embedded icon, such as one added with a build script. If the
+//! `--no-build` flag is used, then the binary must already exist or the
+//! `--target-bin-dir` option must be used.
+//!
+//! If neither this option nor the `product-icon` field in the
+//! `[package.metadata.wix]` section is used, then the `wix\Product.ico` file
+//! is used if it exists.
+//!
+//! ### `--product-language`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Sets the product language of the installer independently of the
+//! `-c,--culture` option, which selects the language of the user interface.
+//! The value is either a culture code, such as `fr-FR`, or a numeric Windows
+//! locale identifier (LCID), such as `1036`. For example, an installer with an
+//! English user interface can declare a language-neutral product with the `0`
+//! LCID:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --culture en-US --product-language 0
+//! ```
+//!
+//! The LCID is defined as the `ProductLanguage` variable for the WiX Toolset
+//! compiler (candle.exe), which the default template uses for the `Language`
+//! attribute of the `Product` tag and the `Languages` attribute of the
+//! `Package` tag. A WiX Source (wxs) file generated before this option existed
+//! uses the `1033` (English, United States) LCID regardless of this option
+//! until it is re-initialized. The default is the LCID of the culture. This
+//! can also be set with the `product-language` field in the
+//! `[package.metadata.wix]` section.
+//!
+//! ### `--product-name`
+//!
+//! Available for the _init_ (`cargo wix init`), _print_ (`cargo wix print`),
+//! and _sign_ (`cargo wix sign`) subcommands.
+//!
+//! Overrides the `name` field of the package's manifest (Cargo.toml) as the
+//! product name within the installer. The product name can be changed after
+//! initialization or printing by directly modifying the WiX Source file (wxs)
+//! with a text editor.
+//!
+//! ### `--property`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Sets the default value of a property for the installer without editing the
+//! WiX Source (wxs) file. The value has the `NAME=VALUE` form, and it is passed
+//! to the WiX compiler (candle.exe) as the `-dNAME=VALUE` argument, i.e. a
+//! preprocessor variable. The WiX Source (wxs) file uses the variable to set the
+//! property, for example:
+//!
+//! ```xml
+//! <?ifdef INSTALLDESKTOPSHORTCUT ?>
+//! <Property Id='INSTALLDESKTOPSHORTCUT' Value='$(var.INSTALLDESKTOPSHORTCUT)'/>
+//! <?endif ?>
+//! ```
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --property INSTALLDESKTOPSHORTCUT=1
+//! ```
+//!
+//! This option can be used multiple times to set multiple properties. The value
+//! can be empty, but the name must be a valid WiX identifier, i.e. it must begin
+//! with a letter or an underscore and only contain letters, digits,
+//! underscores, and periods. Unlike the `-C,--compiler-arg` option, which passes
+//! any argument to the compiler as-is, the form of each property is validated
+//! before the compiler is run. The properties can also be set with the
+//! `properties` field, an array of `NAME=VALUE` strings, in the
+//! `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+//!
+//! ### `--quiet-tools`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Suppresses the logo, or banner, that the WiX Toolset compiler (candle.exe)
+//! and linker (light.exe) print each time they are executed by passing the
+//! `-nologo` argument to both applications. This is the same as using the
+//! `-C -nologo -L -nologo` options, and the argument is not passed twice if
+//! either option already includes it. This flag is only useful with the
+//! `--nocapture` flag, since the output of the applications is hidden by
+//! default.
+//!
+//! ### `--regenerate-guids`
+//!
+//! Available for the _init_ (`cargo wix init`) subcommand.
+//!
+//! Generates a new `UpgradeCode` and path component GUID when an existing
+//! `main.wxs` file is overwritten with the `--force` flag. By default, the
+//! GUIDs are read from the existing file and reused, unless they are set with
+//! the `--upgrade-guid`, `--path-guid`, or `--guid-from` options, or the
+//! matching fields in the `[package.metadata.wix]` section. A new upgrade code
+//! makes the next installer a different product, so it is installed alongside,
+//! instead of upgrading, the application installed with a previous installer.
+//!
+//! ### `--reinstall-mode`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Sets the [REINSTALLMODE] property of the installer, which controls the files
+//! that are replaced when an existing installation is upgraded or repaired. The
+//! value is passed to the WiX Toolset compiler (candle.exe) as the
+//! `ReinstallMode` variable, and the template uses the variable to set the
+//! property. The default is to not set the property, in which case the Windows
+//! Installer default of `omus` is used.
+//!
+//! With the default, a file is only replaced if the installed file has an older
+//! version. This can leave files without a version, or files with a version
+//! that was not incremented, unchanged after an upgrade. Use a value of `amus`
+//! to always replace all files during an upgrade, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --reinstall-mode amus
+//! ```
+//!
+//! The value must only contain the letters `a`, `c`, `d`, `e`, `m`, `o`, `p`,
+//! `s`, `u`, and `v`. The value is case insensitive. WiX Source (wxs) files
+//! generated before this option was added must be updated to use the
+//! `ReinstallMode` variable, see the template from the _print_ subcommand.
+//!
+//! ### `--retain-symbols`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Controls whether the WiX debug symbols (wixpdb) file is written next to the
+//! installer. The value must be either `yes` or `no`. The default is `no`, in
+//! which case the `-spdb` argument is passed to the WiX Toolset linker
+//! (light.exe) to suppress the file. Use `yes` to keep the file, which is
+//! useful for debugging issues with the installer, for example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --retain-symbols yes
+//! ```
+//!
+//! The `retain-symbols` field in the `[package.metadata.wix]` section accepts
+//! either the `yes` and `no` strings or a boolean.
+//!
+//! ### `--run-after-install`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Adds a deferred custom action that runs the command at the end of the
+//! installation, after the files are installed, for example to run a setup
+//! script or register a service:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix init --run-after-install "\"[APPLICATIONFOLDER]bin\setup.exe\" --register"
+//! ```
+//!
+//! The command runs from the installation folder and can use formatted
+//! properties, such as `[APPLICATIONFOLDER]`. It is not run when the product is
+//! repaired, modified, or uninstalled. The command runs in the context of the
+//! installation with the privileges of the installer, which is the LocalSystem
+//! account for a per-machine installation, so only commands from a trusted
+//! source should be used. The installation fails if the command fails, but the
+//! command is not undone if the installation is rolled back afterwards, so the
+//! command must handle any cleanup, or rollback, itself. A warning is displayed
+//! as a reminder of these implications. This can also be set with the
+//! `run-after-install` field in the `[package.metadata.wix]` section.
+//!
+//! ### `--schema-validate`
+//!
+//! Available for the _print_ (`cargo wix print`) subcommand with the `WXS`
+//! template.
+//!
+//! Checks the rendered WiX Source (wxs) file before it is printed or written.
+//! The XML must be well-formed and a subset of the rules from the WiX Toolset
+//! schema is checked: required attributes, such as the `Source` attribute for a
+//! `File` element, must be present, IDs for the same kind of element must be
+//! unique, and GUIDs, such as the `UpgradeCode` attribute, must be valid. Each
+//! problem is reported with its line number in the rendered file, and nothing
+//! is printed or written if any problems are found. This is not a complete
+//! validation against the schema, which is still performed by the compiler
+//! (candle.exe) when the installer is created, but it catches common mistakes in
+//! a customized template without needing the WiX Toolset.
+//!
+//! ### `--sign-all`
+//!
+//! Available for the _sign_ (`cargo wix sign`) subcommand.
+//!
+//! Signs a bundle (exe) and the engine within it. A bundle contains an engine
+//! that is extracted and executed when the bundle runs, so signing only the
+//! bundle leaves the engine unsigned. With this flag, the engine is detached
+//! from the bundle with the WiX Toolset's `insignia` application, signed,
+//! reattached, and then the bundle is signed. The default installer to sign is
+//! the bundle in the `target\wix` folder instead of the Windows installer (msi).
+//!
+//! The packages (msi) contained in a bundle are embedded into the bundle when
+//! it is created, so they must be signed _before_ the bundle is created, for
+//! example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix -p app
+//! C:\Path\to\Project> cargo wix sign -p app
+//! C:\Path\to\Project> cargo wix -p bundle
+//! C:\Path\to\Project> cargo wix sign -p bundle --sign-all
+//! ```
+//!
+//! ### `--sign-retries`
+//!
+//! Available for the _sign_ (`cargo wix sign`) subcommand.
+//!
+//! Sets the number of times signing is retried if the installer, or the engine
+//! of a bundle, is in use by another process. An antivirus scanner or the
+//! search indexer can briefly lock a file right after it is created, which is
+//! common on continuous integration (CI) agents with real-time scanning, and
+//! the signer (signtool.exe) then fails with a sharing violation. The failure
+//! is classified from the output of the signer, and signing is retried after a
+//! two second delay only for this failure. Certificate and timestamp failures
+//! are never retried. The default is to retry three times, and `0` disables
+//! retrying:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix sign --sign-retries 10
+//! ```
+//!
+//! ### `--skip-eula-dialog`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Excludes the license agreement dialog from the installer. A license is used
+//! for two independent purposes: it is displayed as the EULA in the license
+//! agreement dialog, and it is installed as a sidecar file alongside the `bin`
+//! folder. This flag only removes the dialog, so the license is still installed
+//! unless the `--skip-license-sidecar` flag is also used. This can also be set
+//! with the `skip-eula-dialog` field in the `[package.metadata.wix]` section.
+//!
+//! ### `--skip-license-sidecar`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Excludes the license sidecar file that is installed alongside the `bin`
+//! folder. The license is still displayed as the EULA in the license agreement
+//! dialog unless the `--skip-eula-dialog` flag is also used. This can also be
+//! set with the `skip-license-sidecar` field in the `[package.metadata.wix]`
+//! section.
+//!
+//! ### `--strict`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Fails if the culture from the `-c,--culture` option, or the `culture` field
+//! in the `[package.metadata.wix]` section, is not supported by the WixUI
+//! extension. The default is to fall back, with a warning, to the supported
+//! culture for the same language, such as `fr-FR` for `fr-CA`. The undefined
+//! variables found with the `--wxs-variable-check` flag are also reported as
+//! errors instead of warnings.
+//!
+//! The architecture of each binary is read from its header after building,
+//! and a binary built for a different architecture than the installer, such as
+//! an x86 binary in an x64 installer, is reported with a warning. This flag
+//! reports it as an error instead, which catches a mismatch between the
+//! `--target` option and how the binaries were actually built, e.g. with the
+//! `--no-build` flag or a custom `--build-command`.
+//!
+//! The WixUI strings found with the `--check-localization` flag are also
+//! reported as an error instead of a warning.
 //!
-//! Selects the package within a workspace. This is required if a project
-//! organized with a workspace. A workspace can have one or more members, where
-//! each member may have a separate installer. This option has no effect if the
-//! project does not use a workspace.
+//! ### `--strip`
 //!
-//! ### `--path-guid`
+//! Available for the default _create_ (`cargo wix`) subcommand.
 //!
-//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
-//! subcommands.
+//! Strips the symbols from the binaries when they are built, which makes the
+//! installer smaller. The `strip` setting of the Cargo profile used for the
+//! build is enabled with the `CARGO_PROFILE_<NAME>_STRIP` environment variable,
+//! such as `CARGO_PROFILE_RELEASE_STRIP=true`, so the profile in the package's
+//! manifest (Cargo.toml) does not need to be changed. This works for both the
+//! GNU and MSVC toolchains, since Cargo passes the setting to the linker. The
+//! environment variable is also set for a custom build command from the
+//! `--build-command` option, and it is up to the command to pass it along to
+//! Cargo. A warning is printed and nothing is stripped when used with the
+//! `--no-build` flag. The flag can also be set with the `strip` field in the
+//! `[package.metadata.wix]` section.
 //!
-//! Overrides the automatically generated GUID for the path component with an
-//! existing GUID in the hyphenated, uppercase format. The path GUID should only
-//! be generated once for a product/project. The same GUID should be used for
-//! all installer creations to ensure no artifacts are left after uninstalling
-//! and proper modification of the `PATH` environment variable.
+//! ### `-t,--target`
 //!
-//! ### `--product-icon`
+//! Available for the default _create_ (`cargo wix`) subcommand.
 //!
-//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
-//! subcommands.
+//! The rustc target triple to build the package for, and the architecture of the
+//! installer. The default is the host target. This option can be used multiple
+//! times to create an installer for each target in one run, for example:
 //!
-//! Sets the path to a 16x16 image file (.ico) that will be display as an icon in the
-//! Add/Remove Programs (ARP) control panel for the installed application.
+//! ```dos
+//! C:\Path\to\Project> cargo wix --target i686-pc-windows-msvc --target x86_64-pc-windows-msvc
+//! ```
 //!
-//! ### `--product-name`
+//! The package is built, and the installer is compiled and linked, for each
+//! target in turn. The installer file names include the platform, so the
+//! installers do not overwrite each other. If the `-o,--output` option is also
+//! used, then it must be a path to an existing folder or a path with a trailing
+//! slash. See the `--keep-going` flag to continue with the remaining targets if
+//! one of them fails.
+//!
+//! ### `--timeout`
+//!
+//! Available for the default _create_ (`cargo wix`), _sign_ (`cargo wix
+//! sign`), and _verify_ (`cargo wix verify`) subcommands.
+//!
+//! Sets the number of seconds to wait for each external application. For the
+//! _create_ subcommand, these are the build command, i.e. `cargo build` or the
+//! `--build-command` option, the compiler (candle.exe), the linker
+//! (light.exe), and the installer (msiexec.exe) with the `--install` flag. For
+//! the _sign_ subcommand, these are the signer (signtool.exe) and the insignia
+//! application of the WiX Toolset, and for the _verify_ subcommand, this is the
+//! signer. An application that does not finish within the timeout is stopped
+//! and an error is returned, so a stuck application, such as the signer
+//! waiting on a timestamp server, does not hang a CI job indefinitely, for
+//! example:
 //!
-//! Available for the _init_ (`cargo wix init`), _print_ (`cargo wix print`),
-//! and _sign_ (`cargo wix sign`) subcommands.
+//! ```dos
+//! C:\Path\to\Project> cargo wix sign --timestamp Comodo --timeout 120
+//! ```
 //!
-//! Overrides the `name` field of the package's manifest (Cargo.toml) as the
-//! product name within the installer. The product name can be changed after
-//! initialization or printing by directly modifying the WiX Source file (wxs)
-//! with a text editor.
+//! Only the application itself is stopped, not any processes that it started,
+//! such as the `rustc` processes started by `cargo build`. The value must be a
+//! whole number of seconds greater than zero. The default is no timeout.
 //!
 //! ### `-t,--timestamp`
 //!
@@ -1109,6 +2868,18 @@
 //! certificate. Valid aliases are: `Comodo` and `Versign`, which are case
 //! insensitive.
 //!
+//! ### `--uninstall-shortcut`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Adds an "Uninstall <product name>" shortcut to a folder named after the
+//! product in the Start Menu. The shortcut runs `msiexec /x [ProductCode]`, so
+//! the application can be uninstalled without finding it in the Add/Remove
+//! Programs control panel. The default is to only uninstall from the control
+//! panel. This can also be set with the `uninstall-shortcut` field in the
+//! `[package.metadata.wix]` section.
+//!
 //! ### `--upgrade-guid`
 //!
 //! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
@@ -1119,7 +2890,9 @@
 //! should only be generated once for a product/project. The same upgrade code
 //! should then be used for all installer creations of the same product/project. If
 //! a new GUID is used every time an installer is created, then each installer will
-//! be installing the same product but as separate installations.
+//! be installing the same product but as separate installations. The GUID is
+//! validated before the WiX Source (wxs) file is rendered, and an error naming
+//! the option, or the `upgrade-guid` field, is displayed if it is malformed.
 //!
 //! ### `-u,--url`
 //!
@@ -1133,6 +2906,26 @@
 //! be enabled after initialization by directly modifying the WiX Source (wxs)
 //! file with a text editor.
 //!
+//! ### `--verify-manifest`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Checks the WiX Source (wxs) files before compiling for values that are
+//! missing from the package's manifest (Cargo.toml) or are not defined. A
+//! reference to a compiler variable, such as `$(var.Example)`, is reported if
+//! the variable is not defined by cargo-wix or the `-C,--compiler-arg` option,
+//! unless it is defined or tested with a preprocessor instruction, such as
+//! `<?ifdef Example ?>`, in the same file or a file it includes with the
+//! `<?include?>` instruction. The `Description`, `Manufacturer`, and
+//! `Name` attributes are reported if they are empty, and the `Package` element is
+//! reported if it does not have a `Description` attribute, since these are
+//! rendered from the `description`, `authors`, and `name` fields during
+//! initialization. Each problem is printed with the file and line number, and
+//! an error occurs instead of compiling. This is useful for catching an
+//! installer with blank fields in the Add/Remove Programs control panel at build
+//! time. The `verify-manifest` field in the `[package.metadata.wix]` section can
+//! also be used. The default is to not check.
+//!
 //! ### `-V,--version`
 //!
 //! Available for all subcommands.
@@ -1154,6 +2947,61 @@
 //!
 //! Speicifies path to the installer(msi) to be signed.
 //!
+//! ### `--wix-root`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Sets the path to the root folder of the WiX Toolset installation, which is
+//! the same as the value of the `WIX` system environment variable, for
+//! example:
+//!
+//! ```dos
+//! C:\Path\to\Project> cargo wix --wix-root "C:\Program Files (x86)\WiX Toolset v3.11"
+//! ```
+//!
+//! The compiler (candle.exe) and linker (light.exe) are found in the `bin`
+//! folder of the root folder, as if the `WIX` system environment variable was
+//! set to the path for the duration of the command. This option takes
+//! precedence over the `WIX` system environment variable, but the
+//! `-b,--bin-path` option takes precedence over this option.
+//!
+//! ### `--wxs-template-dir`
+//!
+//! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
+//! subcommands.
+//!
+//! Uses the templates in a folder instead of the templates embedded in
+//! cargo-wix, which allows an organization to share a set of customized
+//! templates across all of its packages. The WiX Source (wxs) template is read
+//! from the `main.wxs.mustache` file and a license template from the file with
+//! the same name as the embedded template, e.g. `MIT.rtf.mustache`, where a
+//! translation of a license is read from a sub-folder named after the culture,
+//! e.g. `fr-FR\MIT.rtf.mustache`. The embedded template is used for any
+//! template the folder does not contain. The `wxs-template-dir` field in the
+//! `[package.metadata.wix]` section can also be used, where a relative path is
+//! relative to the package's manifest (Cargo.toml). An error occurs if the
+//! folder does not exist.
+//!
+//! ### `--wxs-variable-check`
+//!
+//! Available for the default _create_ (`cargo wix`) subcommand.
+//!
+//! Checks the WiX Source (wxs) files before compiling for references to compiler
+//! variables that are not defined, such as a misspelled
+//! `$(var.CargoTargetBinDr)`. A variable is defined if it is one of the
+//! variables defined by cargo-wix, it is defined with the `-C,--compiler-arg`
+//! or `--property` options, or it is defined or tested with a preprocessor
+//! instruction, such as `<?ifdef Example ?>`, in the same file or a file it
+//! includes with the `<?include?>` instruction. An included file is found
+//! relative to the including file or an include path added with the
+//! `-C -I<path>` option. Each undefined variable is printed as a warning with
+//! the file and line number, which is
+//! easier to act on than the error from the compiler (candle.exe). Use the
+//! `--strict` flag to fail instead. The `--verify-manifest` flag already
+//! includes this check. The `wxs-variable-check` field in the
+//! `[package.metadata.wix]` section can also be used. The default is to not
+//! check.
+//!
 //! ### `-y,--year`
 //!
 //! Available for the _init_ (`cargo wix init`) and _print_ (`cargo wix print`)
@@ -1184,10 +3032,12 @@
 //! [Microsoft Office]: https://products.office.com/en-us/home
 //! [Microsoft Notepad]: https://en.wikipedia.org/wiki/Microsoft_Notepad
 //! [PowerShell]: https://github.com/PowerShell/PowerShell
+//! [REINSTALLMODE]: https://learn.microsoft.com/en-us/windows/win32/msi/reinstallmode
 //! [`repository`]: https://doc.rust-lang.org/cargo/reference/manifest.html#package-metadata
 //! [Rich Text Format]: https://en.wikipedia.org/wiki/Rich_Text_Format
 //! [Rust]: https://www.rust-lang.org
 //! [sidecar]: https://en.wikipedia.org/wiki/Sidecar_file
+//! [SPDX expression]: https://spdx.github.io/spdx-spec/v2.3/SPDX-license-expressions/
 //! [SignTool]: https://msdn.microsoft.com/en-us/library/windows/desktop/aa387764(v=vs.85).aspx
 //! [statically link the CRT]: https://doc.rust-lang.org/reference/linkage.html#static-and-dynamic-c-runtimes
 //! [`std::process::Command`]: https://doc.rust-lang.org/std/process/struct.Command.html
@@ -1208,23 +3058,28 @@
 //! [WXS]: ../wix/enum.Template.html
 //! [XML]: https://en.wikipedia.org/wiki/XML
 
-use clap::{Arg, ArgAction, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 
 use env_logger::fmt::Color as LogColor;
-use env_logger::Builder;
+use env_logger::{Builder, WriteStyle};
 
 use log::{Level, LevelFilter};
 
-use std::io::Write;
+use std::io::{self, IsTerminal, Write};
 
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
+use wix::check;
 use wix::clean;
+use wix::config;
 use wix::create;
+use wix::extensions;
+use wix::info;
 use wix::initialize;
 use wix::print;
 use wix::purge;
 use wix::sign;
+use wix::verify;
 use wix::{Template, BINARY_FOLDER_NAME, WIX_PATH_KEY};
 
 pub const PKG_DESCRIPTION: &str = env!("CARGO_PKG_DESCRIPTION");
@@ -1233,17 +3088,83 @@ pub const PKG_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const SUBCOMMAND_NAME: &str = "wix";
 
+/// Gets the path to a package's manifest from either the `INPUT` positional
+/// argument or the `--manifest-path` option.
+fn manifest_input(matches: &ArgMatches) -> Option<&str> {
+    matches
+        .get_one::<String>("INPUT")
+        .or_else(|| matches.get_one("manifest-path"))
+        .map(String::as_str)
+}
+
+/// Splits the values of a repeatable image option, i.e. `--banner` or
+/// `--dialog`, into the image for all cultures and the images that are
+/// prefixed with a culture and a colon, e.g. `fr-FR:img\Banner_fr.bmp`.
+///
+/// A single letter before the colon is a drive letter, not a culture.
+fn culture_images<'a>(
+    matches: &'a ArgMatches,
+    id: &str,
+) -> (Option<&'a str>, Vec<(&'a str, &'a str)>) {
+    let mut image = None;
+    let mut culture_images = Vec::new();
+    for value in matches.get_many::<String>(id).into_iter().flatten() {
+        match value.split_once(':') {
+            Some((culture, path))
+                if culture.len() > 1
+                    && culture
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-') =>
+            {
+                culture_images.push((culture, path));
+            }
+            _ => image = Some(value.as_str()),
+        }
+    }
+    (image, culture_images)
+}
+
 fn main() {
+    // The after install launch flag for the `init` and `print` subcommands.
+    let after_install_launch = Arg::new("after-install-launch")
+        .help("Adds a checkbox to launch the application after installation")
+        .long_help(
+            "Adds a checkbox to the last dialog of the installer to launch one \
+             of the installed binaries after the installation completes. The \
+             binary from the 'default-run' field of the package's manifest, or \
+             with the same name as the package, is launched, or the first \
+             binary if none match. Use the '--launch-binary' option to select a \
+             different binary.",
+        )
+        .long("after-install-launch")
+        .action(ArgAction::SetTrue);
+    // The author separator option for the `init` and `print` subcommands.
+    let author_separator = Arg::new("author-separator")
+        .help("The separator between the authors used for the manufacturer")
+        .long_help(
+            "Sets the separator between the authors when all of the authors \
+             from the 'authors' field of the package's manifest (Cargo.toml) \
+             are used for the manufacturer. The default is a semicolon followed \
+             by a space ('; '). This is not used with the '-m,--manufacturer' \
+             option.",
+        )
+        .long("author-separator")
+        .num_args(1);
     // The banner option for the `init` and `print` subcommands.
     let banner = Arg::new("banner")
         .help("A path to an image file (.bmp) for the installer's banner")
         .long_help(
             "Sets the path to a bitmap (.bmp) image file that will be \
              displayed across the top of each dialog in the installer. The banner \
-             image dimensions should be 493 x 58 pixels.",
+             image dimensions should be 493 x 58 pixels. Prefix the path with a \
+             culture and a colon, e.g. 'fr-FR:Banner_fr.bmp', and use this option \
+             repeatedly to use a different image for each culture. The image for \
+             the culture of the installer is used, or else the image without a \
+             culture prefix.",
         )
         .long("banner")
         .short('b')
+        .action(ArgAction::Append)
         .num_args(1);
     // The binaries option for the `init` and `print` subcommands.
     let binaries = Arg::new("binaries")
@@ -1257,13 +3178,31 @@ fn main() {
              defined; otherwise, all binaries defined in the package's manifest \
              in each '[[bin]]' section are included. This option overrides any \
              and all binaries defined in the package's manifest. Use this option \
-             repeatedly to include multiple binaries.",
+             repeatedly to include multiple binaries. Append a colon and a folder \
+             name to the path, e.g. 'helper.exe:tools', to install the binary in \
+             a subfolder of the 'bin' folder. A folder, or a file name with the \
+             '*' and '?' wildcards, e.g. 'target\\release\\*.exe', includes all \
+             of the matching executables.",
         )
         .long("binary")
         .action(ArgAction::Append)
         .short('B')
         .num_args(1);
     // The description option for the `init` and `print` subcommands.
+    // The culture option for the `init` and `print` subcommands.
+    let culture = Arg::new("culture")
+        .help("The culture code for a generated EULA")
+        .long_help(
+            "Sets the culture for the EULA generated from the 'license' field \
+             of the package's manifest (Cargo.toml). The translation of the \
+             license for the culture is used if one is available; otherwise, the \
+             English license is used. The codes are case insensitive, and a \
+             numeric LCID, such as 1036, is also accepted. Use 'auto' to derive \
+             the culture from the locale of the system.",
+        )
+        .long("culture")
+        .short('c')
+        .num_args(1);
     let description = Arg::new("description")
         .help("A string describing the application in the installer")
         .long_help(
@@ -1274,17 +3213,68 @@ fn main() {
         .long("description")
         .short('d')
         .num_args(1);
+    // The deterministic component GUIDs flag for the `init` and `print` subcommands
+    let deterministic_component_guids = Arg::new("deterministic-component-guids")
+        .help("Derives the GUIDs of the components from their install locations")
+        .long_help(
+            "Derives version 5 UUIDs for the GUIDs of the binary, license, and \
+             PATH environment variable components from their install locations, \
+             so identical inputs always yield identical GUIDs. This is useful for \
+             patching and for diffing generated WiX Source (wxs) files. The \
+             default is to let the WiX Toolset generate the GUIDs of the file \
+             components and randomly generate the GUID of the path component.",
+        )
+        .long("deterministic-component-guids")
+        .action(ArgAction::SetTrue);
     // The dialog option for the `init` and `print` subcommands.
     let dialog = Arg::new("dialog")
         .help("A path to an image file (.bmp) for the installer's welcome dialog")
         .long_help(
             "Sets the path to a bitmap (.bmp) image file that will be \
              displayed to the left on the first dialog of the installer. The dialog \
-             image dimensions should be 493 x 312 pxiels.",
+             image dimensions should be 493 x 312 pxiels. Prefix the path with a \
+             culture and a colon, e.g. 'fr-FR:Dialog_fr.bmp', and use this option \
+             repeatedly to use a different image for each culture.",
         )
         .long("dialog")
         .short('D')
+        .action(ArgAction::Append)
         .num_args(1);
+    // The dialog set option for the `init` and `print` subcommands.
+    let dialog_set = Arg::new("dialog-set")
+        .help("The set of dialogs for the installer")
+        .long_help(
+            "Selects the set of dialogs from the WixUI dialog library, which is \
+             substituted into the 'UIRef' element. The license agreement dialog of \
+             the set is skipped if there is no EULA. The default is 'feature-tree'.",
+        )
+        .long("dialog-set")
+        .value_parser(["feature-tree", "install-dir", "mondo"])
+        .num_args(1);
+    // The dump context option for the `init` and `print` subcommands.
+    let dump_context = Arg::new("dump-context")
+        .help("A path to write the context for rendering the template as JSON")
+        .long_help(
+            "Writes all of the values that are substituted into the template to \
+             the file as JSON before the template is rendered. This is useful for \
+             debugging an unexpected value in the rendered file, since the values \
+             are resolved with all of the other options applied.",
+        )
+        .long("dump-context")
+        .num_args(1);
+    // The dump toolset commands flag for the `create`, `extensions`, and `sign`
+    // subcommands.
+    let dump_toolset_commands = Arg::new("dump-toolset-commands")
+        .help("Prints each external command before it runs")
+        .long_help(
+            "Prints each external command, such as the build, the WiX Toolset \
+             applications, and the signer, to stderr right before it runs as a \
+             line that can be copied and pasted into a shell, including the \
+             working directory and the environment variables set for the \
+             command.",
+        )
+        .long("dump-toolset-commands")
+        .action(ArgAction::SetTrue);
     // The eula option for the `init` and `print` subcommands.
     let eula = Arg::new("eula")
         .help("A path to a RTF file (.rtf) for the installer's license agreement dialog")
@@ -1329,6 +3319,81 @@ fn main() {
         .long("url")
         .short('u')
         .num_args(1);
+    // The install scope option for the `init` and `print` subcommands.
+    let install_scope = Arg::new("install-scope")
+        .help("The installation scope: per-machine, per-user, or auto")
+        .long_help(
+            "Sets the installation scope for the installer. A per-machine \
+             installation requires elevated privileges, while a per-user \
+             installation does not. The 'auto' value uses the per-user scope if \
+             the '--no-path' flag is also used and the per-machine scope \
+             otherwise. With the per-user scope, the application is installed \
+             to the user's programs folder, i.e. '%LOCALAPPDATA%\\Programs', and \
+             the user's PATH environment variable is modified instead of the \
+             system's PATH environment variable. The default is the per-machine scope.",
+        )
+        .long("install-scope")
+        .value_parser(["per-machine", "per-user", "auto"])
+        .num_args(1);
+    // The keep author emails flag for the `init` and `print` subcommands.
+    let keep_author_emails = Arg::new("keep-author-emails")
+        .help("Keeps the emails of the authors used for the manufacturer")
+        .long_help(
+            "Keeps the email of each author from the 'authors' field of the \
+             package's manifest (Cargo.toml) in the manufacturer. The default \
+             is to remove the emails. This is not used with the \
+             '-m,--manufacturer' option.",
+        )
+        .long("keep-author-emails")
+        .action(ArgAction::SetTrue);
+    // The feature map option for the `init` and `print` subcommands.
+    let feature_map = Arg::new("feature-map")
+        .help("A CARGO_FEATURE=TITLE optional feature for the installer")
+        .long_help(
+            "Adds an optional feature with the title to the feature tree of \
+             the installer for the Cargo feature of the package. The feature is \
+             only included if the Cargo feature is enabled when creating the \
+             installer, and the binaries that require the Cargo feature are \
+             installed with it. Use this option multiple times to add multiple \
+             features.",
+        )
+        .long("feature-map")
+        .num_args(1)
+        .action(ArgAction::Append);
+    // The GUID seed option for the `init` and `print` subcommands.
+    let guid_from = Arg::new("guid-from")
+        .help("A seed for reproducible upgrade code and path GUIDs")
+        .long_help(
+            "Derives the upgrade code and path component GUIDs as version 5 \
+             UUIDs from the seed, such as a product identifier, so the same seed \
+             always yields the same GUIDs. The '--upgrade-guid' and '--path-guid' \
+             options take precedence.",
+        )
+        .long("guid-from")
+        .num_args(1);
+    // The launch binary option for the `init` and `print` subcommands.
+    let launch_binary = Arg::new("launch-binary")
+        .help("The name of the binary to launch after installation")
+        .long_help(
+            "Sets the binary to launch after installation when the \
+             '--after-install-launch' flag is used. The value is the name of one \
+             of the binaries included in the installer without the file \
+             extension.",
+        )
+        .long("launch-binary")
+        .requires("after-install-launch")
+        .num_args(1);
+    // The manifest path option for all subcommands with the `INPUT` argument
+    let manifest_path = Arg::new("manifest-path")
+        .help("A path to a package's manifest (Cargo.toml)")
+        .long_help(
+            "An alternative to the positional argument for the path to a \
+             package's manifest (Cargo.toml), consistent with other cargo \
+             subcommands. It cannot be used with the positional argument.",
+        )
+        .long("manifest-path")
+        .conflicts_with("INPUT")
+        .num_args(1);
     // The manufacturer option for the `init` and `print` subcommands
     let manufacturer = Arg::new("manufacturer")
         .help("A string for the Add/Remove Programs control panel's Manufacturer")
@@ -1341,6 +3406,99 @@ fn main() {
         .long("manufacturer")
         .short('m')
         .num_args(1);
+    // The manufacturer from option for the `init` and `print` subcommands
+    let manufacturer_from = Arg::new("manufacturer-from")
+        .help("Selects the authors used for the manufacturer: first, all, or an index")
+        .long_help(
+            "Selects which authors from the 'authors' field of the package's \
+             manifest (Cargo.toml) are used for the manufacturer: 'first' for the \
+             first author, 'all' for all of the authors separated by semicolons, \
+             or the zero-based index of an author. The default is 'all'. This is \
+             not used with the '-m,--manufacturer' option.",
+        )
+        .long("manufacturer-from")
+        .num_args(1);
+    // The no modify flag for the `init` and `print` subcommands
+    let no_modify = Arg::new("no-modify")
+        .help("Disables the Change button in the Add/Remove Programs control panel")
+        .long_help(
+            "Sets the 'ARPNOMODIFY' property, so the features of an \
+             installation cannot be changed from the Add/Remove Programs control \
+             panel. The application can still be uninstalled.",
+        )
+        .long("no-modify")
+        .action(ArgAction::SetTrue);
+    // The no repair flag for the `init` and `print` subcommands
+    let no_repair = Arg::new("no-repair")
+        .help("Disables the Repair button in the Add/Remove Programs control panel")
+        .long_help(
+            "Sets the 'ARPNOREPAIR' property, so an installation cannot be \
+             repaired from the Add/Remove Programs control panel.",
+        )
+        .long("no-repair")
+        .action(ArgAction::SetTrue);
+    // The no upgrade flag for the `init` and `print` subcommands
+    let no_upgrade = Arg::new("no-upgrade")
+        .help("Omits the MajorUpgrade element to install versions side by side")
+        .long_help(
+            "Omits the 'MajorUpgrade' element, so installing a new version does \
+             not remove a previously installed version. Each version must be \
+             uninstalled separately.",
+        )
+        .long("no-upgrade")
+        .action(ArgAction::SetTrue);
+    // The skip EULA dialog flag for the `init` and `print` subcommands
+    let skip_eula_dialog = Arg::new("skip-eula-dialog")
+        .help("Excludes the license agreement dialog from the installer")
+        .long_help(
+            "Skips the license agreement dialog, so the EULA is not displayed \
+             during installation. The license is still installed as a sidecar \
+             file unless the '--skip-license-sidecar' flag is also used.",
+        )
+        .long("skip-eula-dialog")
+        .action(ArgAction::SetTrue);
+    // The skip license sidecar flag for the `init` and `print` subcommands
+    let skip_license_sidecar = Arg::new("skip-license-sidecar")
+        .help("Excludes the license sidecar file from the installer")
+        .long_help(
+            "Skips installing the license as a file alongside the 'bin' folder. \
+             The license is still displayed in the license agreement dialog \
+             unless the '--skip-eula-dialog' flag is also used.",
+        )
+        .long("skip-license-sidecar")
+        .action(ArgAction::SetTrue);
+    // The run after install option for the `init` and `print` subcommands
+    let run_after_install = Arg::new("run-after-install")
+        .help("A command to run at the end of the installation")
+        .long_help(
+            "Adds a deferred custom action that runs the command from the \
+             installation folder after the files are installed, with the \
+             privileges of the installer. The command is not undone if the \
+             installation is rolled back.",
+        )
+        .long("run-after-install")
+        .num_args(1);
+    // The uninstall shortcut flag for the `init` and `print` subcommands
+    let uninstall_shortcut = Arg::new("uninstall-shortcut")
+        .help("Adds a Start Menu shortcut that uninstalls the application")
+        .long_help(
+            "Adds a shortcut to the Start Menu that uninstalls the application \
+             with 'msiexec /x [ProductCode]'. The default is to only uninstall \
+             from the Add/Remove Programs control panel.",
+        )
+        .long("uninstall-shortcut")
+        .action(ArgAction::SetTrue);
+    // The no path flag for the `init` and `print` subcommands
+    let no_path = Arg::new("no-path")
+        .help("Excludes adding the 'bin' folder to the PATH environment variable")
+        .long_help(
+            "Excludes the component and feature that add the 'bin' folder of \
+             the installation to the PATH environment variable. The feature can \
+             be added after initialization by directly modifying the WiX Source \
+             (wxs) file with a text editor.",
+        )
+        .long("no-path")
+        .action(ArgAction::SetTrue);
     // The owner option for the `init` and `print` subcommands
     let owner = Arg::new("owner")
         .help("A string for a generated license's copyright holder")
@@ -1354,7 +3512,7 @@ fn main() {
         .long("owner")
         .short('O')
         .num_args(1);
-    // The package option for the `create`, `init`, and `print` subcommands
+    // The package option for the `create`, `clean`, `init`, and `print` subcommands
     let package = Arg::new("package")
         .help("The name of the package in the current workspace")
         .long_help(
@@ -1385,7 +3543,8 @@ fn main() {
         .long_help(
             "Sets the path to an image file that will be displayed as an \
              icon in the Add/Remove Programs (ARP) control panel for the installed \
-             application.",
+             application. Use 'from-binary' to extract the icon embedded in the \
+             resources of the main binary when creating the installer.",
         )
         .long("product-icon")
         .num_args(1);
@@ -1415,6 +3574,31 @@ fn main() {
         )
         .long("upgrade-guid")
         .num_args(1);
+    // The template folder option for the `init` and `print` subcommands.
+    let wxs_template_dir = Arg::new("wxs-template-dir")
+        .help("A folder of templates that overrides the embedded templates")
+        .long_help(
+            "Reads the 'main.wxs.mustache' file and the license templates, such as \
+             'MIT.rtf.mustache', from the folder instead of using the embedded \
+             templates. A translated license template is read from a sub-folder \
+             named after the culture, such as 'fr-FR\\MIT.rtf.mustache'. The \
+             embedded template is used for any template the folder does not \
+             contain.",
+        )
+        .long("wxs-template-dir")
+        .num_args(1);
+    // The timeout option for the default _create_ and `sign` subcommands.
+    let timeout = Arg::new("timeout")
+        .help("The number of seconds to wait for each external application")
+        .long_help(
+            "Stops an external application, such as the compiler (candle.exe), \
+             linker (light.exe), or signer (signtool.exe), and fails if it does \
+             not finish within the number of seconds. This prevents a stuck \
+             application from hanging a CI job indefinitely. The default is no \
+             timeout.",
+        )
+        .long("timeout")
+        .num_args(1);
     // The "global" verbose flag for all subcommands.
     let verbose = Arg::new("verbose")
         .help("The verbosity level for logging statements")
@@ -1446,6 +3630,32 @@ fn main() {
             Command::new(SUBCOMMAND_NAME)
                 .version(PKG_VERSION)
                 .about(PKG_DESCRIPTION)
+                .arg(Arg::new("allow-unpublished")
+                    .help("Silences the warning for a package with 'publish = false'")
+                    .long_help("A warning is displayed when an installer is created \
+                        for a package with 'publish = false' in its manifest \
+                        (Cargo.toml), since it might be an internal or experimental \
+                        crate. This flag silences the warning.")
+                    .long("allow-unpublished")
+                    .action(ArgAction::SetTrue))
+                .arg(Arg::new("append-args")
+                    .help("Appends the compiler and linker arguments to the arguments in the manifest")
+                    .long_help("Passes the arguments from the '-C,--compiler-arg' \
+                        and '-L,--linker-arg' options after the arguments from the \
+                        'compiler-args' and 'linker-args' fields in the \
+                        [package.metadata.wix] section, instead of replacing them.")
+                    .long("append-args")
+                    .action(ArgAction::SetTrue))
+                .arg(Arg::new("arp-size")
+                    .help("The estimated size, in kilobytes, shown in Add/Remove Programs")
+                    .long_help("Overrides the estimated size of the installed \
+                        application, in kilobytes, that is displayed in the \
+                        Add/Remove Programs control panel. The default is the total \
+                        size of the files referenced by the 'File' elements in the \
+                        WiX Source (wxs) files. This overrides the 'arp-size' field in \
+                        the [package.metadata.wix] section.")
+                    .long("arp-size")
+                    .num_args(1))
                 .arg(Arg::new("bin-path")
                      .help(format!(
                          "A path to the WiX Toolset's '{BINARY_FOLDER_NAME}' folder"))
@@ -1460,10 +3670,133 @@ fn main() {
                      .long("bin-path")
                      .short('b')
                      .num_args(1))
+                .arg(Arg::new("build-command")
+                    .help("A command to run instead of 'cargo build'")
+                    .long_help("Runs the command with the system shell, i.e. \
+                        'cmd /C' on Windows and 'sh -c' on other platforms, in \
+                        place of the 'cargo build' command. The command is run from \
+                        the folder containing the package's manifest (Cargo.toml) \
+                        with the CARGO_TARGET_DIR, CARGO_WIX_PROFILE, \
+                        CARGO_WIX_TARGET, and CARGO_WIX_TARGET_BIN_DIR environment \
+                        variables set. The installer is not created if the command \
+                        exits with a non-zero code.")
+                    .long("build-command")
+                    .conflicts_with("no-build")
+                    .num_args(1))
+                .arg(Arg::new("bundle-upgrade-guid")
+                    .help("A string formatted as a hyphenated, uppercase UUID for a bundle's upgrade code")
+                    .long_help("Defines the 'BundleUpgradeCode' variable for the WiX \
+                        compiler (candle.exe) with the GUID. This can be used for the \
+                        'UpgradeCode' attribute of the 'Bundle' tag in a bundle-based \
+                        WiX Source (wxs) file instead of manually editing the GUID into \
+                        the file. The variable is not defined if this option is not used \
+                        and the 'bundle-upgrade-guid' field is not in the \
+                        '[package.metadata.wix]' section of the package's manifest.")
+                    .long("bundle-upgrade-guid")
+                    .num_args(1))
+                .arg(Arg::new("bundle-version")
+                    .help("A string for a bundle's version number")
+                    .long_help("Defines the 'BundleVersion' variable for the WiX \
+                        compiler (candle.exe) with the version. This can be used for \
+                        the 'Version' attribute of the 'Bundle' tag in a bundle-based \
+                        WiX Source (wxs) file. The version is converted to the format \
+                        required by the WiX Toolset in the same way as the \
+                        '-i,--install-version' option. The variable is not defined if \
+                        this option is not used and the 'bundle-version' field is not in \
+                        the '[package.metadata.wix]' section of the package's manifest.")
+                    .long("bundle-version")
+                    .num_args(1))
+                .arg(Arg::new("changelog")
+                    .help("A path to a changelog for the Add/Remove Programs control panel's comments")
+                    .long_help("Extracts the first entry of the section with a heading \
+                        matching the version of the installer from the changelog and \
+                        defines the 'Changelog' variable for the WiX compiler \
+                        (candle.exe) with it, truncated to 255 characters. The template \
+                        uses the variable for the 'ARPCOMMENTS' property, which is \
+                        displayed in the Add/Remove Programs control panel. Only the \
+                        first entry is used because the property is a single line. A \
+                        changelog is not used by default, so an existing CHANGELOG.md \
+                        file does not change the installer unless the 'changelog' field \
+                        in the [package.metadata.wix] section is 'true'. The variable is \
+                        not defined if a matching section is not found.")
+                    .long("changelog")
+                    .num_args(1))
+                .subcommand(Command::new("check")
+                    .version(PKG_VERSION)
+                    .about("Checks the WiX Source (wxs) files for common mistakes")
+                    .long_about("Scans the WiX Source (wxs) files of a package, without \
+                        compiling them, for deprecated variables, a missing \
+                        'UpgradeCode' attribute, hard-coded absolute paths, and \
+                        placeholder GUIDs. Each finding is printed with the path to \
+                        the file and the line number. An error occurs if any findings \
+                        are reported.")
+                    .arg(Arg::new("fix")
+                        .help("Rewrites the deprecated variables in the WiX Source (wxs) files")
+                        .long_help("Replaces the deprecated '$(var.Platform)' and \
+                            '$(var.Profile)' variables with the '$(sys.BUILDARCH)' and \
+                            '$(var.CargoProfile)' variables before checking the files. \
+                            The original content of a rewritten file is saved with the \
+                            '.bak' extension appended to its name.")
+                        .long("fix")
+                        .action(ArgAction::SetTrue))
+                    .arg(Arg::new("include")
+                        .help("A WiX Source (wxs) file to check")
+                        .long_help("Checks only the WiX Source (wxs) files from this \
+                            option instead of the files in the 'wix' folder and the \
+                            'include' field in the '[package.metadata.wix]' section. \
+                            Use this option multiple times to check multiple files.")
+                        .long("include")
+                        .short('I')
+                        .num_args(1)
+                        .action(ArgAction::Append))
+                    .arg(package.clone())
+                    .arg(verbose.clone())
+                    .arg(Arg::new("INPUT")
+                         .help("A path to a package's manifest (Cargo.toml)")
+                         .long_help("The WiX Source (wxs) files of this package are \
+                            checked. This is optional and the default is to use the \
+                            current working directory (cwd).")
+                         .index(1))
+                    .arg(manifest_path.clone()))
+                .arg(Arg::new("check-localization")
+                    .help("Reports the WixUI strings that are displayed in English")
+                    .long_help("Reports the common strings of the WixUI dialogs that \
+                        are displayed in English because the WixUI extension does not \
+                        have localized strings for the culture and the strings are not \
+                        in the localization file from the '-l,--locale' option. The \
+                        strings are reported with a warning, or an error with the \
+                        '--strict' flag, before the installer is created.")
+                    .long("check-localization")
+                    .action(ArgAction::SetTrue))
+                .arg(Arg::new("checksum-manifest")
+                    .help("A path to a file for the SHA-256 checksums of the created files")
+                    .long_help("Writes a '<sha256>  <path>' line for each installer, \
+                        transform, and zip archive created in the run to the file after \
+                        all of the installers are created, in the format of the \
+                        'sha256sum' tool. The paths are relative to the folder of the \
+                        file. The folder is created if it does not exist.")
+                    .long("checksum-manifest")
+                    .num_args(1))
                 .subcommand(Command::new("clean")
                     .version(PKG_VERSION)
-                    .about("Deletes the 'target\\wix' folder")
-                    .long_about("Deletes the 'target\\wix' folder if it exists.")
+                    .about("Deletes the 'target\\wix' folder and created installers")
+                    .long_about("Deletes the 'target\\wix' folder if it exists, \
+                        and the installers at the output location if one is \
+                        specified with the '-o,--output' option or the 'output' \
+                        field in the '[package.metadata.wix]' section.")
+                    .arg(Arg::new("output")
+                        .help("The output location of the created installers")
+                        .long_help("Removes the installers named after the package, \
+                            and their WiX debug symbols (wixpdb), from the folder if \
+                            the path is to an existing folder or has a trailing '/' or \
+                            '\\'. Otherwise, the path is the installer file to remove. \
+                            Other files at the output location are not removed. This \
+                            should be the same value used with the '-o,--output' option \
+                            when creating the installer.")
+                        .long("output")
+                        .short('o')
+                        .num_args(1))
+                    .arg(package.clone())
                     .arg(verbose.clone())
                     .arg(Arg::new("INPUT")
                          .help("A path to a package's manifest (Cargo.toml)")
@@ -1471,13 +3804,52 @@ fn main() {
                             alongside the package's manifest will be removed. This \
                             is optional and the default is to use the current \
                             working directory (cwd).")
-                         .index(1)))
+                         .index(1))
+                    .arg(manifest_path.clone()))
+                .arg(Arg::new("color")
+                    .help("Coloring of the log statements and errors")
+                    .long_help("Controls the coloring of the log statements and \
+                        errors written to stderr. The 'auto' value uses color only \
+                        if stderr is a terminal, 'always' forces color, and 'never' \
+                        disables color, which is useful for CI logs and redirected \
+                        output.")
+                    .long("color")
+                    .value_parser(["auto", "always", "never"])
+                    .default_value("auto")
+                    .global(true)
+                    .num_args(1))
+                .subcommand(Command::new("config")
+                    .version(PKG_VERSION)
+                    .about("Inspects the configuration of a package")
+                    .subcommand_required(true)
+                    .subcommand(Command::new("show")
+                        .version(PKG_VERSION)
+                        .about("Prints the value and source of each configuration field")
+                        .long_about("Prints each supported field of the \
+                            '[package.metadata.wix]' section with its value and source. \
+                            The source is 'package' for the '[package.metadata.wix]' \
+                            section, 'workspace' for the '[workspace.metadata.wix]' \
+                            section, or 'default' if the field is not set. A CLI option \
+                            always overrides the printed value.")
+                        .arg(package.clone())
+                        .arg(verbose.clone())
+                        .arg(Arg::new("INPUT")
+                             .help("A path to a package's manifest (Cargo.toml)")
+                             .long_help("The configuration of this package is printed. \
+                                This is optional and the default is to use the current \
+                                working directory (cwd).")
+                             .index(1))
+                        .arg(manifest_path.clone())))
                 .arg(Arg::new("culture")
                     .help("The culture code for localization")
                     .long_help("Sets the culture for localization. Use with the \
                         '-l,--locale' option. See the WixUI localization \
                         documentation for more information about acceptable culture \
-                        codes. The codes are case insensitive.")
+                        codes. The codes are case insensitive, and a numeric LCID, \
+                        such as 1036, is also accepted. Use 'auto' to derive the \
+                        culture from the locale of the system. A comma-separated list \
+                        creates the installer for the first culture and a transform \
+                        for each of the other cultures.")
                     .long("culture")
                     .short('c')
                     .num_args(1))
@@ -1497,13 +3869,28 @@ fn main() {
                     .num_args(1)
                     .action(ArgAction::Append)
                     .allow_hyphen_values(true))
+                .arg(Arg::new("component-group-ref")
+                    .help("The ID of a component group to install with the binaries")
+                    .long_help("Adds a 'ComponentGroupRef' tag for the component \
+                        group to the main feature of the installer by defining the \
+                        'ComponentGroupRefs' variable for the WiX compiler \
+                        (candle.exe). This is useful for installing the files of a \
+                        fragment generated with the WiX harvester (heat.exe). Use this \
+                        option multiple times to add multiple component groups.")
+                    .long("component-group-ref")
+                    .num_args(1)
+                    .action(ArgAction::Append))
                 .arg(Arg::new("target")
                     .help("The cargo target to build the WiX installer for.")
                     .long_help("Tells cargo to build the given target, and instructs \
-                        WiX to build an installer targeting the right architecture.")
+                        WiX to build an installer targeting the right architecture. \
+                        This option can be used multiple times to create an installer \
+                        for each target in a single run, in which case the output, if \
+                        any, must be a folder.")
                     .long("target")
                     .short('t')
-                    .num_args(1))
+                    .num_args(1)
+                    .action(ArgAction::Append))
                 .arg(Arg::new("debug-build")
                     .help("Builds the package using the Debug profile")
                     .long_help("Uses the Debug profile when building the package \
@@ -1531,6 +3918,94 @@ fn main() {
                     .long("dbg-name")
                     .short('D')
                     .action(ArgAction::SetTrue))
+                .arg(Arg::new("dry-run")
+                    .help("Prints the commands instead of running them")
+                    .long_help("Prints the commands for building the binaries, \
+                        compiling and linking the installer, and launching the \
+                        installer instead of running them. Nothing is built or \
+                        written to the disk.")
+                    .long("dry-run")
+                    .action(ArgAction::SetTrue))
+                .arg(Arg::new("dump-defines")
+                    .help("Prints the variables defined for the WiX compiler")
+                    .long_help("Prints each variable that would be defined for the \
+                        WiX compiler (candle.exe) with the '-d' argument as \
+                        'NAME=VALUE' and exits without building, compiling, or \
+                        linking anything.")
+                    .long("dump-defines")
+                    .action(ArgAction::SetTrue))
+                .arg(dump_toolset_commands.clone())
+                .arg(Arg::new("dump-wixobj")
+                    .help("A path to a folder to copy the WiX object files to")
+                    .long_help("Copies the WiX Object (wixobj) files generated by \
+                        the WiX compiler (candle.exe) to the folder before the WiX \
+                        linker (light.exe) runs. The folder is created if it does not \
+                        exist. This is useful for inspecting the intermediate XML when \
+                        troubleshooting linker errors.")
+                    .long("dump-wixobj")
+                    .num_args(1))
+                .arg(Arg::new("emit-relative-manifest-path")
+                    .help("Defines the paths for the WiX compiler relative to the manifest")
+                    .long_help("Defines the 'CargoTargetDir', 'CargoTargetBinDir', and \
+                        'ProductIcon' variables for the WiX compiler (candle.exe) with \
+                        paths relative to the folder of the package's manifest \
+                        (Cargo.toml) instead of absolute paths, so machine-specific \
+                        paths are not included in the build artifacts.")
+                    .long("emit-relative-manifest-path")
+                    .action(ArgAction::SetTrue))
+                .subcommand(Command::new("extensions")
+                    .version(PKG_VERSION)
+                    .about("Lists the extensions of the WiX Toolset v4 or later")
+                    .long_about("Runs the 'wix extension list' command of the WiX \
+                        Toolset v4 or later and prints the ID and version of each \
+                        installed extension. An error occurs if only the legacy WiX \
+                        Toolset (v3) is installed.")
+                    .arg(Arg::new("bin-path")
+                        .help("A path to the folder containing the 'wix' application")
+                        .long_help("The default is to use the PATH system environment \
+                            variable to locate the application.")
+                        .long("bin-path")
+                        .short('b')
+                        .num_args(1))
+                    .arg(dump_toolset_commands.clone())
+                    .arg(Arg::new("global")
+                        .help("Lists the extensions in the global cache")
+                        .long_help("Lists the extensions in the global cache instead \
+                            of the extensions for the current folder.")
+                        .long("global")
+                        .action(ArgAction::SetTrue))
+                    .arg(verbose.clone()))
+                .arg(Arg::new("features")
+                    .help("Cargo features of the package to enable")
+                    .long_help("Passes the features to the 'cargo build' command with \
+                        the '--features' option and defines the 'CargoFeature_<FEATURE>' \
+                        variable for the WiX compiler (candle.exe) for each enabled \
+                        feature of the package, including the default features. Use \
+                        this option multiple times or with a comma-separated list to \
+                        enable multiple features.")
+                    .long("features")
+                    .short('F')
+                    .num_args(1)
+                    .action(ArgAction::Append))
+                .arg(Arg::new("format")
+                    .help("The format of the output")
+                    .long_help("Creates an installer with the WiX Toolset if 'msi', \
+                        or a portable zip archive of the files in the WiX Source (wxs) \
+                        files without the WiX Toolset if 'zip'. The binaries are in a \
+                        'bin' folder within the archive. The default is 'msi'.")
+                    .long("format")
+                    .value_parser(["msi", "zip"])
+                    .num_args(1))
+                .arg(Arg::new("generate-transforms")
+                    .help("Additional cultures to generate language transforms for")
+                    .long_help("Links the installer again for each additional culture \
+                        and generates a language transform (mst) from the differences \
+                        with the WiX Toolset's transform application (torch.exe). The \
+                        transforms are written next to the installer. Use this option \
+                        multiple times to generate multiple transforms.")
+                    .long("generate-transforms")
+                    .num_args(1)
+                    .action(ArgAction::Append))
                 .arg(Arg::new("include")
                     .help("Include an additional WiX Source (wxs) file")
                     .long_help("Includes a WiX source (wxs) file for a project, \
@@ -1541,6 +4016,47 @@ fn main() {
                     .short('I')
                     .num_args(1)
                     .action(ArgAction::Append))
+                .arg(Arg::new("include-build-metadata")
+                    .help("Embeds the git commit and build time in the installer")
+                    .long_help("Defines the 'GitHash' and 'BuildTime' variables for \
+                        the WiX compiler (candle.exe) with the short hash of the \
+                        current git commit and the time of the build in UTC, \
+                        respectively. The 'GitHash' variable is not defined if the \
+                        package is not in a git repository. The template uses the \
+                        variables for the 'ARPCOMMENTS' property if a changelog is \
+                        not used.")
+                    .long("include-build-metadata")
+                    .action(ArgAction::SetTrue))
+                .arg(Arg::new("incremental")
+                    .help("Skips compiling and linking if the installer's inputs are unchanged")
+                    .long_help("Computes a hash of the inputs for the installer after \
+                        building the binaries and skips running the WiX Toolset compiler \
+                        (candle.exe) and linker (light.exe) if the hash matches the one \
+                        recorded for the existing installer. The hash is recorded in the \
+                        'installer.inputs' file in the 'target\\wix' folder. See the \
+                        documentation for the inputs that are included in the hash.")
+                    .long("incremental")
+                    .action(ArgAction::SetTrue))
+                .subcommand(Command::new("info")
+                    .version(PKG_VERSION)
+                    .about("Prints the details of the installed WiX Toolset")
+                    .long_about("Prints the detected WiX Toolset, the paths to the \
+                        compiler (candle.exe), linker (light.exe), and 'wix' \
+                        applications, their versions, the value of the WIX system \
+                        environment variable, and the path to the signer \
+                        (signtool.exe). Applications that cannot be found are \
+                        reported instead of causing an error.")
+                    .arg(Arg::new("bin-path")
+                        .help("A path to the WiX Toolset's 'bin' folder")
+                        .long_help("Uses the same folder as the default create \
+                            subcommand to find the compiler (candle.exe) and linker \
+                            (light.exe). The default is to use the WIX system \
+                            environment variable and then the PATH system \
+                            environment variable.")
+                        .long("bin-path")
+                        .short('b')
+                        .num_args(1))
+                    .arg(verbose.clone()))
                 .subcommand(Command::new("init")
                     .version(PKG_VERSION)
                     .about("Generates files from a package's manifest (Cargo.toml) to create an installer")
@@ -1556,19 +4072,54 @@ fn main() {
                             then all output from initialization will be placed in a \
                             'wix' folder created alongside this path.")
                         .index(1))
+                    .arg(manifest_path.clone())
+                    .arg(after_install_launch.clone())
+                    .arg(author_separator.clone())
                     .arg(banner.clone())
                     .arg(binaries.clone())
+                    .arg(culture.clone())
                     .arg(description.clone())
+                    .arg(deterministic_component_guids.clone())
                     .arg(dialog.clone())
+                    .arg(dialog_set.clone())
+                    .arg(dump_context.clone())
                     .arg(eula.clone())
+                    .arg(feature_map.clone())
+                    .arg(guid_from.clone())
                     .arg(Arg::new("force")
                         .help("Overwrite existing WiX-related files")
                         .long_help("Overwrites any existing files that are \
                             generated during initialization. Use with caution.")
                         .long("force")
                         .action(ArgAction::SetTrue))
+                    .arg(Arg::new("generate-branding")
+                        .help("Generates banner and dialog images with the product name")
+                        .long_help("Writes a banner image (493 x 58 pixels) and a \
+                            dialog image (493 x 312 pixels) with the product name to \
+                            the 'wix' folder and uses them for the installer. An image \
+                            is not generated if it is specified with the '-b,--banner' \
+                            or '-D,--dialog' options.")
+                        .long("generate-branding")
+                        .action(ArgAction::SetTrue))
+                    .arg(install_scope.clone())
+                    .arg(keep_author_emails.clone())
+                    .arg(launch_binary.clone())
                     .arg(license.clone())
                     .arg(manufacturer.clone())
+                    .arg(manufacturer_from.clone())
+                    .arg(Arg::new("msbuild")
+                        .help("Generates a MSBuild project (wixproj) for the modern WiX Toolset")
+                        .long_help("Writes a MSBuild project file (wixproj) named after \
+                            the package to the 'wix' folder. The project lists the WiX \
+                            Source (wxs) files in the folder and references the \
+                            extensions used in them, so the installer can be built with \
+                            'dotnet build' and the WiX Toolset v4 or later.")
+                        .long("msbuild")
+                        .action(ArgAction::SetTrue))
+                    .arg(no_modify.clone())
+                    .arg(no_path.clone())
+                    .arg(no_repair.clone())
+                    .arg(no_upgrade.clone())
                     .arg(Arg::new("output")
                         .help("A path to a folder for generated files")
                         .long_help("Sets the destination for all files \
@@ -1583,9 +4134,23 @@ fn main() {
                     .arg(path_guid.clone())
                     .arg(product_icon.clone())
                     .arg(product_name.clone())
+                    .arg(Arg::new("regenerate-guids")
+                        .help("Generates new GUIDs when overwriting the WXS file")
+                        .long_help("Generates a new upgrade code and path component \
+                            GUID when an existing WiX Source (wxs) file is overwritten \
+                            with the '--force' flag, instead of reusing the GUIDs from \
+                            the existing file.")
+                        .long("regenerate-guids")
+                        .requires("force")
+                        .action(ArgAction::SetTrue))
+                    .arg(run_after_install.clone())
+                    .arg(skip_eula_dialog.clone())
+                    .arg(skip_license_sidecar.clone())
+                    .arg(uninstall_shortcut.clone())
                     .arg(upgrade_guid.clone())
                     .arg(url.clone())
                     .arg(verbose.clone())
+                    .arg(wxs_template_dir.clone())
                     .arg(year.clone()))
                 .arg(Arg::new("INPUT")
                      .help("Path to a package's manifest (Cargo.toml) file.")
@@ -1606,6 +4171,15 @@ fn main() {
                     .long("install-version")
                     .short('i')
                     .num_args(1))
+                .arg(Arg::new("keep-going")
+                    .help("Continues with the remaining targets if one target fails")
+                    .long_help("When creating installers for multiple targets, \
+                        continues creating the installers for the remaining targets \
+                        if creating the installer for one target fails. A failure is \
+                        still reported after all targets have been attempted. The \
+                        default is to stop at the first failure.")
+                    .long("keep-going")
+                    .action(ArgAction::SetTrue))
                 .arg(Arg::new("linker-arg")
                     .help("Send an argument to the WiX linker (light.exe)")
                     .long_help("Appends the argument to the command that is \
@@ -1630,6 +4204,16 @@ fn main() {
                     .long("locale")
                     .short('l')
                     .num_args(1))
+                .arg(manifest_path.clone())
+                .arg(Arg::new("metadata-output")
+                    .help("A path to write the resolved configuration of the installer to")
+                    .long_help("Writes the settings that produced the installer, such \
+                        as the name, version, target, culture, WiX Source (wxs) files, \
+                        and compiler variables, to a JSON file after the installer is \
+                        created. If the path is a folder, then the file is named after \
+                        the installer with the '.json' file extension.")
+                    .long("metadata-output")
+                    .num_args(1))
                 .arg(Arg::new("name")
                     .help("A string for the installer's product name")
                     .long_help("Overrides the 'name' field in the package's \
@@ -1639,6 +4223,14 @@ fn main() {
                     .long("name")
                     .short('n')
                     .num_args(1))
+                .arg(Arg::new("no-arch-in-name")
+                    .help("Excludes the architecture from the installer's file name")
+                    .long_help("Names the installer '{name}-{version}.msi' instead of \
+                        '{name}-{version}-{arch}.msi'. This cannot be used when creating \
+                        installers for multiple targets, unless the 'per-arch' output \
+                        layout is used.")
+                    .long("no-arch-in-name")
+                    .action(ArgAction::SetTrue))
                 .arg(Arg::new("no-build")
                     .help("Skips building the release binary")
                     .long_help("The installer is created, but the 'cargo build \
@@ -1659,9 +4251,44 @@ fn main() {
                         flag to show the output.")
                     .long("nocapture")
                     .action(ArgAction::SetTrue))
+                .arg(Arg::new("no-default-features")
+                    .help("Disables the default features of the package")
+                    .long_help("Passes the '--no-default-features' flag to the 'cargo \
+                        build' command. The 'CargoFeature_<FEATURE>' variables are not \
+                        defined for the default features of the package.")
+                    .long("no-default-features")
+                    .action(ArgAction::SetTrue))
+                .arg(Arg::new("no-default-wix-folder")
+                    .help("Skips the WiX Source (wxs) files in the default 'wix' folder")
+                    .long_help("Only the WiX Source (wxs) files included with the \
+                        '-I,--include' option, or the 'include' field in the \
+                        '[package.metadata.wix]' section of the package's manifest \
+                        (Cargo.toml), are used to create the installer. The default is \
+                        to also use all WiX Source (wxs) files in the 'wix' folder.")
+                    .long("no-default-wix-folder")
+                    .action(ArgAction::SetTrue))
+                .arg(Arg::new("no-install-if-running")
+                    .help("Skips running the installer if the application is running")
+                    .long_help("Checks if any of the package's binaries are running \
+                        before running the installer with the '--install' flag. An \
+                        error occurs, instead of running the installer, if any of \
+                        the binaries are running. This is only supported on Windows.")
+                    .long("no-install-if-running")
+                    .requires("install")
+                    .action(ArgAction::SetTrue))
+                .arg(Arg::new("no-overwrite-increment")
+                    .help("Appends a number to the installer instead of overwriting it")
+                    .long_help("Appends '-1', '-2', etc. to the file stem of the \
+                        installer until a name that does not exist is found, instead \
+                        of overwriting an existing installer. This keeps the \
+                        installers from previous runs.")
+                    .long("no-overwrite-increment")
+                    .action(ArgAction::SetTrue))
                 .arg(Arg::new("install")
                     .help("Runs the installer after creating it")
-                    .long_help("Creates the installer and runs it after that.")
+                    .long_help("Creates the installer and runs it after that. A \
+                        bundle (exe) installer is run directly instead of with \
+                        'msiexec'. This cannot be used with multiple targets.")
                     .long("install")
                     .action(ArgAction::SetTrue))
                 .arg(Arg::new("output")
@@ -1680,7 +4307,39 @@ fn main() {
                     .long("output")
                     .short('o')
                     .num_args(1))
+                .arg(Arg::new("output-dir")
+                    .help("A path to a destination folder for the installer")
+                    .long_help("Sets the destination folder for the created \
+                        installer, which has the default \
+                        '<product-name>-<version>-<arch>.msi' file name. Unlike the \
+                        '-o,--output' option, the path is always a folder, even if it \
+                        does not exist or does not have a trailing slash, and the \
+                        folder is created if it does not exist.")
+                    .long("output-dir")
+                    .conflicts_with("output")
+                    .num_args(1))
+                .arg(Arg::new("output-layout")
+                    .help("The layout of the destination folder: flat or per-arch")
+                    .long_help("Sets the layout of the destination folder for the \
+                        installers. With 'per-arch', each installer is written to a \
+                        subfolder named after its architecture, such as 'x64' or \
+                        'x86', and the architecture can be excluded from the \
+                        installer's name with multiple targets. The default is \
+                        'flat', where the installers are written directly to the \
+                        destination folder.")
+                    .long("output-layout")
+                    .value_parser(["flat", "per-arch"])
+                    .num_args(1))
                 .arg(package.clone())
+                .arg(Arg::new("preprocess-only")
+                    .help("A path to a folder to write the preprocessed WXS files to")
+                    .long_help("Runs only the preprocessor of the WiX compiler \
+                        (candle.exe) and writes the preprocessed WiX Source (wxs) \
+                        files to the folder instead of creating the installer. The \
+                        folder is created if it does not exist. This is useful for \
+                        debugging the preprocessor variables and directives.")
+                    .long("preprocess-only")
+                    .num_args(1))
                 .subcommand(Command::new("print")
                     .version(PKG_VERSION)
                     .about("Prints a template")
@@ -1706,19 +4365,56 @@ fn main() {
                             current working directory (cwd). An error occurs if a \
                             manifest is not found.")
                         .index(2))
+                    .arg(manifest_path.clone())
+                    .arg(after_install_launch)
+                    .arg(author_separator)
                     .arg(banner)
                     .arg(binaries)
+                    .arg(culture)
                     .arg(description)
+                    .arg(deterministic_component_guids)
                     .arg(dialog)
+                    .arg(dialog_set)
+                    .arg(dump_context)
                     .arg(eula)
+                    .arg(feature_map)
+                    .arg(guid_from)
+                    .arg(Arg::new("include-licenses")
+                        .help("Reports the license files and help URL of the WXS template")
+                        .long_help("Writes the EULA displayed in the license agreement \
+                            dialog, the license installed as a sidecar file, and the \
+                            help URL that the rendered WiX Source (wxs) template \
+                            references to stderr. This only applies to the 'WXS' \
+                            template.")
+                        .long("include-licenses")
+                        .action(ArgAction::SetTrue))
+                    .arg(install_scope)
+                    .arg(keep_author_emails)
+                    .arg(launch_binary)
                     .arg(license)
                     .arg(manufacturer)
+                    .arg(manufacturer_from)
+                    .arg(no_modify)
+                    .arg(no_path)
+                    .arg(no_repair)
+                    .arg(no_upgrade)
+                    .arg(Arg::new("open")
+                        .help("Opens the rendered WXS template in the default application")
+                        .long_help("Writes the rendered WiX Source (wxs) template to \
+                            a new folder in the temporary directory, or the output if \
+                            one is specified, and opens it with the default application \
+                            for WiX Source (wxs) files. This only applies to the 'WXS' \
+                            template and is only supported on Windows. It has no effect \
+                            on other platforms.")
+                        .long("open")
+                        .action(ArgAction::SetTrue))
                     .arg(Arg::new("output")
                         .help("A path to a folder for generated files")
                         .long_help("Sets the destination for printing the \
                             template. The default is to print/write the rendered \
-                            template to stdout. If the destination, a.k.a. file, \
-                            does not exist, it will be created.")
+                            template to stdout. A value of '-' explicitly prints to \
+                            stdout. If the destination, a.k.a. file, or any of its \
+                            parent folders do not exist, they will be created.")
                         .long("output")
                         .short('o')
                         .num_args(1))
@@ -1727,10 +4423,71 @@ fn main() {
                     .arg(path_guid)
                     .arg(product_icon)
                     .arg(product_name.clone())
+                    .arg(Arg::new("schema-validate")
+                        .help("Validates the rendered WXS template")
+                        .long_help("Checks the rendered WiX Source (wxs) template \
+                            for malformed XML and a subset of the WiX Toolset schema \
+                            rules, such as missing required attributes, duplicate IDs, \
+                            and invalid GUIDs. Each problem is reported with its line \
+                            number and nothing is printed or written if any problems \
+                            are found. This only applies to the 'WXS' template.")
+                        .long("schema-validate")
+                        .action(ArgAction::SetTrue))
+                    .arg(run_after_install)
+                    .arg(skip_eula_dialog)
+                    .arg(skip_license_sidecar)
+                    .arg(uninstall_shortcut)
                     .arg(upgrade_guid)
                     .arg(url)
+                    .arg(wxs_template_dir)
                     .arg(year)
                     .arg(verbose.clone()))
+                .arg(Arg::new("print-config-schema")
+                    .help("Prints the supported [package.metadata.wix] fields")
+                    .long_help("Prints the name, type, subcommands, and overriding \
+                        CLI option of each field supported in the \
+                        [package.metadata.wix] section of the package's manifest \
+                        (Cargo.toml) and exits.")
+                    .long("print-config-schema")
+                    .action(ArgAction::SetTrue))
+                .arg(Arg::new("print-exit-codes")
+                    .help("Prints the exit code for each kind of error")
+                    .long_help("Prints the exit code and name for each kind of error \
+                        and exits. The exit codes are stable and can be relied upon \
+                        in scripts.")
+                    .long("print-exit-codes")
+                    .hide(true)
+                    .action(ArgAction::SetTrue))
+                .arg(Arg::new("print-wxs")
+                    .help("Prints the WXS files before compiling them")
+                    .long_help("Prints the path and content of each WiX Source \
+                        (wxs) file passed to the WiX compiler (candle.exe) to stdout \
+                        right before the compiler runs, including for a dry run.")
+                    .long("print-wxs")
+                    .action(ArgAction::SetTrue))
+                .arg(Arg::new("product-language")
+                    .help("The culture code or LCID for the product language")
+                    .long_help("Sets the language of the product and the package \
+                        independently of the '-c,--culture' option for the user \
+                        interface. The value is a culture code, such as fr-FR, or a \
+                        numeric LCID, such as 1036 or 0 for a language-neutral \
+                        product. The LCID is defined as the 'ProductLanguage' \
+                        variable for the WiX compiler (candle.exe). The default is \
+                        the LCID of the culture.")
+                    .long("product-language")
+                    .num_args(1))
+                .arg(Arg::new("property")
+                    .help("A NAME=VALUE property for the installer")
+                    .long_help("Defines a preprocessor variable with the name and \
+                        value for the WiX compiler (candle.exe), i.e. '-dNAME=VALUE', \
+                        so that the WiX Source (wxs) file can set the default value \
+                        of a property, such as <Property Id='NAME' \
+                        Value='$(var.NAME)'/>. The name must be a valid WiX \
+                        identifier. Use this option multiple times to set multiple \
+                        properties.")
+                    .long("property")
+                    .num_args(1)
+                    .action(ArgAction::Append))
                 .subcommand(Command::new("purge")
                     .version(PKG_VERSION)
                     .about("Deletes the 'target\\wix' and 'wix' folders")
@@ -1742,7 +4499,36 @@ fn main() {
                             exists alongside the package's manifest will be removed. \
                             This is optional and the default is to use the current \
                             working directory (cwd).")
-                        .index(1)))
+                        .index(1))
+                    .arg(manifest_path.clone()))
+                .arg(Arg::new("quiet-tools")
+                    .help("Suppresses the logo of the WiX Toolset compiler and linker")
+                    .long_help("Passes the '-nologo' argument to the WiX compiler \
+                        (candle.exe) and linker (light.exe), unless it is already \
+                        passed with the '-C,--compiler-arg' or '-L,--linker-arg' \
+                        options. This reduces the noise in the output when used with \
+                        the '--nocapture' flag.")
+                    .long("quiet-tools")
+                    .action(ArgAction::SetTrue))
+                .arg(Arg::new("reinstall-mode")
+                    .help("The value of the REINSTALLMODE property for upgrades")
+                    .long_help("Defines the 'ReinstallMode' variable for the WiX \
+                        compiler (candle.exe), which the template uses to set the \
+                        'REINSTALLMODE' property. Use 'amus' to always replace all \
+                        files during an upgrade. The default is to not set the \
+                        property, in which case the Windows Installer default of \
+                        'omus' is used.")
+                    .long("reinstall-mode")
+                    .num_args(1))
+                .arg(Arg::new("retain-symbols")
+                    .help("Retains the WiX debug symbols (wixpdb) file")
+                    .long_help("Writes the WiX debug symbols (wixpdb) file next to \
+                        the installer if 'yes'. The default is 'no', in which case \
+                        the '-spdb' argument is passed to the WiX linker (light.exe) \
+                        to suppress the file.")
+                    .long("retain-symbols")
+                    .value_parser(["yes", "no"])
+                    .num_args(1))
                 .subcommand(Command::new("sign")
                     .version(PKG_VERSION)
                     .about("Signs an installer")
@@ -1750,29 +4536,45 @@ fn main() {
                         SignTool application available in the Windows 10 SDK. The \
                         signtool is invoked with the '/a' flag to automatically \
                         obtain an appropriate certificate from the Windows \
-                        certificate manager. The default is to also use the Comodo \
+                        certificate manager, unless the '--cert-subject' option is \
+                        used. The default is to also use the Comodo \
                         timestamp server with the '/t' flag.")
                     .arg(Arg::new("bin-path")
                         .help("A path to the folder containing the 'signtool' application")
-                        .long_help("The default is to use the PATH system environment \
-                             variable to locate the application.")
+                        .long_help("The default is to use the SIGNTOOL_PATH system \
+                             environment variable, if it exists, or the PATH system \
+                             environment variable to locate the application.")
                         .long("bin-path")
                         .short('b')
                         .num_args(1))
+                    .arg(Arg::new("cert-subject")
+                        .help("The subject name of the signing certificate")
+                        .long_help("Selects the certificate from the Windows \
+                            certificate store by its subject name. The value is \
+                            passed to the signer (signtool.exe) with the '/n' option \
+                            instead of the '/a' flag, which automatically selects the \
+                            best certificate.")
+                        .long("cert-subject")
+                        .num_args(1))
                     .arg(Arg::new("description")
-                        .help("A string for the extended ACL dialog")
+                        .help("A string for the extended UAC dialog")
                         .long_help("The information for the extended text of \
-                            the ACL dialog that appears. This will be appended to \
-                            the product name and delimited by a dash, '-'. The \
-                            default is to use the description from the package's \
-                            manifest (Cargo.toml). This option will override the \
-                            default.")
+                            the UAC dialog that appears. This will be appended to \
+                            the product name and delimited by a dash, '-', and \
+                            passed to the signer (signtool.exe) with the '/d' \
+                            option. The default is to use the description from the \
+                            package's manifest (Cargo.toml). This option will \
+                            override the default.")
                         .long("description")
                         .short('d')
                         .num_args(1))
+                    .arg(dump_toolset_commands)
                     .arg(Arg::new("homepage")
                         .help("A URL for the product's homepage")
-                        .long_help("This will be displayed in the ACL dialog.")
+                        .long_help("This will be displayed in the UAC dialog. It is \
+                            passed to the signer (signtool.exe) with the '/du' \
+                            option. The default is to use the homepage from the \
+                            package's manifest (Cargo.toml).")
                         .long("homepage")
                         .short('u')
                         .num_args(1))
@@ -1782,6 +4584,7 @@ fn main() {
                             folder alongside this manifest will be signed based on \
                             the metadata within the manifest.")
                         .index(1))
+                    .arg(manifest_path)
                     .arg(Arg::new("installer")
                         .help("specify the installer to be signed")
                         .long_help("Specify the installer to be signed.")
@@ -1797,6 +4600,25 @@ fn main() {
                         .action(ArgAction::SetTrue))
                     .arg(product_name)
                     .arg(package)
+                    .arg(Arg::new("sign-all")
+                        .help("Signs the engine of a bundle and then the bundle")
+                        .long_help("Detaches the engine from a bundle (exe) with the \
+                            WiX Toolset's insignia application, signs the engine, \
+                            reattaches it, and then signs the bundle. The default \
+                            installer to sign is a bundle instead of a Windows \
+                            installer (msi). The packages (msi) contained in the \
+                            bundle must be signed before the bundle is created.")
+                        .long("sign-all")
+                        .action(ArgAction::SetTrue))
+                    .arg(Arg::new("sign-retries")
+                        .help("The number of times to retry signing a file in use")
+                        .long_help("Retries signing after a short delay if the signer \
+                            (signtool.exe) fails because the file is in use by another \
+                            process, such as an antivirus scanner. Certificate and \
+                            timestamp failures are not retried. The default is 3.")
+                        .long("sign-retries")
+                        .num_args(1))
+                    .arg(timeout.clone())
                     .arg(Arg::new("timestamp")
                         .help("An alias or URL to a timestamp server")
                         .long_help("Either an alias or URL can be used. Aliases \
@@ -1805,17 +4627,125 @@ fn main() {
                         .long("timestamp")
                         .num_args(1))
                     .arg(verbose.clone()))
-                .arg(verbose)
+                .arg(Arg::new("strict")
+                    .help("Fails instead of falling back to a supported culture or warning")
+                    .long_help("Fails if the culture is not supported by the WixUI \
+                        extension. The default is to fall back, with a warning, to \
+                        the supported culture for the same language, such as 'fr-FR' \
+                        for 'fr-CA'. Undefined variables found with the \
+                        '--wxs-variable-check' flag, and binaries built for a \
+                        different architecture than the installer, are also errors \
+                        instead of warnings.")
+                    .long("strict")
+                    .action(ArgAction::SetTrue))
+                .arg(Arg::new("strip")
+                    .help("Strips the symbols from the binaries before packaging")
+                    .long_help("Enables the 'strip' setting of the Cargo profile for \
+                        the build with the 'CARGO_PROFILE_<NAME>_STRIP' environment \
+                        variable, which makes the installer smaller. The variable is \
+                        also set for the '--build-command' option. Nothing is \
+                        stripped with the '--no-build' flag.")
+                    .long("strip")
+                    .action(ArgAction::SetTrue))
+                .arg(timeout.clone())
+                .arg(verbose.clone())
+                .subcommand(Command::new("verify")
+                    .version(PKG_VERSION)
+                    .about("Verifies the signature of an installer")
+                    .long_about("Verifies the signature of an installer, without \
+                        signing it, using the SignTool application available in the \
+                        Windows 10 SDK. The signtool is invoked with the 'verify /pa \
+                        /v' arguments, so the certificate chain and the timestamp of \
+                        the signature are displayed. An error occurs if the signature \
+                        is not valid.")
+                    .arg(Arg::new("bin-path")
+                        .help("A path to the folder containing the 'signtool' application")
+                        .long_help("The default is to use the SIGNTOOL_PATH system \
+                             environment variable, if it exists, or the PATH system \
+                             environment variable to locate the application.")
+                        .long("bin-path")
+                        .short('b')
+                        .num_args(1))
+                    .arg(Arg::new("INSTALLER")
+                        .help("A path to the installer to verify")
+                        .long_help("Any signed file can be verified, including \
+                            installers that were not created with this subcommand. The \
+                            default is to verify the most recently modified installer \
+                            (msi) in the 'target\\wix' folder.")
+                        .index(1))
+                    .arg(Arg::new("manifest-path")
+                        .help("A path to a package's manifest (Cargo.toml)")
+                        .long_help("The most recently modified installer (msi) in \
+                            the 'target\\wix' folder for this manifest is verified. \
+                            It cannot be used with the positional argument.")
+                        .long("manifest-path")
+                        .conflicts_with("INSTALLER")
+                        .num_args(1))
+                    .arg(timeout)
+                    .arg(verbose))
+                .arg(Arg::new("verify-manifest")
+                    .help("Checks the WXS files have the values from the manifest before compiling")
+                    .long_help("Checks the WiX Source (wxs) files for compiler variables \
+                        that are not defined and attributes rendered from fields of the \
+                        package's manifest (Cargo.toml) that are empty or missing, such \
+                        as the 'Manufacturer' attribute from the 'authors' field. An error \
+                        occurs, instead of compiling, if any problems are found.")
+                    .long("verify-manifest")
+                    .action(ArgAction::SetTrue))
+                .arg(Arg::new("wix-root")
+                    .help("A path to the root folder of the WiX Toolset installation")
+                    .long_help(format!(
+                        "Specifies the path to the root folder of the WiX Toolset \
+                        installation, which is the same as the value of the \
+                        {WIX_PATH_KEY} system environment variable. The compiler \
+                        (candle.exe) and linker (light.exe) are found in its \
+                        '{BINARY_FOLDER_NAME}' folder. This takes precedence over the \
+                        {WIX_PATH_KEY} system environment variable but not the \
+                        '-b,--bin-path' option."))
+                    .long("wix-root")
+                    .num_args(1))
+                .arg(Arg::new("WXS")
+                     .help("WiX Source (wxs) files to use instead of the 'wix' folder")
+                     .long_help("The WiX Source (wxs) files used to create the \
+                        installer. If any files are given, only these files are used, \
+                        and the files in the 'wix' folder, the '-I,--include' option, \
+                        and the 'include' field in the '[package.metadata.wix]' section \
+                        are ignored. The path to the package's manifest (Cargo.toml) \
+                        must be given first with the INPUT argument.")
+                     .num_args(1..)
+                     .index(2))
+                .arg(Arg::new("wxs-variable-check")
+                    .help("Warns about undefined variables in the WXS files before compiling")
+                    .long_help("Checks the WiX Source (wxs) files for references to \
+                        compiler variables, such as '$(var.Example)', that are not \
+                        defined by cargo-wix, the '-C,--compiler-arg' or '--property' \
+                        options, or a preprocessor instruction in the same file or \
+                        an included file. Each \
+                        variable is reported with a warning, or an error with the \
+                        '--strict' flag.")
+                    .long("wxs-variable-check")
+                    .action(ArgAction::SetTrue))
         ).get_matches();
     let matches = matches.subcommand_matches(SUBCOMMAND_NAME).unwrap();
     let verbosity = match matches.subcommand() {
+        Some(("check", m)) => m,
         Some(("clean", m)) => m,
+        Some(("config", m)) => m.subcommand().map_or(m, |(_, m)| m),
+        Some(("extensions", m)) => m,
+        Some(("info", m)) => m,
         Some(("init", m)) => m,
         Some(("print", m)) => m,
         Some(("purge", m)) => m,
+        Some(("verify", m)) => m,
         _ => matches,
     }
     .get_count("verbose");
+    let (color_choice, write_style) = match matches.get_one::<String>("color").map(String::as_str) {
+        Some("always") => (ColorChoice::Always, WriteStyle::Always),
+        Some("never") => (ColorChoice::Never, WriteStyle::Never),
+        _ if io::stderr().is_terminal() => (ColorChoice::Auto, WriteStyle::Auto),
+        _ => (ColorChoice::Never, WriteStyle::Never),
+    };
     // Using the `Builder::new` instead of the `Builder::from_env` or `Builder::from_default_env`
     // skips reading the configuration from any environment variable, i.e. `RUST_LOG`. The log
     // level is later configured with the verbosity using the `filter` method. There are many
@@ -1833,6 +4763,7 @@ fn main() {
     // used to set the log level.
     let mut builder = Builder::new();
     builder
+        .write_style(write_style)
         .format(|buf, record| {
             // This implementation for a format is copied from the default format implemented for the
             // `env_logger` crate but modified to use a colon, `:`, to separate the level from the
@@ -1864,34 +4795,122 @@ fn main() {
         )
         .init();
     let result = match matches.subcommand() {
+        None if matches.get_flag("print-exit-codes") => {
+            for (code, name) in wix::Error::codes() {
+                println!("{code}\t{name}");
+            }
+            Ok(())
+        }
+        None if matches.get_flag("print-config-schema") => {
+            println!(
+                "{:<30} {:<12} {:<12} OPTION",
+                "FIELD", "TYPE", "SUBCOMMANDS"
+            );
+            for field in wix::metadata::FIELDS {
+                println!(
+                    "{:<30} {:<12} {:<12} {}",
+                    field.name, field.field_type, field.subcommands, field.option
+                );
+            }
+            Ok(())
+        }
+        Some(("check", m)) => {
+            let mut check = check::Builder::new();
+            check.fix(m.get_flag("fix"));
+            check.includes(
+                m.get_many("include")
+                    .map(|v| v.map(String::as_str).collect()),
+            );
+            check.input(manifest_input(m));
+            check.package(m.get_one("package").map(String::as_str));
+            check.build().run()
+        }
         Some(("clean", m)) => {
             let mut clean = clean::Builder::new();
-            clean.input(m.get_one("INPUT").map(String::as_str));
+            clean.input(manifest_input(m));
+            clean.output(m.get_one("output").map(String::as_str));
+            clean.package(m.get_one("package").map(String::as_str));
             clean.build().run()
         }
+        Some(("config", m)) => match m.subcommand() {
+            Some(("show", m)) => {
+                let mut config = config::Builder::new();
+                config.input(manifest_input(m));
+                config.package(m.get_one("package").map(String::as_str));
+                config.build().run()
+            }
+            _ => unreachable!(), // Guaranteed by clap
+        },
+        Some(("extensions", m)) => {
+            let mut extensions = extensions::Builder::new();
+            extensions.bin_path(m.get_one("bin-path").map(String::as_str));
+            extensions.dump_toolset_commands(m.get_flag("dump-toolset-commands"));
+            extensions.global(m.get_flag("global"));
+            extensions.build().run()
+        }
+        Some(("info", m)) => {
+            let mut info = info::Builder::new();
+            info.bin_path(m.get_one("bin-path").map(String::as_str));
+            info.build().run()
+        }
         Some(("init", m)) => {
             let mut init = initialize::Builder::new();
-            init.banner(m.get_one("banner").map(String::as_str));
+            init.after_install_launch(m.get_flag("after-install-launch"));
+            init.author_separator(m.get_one("author-separator").map(String::as_str));
+            let (banner, culture_banners) = culture_images(m, "banner");
+            init.banner(banner);
+            for (culture, path) in culture_banners {
+                init.culture_banner(culture, path);
+            }
             init.binaries(
                 m.get_many::<String>("binaries")
                     .map(|v| v.map(String::as_str).collect()),
             );
             init.copyright_holder(m.get_one("owner").map(String::as_str));
             init.copyright_year(m.get_one("year").map(String::as_str));
+            init.culture(m.get_one("culture").map(String::as_str));
             init.description(m.get_one("description").map(String::as_str));
-            init.dialog(m.get_one("dialog").map(String::as_str));
+            init.deterministic_component_guids(m.get_flag("deterministic-component-guids"));
+            let (dialog, culture_dialogs) = culture_images(m, "dialog");
+            init.dialog(dialog);
+            for (culture, path) in culture_dialogs {
+                init.culture_dialog(culture, path);
+            }
+            init.dialog_set(m.get_one("dialog-set").map(String::as_str));
+            init.dump_context(m.get_one("dump-context").map(String::as_str));
             init.eula(m.get_one("eula").map(String::as_str));
+            init.feature_maps(
+                m.get_many::<String>("feature-map")
+                    .map(|v| v.map(String::as_str).collect()),
+            );
             init.force(m.get_flag("force"));
+            init.generate_branding(m.get_flag("generate-branding"));
+            init.guid_from(m.get_one("guid-from").map(String::as_str));
             init.help_url(m.get_one("url").map(String::as_str));
-            init.input(m.get_one("INPUT").map(String::as_str));
+            init.input(manifest_input(m));
+            init.install_scope(m.get_one("install-scope").map(String::as_str));
+            init.keep_author_emails(m.get_flag("keep-author-emails"));
+            init.launch_binary(m.get_one("launch-binary").map(String::as_str));
             init.license(m.get_one("license").map(String::as_str));
             init.manufacturer(m.get_one("manufacturer").map(String::as_str));
+            init.manufacturer_from(m.get_one("manufacturer-from").map(String::as_str));
+            init.msbuild(m.get_flag("msbuild"));
+            init.no_modify(m.get_flag("no-modify"));
+            init.no_path(m.get_flag("no-path"));
+            init.no_repair(m.get_flag("no-repair"));
+            init.no_upgrade(m.get_flag("no-upgrade"));
             init.output(m.get_one("output").map(String::as_str));
             init.package(m.get_one("package").map(String::as_str));
             init.path_guid(m.get_one("path-guid").map(String::as_str));
             init.product_icon(m.get_one("product-icon").map(String::as_str));
             init.product_name(m.get_one("product-name").map(String::as_str));
+            init.regenerate_guids(m.get_flag("regenerate-guids"));
+            init.run_after_install(m.get_one("run-after-install").map(String::as_str));
+            init.skip_eula_dialog(m.get_flag("skip-eula-dialog"));
+            init.skip_license_sidecar(m.get_flag("skip-license-sidecar"));
+            init.uninstall_shortcut(m.get_flag("uninstall-shortcut"));
             init.upgrade_guid(m.get_one("upgrade-guid").map(String::as_str));
+            init.wxs_template_dir(m.get_one("wxs-template-dir").map(String::as_str));
             init.build().run()
         }
         Some(("print", m)) => {
@@ -1903,88 +4922,207 @@ fn main() {
             match template {
                 Template::Wxs => {
                     let mut print = print::wxs::Builder::new();
-                    print.banner(m.get_one("banner").map(String::as_str));
+                    print.after_install_launch(m.get_flag("after-install-launch"));
+                    print.author_separator(m.get_one("author-separator").map(String::as_str));
+                    let (banner, culture_banners) = culture_images(m, "banner");
+                    print.banner(banner);
+                    for (culture, path) in culture_banners {
+                        print.culture_banner(culture, path);
+                    }
                     print.binaries(
                         m.get_many("binaries")
                             .map(|v| v.map(String::as_str).collect()),
                     );
+                    print.culture(m.get_one("culture").map(String::as_str));
                     print.description(m.get_one("description").map(String::as_str));
-                    print.dialog(m.get_one("dialog").map(String::as_str));
+                    print
+                        .deterministic_component_guids(m.get_flag("deterministic-component-guids"));
+                    let (dialog, culture_dialogs) = culture_images(m, "dialog");
+                    print.dialog(dialog);
+                    for (culture, path) in culture_dialogs {
+                        print.culture_dialog(culture, path);
+                    }
+                    print.dialog_set(m.get_one("dialog-set").map(String::as_str));
+                    print.dump_context(m.get_one("dump-context").map(String::as_str));
                     print.eula(m.get_one("eula").map(String::as_str));
+                    print.feature_maps(
+                        m.get_many::<String>("feature-map")
+                            .map(|v| v.map(String::as_str).collect()),
+                    );
+                    print.guid_from(m.get_one("guid-from").map(String::as_str));
                     print.help_url(m.get_one("url").map(String::as_str));
-                    print.input(m.get_one("INPUT").map(String::as_str));
+                    print.include_licenses(m.get_flag("include-licenses"));
+                    print.input(manifest_input(m));
+                    print.install_scope(m.get_one("install-scope").map(String::as_str));
+                    print.keep_author_emails(m.get_flag("keep-author-emails"));
+                    print.launch_binary(m.get_one("launch-binary").map(String::as_str));
                     print.license(m.get_one("license").map(String::as_str));
                     print.manufacturer(m.get_one("manufacturer").map(String::as_str));
+                    print.manufacturer_from(m.get_one("manufacturer-from").map(String::as_str));
+                    print.no_modify(m.get_flag("no-modify"));
+                    print.no_path(m.get_flag("no-path"));
+                    print.no_repair(m.get_flag("no-repair"));
+                    print.no_upgrade(m.get_flag("no-upgrade"));
+                    print.open(m.get_flag("open"));
                     print.output(m.get_one("output").map(String::as_str));
                     print.package(m.get_one("package").map(String::as_str));
                     print.path_guid(m.get_one("path-guid").map(String::as_str));
                     print.product_icon(m.get_one("product-icon").map(String::as_str));
                     print.product_name(m.get_one("product-name").map(String::as_str));
+                    print.run_after_install(m.get_one("run-after-install").map(String::as_str));
+                    print.schema_validate(m.get_flag("schema-validate"));
+                    print.skip_eula_dialog(m.get_flag("skip-eula-dialog"));
+                    print.skip_license_sidecar(m.get_flag("skip-license-sidecar"));
+                    print.uninstall_shortcut(m.get_flag("uninstall-shortcut"));
                     print.upgrade_guid(m.get_one("upgrade-guid").map(String::as_str));
+                    print.wxs_template_dir(m.get_one("wxs-template-dir").map(String::as_str));
                     print.build().run()
                 }
                 t => {
                     let mut print = print::license::Builder::new();
                     print.copyright_holder(m.get_one("owner").map(String::as_str));
                     print.copyright_year(m.get_one("year").map(String::as_str));
-                    print.input(m.get_one("INPUT").map(String::as_str));
+                    print.culture(m.get_one("culture").map(String::as_str));
+                    print.dump_context(m.get_one("dump-context").map(String::as_str));
+                    print.input(manifest_input(m));
                     print.output(m.get_one("output").map(String::as_str));
                     print.package(m.get_one("package").map(String::as_str));
+                    print.wxs_template_dir(m.get_one("wxs-template-dir").map(String::as_str));
                     print.build().run(&t)
                 }
             }
         }
         Some(("purge", m)) => {
             let mut purge = purge::Builder::new();
-            purge.input(m.get_one("INPUT").map(String::as_str));
+            purge.input(manifest_input(m));
             purge.build().run()
         }
         Some(("sign", m)) => {
             let mut sign = sign::Builder::new();
             sign.bin_path(m.get_one("bin-path").map(String::as_str));
             sign.capture_output(!m.get_flag("no-capture"));
+            sign.cert_subject(m.get_one("cert-subject").map(String::as_str));
             sign.description(m.get_one("description").map(String::as_str));
+            sign.dump_toolset_commands(m.get_flag("dump-toolset-commands"));
             sign.homepage(m.get_one("homepage").map(String::as_str));
-            sign.input(m.get_one("INPUT").map(String::as_str));
+            sign.input(manifest_input(m));
             sign.installer(m.get_one("installer").map(String::as_str));
             sign.package(m.get_one("package").map(String::as_str));
             sign.product_name(m.get_one("product-name").map(String::as_str));
+            sign.sign_all(m.get_flag("sign-all"));
+            sign.sign_retries(m.get_one("sign-retries").map(String::as_str));
+            sign.timeout(m.get_one("timeout").map(String::as_str));
             sign.timestamp(m.get_one("timestamp").map(String::as_str));
             sign.build().run()
         }
+        Some(("verify", m)) => {
+            let mut verify = verify::Builder::new();
+            verify.bin_path(m.get_one("bin-path").map(String::as_str));
+            verify.input(m.get_one("manifest-path").map(String::as_str));
+            verify.installer(m.get_one("INSTALLER").map(String::as_str));
+            verify.timeout(m.get_one("timeout").map(String::as_str));
+            verify.build().run()
+        }
         _ => {
             let mut create = create::Builder::new();
+            create.allow_unpublished(matches.get_flag("allow-unpublished"));
+            create.append_args(matches.get_flag("append-args"));
+            create.arp_size(matches.get_one("arp-size").map(String::as_str));
             create.bin_path(matches.get_one("bin-path").map(String::as_str));
+            create.wix_root(matches.get_one("wix-root").map(String::as_str));
+            create.bundle_upgrade_guid(matches.get_one("bundle-upgrade-guid").map(String::as_str));
+            create.bundle_version(matches.get_one("bundle-version").map(String::as_str));
             create.capture_output(!matches.get_flag("no-capture"));
+            create.changelog(matches.get_one("changelog").map(String::as_str));
+            create.check_localization(matches.get_flag("check-localization"));
+            create.checksum_manifest(matches.get_one("checksum-manifest").map(String::as_str));
             create.compiler_args(
                 matches
                     .get_many("compiler-arg")
                     .map(|v| v.map(String::as_str).collect()),
             );
+            create.component_group_refs(
+                matches
+                    .get_many("component-group-ref")
+                    .map(|v| v.map(String::as_str).collect()),
+            );
             create.culture(matches.get_one("culture").map(String::as_str));
             create.debug_build(matches.get_flag("debug-build"));
             create.profile(matches.get_one("profile").map(String::as_str));
             create.debug_name(matches.get_flag("debug-name"));
+            create.dry_run(matches.get_flag("dry-run"));
+            create.dump_defines(matches.get_flag("dump-defines"));
+            create.dump_toolset_commands(matches.get_flag("dump-toolset-commands"));
+            create.dump_wixobj(matches.get_one("dump-wixobj").map(String::as_str));
+            create.emit_relative_manifest_path(matches.get_flag("emit-relative-manifest-path"));
+            create.features(
+                matches
+                    .get_many::<String>("features")
+                    .map(|v| v.map(String::as_str).collect()),
+            );
+            create.format(matches.get_one("format").map(String::as_str));
+            create.generate_transforms(
+                matches
+                    .get_many::<String>("generate-transforms")
+                    .map(|v| v.map(String::as_str).collect()),
+            );
+            create.include_build_metadata(matches.get_flag("include-build-metadata"));
             create.includes(
                 matches
                     .get_many("include")
                     .map(|v| v.map(String::as_str).collect()),
             );
-            create.input(matches.get_one("INPUT").map(String::as_str));
+            create.input(manifest_input(matches));
             create.linker_args(
                 matches
                     .get_many("linker-arg")
                     .map(|v| v.map(String::as_str).collect()),
             );
+            create.incremental(matches.get_flag("incremental"));
             create.locale(matches.get_one("locale").map(String::as_str));
+            create.metadata_output(matches.get_one("metadata-output").map(String::as_str));
             create.name(matches.get_one("name").map(String::as_str));
+            create.no_arch_in_name(matches.get_flag("no-arch-in-name"));
             create.no_build(matches.get_flag("no-build"));
+            create.no_default_features(matches.get_flag("no-default-features"));
+            create.build_command(matches.get_one("build-command").map(String::as_str));
+            create.no_default_wix_folder(matches.get_flag("no-default-wix-folder"));
+            create.no_install_if_running(matches.get_flag("no-install-if-running"));
+            create.no_overwrite_increment(matches.get_flag("no-overwrite-increment"));
+            create.preprocess_only(matches.get_one("preprocess-only").map(String::as_str));
+            create.print_wxs(matches.get_flag("print-wxs"));
+            create.product_language(matches.get_one("product-language").map(String::as_str));
+            create.properties(
+                matches
+                    .get_many("property")
+                    .map(|v| v.map(String::as_str).collect()),
+            );
+            create.quiet_tools(matches.get_flag("quiet-tools"));
+            create.verify_manifest(matches.get_flag("verify-manifest"));
+            create.wxs_sources(
+                matches
+                    .get_many("WXS")
+                    .map(|v| v.map(String::as_str).collect()),
+            );
+            create.wxs_variable_check(matches.get_flag("wxs-variable-check"));
+            create.reinstall_mode(matches.get_one("reinstall-mode").map(String::as_str));
+            create.retain_symbols(matches.get_one("retain-symbols").map(String::as_str));
+            create.strict(matches.get_flag("strict"));
+            create.strip(matches.get_flag("strip"));
             create.target_bin_dir(matches.get_one("target-bin-dir").map(String::as_str));
             create.install(matches.get_flag("install"));
             create.output(matches.get_one("output").map(String::as_str));
+            create.output_dir(matches.get_one("output-dir").map(String::as_str));
+            create.output_layout(matches.get_one("output-layout").map(String::as_str));
             create.version(matches.get_one("install-version").map(String::as_str));
             create.package(matches.get_one("package").map(String::as_str));
-            create.target(matches.get_one("target").map(String::as_str));
+            create.targets(
+                matches
+                    .get_many::<String>("target")
+                    .map(|v| v.map(String::as_str).collect()),
+            );
+            create.keep_going(matches.get_flag("keep-going"));
+            create.timeout(matches.get_one("timeout").map(String::as_str));
             create.build().run()
         }
     };
@@ -1992,7 +5130,7 @@ fn main() {
         Ok(_) => std::process::exit(0),
         Err(e) => {
             {
-                let mut stderr = StandardStream::stderr(ColorChoice::Auto);
+                let mut stderr = StandardStream::stderr(color_choice);
                 stderr
                     .set_color(ColorSpec::new().set_fg(Some(Color::Red)).set_bold(true))
                     .expect("Coloring stderr");
@@ -0,0 +1,459 @@
+// Copyright (C) 2017 Christopher R. Field.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The fields supported in the `[package.metadata.wix]` section of a package's
+//! manifest (Cargo.toml).
+//!
+//! The [`FIELDS`] table is the single list of supported fields. It is used to
+//! print the schema with the `--print-config-schema` flag and to warn about
+//! unknown fields, or fields with the wrong type, when creating an installer.
+
+use serde_json::Value;
+
+use std::fmt;
+
+/// The subcommand that uses a field for the default _create_ subcommand.
+pub const CREATE: &str = "create";
+
+/// The subcommands that use a field when rendering the WiX Source (wxs) file.
+pub const INIT_PRINT: &str = "init, print";
+
+/// The type of the value for a field in the `[package.metadata.wix]` section.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldType {
+    /// A TOML array of strings.
+    Array,
+    /// A TOML boolean.
+    Bool,
+    /// A TOML integer that is not negative.
+    Integer,
+    /// A TOML string.
+    String,
+    /// A TOML string or array of strings.
+    StringOrArray,
+    /// A TOML string or boolean.
+    StringOrBool,
+}
+
+impl FieldType {
+    /// Gets the name of the type.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            FieldType::Array => "array",
+            FieldType::Bool => "bool",
+            FieldType::Integer => "integer",
+            FieldType::String => "string",
+            FieldType::StringOrArray => "string|array",
+            FieldType::StringOrBool => "string|bool",
+        }
+    }
+
+    /// Determines if a value from the manifest has this type.
+    pub fn matches(&self, value: &Value) -> bool {
+        match *self {
+            FieldType::Array => value
+                .as_array()
+                .map(|a| a.iter().all(Value::is_string))
+                .unwrap_or(false),
+            FieldType::Bool => value.is_boolean(),
+            FieldType::Integer => value.is_u64(),
+            FieldType::String => value.is_string(),
+            FieldType::StringOrArray => value.is_string() || FieldType::Array.matches(value),
+            FieldType::StringOrBool => value.is_string() || value.is_boolean(),
+        }
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// A field in the `[package.metadata.wix]` section.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Field {
+    /// The name, or key, of the field.
+    pub name: &'static str,
+    /// The type of the value.
+    pub field_type: FieldType,
+    /// The subcommands that read the field.
+    pub subcommands: &'static str,
+    /// The CLI option that overrides the field.
+    pub option: &'static str,
+}
+
+const fn field(
+    name: &'static str,
+    field_type: FieldType,
+    subcommands: &'static str,
+    option: &'static str,
+) -> Field {
+    Field {
+        name,
+        field_type,
+        subcommands,
+        option,
+    }
+}
+
+/// All of the supported fields in the `[package.metadata.wix]` section, in
+/// alphabetical order of the name.
+pub const FIELDS: &[Field] = &[
+    field(
+        "after-install-launch",
+        FieldType::Bool,
+        INIT_PRINT,
+        "--after-install-launch",
+    ),
+    field(
+        "allow-unpublished",
+        FieldType::Bool,
+        CREATE,
+        "--allow-unpublished",
+    ),
+    field("append-args", FieldType::Bool, CREATE, "--append-args"),
+    field("arp-size", FieldType::Integer, CREATE, "--arp-size"),
+    field(
+        "author-separator",
+        FieldType::String,
+        INIT_PRINT,
+        "--author-separator",
+    ),
+    field("banner", FieldType::String, INIT_PRINT, "-b,--banner"),
+    field(
+        "build-command",
+        FieldType::String,
+        CREATE,
+        "--build-command",
+    ),
+    field(
+        "bundle-upgrade-guid",
+        FieldType::String,
+        CREATE,
+        "--bundle-upgrade-guid",
+    ),
+    field(
+        "bundle-version",
+        FieldType::String,
+        CREATE,
+        "--bundle-version",
+    ),
+    field("changelog", FieldType::StringOrBool, CREATE, "--changelog"),
+    field(
+        "compiler-args",
+        FieldType::Array,
+        CREATE,
+        "-C,--compiler-arg",
+    ),
+    field(
+        "component-group-ref",
+        FieldType::Array,
+        CREATE,
+        "--component-group-ref",
+    ),
+    field("culture", FieldType::StringOrArray, CREATE, "-c,--culture"),
+    field("dbg-build", FieldType::Bool, CREATE, "-d,--dbg-build"),
+    field("dbg-name", FieldType::Bool, CREATE, "-D,--dbg-name"),
+    field(
+        "default-culture",
+        FieldType::String,
+        "create, init, print",
+        "-c,--culture",
+    ),
+    field(
+        "deterministic-component-guids",
+        FieldType::Bool,
+        INIT_PRINT,
+        "--deterministic-component-guids",
+    ),
+    field("dialog", FieldType::String, INIT_PRINT, "-D,--dialog"),
+    field("dialog-set", FieldType::String, INIT_PRINT, "--dialog-set"),
+    field(
+        "emit-relative-manifest-path",
+        FieldType::Bool,
+        CREATE,
+        "--emit-relative-manifest-path",
+    ),
+    field("eula", FieldType::StringOrBool, INIT_PRINT, "-e,--eula"),
+    field("feature-map", FieldType::Array, INIT_PRINT, "--feature-map"),
+    field("features", FieldType::Array, CREATE, "-F,--features"),
+    field("format", FieldType::String, CREATE, "--format"),
+    field("guid-from", FieldType::String, INIT_PRINT, "--guid-from"),
+    field("include", FieldType::Array, CREATE, "-I,--include"),
+    field(
+        "include-build-metadata",
+        FieldType::Bool,
+        CREATE,
+        "--include-build-metadata",
+    ),
+    field("incremental", FieldType::Bool, CREATE, "--incremental"),
+    field(
+        "install-scope",
+        FieldType::String,
+        INIT_PRINT,
+        "--install-scope",
+    ),
+    field(
+        "keep-author-emails",
+        FieldType::Bool,
+        INIT_PRINT,
+        "--keep-author-emails",
+    ),
+    field(
+        "launch-binary",
+        FieldType::String,
+        INIT_PRINT,
+        "--launch-binary",
+    ),
+    field(
+        "license",
+        FieldType::StringOrBool,
+        INIT_PRINT,
+        "-l,--license",
+    ),
+    field("linker-args", FieldType::Array, CREATE, "-L,--linker-arg"),
+    field("locale", FieldType::String, CREATE, "-l,--locale"),
+    field(
+        "manufacturer-from",
+        FieldType::String,
+        INIT_PRINT,
+        "--manufacturer-from",
+    ),
+    field("name", FieldType::String, CREATE, "-n,--name"),
+    field(
+        "no-arch-in-name",
+        FieldType::Bool,
+        CREATE,
+        "--no-arch-in-name",
+    ),
+    field("no-build", FieldType::Bool, CREATE, "--no-build"),
+    field(
+        "no-default-features",
+        FieldType::Bool,
+        CREATE,
+        "--no-default-features",
+    ),
+    field(
+        "no-default-wix-folder",
+        FieldType::Bool,
+        CREATE,
+        "--no-default-wix-folder",
+    ),
+    field("no-modify", FieldType::Bool, INIT_PRINT, "--no-modify"),
+    field(
+        "no-overwrite-increment",
+        FieldType::Bool,
+        CREATE,
+        "--no-overwrite-increment",
+    ),
+    field("no-path", FieldType::Bool, INIT_PRINT, "--no-path"),
+    field("no-repair", FieldType::Bool, INIT_PRINT, "--no-repair"),
+    field("no-upgrade", FieldType::Bool, INIT_PRINT, "--no-upgrade"),
+    field("output", FieldType::String, "clean, create", "-o,--output"),
+    field(
+        "output-layout",
+        FieldType::String,
+        CREATE,
+        "--output-layout",
+    ),
+    field("path-guid", FieldType::String, INIT_PRINT, "--path-guid"),
+    field(
+        "product-icon",
+        FieldType::String,
+        INIT_PRINT,
+        "--product-icon",
+    ),
+    field(
+        "product-language",
+        FieldType::String,
+        CREATE,
+        "--product-language",
+    ),
+    field("profile", FieldType::String, CREATE, "--profile"),
+    field("properties", FieldType::Array, CREATE, "--property"),
+    field("quiet-tools", FieldType::Bool, CREATE, "--quiet-tools"),
+    field(
+        "reinstall-mode",
+        FieldType::String,
+        CREATE,
+        "--reinstall-mode",
+    ),
+    field(
+        "retain-symbols",
+        FieldType::StringOrBool,
+        CREATE,
+        "--retain-symbols",
+    ),
+    field(
+        "run-after-install",
+        FieldType::String,
+        INIT_PRINT,
+        "--run-after-install",
+    ),
+    field(
+        "skip-eula-dialog",
+        FieldType::Bool,
+        INIT_PRINT,
+        "--skip-eula-dialog",
+    ),
+    field(
+        "skip-license-sidecar",
+        FieldType::Bool,
+        INIT_PRINT,
+        "--skip-license-sidecar",
+    ),
+    field("strip", FieldType::Bool, CREATE, "--strip"),
+    field(
+        "uninstall-shortcut",
+        FieldType::Bool,
+        INIT_PRINT,
+        "--uninstall-shortcut",
+    ),
+    field(
+        "upgrade-guid",
+        FieldType::String,
+        INIT_PRINT,
+        "--upgrade-guid",
+    ),
+    field(
+        "verify-manifest",
+        FieldType::Bool,
+        CREATE,
+        "--verify-manifest",
+    ),
+    field("version", FieldType::String, CREATE, "-i,--install-version"),
+    field(
+        "wxs-template-dir",
+        FieldType::String,
+        INIT_PRINT,
+        "--wxs-template-dir",
+    ),
+    field(
+        "wxs-variable-check",
+        FieldType::Bool,
+        CREATE,
+        "--wxs-variable-check",
+    ),
+];
+
+/// Finds a supported field by its name.
+///
+/// # Examples
+///
+/// ```rust
+/// use wix::metadata::{self, FieldType};
+///
+/// let field = metadata::find("culture").unwrap();
+/// assert_eq!(field.field_type, FieldType::StringOrArray);
+/// assert_eq!(field.option, "-c,--culture");
+/// assert!(metadata::find("not-a-field").is_none());
+/// ```
+pub fn find(name: &str) -> Option<&'static Field> {
+    FIELDS.iter().find(|f| f.name == name)
+}
+
+/// Gets a description of each problem with the fields in the
+/// `[package.metadata.wix]` section of a package's metadata.
+///
+/// A problem is a field that is not supported or a field with a value of the
+/// wrong type. An empty list is returned if the section does not exist.
+pub fn problems(metadata: &Value) -> Vec<String> {
+    metadata
+        .get("wix")
+        .and_then(|w| w.as_object())
+        .map(|table| {
+            table
+                .iter()
+                .filter_map(|(name, value)| match find(name) {
+                    None => Some(format!(
+                        "The '{name}' field in the '[package.metadata.wix]' section is not \
+                         supported and will be ignored. Use the '--print-config-schema' flag \
+                         to list the supported fields."
+                    )),
+                    Some(field) if !field.field_type.matches(value) => Some(format!(
+                        "The '{name}' field in the '[package.metadata.wix]' section should be \
+                         a {} value.",
+                        field.field_type
+                    )),
+                    Some(_) => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn fields_are_sorted_and_unique() {
+        assert!(FIELDS.windows(2).all(|w| w[0].name < w[1].name));
+    }
+
+    #[test]
+    fn field_type_matches_works() {
+        assert!(FieldType::Array.matches(&json!(["a", "b"])));
+        assert!(!FieldType::Array.matches(&json!([1])));
+        assert!(!FieldType::Array.matches(&json!("a")));
+        assert!(FieldType::Bool.matches(&json!(true)));
+        assert!(!FieldType::Bool.matches(&json!("true")));
+        assert!(FieldType::Integer.matches(&json!(2048)));
+        assert!(!FieldType::Integer.matches(&json!(-1)));
+        assert!(!FieldType::Integer.matches(&json!("2048")));
+        assert!(FieldType::String.matches(&json!("a")));
+        assert!(!FieldType::String.matches(&json!(1)));
+        assert!(FieldType::StringOrArray.matches(&json!("a")));
+        assert!(FieldType::StringOrArray.matches(&json!(["a", "b"])));
+        assert!(!FieldType::StringOrArray.matches(&json!(false)));
+        assert!(FieldType::StringOrBool.matches(&json!(false)));
+        assert!(FieldType::StringOrBool.matches(&json!("a")));
+        assert!(!FieldType::StringOrBool.matches(&json!([])));
+    }
+
+    #[test]
+    fn problems_without_section_works() {
+        assert!(problems(&Value::Null).is_empty());
+        assert!(problems(&json!({"other": {"culture": 1}})).is_empty());
+    }
+
+    #[test]
+    fn problems_with_supported_fields_works() {
+        let metadata = json!({
+            "wix": {
+                "culture": "fr-FR",
+                "eula": false,
+                "include": ["One.wxs"],
+                "no-build": true,
+            }
+        });
+        assert!(problems(&metadata).is_empty());
+    }
+
+    #[test]
+    fn problems_with_unknown_field_works() {
+        let actual = problems(&json!({"wix": {"cultrue": "fr-FR"}}));
+        assert_eq!(actual.len(), 1);
+        assert!(actual[0].contains("'cultrue'"));
+    }
+
+    #[test]
+    fn problems_with_wrong_type_works() {
+        let actual = problems(&json!({"wix": {"no-build": "yes"}}));
+        assert_eq!(actual.len(), 1);
+        assert!(actual[0].contains("bool"));
+    }
+}
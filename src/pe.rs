@@ -0,0 +1,270 @@
+// Copyright (C) 2017 Christopher R. Field.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reading of a Portable Executable (PE), i.e. an `.exe` file.
+//!
+//! Only the parts of the [PE format] needed by the other modules are read,
+//! which are the machine type in the COFF file header, i.e. the architecture
+//! the executable was compiled for, and the embedded resources.
+//!
+//! [PE format]: https://learn.microsoft.com/en-us/windows/win32/debug/pe-format
+
+use crate::Error;
+use crate::Result;
+use crate::WixArch;
+
+/// The machine type for the x86 32-bit architecture.
+const IMAGE_FILE_MACHINE_I386: u16 = 0x014c;
+
+/// The machine type for the ARM 32-bit (Thumb-2) architecture.
+const IMAGE_FILE_MACHINE_ARMNT: u16 = 0x01c4;
+
+/// The machine type for the x86_64 or AMD64 64-bit architecture.
+const IMAGE_FILE_MACHINE_AMD64: u16 = 0x8664;
+
+/// The machine type for the ARM 64-bit architecture.
+const IMAGE_FILE_MACHINE_ARM64: u16 = 0xaa64;
+
+/// The flag for a resource directory entry that points to a subdirectory, or
+/// has a string name instead of an ID.
+pub const HIGH_BIT: u32 = 0x8000_0000;
+
+/// Gets the architecture of an executable from its content.
+///
+/// `None` is returned if the machine type is not one of the architectures
+/// supported by the WiX Toolset. An error occurs if the content is not an
+/// executable.
+pub fn arch(executable: &[u8]) -> Result<Option<WixArch>> {
+    PeImage::parse(executable).map(|image| image.arch())
+}
+
+/// The headers, sections, and resources of a Portable Executable (PE).
+pub struct PeImage<'a> {
+    data: &'a [u8],
+    machine: u16,
+    sections: Vec<Section>,
+    resources: Option<usize>,
+}
+
+/// A section header, used to map relative virtual addresses (RVAs) to file
+/// offsets.
+struct Section {
+    virtual_address: u32,
+    virtual_size: u32,
+    raw_offset: u32,
+}
+
+impl<'a> PeImage<'a> {
+    /// Parses the headers of an executable from its content.
+    ///
+    /// An error occurs if the content is not an executable or the headers are
+    /// malformed.
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        if data.get(0..2) != Some(b"MZ") {
+            return Err(Error::Generic(String::from(
+                "The file is not an executable because it does not start with the 'MZ' signature",
+            )));
+        }
+        let pe = read_u32(data, 0x3c)? as usize;
+        if data.get(pe..add(pe, 4)?) != Some(b"PE\0\0") {
+            return Err(malformed("PE signature"));
+        }
+        let coff = add(pe, 4)?;
+        let machine = read_u16(data, coff)?;
+        let number_of_sections = read_u16(data, add(coff, 2)?)? as usize;
+        let optional = add(coff, 20)?;
+        let size_of_optional_header = read_u16(data, add(coff, 16)?)? as usize;
+        let data_directories = match read_u16(data, optional)? {
+            0x10b => add(optional, 96)?,
+            0x20b => add(optional, 112)?,
+            _ => return Err(malformed("optional header")),
+        };
+        let section_table = add(optional, size_of_optional_header)?;
+        let sections = (0..number_of_sections)
+            .map(|index| {
+                let offset = add(section_table, index * 40)?;
+                Ok(Section {
+                    virtual_size: read_u32(data, add(offset, 8)?)?
+                        .max(read_u32(data, add(offset, 16)?)?),
+                    virtual_address: read_u32(data, add(offset, 12)?)?,
+                    raw_offset: read_u32(data, add(offset, 20)?)?,
+                })
+            })
+            .collect::<Result<Vec<Section>>>()?;
+        let mut image = PeImage {
+            data,
+            machine,
+            sections,
+            resources: None,
+        };
+        // The resource table is the third data directory.
+        let resources_rva = read_u32(data, add(data_directories, 2 * 8)?)?;
+        if resources_rva != 0 {
+            image.resources = Some(image.offset(resources_rva)?);
+        }
+        Ok(image)
+    }
+
+    /// Gets the architecture the executable was compiled for.
+    ///
+    /// `None` is returned if the machine type is not one of the architectures
+    /// supported by the WiX Toolset.
+    pub fn arch(&self) -> Option<WixArch> {
+        match self.machine {
+            IMAGE_FILE_MACHINE_I386 => Some(WixArch::X86),
+            IMAGE_FILE_MACHINE_ARMNT => Some(WixArch::Arm),
+            IMAGE_FILE_MACHINE_AMD64 => Some(WixArch::X64),
+            IMAGE_FILE_MACHINE_ARM64 => Some(WixArch::Arm64),
+            _ => None,
+        }
+    }
+
+    /// Gets the content of a resource by its type and ID.
+    ///
+    /// The first resource of the type is used if an ID is not specified. The
+    /// first language is always used. `None` is returned if the executable
+    /// does not have any resources of the type.
+    pub fn resource(&self, kind: u32, id: Option<u32>) -> Result<Option<&'a [u8]>> {
+        let Some(resources) = self.resources else {
+            return Ok(None);
+        };
+        let Some(names) = self.entry(resources, Some(kind))? else {
+            return Ok(None);
+        };
+        let languages = self
+            .entry(self.subdirectory(resources, names)?, id)?
+            .ok_or_else(|| malformed("resource directory"))?;
+        let data_entry = self
+            .entry(self.subdirectory(resources, languages)?, None)?
+            .ok_or_else(|| malformed("resource directory"))?;
+        if data_entry & HIGH_BIT != 0 {
+            return Err(malformed("resource data entry"));
+        }
+        let data_entry = add(resources, data_entry as usize)?;
+        let offset = self.offset(read_u32(self.data, data_entry)?)?;
+        let size = read_u32(self.data, add(data_entry, 4)?)? as usize;
+        self.data
+            .get(offset..add(offset, size)?)
+            .map(Some)
+            .ok_or_else(|| malformed("resource data"))
+    }
+
+    /// Converts a relative virtual address (RVA) to an offset in the file.
+    fn offset(&self, rva: u32) -> Result<usize> {
+        self.sections
+            .iter()
+            .find(|s| rva >= s.virtual_address && rva - s.virtual_address < s.virtual_size)
+            .and_then(|s| s.raw_offset.checked_add(rva - s.virtual_address))
+            .map(|offset| offset as usize)
+            .ok_or_else(|| malformed("section table"))
+    }
+
+    /// Gets the offset of a subdirectory from the value of a directory entry.
+    fn subdirectory(&self, resources: usize, value: u32) -> Result<usize> {
+        if value & HIGH_BIT == 0 {
+            Err(malformed("resource directory"))
+        } else {
+            add(resources, (value & !HIGH_BIT) as usize)
+        }
+    }
+
+    /// Finds the value of the entry with an ID in a resource directory.
+    ///
+    /// The first entry is used if an ID is not specified.
+    fn entry(&self, directory: usize, id: Option<u32>) -> Result<Option<u32>> {
+        let count = read_u16(self.data, add(directory, 12)?)? as usize
+            + read_u16(self.data, add(directory, 14)?)? as usize;
+        for index in 0..count {
+            let entry = add(directory, 16 + index * 8)?;
+            let name = read_u32(self.data, entry)?;
+            if id.map_or(true, |id| name & HIGH_BIT == 0 && name == id) {
+                return read_u32(self.data, add(entry, 4)?).map(Some);
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Adds an offset read from the executable to a position, which fails instead
+/// of overflowing for a malformed executable.
+pub fn add(position: usize, offset: usize) -> Result<usize> {
+    position
+        .checked_add(offset)
+        .ok_or_else(|| malformed("file"))
+}
+
+/// Reads a little-endian `u16` at an offset.
+pub fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    data.get(offset..add(offset, 2)?)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| malformed("file"))
+}
+
+/// Reads a little-endian `u32` at an offset.
+pub fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    data.get(offset..add(offset, 4)?)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| malformed("file"))
+}
+
+/// Creates the error for a malformed part of an executable.
+pub fn malformed(part: &str) -> Error {
+    Error::Generic(format!("The {part} of the executable is malformed"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn executable(machine: u16) -> Vec<u8> {
+        let mut data = vec![0u8; 0x58 + 240];
+        data[0..2].copy_from_slice(b"MZ");
+        data[0x3c..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+        data[0x40..0x44].copy_from_slice(b"PE\0\0");
+        data[0x44..0x46].copy_from_slice(&machine.to_le_bytes());
+        data[0x54..0x56].copy_from_slice(&240u16.to_le_bytes());
+        data[0x58..0x5a].copy_from_slice(&0x20bu16.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn arch_works() {
+        assert_eq!(arch(&executable(0x014c)).unwrap(), Some(WixArch::X86));
+        assert_eq!(arch(&executable(0x01c4)).unwrap(), Some(WixArch::Arm));
+        assert_eq!(arch(&executable(0x8664)).unwrap(), Some(WixArch::X64));
+        assert_eq!(arch(&executable(0xaa64)).unwrap(), Some(WixArch::Arm64));
+    }
+
+    #[test]
+    fn arch_with_unknown_machine_works() {
+        assert_eq!(arch(&executable(0x0200)).unwrap(), None);
+    }
+
+    #[test]
+    fn arch_without_executable_fails() {
+        assert!(arch(b"Not an executable").is_err());
+    }
+
+    #[test]
+    fn arch_with_truncated_executable_fails() {
+        assert!(arch(&executable(0x8664)[..0x42]).is_err());
+    }
+
+    #[test]
+    fn resource_without_resources_works() {
+        let data = executable(0x8664);
+        let image = PeImage::parse(&data).unwrap();
+        assert_eq!(image.resource(3, None).unwrap(), None);
+    }
+}
@@ -18,15 +18,17 @@ use chrono::{Datelike, Utc};
 
 use crate::manifest;
 use crate::package;
+use crate::Cultures;
 use crate::Error;
 use crate::Result;
 use crate::Template;
 
-use log::debug;
+use log::{debug, info, trace};
 
 use mustache::{self, MapBuilder};
 
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use cargo_metadata::Package;
 
@@ -37,9 +39,12 @@ use super::RenderOutput;
 pub struct Builder<'a> {
     copyright_year: Option<&'a str>,
     copyright_holder: Option<&'a str>,
+    culture: Option<&'a str>,
+    dump_context: Option<&'a str>,
     input: Option<&'a str>,
     output: Option<&'a str>,
     package: Option<&'a str>,
+    wxs_template_dir: Option<&'a str>,
 }
 
 impl<'a> Builder<'a> {
@@ -48,9 +53,12 @@ impl<'a> Builder<'a> {
         Builder {
             copyright_year: None,
             copyright_holder: None,
+            culture: None,
+            dump_context: None,
             input: None,
             output: None,
             package: None,
+            wxs_template_dir: None,
         }
     }
 
@@ -76,6 +84,29 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the culture for the generated license.
+    ///
+    /// The translation of the license template for the culture is used if one
+    /// is available; otherwise, the English license template is used. The
+    /// `auto` value derives the culture from the locale of the system.
+    ///
+    /// The default is to use the `culture` field in the `[package.metadata.wix]`
+    /// section of the package's manifest (Cargo.toml), or `en-US` if the field
+    /// does not exist.
+    pub fn culture(&mut self, c: Option<&'a str>) -> &mut Self {
+        self.culture = c;
+        self
+    }
+
+    /// Sets the path to a file for the context used to render the license.
+    ///
+    /// The copyright holder and year are written to the file as JSON before
+    /// the license is rendered. The default is to not write the context.
+    pub fn dump_context(&mut self, d: Option<&'a str>) -> &mut Self {
+        self.dump_context = d;
+        self
+    }
+
     /// Sets the path to a package's manifest (Cargo.toml) to be used to
     /// generate license in the Rich Text Format (RTF).
     ///
@@ -92,7 +123,8 @@ impl<'a> Builder<'a> {
     ///
     /// The default is to print all output to `STDOUT`. This method can be used
     /// to specify that the generated license be written, or "printed", to a
-    /// file instead of `STDOUT`.
+    /// file instead of `STDOUT`. A value of `-` explicitly prints to `STDOUT`,
+    /// and any missing parent folders of the file are created.
     pub fn output(&mut self, o: Option<&'a str>) -> &mut Self {
         self.output = o;
         self
@@ -105,14 +137,33 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the folder of templates that overrides the embedded templates.
+    ///
+    /// The license template is read from the folder with the same file name as
+    /// the embedded template, e.g. `MIT.rtf.mustache`, and a translation is
+    /// read from a sub-folder named after the culture, e.g.
+    /// `fr-FR\MIT.rtf.mustache`. The embedded template is used if the folder
+    /// does not contain the license template.
+    ///
+    /// The default is to use the `wxs-template-dir` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or only the embedded templates if the field does not exist.
+    pub fn wxs_template_dir(&mut self, w: Option<&'a str>) -> &mut Self {
+        self.wxs_template_dir = w;
+        self
+    }
+
     /// Builds an execution context based on the configuration.
     pub fn build(&self) -> Execution {
         Execution {
             copyright_holder: self.copyright_holder.map(String::from),
             copyright_year: self.copyright_year.map(String::from),
+            culture: self.culture.map(String::from),
+            dump_context: self.dump_context.map(PathBuf::from),
             input: self.input.map(PathBuf::from),
-            output: self.output.map(PathBuf::from),
+            output: super::output_path(self.output),
             package: self.package.map(PathBuf::from),
+            wxs_template_dir: self.wxs_template_dir.map(PathBuf::from),
         }
     }
 }
@@ -128,9 +179,12 @@ impl<'a> Default for Builder<'a> {
 pub struct Execution {
     copyright_holder: Option<String>,
     copyright_year: Option<String>,
+    culture: Option<String>,
+    dump_context: Option<PathBuf>,
     input: Option<PathBuf>,
     output: Option<PathBuf>,
     package: Option<PathBuf>,
+    wxs_template_dir: Option<PathBuf>,
 }
 
 impl Execution {
@@ -144,15 +198,43 @@ impl Execution {
     pub fn render(self, template: &Template) -> Result<RenderOutput> {
         debug!("copyright_holder = {:?}", self.copyright_holder);
         debug!("copyright_year = {:?}", self.copyright_year);
+        debug!("culture = {:?}", self.culture);
+        debug!("dump_context = {:?}", self.dump_context);
         debug!("input = {:?}", self.input);
         debug!("output = {:?}", self.output);
+        debug!("wxs_template_dir = {:?}", self.wxs_template_dir);
         let manifest = manifest(self.input.as_ref())?;
         let package = package(&manifest, self.package.as_ref().and_then(|p| p.to_str()))?;
-        let template = mustache::compile_str(template.to_str())?;
+        let culture = self.culture(&package)?;
+        debug!("culture = {:?}", culture);
+        let template_dir = super::template_dir(self.wxs_template_dir.as_ref(), &package)?;
+        debug!("template_dir = {:?}", template_dir);
+        let custom_template = template_dir
+            .map(|d| template.read_from(&d, Some(&culture)))
+            .transpose()?
+            .flatten();
+        let template = if let Some(custom_template) = custom_template {
+            mustache::compile_str(&custom_template)?
+        } else {
+            mustache::compile_str(template.to_localized_str(&culture).unwrap_or_else(|| {
+                if culture != Cultures::EnUs {
+                    info!(
+                        "The '{}' license is not translated for the '{}' culture, so the \
+                         English license is used",
+                        template, culture
+                    );
+                }
+                template.to_str()
+            }))?
+        };
         let data = MapBuilder::new()
             .insert_str("copyright-year", self.copyright_year())
             .insert_str("copyright-holder", self.copyright_holder(&package)?)
             .build();
+        if let Some(path) = &self.dump_context {
+            info!("Writing the render context to '{}'", path.display());
+            super::write_context(&data, path)?;
+        }
         let rendered = template.render_data_to_string(&data).map_err(Error::from)?;
 
         Ok(RenderOutput {
@@ -165,7 +247,36 @@ impl Execution {
         if let Some(ref h) = self.copyright_holder {
             Ok(h.to_owned())
         } else {
-            super::authors(manifest)
+            super::authors(manifest, super::DEFAULT_AUTHOR_SEPARATOR, false)
+        }
+    }
+
+    fn culture(&self, manifest: &Package) -> Result<Cultures> {
+        let culture = if let Some(culture) = &self.culture {
+            culture.as_str()
+        } else if let Some(pkg_meta_wix_culture) =
+            crate::metadata_cultures(&manifest.metadata).and_then(|c| c.first().copied())
+        {
+            pkg_meta_wix_culture
+        } else if let Some(pkg_meta_wix_default_culture) = manifest
+            .metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("default-culture"))
+            .and_then(|c| c.as_str())
+        {
+            pkg_meta_wix_default_culture
+        } else {
+            return Ok(Cultures::EnUs);
+        };
+        if culture
+            .trim()
+            .eq_ignore_ascii_case(crate::create::AUTO_CULTURE)
+        {
+            trace!("Deriving the culture from the locale of the system");
+            Ok(crate::create::system_culture())
+        } else {
+            Cultures::from_str(culture)
         }
     }
 
@@ -206,6 +317,14 @@ mod tests {
             assert_eq!(actual.copyright_year, Some(EXPECTED));
         }
 
+        #[test]
+        fn culture_works() {
+            const EXPECTED: &str = "fr-FR";
+            let mut actual = Builder::new();
+            actual.culture(Some(EXPECTED));
+            assert_eq!(actual.culture, Some(EXPECTED));
+        }
+
         #[test]
         fn input_works() {
             const EXPECTED: &str = "Example.wxs";
@@ -221,6 +340,14 @@ mod tests {
             actual.output(Some(EXPECTED));
             assert_eq!(actual.output, Some(EXPECTED));
         }
+
+        #[test]
+        fn wxs_template_dir_works() {
+            const EXPECTED: &str = "C:\\Example\\templates";
+            let mut actual = Builder::new();
+            actual.wxs_template_dir(Some(EXPECTED));
+            assert_eq!(actual.wxs_template_dir, Some(EXPECTED));
+        }
     }
 
     mod execution {
@@ -272,5 +399,182 @@ mod tests {
                 .copyright_year();
             assert_eq!(actual, String::from(EXPECTED));
         }
+
+        #[test]
+        fn culture_works() {
+            let manifest = serde_json::from_str(MIN_MANIFEST).expect("Parsing TOML");
+            let actual = Execution::default().culture(&manifest).unwrap();
+            assert_eq!(actual, Cultures::EnUs);
+        }
+
+        #[test]
+        fn culture_with_override_works() {
+            let manifest = serde_json::from_str(MIN_MANIFEST).expect("Parsing TOML");
+            let actual = Builder::new()
+                .culture(Some("Fr-Fr"))
+                .build()
+                .culture(&manifest)
+                .unwrap();
+            assert_eq!(actual, Cultures::FrFr);
+        }
+
+        #[test]
+        fn culture_metadata_works() {
+            const MANIFEST: &str = r#"{
+                "name": "Example",
+                "version": "0.1.0",
+                "authors": ["First Last <first.last@example.com>"],
+
+                "id": "",
+                "dependencies": [],
+                "targets": [],
+                "features": {},
+                "manifest_path": "",
+                "metadata": {
+                    "wix": {
+                        "culture": "de-DE"
+                    }
+                }
+            }"#;
+            let manifest = serde_json::from_str(MANIFEST).expect("Parsing TOML");
+            let actual = Execution::default().culture(&manifest).unwrap();
+            assert_eq!(actual, Cultures::DeDe);
+        }
+
+        #[test]
+        fn culture_metadata_array_works() {
+            const MANIFEST: &str = r#"{
+                "name": "Example",
+                "version": "0.1.0",
+                "authors": ["First Last <first.last@example.com>"],
+
+                "id": "",
+                "dependencies": [],
+                "targets": [],
+                "features": {},
+                "manifest_path": "",
+                "metadata": {
+                    "wix": {
+                        "culture": ["fr-FR", "de-DE"]
+                    }
+                }
+            }"#;
+            let manifest = serde_json::from_str(MANIFEST).expect("Parsing TOML");
+            let actual = Execution::default().culture(&manifest).unwrap();
+            assert_eq!(actual, Cultures::FrFr);
+        }
+
+        #[test]
+        fn culture_default_culture_metadata_works() {
+            const MANIFEST: &str = r#"{
+                "name": "Example",
+                "version": "0.1.0",
+                "authors": ["First Last <first.last@example.com>"],
+
+                "id": "",
+                "dependencies": [],
+                "targets": [],
+                "features": {},
+                "manifest_path": "",
+                "metadata": {
+                    "wix": {
+                        "default-culture": "es-ES"
+                    }
+                }
+            }"#;
+            let manifest = serde_json::from_str(MANIFEST).expect("Parsing TOML");
+            let actual = Execution::default().culture(&manifest).unwrap();
+            assert_eq!(actual, Cultures::EsEs);
+        }
+
+        #[test]
+        fn culture_with_unknown_culture_fails() {
+            let manifest = serde_json::from_str(MIN_MANIFEST).expect("Parsing TOML");
+            assert!(Builder::new()
+                .culture(Some("xx-XX"))
+                .build()
+                .culture(&manifest)
+                .is_err());
+        }
+
+        const MIT_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+            license = "MIT"
+        "#;
+
+        #[test]
+        fn render_with_translated_culture_works() {
+            let project = crate::tests::setup_project(MIT_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let actual = Builder::new()
+                .input(input.to_str())
+                .culture(Some("fr-FR"))
+                .build()
+                .render(&Template::Mit)
+                .unwrap();
+            assert!(actual.rendered.contains("\\lang1036 Copyright (c)"));
+            assert!(actual.rendered.contains("First Last"));
+        }
+
+        #[test]
+        fn render_with_untranslated_culture_works() {
+            let project = crate::tests::setup_project(MIT_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let actual = Builder::new()
+                .input(input.to_str())
+                .culture(Some("ja-JP"))
+                .build()
+                .render(&Template::Mit)
+                .unwrap();
+            assert!(actual.rendered.contains("\\lang9 Copyright (c)"));
+        }
+
+        #[test]
+        fn render_with_template_dir_works() {
+            let project = crate::tests::setup_project(MIT_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let templates = project.path().join("templates");
+            std::fs::create_dir_all(templates.join("fr-FR")).unwrap();
+            std::fs::write(
+                templates.join("MIT.rtf.mustache"),
+                "Custom {{copyright-holder}}",
+            )
+            .unwrap();
+            std::fs::write(
+                templates.join("fr-FR").join("MIT.rtf.mustache"),
+                "Personnalisé {{copyright-holder}}",
+            )
+            .unwrap();
+            let actual = Builder::new()
+                .input(input.to_str())
+                .wxs_template_dir(templates.to_str())
+                .build()
+                .render(&Template::Mit)
+                .unwrap();
+            assert_eq!(actual.rendered, "Custom First Last");
+            let actual = Builder::new()
+                .input(input.to_str())
+                .culture(Some("fr-FR"))
+                .wxs_template_dir(templates.to_str())
+                .build()
+                .render(&Template::Mit)
+                .unwrap();
+            assert_eq!(actual.rendered, "Personnalisé First Last");
+        }
+
+        #[test]
+        fn render_with_template_dir_without_template_works() {
+            let project = crate::tests::setup_project(MIT_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let actual = Builder::new()
+                .input(input.to_str())
+                .wxs_template_dir(project.path().to_str())
+                .build()
+                .render(&Template::Mit)
+                .unwrap();
+            assert!(actual.rendered.contains("\\lang9 Copyright (c)"));
+        }
     }
 }
@@ -19,6 +19,7 @@
 use itertools::Itertools;
 use log::warn;
 pub mod license;
+pub(crate) mod schema;
 pub mod wxs;
 
 use crate::Error;
@@ -26,14 +27,21 @@ use crate::Result;
 
 use log::trace;
 
+use mustache::Data;
+
 use regex::Regex;
 
-use std::fs::File;
+use serde_json::Value;
+
+use std::fs::{self, File};
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use cargo_metadata::Package;
 
+/// The value for the destination that explicitly prints to `STDOUT`.
+pub const STDOUT: &str = "-";
+
 /// The result of rendering a template (main.wxs, License.rtf, ...)
 pub struct RenderOutput {
     /// The path the template should be written to
@@ -73,9 +81,21 @@ impl RenderOutput {
     }
 }
 
+/// Gets the path to write the output to from the `output` option.
+///
+/// `None` is returned if the output is not specified or is [`STDOUT`], in which
+/// case the output is printed to `STDOUT`.
+fn output_path(output: Option<&str>) -> Option<PathBuf> {
+    output.filter(|o| *o != STDOUT).map(PathBuf::from)
+}
+
 fn destination(output: Option<&PathBuf>) -> Result<Box<dyn Write>> {
     if let Some(ref output) = output {
         trace!("An output path has been explicitly specified");
+        if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+            trace!("Creating the '{}' parent folder", parent.display());
+            fs::create_dir_all(parent)?;
+        }
         let f = File::create(output)?;
         Ok(Box::new(f))
     } else {
@@ -87,18 +107,96 @@ fn destination(output: Option<&PathBuf>) -> Result<Box<dyn Write>> {
     }
 }
 
-fn authors(package: &Package) -> Result<String> {
-    let result = package
+/// Writes the data used to render a template to a file as JSON.
+///
+/// This is for debugging the values that are substituted into a template.
+/// Any missing parent folders of the file are created.
+fn write_context(data: &Data, path: &Path) -> Result<()> {
+    trace!("Writing the render context to '{}'", path.display());
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, format!("{:#}\n", context_json(data)))?;
+    Ok(())
+}
+
+/// Converts the data used to render a template to JSON.
+///
+/// The keys of a map are sorted. A lambda cannot be represented, so it is
+/// converted to `null`.
+fn context_json(data: &Data) -> Value {
+    match data {
+        Data::Null | Data::Fun(..) => Value::Null,
+        Data::String(s) => Value::String(s.clone()),
+        Data::Bool(b) => Value::Bool(*b),
+        Data::Vec(v) => Value::Array(v.iter().map(context_json).collect()),
+        Data::Map(m) => Value::Object(
+            m.iter()
+                .map(|(k, v)| (k.clone(), context_json(v)))
+                .collect(),
+        ),
+    }
+}
+
+/// Gets the folder of templates that overrides the embedded templates.
+///
+/// The folder from the `--wxs-template-dir` option is used first, followed by
+/// the `wxs-template-dir` field in the `[package.metadata.wix]` section, which
+/// is relative to the package's manifest (Cargo.toml). `None` is returned if
+/// neither is specified, and an error occurs if the folder does not exist.
+fn template_dir(dir: Option<&PathBuf>, package: &Package) -> Result<Option<PathBuf>> {
+    let dir = if let Some(dir) = dir {
+        trace!("A template folder has been explicitly specified");
+        dir.to_owned()
+    } else if let Some(pkg_meta_wix_template_dir) = package
+        .metadata
+        .get("wix")
+        .and_then(|w| w.as_object())
+        .and_then(|t| t.get("wxs-template-dir"))
+        .and_then(|d| d.as_str())
+    {
+        trace!("Using the 'wxs-template-dir' field from the package's manifest");
+        crate::package_relative_path(package, pkg_meta_wix_template_dir)
+    } else {
+        return Ok(None);
+    };
+    if dir.is_dir() {
+        Ok(Some(dir))
+    } else {
+        Err(Error::Generic(format!(
+            "The '{}' template folder does not exist. Please check the path \
+             used with the '--wxs-template-dir' option or the \
+             'wxs-template-dir' field in the '[package.metadata.wix]' section.",
+            dir.display()
+        )))
+    }
+}
+
+/// The separator used between the authors when all of them are used.
+const DEFAULT_AUTHOR_SEPARATOR: &str = "; ";
+
+fn author_names(package: &Package, keep_emails: bool) -> impl Iterator<Item = String> + '_ {
+    package
         .authors
         .iter()
-        .map(|s| {
-            // Strip email if it exists.
-            let re = Regex::new(r"<(.*?)>").unwrap();
-            re.replace_all(s, "")
+        .map(move |s| {
+            if keep_emails {
+                std::borrow::Cow::Borrowed(s.as_str())
+            } else {
+                // Strip email if it exists.
+                let re = Regex::new(r"<(.*?)>").unwrap();
+                re.replace_all(s, "")
+            }
         })
         .map(|s| String::from(s.trim()))
-        // Replace this with intersperse from stdlib when it is stabilized: https://github.com/rust-lang/rust/issues/79524
-        .join("; ");
+}
+
+/// Gets all of the authors from the `authors` field joined with a separator.
+///
+/// The email of each author is removed unless `keep_emails` is `true`.
+fn authors(package: &Package, separator: &str, keep_emails: bool) -> Result<String> {
+    // Replace this with intersperse from stdlib when it is stabilized: https://github.com/rust-lang/rust/issues/79524
+    let result = author_names(package, keep_emails).join(separator);
 
     if result.is_empty() {
         Err(Error::Manifest("authors"))
@@ -107,6 +205,23 @@ fn authors(package: &Package) -> Result<String> {
     }
 }
 
+/// Gets the name of the author at a zero-based index in the `authors` field.
+fn author(package: &Package, index: usize, keep_emails: bool) -> Result<String> {
+    if package.authors.is_empty() {
+        return Err(Error::Manifest("authors"));
+    }
+    author_names(package, keep_emails)
+        .nth(index)
+        .filter(|a| !a.is_empty())
+        .ok_or_else(|| {
+            Error::Generic(format!(
+                "There is no author at index {index} in the 'authors' field of the package's \
+                 manifest (Cargo.toml), which has {} author(s). The index starts at zero.",
+                package.authors.len()
+            ))
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,17 +250,146 @@ mod tests {
             "manifest_path": ""
         }"#;
 
+    #[test]
+    fn output_path_works() {
+        assert_eq!(
+            output_path(Some("main.wxs")),
+            Some(PathBuf::from("main.wxs"))
+        );
+        assert!(output_path(Some(STDOUT)).is_none());
+        assert!(output_path(None).is_none());
+    }
+
+    #[test]
+    fn destination_creates_parent_folders() {
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+        let output = temp_dir
+            .path()
+            .join("dist")
+            .join("installer")
+            .join("main.wxs");
+        destination(Some(&output)).unwrap();
+        assert!(output.is_file());
+    }
+
+    #[test]
+    fn context_json_works() {
+        let data = mustache::MapBuilder::new()
+            .insert_str("product-name", "Example")
+            .insert_bool("no-path", true)
+            .insert_vec("binaries", |builder| {
+                builder.push_map(|builder| builder.insert_str("binary-index", "0"))
+            })
+            .build();
+        assert_eq!(
+            context_json(&data),
+            serde_json::json!({
+                "binaries": [{"binary-index": "0"}],
+                "no-path": true,
+                "product-name": "Example",
+            })
+        );
+    }
+
+    #[test]
+    fn write_context_works() {
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+        let path = temp_dir.path().join("debug").join("context.json");
+        let data = mustache::MapBuilder::new()
+            .insert_str("product-name", "Example")
+            .build();
+        write_context(&data, &path).unwrap();
+        let actual: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(actual, serde_json::json!({"product-name": "Example"}));
+    }
+
+    #[test]
+    fn template_dir_works() {
+        let manifest = serde_json::from_str(SINGLE_AUTHOR_MANIFEST).expect("Parsing TOML");
+        assert!(template_dir(None, &manifest).unwrap().is_none());
+    }
+
+    #[test]
+    fn template_dir_with_override_works() {
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+        let expected = temp_dir.path().to_owned();
+        let manifest = serde_json::from_str(SINGLE_AUTHOR_MANIFEST).expect("Parsing TOML");
+        let actual = template_dir(Some(&expected), &manifest).unwrap();
+        assert_eq!(actual, Some(expected));
+    }
+
+    #[test]
+    fn template_dir_metadata_works() {
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+        let manifest = serde_json::from_value(serde_json::json!({
+            "name": "Example",
+            "version": "0.1.0",
+            "authors": [],
+            "id": "",
+            "dependencies": [],
+            "targets": [],
+            "features": {},
+            "manifest_path": temp_dir.path().join("Cargo.toml").to_str().unwrap(),
+            "metadata": {"wix": {"wxs-template-dir": "templates"}},
+        }))
+        .expect("Parsing TOML");
+        fs::create_dir(temp_dir.path().join("templates")).unwrap();
+        let actual = template_dir(None, &manifest).unwrap();
+        assert_eq!(actual, Some(temp_dir.path().join("templates")));
+    }
+
+    #[test]
+    fn template_dir_that_does_not_exist_fails() {
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+        let dir = temp_dir.path().join("missing");
+        let manifest = serde_json::from_str(SINGLE_AUTHOR_MANIFEST).expect("Parsing TOML");
+        assert!(template_dir(Some(&dir), &manifest).is_err());
+    }
+
     #[test]
     fn authors_with_single_author_works() {
         let manifest = serde_json::from_str(SINGLE_AUTHOR_MANIFEST).expect("Parsing TOML");
-        let actual = authors(&manifest).unwrap();
+        let actual = authors(&manifest, DEFAULT_AUTHOR_SEPARATOR, false).unwrap();
         assert_eq!(actual, String::from("First Last"));
     }
 
     #[test]
     fn authors_with_multiple_authors_works() {
         let manifest = serde_json::from_str(MULTIPLE_AUTHORS_MANIFEST).expect("Parsing TOML");
-        let actual = authors(&manifest).unwrap();
+        let actual = authors(&manifest, DEFAULT_AUTHOR_SEPARATOR, false).unwrap();
         assert_eq!(actual, String::from("1 Author; 2 Author; 3 author"));
     }
+
+    #[test]
+    fn authors_with_separator_works() {
+        let manifest = serde_json::from_str(MULTIPLE_AUTHORS_MANIFEST).expect("Parsing TOML");
+        let actual = authors(&manifest, ", ", false).unwrap();
+        assert_eq!(actual, String::from("1 Author, 2 Author, 3 author"));
+    }
+
+    #[test]
+    fn authors_with_emails_works() {
+        let manifest = serde_json::from_str(SINGLE_AUTHOR_MANIFEST).expect("Parsing TOML");
+        let actual = authors(&manifest, DEFAULT_AUTHOR_SEPARATOR, true).unwrap();
+        assert_eq!(actual, String::from("First Last <first.last@example.com>"));
+    }
+
+    #[test]
+    fn author_with_multiple_authors_works() {
+        let manifest = serde_json::from_str(MULTIPLE_AUTHORS_MANIFEST).expect("Parsing TOML");
+        assert_eq!(
+            author(&manifest, 0, false).unwrap(),
+            String::from("1 Author")
+        );
+        assert_eq!(
+            author(&manifest, 2, false).unwrap(),
+            String::from("3 author")
+        );
+    }
+
+    #[test]
+    fn author_with_out_of_range_index_fails() {
+        let manifest = serde_json::from_str(MULTIPLE_AUTHORS_MANIFEST).expect("Parsing TOML");
+        assert!(author(&manifest, 3, false).is_err());
+    }
 }
@@ -0,0 +1,481 @@
+// Copyright (C) 2017 Christopher R. Field.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A pragmatic validation of a WiX Source (wxs) file.
+//!
+//! Full validation against the XML Schema Definition (XSD) of the WiX Toolset
+//! is not performed. Instead, a subset of the schema rules that catch common
+//! authoring mistakes is checked, such as missing required attributes,
+//! duplicate IDs, and malformed GUIDs.
+
+use log::warn;
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use sxd_document::dom::{ChildOfElement, ChildOfRoot, Element};
+
+use uuid::Uuid;
+
+/// The XML namespace for WiX Source (wxs) files.
+const WIX_NAMESPACE: &str = "http://schemas.microsoft.com/wix/2006/wi";
+
+/// The attributes that must be present for an element.
+///
+/// The attributes are required by the schema of the WiX Toolset v3, or by its
+/// compiler (candle.exe) for the `Product` element. Some identifiers, such as
+/// the `Id` of a `Component`, `Directory`, or `File`, are optional because the
+/// compiler generates them.
+const REQUIRED_ATTRIBUTES: &[(&str, &[&str])] = &[
+    ("ComponentGroupRef", &["Id"]),
+    ("ComponentRef", &["Id"]),
+    ("DirectoryRef", &["Id"]),
+    ("Environment", &["Id", "Name"]),
+    ("Feature", &["Id"]),
+    ("FeatureRef", &["Id"]),
+    ("Icon", &["Id", "SourceFile"]),
+    ("Media", &["Id"]),
+    (
+        "Product",
+        &["Id", "Language", "Manufacturer", "Name", "Version"],
+    ),
+    ("Property", &["Id"]),
+    ("SetProperty", &["Id", "Value"]),
+    ("UIRef", &["Id"]),
+    ("WixVariable", &["Id", "Value"]),
+];
+
+/// The attributes that must be a GUID, `*`, or a preprocessor variable.
+const GUID_ATTRIBUTES: &[(&str, &str)] = &[
+    ("Bundle", "UpgradeCode"),
+    ("Component", "Guid"),
+    ("Package", "Id"),
+    ("Product", "Id"),
+    ("Product", "UpgradeCode"),
+];
+
+/// The elements with an `Id` attribute that must be unique.
+const UNIQUE_IDS: &[&str] = &[
+    "Component",
+    "ComponentGroup",
+    "Directory",
+    "Feature",
+    "File",
+    "Icon",
+    "Property",
+    "WixVariable",
+];
+
+/// The commonly used elements of the WiX Toolset schema.
+///
+/// An element that is not in this list is only reported as a warning because
+/// the list is not exhaustive.
+const KNOWN_ELEMENTS: &[&str] = &[
+    "AdminExecuteSequence",
+    "AdminUISequence",
+    "AppSearch",
+    "Binary",
+    "BootstrapperApplication",
+    "BootstrapperApplicationRef",
+    "Bundle",
+    "Chain",
+    "Condition",
+    "Component",
+    "ComponentGroup",
+    "ComponentGroupRef",
+    "ComponentRef",
+    "ComponentSearch",
+    "Control",
+    "CopyFile",
+    "CreateFolder",
+    "Custom",
+    "CustomAction",
+    "CustomActionRef",
+    "Dialog",
+    "DialogRef",
+    "Directory",
+    "DirectoryRef",
+    "DirectorySearch",
+    "Environment",
+    "ExePackage",
+    "Extension",
+    "Feature",
+    "FeatureGroup",
+    "FeatureGroupRef",
+    "FeatureRef",
+    "File",
+    "FileSearch",
+    "Fragment",
+    "Icon",
+    "IniFile",
+    "InstallExecuteSequence",
+    "InstallUISequence",
+    "MajorUpgrade",
+    "Media",
+    "MediaTemplate",
+    "Merge",
+    "MergeRef",
+    "MsiPackage",
+    "MspPackage",
+    "MsuPackage",
+    "Package",
+    "PackageGroup",
+    "PackageGroupRef",
+    "Payload",
+    "PayloadGroup",
+    "PayloadGroupRef",
+    "Permission",
+    "ProgId",
+    "Property",
+    "PropertyRef",
+    "Publish",
+    "RegistryKey",
+    "RegistrySearch",
+    "RegistryValue",
+    "RemoveFile",
+    "RemoveFolder",
+    "RemoveRegistryKey",
+    "RemoveRegistryValue",
+    "RollbackBoundary",
+    "ServiceControl",
+    "ServiceInstall",
+    "SetDirectory",
+    "SetProperty",
+    "Shortcut",
+    "UI",
+    "UIRef",
+    "Upgrade",
+    "UpgradeVersion",
+    "Variable",
+    "Verb",
+    "Wix",
+    "WixVariable",
+];
+
+/// Validates the content of a WiX Source (wxs) file.
+///
+/// Each problem is prefixed with the line number, and the column number for
+/// malformed XML, of the problem. An empty list is returned if no problems
+/// are found.
+pub fn validate(content: &str) -> Vec<String> {
+    let package = match sxd_document::parser::parse(content) {
+        Ok(p) => p,
+        Err(err) => {
+            let (line, column) = position(content, err.location());
+            return vec![format!("{line}:{column}: The XML is malformed: {err}")];
+        }
+    };
+    let document = package.as_document();
+    let mut validator = Validator {
+        start_tags: start_tags(content).into_iter(),
+        ids: HashMap::new(),
+        problems: Vec::new(),
+    };
+    for child in document.root().children() {
+        if let ChildOfRoot::Element(element) = child {
+            if element.name().local_part() != "Wix"
+                || element.name().namespace_uri() != Some(WIX_NAMESPACE)
+            {
+                validator.problems.push(format!(
+                    "The root element must be the 'Wix' element with the '{WIX_NAMESPACE}' \
+                     namespace"
+                ));
+            }
+            validator.element(element);
+        }
+    }
+    validator.problems
+}
+
+/// The branches of the preprocessor conditional blocks that contain an
+/// element, from the outermost to the innermost block.
+///
+/// Each branch is a pair of the index of the conditional block, i.e. the
+/// `<?if ?>`, `<?ifdef ?>`, or `<?ifndef ?>` directive, in the content and the
+/// index of the branch within the block.
+type Branches = Vec<(usize, usize)>;
+
+/// The position of a start tag in the content.
+struct StartTag {
+    line: usize,
+    branches: Branches,
+}
+
+struct Validator {
+    start_tags: std::vec::IntoIter<StartTag>,
+    ids: HashMap<(String, String), Vec<Branches>>,
+    problems: Vec<String>,
+}
+
+impl Validator {
+    fn element(&mut self, element: Element) {
+        // The start tags are in the same order as the elements are visited, so
+        // the next start tag is always for this element.
+        let StartTag { line, branches } = self.start_tags.next().unwrap_or(StartTag {
+            line: 0,
+            branches: Vec::new(),
+        });
+        let name = element.name().local_part();
+        if element.name().namespace_uri() == Some(WIX_NAMESPACE) {
+            if !KNOWN_ELEMENTS.contains(&name) {
+                warn!("{line}: The '{name}' element is not a commonly used WiX element");
+            }
+            if let Some((_, attributes)) = REQUIRED_ATTRIBUTES.iter().find(|(e, _)| *e == name) {
+                for attribute in attributes.iter() {
+                    if element.attribute_value(*attribute).is_none() {
+                        self.problems.push(format!(
+                            "{line}: The '{name}' element is missing the required \
+                             '{attribute}' attribute"
+                        ));
+                    }
+                }
+            }
+            for (_, attribute) in GUID_ATTRIBUTES.iter().filter(|(e, _)| *e == name) {
+                if let Some(value) = element.attribute_value(*attribute) {
+                    if !is_guid(value) {
+                        self.problems.push(format!(
+                            "{line}: The '{value}' value for the '{attribute}' attribute of \
+                             the '{name}' element is not a GUID"
+                        ));
+                    }
+                }
+            }
+            if UNIQUE_IDS.contains(&name) {
+                if let Some(id) = element.attribute_value("Id") {
+                    let others = self
+                        .ids
+                        .entry((name.to_owned(), id.to_owned()))
+                        .or_default();
+                    if others.iter().any(|o| can_coexist(o, &branches)) {
+                        self.problems.push(format!(
+                            "{line}: The '{id}' ID is used by more than one '{name}' element"
+                        ));
+                    }
+                    others.push(branches);
+                }
+            }
+        }
+        for child in element.children() {
+            if let ChildOfElement::Element(child) = child {
+                self.element(child);
+            }
+        }
+    }
+}
+
+/// Checks if a value is a GUID, `*` for an automatically generated GUID, or
+/// contains a preprocessor variable that is only known at compile time.
+fn is_guid(value: &str) -> bool {
+    value == "*" || value.contains("$(") || Uuid::from_str(value).is_ok()
+}
+
+/// Checks if two elements can both be in the preprocessed content.
+///
+/// Elements in different branches of the same conditional block are mutually
+/// exclusive, such as a property defined in both the `<?ifdef ?>` and the
+/// `<?else ?>` branches.
+fn can_coexist(a: &Branches, b: &Branches) -> bool {
+    a.iter()
+        .all(|(block, branch)| b.iter().all(|(o, p)| o != block || p == branch))
+}
+
+/// Gets the line number and conditional branches of each start tag in the
+/// content in document order.
+///
+/// Comments, processing instructions, CDATA sections, declarations, and end
+/// tags are skipped, but the preprocessor conditional directives are tracked.
+fn start_tags(content: &str) -> Vec<StartTag> {
+    let mut tags = Vec::new();
+    let mut blocks = 0;
+    let mut branches = Branches::new();
+    let mut offset = 0;
+    while let Some(index) = content[offset..].find('<') {
+        let start = offset + index;
+        let tail = &content[start..];
+        let end = if tail.starts_with("<!--") {
+            tail.find("-->").map(|e| e + 3)
+        } else if let Some(instruction) = tail.strip_prefix("<?") {
+            let directive = instruction.split_whitespace().next().unwrap_or_default();
+            match directive.trim_end_matches("?>") {
+                "if" | "ifdef" | "ifndef" => {
+                    branches.push((blocks, 0));
+                    blocks += 1;
+                }
+                "elseif" | "else" => {
+                    if let Some((_, branch)) = branches.last_mut() {
+                        *branch += 1;
+                    }
+                }
+                "endif" => {
+                    branches.pop();
+                }
+                _ => {}
+            }
+            tail.find("?>").map(|e| e + 2)
+        } else if tail.starts_with("<![CDATA[") {
+            tail.find("]]>").map(|e| e + 3)
+        } else if tail.starts_with("<!") || tail.starts_with("</") {
+            tail.find('>').map(|e| e + 1)
+        } else {
+            tags.push(StartTag {
+                line: position(content, start).0,
+                branches: branches.clone(),
+            });
+            Some(1)
+        };
+        match end {
+            Some(end) => offset = start + end,
+            None => break,
+        }
+    }
+    tags
+}
+
+/// Gets the line number of each start tag in the content in document order.
+///
+/// The line numbers are in the same order as the elements of the parsed
+/// document are visited in a depth-first traversal.
+pub(crate) fn start_lines(content: &str) -> Vec<usize> {
+    start_tags(content).into_iter().map(|t| t.line).collect()
+}
+
+/// Gets the line and column numbers, starting at one, for a byte offset.
+pub(crate) fn position(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Template;
+
+    const VALID: &str = r#"<?xml version='1.0'?>
+<Wix xmlns='http://schemas.microsoft.com/wix/2006/wi'>
+    <!-- <Component Id='Commented'/> -->
+    <Product Id='*' Name='Example' UpgradeCode='B36177BE-EA4D-44FB-B05C-EDDABDAA95CA' Manufacturer='Example' Language='1033' Version='$(var.Version)'>
+        <Directory Id='TARGETDIR' Name='SourceDir'>
+            <Component Id='binary0' Guid='*'>
+                <File Id='exe0' Source='bin\Example.exe'/>
+            </Component>
+        </Directory>
+    </Product>
+</Wix>"#;
+
+    #[test]
+    fn validate_with_valid_content_works() {
+        assert!(validate(VALID).is_empty());
+    }
+
+    #[test]
+    fn validate_with_template_works() {
+        let content = Template::Wxs
+            .to_str()
+            .replace(
+                "{{upgrade-code-guid}}",
+                "B36177BE-EA4D-44FB-B05C-EDDABDAA95CA",
+            )
+            .replace(
+                "{{path-component-guid}}",
+                "BFD25009-65A4-4D1E-97F1-0030465D90D6",
+            );
+        let rendered = mustache::compile_str(&content)
+            .unwrap()
+            .render_data_to_string(&mustache::MapBuilder::new().build())
+            .unwrap();
+        assert_eq!(validate(&rendered), Vec::<String>::new());
+    }
+
+    #[test]
+    fn validate_with_malformed_xml_fails() {
+        let problems = validate("<Wix>\n<Product>\n</Wix>");
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("3:"));
+    }
+
+    #[test]
+    fn validate_with_missing_attribute_fails() {
+        let content = VALID.replace(" Manufacturer='Example'", "");
+        assert_eq!(
+            validate(&content),
+            vec![String::from(
+                "4: The 'Product' element is missing the required 'Manufacturer' attribute"
+            )]
+        );
+    }
+
+    #[test]
+    fn validate_with_generated_identifiers_works() {
+        let content = VALID
+            .replace("<Component Id='binary0' Guid='*'>", "<Component Guid='*'>")
+            .replace(
+                "<File Id='exe0' Source='bin\\Example.exe'/>",
+                "<File Name='Example.exe'/>",
+            );
+        assert!(validate(&content).is_empty());
+    }
+
+    #[test]
+    fn validate_with_invalid_guid_fails() {
+        let content = VALID.replace("Guid='*'", "Guid='Not a GUID'");
+        assert_eq!(
+            validate(&content),
+            vec![String::from(
+                "6: The 'Not a GUID' value for the 'Guid' attribute of the 'Component' \
+                 element is not a GUID"
+            )]
+        );
+    }
+
+    #[test]
+    fn validate_with_duplicate_id_fails() {
+        let content = VALID.replace(
+            "</Component>\n",
+            "</Component>\n            <Component Id='binary0' Guid='*'/>\n",
+        );
+        assert_eq!(
+            validate(&content),
+            vec![String::from(
+                "9: The 'binary0' ID is used by more than one 'Component' element"
+            )]
+        );
+    }
+
+    #[test]
+    fn validate_with_duplicate_id_in_exclusive_branches_works() {
+        let content = VALID.replace(
+            "</Component>\n",
+            "</Component>\n<?ifdef A ?>\n<Component Id='a' Guid='*'/>\n<?else ?>\n\
+             <Component Id='a' Guid='*'/>\n<?endif ?>\n",
+        );
+        assert!(validate(&content).is_empty());
+    }
+
+    #[test]
+    fn validate_with_duplicate_id_in_nested_branches_fails() {
+        let content = VALID.replace(
+            "</Component>\n",
+            "</Component>\n<?ifdef A ?>\n<Component Id='a' Guid='*'/>\n<?ifdef B ?>\n\
+             <Component Id='a' Guid='*'/>\n<?endif ?>\n<?endif ?>\n",
+        );
+        assert_eq!(validate(&content).len(), 1);
+    }
+
+    #[test]
+    fn validate_with_wrong_root_fails() {
+        assert_eq!(validate("<Include/>").len(), 1);
+    }
+}
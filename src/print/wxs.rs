@@ -14,23 +14,37 @@
 
 //! The implementation for printing a WiX Source (wxs) file.
 
+use crate::cargo_feature_variable;
 use crate::description;
 use crate::licenses::{License, Licenses};
 use crate::manifest;
 use crate::package;
 use crate::product_name;
-use crate::stored_path::StoredPathBuf;
+use crate::stored_path::{StoredPath, StoredPathBuf};
+use crate::Cultures;
+use crate::DialogSet;
 use crate::Error;
+use crate::InstallScope;
 use crate::Result;
 use crate::Template;
 use crate::EXE_FILE_EXTENSION;
+use crate::LICENSE_FILE_NAME;
+use crate::WIX;
+use crate::{BANNER_FILE_NAME, BMP_FILE_EXTENSION, DIALOG_FILE_NAME};
+use crate::{PRODUCT_ICON_FROM_BINARY, PRODUCT_ICON_VARIABLE};
 
 use camino::Utf8Path;
-use log::{debug, trace, warn};
+use log::{debug, error, info, trace, warn};
 
-use mustache::{self, MapBuilder};
+use mustache::{self, MapBuilder, VecBuilder};
 
+use regex::Regex;
+
+use std::env;
+use std::fs;
+use std::io::{Read, Write};
 use std::path::Path;
+use std::process::Command;
 use std::{collections::HashMap, str::FromStr};
 
 use cargo_metadata::Package;
@@ -39,52 +53,182 @@ use uuid::Uuid;
 
 use super::RenderOutput;
 
+/// The value for the GUID of a component that lets the WiX Toolset generate a
+/// stable GUID from the key path of the component.
+const GENERATED_GUID: &str = "*";
+
+/// The namespace for the deterministic (version 5) GUIDs of the components.
+const COMPONENT_GUID_NAMESPACE: Uuid = Uuid::from_u128(0x5C1B_9E52_74A1_4D3E_9B0F_6E2A_8D47_C913);
+
+/// The namespace for the GUIDs derived from a seed with the `guid_from` method.
+const SEEDED_GUID_NAMESPACE: Uuid = Uuid::from_u128(0x9A4E_2F61_C83B_4B57_A1D2_3E8F_0C6B_75D4);
+
+/// The folder for the program files of a 32-bit platform.
+const PROGRAM_FILES_FOLDER: &str = "ProgramFilesFolder";
+
+/// The folder for the program files of a 64-bit platform.
+const PROGRAM_FILES_64_FOLDER: &str = "ProgramFiles64Folder";
+
+/// The name for deriving the GUID of the path component from a seed.
+const PATH_GUID_NAME: &str = "Path";
+
+/// The name for deriving the GUID of the uninstall shortcut component from the
+/// product name when deterministic component GUIDs are used.
+const UNINSTALL_SHORTCUT_NAME: &str = "UninstallShortcut";
+
+/// The name for deriving the upgrade code GUID from a seed.
+const UPGRADE_GUID_NAME: &str = "UpgradeCode";
+
+/// The bit depth of the bitmap (BMP) images expected by the WixUI dialog
+/// library for the banner and dialog images.
+const BITMAP_BIT_DEPTH: u16 = 24;
+
+/// The file extension of a bitmap (BMP) image.
+const BITMAP_FILE_EXTENSION: &str = "bmp";
+
+/// The number of bytes of the header of a bitmap (BMP) file needed to read its
+/// bit depth.
+const BITMAP_HEADER_LENGTH: usize = 30;
+
+/// The file name, without the extension, of the product icon that is used if
+/// it exists in the `wix` folder and a product icon is not specified.
+const PRODUCT_ICON_FILE_NAME: &str = "Product";
+
+/// The file extension for an icon.
+const ICO_FILE_EXTENSION: &str = "ico";
+
 /// A builder for creating an execution context to print a WiX Toolset source file (wxs).
 #[derive(Debug, Clone)]
 pub struct Builder<'a> {
+    after_install_launch: bool,
+    author_separator: Option<&'a str>,
     banner: Option<&'a str>,
     binaries: Option<Vec<&'a str>>,
     copyright_year: Option<&'a str>,
+    culture: Option<&'a str>,
+    culture_banners: Vec<(&'a str, &'a str)>,
+    culture_dialogs: Vec<(&'a str, &'a str)>,
     copyright_holder: Option<&'a str>,
     description: Option<&'a str>,
+    deterministic_component_guids: bool,
     dialog: Option<&'a str>,
+    dialog_set: Option<&'a str>,
+    dump_context: Option<&'a str>,
     eula: Option<&'a str>,
+    feature_maps: Option<Vec<&'a str>>,
+    guid_from: Option<&'a str>,
     help_url: Option<&'a str>,
+    include_licenses: bool,
     input: Option<&'a str>,
+    install_scope: Option<&'a str>,
+    keep_author_emails: bool,
+    launch_binary: Option<&'a str>,
     license: Option<&'a str>,
     manufacturer: Option<&'a str>,
+    manufacturer_from: Option<&'a str>,
+    no_modify: bool,
+    no_path: bool,
+    no_repair: bool,
+    no_upgrade: bool,
+    open: bool,
     output: Option<&'a str>,
     package: Option<&'a str>,
     path_guid: Option<&'a str>,
     product_icon: Option<&'a str>,
     product_name: Option<&'a str>,
+    run_after_install: Option<&'a str>,
+    schema_validate: bool,
+    skip_eula_dialog: bool,
+    skip_license_sidecar: bool,
+    uninstall_shortcut: bool,
     upgrade_guid: Option<&'a str>,
+    wxs_template_dir: Option<&'a str>,
 }
 
 impl<'a> Builder<'a> {
     /// Creates a new `Builder` instance.
     pub fn new() -> Self {
         Builder {
+            after_install_launch: false,
+            author_separator: None,
             banner: None,
             binaries: None,
             copyright_year: None,
+            culture: None,
+            culture_banners: Vec::new(),
+            culture_dialogs: Vec::new(),
             copyright_holder: None,
             description: None,
+            deterministic_component_guids: false,
             dialog: None,
+            dialog_set: None,
+            dump_context: None,
             eula: None,
+            feature_maps: None,
+            guid_from: None,
             help_url: None,
+            include_licenses: false,
             input: None,
+            install_scope: None,
+            keep_author_emails: false,
+            launch_binary: None,
             license: None,
             manufacturer: None,
+            manufacturer_from: None,
+            no_modify: false,
+            no_path: false,
+            no_repair: false,
+            no_upgrade: false,
+            open: false,
             output: None,
             package: None,
             path_guid: None,
             product_icon: None,
             product_name: None,
+            run_after_install: None,
+            schema_validate: false,
+            skip_eula_dialog: false,
+            skip_license_sidecar: false,
+            uninstall_shortcut: false,
             upgrade_guid: None,
+            wxs_template_dir: None,
         }
     }
 
+    /// Adds a checkbox to the last dialog of the installer to launch a binary.
+    ///
+    /// If `true`, the last dialog of the installer has a checkbox to launch
+    /// one of the installed binaries after the installation completes. The
+    /// binary is the one from the `default-run` field of the package's
+    /// manifest, or with the same name as the package, or the first binary if
+    /// none match, unless a binary is selected with the `launch_binary`
+    /// method. The default is to not add the checkbox.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn after_install_launch(&mut self, a: bool) -> &mut Self {
+        self.after_install_launch = a;
+        self
+    }
+
+    /// Sets the separator between the authors when all of the authors from
+    /// the `authors` field of the package's manifest (Cargo.toml) are used for
+    /// the manufacturer.
+    ///
+    /// The default is to use the `author-separator` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or a semicolon followed by a space (`; `) if the field is not
+    /// specified. This is not used if the manufacturer is set with the
+    /// [`manufacturer`] method or a single author is selected with the
+    /// [`manufacturer_from`] method.
+    ///
+    /// [`manufacturer`]: #method.manufacturer
+    /// [`manufacturer_from`]: #method.manufacturer_from
+    pub fn author_separator(&mut self, a: Option<&'a str>) -> &mut Self {
+        self.author_separator = a;
+        self
+    }
+
     /// Sets the path to a bitmap (BMP) file to be used as a banner image across
     /// the top of each dialog in the installer.
     ///
@@ -112,6 +256,17 @@ impl<'a> Builder<'a> {
     /// in the Add/Remove Programs control panel. Use the `product_name` method
     /// to change the name that appears in the Add/Remove Programs control
     /// panel.
+    ///
+    /// A path can optionally be suffixed with a colon and the name of a
+    /// subfolder, e.g. `target\release\helper.exe:tools`, to install the
+    /// binary in that subfolder of the `bin` folder instead of the `bin` folder
+    /// itself. Binaries without a subfolder are installed in the `bin` folder.
+    ///
+    /// A path can also be a folder or contain the `*` and `?` wildcards in the
+    /// file name, e.g. `target\release\*.exe`, to include all of the
+    /// matching executables, sorted by name. A folder includes all of the
+    /// executables (`.exe`) in it. A binary with the same name as a binary
+    /// already included is skipped.
     pub fn binaries(&mut self, b: Option<Vec<&'a str>>) -> &mut Self {
         self.binaries = b;
         self
@@ -154,6 +309,49 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the culture for the generated EULA.
+    ///
+    /// The translation of the license template for the culture is used if
+    /// one is available; otherwise, the English license template is used. The
+    /// `auto` value derives the culture from the locale of the system.
+    ///
+    /// The default is to use the `culture` field in the `[package.metadata.wix]`
+    /// section of the package's manifest (Cargo.toml), or `en-US` if the field
+    /// does not exist. This value is ignored if an EULA is _not_ generated from
+    /// the `license` field in the package's manifest (Cargo.toml).
+    pub fn culture(&mut self, c: Option<&'a str>) -> &mut Self {
+        self.culture = c;
+        self
+    }
+
+    /// Adds the path to a banner image for a culture, e.g. `fr-FR`, or a
+    /// language, e.g. `fr`.
+    ///
+    /// The image is used instead of the image from the [`banner`] method if
+    /// the culture from the [`culture`] method matches. An image for the
+    /// culture takes precedence over an image for its language. This can be
+    /// used repeatedly to add an image for each culture.
+    ///
+    /// [`banner`]: #method.banner
+    /// [`culture`]: #method.culture
+    pub fn culture_banner(&mut self, culture: &'a str, path: &'a str) -> &mut Self {
+        self.culture_banners.push((culture, path));
+        self
+    }
+
+    /// Adds the path to a dialog image for a culture, e.g. `fr-FR`, or a
+    /// language, e.g. `fr`.
+    ///
+    /// The image is selected in the same way as the [`culture_banner`]
+    /// method, and it is used instead of the image from the [`dialog`] method.
+    ///
+    /// [`culture_banner`]: #method.culture_banner
+    /// [`dialog`]: #method.dialog
+    pub fn culture_dialog(&mut self, culture: &'a str, path: &'a str) -> &mut Self {
+        self.culture_dialogs.push((culture, path));
+        self
+    }
+
     /// Sets the description.
     ///
     /// This overrides the description determined from the `description` field
@@ -163,6 +361,30 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Enables or disables deterministic GUIDs for the components.
+    ///
+    /// If enabled, the GUIDs for the components of the binaries, the license
+    /// sidecar file, and the PATH environment variable are version 5 UUIDs
+    /// derived from a namespace and the install location of the component,
+    /// including the Program Files folder of the platform, so identical inputs
+    /// always yield identical GUIDs. This is useful for
+    /// patching and for generated WiX Source (wxs) files that are easy to diff.
+    /// An explicit path GUID from the [`path_guid`] method, or a GUID derived
+    /// from a seed with the [`guid_from`] method, takes precedence.
+    ///
+    /// The default is to use the `deterministic-component-guids` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or to let the WiX Toolset generate the GUIDs of the file components
+    /// (`Guid='*'`) and randomly generate the GUID of the path component if the
+    /// field is not specified.
+    ///
+    /// [`path_guid`]: Builder::path_guid
+    /// [`guid_from`]: Builder::guid_from
+    pub fn deterministic_component_guids(&mut self, d: bool) -> &mut Self {
+        self.deterministic_component_guids = d;
+        self
+    }
+
     /// Sets the path to a bitmap (`.bmp`) file that will be displayed on the
     /// first dialog to the left.
     ///
@@ -176,6 +398,34 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the set of dialogs from the WixUI dialog library for the installer.
+    ///
+    /// The value is `feature-tree`, `install-dir`, or `mondo`, which is
+    /// substituted into the `UIRef` element of the WiX Source (wxs) file. The
+    /// license agreement dialog of the set is shown if there is an EULA, see
+    /// the [`eula`] method, and skipped otherwise.
+    ///
+    /// The default is to use the `dialog-set` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or the `feature-tree` set if the field is not specified.
+    ///
+    /// [`eula`]: #method.eula
+    pub fn dialog_set(&mut self, d: Option<&'a str>) -> &mut Self {
+        self.dialog_set = d;
+        self
+    }
+
+    /// Sets the path to a file for the context used to render the template.
+    ///
+    /// All of the values substituted into the template are written to the
+    /// file as JSON before the template is rendered, which is useful for
+    /// debugging an unexpected value in the rendered WiX Source (wxs) file.
+    /// The default is to not write the context.
+    pub fn dump_context(&mut self, d: Option<&'a str>) -> &mut Self {
+        self.dump_context = d;
+        self
+    }
+
     /// Sets the path to a custom EULA.
     ///
     /// The default is to generate an EULA from an embedded template as a RTF
@@ -194,6 +444,47 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the Cargo features of the package that are optional features of
+    /// the installer.
+    ///
+    /// Each value is in the form of `CARGO_FEATURE=TITLE`, where `TITLE` is
+    /// displayed for the feature in the feature tree of the installer. A
+    /// feature is added to the installer for each value, but only if the
+    /// `CargoFeature_<CARGO_FEATURE>` variable is defined for the WiX compiler
+    /// (candle.exe), which is done when creating the installer if the Cargo
+    /// feature is enabled. Binaries that require the Cargo feature, i.e. the
+    /// `required-features` field of the binary, are installed with the
+    /// feature instead of the main feature. Hyphens in the name of the Cargo
+    /// feature are replaced with underscores in the name of the variable.
+    ///
+    /// The default is to use the `feature-map` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or to not add any features if the field is not specified.
+    pub fn feature_maps(&mut self, f: Option<Vec<&'a str>>) -> &mut Self {
+        self.feature_maps = f;
+        self
+    }
+
+    /// Sets a seed for deriving the upgrade code and path component GUIDs.
+    ///
+    /// The GUIDs are version 5 UUIDs derived from the seed, such as a product
+    /// identifier, so the same seed always yields the same GUIDs and different
+    /// seeds yield different GUIDs. This makes the GUIDs reproducible without
+    /// storing them in the package's manifest (Cargo.toml). An explicit GUID
+    /// from the [`upgrade_guid`] or [`path_guid`] methods, or the
+    /// `upgrade-guid` or `path-guid` fields, takes precedence.
+    ///
+    /// The default is to use the `guid-from` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or to randomly generate the GUIDs if the field is not specified.
+    ///
+    /// [`upgrade_guid`]: Builder::upgrade_guid
+    /// [`path_guid`]: Builder::path_guid
+    pub fn guid_from(&mut self, g: Option<&'a str>) -> &mut Self {
+        self.guid_from = g;
+        self
+    }
+
     /// Sets the help URL.
     ///
     /// The default is to obtain a URL from one of the following fields in the
@@ -207,6 +498,20 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Reports the license files and help URL that the rendered template
+    /// references.
+    ///
+    /// If `true`, the EULA displayed in the license agreement dialog, the
+    /// license installed as a sidecar file, and the help URL are written to
+    /// stderr after they are resolved, so they can be seen alongside the
+    /// rendered template printed to stdout. A license that is generated from a
+    /// template is reported with the name of the template. The same report is
+    /// always logged at the info level. The default is `false`.
+    pub fn include_licenses(&mut self, i: bool) -> &mut Self {
+        self.include_licenses = i;
+        self
+    }
+
     /// Sets the path to a package's manifest (Cargo.toml) to be used to
     /// generate a WiX Source (wxs) file from the embedded template.
     ///
@@ -218,6 +523,40 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the installation scope, i.e. per-machine or per-user.
+    ///
+    /// A per-machine installation requires elevated privileges, while a
+    /// per-user installation does not. The `auto` value uses the per-user
+    /// scope if the PATH environment variable is _not_ modified, see the
+    /// [`no_path`] method, and the per-machine scope otherwise. With the
+    /// per-user scope, the application is installed to the user's programs
+    /// folder, i.e. `%LOCALAPPDATA%\Programs`, and the user's PATH
+    /// environment variable is modified instead of the system's PATH
+    /// environment variable.
+    ///
+    /// The default is to use the `install-scope` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or the per-machine scope if the field is not specified.
+    ///
+    /// [`no_path`]: #method.no_path
+    pub fn install_scope(&mut self, i: Option<&'a str>) -> &mut Self {
+        self.install_scope = i;
+        self
+    }
+
+    /// Keeps the email of each author from the `authors` field of the
+    /// package's manifest (Cargo.toml) in the manufacturer.
+    ///
+    /// If `true`, an author such as `First Last <first.last@example.com>` is
+    /// used as is for the manufacturer instead of `First Last`. The default is
+    /// to use the `keep-author-emails` field in the `[package.metadata.wix]`
+    /// section of the package's manifest (Cargo.toml), or to remove the emails
+    /// if the field is not specified.
+    pub fn keep_author_emails(&mut self, k: bool) -> &mut Self {
+        self.keep_author_emails = k;
+        self
+    }
+
     /// Sets the path to a file to be used as the license [sidecar] file.
     ///
     /// The default is to use the value specified in the `license-file` field of
@@ -245,6 +584,104 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets which authors from the `authors` field of the package's manifest
+    /// (Cargo.toml) are used for the manufacturer.
+    ///
+    /// The value is `first` for the first author, `all` for all of the authors
+    /// separated by semicolons, or the zero-based index of an author. An error
+    /// occurs for any other value or an index without an author. This is not
+    /// used if the manufacturer is set with the [`manufacturer`] method. The
+    /// default is to use the `manufacturer-from` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or `all` if the field is not specified.
+    ///
+    /// [`manufacturer`]: #method.manufacturer
+    pub fn manufacturer_from(&mut self, m: Option<&'a str>) -> &mut Self {
+        self.manufacturer_from = m;
+        self
+    }
+
+    /// Disables the Change button for the application in the Add/Remove
+    /// Programs control panel.
+    ///
+    /// If `true`, the `ARPNOMODIFY` property is set in the generated WiX
+    /// Source (wxs) file, so the installed features cannot be changed from the
+    /// control panel. The default is to use the `no-modify` field in the
+    /// `[package.metadata.wix]` section of the package's manifest
+    /// (Cargo.toml), or to allow changing the features if the field is not
+    /// specified.
+    pub fn no_modify(&mut self, n: bool) -> &mut Self {
+        self.no_modify = n;
+        self
+    }
+
+    /// Excludes the component and feature that add the `bin` folder to the
+    /// PATH environment variable.
+    ///
+    /// The default is to use the `no-path` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or to include the PATH environment variable feature if the field is not
+    /// specified.
+    pub fn no_path(&mut self, n: bool) -> &mut Self {
+        self.no_path = n;
+        self
+    }
+
+    /// Disables the Repair button for the application in the Add/Remove
+    /// Programs control panel.
+    ///
+    /// If `true`, the `ARPNOREPAIR` property is set in the generated WiX
+    /// Source (wxs) file. The default is to use the `no-repair` field in the
+    /// `[package.metadata.wix]` section of the package's manifest
+    /// (Cargo.toml), or to allow repairing the installation if the field is
+    /// not specified.
+    pub fn no_repair(&mut self, n: bool) -> &mut Self {
+        self.no_repair = n;
+        self
+    }
+
+    /// Omits the `MajorUpgrade` element from the generated WiX Source (wxs)
+    /// file.
+    ///
+    /// If `true`, installing a new version of the application does _not_
+    /// remove a previously installed version, so each version is installed side
+    /// by side and must be uninstalled separately. The default is to use the
+    /// `no-upgrade` field in the `[package.metadata.wix]` section of the
+    /// package's manifest (Cargo.toml), or to replace a previously installed
+    /// version with a major upgrade if the field is not specified.
+    pub fn no_upgrade(&mut self, n: bool) -> &mut Self {
+        self.no_upgrade = n;
+        self
+    }
+
+    /// Opens the rendered template with the default application for WiX Source
+    /// (wxs) files.
+    ///
+    /// If `true`, the rendered template is written to a new folder in the
+    /// temporary directory, unless an output is set with the `output` method,
+    /// and then the file is opened as if it was double-clicked in the File
+    /// Explorer. This is only supported on Windows. It has no effect on other
+    /// platforms, where the template is printed as usual.
+    pub fn open(&mut self, o: bool) -> &mut Self {
+        self.open = o;
+        self
+    }
+
+    /// Sets the name of the binary to launch after installation.
+    ///
+    /// The name is the file stem, i.e. the file name without the extension, of
+    /// one of the binaries included in the installer. This is only used if the
+    /// `after_install_launch` method is used, or the `after-install-launch`
+    /// field in the `[package.metadata.wix]` section is `true`. An error occurs
+    /// if the binary is not included in the installer.
+    ///
+    /// This value will override any default and skip looking for a value in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml).
+    pub fn launch_binary(&mut self, l: Option<&'a str>) -> &mut Self {
+        self.launch_binary = l;
+        self
+    }
+
     /// Sets the destination for creating all of the output from initialization.
     ///
     /// The default is to create all initialization output in the current
@@ -308,6 +745,73 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets a command to run at the end of the installation.
+    ///
+    /// The command is run by a deferred custom action from the installation
+    /// folder after the files are installed, but it is not run when the
+    /// product is repaired, modified, or uninstalled. The command can use
+    /// formatted properties, such as `[APPLICATIONFOLDER]`. It runs with the
+    /// privileges of the installer, i.e. as the LocalSystem account for a
+    /// per-machine installation, and the installation fails if the command
+    /// fails. The command is not undone if the installation is rolled back
+    /// afterwards, so it must handle any cleanup itself. The default is to
+    /// use the `run-after-install` field in the `[package.metadata.wix]`
+    /// section of the package's manifest (Cargo.toml), or to not run any
+    /// command if the field is not specified.
+    pub fn run_after_install(&mut self, r: Option<&'a str>) -> &mut Self {
+        self.run_after_install = r;
+        self
+    }
+
+    /// Validates the rendered template before it is printed or written.
+    ///
+    /// If `true`, the rendered template is checked for malformed XML and a
+    /// subset of the WiX Toolset schema rules, such as missing required
+    /// attributes, duplicate IDs, and invalid GUIDs. Each problem is logged
+    /// with its line number and nothing is printed or written if any problems
+    /// are found. The default is `false`.
+    pub fn schema_validate(&mut self, s: bool) -> &mut Self {
+        self.schema_validate = s;
+        self
+    }
+
+    /// Excludes the license agreement dialog from the installer.
+    ///
+    /// If `true`, the EULA is not displayed in the license agreement dialog
+    /// and the dialog is skipped, but the license can still be installed as a
+    /// sidecar file. The default is to use the `skip-eula-dialog` field in the
+    /// `[package.metadata.wix]` section of the package's manifest
+    /// (Cargo.toml), or to include the dialog if a EULA is found.
+    pub fn skip_eula_dialog(&mut self, s: bool) -> &mut Self {
+        self.skip_eula_dialog = s;
+        self
+    }
+
+    /// Excludes the license sidecar file from the installer.
+    ///
+    /// If `true`, the license is not installed as a file next to the `bin`
+    /// folder, but it can still be displayed in the license agreement dialog.
+    /// The default is to use the `skip-license-sidecar` field in the
+    /// `[package.metadata.wix]` section of the package's manifest
+    /// (Cargo.toml), or to install the license if one is found.
+    pub fn skip_license_sidecar(&mut self, s: bool) -> &mut Self {
+        self.skip_license_sidecar = s;
+        self
+    }
+
+    /// Adds a shortcut to uninstall the application to the Start Menu.
+    ///
+    /// If `true`, a shortcut that runs `msiexec /x [ProductCode]` is added to
+    /// a folder named after the product in the Start Menu. The default is to
+    /// use the `uninstall-shortcut` field in the `[package.metadata.wix]`
+    /// section of the package's manifest (Cargo.toml), or to only uninstall
+    /// from the Add/Remove Programs control panel if the field is not
+    /// specified.
+    pub fn uninstall_shortcut(&mut self, u: bool) -> &mut Self {
+        self.uninstall_shortcut = u;
+        self
+    }
+
     /// Sets the Upgrade Code GUID.
     ///
     /// The default automatically generates the GUID needed for the `UpgradeCode`
@@ -327,9 +831,27 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the folder of templates that overrides the embedded templates.
+    ///
+    /// The WiX Source (wxs) template is read from the `main.wxs.mustache` file
+    /// in the folder, and a generated license is read from the file with the
+    /// same name as the embedded license template, e.g. `MIT.rtf.mustache`.
+    /// The embedded template is used for any template the folder does not
+    /// contain, so the folder only needs the templates that are customized.
+    ///
+    /// The default is to use the `wxs-template-dir` field in the
+    /// `[package.metadata.wix]` section of the package's manifest (Cargo.toml),
+    /// or only the embedded templates if the field does not exist.
+    pub fn wxs_template_dir(&mut self, w: Option<&'a str>) -> &mut Self {
+        self.wxs_template_dir = w;
+        self
+    }
+
     /// Builds an execution context for printing a template.
     pub fn build(&self) -> Execution {
         Execution {
+            after_install_launch: self.after_install_launch,
+            author_separator: self.author_separator.map(String::from),
             banner: self.banner.map(StoredPathBuf::from),
             binaries: self
                 .binaries
@@ -337,19 +859,46 @@ impl<'a> Builder<'a> {
                 .map(|b| b.iter().copied().map(StoredPathBuf::from).collect()),
             copyright_holder: self.copyright_holder.map(String::from),
             copyright_year: self.copyright_year.map(String::from),
+            culture: self.culture.map(String::from),
+            culture_banners: culture_images(&self.culture_banners),
+            culture_dialogs: culture_images(&self.culture_dialogs),
             description: self.description.map(String::from),
+            deterministic_component_guids: self.deterministic_component_guids,
             dialog: self.dialog.map(StoredPathBuf::from),
+            dialog_set: self.dialog_set.map(String::from),
+            dump_context: self.dump_context.map(std::path::PathBuf::from),
             eula: self.eula.map(StoredPathBuf::from),
+            feature_maps: self
+                .feature_maps
+                .as_ref()
+                .map(|f| f.iter().map(|s| (*s).to_string()).collect()),
+            guid_from: self.guid_from.map(String::from),
             help_url: self.help_url.map(String::from),
+            include_licenses: self.include_licenses,
             input: self.input.map(std::path::PathBuf::from),
+            install_scope: self.install_scope.map(String::from),
+            keep_author_emails: self.keep_author_emails,
+            launch_binary: self.launch_binary.map(String::from),
             license: self.license.map(StoredPathBuf::from),
             manufacturer: self.manufacturer.map(String::from),
-            output: self.output.map(std::path::PathBuf::from),
+            manufacturer_from: self.manufacturer_from.map(String::from),
+            no_modify: self.no_modify,
+            no_path: self.no_path,
+            no_repair: self.no_repair,
+            no_upgrade: self.no_upgrade,
+            open: self.open,
+            output: super::output_path(self.output),
             package: self.package.map(String::from),
             path_guid: self.path_guid.map(String::from),
             product_icon: self.product_icon.map(StoredPathBuf::from),
             product_name: self.product_name.map(String::from),
+            run_after_install: self.run_after_install.map(String::from),
+            schema_validate: self.schema_validate,
+            skip_eula_dialog: self.skip_eula_dialog,
+            skip_license_sidecar: self.skip_license_sidecar,
+            uninstall_shortcut: self.uninstall_shortcut,
             upgrade_guid: self.upgrade_guid.map(String::from),
+            wxs_template_dir: self.wxs_template_dir.map(std::path::PathBuf::from),
         }
     }
 }
@@ -363,23 +912,49 @@ impl<'a> Default for Builder<'a> {
 /// A context for printing a WiX Toolset source file (wxs).
 #[derive(Debug)]
 pub struct Execution {
+    after_install_launch: bool,
+    author_separator: Option<String>,
     banner: Option<StoredPathBuf>,
     binaries: Option<Vec<StoredPathBuf>>,
     copyright_holder: Option<String>,
     copyright_year: Option<String>,
+    culture: Option<String>,
+    culture_banners: Vec<(String, StoredPathBuf)>,
+    culture_dialogs: Vec<(String, StoredPathBuf)>,
     description: Option<String>,
+    deterministic_component_guids: bool,
     dialog: Option<StoredPathBuf>,
+    dialog_set: Option<String>,
+    dump_context: Option<std::path::PathBuf>,
     eula: Option<StoredPathBuf>,
+    feature_maps: Option<Vec<String>>,
+    guid_from: Option<String>,
     help_url: Option<String>,
+    include_licenses: bool,
     input: Option<std::path::PathBuf>,
+    install_scope: Option<String>,
+    keep_author_emails: bool,
+    launch_binary: Option<String>,
     license: Option<StoredPathBuf>,
     manufacturer: Option<String>,
+    manufacturer_from: Option<String>,
+    no_modify: bool,
+    no_path: bool,
+    no_repair: bool,
+    no_upgrade: bool,
+    open: bool,
     output: Option<std::path::PathBuf>,
     package: Option<String>,
     path_guid: Option<String>,
     product_icon: Option<StoredPathBuf>,
     product_name: Option<String>,
+    run_after_install: Option<String>,
+    schema_validate: bool,
+    skip_eula_dialog: bool,
+    skip_license_sidecar: bool,
+    uninstall_shortcut: bool,
     upgrade_guid: Option<String>,
+    wxs_template_dir: Option<std::path::PathBuf>,
 }
 
 /// All the possible output files of [`Execution::render`][].
@@ -403,8 +978,35 @@ pub struct WxsRenders {
 
 impl Execution {
     /// Prints a WiX Source (wxs) file based on the built context.
-    pub fn run(self) -> Result<()> {
+    pub fn run(mut self) -> Result<()> {
+        let open = self.open && cfg!(windows);
+        if self.open && !open {
+            debug!("Opening the rendered template is only supported on Windows");
+        }
+        if open && self.output.is_none() {
+            let folder = env::temp_dir().join(format!("cargo-wix-{}", Uuid::new_v4().as_simple()));
+            fs::create_dir(&folder)?;
+            self.output = Some(folder.join(format!(
+                "{}.{}",
+                crate::WIX_SOURCE_FILE_NAME,
+                crate::WIX_SOURCE_FILE_EXTENSION
+            )));
+        }
+        let schema_validate = self.schema_validate;
         let renders = self.render()?;
+        if schema_validate {
+            let problems = super::schema::validate(&renders.wxs.rendered);
+            for problem in &problems {
+                error!("{}", problem);
+            }
+            if !problems.is_empty() {
+                return Err(Error::Generic(format!(
+                    "The rendered WiX Source (wxs) file failed validation with {} problem(s). \
+                     Please fix the template or the values used to render it.",
+                    problems.len()
+                )));
+            }
+        }
         renders.wxs.write()?;
         if let Some(license) = renders.license {
             license.write_disk_only()?;
@@ -412,6 +1014,11 @@ impl Execution {
         if let Some(eula) = renders.eula {
             eula.write_disk_only()?;
         }
+        if open {
+            if let Some(path) = renders.wxs.path {
+                open_with_default_application(&path)?;
+            }
+        }
         Ok(())
     }
 
@@ -419,47 +1026,172 @@ impl Execution {
     ///
     /// See [`WxsRenders`][] for details of the output.
     pub fn render(self) -> Result<WxsRenders> {
+        debug!("after_install_launch = {:?}", self.after_install_launch);
+        debug!("author_separator = {:?}", self.author_separator);
         debug!("banner = {:?}", self.banner);
         debug!("binaries = {:?}", self.binaries);
         debug!("copyright_holder = {:?}", self.copyright_holder);
         debug!("copyright_year = {:?}", self.copyright_year);
+        debug!("culture = {:?}", self.culture);
+        debug!("culture_banners = {:?}", self.culture_banners);
+        debug!("culture_dialogs = {:?}", self.culture_dialogs);
         debug!("description = {:?}", self.description);
+        debug!(
+            "deterministic_component_guids = {:?}",
+            self.deterministic_component_guids
+        );
         debug!("dialog = {:?}", self.description);
+        debug!("dialog_set = {:?}", self.dialog_set);
+        debug!("dump_context = {:?}", self.dump_context);
         debug!("eula = {:?}", self.eula);
+        debug!("feature_maps = {:?}", self.feature_maps);
+        debug!("guid_from = {:?}", self.guid_from);
         debug!("help_url = {:?}", self.help_url);
+        debug!("include_licenses = {:?}", self.include_licenses);
         debug!("input = {:?}", self.input);
+        debug!("install_scope = {:?}", self.install_scope);
+        debug!("keep_author_emails = {:?}", self.keep_author_emails);
+        debug!("launch_binary = {:?}", self.launch_binary);
         debug!("license = {:?}", self.license);
         debug!("manufacturer = {:?}", self.manufacturer);
+        debug!("manufacturer_from = {:?}", self.manufacturer_from);
+        debug!("no_modify = {:?}", self.no_modify);
+        debug!("no_path = {:?}", self.no_path);
+        debug!("no_repair = {:?}", self.no_repair);
+        debug!("no_upgrade = {:?}", self.no_upgrade);
         debug!("output = {:?}", self.output);
         debug!("package = {:?}", self.package);
         debug!("path_guid = {:?}", self.path_guid);
         debug!("product_icon = {:?}", self.product_icon);
         debug!("product_name = {:?}", self.product_name);
+        debug!("run_after_install = {:?}", self.run_after_install);
+        debug!("skip_eula_dialog = {:?}", self.skip_eula_dialog);
+        debug!("skip_license_sidecar = {:?}", self.skip_license_sidecar);
+        debug!("uninstall_shortcut = {:?}", self.uninstall_shortcut);
         debug!("upgrade_guid = {:?}", self.upgrade_guid);
+        debug!("wxs_template_dir = {:?}", self.wxs_template_dir);
         let manifest = manifest(self.input.as_ref())?;
         let package = package(&manifest, self.package.as_deref())?;
-        let binaries = self.binaries(&package)?;
+        let feature_maps = self.feature_maps(&package)?;
+        let mut binaries = self.binaries(&package)?;
+        if self.binaries.is_none() {
+            for binary in &mut binaries {
+                if let Some(feature) =
+                    binary_feature(&package, &binary["binary-name"], &feature_maps)
+                {
+                    binary.insert("binary-feature", cargo_feature_variable(feature));
+                }
+            }
+        }
+        let product_name = product_name(self.product_name.as_ref(), &package);
+        let deterministic_component_guids = self.deterministic_component_guids(&package);
+        let mut component_guids = ComponentGuids::default();
+        for binary in &mut binaries {
+            let guid = if deterministic_component_guids {
+                component_guids.add(
+                    &format!("binary{}", binary["binary-index"]),
+                    &binary_key_path(&product_name, binary),
+                )
+            } else {
+                String::from(GENERATED_GUID)
+            };
+            binary.insert("binary-component-guid", guid);
+        }
+        let launch_binary_index = self.launch_binary_index(&package, &binaries)?;
         let licenses = self.licenses(&package)?;
+        let help_url = self.help_url(&package);
+        for line in license_report(&licenses, help_url.as_deref()) {
+            if self.include_licenses {
+                writeln!(std::io::stderr(), "{line}")?;
+            } else {
+                info!("{}", line);
+            }
+        }
+        let no_path = self.no_path(&package);
+        let install_scope = self.install_scope(&package, no_path)?;
+        let dialog_set = self.dialog_set(&package)?;
+        let path_key_path = deterministic_component_guids
+            .then(|| format!("{product_name}\\bin\\PATH\\{install_scope}"));
         let mut map = MapBuilder::new()
-            .insert_vec("binaries", |mut builder| {
+            .insert_vec("binaries", |builder| {
+                push_binaries(
+                    builder,
+                    binaries
+                        .iter()
+                        .filter(|b| !b.contains_key("binary-subdirectory")),
+                )
+            })
+            .insert_vec("binary-directories", |mut builder| {
+                let mut subdirectories: Vec<&str> = Vec::new();
                 for binary in &binaries {
+                    if let Some(subdirectory) = binary.get("binary-subdirectory") {
+                        if !subdirectories.contains(&subdirectory.as_str()) {
+                            subdirectories.push(subdirectory);
+                        }
+                    }
+                }
+                for (index, subdirectory) in subdirectories.into_iter().enumerate() {
                     builder = builder.push_map(|builder| {
                         builder
-                            .insert_str("binary-index", binary.get("binary-index").unwrap())
-                            .insert_str("binary-name", binary.get("binary-name").unwrap())
-                            .insert_str("binary-source", binary.get("binary-source").unwrap())
+                            .insert_str("binary-directory-index", index.to_string())
+                            .insert_str("binary-directory-name", subdirectory)
+                            .insert_vec("binaries", |builder| {
+                                push_binaries(
+                                    builder,
+                                    binaries.iter().filter(|b| {
+                                        b.get("binary-subdirectory").map(String::as_str)
+                                            == Some(subdirectory)
+                                    }),
+                                )
+                            })
                     });
                 }
                 builder
             })
-            .insert_str(
-                "product-name",
-                product_name(self.product_name.as_ref(), &package),
-            )
+            .insert_vec("cargo-features", |mut builder| {
+                for (feature, title) in &feature_maps {
+                    let variable = cargo_feature_variable(feature);
+                    builder = builder.push_map(|builder| {
+                        builder
+                            .insert_str("feature-name", feature)
+                            .insert_str("feature-title", title)
+                            .insert_vec("feature-binaries", |mut builder| {
+                                for binary in binaries
+                                    .iter()
+                                    .filter(|b| b.get("binary-feature") == Some(&variable))
+                                {
+                                    builder = builder.push_map(|builder| {
+                                        builder.insert_str(
+                                            "binary-index",
+                                            binary.get("binary-index").unwrap(),
+                                        )
+                                    });
+                                }
+                                builder
+                            })
+                            .insert_str("feature-variable", &variable)
+                    });
+                }
+                builder
+            })
+            .insert_str("product-name", &product_name)
             .insert_str("manufacturer", self.manufacturer(&package)?)
             .insert_str("upgrade-code-guid", self.upgrade_guid(&package)?)
-            .insert_str("path-component-guid", self.path_guid(&package)?);
+            .insert_str(
+                "path-component-guid",
+                self.path_guid(&package, path_key_path.as_deref(), &mut component_guids)?,
+            )
+            .insert_str("install-scope", install_scope.to_string())
+            .insert_bool("per-machine", install_scope == InstallScope::PerMachine)
+            .insert_str("dialog-set", dialog_set.to_string())
+            .insert_bool("install-dir-dialog", dialog_set == DialogSet::InstallDir)
+            .insert_str("license-next-dialog", dialog_set.license_next_dialog())
+            .insert_bool("no-path", no_path)
+            .insert_bool("no-modify", self.no_modify(&package))
+            .insert_bool("no-repair", self.no_repair(&package))
+            .insert_bool("no-upgrade", self.no_upgrade(&package));
         if let Some(banner) = self.banner_image(&package) {
+            check_bitmap(&package, "banner", &banner);
             map = map.insert_str("banner", banner);
         }
         if let Some(description) = description(self.description.clone(), &package) {
@@ -472,12 +1204,13 @@ impl Execution {
             );
         }
         if let Some(dialog) = self.dialog_image(&package) {
+            check_bitmap(&package, "dialog", &dialog);
             map = map.insert_str("dialog", dialog);
         }
         if let Some(eula) = &licenses.end_user {
             map = map.insert_str("eula", &eula.stored_path);
         }
-        if let Some(url) = self.help_url(&package) {
+        if let Some(url) = help_url {
             map = map.insert_str("help-url", url);
         } else {
             warn!(
@@ -491,15 +1224,89 @@ impl Execution {
             if let Some(name) = &license.name {
                 map = map.insert_str("license-name", name);
             }
+            let license_file_name = license
+                .name
+                .as_deref()
+                .or_else(|| license.stored_path.file_name())
+                .unwrap_or(LICENSE_FILE_NAME);
+            map = map.insert_str(
+                "license-component-guid",
+                if deterministic_component_guids {
+                    component_guids.add("License", &format!("{product_name}\\{license_file_name}"))
+                } else {
+                    String::from(GENERATED_GUID)
+                },
+            );
         }
         if let Some(icon) = self.product_icon(&package) {
             map = map.insert_str("product-icon", icon);
         }
+        if let Some(index) = launch_binary_index {
+            map = map.insert_str("launch-binary-index", index);
+        }
+        if let Some(command) = self.run_after_install(&package) {
+            warn!(
+                "The '{command}' command runs at the end of the installation with the \
+                 privileges of the installer, which is the LocalSystem account for a \
+                 per-machine installation, and the installation fails if the command \
+                 fails. The command is not undone if the installation is rolled back."
+            );
+            map = map.insert_str("run-after-install", command);
+        }
+        if self.uninstall_shortcut(&package) {
+            map = map.insert_str(
+                "uninstall-shortcut-guid",
+                if deterministic_component_guids {
+                    component_guids.add(
+                        UNINSTALL_SHORTCUT_NAME,
+                        &format!("{product_name}\\{UNINSTALL_SHORTCUT_NAME}"),
+                    )
+                } else {
+                    String::from(GENERATED_GUID)
+                },
+            );
+        }
+
+        if !component_guids.0.is_empty() {
+            map = map
+                .insert_bool("deterministic-component-guids", true)
+                .insert_vec("component-guids", |mut builder| {
+                    for (name, key_path) in &component_guids.0 {
+                        builder = builder.push_map(|builder| {
+                            builder
+                                .insert_str("component-guid-name", name)
+                                .insert_str(
+                                    "component-guid-x86",
+                                    component_guid(&format!("{PROGRAM_FILES_FOLDER}\\{key_path}")),
+                                )
+                                .insert_str(
+                                    "component-guid-x64",
+                                    component_guid(&format!(
+                                        "{PROGRAM_FILES_64_FOLDER}\\{key_path}"
+                                    )),
+                                )
+                        });
+                    }
+                    builder
+                });
+        }
 
         let wxs = {
             let data = map.build();
+            if let Some(path) = &self.dump_context {
+                info!("Writing the render context to '{}'", path.display());
+                super::write_context(&data, path)?;
+            }
             let main_destination = self.output.clone();
-            let template = mustache::compile_str(Template::Wxs.to_str())?;
+            let custom_template = super::template_dir(self.wxs_template_dir.as_ref(), &package)?
+                .map(|d| Template::Wxs.read_from(&d, None))
+                .transpose()?
+                .flatten();
+            let template = mustache::compile_str(
+                custom_template
+                    .as_deref()
+                    .unwrap_or_else(|| Template::Wxs.to_str()),
+            )?;
             let rendered = template.render_data_to_string(&data).map_err(Error::from)?;
             RenderOutput {
                 path: main_destination,
@@ -522,6 +1329,7 @@ impl Execution {
         let mut printer = crate::print::license::Builder::new();
         printer.copyright_holder(self.copyright_holder.as_ref().map(String::as_ref));
         printer.copyright_year(self.copyright_year.as_ref().map(String::as_ref));
+        printer.culture(self.culture.as_deref());
         printer.input(self.input.as_deref().and_then(Path::to_str));
         // Slightly hacky: only respect the template's desire to be written to disk
         // if we're writing the overall wxs to disk! This makes "stdout" mode
@@ -530,6 +1338,7 @@ impl Execution {
             printer.output(Some(output.as_str()));
         }
         printer.package(self.package.as_deref());
+        printer.wxs_template_dir(self.wxs_template_dir.as_deref().and_then(Path::to_str));
 
         let render = printer.build().render(template)?;
         Ok(Some(render))
@@ -538,19 +1347,41 @@ impl Execution {
     fn binaries(&self, package: &Package) -> Result<Vec<HashMap<&'static str, String>>> {
         let mut binaries = Vec::new();
         if let Some(binary_paths) = &self.binaries {
-            let mut map = HashMap::with_capacity(3);
-            for (index, binary) in binary_paths.iter().enumerate() {
+            for binary in expand_binaries(binary_paths)? {
+                let (binary, subdirectory) = split_binary_subdirectory(&binary);
+                let binary = StoredPath::new(binary);
                 let binary_file_stem = binary.file_stem().ok_or_else(|| {
                     Error::Generic(format!(
                         "The '{}' binary path does not have a file name",
                         binary
                     ))
                 })?;
-                map.insert("binary-index", index.to_string());
+                if binaries
+                    .iter()
+                    .any(|b: &HashMap<&str, String>| b["binary-name"] == binary_file_stem)
+                {
+                    debug!(
+                        "The '{}' binary is already included, so it is skipped",
+                        binary
+                    );
+                    continue;
+                }
+                let mut map = HashMap::with_capacity(4);
+                map.insert("binary-index", binaries.len().to_string());
                 map.insert("binary-name", binary_file_stem.to_owned());
                 map.insert("binary-source", binary.to_string());
+                if let Some(subdirectory) = subdirectory {
+                    if subdirectory.is_empty() || subdirectory.contains(['/', '\\']) {
+                        return Err(Error::Generic(format!(
+                            "The '{subdirectory}' subfolder for the '{binary}' binary is \
+                             invalid. The subfolder must be a single, non-empty folder name \
+                             without any path separators."
+                        )));
+                    }
+                    map.insert("binary-subdirectory", subdirectory.to_owned());
+                }
+                binaries.push(map);
             }
-            binaries.push(map);
         } else {
             // cargo-metadata attempts to sort binaries by name to keep things stable,
             // but for whatever reason it internally uses the platform-specific binary name
@@ -580,6 +1411,71 @@ impl Execution {
         Ok(binaries)
     }
 
+    /// Gets the index of the binary to launch after installation.
+    ///
+    /// `None` is returned if a binary should not be launched.
+    fn launch_binary_index(
+        &self,
+        package: &Package,
+        binaries: &[HashMap<&'static str, String>],
+    ) -> Result<Option<String>> {
+        let pkg_meta_wix = package.metadata.get("wix").and_then(|w| w.as_object());
+        let after_install_launch = self.after_install_launch
+            || pkg_meta_wix
+                .and_then(|t| t.get("after-install-launch"))
+                .and_then(|a| a.as_bool())
+                .unwrap_or(false);
+        if !after_install_launch {
+            if self.launch_binary.is_some() {
+                warn!(
+                    "A binary to launch after installation was specified, but launching a \
+                     binary after installation is not enabled. Please use the \
+                     '--after-install-launch' flag to launch the binary."
+                );
+            }
+            return Ok(None);
+        }
+        let launch_binary = self.launch_binary.clone().or_else(|| {
+            pkg_meta_wix
+                .and_then(|t| t.get("launch-binary"))
+                .and_then(|l| l.as_str())
+                .map(String::from)
+        });
+        let binary = if let Some(name) = launch_binary {
+            trace!("A binary to launch after installation has been specified");
+            Some(
+                binaries
+                    .iter()
+                    .find(|b| b.get("binary-name") == Some(&name))
+                    .ok_or_else(|| {
+                        Error::Generic(format!(
+                            "The '{}' binary to launch after installation is not included in \
+                             the installer. Please use one of the following binaries: {}.",
+                            name,
+                            binaries
+                                .iter()
+                                .filter_map(|b| b.get("binary-name").map(String::as_str))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ))
+                    })?,
+            )
+        } else {
+            let primary = crate::primary_binary_name(package);
+            binaries
+                .iter()
+                .find(|b| b.get("binary-name").map(String::as_str) == Some(primary))
+                .or_else(|| binaries.first())
+        };
+        if binary.is_none() {
+            warn!(
+                "There are no binaries in the installer to launch after installation. The \
+                 checkbox to launch a binary will not be added to the installer."
+            );
+        }
+        Ok(binary.and_then(|b| b.get("binary-index")).cloned())
+    }
+
     fn default_binary_path(name: &str) -> StoredPathBuf {
         // Use hardcoded path separator here to avoid platform-specific output
         StoredPathBuf::from(format!(
@@ -602,106 +1498,450 @@ impl Execution {
             .as_deref()
             .and_then(|p| p.parent())
             .and_then(Utf8Path::from_path);
-        let licenses = Licenses::new(
+        let mut licenses = Licenses::new(
             output_dir,
             self.license.as_deref(),
             self.eula.as_deref(),
             manifest,
         )?;
+        // The EULA is resolved before the source license is removed, because
+        // the source license is also the default EULA.
+        if self.skip_license_sidecar(manifest) {
+            trace!("Excluding the license sidecar file from the installer");
+            if let (Some(source), Some(eula)) = (licenses.source.take(), &mut licenses.end_user) {
+                // A generated license is only generated for the sidecar file,
+                // so the EULA must generate it instead.
+                if eula.generate.is_none() && eula.stored_path == source.stored_path {
+                    eula.generate = source.generate;
+                }
+            }
+        }
+        if self.skip_eula_dialog(manifest) {
+            trace!("Excluding the EULA from the license agreement dialog");
+            licenses.end_user = None;
+        }
         Ok(licenses)
     }
 
     fn manufacturer(&self, manifest: &Package) -> Result<String> {
         if let Some(ref m) = self.manufacturer {
-            Ok(m.to_owned())
-        } else {
-            super::authors(manifest)
+            return Ok(m.to_owned());
+        }
+        let from = self.manufacturer_from.clone().or_else(|| {
+            manifest
+                .metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("manufacturer-from"))
+                .and_then(|m| m.as_str())
+                .map(String::from)
+        });
+        let keep_emails = self.keep_author_emails(manifest);
+        match from.as_deref() {
+            None => super::authors(manifest, &self.author_separator(manifest), keep_emails),
+            Some(f) if f.eq_ignore_ascii_case("all") => {
+                super::authors(manifest, &self.author_separator(manifest), keep_emails)
+            }
+            Some(f) if f.eq_ignore_ascii_case("first") => super::author(manifest, 0, keep_emails),
+            Some(f) => f
+                .parse::<usize>()
+                .map_err(|_| {
+                    Error::Generic(format!(
+                        "The '{f}' value for selecting the manufacturer from the authors is \
+                         not valid. Please use 'first', 'all', or the zero-based index of an \
+                         author."
+                    ))
+                })
+                .and_then(|index| super::author(manifest, index, keep_emails)),
         }
     }
 
-    fn path_guid(&self, manifest: &Package) -> Result<String> {
-        if let Some(ref u) = self.path_guid {
-            trace!("An path GUID has been explicitly specified");
-            Ok(u.to_owned())
-        } else if let Some(pkg_meta_wix_path_guid) = manifest
+    fn author_separator(&self, manifest: &Package) -> String {
+        self.author_separator
+            .clone()
+            .or_else(|| {
+                manifest
+                    .metadata
+                    .get("wix")
+                    .and_then(|w| w.as_object())
+                    .and_then(|t| t.get("author-separator"))
+                    .and_then(|s| s.as_str())
+                    .map(String::from)
+            })
+            .unwrap_or_else(|| String::from(super::DEFAULT_AUTHOR_SEPARATOR))
+    }
+
+    fn keep_author_emails(&self, manifest: &Package) -> bool {
+        self.keep_author_emails
+            || manifest
+                .metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("keep-author-emails"))
+                .and_then(|k| k.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn feature_maps(&self, manifest: &Package) -> Result<Vec<(String, String)>> {
+        let feature_maps = if let Some(f) = &self.feature_maps {
+            trace!("Feature maps have been explicitly specified");
+            f.to_owned()
+        } else if let Some(pkg_meta_wix_feature_map) = manifest
             .metadata
             .get("wix")
             .and_then(|w| w.as_object())
-            .and_then(|t| t.get("path-guid"))
-            .and_then(|u| u.as_str())
+            .and_then(|t| t.get("feature-map"))
+            .and_then(|f| f.as_array())
         {
-            Uuid::from_str(pkg_meta_wix_path_guid)
-                .map(|u| u.as_hyphenated().to_string().to_uppercase())
-                .map_err(Error::from)
+            trace!("Feature maps have been specified in the metadata");
+            pkg_meta_wix_feature_map
+                .iter()
+                .map(|f| {
+                    f.as_str().map(String::from).ok_or_else(|| {
+                        Error::Generic(format!(
+                            "The '{f}' value for the 'feature-map' field in the \
+                             'package.metadata.wix' section of the package's manifest \
+                             (Cargo.toml) is not a string."
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<String>>>()?
         } else {
-            Ok(Uuid::new_v4().as_hyphenated().to_string().to_uppercase())
+            Vec::new()
+        };
+        let mut maps: Vec<(String, String)> = Vec::with_capacity(feature_maps.len());
+        for feature_map in &feature_maps {
+            let (feature, title) = feature_map
+                .split_once('=')
+                .map(|(f, t)| (f.trim(), t.trim()))
+                .filter(|(f, t)| !f.is_empty() && !t.is_empty())
+                .ok_or_else(|| {
+                    Error::Generic(format!(
+                        "The '{feature_map}' feature map is invalid. A feature map must be \
+                         in the form of CARGO_FEATURE=TITLE."
+                    ))
+                })?;
+            if !manifest.features.contains_key(feature) {
+                return Err(Error::Generic(format!(
+                    "The '{feature}' feature does not exist for the '{}' package. Please \
+                     use one of the following features: {}.",
+                    manifest.name,
+                    manifest
+                        .features
+                        .keys()
+                        .map(String::as_str)
+                        .collect::<Vec<&str>>()
+                        .join(", ")
+                )));
+            }
+            if maps.iter().any(|(f, _)| f == feature) {
+                return Err(Error::Generic(format!(
+                    "The '{feature}' feature is mapped more than once. Please use only one \
+                     title for each feature."
+                )));
+            }
+            maps.push((feature.to_owned(), title.to_owned()));
         }
+        Ok(maps)
     }
 
-    fn upgrade_guid(&self, manifest: &Package) -> Result<String> {
-        if let Some(ref u) = self.upgrade_guid {
-            trace!("An upgrade GUID has been explicitly specified");
-            Ok(u.to_owned())
-        } else if let Some(pkg_meta_wix_upgrade_guid) = manifest
+    fn dialog_set(&self, manifest: &Package) -> Result<DialogSet> {
+        if let Some(d) = &self.dialog_set {
+            trace!("A dialog set has been explicitly specified");
+            DialogSet::from_str(d)
+        } else if let Some(pkg_meta_wix_dialog_set) = manifest
             .metadata
             .get("wix")
             .and_then(|w| w.as_object())
-            .and_then(|t| t.get("upgrade-guid"))
-            .and_then(|u| u.as_str())
+            .and_then(|t| t.get("dialog-set"))
+            .and_then(|d| d.as_str())
         {
-            Uuid::from_str(pkg_meta_wix_upgrade_guid)
-                .map(|u| u.as_hyphenated().to_string().to_uppercase())
-                .map_err(Error::from)
+            trace!("A dialog set has been specified in the metadata");
+            DialogSet::from_str(pkg_meta_wix_dialog_set)
         } else {
-            Ok(Uuid::new_v4().as_hyphenated().to_string().to_uppercase())
+            Ok(DialogSet::default())
         }
     }
 
-    fn banner_image(&self, manifest: &Package) -> Option<StoredPathBuf> {
-        if let Some(path) = &self.banner {
-            trace!("A banner image has been explicitly specified");
-            Some(path.clone())
+    fn install_scope(&self, manifest: &Package, no_path: bool) -> Result<InstallScope> {
+        let scope = if let Some(s) = &self.install_scope {
+            trace!("An installation scope has been explicitly specified");
+            Some(s.as_str())
         } else {
             manifest
                 .metadata
                 .get("wix")
                 .and_then(|w| w.as_object())
-                .and_then(|t| t.get("banner"))
-                .and_then(|p| p.as_str())
-                .map(|p| StoredPathBuf::new(p.to_owned()))
+                .and_then(|t| t.get("install-scope"))
+                .and_then(|s| s.as_str())
+        };
+        match scope {
+            Some(s) if s.eq_ignore_ascii_case(InstallScope::AUTO) => {
+                if no_path {
+                    trace!("The PATH is not modified, so the per-user installation scope is used");
+                    Ok(InstallScope::PerUser)
+                } else {
+                    trace!("The PATH is modified, so the per-machine installation scope is used");
+                    Ok(InstallScope::PerMachine)
+                }
+            }
+            Some(s) => InstallScope::from_str(s),
+            None => Ok(InstallScope::default()),
         }
     }
 
-    fn dialog_image(&self, manifest: &Package) -> Option<StoredPathBuf> {
-        if let Some(path) = &self.dialog {
-            trace!("A dialog image has been explicitly specified");
-            Some(path.clone())
-        } else {
-            manifest
+    fn no_path(&self, manifest: &Package) -> bool {
+        self.no_path
+            || manifest
                 .metadata
                 .get("wix")
                 .and_then(|w| w.as_object())
-                .and_then(|t| t.get("dialog"))
-                .and_then(|p| p.as_str())
-                .map(|p| StoredPathBuf::new(p.to_owned()))
-        }
+                .and_then(|t| t.get("no-path"))
+                .and_then(|n| n.as_bool())
+                .unwrap_or(false)
     }
 
-    fn product_icon(&self, manifest: &Package) -> Option<StoredPathBuf> {
-        if let Some(path) = &self.product_icon {
-            trace!("A product icon has been explicitly specified");
-            Some(path.clone())
-        } else {
-            manifest
+    fn no_modify(&self, manifest: &Package) -> bool {
+        self.no_modify
+            || manifest
                 .metadata
                 .get("wix")
                 .and_then(|w| w.as_object())
-                .and_then(|t| t.get("product-icon"))
-                .and_then(|p| p.as_str())
-                .map(|p| StoredPathBuf::new(p.to_owned()))
+                .and_then(|t| t.get("no-modify"))
+                .and_then(|n| n.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn no_repair(&self, manifest: &Package) -> bool {
+        self.no_repair
+            || manifest
+                .metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("no-repair"))
+                .and_then(|n| n.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn no_upgrade(&self, manifest: &Package) -> bool {
+        self.no_upgrade
+            || manifest
+                .metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("no-upgrade"))
+                .and_then(|n| n.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn run_after_install(&self, manifest: &Package) -> Option<String> {
+        self.run_after_install.clone().or_else(|| {
+            manifest
+                .metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("run-after-install"))
+                .and_then(|r| r.as_str())
+                .map(String::from)
+        })
+    }
+
+    fn uninstall_shortcut(&self, manifest: &Package) -> bool {
+        self.uninstall_shortcut
+            || manifest
+                .metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("uninstall-shortcut"))
+                .and_then(|u| u.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn skip_eula_dialog(&self, manifest: &Package) -> bool {
+        self.skip_eula_dialog
+            || manifest
+                .metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("skip-eula-dialog"))
+                .and_then(|s| s.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn skip_license_sidecar(&self, manifest: &Package) -> bool {
+        self.skip_license_sidecar
+            || manifest
+                .metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("skip-license-sidecar"))
+                .and_then(|s| s.as_bool())
+                .unwrap_or(false)
+    }
+
+    fn deterministic_component_guids(&self, manifest: &Package) -> bool {
+        self.deterministic_component_guids
+            || manifest
+                .metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("deterministic-component-guids"))
+                .and_then(|d| d.as_bool())
+                .unwrap_or(false)
+    }
+
+    /// Gets the GUID for the path component.
+    ///
+    /// If a key path is provided and a GUID is not explicitly specified, then
+    /// the GUID is derived from the key path instead of randomly generated,
+    /// and it is added to the deterministic GUIDs of the components.
+    fn path_guid(
+        &self,
+        manifest: &Package,
+        key_path: Option<&str>,
+        component_guids: &mut ComponentGuids,
+    ) -> Result<String> {
+        if let Some(ref u) = self.path_guid {
+            trace!("An path GUID has been explicitly specified");
+            crate::guid(u, "'--path-guid' option")
+        } else if let Some(pkg_meta_wix_path_guid) = manifest
+            .metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("path-guid"))
+            .and_then(|u| u.as_str())
+        {
+            crate::guid(
+                pkg_meta_wix_path_guid,
+                "'path-guid' field in the '[package.metadata.wix]' section",
+            )
+        } else if let Some(seed) = self.guid_from(manifest) {
+            trace!("Deriving the path GUID from the '{}' seed", seed);
+            Ok(seeded_guid(seed, PATH_GUID_NAME))
+        } else if let Some(key_path) = key_path {
+            trace!("Deriving the path GUID from the '{}' key path", key_path);
+            Ok(component_guids.add(PATH_GUID_NAME, key_path))
+        } else {
+            Ok(Uuid::new_v4().as_hyphenated().to_string().to_uppercase())
+        }
+    }
+
+    /// Gets the seed for deriving the upgrade code and path component GUIDs.
+    fn guid_from<'b>(&'b self, manifest: &'b Package) -> Option<&'b str> {
+        self.guid_from.as_deref().or_else(|| {
+            manifest
+                .metadata
+                .get("wix")
+                .and_then(|w| w.as_object())
+                .and_then(|t| t.get("guid-from"))
+                .and_then(|g| g.as_str())
+        })
+    }
+
+    fn upgrade_guid(&self, manifest: &Package) -> Result<String> {
+        if let Some(ref u) = self.upgrade_guid {
+            trace!("An upgrade GUID has been explicitly specified");
+            crate::guid(u, "'--upgrade-guid' option")
+        } else if let Some(pkg_meta_wix_upgrade_guid) = manifest
+            .metadata
+            .get("wix")
+            .and_then(|w| w.as_object())
+            .and_then(|t| t.get("upgrade-guid"))
+            .and_then(|u| u.as_str())
+        {
+            crate::guid(
+                pkg_meta_wix_upgrade_guid,
+                "'upgrade-guid' field in the '[package.metadata.wix]' section",
+            )
+        } else if let Some(seed) = self.guid_from(manifest) {
+            trace!("Deriving the upgrade GUID from the '{}' seed", seed);
+            Ok(seeded_guid(seed, UPGRADE_GUID_NAME))
+        } else {
+            Ok(Uuid::new_v4().as_hyphenated().to_string().to_uppercase())
+        }
+    }
+
+    fn banner_image(&self, manifest: &Package) -> Option<StoredPathBuf> {
+        if let Some(path) = culture_image(&self.culture_banners, &self.image_culture(manifest)) {
+            trace!("A banner image for the culture has been explicitly specified");
+            Some(path.clone())
+        } else if let Some(path) = &self.banner {
+            trace!("A banner image has been explicitly specified");
+            Some(path.clone())
+        } else {
+            metadata_path(manifest, "banner").or_else(|| {
+                wix_folder_path(
+                    manifest,
+                    &format!("{BANNER_FILE_NAME}.{BMP_FILE_EXTENSION}"),
+                )
+            })
+        }
+    }
+
+    fn dialog_image(&self, manifest: &Package) -> Option<StoredPathBuf> {
+        if let Some(path) = culture_image(&self.culture_dialogs, &self.image_culture(manifest)) {
+            trace!("A dialog image for the culture has been explicitly specified");
+            Some(path.clone())
+        } else if let Some(path) = &self.dialog {
+            trace!("A dialog image has been explicitly specified");
+            Some(path.clone())
+        } else {
+            metadata_path(manifest, "dialog").or_else(|| {
+                wix_folder_path(
+                    manifest,
+                    &format!("{DIALOG_FILE_NAME}.{BMP_FILE_EXTENSION}"),
+                )
+            })
+        }
+    }
+
+    /// Gets the culture used to select the banner and dialog images.
+    ///
+    /// This is the same culture used for the generated EULA.
+    fn image_culture(&self, manifest: &Package) -> String {
+        let culture = self.culture.as_deref().or_else(|| {
+            crate::metadata_cultures(&manifest.metadata)
+                .and_then(|c| c.first().copied())
+                .or_else(|| {
+                    manifest
+                        .metadata
+                        .get("wix")
+                        .and_then(|w| w.as_object())
+                        .and_then(|t| t.get("default-culture"))
+                        .and_then(|c| c.as_str())
+                })
+        });
+        match culture {
+            Some(c) if c.trim().eq_ignore_ascii_case(crate::create::AUTO_CULTURE) => {
+                crate::create::system_culture().to_string()
+            }
+            Some(c) => c.trim().to_owned(),
+            None => Cultures::EnUs.to_string(),
         }
     }
 
+    fn product_icon(&self, manifest: &Package) -> Option<StoredPathBuf> {
+        let icon = if let Some(path) = &self.product_icon {
+            trace!("A product icon has been explicitly specified");
+            Some(path.clone())
+        } else {
+            metadata_path(manifest, "product-icon").or_else(|| {
+                wix_folder_path(
+                    manifest,
+                    &format!("{PRODUCT_ICON_FILE_NAME}.{ICO_FILE_EXTENSION}"),
+                )
+            })
+        };
+        icon.map(|i| {
+            if i.as_str().eq_ignore_ascii_case(PRODUCT_ICON_FROM_BINARY) {
+                trace!("The product icon is extracted from the binary during creation");
+                StoredPathBuf::new(format!("$(var.{PRODUCT_ICON_VARIABLE})"))
+            } else {
+                i
+            }
+        })
+    }
+
     #[cfg(test)]
     pub fn for_test(input: &Path) -> Self {
         let input = Utf8Path::from_path(input).expect("utf8 path");
@@ -721,6 +1961,406 @@ impl Execution {
     }
 }
 
+/// Opens a file with the default application for its file extension.
+///
+/// This uses the `start` command of the Windows command prompt (cmd.exe),
+/// which opens the file the same way as double-clicking it in the File
+/// Explorer.
+fn open_with_default_application(path: &Path) -> Result<()> {
+    info!("Opening the '{}' file", path.display());
+    let mut opener = Command::new("cmd");
+    opener.arg("/C").arg("start").arg("").arg(path);
+    debug!("command = {:?}", opener);
+    let status = opener.status()?;
+    if !status.success() {
+        return Err(Error::Command("cmd", status.code().unwrap_or(100), false));
+    }
+    Ok(())
+}
+
+/// Adds the index, name, and source of each binary to the template data.
+/// Gets a path from the `[package.metadata.wix]` section of the package's
+/// manifest (Cargo.toml).
+///
+/// The path is kept relative to the folder containing the manifest, which is
+/// the base path for the linker (light.exe) when creating the installer, so a
+/// warning is logged if the file does not exist relative to that folder, even
+/// if it exists relative to the current working directory.
+fn metadata_path(package: &Package, field: &str) -> Option<StoredPathBuf> {
+    let path = package
+        .metadata
+        .get("wix")
+        .and_then(|w| w.as_object())
+        .and_then(|t| t.get(field))
+        .and_then(|p| p.as_str())?;
+    if !path.eq_ignore_ascii_case(PRODUCT_ICON_FROM_BINARY)
+        && !crate::package_relative_path(package, path).exists()
+    {
+        warn!(
+            "The '{path}' path for the '{field}' field in the 'package.metadata.wix' section \
+             of the package's manifest (Cargo.toml) does not exist. Paths in the \
+             'package.metadata.wix' section are relative to the folder containing the \
+             package's manifest, not the current working directory."
+        );
+    }
+    Some(StoredPathBuf::new(path.to_owned()))
+}
+
+/// Gets the path to a file with a conventional name in the `wix` folder of a
+/// package, such as `wix\Banner.bmp`, if it exists.
+///
+/// The file name is matched case-insensitively. The path is relative to the
+/// folder containing the package's manifest (Cargo.toml), like the paths in
+/// the `package.metadata.wix` section.
+fn wix_folder_path(package: &Package, file_name: &str) -> Option<StoredPathBuf> {
+    let folder = package.manifest_path.parent()?.join(WIX);
+    let name = fs::read_dir(&folder)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+        .filter_map(|e| e.file_name().into_string().ok())
+        .find(|n| n.eq_ignore_ascii_case(file_name))?;
+    info!("Using the '{}' file found in the '{}' folder", name, WIX);
+    Some(StoredPathBuf::new(format!("{WIX}\\{name}")))
+}
+
+/// Warns if a banner or dialog image with the `.bmp` file extension is not a
+/// 24-bit bitmap (BMP).
+///
+/// The WixUI dialog library expects 24-bit bitmaps. Other bit depths, notably
+/// 32-bit bitmaps with an alpha channel, can render incorrectly in the
+/// installer. Only the header of the image is read, and nothing is checked if
+/// the image cannot be read, such as a path that does not exist yet.
+fn check_bitmap(package: &Package, kind: &str, image: &StoredPath) {
+    let path = crate::package_relative_path(package, image.as_str());
+    if !path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case(BITMAP_FILE_EXTENSION))
+    {
+        return;
+    }
+    let mut header = Vec::with_capacity(BITMAP_HEADER_LENGTH);
+    if let Err(err) = fs::File::open(&path)
+        .and_then(|f| f.take(BITMAP_HEADER_LENGTH as u64).read_to_end(&mut header))
+    {
+        trace!(
+            "Skipped checking the '{}' {} image: {}",
+            path.display(),
+            kind,
+            err
+        );
+        return;
+    }
+    match bitmap_bit_depth(&header) {
+        Some(BITMAP_BIT_DEPTH) => trace!("The '{}' {} image is a 24-bit bitmap", image, kind),
+        Some(depth) => warn!(
+            "The '{image}' {kind} image is a {depth}-bit bitmap (BMP), but the WixUI \
+             dialog library expects a 24-bit bitmap, so the image might render \
+             incorrectly in the installer. Save the image as a 24-bit bitmap without \
+             an alpha channel using an image editor."
+        ),
+        None => warn!(
+            "The '{image}' {kind} image has the '.bmp' file extension, but it is not a \
+             bitmap (BMP) file, so the image might not be displayed in the installer."
+        ),
+    }
+}
+
+/// Gets the bit depth, i.e. bits per pixel, from the header of a bitmap (BMP)
+/// file.
+///
+/// `None` is returned if the header is not for a bitmap or it is truncated.
+fn bitmap_bit_depth(header: &[u8]) -> Option<u16> {
+    if header.get(0..2) != Some(b"BM") {
+        return None;
+    }
+    let dib_header_size = header
+        .get(14..18)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))?;
+    // The OS/2 core header has 16-bit dimensions, so the bit depth comes
+    // earlier than in the Windows info header and its later versions.
+    let offset = if dib_header_size == 12 { 24 } else { 28 };
+    header
+        .get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn push_binaries<'a, I>(mut builder: VecBuilder, binaries: I) -> VecBuilder
+where
+    I: Iterator<Item = &'a HashMap<&'static str, String>>,
+{
+    for binary in binaries {
+        builder = builder.push_map(|builder| {
+            let builder = builder
+                .insert_str("binary-index", binary.get("binary-index").unwrap())
+                .insert_str("binary-name", binary.get("binary-name").unwrap())
+                .insert_str("binary-source", binary.get("binary-source").unwrap())
+                .insert_str(
+                    "binary-component-guid",
+                    binary.get("binary-component-guid").unwrap(),
+                );
+            if let Some(feature) = binary.get("binary-feature") {
+                builder.insert_str("binary-feature", feature)
+            } else {
+                builder
+            }
+        });
+    }
+    builder
+}
+
+/// Describes the license files and help URL that a rendered template references.
+///
+/// The EULA is displayed in the license agreement dialog and the source license
+/// is installed as a sidecar file. Either can be generated from a template, in
+/// which case the file does not exist until the template is rendered. The EULA
+/// is also generated if it is the same file as a generated source license.
+fn license_report(licenses: &Licenses, help_url: Option<&str>) -> Vec<String> {
+    let describe = |license: Option<&License>| match license {
+        Some(license) => {
+            let mut description = format!("'{}'", license.stored_path);
+            let generate = license.generate.as_ref().or_else(|| {
+                licenses
+                    .source
+                    .as_ref()
+                    .filter(|s| s.stored_path == license.stored_path)
+                    .and_then(|s| s.generate.as_ref())
+            });
+            if let Some((_, template)) = generate {
+                description.push_str(&format!(", generated from the {template} template"));
+            }
+            if let Some(name) = &license.name {
+                description.push_str(&format!(", installed as '{name}'"));
+            }
+            description
+        }
+        None => String::from("none"),
+    };
+    vec![
+        format!(
+            "EULA for the license agreement dialog: {}",
+            describe(licenses.end_user.as_ref())
+        ),
+        format!(
+            "License sidecar file: {}",
+            describe(licenses.source.as_ref())
+        ),
+        format!("Help URL: {}", help_url.unwrap_or("none")),
+    ]
+}
+
+/// The deterministic GUIDs of the components, i.e. the identifier of each
+/// component and its key path.
+///
+/// The Program Files folder, and so the install location of a component,
+/// depends on the platform, so a GUID is derived for each platform and the
+/// GUIDs are defined as preprocessor variables in the template. The 32-bit
+/// and 64-bit installers do not share components.
+#[derive(Debug, Default)]
+struct ComponentGuids(Vec<(String, String)>);
+
+impl ComponentGuids {
+    /// Adds a component with a key path and gets the reference to the
+    /// preprocessor variable for its GUID.
+    fn add(&mut self, component_id: &str, key_path: &str) -> String {
+        let name = format!("{component_id}ComponentGuid");
+        let reference = format!("$(var.{name})");
+        self.0.push((name, key_path.to_owned()));
+        reference
+    }
+}
+
+/// Derives a deterministic (version 5) GUID for a component from its key path.
+///
+/// The key path is the install location of the component, including the
+/// folder for the program files of the platform, and it is case-insensitive
+/// like paths on Windows, so identical inputs always yield identical GUIDs.
+fn component_guid(key_path: &str) -> String {
+    Uuid::new_v5(
+        &COMPONENT_GUID_NAMESPACE,
+        key_path.to_lowercase().as_bytes(),
+    )
+    .as_hyphenated()
+    .to_string()
+    .to_uppercase()
+}
+
+/// Derives a deterministic (version 5) GUID from a seed and a name.
+///
+/// The seed is case-sensitive and is used to derive a namespace, so each seed
+/// has its own set of GUIDs and the name selects the GUID within the set,
+/// such as the upgrade code or the path component.
+fn seeded_guid(seed: &str, name: &str) -> String {
+    let namespace = Uuid::new_v5(&SEEDED_GUID_NAMESPACE, seed.as_bytes());
+    Uuid::new_v5(&namespace, name.as_bytes())
+        .as_hyphenated()
+        .to_string()
+        .to_uppercase()
+}
+
+/// Gets the key path of the component for a binary, which is the install
+/// location of the binary, i.e. `<product-name>\bin\<subfolder>\<name>.exe`.
+fn binary_key_path(product_name: &str, binary: &HashMap<&'static str, String>) -> String {
+    let mut key_path = format!("{product_name}\\bin\\");
+    if let Some(subdirectory) = binary.get("binary-subdirectory") {
+        key_path.push_str(subdirectory);
+        key_path.push('\\');
+    }
+    key_path.push_str(&binary["binary-name"]);
+    key_path.push('.');
+    key_path.push_str(EXE_FILE_EXTENSION);
+    key_path
+}
+
+/// Gets the first mapped Cargo feature that is required by a binary of the
+/// package, i.e. listed in the `required-features` field of the binary.
+fn binary_feature<'a>(
+    package: &Package,
+    binary_name: &str,
+    feature_maps: &'a [(String, String)],
+) -> Option<&'a str> {
+    let binary = package
+        .targets
+        .iter()
+        .find(|t| t.name == binary_name && t.kind.iter().any(|k| k == "bin"))?;
+    feature_maps
+        .iter()
+        .map(|(f, _)| f.as_str())
+        .find(|f| binary.required_features.iter().any(|r| r == f))
+}
+
+/// Splits the optional install subfolder from the path to a binary.
+///
+/// The subfolder follows the last colon in the value, e.g.
+/// `target\release\helper.exe:tools`. The colon after a drive letter, e.g.
+/// `C:\helper.exe`, is _not_ treated as a separator.
+/// Expands the paths to binaries that are folders or contain wildcards into the
+/// paths to the matching executables.
+///
+/// The subfolder suffix of a path, if any, is kept for each matching
+/// executable. Other paths are unchanged.
+fn expand_binaries(binary_paths: &[StoredPathBuf]) -> Result<Vec<String>> {
+    let mut expanded = Vec::new();
+    for binary in binary_paths {
+        let (path, subdirectory) = split_binary_subdirectory(binary.as_str());
+        match matching_binaries(path)? {
+            Some(matches) => {
+                debug!("The '{}' binary path matches {:?}", path, matches);
+                for matched in matches {
+                    if let Some(subdirectory) = subdirectory {
+                        expanded.push(format!("{matched}:{subdirectory}"));
+                    } else {
+                        expanded.push(matched);
+                    }
+                }
+            }
+            None => expanded.push(binary.as_str().to_owned()),
+        }
+    }
+    Ok(expanded)
+}
+
+/// Gets the sorted paths to the executables matching a binary path that is a
+/// folder or contains the `*` and `?` wildcards in the file name.
+///
+/// `None` is returned if the path is neither, i.e. it is a path to a single
+/// binary. Matching is case-insensitive, like file names on Windows.
+fn matching_binaries(path: &str) -> Result<Option<Vec<String>>> {
+    let (folder, pattern) = if path.contains(['*', '?']) {
+        match path.rfind(['/', '\\']) {
+            Some(index) => (&path[..=index], &path[index + 1..]),
+            None => ("", path),
+        }
+    } else if Path::new(path).is_dir() {
+        (path, "*.exe")
+    } else {
+        return Ok(None);
+    };
+    if folder.contains(['*', '?']) {
+        return Err(Error::Generic(format!(
+            "The '{path}' binary path has a wildcard in a folder name. Wildcards \
+             are only supported in the file name."
+        )));
+    }
+    let regex = Regex::new(&format!(
+        "(?i)^{}$",
+        regex::escape(pattern)
+            .replace("\\*", ".*")
+            .replace("\\?", ".")
+    ))
+    .expect("Valid regex for a binary path pattern");
+    let separator = if folder.is_empty() || folder.ends_with(['/', '\\']) {
+        ""
+    } else if folder.contains('/') {
+        "/"
+    } else {
+        "\\"
+    };
+    let mut matches = Vec::new();
+    let entries = fs::read_dir(if folder.is_empty() { "." } else { folder }).map_err(|err| {
+        Error::Generic(format!(
+            "The folder for the '{path}' binary path could not be read: {err}"
+        ))
+    })?;
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        if let Some(name) = entry.file_name().to_str() {
+            if regex.is_match(name) {
+                matches.push(format!("{folder}{separator}{name}"));
+            }
+        }
+    }
+    if matches.is_empty() {
+        return Err(Error::Generic(format!(
+            "The '{path}' binary path does not match any executables"
+        )));
+    }
+    matches.sort();
+    Ok(Some(matches))
+}
+
+fn culture_images(images: &[(&str, &str)]) -> Vec<(String, StoredPathBuf)> {
+    images
+        .iter()
+        .map(|(culture, path)| ((*culture).to_owned(), StoredPathBuf::from(*path)))
+        .collect()
+}
+
+/// Selects the image for a culture from the images for each culture.
+///
+/// The image for the culture, e.g. `fr-FR`, is preferred over the image for
+/// the language of the culture, e.g. `fr`. `None` is returned if none of the
+/// images apply to the culture.
+fn culture_image<'a>(
+    images: &'a [(String, StoredPathBuf)],
+    culture: &str,
+) -> Option<&'a StoredPathBuf> {
+    let language = culture.split('-').next().unwrap_or(culture);
+    images
+        .iter()
+        .find(|(c, _)| c.eq_ignore_ascii_case(culture))
+        .or_else(|| {
+            images
+                .iter()
+                .find(|(c, _)| c.eq_ignore_ascii_case(language))
+        })
+        .map(|(_, path)| path)
+}
+
+fn split_binary_subdirectory(value: &str) -> (&str, Option<&str>) {
+    match value.rsplit_once(':') {
+        Some(("", _)) => (value, None),
+        Some((path, _)) if path.len() == 1 && path.chars().all(|c| c.is_ascii_alphabetic()) => {
+            (value, None)
+        }
+        Some((path, subdirectory)) => (path, Some(subdirectory)),
+        None => (value, None),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -730,6 +2370,21 @@ mod tests {
     mod builder {
         use super::*;
 
+        #[test]
+        fn after_install_launch_works() {
+            let mut actual = Builder::new();
+            actual.after_install_launch(true);
+            assert!(actual.after_install_launch);
+        }
+
+        #[test]
+        fn author_separator_works() {
+            const EXPECTED: &str = ", ";
+            let mut actual = Builder::new();
+            actual.author_separator(Some(EXPECTED));
+            assert_eq!(actual.author_separator, Some(EXPECTED));
+        }
+
         #[test]
         fn banner_works() {
             const EXPECTED: &str = "img\\Banner.bmp";
@@ -762,6 +2417,47 @@ mod tests {
             assert_eq!(actual.dialog, Some(EXPECTED));
         }
 
+        #[test]
+        fn culture_banner_works() {
+            let mut actual = Builder::new();
+            actual
+                .culture_banner("fr-FR", "img\\Banner_fr.bmp")
+                .culture_banner("de", "img\\Banner_de.bmp");
+            assert_eq!(
+                actual.culture_banners,
+                vec![
+                    ("fr-FR", "img\\Banner_fr.bmp"),
+                    ("de", "img\\Banner_de.bmp")
+                ]
+            );
+        }
+
+        #[test]
+        fn culture_dialog_works() {
+            let mut actual = Builder::new();
+            actual.culture_dialog("fr-FR", "img\\Dialog_fr.bmp");
+            assert_eq!(
+                actual.culture_dialogs,
+                vec![("fr-FR", "img\\Dialog_fr.bmp")]
+            );
+        }
+
+        #[test]
+        fn dialog_set_works() {
+            const EXPECTED: &str = "install-dir";
+            let mut actual = Builder::new();
+            actual.dialog_set(Some(EXPECTED));
+            assert_eq!(actual.dialog_set, Some(EXPECTED));
+        }
+
+        #[test]
+        fn dump_context_works() {
+            const EXPECTED: &str = "context.json";
+            let mut actual = Builder::new();
+            actual.dump_context(Some(EXPECTED));
+            assert_eq!(actual.dump_context, Some(EXPECTED));
+        }
+
         #[test]
         fn eula_works() {
             const EXPECTED: &str = "Example_Eula.rtf";
@@ -770,6 +2466,22 @@ mod tests {
             assert_eq!(actual.eula, Some(EXPECTED));
         }
 
+        #[test]
+        fn feature_maps_works() {
+            const EXPECTED: &str = "gui=Graphical Interface";
+            let mut actual = Builder::new();
+            actual.feature_maps(Some(vec![EXPECTED]));
+            assert_eq!(actual.feature_maps, Some(vec![EXPECTED]));
+        }
+
+        #[test]
+        fn guid_from_works() {
+            const EXPECTED: &str = "com.example.product";
+            let mut actual = Builder::new();
+            actual.guid_from(Some(EXPECTED));
+            assert_eq!(actual.guid_from, Some(EXPECTED));
+        }
+
         #[test]
         fn help_url_works() {
             const EXPECTED: &str = "http://www.example.com";
@@ -778,6 +2490,13 @@ mod tests {
             assert_eq!(actual.help_url, Some(EXPECTED));
         }
 
+        #[test]
+        fn include_licenses_works() {
+            let mut actual = Builder::new();
+            actual.include_licenses(true);
+            assert!(actual.include_licenses);
+        }
+
         #[test]
         fn input_works() {
             const EXPECTED: &str = "C:\\example\\Cargo.toml";
@@ -786,6 +2505,14 @@ mod tests {
             assert_eq!(actual.input, Some(EXPECTED));
         }
 
+        #[test]
+        fn install_scope_works() {
+            const EXPECTED: &str = "auto";
+            let mut actual = Builder::new();
+            actual.install_scope(Some(EXPECTED));
+            assert_eq!(actual.install_scope, Some(EXPECTED));
+        }
+
         #[test]
         fn license_works() {
             const EXPECTED: &str = "C:\\example\\Example License.rtf";
@@ -795,52 +2522,161 @@ mod tests {
         }
 
         #[test]
-        fn manufacturer_works() {
-            const EXPECTED: &str = "Example";
+        fn keep_author_emails_works() {
             let mut actual = Builder::new();
-            actual.manufacturer(Some(EXPECTED));
-            assert_eq!(actual.manufacturer, Some(EXPECTED));
+            actual.keep_author_emails(true);
+            assert!(actual.keep_author_emails);
         }
 
         #[test]
-        fn output_works() {
-            const EXPECTED: &str = "C:\\example\\output";
+        fn launch_binary_works() {
+            const EXPECTED: &str = "Example";
             let mut actual = Builder::new();
-            actual.output(Some(EXPECTED));
-            assert_eq!(actual.output, Some(EXPECTED));
+            actual.launch_binary(Some(EXPECTED));
+            assert_eq!(actual.launch_binary, Some(EXPECTED));
         }
 
         #[test]
-        fn path_guid_works() {
-            let expected = Uuid::new_v4().as_hyphenated().to_string().to_uppercase();
+        fn manufacturer_from_works() {
+            const EXPECTED: &str = "first";
             let mut actual = Builder::new();
-            actual.path_guid(Some(&expected));
-            assert_eq!(actual.path_guid, Some(expected.as_ref()));
+            actual.manufacturer_from(Some(EXPECTED));
+            assert_eq!(actual.manufacturer_from, Some(EXPECTED));
         }
 
         #[test]
-        fn product_icon_works() {
-            const EXPECTED: &str = "img\\Product.ico";
+        fn manufacturer_works() {
+            const EXPECTED: &str = "Example";
             let mut actual = Builder::new();
-            actual.product_icon(Some(EXPECTED));
-            assert_eq!(actual.product_icon, Some(EXPECTED));
+            actual.manufacturer(Some(EXPECTED));
+            assert_eq!(actual.manufacturer, Some(EXPECTED));
         }
 
         #[test]
-        fn product_name_works() {
-            const EXPECTED: &str = "Example Product Name";
+        fn no_path_works() {
             let mut actual = Builder::new();
-            actual.product_name(Some(EXPECTED));
-            assert_eq!(actual.product_name, Some(EXPECTED));
+            actual.no_path(true);
+            assert!(actual.no_path);
         }
 
         #[test]
-        fn upgrade_guid_works() {
+        fn no_modify_works() {
+            let mut actual = Builder::new();
+            actual.no_modify(true);
+            assert!(actual.no_modify);
+        }
+
+        #[test]
+        fn no_repair_works() {
+            let mut actual = Builder::new();
+            actual.no_repair(true);
+            assert!(actual.no_repair);
+        }
+
+        #[test]
+        fn no_upgrade_works() {
+            let mut actual = Builder::new();
+            actual.no_upgrade(true);
+            assert!(actual.no_upgrade);
+        }
+
+        #[test]
+        fn run_after_install_works() {
+            const EXPECTED: &str = "[APPLICATIONFOLDER]bin\\setup.exe --register";
+            let mut actual = Builder::new();
+            actual.run_after_install(Some(EXPECTED));
+            assert_eq!(actual.run_after_install, Some(EXPECTED));
+        }
+
+        #[test]
+        fn uninstall_shortcut_works() {
+            let mut actual = Builder::new();
+            actual.uninstall_shortcut(true);
+            assert!(actual.uninstall_shortcut);
+        }
+
+        #[test]
+        fn deterministic_component_guids_works() {
+            let mut actual = Builder::new();
+            actual.deterministic_component_guids(true);
+            assert!(actual.deterministic_component_guids);
+        }
+
+        #[test]
+        fn schema_validate_works() {
+            let mut actual = Builder::new();
+            actual.schema_validate(true);
+            assert!(actual.schema_validate);
+        }
+
+        #[test]
+        fn skip_eula_dialog_works() {
+            let mut actual = Builder::new();
+            actual.skip_eula_dialog(true);
+            assert!(actual.skip_eula_dialog);
+        }
+
+        #[test]
+        fn skip_license_sidecar_works() {
+            let mut actual = Builder::new();
+            actual.skip_license_sidecar(true);
+            assert!(actual.skip_license_sidecar);
+        }
+
+        #[test]
+        fn open_works() {
+            let mut actual = Builder::new();
+            actual.open(true);
+            assert!(actual.open);
+        }
+
+        #[test]
+        fn output_works() {
+            const EXPECTED: &str = "C:\\example\\output";
+            let mut actual = Builder::new();
+            actual.output(Some(EXPECTED));
+            assert_eq!(actual.output, Some(EXPECTED));
+        }
+
+        #[test]
+        fn path_guid_works() {
+            let expected = Uuid::new_v4().as_hyphenated().to_string().to_uppercase();
+            let mut actual = Builder::new();
+            actual.path_guid(Some(&expected));
+            assert_eq!(actual.path_guid, Some(expected.as_ref()));
+        }
+
+        #[test]
+        fn product_icon_works() {
+            const EXPECTED: &str = "img\\Product.ico";
+            let mut actual = Builder::new();
+            actual.product_icon(Some(EXPECTED));
+            assert_eq!(actual.product_icon, Some(EXPECTED));
+        }
+
+        #[test]
+        fn product_name_works() {
+            const EXPECTED: &str = "Example Product Name";
+            let mut actual = Builder::new();
+            actual.product_name(Some(EXPECTED));
+            assert_eq!(actual.product_name, Some(EXPECTED));
+        }
+
+        #[test]
+        fn upgrade_guid_works() {
             let expected = Uuid::new_v4().as_hyphenated().to_string().to_uppercase();
             let mut actual = Builder::new();
             actual.upgrade_guid(Some(&expected));
             assert_eq!(actual.upgrade_guid, Some(expected.as_ref()));
         }
+
+        #[test]
+        fn wxs_template_dir_works() {
+            const EXPECTED: &str = "C:\\Example\\templates";
+            let mut actual = Builder::new();
+            actual.wxs_template_dir(Some(EXPECTED));
+            assert_eq!(actual.wxs_template_dir, Some(EXPECTED));
+        }
     }
 
     mod execution {
@@ -878,6 +2714,27 @@ mod tests {
             license = "Apache-2.0"
         "#;
 
+        const DUAL_LICENSE_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+            license = "MIT OR Apache-2.0"
+        "#;
+
+        const EXCEPTION_LICENSE_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+            license = "(Apache-2.0 WITH LLVM-exception) OR GPL-3.0"
+        "#;
+
+        const UNKNOWN_EXPRESSION_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+            license = "XYZ OR ABC AND WXS"
+        "#;
+
         const UNKNOWN_MANIFEST: &str = r#"[package]
             name = "Example"
             version = "0.1.0"
@@ -1282,6 +3139,71 @@ mod tests {
             assert_eq!(licenses.end_user, None);
         }
 
+        #[test]
+        fn skip_license_sidecar_works() {
+            let project = setup_project(MIT_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let licenses = Builder::default()
+                .input(input.to_str())
+                .skip_license_sidecar(true)
+                .build()
+                .licenses(&package)
+                .expect("licenses");
+            assert_eq!(licenses.source, None);
+            assert_eq!(
+                licenses.end_user.unwrap().stored_path,
+                StoredPathBuf::from(format!("{WIX}\\{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}"))
+            );
+        }
+
+        #[test]
+        fn skip_eula_dialog_metadata_works() {
+            const SKIP_EULA_DIALOG_MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+                license = "MIT"
+
+                [package.metadata.wix]
+                skip-eula-dialog = true
+            "#;
+            let project = setup_project(SKIP_EULA_DIALOG_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let licenses = Execution::for_test(&input)
+                .licenses(&package)
+                .expect("licenses");
+            assert_eq!(
+                licenses.source.unwrap().stored_path,
+                StoredPathBuf::from(format!("{WIX}\\{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}"))
+            );
+            assert_eq!(licenses.end_user, None);
+        }
+
+        #[test]
+        fn render_with_skip_license_sidecar_works() {
+            let project = setup_project(MIT_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let renders = Builder::default()
+                .input(input.to_str())
+                .skip_license_sidecar(true)
+                .build()
+                .render()
+                .unwrap();
+            assert!(!renders.wxs.rendered.contains("Source='wix\\License.rtf'"));
+            assert!(renders
+                .wxs
+                .rendered
+                .contains("<WixVariable Id='WixUILicenseRtf' Value='wix\\License.rtf'/>"));
+            assert!(renders.license.is_none());
+            assert!(renders.eula.is_some());
+        }
+
         #[test]
         fn license_path_rtf_works() {
             let project = setup_project(LICENSE_PATH_RTF_MANIFEST);
@@ -1491,369 +3413,1833 @@ mod tests {
         }
 
         #[test]
-        fn manufacturer_with_defaults_works() {
-            const EXPECTED: &str = "First Last";
-
+        fn binaries_with_subdirectories_works() {
             let project = setup_project(MIN_MANIFEST);
             let input = project.path().join("Cargo.toml");
             let manifest = crate::manifest(Some(&input)).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let actual = Execution::for_test(&input).manufacturer(&package).unwrap();
-            assert_eq!(actual, String::from(EXPECTED));
+            let actual = Builder::new()
+                .input(input.to_str())
+                .binaries(Some(vec!["bin\\Example.exe", "C:\\bin\\Helper.exe:tools"]))
+                .build()
+                .binaries(&package)
+                .unwrap();
+            assert_eq!(
+                actual,
+                vec![
+                    hashmap! {
+                        "binary-index" => 0.to_string(),
+                        "binary-name" => String::from("Example"),
+                        "binary-source" => String::from("bin\\Example.exe")
+                    },
+                    hashmap! {
+                        "binary-index" => 1.to_string(),
+                        "binary-name" => String::from("Helper"),
+                        "binary-source" => String::from("C:\\bin\\Helper.exe"),
+                        "binary-subdirectory" => String::from("tools")
+                    }
+                ]
+            )
         }
 
         #[test]
-        fn manufacturer_with_override_works() {
-            const EXPECTED: &str = "Example";
-
+        fn binaries_with_nested_subdirectory_fails() {
             let project = setup_project(MIN_MANIFEST);
-            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let actual = Builder::default()
-                .manufacturer(Some(EXPECTED))
+            let actual = Builder::new()
+                .input(input.to_str())
+                .binaries(Some(vec!["bin\\Helper.exe:tools\\extra"]))
                 .build()
-                .manufacturer(&package)
-                .unwrap();
-            assert_eq!(actual, String::from(EXPECTED));
+                .binaries(&package);
+            assert!(actual.is_err());
         }
 
         #[test]
-        fn help_url_with_defaults_works() {
+        fn binaries_with_pattern_works() {
             let project = setup_project(MIN_MANIFEST);
-            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
             let package = crate::package(&manifest, None).unwrap();
+            let bin = project.path().join("bin");
+            fs::create_dir(&bin).unwrap();
+            for name in ["Plugin2.exe", "Plugin1.EXE", "Readme.txt", "Example.exe"] {
+                fs::write(bin.join(name), "").unwrap();
+            }
+            let bin = bin.to_str().unwrap();
+            let pattern = format!("{bin}/Plugin*.exe:plugins");
 
-            let actual = Builder::default().build().help_url(&package);
-            assert!(actual.is_none());
+            let actual = Builder::new()
+                .input(input.to_str())
+                .binaries(Some(vec!["bin\\Plugin1.exe", &pattern, bin]))
+                .build()
+                .binaries(&package)
+                .unwrap();
+            assert_eq!(
+                actual,
+                vec![
+                    hashmap! {
+                        "binary-index" => 0.to_string(),
+                        "binary-name" => String::from("Plugin1"),
+                        "binary-source" => String::from("bin\\Plugin1.exe")
+                    },
+                    hashmap! {
+                        "binary-index" => 1.to_string(),
+                        "binary-name" => String::from("Plugin2"),
+                        "binary-source" => format!("{bin}/Plugin2.exe"),
+                        "binary-subdirectory" => String::from("plugins")
+                    },
+                    hashmap! {
+                        "binary-index" => 2.to_string(),
+                        "binary-name" => String::from("Example"),
+                        "binary-source" => format!("{bin}/Example.exe")
+                    },
+                ]
+            )
         }
 
         #[test]
-        fn help_url_with_documentation_works() {
-            const EXPECTED: &str = "http://www.example.com";
-
-            let project = setup_project(DOCUMENTATION_MANIFEST);
-            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+        fn binaries_with_unmatched_pattern_fails() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
             let package = crate::package(&manifest, None).unwrap();
+            let pattern = format!("{}/*.exe", project.path().to_str().unwrap());
 
-            let actual = Builder::default().build().help_url(&package);
-            assert_eq!(actual, Some(String::from(EXPECTED)));
+            let actual = Builder::new()
+                .input(input.to_str())
+                .binaries(Some(vec![&pattern]))
+                .build()
+                .binaries(&package);
+            assert!(actual.is_err());
         }
 
         #[test]
-        fn help_url_with_homepage_works() {
-            const EXPECTED: &str = "http://www.example.com";
-
-            let project = setup_project(HOMEPAGE_MANIFEST);
-            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+        fn launch_binary_index_without_flag_works() {
+            let project = setup_project(MULTIPLE_BIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let actual = Builder::default().build().help_url(&package);
-            assert_eq!(actual, Some(String::from(EXPECTED)));
+            let execution = Execution::for_test(&input);
+            let binaries = execution.binaries(&package).unwrap();
+            let actual = execution.launch_binary_index(&package, &binaries).unwrap();
+            assert_eq!(actual, None);
         }
 
         #[test]
-        fn help_url_with_repository_works() {
-            const EXPECTED: &str = "http://www.example.com";
-
-            let project = setup_project(REPOSITORY_MANIFEST);
-            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+        fn launch_binary_index_with_default_works() {
+            let project = setup_project(MULTIPLE_BIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let actual = Builder::default().build().help_url(&package);
-            assert_eq!(actual, Some(String::from(EXPECTED)));
+            let execution = Builder::new()
+                .input(input.to_str())
+                .after_install_launch(true)
+                .build();
+            let binaries = execution.binaries(&package).unwrap();
+            let actual = execution.launch_binary_index(&package, &binaries).unwrap();
+            assert_eq!(actual, Some(String::from("0")));
         }
 
         #[test]
-        fn eula_with_defaults_works() {
+        fn launch_binary_index_with_package_name_works() {
             let project = setup_project(MIN_MANIFEST);
             let input = project.path().join("Cargo.toml");
             let manifest = crate::manifest(Some(&input)).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let actual = Execution::for_test(&input)
-                .licenses(&package)
-                .unwrap()
-                .end_user;
-            assert!(actual.is_none());
+            let execution = Builder::new()
+                .input(input.to_str())
+                .binaries(Some(vec!["bin\\Helper.exe", "bin\\Example.exe"]))
+                .after_install_launch(true)
+                .build();
+            let binaries = execution.binaries(&package).unwrap();
+            let actual = execution.launch_binary_index(&package, &binaries).unwrap();
+            assert_eq!(actual, Some(String::from("1")));
         }
 
         #[test]
-        fn eula_with_mit_license_field_works() {
-            let project = setup_project(MIT_MANIFEST);
+        fn launch_binary_index_with_launch_binary_works() {
+            let project = setup_project(MULTIPLE_BIN_MANIFEST);
             let input = project.path().join("Cargo.toml");
             let manifest = crate::manifest(Some(&input)).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let licenses = Execution::for_test(&input).licenses(&package).unwrap();
-            let source = licenses.source.unwrap();
-            let source_path = source.stored_path;
-            let (template_out, source_template) = source.generate.unwrap();
-            let eula_path = licenses.end_user.unwrap().stored_path;
-
-            let expected_rel_path = format!("{WIX}\\{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}");
-            let expected_abs_path = Utf8Path::from_path(input.parent().unwrap())
-                .unwrap()
-                .join(WIX)
-                .join(format!("{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}"));
-            assert_eq!(source_template, Template::Mit);
-            assert_eq!(source_path.as_str(), expected_rel_path);
-            assert_eq!(template_out, expected_abs_path);
-            assert_eq!(eula_path.as_str(), expected_rel_path);
+            let execution = Builder::new()
+                .input(input.to_str())
+                .after_install_launch(true)
+                .launch_binary(Some("binary2"))
+                .build();
+            let binaries = execution.binaries(&package).unwrap();
+            let actual = execution.launch_binary_index(&package, &binaries).unwrap();
+            assert_eq!(actual, Some(String::from("2")));
         }
 
         #[test]
-        fn eula_with_apache2_license_field_works() {
-            let project = setup_project(APACHE2_MANIFEST);
+        fn launch_binary_index_with_metadata_works() {
+            const LAUNCH_MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [[bin]]
+                name = "binary0"
+                path = "src/binary0/main.rs"
+
+                [[bin]]
+                name = "binary1"
+                path = "src/binary1/main.rs"
+
+                [package.metadata.wix]
+                after-install-launch = true
+                launch-binary = "binary1"
+            "#;
+            let project = setup_project(LAUNCH_MANIFEST);
             let input = project.path().join("Cargo.toml");
             let manifest = crate::manifest(Some(&input)).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let licenses = Execution::for_test(&input).licenses(&package).unwrap();
-            let source = licenses.source.unwrap();
-            let source_path = source.stored_path;
-            let (template_out, source_template) = source.generate.unwrap();
-            let eula_path = licenses.end_user.unwrap().stored_path;
-
-            let expected_rel_path = format!("{WIX}\\{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}");
-            let expected_abs_path = Utf8Path::from_path(input.parent().unwrap())
-                .unwrap()
-                .join(WIX)
-                .join(format!("{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}"));
-            assert_eq!(source_template, Template::Apache2);
-            assert_eq!(source_path.as_str(), expected_rel_path);
-            assert_eq!(template_out, expected_abs_path);
-            assert_eq!(eula_path.as_str(), expected_rel_path);
+            let execution = Execution::for_test(&input);
+            let binaries = execution.binaries(&package).unwrap();
+            let actual = execution.launch_binary_index(&package, &binaries).unwrap();
+            assert_eq!(actual, Some(String::from("1")));
         }
 
         #[test]
-        fn eula_with_gpl3_license_field_works() {
-            let project = setup_project(GPL3_MANIFEST);
+        fn launch_binary_index_with_default_run_works() {
+            const DEFAULT_RUN_MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+                default-run = "binary1"
+
+                [[bin]]
+                name = "binary0"
+                path = "src/binary0/main.rs"
+
+                [[bin]]
+                name = "binary1"
+                path = "src/binary1/main.rs"
+            "#;
+            let project = setup_project(DEFAULT_RUN_MANIFEST);
             let input = project.path().join("Cargo.toml");
             let manifest = crate::manifest(Some(&input)).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let licenses = Execution::for_test(&input).licenses(&package).unwrap();
-            let source = licenses.source.unwrap();
-            let source_path = source.stored_path;
-            let (template_out, source_template) = source.generate.unwrap();
-            let eula_path = licenses.end_user.unwrap().stored_path;
-
-            let expected_rel_path = format!("{WIX}\\{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}");
-            let expected_abs_path = Utf8Path::from_path(input.parent().unwrap())
-                .unwrap()
-                .join(WIX)
-                .join(format!("{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}"));
-            assert_eq!(source_template, Template::Gpl3);
-            assert_eq!(source_path.as_str(), expected_rel_path);
-            assert_eq!(template_out, expected_abs_path);
-            assert_eq!(eula_path.as_str(), expected_rel_path);
+            let execution = Builder::new()
+                .input(input.to_str())
+                .after_install_launch(true)
+                .build();
+            let binaries = execution.binaries(&package).unwrap();
+            let actual = execution.launch_binary_index(&package, &binaries).unwrap();
+            assert_eq!(actual, Some(String::from("1")));
         }
 
         #[test]
-        fn eula_with_unknown_license_field_works() {
-            let project = setup_project(UNKNOWN_MANIFEST);
+        fn launch_binary_index_with_unknown_binary_fails() {
+            let project = setup_project(MULTIPLE_BIN_MANIFEST);
             let input = project.path().join("Cargo.toml");
             let manifest = crate::manifest(Some(&input)).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let licenses = Execution::for_test(&input).licenses(&package).unwrap();
-            let source = licenses.source;
-            let eula = licenses.end_user;
+            let execution = Builder::new()
+                .input(input.to_str())
+                .after_install_launch(true)
+                .launch_binary(Some("Unknown"))
+                .build();
+            let binaries = execution.binaries(&package).unwrap();
+            assert!(execution.launch_binary_index(&package, &binaries).is_err());
+        }
 
-            assert_eq!(source, None);
-            assert_eq!(eula, None);
+        fn bitmap_header(dib_header_size: u32, bit_depth: u16) -> Vec<u8> {
+            let mut header = vec![0u8; BITMAP_HEADER_LENGTH];
+            header[0..2].copy_from_slice(b"BM");
+            header[14..18].copy_from_slice(&dib_header_size.to_le_bytes());
+            let offset = if dib_header_size == 12 { 24 } else { 28 };
+            header[offset..offset + 2].copy_from_slice(&bit_depth.to_le_bytes());
+            header
         }
 
         #[test]
-        #[cfg(windows)]
-        fn eula_with_override_works() {
-            let project = setup_project(MIT_MANIFEST);
-            let license_file_path = project.path().join("Example.rtf");
-            let _license_file_handle = File::create(&license_file_path).expect("Create file");
+        fn bitmap_bit_depth_works() {
+            assert_eq!(bitmap_bit_depth(&bitmap_header(40, 24)), Some(24));
+            assert_eq!(bitmap_bit_depth(&bitmap_header(124, 32)), Some(32));
+            assert_eq!(bitmap_bit_depth(&bitmap_header(12, 8)), Some(8));
+        }
+
+        #[test]
+        fn bitmap_bit_depth_without_bitmap_works() {
+            assert_eq!(bitmap_bit_depth(b"\x89PNG\r\n\x1a\n"), None);
+            assert_eq!(bitmap_bit_depth(&bitmap_header(40, 24)[..20]), None);
+        }
+
+        #[test]
+        fn culture_image_works() {
+            let images = culture_images(&[
+                ("fr", "img\\Banner_fr.bmp"),
+                ("de-DE", "C:\\img\\Banner_de.bmp"),
+                ("fr-CA", "img\\Banner_ca.bmp"),
+            ]);
+            let image = |culture| culture_image(&images, culture).unwrap().as_str();
+            assert_eq!(image("fr-CA"), "img\\Banner_ca.bmp");
+            assert_eq!(image("fr-FR"), "img\\Banner_fr.bmp");
+            assert_eq!(image("de-de"), "C:\\img\\Banner_de.bmp");
+            assert!(culture_image(&images, "en-US").is_none());
+        }
 
+        #[test]
+        fn banner_image_with_culture_banner_works() {
+            let project = setup_project(MIN_MANIFEST);
             let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let actual = Builder::default()
-                .eula(license_file_path.to_str())
-                .build()
-                .licenses(&package)
-                .unwrap()
-                .end_user
-                .unwrap()
-                .stored_path;
+            let mut builder = Builder::default();
+            builder
+                .banner(Some("img\\Banner.bmp"))
+                .culture_banner("fr-FR", "img\\Banner_fr.bmp");
+            let image = |culture| {
+                builder
+                    .clone()
+                    .culture(Some(culture))
+                    .build()
+                    .banner_image(&package)
+                    .unwrap()
+            };
+            assert_eq!(image("fr-FR").as_str(), "img\\Banner_fr.bmp");
+            assert_eq!(image("en-US").as_str(), "img\\Banner.bmp");
+        }
+
+        #[test]
+        fn split_binary_subdirectory_works() {
             assert_eq!(
-                actual,
-                StoredPathBuf::from_std_path(&license_file_path).unwrap(),
+                split_binary_subdirectory("bin\\Helper.exe"),
+                ("bin\\Helper.exe", None)
+            );
+            assert_eq!(
+                split_binary_subdirectory("bin\\Helper.exe:tools"),
+                ("bin\\Helper.exe", Some("tools"))
+            );
+            assert_eq!(
+                split_binary_subdirectory("C:\\bin\\Helper.exe"),
+                ("C:\\bin\\Helper.exe", None)
+            );
+            assert_eq!(
+                split_binary_subdirectory("C:\\bin\\Helper.exe:tools"),
+                ("C:\\bin\\Helper.exe", Some("tools"))
             );
         }
 
         #[test]
-        fn eula_with_license_file_field_works() {
-            let project = setup_project(LICENSE_FILE_RTF_MANIFEST);
-            let license_file_path = project.path().join("Example.rtf");
-            let _license_file_handle = File::create(license_file_path).expect("Create file");
+        fn manufacturer_with_defaults_works() {
+            const EXPECTED: &str = "First Last";
 
+            let project = setup_project(MIN_MANIFEST);
             let input = project.path().join("Cargo.toml");
             let manifest = crate::manifest(Some(&input)).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let actual = Execution::for_test(&input)
-                .licenses(&package)
-                .unwrap()
-                .end_user
-                .unwrap()
-                .stored_path;
-            assert_eq!(actual.as_str(), "Example.rtf");
+            let actual = Execution::for_test(&input).manufacturer(&package).unwrap();
+            assert_eq!(actual, String::from(EXPECTED));
         }
 
         #[test]
-        fn eula_with_license_file_extension_works() {
-            let project = setup_project(LICENSE_FILE_TXT_MANIFEST);
-            let license_file_path = project.path().join("Example.txt");
-            let _license_file_handle = File::create(license_file_path).expect("Create file");
+        fn manufacturer_with_override_works() {
+            const EXPECTED: &str = "Example";
 
-            let input = project.path().join("Cargo.toml");
-            let manifest = crate::manifest(Some(&input)).unwrap();
+            let project = setup_project(MIN_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let licenses = Execution::for_test(&input).licenses(&package).unwrap();
-            let source = licenses.source.unwrap();
-            let eula = licenses.end_user;
-
-            assert_eq!(source.generate, None);
-            assert_eq!(source.name, None);
-            assert_eq!(source.stored_path.as_str(), "Example.txt");
-            assert_eq!(eula, None);
+            let actual = Builder::default()
+                .manufacturer(Some(EXPECTED))
+                .build()
+                .manufacturer(&package)
+                .unwrap();
+            assert_eq!(actual, String::from(EXPECTED));
         }
 
         #[test]
-        fn eula_with_wrong_file_extension_override_works() {
-            let project = setup_project(LICENSE_FILE_TXT_MANIFEST);
-            let license_file_path = project.path().join("Example.txt");
-            let _license_file_handle = File::create(&license_file_path).expect("Create file");
-
+        fn manufacturer_from_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>", "Second Author"]
+            "#;
+            let project = setup_project(MANIFEST);
             let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            // We want to check that if the user hands us an OS-specific path here then we preserve it's format
-            // So we turn the input to a string without escaping.
-            let input = license_file_path.to_str().unwrap();
-            let expected = StoredPathBuf::new(input.to_owned());
-            let licenses = Builder::default()
-                .eula(Some(input))
-                .build()
-                .licenses(&package)
-                .unwrap();
-            let eula = licenses.end_user.unwrap();
-
-            assert_eq!(eula.stored_path, expected);
+            let manufacturer = |from: Option<&str>| {
+                Builder::default()
+                    .manufacturer_from(from)
+                    .build()
+                    .manufacturer(&package)
+            };
+            assert_eq!(
+                manufacturer(None).unwrap(),
+                String::from("First Last; Second Author")
+            );
+            assert_eq!(
+                manufacturer(Some("all")).unwrap(),
+                String::from("First Last; Second Author")
+            );
+            assert_eq!(
+                manufacturer(Some("first")).unwrap(),
+                String::from("First Last")
+            );
+            assert_eq!(
+                manufacturer(Some("1")).unwrap(),
+                String::from("Second Author")
+            );
+            assert!(manufacturer(Some("2")).is_err());
+            assert!(manufacturer(Some("last")).is_err());
         }
 
         #[test]
-        fn path_guid_with_override_works() {
-            let expected = Uuid::new_v4().as_hyphenated().to_string().to_uppercase();
+        fn manufacturer_from_metadata_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>", "Second Author"]
+
+                [package.metadata.wix]
+                manufacturer-from = "first"
+            "#;
+            let project = setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
 
-            let project = setup_project(MIN_MANIFEST);
+            let actual = Builder::default().build().manufacturer(&package).unwrap();
+            assert_eq!(actual, String::from("First Last"));
+        }
+
+        #[test]
+        fn manufacturer_with_author_formatting_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>", "Second Author"]
+            "#;
+            let project = setup_project(MANIFEST);
             let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
             let actual = Builder::default()
-                .path_guid(Some(&expected))
+                .author_separator(Some(", "))
+                .keep_author_emails(true)
                 .build()
-                .path_guid(&package)
+                .manufacturer(&package)
                 .unwrap();
-            assert_eq!(actual, expected);
+            assert_eq!(
+                actual,
+                String::from("First Last <first.last@example.com>, Second Author")
+            );
         }
 
         #[test]
-        fn path_guid_metadata_works() {
-            let project = setup_project(PATH_GUID_MANIFEST);
+        fn manufacturer_with_author_formatting_metadata_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>", "Second Author"]
+
+                [package.metadata.wix]
+                author-separator = " & "
+                keep-author-emails = true
+            "#;
+            let project = setup_project(MANIFEST);
             let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let actual = Builder::default().build().path_guid(&package).unwrap();
-            assert_eq!(actual, PATH_GUID);
+            let actual = Builder::default().build().manufacturer(&package).unwrap();
+            assert_eq!(
+                actual,
+                String::from("First Last <first.last@example.com> & Second Author")
+            );
         }
 
         #[test]
-        fn path_guid_metadata_and_override_works() {
-            let expected = Uuid::new_v4().as_hyphenated().to_string().to_uppercase();
-
-            let project = setup_project(PATH_GUID_MANIFEST);
+        fn help_url_with_defaults_works() {
+            let project = setup_project(MIN_MANIFEST);
             let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let actual = Builder::default()
-                .path_guid(Some(&expected))
-                .build()
-                .path_guid(&package)
-                .unwrap();
-            assert_eq!(actual, expected);
+            let actual = Builder::default().build().help_url(&package);
+            assert!(actual.is_none());
         }
 
         #[test]
-        fn upgrade_guid_with_override_works() {
-            let expected = Uuid::new_v4().as_hyphenated().to_string().to_uppercase();
+        fn help_url_with_documentation_works() {
+            const EXPECTED: &str = "http://www.example.com";
 
-            let project = setup_project(MIN_MANIFEST);
+            let project = setup_project(DOCUMENTATION_MANIFEST);
             let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let actual = Builder::default()
-                .upgrade_guid(Some(&expected))
-                .build()
-                .upgrade_guid(&package)
-                .unwrap();
-            assert_eq!(actual, expected);
+            let actual = Builder::default().build().help_url(&package);
+            assert_eq!(actual, Some(String::from(EXPECTED)));
         }
 
         #[test]
-        fn upgrade_guid_metadata_works() {
-            let project = setup_project(UPGRADE_GUID_MANIFEST);
+        fn help_url_with_homepage_works() {
+            const EXPECTED: &str = "http://www.example.com";
+
+            let project = setup_project(HOMEPAGE_MANIFEST);
             let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let actual = Builder::default().build().upgrade_guid(&package).unwrap();
-            assert_eq!(actual, UPGRADE_GUID);
+            let actual = Builder::default().build().help_url(&package);
+            assert_eq!(actual, Some(String::from(EXPECTED)));
         }
 
         #[test]
-        fn upgrade_guid_metadata_and_override_works() {
-            let expected = Uuid::new_v4().as_hyphenated().to_string().to_uppercase();
+        fn help_url_with_repository_works() {
+            const EXPECTED: &str = "http://www.example.com";
 
-            let project = setup_project(UPGRADE_GUID_MANIFEST);
+            let project = setup_project(REPOSITORY_MANIFEST);
             let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let actual = Builder::default()
-                .upgrade_guid(Some(&expected))
-                .build()
-                .upgrade_guid(&package)
-                .unwrap();
-            assert_eq!(actual, expected);
+            let actual = Builder::default().build().help_url(&package);
+            assert_eq!(actual, Some(String::from(EXPECTED)));
         }
 
         #[test]
-        fn image_metadata_works() {
-            let project = setup_project(IMAGES_MANIFEST);
-            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+        fn eula_with_defaults_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
             let package = crate::package(&manifest, None).unwrap();
 
-            let actual = Builder::default().build();
+            let actual = Execution::for_test(&input)
+                .licenses(&package)
+                .unwrap()
+                .end_user;
+            assert!(actual.is_none());
+        }
+
+        #[test]
+        fn license_report_with_mit_license_field_works() {
+            let project = setup_project(MIT_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let licenses = Execution::for_test(&input).licenses(&package).unwrap();
+            let actual = license_report(&licenses, Some("http://www.example.com"));
+            let expected_path = format!("{WIX}\\{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}");
             assert_eq!(
-                actual.product_icon(&package).unwrap().as_str(),
-                "wix/product.ico"
+                actual,
+                vec![
+                    format!(
+                        "EULA for the license agreement dialog: '{expected_path}', generated \
+                         from the MIT template"
+                    ),
+                    format!(
+                        "License sidecar file: '{expected_path}', generated from the MIT template"
+                    ),
+                    String::from("Help URL: http://www.example.com"),
+                ]
             );
+        }
+
+        #[test]
+        fn license_report_without_licenses_works() {
+            let licenses = Licenses {
+                source: None,
+                end_user: None,
+            };
             assert_eq!(
-                actual.dialog_image(&package).unwrap().as_str(),
-                "wix/dialog.png"
+                license_report(&licenses, None),
+                vec![
+                    "EULA for the license agreement dialog: none",
+                    "License sidecar file: none",
+                    "Help URL: none",
+                ]
             );
-            assert_eq!(
-                actual.banner_image(&package).unwrap().as_str(),
-                "wix/banner.png"
+        }
+
+        #[test]
+        fn eula_with_mit_license_field_works() {
+            let project = setup_project(MIT_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let licenses = Execution::for_test(&input).licenses(&package).unwrap();
+            let source = licenses.source.unwrap();
+            let source_path = source.stored_path;
+            let (template_out, source_template) = source.generate.unwrap();
+            let eula_path = licenses.end_user.unwrap().stored_path;
+
+            let expected_rel_path = format!("{WIX}\\{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}");
+            let expected_abs_path = Utf8Path::from_path(input.parent().unwrap())
+                .unwrap()
+                .join(WIX)
+                .join(format!("{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}"));
+            assert_eq!(source_template, Template::Mit);
+            assert_eq!(source_path.as_str(), expected_rel_path);
+            assert_eq!(template_out, expected_abs_path);
+            assert_eq!(eula_path.as_str(), expected_rel_path);
+        }
+
+        #[test]
+        fn eula_with_apache2_license_field_works() {
+            let project = setup_project(APACHE2_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let licenses = Execution::for_test(&input).licenses(&package).unwrap();
+            let source = licenses.source.unwrap();
+            let source_path = source.stored_path;
+            let (template_out, source_template) = source.generate.unwrap();
+            let eula_path = licenses.end_user.unwrap().stored_path;
+
+            let expected_rel_path = format!("{WIX}\\{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}");
+            let expected_abs_path = Utf8Path::from_path(input.parent().unwrap())
+                .unwrap()
+                .join(WIX)
+                .join(format!("{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}"));
+            assert_eq!(source_template, Template::Apache2);
+            assert_eq!(source_path.as_str(), expected_rel_path);
+            assert_eq!(template_out, expected_abs_path);
+            assert_eq!(eula_path.as_str(), expected_rel_path);
+        }
+
+        #[test]
+        fn eula_with_gpl3_license_field_works() {
+            let project = setup_project(GPL3_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let licenses = Execution::for_test(&input).licenses(&package).unwrap();
+            let source = licenses.source.unwrap();
+            let source_path = source.stored_path;
+            let (template_out, source_template) = source.generate.unwrap();
+            let eula_path = licenses.end_user.unwrap().stored_path;
+
+            let expected_rel_path = format!("{WIX}\\{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}");
+            let expected_abs_path = Utf8Path::from_path(input.parent().unwrap())
+                .unwrap()
+                .join(WIX)
+                .join(format!("{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}"));
+            assert_eq!(source_template, Template::Gpl3);
+            assert_eq!(source_path.as_str(), expected_rel_path);
+            assert_eq!(template_out, expected_abs_path);
+            assert_eq!(eula_path.as_str(), expected_rel_path);
+        }
+
+        #[test]
+        fn eula_with_dual_license_field_works() {
+            let project = setup_project(DUAL_LICENSE_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let licenses = Execution::for_test(&input).licenses(&package).unwrap();
+            let (_, source_template) = licenses.source.unwrap().generate.unwrap();
+            let eula_path = licenses.end_user.unwrap().stored_path;
+            assert_eq!(source_template, Template::Mit);
+            assert_eq!(
+                eula_path.as_str(),
+                format!("{WIX}\\{LICENSE_FILE_NAME}.{RTF_FILE_EXTENSION}")
+            );
+        }
+
+        #[test]
+        fn eula_with_exception_license_field_works() {
+            let project = setup_project(EXCEPTION_LICENSE_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let licenses = Execution::for_test(&input).licenses(&package).unwrap();
+            let (_, source_template) = licenses.source.unwrap().generate.unwrap();
+            assert_eq!(source_template, Template::Gpl3);
+        }
+
+        #[test]
+        fn eula_with_unknown_expression_license_field_works() {
+            let project = setup_project(UNKNOWN_EXPRESSION_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let licenses = Execution::for_test(&input).licenses(&package).unwrap();
+            assert!(licenses.source.is_none());
+            assert!(licenses.end_user.is_none());
+        }
+
+        #[test]
+        fn eula_with_unknown_license_field_works() {
+            let project = setup_project(UNKNOWN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let licenses = Execution::for_test(&input).licenses(&package).unwrap();
+            let source = licenses.source;
+            let eula = licenses.end_user;
+
+            assert_eq!(source, None);
+            assert_eq!(eula, None);
+        }
+
+        #[test]
+        #[cfg(windows)]
+        fn eula_with_override_works() {
+            let project = setup_project(MIT_MANIFEST);
+            let license_file_path = project.path().join("Example.rtf");
+            let _license_file_handle = File::create(&license_file_path).expect("Create file");
+
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default()
+                .eula(license_file_path.to_str())
+                .build()
+                .licenses(&package)
+                .unwrap()
+                .end_user
+                .unwrap()
+                .stored_path;
+            assert_eq!(
+                actual,
+                StoredPathBuf::from_std_path(&license_file_path).unwrap(),
+            );
+        }
+
+        #[test]
+        fn eula_with_license_file_field_works() {
+            let project = setup_project(LICENSE_FILE_RTF_MANIFEST);
+            let license_file_path = project.path().join("Example.rtf");
+            let _license_file_handle = File::create(license_file_path).expect("Create file");
+
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Execution::for_test(&input)
+                .licenses(&package)
+                .unwrap()
+                .end_user
+                .unwrap()
+                .stored_path;
+            assert_eq!(actual.as_str(), "Example.rtf");
+        }
+
+        #[test]
+        fn eula_with_license_file_extension_works() {
+            let project = setup_project(LICENSE_FILE_TXT_MANIFEST);
+            let license_file_path = project.path().join("Example.txt");
+            let _license_file_handle = File::create(license_file_path).expect("Create file");
+
+            let input = project.path().join("Cargo.toml");
+            let manifest = crate::manifest(Some(&input)).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let licenses = Execution::for_test(&input).licenses(&package).unwrap();
+            let source = licenses.source.unwrap();
+            let eula = licenses.end_user;
+
+            assert_eq!(source.generate, None);
+            assert_eq!(source.name, None);
+            assert_eq!(source.stored_path.as_str(), "Example.txt");
+            assert_eq!(eula, None);
+        }
+
+        #[test]
+        fn eula_with_wrong_file_extension_override_works() {
+            let project = setup_project(LICENSE_FILE_TXT_MANIFEST);
+            let license_file_path = project.path().join("Example.txt");
+            let _license_file_handle = File::create(&license_file_path).expect("Create file");
+
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            // We want to check that if the user hands us an OS-specific path here then we preserve it's format
+            // So we turn the input to a string without escaping.
+            let input = license_file_path.to_str().unwrap();
+            let expected = StoredPathBuf::new(input.to_owned());
+            let licenses = Builder::default()
+                .eula(Some(input))
+                .build()
+                .licenses(&package)
+                .unwrap();
+            let eula = licenses.end_user.unwrap();
+
+            assert_eq!(eula.stored_path, expected);
+        }
+
+        #[test]
+        fn path_guid_with_override_works() {
+            let expected = Uuid::new_v4().as_hyphenated().to_string().to_uppercase();
+
+            let project = setup_project(MIN_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default()
+                .path_guid(Some(&expected))
+                .build()
+                .path_guid(&package, None, &mut ComponentGuids::default())
+                .unwrap();
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn path_guid_metadata_works() {
+            let project = setup_project(PATH_GUID_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default()
+                .build()
+                .path_guid(&package, None, &mut ComponentGuids::default())
+                .unwrap();
+            assert_eq!(actual, PATH_GUID);
+        }
+
+        #[test]
+        fn path_guid_metadata_and_override_works() {
+            let expected = Uuid::new_v4().as_hyphenated().to_string().to_uppercase();
+
+            let project = setup_project(PATH_GUID_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default()
+                .path_guid(Some(&expected))
+                .build()
+                .path_guid(&package, None, &mut ComponentGuids::default())
+                .unwrap();
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn path_guid_with_lowercase_override_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default()
+                .path_guid(Some(&PATH_GUID.to_lowercase()))
+                .build()
+                .path_guid(&package, None, &mut ComponentGuids::default())
+                .unwrap();
+            assert_eq!(actual, PATH_GUID);
+        }
+
+        #[test]
+        fn path_guid_with_invalid_override_fails() {
+            let project = setup_project(MIN_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let err = Builder::default()
+                .path_guid(Some("Not a GUID"))
+                .build()
+                .path_guid(&package, None, &mut ComponentGuids::default())
+                .unwrap_err();
+            assert_eq!(err.code(), 6);
+        }
+
+        #[test]
+        fn upgrade_guid_with_invalid_metadata_fails() {
+            let project = setup_project(
+                r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                upgrade-guid = "B36177BE-EA4D-44FB-B05C"
+                "#,
+            );
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let err = Builder::default()
+                .build()
+                .upgrade_guid(&package)
+                .unwrap_err();
+            assert_eq!(err.code(), 6);
+        }
+
+        #[test]
+        fn upgrade_guid_with_override_works() {
+            let expected = Uuid::new_v4().as_hyphenated().to_string().to_uppercase();
+
+            let project = setup_project(MIN_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default()
+                .upgrade_guid(Some(&expected))
+                .build()
+                .upgrade_guid(&package)
+                .unwrap();
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn upgrade_guid_metadata_works() {
+            let project = setup_project(UPGRADE_GUID_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default().build().upgrade_guid(&package).unwrap();
+            assert_eq!(actual, UPGRADE_GUID);
+        }
+
+        #[test]
+        fn upgrade_guid_metadata_and_override_works() {
+            let expected = Uuid::new_v4().as_hyphenated().to_string().to_uppercase();
+
+            let project = setup_project(UPGRADE_GUID_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default()
+                .upgrade_guid(Some(&expected))
+                .build()
+                .upgrade_guid(&package)
+                .unwrap();
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn image_metadata_works() {
+            let project = setup_project(IMAGES_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default().build();
+            assert_eq!(
+                actual.product_icon(&package).unwrap().as_str(),
+                "wix/product.ico"
+            );
+            assert_eq!(
+                actual.dialog_image(&package).unwrap().as_str(),
+                "wix/dialog.png"
+            );
+            assert_eq!(
+                actual.banner_image(&package).unwrap().as_str(),
+                "wix/banner.png"
+            );
+        }
+
+        #[test]
+        fn image_wix_folder_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let wix = project.path().join(WIX);
+            fs::create_dir(&wix).unwrap();
+            for file_name in ["banner.bmp", "Dialog.bmp", "product.ico"] {
+                fs::write(wix.join(file_name), []).unwrap();
+            }
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default().build();
+            assert_eq!(
+                actual.product_icon(&package).unwrap().as_str(),
+                "wix\\product.ico"
+            );
+            assert_eq!(
+                actual.dialog_image(&package).unwrap().as_str(),
+                "wix\\Dialog.bmp"
+            );
+            assert_eq!(
+                actual.banner_image(&package).unwrap().as_str(),
+                "wix\\banner.bmp"
+            );
+        }
+
+        #[test]
+        fn image_metadata_and_wix_folder_works() {
+            let project = setup_project(IMAGES_MANIFEST);
+            let wix = project.path().join(WIX);
+            fs::create_dir(&wix).unwrap();
+            fs::write(wix.join("banner.bmp"), []).unwrap();
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default().banner(Some("img/Banner.bmp")).build();
+            assert_eq!(
+                actual.banner_image(&package).unwrap().as_str(),
+                "img/Banner.bmp"
+            );
+            assert_eq!(
+                actual.dialog_image(&package).unwrap().as_str(),
+                "wix/dialog.png"
+            );
+        }
+
+        #[test]
+        fn image_without_wix_folder_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default().build();
+            assert!(actual.product_icon(&package).is_none());
+            assert!(actual.dialog_image(&package).is_none());
+            assert!(actual.banner_image(&package).is_none());
+        }
+
+        #[test]
+        fn product_icon_from_binary_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default()
+                .product_icon(Some("from-binary"))
+                .build()
+                .product_icon(&package)
+                .unwrap();
+            assert_eq!(actual.as_str(), "$(var.ProductIcon)");
+        }
+
+        #[test]
+        fn product_icon_from_binary_metadata_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                product-icon = "From-Binary"
+            "#;
+            let project = setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default().build().product_icon(&package).unwrap();
+            assert_eq!(actual.as_str(), "$(var.ProductIcon)");
+        }
+
+        #[test]
+        fn install_scope_defaults_to_per_machine() {
+            let project = setup_project(MIN_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default()
+                .build()
+                .install_scope(&package, false)
+                .unwrap();
+            assert_eq!(actual, InstallScope::PerMachine);
+        }
+
+        #[test]
+        fn install_scope_auto_with_path_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default()
+                .install_scope(Some("auto"))
+                .build()
+                .install_scope(&package, false)
+                .unwrap();
+            assert_eq!(actual, InstallScope::PerMachine);
+        }
+
+        #[test]
+        fn install_scope_auto_without_path_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default()
+                .install_scope(Some("auto"))
+                .build()
+                .install_scope(&package, true)
+                .unwrap();
+            assert_eq!(actual, InstallScope::PerUser);
+        }
+
+        #[test]
+        fn install_scope_unknown_fails() {
+            let project = setup_project(MIN_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default()
+                .install_scope(Some("everyone"))
+                .build()
+                .install_scope(&package, false);
+            assert!(actual.is_err());
+        }
+
+        #[test]
+        fn install_scope_auto_and_no_path_metadata_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                install-scope = "auto"
+                no-path = true
+            "#;
+            let project = setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let execution = Builder::default().build();
+            let no_path = execution.no_path(&package);
+            assert!(no_path);
+            assert_eq!(
+                execution.install_scope(&package, no_path).unwrap(),
+                InstallScope::PerUser
+            );
+        }
+
+        #[test]
+        fn dialog_set_metadata_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                dialog-set = "mondo"
+            "#;
+            let project = setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let mut builder = Builder::default();
+            assert_eq!(
+                builder.build().dialog_set(&package).unwrap(),
+                DialogSet::Mondo
+            );
+            assert_eq!(
+                builder
+                    .dialog_set(Some("install-dir"))
+                    .build()
+                    .dialog_set(&package)
+                    .unwrap(),
+                DialogSet::InstallDir
+            );
+            assert!(builder
+                .dialog_set(Some("minimal"))
+                .build()
+                .dialog_set(&package)
+                .is_err());
+        }
+
+        #[test]
+        fn render_with_default_dialog_set_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+
+            let renders = Builder::default()
+                .input(input.to_str())
+                .build()
+                .render()
+                .unwrap();
+            assert!(renders
+                .wxs
+                .rendered
+                .contains("<UIRef Id='WixUI_FeatureTree'/>"));
+            assert!(renders.wxs.rendered.contains(
+                "<Publish Dialog='WelcomeDlg' Control='Next' Event='NewDialog' \
+                 Value='CustomizeDlg' Order='99'>1</Publish>"
+            ));
+            assert!(!renders.wxs.rendered.contains("WIXUI_INSTALLDIR"));
+        }
+
+        #[test]
+        fn render_with_install_dir_dialog_set_without_eula_skips_license() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+
+            let renders = Builder::default()
+                .input(input.to_str())
+                .dialog_set(Some("install-dir"))
+                .build()
+                .render()
+                .unwrap();
+            assert!(renders
+                .wxs
+                .rendered
+                .contains("<UIRef Id='WixUI_InstallDir'/>"));
+            assert!(renders
+                .wxs
+                .rendered
+                .contains("<Property Id='WIXUI_INSTALLDIR' Value='APPLICATIONFOLDER'/>"));
+            assert!(renders.wxs.rendered.contains(
+                "            <Publish Dialog='InstallDirDlg' Control='Back' Event='NewDialog' \
+                 Value='WelcomeDlg' Order='99'>1</Publish>"
+            ));
+        }
+
+        #[test]
+        fn render_with_mondo_dialog_set_and_eula_shows_license() {
+            let project = setup_project(MIT_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+
+            let renders = Builder::default()
+                .input(input.to_str())
+                .dialog_set(Some("mondo"))
+                .build()
+                .render()
+                .unwrap();
+            assert!(renders.wxs.rendered.contains("<UIRef Id='WixUI_Mondo'/>"));
+            assert!(renders.wxs.rendered.contains(
+                "<!--<Publish Dialog='WelcomeDlg' Control='Next' Event='NewDialog' \
+                 Value='SetupTypeDlg' Order='99'>1</Publish>-->"
+            ));
+            assert!(!renders.wxs.rendered.contains(
+                "            <Publish Dialog='WelcomeDlg' Control='Next' Event='NewDialog'"
+            ));
+        }
+
+        #[test]
+        fn render_with_install_scope_auto_and_no_path_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+
+            let renders = Builder::default()
+                .input(input.to_str())
+                .install_scope(Some("auto"))
+                .no_path(true)
+                .build()
+                .render()
+                .unwrap();
+            assert!(renders.wxs.rendered.contains("InstallScope='perUser'"));
+            assert!(!renders.wxs.rendered.contains("<ComponentRef Id='Path'/>"));
+        }
+
+        #[test]
+        fn render_with_per_user_install_scope_modifies_user_path() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+
+            let renders = Builder::default()
+                .input(input.to_str())
+                .install_scope(Some("per-user"))
+                .build()
+                .render()
+                .unwrap();
+            assert!(renders.wxs.rendered.contains("InstallScope='perUser'"));
+            assert!(renders.wxs.rendered.contains("System='no'"));
+            assert!(!renders.wxs.rendered.contains("System='yes'"));
+        }
+
+        #[test]
+        fn render_with_per_user_install_scope_installs_to_user_programs_folder() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+
+            let renders = Builder::default()
+                .input(input.to_str())
+                .install_scope(Some("per-user"))
+                .build()
+                .render()
+                .unwrap();
+            assert!(renders
+                .wxs
+                .rendered
+                .contains("<Property Id='MSIINSTALLPERUSER' Value='1'/>"));
+            assert!(renders
+                .wxs
+                .rendered
+                .contains("<Property Id='ALLUSERS' Value='2'/>"));
+        }
+
+        #[test]
+        fn render_with_per_machine_install_scope_installs_to_program_files_folder() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+
+            let renders = Builder::default()
+                .input(input.to_str())
+                .install_scope(Some("per-machine"))
+                .build()
+                .render()
+                .unwrap();
+            assert!(!renders.wxs.rendered.contains("MSIINSTALLPERUSER"));
+            assert!(!renders.wxs.rendered.contains("ALLUSERS"));
+        }
+
+        const FEATURES_MANIFEST: &str = r#"[package]
+            name = "Example"
+            version = "0.1.0"
+            authors = ["First Last <first.last@example.com>"]
+
+            [features]
+            gui = []
+
+            [[bin]]
+            name = "Example"
+            path = "src/main.rs"
+
+            [[bin]]
+            name = "example-gui"
+            path = "src/main.rs"
+            required-features = ["gui"]
+        "#;
+
+        #[test]
+        fn feature_maps_metadata_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [features]
+                gui = []
+
+                [package.metadata.wix]
+                feature-map = ["gui = Graphical Interface"]
+            "#;
+            let project = setup_project(MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default().build().feature_maps(&package).unwrap();
+            assert_eq!(
+                actual,
+                vec![(String::from("gui"), String::from("Graphical Interface"))]
+            );
+        }
+
+        #[test]
+        fn feature_maps_with_unknown_feature_fails() {
+            let project = setup_project(FEATURES_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default()
+                .feature_maps(Some(vec!["cli=Command Line Tools"]))
+                .build()
+                .feature_maps(&package);
+            assert!(actual.is_err());
+        }
+
+        #[test]
+        fn feature_maps_without_title_fails() {
+            let project = setup_project(FEATURES_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+
+            let actual = Builder::default()
+                .feature_maps(Some(vec!["gui"]))
+                .build()
+                .feature_maps(&package);
+            assert!(actual.is_err());
+        }
+
+        #[test]
+        fn render_with_feature_maps_works() {
+            let project = setup_project(FEATURES_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+
+            let renders = Builder::default()
+                .input(input.to_str())
+                .feature_maps(Some(vec!["gui=Graphical Interface"]))
+                .build()
+                .render()
+                .unwrap();
+            let rendered = renders.wxs.rendered;
+            assert!(rendered.contains("Id='CargoFeature_gui'"));
+            assert!(rendered.contains("Title='Graphical Interface'"));
+            assert_eq!(rendered.matches("<?ifdef CargoFeature_gui ?>").count(), 2);
+            assert_eq!(rendered.matches("<ComponentRef Id='binary0'/>").count(), 1);
+            assert_eq!(rendered.matches("<ComponentRef Id='binary1'/>").count(), 1);
+            assert!(
+                rendered.find("<ComponentRef Id='binary1'/>").unwrap()
+                    > rendered.find("Id='CargoFeature_gui'").unwrap()
+            );
+        }
+
+        #[test]
+        fn render_without_no_modify_and_no_repair_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            assert!(!rendered.contains("ARPNOMODIFY"));
+            assert!(!rendered.contains("ARPNOREPAIR"));
+        }
+
+        #[test]
+        fn render_with_no_modify_and_no_repair_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .no_modify(true)
+                .no_repair(true)
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            assert!(rendered.contains("<Property Id='ARPNOMODIFY' Value='1'/>"));
+            assert!(rendered.contains("<Property Id='ARPNOREPAIR' Value='1'/>"));
+        }
+
+        #[test]
+        fn no_modify_and_no_repair_metadata_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                no-modify = true
+                no-repair = true
+            "#;
+            let project = setup_project(MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            assert!(rendered.contains("<Property Id='ARPNOMODIFY' Value='1'/>"));
+            assert!(rendered.contains("<Property Id='ARPNOREPAIR' Value='1'/>"));
+        }
+
+        #[test]
+        fn render_without_no_upgrade_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            assert!(rendered.contains("<MajorUpgrade"));
+        }
+
+        #[test]
+        fn render_with_no_upgrade_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .no_upgrade(true)
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            assert!(!rendered.contains("<MajorUpgrade"));
+            assert!(rendered.contains("UpgradeCode="));
+        }
+
+        #[test]
+        fn no_upgrade_metadata_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                no-upgrade = true
+            "#;
+            let project = setup_project(MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            assert!(!rendered.contains("<MajorUpgrade"));
+        }
+
+        #[test]
+        fn render_without_uninstall_shortcut_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            assert!(!rendered.contains("ProgramMenuFolder"));
+            assert!(!rendered.contains("<ComponentRef Id='UninstallShortcut'/>"));
+        }
+
+        #[test]
+        fn render_with_uninstall_shortcut_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .uninstall_shortcut(true)
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            assert!(rendered.contains("<Directory Id='ProgramMenuFolder'>"));
+            assert!(rendered.contains("<Component Id='UninstallShortcut' Guid='*'>"));
+            assert!(rendered.contains("Target='[SystemFolder]msiexec.exe'"));
+            assert!(rendered.contains("Arguments='/x [ProductCode]'"));
+            assert!(rendered.contains("<ComponentRef Id='UninstallShortcut'/>"));
+        }
+
+        #[test]
+        fn render_without_run_after_install_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            assert!(!rendered.contains("RunAfterInstall"));
+        }
+
+        #[test]
+        fn render_with_run_after_install_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .run_after_install(Some("\"[APPLICATIONFOLDER]bin\\setup.exe\" --register"))
+                .schema_validate(true)
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            assert!(rendered
+                .contains("ExeCommand='&quot;[APPLICATIONFOLDER]bin\\setup.exe&quot; --register'"));
+            assert!(rendered.contains("Execute='deferred' Impersonate='no'"));
+            assert!(rendered.contains(
+                "<Custom Action='RunAfterInstall' Before='InstallFinalize'>NOT Installed</Custom>"
+            ));
+        }
+
+        #[test]
+        fn render_with_run_after_install_per_user_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .install_scope(Some("per-user"))
+                .run_after_install(Some("setup.exe"))
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            assert!(
+                rendered.contains("ExeCommand='setup.exe' Execute='deferred' Impersonate='yes'")
+            );
+        }
+
+        #[test]
+        fn run_after_install_metadata_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                run-after-install = "setup.exe --register"
+            "#;
+            let project = setup_project(MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            assert!(rendered.contains("ExeCommand='setup.exe --register'"));
+        }
+
+        #[test]
+        fn uninstall_shortcut_metadata_works() {
+            const MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                deterministic-component-guids = true
+                uninstall-shortcut = true
+            "#;
+            let project = setup_project(MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            assert!(rendered.contains(
+                "<Component Id='UninstallShortcut' Guid='$(var.UninstallShortcutComponentGuid)'>"
+            ));
+            assert!(rendered.contains(&format!(
+                "<?define UninstallShortcutComponentGuid = \"{}\" ?>",
+                component_guid("ProgramFiles64Folder\\Example\\UninstallShortcut")
+            )));
+        }
+
+        #[test]
+        fn render_without_deterministic_component_guids_works() {
+            let project = setup_project(MIT_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            assert!(rendered.contains("<Component Id='License' Guid='*'>"));
+            assert!(rendered.contains("<Component Id='binary0' Guid='*'>"));
+        }
+
+        #[test]
+        fn render_with_deterministic_component_guids_works() {
+            let project = setup_project(MIT_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let render = || {
+                Builder::default()
+                    .input(input.to_str())
+                    .deterministic_component_guids(true)
+                    .upgrade_guid(Some("B36177BE-EA4D-44FB-B05C-EDDABDAA95CA"))
+                    .build()
+                    .render()
+                    .unwrap()
+                    .wxs
+                    .rendered
+            };
+            let rendered = render();
+            assert_eq!(rendered, render());
+            assert!(!rendered.contains("Guid='*'"));
+            assert!(
+                rendered.contains("<Component Id='binary0' Guid='$(var.binary0ComponentGuid)'>")
+            );
+            assert!(
+                rendered.contains("<Component Id='License' Guid='$(var.LicenseComponentGuid)'>")
+            );
+            assert!(rendered.contains(&format!(
+                "<?define binary0ComponentGuid = \"{}\" ?>",
+                component_guid("ProgramFiles64Folder\\Example\\bin\\Example.exe")
+            )));
+            assert!(rendered.contains(&format!(
+                "<?define LicenseComponentGuid = \"{}\" ?>",
+                component_guid("ProgramFiles64Folder\\Example\\License.rtf")
+            )));
+        }
+
+        #[test]
+        fn render_with_deterministic_component_guids_differs_by_platform() {
+            let project = setup_project(MIT_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .deterministic_component_guids(true)
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            let x86 = component_guid("ProgramFilesFolder\\Example\\bin\\Example.exe");
+            let x64 = component_guid("ProgramFiles64Folder\\Example\\bin\\Example.exe");
+            assert_ne!(x86, x64);
+            assert!(rendered.contains(&format!("<?define binary0ComponentGuid = \"{x86}\" ?>")));
+            assert!(rendered.contains(&format!("<?define binary0ComponentGuid = \"{x64}\" ?>")));
+        }
+
+        #[test]
+        fn render_with_dump_context_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let context = project.path().join("context.json");
+            Builder::default()
+                .input(input.to_str())
+                .dump_context(context.to_str())
+                .upgrade_guid(Some("B36177BE-EA4D-44FB-B05C-EDDABDAA95CA"))
+                .build()
+                .render()
+                .unwrap();
+            let actual: serde_json::Value =
+                serde_json::from_str(&std::fs::read_to_string(&context).unwrap()).unwrap();
+            assert_eq!(actual["product-name"], "Example");
+            assert_eq!(
+                actual["upgrade-code-guid"],
+                "B36177BE-EA4D-44FB-B05C-EDDABDAA95CA"
+            );
+            assert_eq!(actual["binaries"][0]["binary-name"], "Example");
+        }
+
+        #[test]
+        fn render_with_wxs_template_dir_metadata_works() {
+            const TEMPLATE_DIR_MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+                license = "MIT"
+
+                [package.metadata.wix]
+                wxs-template-dir = "templates"
+            "#;
+            let project = setup_project(TEMPLATE_DIR_MANIFEST);
+            let templates = project.path().join("templates");
+            std::fs::create_dir(&templates).unwrap();
+            std::fs::write(
+                templates.join("main.wxs.mustache"),
+                "<Custom Name='{{product-name}}'/>",
+            )
+            .unwrap();
+            let renders = Execution::for_test(&project.path().join("Cargo.toml"))
+                .render()
+                .unwrap();
+            assert_eq!(renders.wxs.rendered, "<Custom Name='Example'/>");
+            assert!(renders
+                .license
+                .unwrap()
+                .rendered
+                .contains("\\lang9 Copyright (c)"));
+        }
+
+        #[test]
+        fn render_with_wxs_template_dir_that_does_not_exist_fails() {
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let templates = project.path().join("templates");
+            assert!(Builder::default()
+                .input(input.to_str())
+                .wxs_template_dir(templates.to_str())
+                .build()
+                .render()
+                .is_err());
+        }
+
+        #[test]
+        fn render_with_deterministic_component_guids_and_path_guid_works() {
+            const EXPECTED: &str = "C8B3C4B1-3A3C-4A35-8A70-5BD5D8B0E2A1";
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .deterministic_component_guids(true)
+                .path_guid(Some(EXPECTED))
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            assert!(rendered.contains(&format!("<Component Id='Path' Guid='{EXPECTED}'")));
+        }
+
+        #[test]
+        fn render_with_guid_from_works() {
+            const SEED: &str = "com.example.product";
+            let project = setup_project(MIN_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let render = || {
+                Builder::default()
+                    .input(input.to_str())
+                    .guid_from(Some(SEED))
+                    .build()
+                    .render()
+                    .unwrap()
+                    .wxs
+                    .rendered
+            };
+            let rendered = render();
+            assert_eq!(rendered, render());
+            assert!(rendered.contains(&format!(
+                "UpgradeCode='{}'",
+                seeded_guid(SEED, UPGRADE_GUID_NAME)
+            )));
+            assert!(rendered.contains(&format!(
+                "<Component Id='Path' Guid='{}'",
+                seeded_guid(SEED, PATH_GUID_NAME)
+            )));
+        }
+
+        #[test]
+        fn render_with_guid_from_metadata_and_upgrade_guid_works() {
+            const UPGRADE_GUID: &str = "B36177BE-EA4D-44FB-B05C-EDDABDAA95CA";
+            const GUID_FROM_MANIFEST: &str = r#"[package]
+                name = "Example"
+                version = "0.1.0"
+                authors = ["First Last <first.last@example.com>"]
+
+                [package.metadata.wix]
+                guid-from = "com.example.product"
+            "#;
+            let project = setup_project(GUID_FROM_MANIFEST);
+            let input = project.path().join("Cargo.toml");
+            let rendered = Builder::default()
+                .input(input.to_str())
+                .upgrade_guid(Some(UPGRADE_GUID))
+                .build()
+                .render()
+                .unwrap()
+                .wxs
+                .rendered;
+            assert!(rendered.contains(&format!("UpgradeCode='{UPGRADE_GUID}'")));
+            assert!(rendered.contains(&format!(
+                "<Component Id='Path' Guid='{}'",
+                seeded_guid("com.example.product", PATH_GUID_NAME)
+            )));
+        }
+
+        #[test]
+        fn seeded_guid_works() {
+            assert_eq!(
+                seeded_guid("com.example.product", UPGRADE_GUID_NAME),
+                seeded_guid("com.example.product", UPGRADE_GUID_NAME)
+            );
+            assert_ne!(
+                seeded_guid("com.example.product", UPGRADE_GUID_NAME),
+                seeded_guid("com.example.product", PATH_GUID_NAME)
+            );
+            assert_ne!(
+                seeded_guid("com.example.product", UPGRADE_GUID_NAME),
+                seeded_guid("com.example.other", UPGRADE_GUID_NAME)
+            );
+        }
+
+        #[test]
+        fn component_guid_is_case_insensitive() {
+            assert_eq!(
+                component_guid("Example\\bin\\Example.exe"),
+                component_guid("example\\BIN\\example.EXE")
+            );
+            assert_ne!(
+                component_guid("Example\\bin\\Example.exe"),
+                component_guid("Example\\bin\\Other.exe")
             );
         }
     }
@@ -15,6 +15,10 @@
 //! The implementation for the `sign` command. This command focuses on signing
 //! installers using the Windows SDK `signtool` application.
 
+use crate::command_line;
+use crate::create::{CreateStage, Progress};
+use crate::status_with_stderr;
+use crate::status_with_timeout;
 use crate::Error;
 use crate::Result;
 use crate::TimestampServer;
@@ -24,30 +28,60 @@ use crate::MSI_FILE_EXTENSION;
 use crate::SIGNTOOL;
 use crate::SIGNTOOL_PATH_KEY;
 use crate::WIX;
+use crate::WIX_INSIGNIA;
+use crate::WIX_PATH_KEY;
 
-use log::{debug, info, trace};
+use log::{debug, info, trace, warn};
 
 use std::env;
-use std::ffi::OsStr;
+use std::ffi::{OsStr, OsString};
 use std::fs;
-use std::io::ErrorKind;
+use std::io::{self, ErrorKind, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::str::FromStr;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
 use cargo_metadata::Package;
 
+/// The default number of times signing is retried if the file is in use.
+const DEFAULT_SIGN_RETRIES: u32 = 3;
+
+/// The delay before signing is retried if the file is in use.
+const SIGN_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// The messages, and error codes, in the output of the signer (signtool.exe)
+/// for a file that is in use by another process.
+///
+/// The codes are the `HRESULT` values for the `ERROR_SHARING_VIOLATION` and
+/// `ERROR_LOCK_VIOLATION` Win32 errors, which are displayed regardless of the
+/// language of the system.
+const FILE_IN_USE_MESSAGES: [&str; 4] = [
+    "0x80070020",
+    "0x80070021",
+    "being used by another process",
+    "another process has locked a portion of the file",
+];
+
 /// A builder for creating an execution context to sign an installer.
 #[derive(Debug, Clone)]
 pub struct Builder<'a> {
     bin_path: Option<&'a str>,
     capture_output: bool,
+    cert_subject: Option<&'a str>,
     description: Option<&'a str>,
+    dump_toolset_commands: bool,
     homepage: Option<&'a str>,
     input: Option<&'a str>,
     installer: Option<&'a str>,
     package: Option<&'a str>,
     product_name: Option<&'a str>,
+    progress: Option<Progress>,
+    sign_all: bool,
+    sign_retries: Option<&'a str>,
+    timeout: Option<&'a str>,
     timestamp: Option<&'a str>,
 }
 
@@ -57,12 +91,18 @@ impl<'a> Builder<'a> {
         Builder {
             bin_path: None,
             capture_output: true,
+            cert_subject: None,
             description: None,
+            dump_toolset_commands: false,
             homepage: None,
             input: None,
             installer: None,
             package: None,
             product_name: None,
+            progress: None,
+            sign_all: false,
+            sign_retries: None,
+            timeout: None,
             timestamp: None,
         }
     }
@@ -74,9 +114,10 @@ impl<'a> Builder<'a> {
     }
     /// Sets the path to the folder containing the `signtool.exe` file.
     ///
-    // Normally the `signtool.exe` is installed in the `bin` folder of the
-    // Windows SDK installation. The default is to use the `PATH` system
-    // environment variable.
+    /// Normally the `signtool.exe` is installed in the `bin` folder of the
+    /// Windows SDK installation. The descending order of precedence is: (1)
+    /// this path, (2) the `SIGNTOOL_PATH` system environment variable, then (3)
+    /// the `PATH` system environment variable.
     pub fn bin_path(&mut self, b: Option<&'a str>) -> &mut Self {
         self.bin_path = b;
         self
@@ -92,22 +133,52 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the subject name of the certificate used to sign the installer.
+    ///
+    /// The subject name is passed to the signer (signtool.exe) with the `/n`
+    /// option, which selects the certificate from the Windows certificate
+    /// store with a subject name that contains the value. This is useful if
+    /// multiple certificates are installed, such as a named code signing
+    /// certificate on a build machine. The default is to pass the `/a` flag
+    /// instead, which lets the signer automatically select the best
+    /// certificate.
+    pub fn cert_subject(&mut self, c: Option<&'a str>) -> &mut Self {
+        self.cert_subject = c;
+        self
+    }
+
     /// Sets the description.
     ///
     /// This override the description obtained from the `description` field in
     /// the package's manifest (Cargo.toml).
     ///
-    /// The description is displayed in the ACL dialog.
+    /// The description is appended to the product name and passed to the
+    /// signer (signtool.exe) with the `/d` option. It is displayed as the
+    /// program name in the blue User Account Control (UAC) dialog. The
+    /// publisher in the UAC dialog is the subject of the signing certificate,
+    /// not the manufacturer of the installer.
     pub fn description(&mut self, d: Option<&'a str>) -> &mut Self {
         self.description = d;
         self
     }
 
-    /// Sets the homepage URL that is displayed in the ACL dialog.
+    /// Prints each command that is run to sign the installer.
+    ///
+    /// If `true`, each run of the signer (signtool.exe), and the WiX Toolset
+    /// insignia application (insignia.exe) for a bundle, is printed to stderr
+    /// right before it runs as a line that can be copied and pasted into a
+    /// shell. The default is to not print the commands.
+    pub fn dump_toolset_commands(&mut self, d: bool) -> &mut Self {
+        self.dump_toolset_commands = d;
+        self
+    }
+
+    /// Sets the homepage URL that is displayed in the UAC dialog.
     ///
+    /// The URL is passed to the signer (signtool.exe) with the `/du` option.
     /// The default is to use the value for the `homepage` field in the
     /// package's manifest (Cargo.toml) if it exists; otherwise, a URL
-    /// is _not_ displayed in the ACL dialog.
+    /// is _not_ displayed in the UAC dialog.
     pub fn homepage(&mut self, h: Option<&'a str>) -> &mut Self {
         self.homepage = h;
         self
@@ -134,6 +205,70 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets a callback that is invoked each time the signing of the installer
+    /// moves to a new stage.
+    ///
+    /// This is the same callback as the [`create::Builder::progress`] method,
+    /// so an application can report the progress of creating and signing an
+    /// installer together. The callback is invoked with the
+    /// [`CreateStage::Signing`] stage before each file is signed, which is
+    /// twice for a bundle if the engine is also signed, and with the
+    /// [`CreateStage::Done`] stage after the installer is signed. The default
+    /// is to not report the progress.
+    ///
+    /// [`create::Builder::progress`]: ../create/struct.Builder.html#method.progress
+    /// [`CreateStage::Signing`]: ../create/enum.CreateStage.html#variant.Signing
+    /// [`CreateStage::Done`]: ../create/enum.CreateStage.html#variant.Done
+    pub fn progress<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(CreateStage) + Send + Sync + 'static,
+    {
+        self.progress = Some(Progress(Arc::new(f)));
+        self
+    }
+
+    /// Enables or disables signing the engine of a bundle in addition to the
+    /// bundle.
+    ///
+    /// A bundle (exe) contains an engine that is extracted and run when the
+    /// bundle is executed. If enabled, the engine is detached from the bundle
+    /// with the WiX Toolset's `insignia` application, signed, and reattached
+    /// before the bundle is signed. The default installer to sign is also a
+    /// bundle (exe) instead of a Windows installer (msi). The packages (msi)
+    /// contained in the bundle must be signed _before_ the bundle is created,
+    /// because they are embedded into the bundle by the linker (light.exe).
+    ///
+    /// The default is to only sign the installer.
+    pub fn sign_all(&mut self, s: bool) -> &mut Self {
+        self.sign_all = s;
+        self
+    }
+
+    /// Sets the number of times signing a file is retried if the file is in
+    /// use by another process.
+    ///
+    /// An antivirus scanner or the search indexer can briefly lock a file
+    /// right after it is created, which causes the signer (signtool.exe) to
+    /// fail with a sharing violation. The failure is classified from the
+    /// output of the signer, and signing is retried after a short delay only
+    /// for this failure, not for certificate or timestamp failures. Use `0` to
+    /// never retry. The default is to retry three times.
+    pub fn sign_retries(&mut self, s: Option<&'a str>) -> &mut Self {
+        self.sign_retries = s;
+        self
+    }
+
+    /// Sets the number of seconds to wait for the signer (signtool.exe) and
+    /// the WiX Toolset's insignia application.
+    ///
+    /// An application is stopped, and an error is returned, if it does not
+    /// finish within the timeout, such as when the signer is waiting on an
+    /// unresponsive timestamp server. The default is no timeout.
+    pub fn timeout(&mut self, t: Option<&'a str>) -> &mut Self {
+        self.timeout = t;
+        self
+    }
+
     /// Sets the URL for the timestamp server used when signing an installer.
     ///
     /// The default is to _not_ use a timestamp server, even though it is highly
@@ -148,12 +283,18 @@ impl<'a> Builder<'a> {
         Execution {
             bin_path: self.bin_path.map(PathBuf::from),
             capture_output: self.capture_output,
+            cert_subject: self.cert_subject.map(String::from),
             description: self.description.map(String::from),
+            dump_toolset_commands: self.dump_toolset_commands,
             homepage: self.homepage.map(String::from),
             input: self.input.map(PathBuf::from),
             installer: self.installer.map(PathBuf::from),
             package: self.package.map(String::from),
             product_name: self.product_name.map(String::from),
+            progress: self.progress.clone(),
+            sign_all: self.sign_all,
+            sign_retries: self.sign_retries.map(String::from),
+            timeout: self.timeout.map(String::from),
             timestamp: self.timestamp.map(String::from),
         }
     }
@@ -170,12 +311,18 @@ impl<'a> Default for Builder<'a> {
 pub struct Execution {
     bin_path: Option<PathBuf>,
     capture_output: bool,
+    cert_subject: Option<String>,
     description: Option<String>,
+    dump_toolset_commands: bool,
     homepage: Option<String>,
     input: Option<PathBuf>,
     installer: Option<PathBuf>,
     package: Option<String>,
     product_name: Option<String>,
+    progress: Option<Progress>,
+    sign_all: bool,
+    sign_retries: Option<String>,
+    timeout: Option<String>,
     timestamp: Option<String>,
 }
 
@@ -185,60 +332,215 @@ impl Execution {
         info!("Signing the installer");
         debug!("bin_path = {:?}", self.bin_path);
         debug!("capture_output = {:?}", self.capture_output);
+        debug!("cert_subject = {:?}", self.cert_subject);
         debug!("description = {:?}", self.description);
+        debug!("dump_toolset_commands = {:?}", self.dump_toolset_commands);
         debug!("homepage = {:?}", self.homepage);
         debug!("input = {:?}", self.input);
         debug!("installer = {:?}", self.installer);
         debug!("package = {:?}", self.package);
         debug!("product_name = {:?}", self.product_name);
+        debug!("sign_all = {:?}", self.sign_all);
+        debug!("sign_retries = {:?}", self.sign_retries);
+        debug!("timeout = {:?}", self.timeout);
         debug!("timestamp = {:?}", self.timestamp);
         let manifest = super::manifest(self.input.as_ref())?;
         debug!("target_directory = {:?}", manifest.target_directory);
         let package = super::package(&manifest, self.package.as_deref())?;
-        let product_name = super::product_name(self.product_name.as_ref(), &package);
-        let description = if let Some(d) = super::description(self.description.clone(), &package) {
-            trace!("A description was provided either at the command line or in the package's manifest (Cargo.toml).");
-            format!("{product_name} - {d}")
-        } else {
-            trace!("A description was not provided at the command line or in the package's manifest (Cargo.toml).");
-            product_name
-        };
-        debug!("description = {:?}", description);
-        let msi = self.msi(manifest.target_directory.as_std_path())?;
+        let installer = self.msi(manifest.target_directory.as_std_path())?;
+        debug!("installer = {:?}", installer);
+        if self.sign_all {
+            if installer.extension() == Some(OsStr::new(EXE_FILE_EXTENSION)) {
+                let engine = engine_path(&installer);
+                debug!("engine = {:?}", engine);
+                info!("Detaching the engine from the bundle");
+                self.run_insignia(&detach_args(&installer, &engine))?;
+                info!("Signing the engine");
+                self.sign(&package, &engine)?;
+                info!("Reattaching the engine to the bundle");
+                self.run_insignia(&reattach_args(&engine, &installer))?;
+                fs::remove_file(&engine)?;
+            } else {
+                warn!(
+                    "The '{}' installer is not a bundle ({}), so only the installer will be signed.",
+                    installer.display(),
+                    EXE_FILE_EXTENSION
+                );
+            }
+        }
+        self.sign(&package, &installer)?;
+        self.report(CreateStage::Done);
+        Ok(())
+    }
+
+    fn report(&self, stage: CreateStage) {
+        if let Some(Progress(callback)) = &self.progress {
+            callback(stage);
+        }
+    }
+
+    /// Signs a file with the signer (signtool.exe).
+    ///
+    /// Signing is retried after a short delay if the signer fails because the
+    /// file is in use by another process, up to the number of sign retries.
+    fn sign(&self, package: &Package, file: &Path) -> Result<()> {
+        self.report(CreateStage::Signing);
         let mut signer = self.signer()?;
         debug!("signer = {:?}", signer);
         if self.capture_output {
             trace!("Capturing the {} output", SIGNTOOL);
             signer.stdout(Stdio::null());
-            signer.stderr(Stdio::null());
-        }
-        signer
-            .arg("sign")
-            .arg("/a")
-            .arg("/fd")
-            .arg("certHash")
-            .arg("/d")
-            .arg(description);
-        if let Some(h) = self.homepage(&package) {
+        }
+        signer.args(self.sign_args(package, file)?);
+        let timeout = crate::timeout(self.timeout.as_deref())?;
+        let retries = self.sign_retries()?;
+        debug!("retries = {:?}", retries);
+        let mut attempt = 0;
+        loop {
+            if self.dump_toolset_commands {
+                eprintln!("{}", command_line(&signer));
+            }
+            let (status, stderr) =
+                status_with_stderr(&mut signer, timeout).map_err(signer_error)?;
+            if !self.capture_output {
+                io::stderr().write_all(&stderr)?;
+            }
+            if status.success() {
+                return Ok(());
+            }
+            if attempt < retries && is_file_in_use(&String::from_utf8_lossy(&stderr)) {
+                attempt += 1;
+                warn!(
+                    "The '{}' file is in use by another process, such as an antivirus \
+                     scanner. Signing is retried in {} seconds ({}/{}).",
+                    file.display(),
+                    SIGN_RETRY_DELAY.as_secs(),
+                    attempt,
+                    retries
+                );
+                thread::sleep(SIGN_RETRY_DELAY);
+            } else {
+                return Err(Error::Command(
+                    SIGNTOOL,
+                    status.code().unwrap_or(100),
+                    self.capture_output,
+                ));
+            }
+        }
+    }
+
+    fn sign_retries(&self) -> Result<u32> {
+        self.sign_retries
+            .as_deref()
+            .map(|r| {
+                r.trim().parse::<u32>().map_err(|_| {
+                    Error::Generic(format!(
+                        "The '{r}' number of sign retries is not valid. The number must be a \
+                         whole number greater than or equal to zero."
+                    ))
+                })
+            })
+            .unwrap_or(Ok(DEFAULT_SIGN_RETRIES))
+    }
+
+    /// Gets the arguments for the signer (signtool.exe).
+    ///
+    /// The description is passed with the `/d` option and the homepage, if
+    /// any, with the `/du` option. Both are displayed in the blue User Account
+    /// Control (UAC) dialog when the signed installer is executed. The
+    /// publisher shown in the UAC dialog is _not_ set by these options. It is
+    /// the subject of the signing certificate and is independent of the
+    /// manufacturer for the installer.
+    ///
+    /// The certificate is selected by its subject name with the `/n` option
+    /// if a subject name is set; otherwise, the `/a` flag is used to
+    /// automatically select the certificate.
+    fn sign_args(&self, manifest: &Package, msi: &Path) -> Result<Vec<OsString>> {
+        let mut args: Vec<OsString> = vec!["sign".into()];
+        if let Some(s) = &self.cert_subject {
+            trace!("Using the certificate with the '{}' subject name", s);
+            args.push("/n".into());
+            args.push(s.into());
+        } else {
+            args.push("/a".into());
+        }
+        args.extend([
+            "/fd".into(),
+            "certHash".into(),
+            "/d".into(),
+            self.description(manifest).into(),
+        ]);
+        if let Some(h) = self.homepage(manifest) {
             trace!("Using the '{}' URL for the expanded description", h);
-            signer.arg("/du").arg(h);
+            args.push("/du".into());
+            args.push(h.into());
         }
-        if let Some(t) = self.timestamp {
-            let server = TimestampServer::from_str(&t)?;
+        if let Some(t) = &self.timestamp {
+            let server = TimestampServer::from_str(t)?;
             trace!(
                 "Using the '{}' timestamp server to sign the installer",
                 server
             );
-            signer.arg("/t");
-            signer.arg(server.url());
+            args.push("/t".into());
+            args.push(server.url().into());
+        }
+        args.push(msi.into());
+        Ok(args)
+    }
+
+    fn description(&self, manifest: &Package) -> String {
+        let product_name = super::product_name(self.product_name.as_ref(), manifest);
+        let description = if let Some(d) = super::description(self.description.clone(), manifest) {
+            trace!("A description was provided either at the command line or in the package's manifest (Cargo.toml).");
+            format!("{product_name} - {d}")
+        } else {
+            trace!("A description was not provided at the command line or in the package's manifest (Cargo.toml).");
+            product_name
+        };
+        debug!("description = {:?}", description);
+        description
+    }
+
+    fn homepage(&self, manifest: &Package) -> Option<String> {
+        self.homepage
+            .as_ref()
+            .map(String::from)
+            .or_else(|| manifest.homepage.clone())
+    }
+
+    /// Runs the WiX Toolset's `insignia` application.
+    ///
+    /// The application is found in the `bin` folder of the `WIX` system
+    /// environment variable, and then the `PATH` system environment variable.
+    fn run_insignia(&self, args: &[OsString]) -> Result<()> {
+        let mut insignia = env::var_os(WIX_PATH_KEY)
+            .map(|w| {
+                PathBuf::from(w)
+                    .join(BINARY_FOLDER_NAME)
+                    .join(WIX_INSIGNIA)
+                    .with_extension(EXE_FILE_EXTENSION)
+            })
+            .filter(|p| p.exists())
+            .map(Command::new)
+            .unwrap_or_else(|| Command::new(WIX_INSIGNIA));
+        if self.capture_output {
+            trace!("Capturing the {} output", WIX_INSIGNIA);
+            insignia.stdout(Stdio::null());
+            insignia.stderr(Stdio::null());
+        }
+        insignia.args(args);
+        debug!("command = {:?}", insignia);
+        let timeout = crate::timeout(self.timeout.as_deref())?;
+        if self.dump_toolset_commands {
+            eprintln!("{}", command_line(&insignia));
         }
-        let status = signer.arg(&msi).status().map_err(|err| {
+        let status = status_with_timeout(&mut insignia, timeout).map_err(|err| {
             if err.kind() == ErrorKind::NotFound {
                 Error::Generic(format!(
-                    "The {SIGNTOOL} application could not be found. Please check the Windows 10 SDK \
-                     (https://developer.microsoft.com/en-us/windows/downloads/windows-10-sdk) is \
-                     installed and you are using the x64 or x86 Native Build Tools prompt so the \
-                     {SIGNTOOL} application is available."
+                    "The {WIX_INSIGNIA} application could not be found. Please check the WiX \
+                     Toolset (http://wixtoolset.org/) is installed and check the WiX Toolset's \
+                     '{BINARY_FOLDER_NAME}' folder has been added to the PATH environment \
+                     variable or the {WIX_PATH_KEY} system environment variable exists."
                 ))
             } else {
                 err.into()
@@ -246,7 +548,7 @@ impl Execution {
         })?;
         if !status.success() {
             return Err(Error::Command(
-                SIGNTOOL,
+                WIX_INSIGNIA,
                 status.code().unwrap_or(100),
                 self.capture_output,
             ));
@@ -254,13 +556,6 @@ impl Execution {
         Ok(())
     }
 
-    fn homepage(&self, manifest: &Package) -> Option<String> {
-        self.homepage
-            .as_ref()
-            .map(String::from)
-            .or_else(|| manifest.homepage.clone())
-    }
-
     fn msi(&self, target_directory: &Path) -> Result<PathBuf> {
         if let Some(ref i) = self.installer {
             trace!("The path to an installer to sign has been explicitly set");
@@ -276,71 +571,27 @@ impl Execution {
             }
         } else {
             trace!("The path to an installer has not been explicitly set");
+            let extension = if self.sign_all {
+                EXE_FILE_EXTENSION
+            } else {
+                MSI_FILE_EXTENSION
+            };
             let cwd = target_directory.join(WIX);
             for entry in fs::read_dir(cwd)? {
                 let entry = entry?;
                 let path = entry.path();
-                if path.extension() == Some(OsStr::new(MSI_FILE_EXTENSION)) {
+                if path.extension() == Some(OsStr::new(extension)) && !is_engine(&path) {
                     return Ok(path);
                 }
             }
             Err(Error::Generic(format!(
-                "Could not find an installer ({MSI_FILE_EXTENSION}) to sign"
+                "Could not find an installer ({extension}) to sign"
             )))
         }
     }
 
     fn signer(&self) -> Result<Command> {
-        if let Some(mut path) = self.bin_path.as_ref().map(|s| {
-            let mut p = PathBuf::from(s);
-            trace!(
-                "Using the '{}' path to the Windows SDK '{}' folder for the signer",
-                p.display(),
-                BINARY_FOLDER_NAME
-            );
-            p.push(SIGNTOOL);
-            p.set_extension(EXE_FILE_EXTENSION);
-            p
-        }) {
-            if !path.exists() {
-                path.pop(); // Remove the 'signtool' application from the path
-                Err(Error::Generic(format!(
-                    "The signer application ('{}') does not exist at the '{}' path specified via \
-                    the '-S, --sign-path' command line argument. Please check the path is correct and \
-                    the signer application exists at the path.",
-                    SIGNTOOL,
-                    path.display()
-                )))
-            } else {
-                Ok(Command::new(path))
-            }
-        } else if let Some(mut path) = env::var_os(SIGNTOOL_PATH_KEY).map(|s| {
-            let mut p = PathBuf::from(s);
-            trace!(
-                "Using the '{}' path to the Windows SDK '{}' folder for the signer",
-                p.display(),
-                BINARY_FOLDER_NAME
-            );
-            p.push(SIGNTOOL);
-            p.set_extension(EXE_FILE_EXTENSION);
-            p
-        }) {
-            if !path.exists() {
-                path.pop(); // Remove the `signtool` application from the path
-                Err(Error::Generic(format!(
-                    "The signer application ('{}') does not exist at the '{}' path specified \
-                     via the {} environment variable. Please check the path is correct and the \
-                     signer application exists at the path.",
-                    SIGNTOOL,
-                    path.display(),
-                    SIGNTOOL_PATH_KEY
-                )))
-            } else {
-                Ok(Command::new(path))
-            }
-        } else {
-            Ok(Command::new(SIGNTOOL))
-        }
+        signer(self.bin_path.as_deref())
     }
 }
 
@@ -350,6 +601,122 @@ impl Default for Execution {
     }
 }
 
+/// Gets the command for the signer (signtool.exe).
+///
+/// The descending order of precedence for the location of the signer is: (1)
+/// the path to the folder containing the signer, (2) the `SIGNTOOL_PATH`
+/// system environment variable, then (3) the `PATH` system environment
+/// variable.
+pub(crate) fn signer(bin_path: Option<&Path>) -> Result<Command> {
+    if let Some(mut path) = bin_path.map(|s| {
+        let mut p = PathBuf::from(s);
+        trace!(
+            "Using the '{}' path to the Windows SDK '{}' folder for the signer",
+            p.display(),
+            BINARY_FOLDER_NAME
+        );
+        p.push(SIGNTOOL);
+        p.set_extension(EXE_FILE_EXTENSION);
+        p
+    }) {
+        if !path.exists() {
+            path.pop(); // Remove the 'signtool' application from the path
+            Err(Error::Generic(format!(
+                "The signer application ('{}') does not exist at the '{}' path specified via \
+                the '-b,--bin-path' command line argument. Please check the path is correct and \
+                the signer application exists at the path.",
+                SIGNTOOL,
+                path.display()
+            )))
+        } else {
+            Ok(Command::new(path))
+        }
+    } else if let Some(mut path) = env::var_os(SIGNTOOL_PATH_KEY).map(|s| {
+        let mut p = PathBuf::from(s);
+        trace!(
+            "Using the '{}' path to the Windows SDK '{}' folder for the signer",
+            p.display(),
+            BINARY_FOLDER_NAME
+        );
+        p.push(SIGNTOOL);
+        p.set_extension(EXE_FILE_EXTENSION);
+        p
+    }) {
+        if !path.exists() {
+            path.pop(); // Remove the `signtool` application from the path
+            Err(Error::Generic(format!(
+                "The signer application ('{}') does not exist at the '{}' path specified \
+                 via the {} environment variable. Please check the path is correct and the \
+                 signer application exists at the path.",
+                SIGNTOOL,
+                path.display(),
+                SIGNTOOL_PATH_KEY
+            )))
+        } else {
+            Ok(Command::new(path))
+        }
+    } else {
+        Ok(Command::new(SIGNTOOL))
+    }
+}
+
+/// Converts an error from running the signer (signtool.exe), with a helpful
+/// message if the signer could not be found.
+pub(crate) fn signer_error(err: io::Error) -> Error {
+    if err.kind() == ErrorKind::NotFound {
+        Error::Generic(format!(
+            "The {SIGNTOOL} application could not be found. Please check the Windows 10 SDK \
+             (https://developer.microsoft.com/en-us/windows/downloads/windows-10-sdk) is \
+             installed and you are using the x64 or x86 Native Build Tools prompt so the \
+             {SIGNTOOL} application is available."
+        ))
+    } else {
+        err.into()
+    }
+}
+
+/// The suffix of the file stem for an engine detached from a bundle.
+const ENGINE_SUFFIX: &str = ".engine";
+
+/// Gets the path for the engine detached from a bundle, which is next to the
+/// bundle, i.e. `example.exe` becomes `example.engine.exe`.
+fn engine_path(bundle: &Path) -> PathBuf {
+    let mut stem = bundle.file_stem().map(OsString::from).unwrap_or_default();
+    stem.push(format!("{ENGINE_SUFFIX}.{EXE_FILE_EXTENSION}"));
+    bundle.with_file_name(stem)
+}
+
+/// Determines if a path is an engine left over from signing a bundle.
+fn is_engine(path: &Path) -> bool {
+    path.file_stem()
+        .and_then(OsStr::to_str)
+        .map(|s| s.ends_with(ENGINE_SUFFIX))
+        .unwrap_or(false)
+}
+
+/// Gets the arguments for `insignia` to detach the engine from a bundle.
+fn detach_args(bundle: &Path, engine: &Path) -> Vec<OsString> {
+    vec!["-ib".into(), bundle.into(), "-o".into(), engine.into()]
+}
+
+/// Determines if the output of the signer (signtool.exe) is for a failure
+/// because the file is in use by another process.
+fn is_file_in_use(output: &str) -> bool {
+    let output = output.to_lowercase();
+    FILE_IN_USE_MESSAGES.iter().any(|m| output.contains(m))
+}
+
+/// Gets the arguments for `insignia` to reattach a signed engine to a bundle.
+fn reattach_args(engine: &Path, bundle: &Path) -> Vec<OsString> {
+    vec![
+        "-ab".into(),
+        engine.into(),
+        bundle.into(),
+        "-o".into(),
+        bundle.into(),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -372,6 +739,14 @@ mod tests {
             assert!(!actual.capture_output);
         }
 
+        #[test]
+        fn cert_subject_works() {
+            const EXPECTED: &str = "Example Corp";
+            let mut actual = Builder::new();
+            actual.cert_subject(Some(EXPECTED));
+            assert_eq!(actual.cert_subject, Some(EXPECTED));
+        }
+
         #[test]
         fn description_works() {
             const EXPECTED: &str = "This is a description";
@@ -380,6 +755,13 @@ mod tests {
             assert_eq!(actual.description, Some(EXPECTED));
         }
 
+        #[test]
+        fn dump_toolset_commands_works() {
+            let mut actual = Builder::new();
+            actual.dump_toolset_commands(true);
+            assert!(actual.dump_toolset_commands);
+        }
+
         #[test]
         fn homepage_works() {
             const EXPECTED: &str = "http://www.example.com";
@@ -404,6 +786,36 @@ mod tests {
             assert_eq!(actual.product_name, Some(EXPECTED));
         }
 
+        #[test]
+        fn progress_works() {
+            let mut actual = Builder::new();
+            actual.progress(|_| {});
+            assert!(actual.progress.is_some());
+        }
+
+        #[test]
+        fn sign_all_works() {
+            let mut actual = Builder::new();
+            actual.sign_all(true);
+            assert!(actual.sign_all);
+        }
+
+        #[test]
+        fn sign_retries_works() {
+            const EXPECTED: &str = "5";
+            let mut actual = Builder::new();
+            actual.sign_retries(Some(EXPECTED));
+            assert_eq!(actual.sign_retries, Some(EXPECTED));
+        }
+
+        #[test]
+        fn timeout_works() {
+            const EXPECTED: &str = "60";
+            let mut actual = Builder::new();
+            actual.timeout(Some(EXPECTED));
+            assert_eq!(actual.timeout, Some(EXPECTED));
+        }
+
         #[test]
         fn timestamp_works() {
             const EXPECTED: &str = "http://www.example.com";
@@ -420,6 +832,7 @@ mod tests {
 
         use super::*;
         use crate::tests::setup_project;
+        use serial_test::serial;
 
         const MIN_MANIFEST: &str = r#"[package]
             name = "Example"
@@ -469,6 +882,108 @@ mod tests {
             assert_eq!(actual, Some(String::from(EXPECTED)));
         }
 
+        #[test]
+        fn sign_args_without_description_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+            let actual = Execution::default()
+                .sign_args(&package, Path::new("Example.msi"))
+                .unwrap();
+            assert_eq!(
+                actual,
+                vec![
+                    "sign",
+                    "/a",
+                    "/fd",
+                    "certHash",
+                    "/d",
+                    "Example",
+                    "Example.msi"
+                ]
+            );
+        }
+
+        #[test]
+        fn sign_args_with_description_and_homepage_works() {
+            let project = setup_project(HOMEPAGE_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+            let actual = Builder::new()
+                .description(Some("An example"))
+                .product_name(Some("Product"))
+                .build()
+                .sign_args(&package, Path::new("Example.msi"))
+                .unwrap();
+            assert_eq!(
+                actual,
+                vec![
+                    "sign",
+                    "/a",
+                    "/fd",
+                    "certHash",
+                    "/d",
+                    "Product - An example",
+                    "/du",
+                    "http://www.example.com",
+                    "Example.msi"
+                ]
+            );
+        }
+
+        #[test]
+        fn sign_args_with_homepage_override_works() {
+            let project = setup_project(HOMEPAGE_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+            let actual = Builder::new()
+                .homepage(Some("http://www.another.com"))
+                .build()
+                .sign_args(&package, Path::new("Example.msi"))
+                .unwrap();
+            assert_eq!(actual[6], "/du");
+            assert_eq!(actual[7], "http://www.another.com");
+        }
+
+        #[test]
+        fn sign_args_with_cert_subject_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+            let actual = Builder::new()
+                .cert_subject(Some("Example Corp"))
+                .build()
+                .sign_args(&package, Path::new("Example.msi"))
+                .unwrap();
+            assert_eq!(
+                actual,
+                vec![
+                    "sign",
+                    "/n",
+                    "Example Corp",
+                    "/fd",
+                    "certHash",
+                    "/d",
+                    "Example",
+                    "Example.msi"
+                ]
+            );
+        }
+
+        #[test]
+        fn sign_args_with_timestamp_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+            let actual = Builder::new()
+                .timestamp(Some("http://timestamp.example.com"))
+                .build()
+                .sign_args(&package, Path::new("Example.msi"))
+                .unwrap();
+            assert_eq!(actual[6], "/t");
+            assert_eq!(actual[7], "http://timestamp.example.com");
+        }
+
         #[test]
         fn msi_with_nonexistent_installer_fails() {
             let result = Execution::default().msi(Path::new("target"));
@@ -488,8 +1003,116 @@ mod tests {
             assert_eq!(actual, msi_path);
         }
 
+        #[test]
+        fn msi_with_sign_all_finds_bundle_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let wix = temp_dir.path().join(WIX);
+            fs::create_dir(&wix).unwrap();
+            File::create(wix.join("Example.msi")).unwrap();
+            File::create(wix.join("Example.engine.exe")).unwrap();
+            let bundle = wix.join("Example.exe");
+            File::create(&bundle).unwrap();
+            let actual = Builder::new()
+                .sign_all(true)
+                .build()
+                .msi(temp_dir.path())
+                .unwrap();
+            assert_eq!(actual, bundle);
+        }
+
+        #[test]
+        fn engine_path_works() {
+            let actual = engine_path(Path::new("target/wix/Example-0.1.0-x86_64.exe"));
+            assert_eq!(
+                actual,
+                PathBuf::from("target/wix/Example-0.1.0-x86_64.engine.exe")
+            );
+            assert!(is_engine(&actual));
+            assert!(!is_engine(Path::new("Example.exe")));
+        }
+
+        #[test]
+        fn detach_and_reattach_args_work() {
+            let bundle = Path::new("Example.exe");
+            let engine = Path::new("Example.engine.exe");
+            assert_eq!(
+                detach_args(bundle, engine),
+                vec!["-ib", "Example.exe", "-o", "Example.engine.exe"]
+            );
+            assert_eq!(
+                reattach_args(engine, bundle),
+                vec![
+                    "-ab",
+                    "Example.engine.exe",
+                    "Example.exe",
+                    "-o",
+                    "Example.exe"
+                ]
+            );
+        }
+
+        #[test]
+        fn sign_retries_defaults_to_three() {
+            assert_eq!(Execution::default().sign_retries().unwrap(), 3);
+        }
+
+        #[test]
+        fn sign_retries_with_value_works() {
+            let execution = Builder::new().sign_retries(Some("0")).build();
+            assert_eq!(execution.sign_retries().unwrap(), 0);
+        }
+
+        #[test]
+        fn sign_retries_with_invalid_value_fails() {
+            let execution = Builder::new().sign_retries(Some("-1")).build();
+            assert!(execution.sign_retries().is_err());
+        }
+
+        #[test]
+        fn is_file_in_use_works() {
+            assert!(is_file_in_use(
+                "SignTool Error: An unexpected internal error has occurred.\r\n\
+                 Error information: \"Error: SignerSign() failed.\" (-2147024864/0x80070020)"
+            ));
+            assert!(is_file_in_use(
+                "SignTool Error: The process cannot access the file because it is being \
+                 used by another process."
+            ));
+            assert!(!is_file_in_use(
+                "SignTool Error: No certificates were found that met all the given criteria."
+            ));
+            assert!(!is_file_in_use(
+                "SignTool Error: The specified timestamp server either could not be reached \
+                 or returned an invalid response."
+            ));
+        }
+
+        #[test]
+        fn sign_reports_signing_works() {
+            let project = setup_project(MIN_MANIFEST);
+            let manifest = crate::manifest(Some(&project.path().join("Cargo.toml"))).unwrap();
+            let package = crate::package(&manifest, None).unwrap();
+            let stages = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let reported = Arc::clone(&stages);
+            let execution = Builder::new()
+                .bin_path(Some("Example.exe"))
+                .progress(move |stage| reported.lock().unwrap().push(stage))
+                .build();
+            // The signer does not exist, so signing fails after the stage is
+            // reported.
+            assert!(execution.sign(&package, Path::new("Example.msi")).is_err());
+            assert_eq!(*stages.lock().unwrap(), vec![CreateStage::Signing]);
+        }
+
+        #[test]
+        fn report_without_progress_works() {
+            let execution = Execution::default();
+            execution.report(CreateStage::Done);
+        }
+
         #[test]
         #[cfg(windows)]
+        #[serial]
         fn signer_works() {
             let result = Execution::default().signer();
             assert!(result.is_ok());
@@ -505,11 +1128,39 @@ mod tests {
         }
 
         #[test]
+        #[serial]
         fn signer_with_nonexistent_environment_path_fails() {
             env::set_var(SIGNTOOL_PATH_KEY, "Example");
             let result = Execution::default().signer();
             env::remove_var(SIGNTOOL_PATH_KEY);
             assert!(result.is_err());
         }
+
+        #[test]
+        #[serial]
+        fn signer_resolution_order_works() {
+            let bin_path = assert_fs::TempDir::new().unwrap();
+            let sdk_path = assert_fs::TempDir::new().unwrap();
+            let signtool = PathBuf::from(SIGNTOOL).with_extension(EXE_FILE_EXTENSION);
+            File::create(bin_path.path().join(&signtool)).unwrap();
+            File::create(sdk_path.path().join(&signtool)).unwrap();
+            env::set_var(SIGNTOOL_PATH_KEY, sdk_path.path());
+            let from_bin_path = Builder::new()
+                .bin_path(bin_path.path().to_str())
+                .build()
+                .signer();
+            let from_environment = Execution::default().signer();
+            env::remove_var(SIGNTOOL_PATH_KEY);
+            let from_path = Execution::default().signer();
+            assert_eq!(
+                from_bin_path.unwrap().get_program(),
+                bin_path.path().join(&signtool)
+            );
+            assert_eq!(
+                from_environment.unwrap().get_program(),
+                sdk_path.path().join(&signtool)
+            );
+            assert_eq!(from_path.unwrap().get_program(), SIGNTOOL);
+        }
     }
 }
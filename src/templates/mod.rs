@@ -12,9 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::Cultures;
 use crate::Error;
+use crate::Result;
 use lazy_static::lazy_static;
+use log::trace;
+use serde::Serialize;
 use std::fmt;
+use std::fs;
+use std::path::Path;
 use std::str::FromStr;
 
 /// The WiX Source (wxs) template.
@@ -29,6 +35,14 @@ static GPL3_LICENSE_TEMPLATE: &str = include_str!("GPL-3.0.rtf.mustache");
 /// The MIT Rich Text Format (RTF) license template.
 static MIT_LICENSE_TEMPLATE: &str = include_str!("MIT.rtf.mustache");
 
+/// The German (de-DE) translation of the MIT Rich Text Format (RTF) license
+/// template.
+static MIT_LICENSE_TEMPLATE_DE_DE: &str = include_str!("de-DE/MIT.rtf.mustache");
+
+/// The French (fr-FR) translation of the MIT Rich Text Format (RTF) license
+/// template.
+static MIT_LICENSE_TEMPLATE_FR_FR: &str = include_str!("fr-FR/MIT.rtf.mustache");
+
 /// The different templates that can be printed or written to a file.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Template {
@@ -143,6 +157,56 @@ impl Template {
         ]
     }
 
+    /// Gets the file name of the template.
+    ///
+    /// This is the name of the embedded template file, which is also the name
+    /// used to look up the template in a folder of templates with the
+    /// [`read_from`] method.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use wix::Template;
+    ///
+    /// assert_eq!(Template::Apache2.file_name(), "Apache-2.0.rtf.mustache");
+    /// assert_eq!(Template::Mit.file_name(), "MIT.rtf.mustache");
+    /// assert_eq!(Template::Wxs.file_name(), "main.wxs.mustache");
+    /// ```
+    ///
+    /// [`read_from`]: #method.read_from
+    pub fn file_name(&self) -> &'static str {
+        match *self {
+            Template::Apache2 => "Apache-2.0.rtf.mustache",
+            Template::Gpl3 => "GPL-3.0.rtf.mustache",
+            Template::Mit => "MIT.rtf.mustache",
+            Template::Wxs => "main.wxs.mustache",
+        }
+    }
+
+    /// Reads the contents of the template from a folder of templates.
+    ///
+    /// The folder is organized like the embedded templates, where the template
+    /// is named with the [`file_name`] and translations are in sub-folders
+    /// named after the culture, e.g. `fr-FR\MIT.rtf.mustache`. The translation
+    /// for the culture is used if it exists; otherwise, the template in the
+    /// folder is used. `None` is returned if the folder does not contain the
+    /// template, in which case the embedded template should be used.
+    ///
+    /// [`file_name`]: #method.file_name
+    pub fn read_from(&self, dir: &Path, culture: Option<&Cultures>) -> Result<Option<String>> {
+        let localized = culture.map(|c| dir.join(c.to_string()).join(self.file_name()));
+        for path in localized
+            .into_iter()
+            .chain(std::iter::once(dir.join(self.file_name())))
+        {
+            if path.is_file() {
+                trace!("Using the '{}' template file", path.display());
+                return fs::read_to_string(path).map(Some).map_err(Error::from);
+            }
+        }
+        Ok(None)
+    }
+
     /// Gets the embedded contents of the template as a string.
     pub fn to_str(&self) -> &str {
         match *self {
@@ -152,6 +216,65 @@ impl Template {
             Template::Wxs => WIX_SOURCE_TEMPLATE,
         }
     }
+
+    /// Gets the embedded contents of the translation of the template for a
+    /// culture as a string.
+    ///
+    /// Translations are organized by culture in sub-folders of the templates
+    /// folder. `None` is returned if the template is not translated for the
+    /// culture, in which case the English template from [`to_str`] should be
+    /// used. Only the license templates are translated, and the translations
+    /// are unofficial with the English license being the legally binding one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use wix::{Cultures, Template};
+    ///
+    /// assert!(Template::Mit.to_localized_str(&Cultures::FrFr).is_some());
+    /// assert!(Template::Mit.to_localized_str(&Cultures::EnUs).is_none());
+    /// assert!(Template::Apache2.to_localized_str(&Cultures::FrFr).is_none());
+    /// ```
+    ///
+    /// [`to_str`]: #method.to_str
+    pub fn to_localized_str(&self, culture: &Cultures) -> Option<&str> {
+        match (*self, culture) {
+            (Template::Mit, Cultures::DeDe) => Some(MIT_LICENSE_TEMPLATE_DE_DE),
+            (Template::Mit, Cultures::FrFr) => Some(MIT_LICENSE_TEMPLATE_FR_FR),
+            _ => None,
+        }
+    }
+
+    /// Renders the embedded template with the data.
+    ///
+    /// The data can be any value that can be serialized, such as a struct
+    /// deriving `Serialize` or a [`serde_json::Value`], and its fields are
+    /// used for the tags in the template. A tag without a matching field is
+    /// rendered as an empty string. This is the same rendering used by the
+    /// _print_ subcommand, but without the values from the package's manifest
+    /// (Cargo.toml), so all of the data must be provided. An error occurs if
+    /// the data cannot be serialized.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use wix::Template;
+    ///
+    /// let rendered = Template::Wxs.render(&json!({
+    ///     "product-name": "Example",
+    ///     "upgrade-code-guid": "B36177BE-EA4D-44FB-B05C-EDDABDAA95CA",
+    /// }))?;
+    /// assert!(rendered.contains("Name='Example'"));
+    /// assert!(rendered.contains("UpgradeCode='B36177BE-EA4D-44FB-B05C-EDDABDAA95CA'"));
+    /// # Ok::<(), wix::Error>(())
+    /// ```
+    ///
+    /// [`serde_json::Value`]: https://docs.rs/serde_json/latest/serde_json/enum.Value.html
+    pub fn render<T: Serialize>(&self, data: &T) -> Result<String> {
+        let template = mustache::compile_str(self.to_str())?;
+        template.render_to_string(data).map_err(Error::from)
+    }
 }
 
 impl fmt::Display for Template {
@@ -0,0 +1,307 @@
+// Copyright (C) 2017 Christopher R. Field.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The implementation for the `verify` command. This command focuses on
+//! verifying the signature of an existing installer using the Windows SDK
+//! `signtool` application, without signing it.
+
+use crate::sign;
+use crate::status_with_timeout;
+use crate::Error;
+use crate::Result;
+use crate::MSI_FILE_EXTENSION;
+use crate::SIGNTOOL;
+use crate::WIX;
+
+use log::{debug, info, trace};
+
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// A builder for creating an execution context to verify the signature of an
+/// installer.
+#[derive(Debug, Clone)]
+pub struct Builder<'a> {
+    bin_path: Option<&'a str>,
+    input: Option<&'a str>,
+    installer: Option<&'a str>,
+    timeout: Option<&'a str>,
+}
+
+impl<'a> Builder<'a> {
+    /// Creates a new `Builder` instance.
+    pub fn new() -> Self {
+        Builder {
+            bin_path: None,
+            input: None,
+            installer: None,
+            timeout: None,
+        }
+    }
+
+    /// Sets the path to the folder containing the `signtool.exe` file.
+    ///
+    /// The descending order of precedence is: (1) this path, (2) the
+    /// `SIGNTOOL_PATH` system environment variable, then (3) the `PATH` system
+    /// environment variable, which is the same as for signing an installer.
+    pub fn bin_path(&mut self, b: Option<&'a str>) -> &mut Self {
+        self.bin_path = b;
+        self
+    }
+
+    /// Sets the path to a package's manifest (Cargo.toml).
+    ///
+    /// The manifest is only used to find the installer in the `target\wix`
+    /// folder if an installer is not set with the [`installer`] method.
+    ///
+    /// [`installer`]: #method.installer
+    pub fn input(&mut self, i: Option<&'a str>) -> &mut Self {
+        self.input = i;
+        self
+    }
+
+    /// Sets the path to the installer to verify.
+    ///
+    /// Any signed file can be verified, including installers that were not
+    /// created with this crate. The default is to verify the most recently
+    /// modified Windows installer (msi) in the `target\wix` folder.
+    pub fn installer(&mut self, i: Option<&'a str>) -> &mut Self {
+        self.installer = i;
+        self
+    }
+
+    /// Sets the number of seconds to wait for the signer (signtool.exe).
+    ///
+    /// The signer is stopped, and an error is returned, if it does not finish
+    /// within the timeout. The default is no timeout.
+    pub fn timeout(&mut self, t: Option<&'a str>) -> &mut Self {
+        self.timeout = t;
+        self
+    }
+
+    /// Creates an execution context for verifying the signature of an
+    /// installer.
+    pub fn build(&mut self) -> Execution {
+        Execution {
+            bin_path: self.bin_path.map(PathBuf::from),
+            input: self.input.map(PathBuf::from),
+            installer: self.installer.map(PathBuf::from),
+            timeout: self.timeout.map(String::from),
+        }
+    }
+}
+
+impl<'a> Default for Builder<'a> {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
+
+/// A context for verifying the signature of an installer.
+#[derive(Debug)]
+pub struct Execution {
+    bin_path: Option<PathBuf>,
+    input: Option<PathBuf>,
+    installer: Option<PathBuf>,
+    timeout: Option<String>,
+}
+
+impl Execution {
+    /// Verifies the signature of an installer.
+    ///
+    /// The output of the signer (signtool.exe), which includes the
+    /// certificate chain and whether the signature is timestamped, is always
+    /// displayed. An error is returned if the signature is not valid.
+    pub fn run(self) -> Result<()> {
+        debug!("bin_path = {:?}", self.bin_path);
+        debug!("input = {:?}", self.input);
+        debug!("installer = {:?}", self.installer);
+        debug!("timeout = {:?}", self.timeout);
+        let installer = self.installer()?;
+        debug!("installer = {:?}", installer);
+        info!(
+            "Verifying the signature of the '{}' installer",
+            installer.display()
+        );
+        let mut signer = sign::signer(self.bin_path.as_deref())?;
+        signer.args(verify_args(&installer));
+        debug!("command = {:?}", signer);
+        let timeout = crate::timeout(self.timeout.as_deref())?;
+        let status = status_with_timeout(&mut signer, timeout).map_err(sign::signer_error)?;
+        if !status.success() {
+            return Err(Error::Command(
+                SIGNTOOL,
+                status.code().unwrap_or(100),
+                false,
+            ));
+        }
+        Ok(())
+    }
+
+    fn installer(&self) -> Result<PathBuf> {
+        if let Some(installer) = &self.installer {
+            trace!("The path to an installer to verify has been explicitly set");
+            if installer.exists() {
+                Ok(installer.clone())
+            } else {
+                Err(Error::Generic(format!(
+                    "The '{}' path does not exist for the installer",
+                    installer.display()
+                )))
+            }
+        } else {
+            trace!("The path to an installer has not been explicitly set");
+            let manifest = crate::manifest(self.input.as_ref())?;
+            debug!("target_directory = {:?}", manifest.target_directory);
+            latest_msi(&manifest.target_directory.as_std_path().join(WIX))
+        }
+    }
+}
+
+impl Default for Execution {
+    fn default() -> Self {
+        Builder::new().build()
+    }
+}
+
+/// Gets the most recently modified Windows installer (msi) in a folder.
+fn latest_msi(folder: &Path) -> Result<PathBuf> {
+    let mut latest: Option<(SystemTime, PathBuf)> = None;
+    if folder.is_dir() {
+        for entry in fs::read_dir(folder)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension() != Some(OsStr::new(MSI_FILE_EXTENSION)) {
+                continue;
+            }
+            let modified = entry.metadata()?.modified()?;
+            if latest.as_ref().map_or(true, |(t, _)| modified > *t) {
+                latest = Some((modified, path));
+            }
+        }
+    }
+    latest.map(|(_, path)| path).ok_or_else(|| {
+        Error::Generic(format!(
+            "Could not find an installer ({MSI_FILE_EXTENSION}) to verify in the '{}' folder",
+            folder.display()
+        ))
+    })
+}
+
+/// Gets the arguments for the signer (signtool.exe) to verify a file.
+///
+/// The `/pa` flag verifies the signature with the Default Authenticode
+/// verification policy, which is the policy for code signing certificates,
+/// and the `/v` flag prints the certificate chain and the timestamp.
+fn verify_args(installer: &Path) -> Vec<OsString> {
+    vec!["verify".into(), "/pa".into(), "/v".into(), installer.into()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod builder {
+        use super::*;
+
+        #[test]
+        fn bin_path_works() {
+            const EXPECTED: &str = "C:\\signtool.exe";
+            let mut actual = Builder::new();
+            actual.bin_path(Some(EXPECTED));
+            assert_eq!(actual.bin_path, Some(EXPECTED));
+        }
+
+        #[test]
+        fn input_works() {
+            const EXPECTED: &str = "C:\\Example";
+            let mut actual = Builder::new();
+            actual.input(Some(EXPECTED));
+            assert_eq!(actual.input, Some(EXPECTED));
+        }
+
+        #[test]
+        fn installer_works() {
+            const EXPECTED: &str = "C:\\Example.msi";
+            let mut actual = Builder::new();
+            actual.installer(Some(EXPECTED));
+            assert_eq!(actual.installer, Some(EXPECTED));
+        }
+
+        #[test]
+        fn timeout_works() {
+            const EXPECTED: &str = "60";
+            let mut actual = Builder::new();
+            actual.timeout(Some(EXPECTED));
+            assert_eq!(actual.timeout, Some(EXPECTED));
+        }
+    }
+
+    mod execution {
+        extern crate assert_fs;
+
+        use std::fs::File;
+
+        use super::*;
+
+        #[test]
+        fn installer_with_nonexistent_installer_fails() {
+            let result = Builder::new()
+                .installer(Some("Nonexistent.msi"))
+                .build()
+                .installer();
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn installer_with_existing_file_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            let installer = temp_dir.path().join("Example.exe");
+            File::create(&installer).unwrap();
+            let actual = Builder::new()
+                .installer(installer.to_str())
+                .build()
+                .installer()
+                .unwrap();
+            assert_eq!(actual, installer);
+        }
+
+        #[test]
+        fn latest_msi_works() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            File::create(temp_dir.path().join("Example.exe")).unwrap();
+            File::create(temp_dir.path().join("Example.wixpdb")).unwrap();
+            let msi = temp_dir.path().join("Example.msi");
+            File::create(&msi).unwrap();
+            assert_eq!(latest_msi(temp_dir.path()).unwrap(), msi);
+        }
+
+        #[test]
+        fn latest_msi_without_installer_fails() {
+            let temp_dir = assert_fs::TempDir::new().unwrap();
+            assert!(latest_msi(temp_dir.path()).is_err());
+            assert!(latest_msi(&temp_dir.path().join(WIX)).is_err());
+        }
+
+        #[test]
+        fn verify_args_works() {
+            assert_eq!(
+                verify_args(Path::new("Example.msi")),
+                vec!["verify", "/pa", "/v", "Example.msi"]
+            );
+        }
+    }
+}
@@ -190,6 +190,72 @@ fn binaries_works() {
     );
 }
 
+#[test]
+#[serial]
+fn binaries_with_subdirectory_works() {
+    const EXPECTED: &str = "bin\\Helper.exe";
+    let original_working_directory = env::current_dir().unwrap();
+    let package = common::create_test_package();
+    env::set_current_dir(package.path()).unwrap();
+    let result = Builder::default()
+        .binaries(Some(vec!["bin\\Example.exe", "bin\\Helper.exe:tools"]))
+        .build()
+        .run();
+    env::set_current_dir(original_working_directory).unwrap();
+    assert!(result.is_ok());
+    assert_eq!(
+        common::evaluate_xpath(
+            package.child(MAIN_WXS_PATH.as_path()).path(),
+            "//*/wix:Directory[@Id='Bin']/wix:Component/wix:File[@Id='exe0']/@Name"
+        ),
+        "Example.exe"
+    );
+    assert_eq!(
+        common::evaluate_xpath(
+            package.child(MAIN_WXS_PATH.as_path()).path(),
+            "//*/wix:Directory[@Name='tools']/wix:Component/wix:File[@Id='exe1']/@Source"
+        ),
+        EXPECTED
+    );
+    assert_eq!(
+        common::evaluate_xpath(
+            package.child(MAIN_WXS_PATH.as_path()).path(),
+            "//*/wix:ComponentRef[@Id='binary1']/@Id"
+        ),
+        "binary1"
+    );
+}
+
+#[test]
+#[serial]
+fn after_install_launch_works() {
+    let original_working_directory = env::current_dir().unwrap();
+    let package = common::create_test_package();
+    env::set_current_dir(package.path()).unwrap();
+    let result = Builder::default()
+        .binaries(Some(vec!["bin\\Example.exe", "bin\\Helper.exe"]))
+        .after_install_launch(true)
+        .launch_binary(Some("Helper"))
+        .build()
+        .run();
+    env::set_current_dir(original_working_directory).unwrap();
+    assert!(result.is_ok());
+    assert_eq!(
+        common::evaluate_xpath(
+            package.child(MAIN_WXS_PATH.as_path()).path(),
+            "//*/wix:Property[@Id='WixShellExecTarget']/@Value"
+        ),
+        "[#exe1]"
+    );
+    assert_eq!(
+        common::evaluate_xpath(
+            package.child(MAIN_WXS_PATH.as_path()).path(),
+            "//*/wix:Publish[@Dialog='ExitDialog']/@Value"
+        ),
+        "LaunchApplication"
+    );
+}
+
 #[test]
 fn input_works() {
     let package = common::create_test_package();
@@ -360,6 +426,21 @@ fn eula_works() {
     );
 }
 
+#[test]
+#[serial]
+fn mit_license_id_with_culture_works() {
+    let original_working_directory = env::current_dir().unwrap();
+    let package = common::create_test_package();
+    env::set_current_dir(package.path()).unwrap();
+    add_license_to_package(package.path(), "MIT");
+    let result = Builder::default().culture(Some("de-DE")).build().run();
+    env::set_current_dir(original_working_directory).unwrap();
+    assert!(result.is_ok());
+    package
+        .child(LICENSE_RTF_PATH.as_path())
+        .assert(predicate::str::contains("\\lang1031 Copyright (c)"));
+}
+
 #[test]
 #[serial]
 fn mit_license_id_works() {
@@ -741,6 +822,193 @@ fn dialog_works() {
     );
 }
 
+#[test]
+#[serial]
+fn generate_branding_works() {
+    let original_working_directory = env::current_dir().unwrap();
+    let package = common::create_test_package();
+    env::set_current_dir(package.path()).unwrap();
+    let result = Builder::default().generate_branding(true).build().run();
+    env::set_current_dir(original_working_directory).unwrap();
+    assert!(result.is_ok());
+    package
+        .child(WIX_PATH.join("Banner.bmp"))
+        .assert(predicate::path::is_file());
+    package
+        .child(WIX_PATH.join("Dialog.bmp"))
+        .assert(predicate::path::is_file());
+    assert_eq!(
+        common::evaluate_xpath(
+            package.child(MAIN_WXS_PATH.as_path()).path(),
+            "//*/wix:WixVariable[@Id='WixUIBannerBmp']/@Value"
+        ),
+        "wix\\Banner.bmp"
+    );
+    assert_eq!(
+        common::evaluate_xpath(
+            package.child(MAIN_WXS_PATH.as_path()).path(),
+            "//*/wix:WixVariable[@Id='WixUIDialogBmp']/@Value"
+        ),
+        "wix\\Dialog.bmp"
+    );
+}
+
+#[test]
+#[serial]
+fn generate_branding_with_banner_works() {
+    const EXPECTED: &str = "img\\Banner.bmp";
+    let original_working_directory = env::current_dir().unwrap();
+    let package = common::create_test_package();
+    let package_banner = package.child(EXPECTED);
+    env::set_current_dir(package.path()).unwrap();
+    fs::create_dir("img").unwrap();
+    let _banner_handle = File::create(package_banner.path()).unwrap();
+    let result = Builder::default()
+        .banner(package_banner.path().to_str())
+        .generate_branding(true)
+        .build()
+        .run();
+    env::set_current_dir(original_working_directory).unwrap();
+    assert!(result.is_ok());
+    package
+        .child(WIX_PATH.join("Banner.bmp"))
+        .assert(predicate::path::missing());
+    package
+        .child(WIX_PATH.join("Dialog.bmp"))
+        .assert(predicate::path::is_file());
+    assert_eq!(
+        common::evaluate_xpath(
+            package.child(MAIN_WXS_PATH.as_path()).path(),
+            "//*/wix:WixVariable[@Id='WixUIBannerBmp']/@Value"
+        ),
+        package_banner.path().to_str().unwrap()
+    );
+}
+
+#[test]
+#[serial]
+fn msbuild_works() {
+    let original_working_directory = env::current_dir().unwrap();
+    let package = common::create_test_package();
+    env::set_current_dir(package.path()).unwrap();
+    fs::create_dir(WIX).unwrap();
+    fs::write(
+        WIX_PATH.join("Other.wxs"),
+        "<Wix xmlns='http://schemas.microsoft.com/wix/2006/wi' \
+         xmlns:util='http://schemas.microsoft.com/wix/UtilExtension'/>",
+    )
+    .unwrap();
+    let result = Builder::default().msbuild(true).build().run();
+    env::set_current_dir(original_working_directory).unwrap();
+    assert!(result.is_ok());
+    let project =
+        fs::read_to_string(package.child(WIX_PATH.join("cargowixtest.wixproj")).path()).unwrap();
+    assert!(project.contains("<OutputName>cargowixtest</OutputName>"));
+    assert!(project.contains(">0.1.0</Version>"));
+    assert!(project.contains(">..\\target</CargoTargetDir>"));
+    assert!(
+        project.contains("<Compile Include=\"Other.wxs\" />\n    <Compile Include=\"main.wxs\" />")
+    );
+    assert!(project.contains("<PackageReference Include=\"WixToolset.UI.wixext\""));
+    assert!(project.contains("<PackageReference Include=\"WixToolset.Util.wixext\""));
+}
+
+#[test]
+#[serial]
+fn msbuild_with_existing_project_fails() {
+    let original_working_directory = env::current_dir().unwrap();
+    let package = common::create_test_package();
+    env::set_current_dir(package.path()).unwrap();
+    fs::create_dir(WIX).unwrap();
+    fs::write(WIX_PATH.join("cargowixtest.wixproj"), "").unwrap();
+    let result = Builder::default().msbuild(true).build().run();
+    let forced = Builder::default().msbuild(true).force(true).build().run();
+    env::set_current_dir(original_working_directory).unwrap();
+    assert!(result.is_err());
+    assert!(forced.is_ok());
+}
+
+#[test]
+#[serial]
+fn force_reuses_existing_guids() {
+    const UPGRADE_CODE: &str = "/wix:Wix/wix:Product/@UpgradeCode";
+    const PATH_GUID: &str = "//wix:Component[@Id='Path']/@Guid";
+    let original_working_directory = env::current_dir().unwrap();
+    let package = common::create_test_package();
+    env::set_current_dir(package.path()).unwrap();
+    let initialized = Execution::default().run();
+    let main_wxs = package.child(MAIN_WXS_PATH.as_path());
+    let expected_upgrade_code = common::evaluate_xpath(main_wxs.path(), UPGRADE_CODE);
+    let expected_path_guid = common::evaluate_xpath(main_wxs.path(), PATH_GUID);
+    let forced = Builder::default().force(true).build().run();
+    env::set_current_dir(original_working_directory).unwrap();
+    assert!(initialized.is_ok());
+    assert!(forced.is_ok());
+    assert_eq!(
+        common::evaluate_xpath(main_wxs.path(), UPGRADE_CODE),
+        expected_upgrade_code
+    );
+    assert_eq!(
+        common::evaluate_xpath(main_wxs.path(), PATH_GUID),
+        expected_path_guid
+    );
+}
+
+#[test]
+#[serial]
+fn force_with_regenerate_guids_works() {
+    const UPGRADE_CODE: &str = "/wix:Wix/wix:Product/@UpgradeCode";
+    const PATH_GUID: &str = "//wix:Component[@Id='Path']/@Guid";
+    let original_working_directory = env::current_dir().unwrap();
+    let package = common::create_test_package();
+    env::set_current_dir(package.path()).unwrap();
+    let initialized = Execution::default().run();
+    let main_wxs = package.child(MAIN_WXS_PATH.as_path());
+    let previous_upgrade_code = common::evaluate_xpath(main_wxs.path(), UPGRADE_CODE);
+    let previous_path_guid = common::evaluate_xpath(main_wxs.path(), PATH_GUID);
+    let forced = Builder::default()
+        .force(true)
+        .regenerate_guids(true)
+        .build()
+        .run();
+    env::set_current_dir(original_working_directory).unwrap();
+    assert!(initialized.is_ok());
+    assert!(forced.is_ok());
+    assert_ne!(
+        common::evaluate_xpath(main_wxs.path(), UPGRADE_CODE),
+        previous_upgrade_code
+    );
+    assert_ne!(
+        common::evaluate_xpath(main_wxs.path(), PATH_GUID),
+        previous_path_guid
+    );
+}
+
+#[test]
+#[serial]
+fn force_with_upgrade_guid_works() {
+    const EXPECTED: &str = "B36177BE-EA4D-44FB-B05C-EDDABDAA95CA";
+    let original_working_directory = env::current_dir().unwrap();
+    let package = common::create_test_package();
+    env::set_current_dir(package.path()).unwrap();
+    let initialized = Execution::default().run();
+    let forced = Builder::default()
+        .force(true)
+        .upgrade_guid(Some(EXPECTED))
+        .build()
+        .run();
+    env::set_current_dir(original_working_directory).unwrap();
+    assert!(initialized.is_ok());
+    assert!(forced.is_ok());
+    assert_eq!(
+        common::evaluate_xpath(
+            package.child(MAIN_WXS_PATH.as_path()).path(),
+            "/wix:Wix/wix:Product/@UpgradeCode"
+        ),
+        EXPECTED
+    );
+}
+
 #[test]
 #[serial]
 fn product_icon_works() {